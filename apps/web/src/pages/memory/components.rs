@@ -69,7 +69,12 @@ pub fn get_entity_color(kind: &str) -> &'static str {
         "error" | "issue" => "var(--color-error)",
         "config" | "setting" => "#ec4899", // Pink
         "concept" | "thought" => "#6366f1", // Indigo
-        "persona" | "user" => "#f59e0b", // Amber
+        "persona" | "user" | "person" => "#f59e0b", // Amber
+        "project" => "#14b8a6", // Teal
+        "organization" => "#0ea5e9", // Sky
+        "place" => "#84cc16", // Lime
+        "event" => "#f97316", // Orange
+        "url" => "#64748b", // Slate
         _ => "var(--color-primary-600)",
     }
 }
@@ -1,4 +1,4 @@
-use cratos_core::Orchestrator;
+use cratos_core::{Orchestrator, SessionContext};
 use serenity::all::CreateEmbed;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -67,6 +67,22 @@ impl DiscordCommands {
         }
     }
 
+    /// Summarize older turns of the caller's conversation.
+    pub async fn handle_compact(&self, channel_id: &str, user_id: &str) -> String {
+        let session_key = SessionContext::make_key("discord", channel_id, user_id);
+        match self.orchestrator.compact_session(&session_key).await {
+            Ok(Some(report)) => format!(
+                "Compacted {} message(s): {} → {} tokens.",
+                report.messages_compacted, report.tokens_before, report.tokens_after
+            ),
+            Ok(None) => "Nothing to compact yet.".to_string(),
+            Err(e) => format!(
+                "Compaction failed: {}",
+                crate::util::sanitize_error_for_user(&e.to_string())
+            ),
+        }
+    }
+
     /// Approve a pending approval request by ID.
     pub async fn handle_approve(&self, id: &str) -> String {
         if id.is_empty() {
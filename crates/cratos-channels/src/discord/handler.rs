@@ -6,8 +6,8 @@ use cratos_core::{Orchestrator, OrchestratorInput};
 use serenity::all::{
     Command, CommandInteraction, CommandOptionType, ComponentInteraction, Context,
     CreateAttachment, CreateCommand, CreateCommandOption, CreateInteractionResponse,
-    CreateInteractionResponseMessage, CreateMessage, EventHandler, Interaction, Message,
-    MessageReference, Ready,
+    CreateInteractionResponseMessage, CreateMessage, EditInteractionResponse, EventHandler,
+    Interaction, Message, MessageReference, Ready,
 };
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -54,6 +54,7 @@ impl EventHandler for DiscordHandler {
             CreateCommand::new("status").description("Show system status"),
             CreateCommand::new("sessions").description("List active AI sessions"),
             CreateCommand::new("tools").description("List available tools"),
+            CreateCommand::new("compact").description("Summarize older conversation turns"),
             CreateCommand::new("cancel")
                 .description("Cancel an execution")
                 .add_option(
@@ -84,6 +85,24 @@ impl EventHandler for DiscordHandler {
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         match interaction {
+            Interaction::Command(command) if command.data.name == "compact" => {
+                // Summarization can exceed Discord's 3s response window: defer first
+                if let Err(e) = command.defer(&ctx.http).await {
+                    error!(error = %e, "Failed to defer slash command");
+                    return;
+                }
+                let content = self
+                    .commands
+                    .handle_compact(
+                        &command.channel_id.get().to_string(),
+                        &command.user.id.get().to_string(),
+                    )
+                    .await;
+                let builder = EditInteractionResponse::new().content(content);
+                if let Err(e) = command.edit_response(&ctx.http, builder).await {
+                    error!(error = %e, "Failed to respond to slash command");
+                }
+            }
             Interaction::Command(command) => {
                 let response = match command.data.name.as_str() {
                     "status" => {
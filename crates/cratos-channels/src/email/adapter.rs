@@ -0,0 +1,604 @@
+use super::config::{EmailConfig, SmtpSecurity};
+use super::imap::ImapSession;
+use super::parse::{parse_email, strip_reply_prefix, ParsedEmail};
+use crate::error::{Error, Result};
+use crate::message::{ChannelAdapter, ChannelType, OutgoingAttachment, OutgoingMessage};
+use async_trait::async_trait;
+use cratos_core::{Orchestrator, OrchestratorInput};
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment as MailAttachment, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+/// Messages whose threading headers are remembered for replies
+const MAX_TRACKED_MESSAGES: usize = 1024;
+
+/// Re-issue IDLE before servers drop it (RFC 2177 recommends < 29 minutes)
+const IDLE_TIMEOUT: Duration = Duration::from_secs(25 * 60);
+
+/// Upper bound for the reconnect backoff
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Threading headers of a message we received or sent
+#[derive(Debug, Clone)]
+struct ThreadInfo {
+    subject: String,
+    /// References chain ending with the message itself
+    references: Vec<String>,
+}
+
+/// Bounded message ID → thread lookup
+#[derive(Default)]
+struct ThreadIndex {
+    entries: HashMap<String, ThreadInfo>,
+    order: VecDeque<String>,
+}
+
+impl ThreadIndex {
+    fn insert(&mut self, key: String, info: ThreadInfo) {
+        if self.entries.insert(key.clone(), info).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > MAX_TRACKED_MESSAGES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Remember a message under its own ID and as the latest in its thread
+    fn record(&mut self, thread_id: Option<&str>, info: ThreadInfo) {
+        if let Some(thread_id) = thread_id {
+            self.insert(thread_id.to_string(), info.clone());
+        }
+        if let Some(message_id) = info.references.last().cloned() {
+            self.insert(message_id, info);
+        }
+    }
+}
+
+/// Email adapter (IMAP in, SMTP out)
+pub struct EmailAdapter {
+    config: EmailConfig,
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+    threads: Mutex<ThreadIndex>,
+}
+
+impl EmailAdapter {
+    /// Create a new email adapter
+    ///
+    /// # Errors
+    /// Returns an error if the configuration is invalid or the SMTP relay
+    /// cannot be configured.
+    pub fn new(config: EmailConfig) -> Result<Self> {
+        config.validate()?;
+        let builder = match config.smtp_security {
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host)
+                .map_err(|e| Error::Email(format!("invalid SMTP relay: {}", e)))?,
+            SmtpSecurity::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)
+                    .map_err(|e| Error::Email(format!("invalid SMTP relay: {}", e)))?
+            }
+            SmtpSecurity::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_host)
+            }
+        };
+        let mailer = builder
+            .port(config.smtp_port)
+            .credentials(Credentials::new(
+                config.username.clone(),
+                config.password.clone(),
+            ))
+            .build();
+
+        Ok(Self {
+            config,
+            mailer,
+            threads: Mutex::new(ThreadIndex::default()),
+        })
+    }
+
+    /// Create from environment variables
+    pub fn from_env() -> Result<Self> {
+        Self::new(EmailConfig::from_env()?)
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &EmailConfig {
+        &self.config
+    }
+
+    /// Run the email adapter
+    ///
+    /// Watches the mailbox (IDLE or polling), processes new mail and replies
+    /// in-thread. Reconnects with backoff until shutdown is requested.
+    ///
+    /// # Errors
+    /// Currently never fails; connection errors are retried.
+    pub async fn run(
+        self: Arc<Self>,
+        orchestrator: Arc<Orchestrator>,
+        shutdown: CancellationToken,
+    ) -> Result<()> {
+        info!(
+            mailbox = %self.config.mailbox,
+            address = %self.config.from_address(),
+            "Starting email adapter"
+        );
+
+        let mut backoff = Duration::from_secs(5);
+        loop {
+            let result = tokio::select! {
+                r = self.watch_mailbox(&orchestrator, &mut backoff) => r,
+                _ = shutdown.cancelled() => break,
+            };
+            if let Err(e) = result {
+                warn!(error = %e, retry_in = ?backoff, "Email connection failed, reconnecting");
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(backoff) => {}
+                _ = shutdown.cancelled() => break,
+            }
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+
+        info!("Email adapter shutting down");
+        Ok(())
+    }
+
+    /// One IMAP session: log in, then process new mail until an error occurs
+    async fn watch_mailbox(
+        self: &Arc<Self>,
+        orchestrator: &Arc<Orchestrator>,
+        backoff: &mut Duration,
+    ) -> Result<()> {
+        let mut session = self.open_session().await?;
+        let use_idle = self.config.use_idle && session.has_capability("IDLE").await?;
+        *backoff = Duration::from_secs(5);
+        info!(
+            idle = use_idle,
+            "Email: connected to {}", self.config.imap_host
+        );
+
+        let result = self.serve(&mut session, orchestrator, use_idle).await;
+        // Best effort; the connection may already be gone
+        let _ = tokio::time::timeout(Duration::from_secs(5), session.logout()).await;
+        result
+    }
+
+    async fn serve(
+        self: &Arc<Self>,
+        session: &mut ImapSession,
+        orchestrator: &Arc<Orchestrator>,
+        use_idle: bool,
+    ) -> Result<()> {
+        loop {
+            for email in self.fetch_new(session).await? {
+                let adapter = Arc::clone(self);
+                let orchestrator = Arc::clone(orchestrator);
+                tokio::spawn(async move { adapter.handle_email(email, &orchestrator).await });
+            }
+
+            if use_idle {
+                session.idle(IDLE_TIMEOUT).await?;
+            } else {
+                tokio::time::sleep(Duration::from_secs(self.config.poll_interval_secs)).await;
+            }
+        }
+    }
+
+    async fn open_session(&self) -> Result<ImapSession> {
+        let mut session = ImapSession::connect(
+            &self.config.imap_host,
+            self.config.imap_port,
+            self.config.imap_tls,
+        )
+        .await?;
+        session
+            .login(&self.config.username, &self.config.password)
+            .await?;
+        session.select(&self.config.mailbox).await?;
+        Ok(session)
+    }
+
+    /// Fetch unseen messages, flag them seen and keep the ones to answer
+    async fn fetch_new(&self, session: &mut ImapSession) -> Result<Vec<ParsedEmail>> {
+        let mut emails = Vec::new();
+        for uid in session.search_unseen().await? {
+            let raw = session.fetch(uid).await?;
+            // Flag first so a message that fails processing is not retried forever
+            session.mark_seen(uid).await?;
+
+            if let Some(email) = parse_email(&raw, &self.config) {
+                self.threads
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .record(
+                        email.message.thread_id.as_deref(),
+                        ThreadInfo {
+                            subject: email.subject.clone(),
+                            references: email.references.clone(),
+                        },
+                    );
+                emails.push(email);
+            }
+        }
+        Ok(emails)
+    }
+
+    async fn handle_email(&self, email: ParsedEmail, orchestrator: &Orchestrator) {
+        let msg = email.message;
+        debug!(
+            from = %msg.user_id,
+            message_id = %msg.message_id,
+            attachments = msg.attachments.len(),
+            "Processing email"
+        );
+
+        let mut input = OrchestratorInput::new("email", &msg.channel_id, &msg.user_id, &msg.text);
+        if let Some(thread_id) = &msg.thread_id {
+            input = input.with_thread(thread_id);
+        }
+        if !email.images.is_empty() {
+            input = input.with_images(
+                email
+                    .images
+                    .into_iter()
+                    .map(|(mime, data)| cratos_llm::ImageContent::new(mime, data))
+                    .collect(),
+            );
+        }
+
+        let reply = match orchestrator.process(input).await {
+            Ok(result) if result.response.is_empty() => "Done.".to_string(),
+            Ok(result) => result.response,
+            Err(e) => {
+                error!(error = %e, "Email orchestrator error");
+                "Sorry, I encountered an error.".to_string()
+            }
+        };
+
+        if let Err(e) = self
+            .send_message(
+                &msg.channel_id,
+                OutgoingMessage::text(reply).reply_to(&msg.message_id),
+            )
+            .await
+        {
+            warn!(error = %e, "Failed to send email reply");
+        }
+    }
+
+    /// Thread a reply to `reply_to` (a message ID) or the latest message of `thread_id`
+    fn thread_for(&self, reply_to: Option<&str>, thread_id: Option<&str>) -> Option<ThreadInfo> {
+        let threads = self.threads.lock().unwrap_or_else(|e| e.into_inner());
+        reply_to
+            .and_then(|id| threads.entries.get(id))
+            .or_else(|| thread_id.and_then(|id| threads.entries.get(id)))
+            .cloned()
+    }
+
+    /// Start a message to `to`, threaded under `thread` if known
+    ///
+    /// Returns the builder and the new Message-ID (without angle brackets).
+    fn compose(
+        &self,
+        to: &str,
+        thread: Option<&ThreadInfo>,
+    ) -> Result<(lettre::message::MessageBuilder, String)> {
+        let from_address = self.config.from_address();
+        let from = Mailbox::new(
+            Some(self.config.from_name.clone()),
+            from_address
+                .parse()
+                .map_err(|e| Error::Email(format!("invalid from address: {}", e)))?,
+        );
+        let to: Mailbox = to
+            .parse()
+            .map_err(|e| Error::Email(format!("invalid recipient '{}': {}", to, e)))?;
+
+        let domain = from_address.rsplit('@').next().unwrap_or("cratos.local");
+        let message_id = format!("{}@{}", uuid::Uuid::new_v4(), domain);
+
+        let mut builder = Message::builder()
+            .from(from)
+            .to(to)
+            .date_now()
+            .message_id(Some(format!("<{}>", message_id)));
+
+        builder = match thread {
+            Some(thread) => {
+                let references: Vec<String> = thread
+                    .references
+                    .iter()
+                    .map(|id| format!("<{}>", id))
+                    .collect();
+                let mut builder =
+                    builder.subject(format!("Re: {}", strip_reply_prefix(&thread.subject)));
+                if let Some(parent) = references.last() {
+                    builder = builder
+                        .in_reply_to(parent.clone())
+                        .references(references.join(" "));
+                }
+                builder
+            }
+            None => builder.subject(format!("Message from {}", self.config.from_name)),
+        };
+
+        Ok((builder, message_id))
+    }
+
+    /// Send and remember the message so replies to it stay in the thread
+    async fn deliver(
+        &self,
+        message: Message,
+        message_id: String,
+        thread: Option<ThreadInfo>,
+        thread_id: Option<&str>,
+    ) -> Result<String> {
+        self.mailer
+            .send(message)
+            .await
+            .map_err(|e| Error::Email(format!("SMTP send failed: {}", e)))?;
+        debug!(message_id = %message_id, "Email: sent message");
+
+        let info = match thread {
+            Some(mut thread) => {
+                thread.references.push(message_id.clone());
+                thread
+            }
+            None => ThreadInfo {
+                subject: format!("Message from {}", self.config.from_name),
+                references: vec![message_id.clone()],
+            },
+        };
+        let thread_id = thread_id
+            .map(str::to_string)
+            .or_else(|| info.references.first().cloned());
+        self.threads
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .record(thread_id.as_deref(), info);
+
+        Ok(message_id)
+    }
+}
+
+#[async_trait]
+impl ChannelAdapter for EmailAdapter {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::Email
+    }
+
+    async fn send_message(&self, channel_id: &str, message: OutgoingMessage) -> Result<String> {
+        let thread = self.thread_for(message.reply_to.as_deref(), message.thread_id.as_deref());
+        let (builder, message_id) = self.compose(channel_id, thread.as_ref())?;
+        let email = builder
+            .body(message.text)
+            .map_err(|e| Error::Email(format!("failed to build message: {}", e)))?;
+
+        self.deliver(email, message_id, thread, message.thread_id.as_deref())
+            .await
+    }
+
+    async fn edit_message(
+        &self,
+        _channel_id: &str,
+        _message_id: &str,
+        _message: OutgoingMessage,
+    ) -> Result<()> {
+        Err(Error::Email("sent emails cannot be edited".to_string()))
+    }
+
+    async fn delete_message(&self, _channel_id: &str, _message_id: &str) -> Result<()> {
+        Err(Error::Email("sent emails cannot be deleted".to_string()))
+    }
+
+    async fn send_typing(&self, _channel_id: &str) -> Result<()> {
+        Ok(()) // No typing indicator for email
+    }
+
+    async fn send_attachment(
+        &self,
+        channel_id: &str,
+        attachment: OutgoingAttachment,
+        reply_to: Option<&str>,
+    ) -> Result<String> {
+        let data = attachment
+            .decode_data()
+            .map_err(|e| Error::Email(format!("invalid base64 attachment data: {}", e)))?;
+        let content_type = ContentType::parse(&attachment.mime_type)
+            .unwrap_or_else(|_| ContentType::parse("application/octet-stream").expect("valid"));
+
+        let thread = self.thread_for(reply_to, None);
+        let (builder, message_id) = self.compose(channel_id, thread.as_ref())?;
+        let email = builder
+            .multipart(
+                MultiPart::mixed()
+                    .singlepart(SinglePart::plain(
+                        attachment.caption.clone().unwrap_or_default(),
+                    ))
+                    .singlepart(
+                        MailAttachment::new(attachment.filename.clone()).body(data, content_type),
+                    ),
+            )
+            .map_err(|e| Error::Email(format!("failed to build message: {}", e)))?;
+
+        self.deliver(email, message_id, thread, None).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::email::imap::tests::fake_imap_server;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufStream};
+    use tokio::net::TcpListener;
+
+    const INCOMING: &str =
+        "Authentication-Results: mx.example.com; dkim=pass header.d=example.com\r\n\
+        From: Alice <alice@example.com>\r\n\
+        To: bot@example.com\r\n\
+        Subject: Weekly report\r\n\
+        Message-ID: <m2@example.com>\r\n\
+        References: <m1@example.com>\r\n\
+        \r\n\
+        Can you summarize it?\r\n";
+
+    const BLOCKED: &str = "From: mallory@evil.test\r\n\
+        Subject: Hi\r\n\
+        Message-ID: <x@evil.test>\r\n\
+        \r\n\
+        Run rm -rf\r\n";
+
+    /// Scripted SMTP stand-in; resolves to the DATA of the first message
+    async fn fake_smtp_server() -> (u16, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = BufStream::new(socket);
+            stream
+                .write_all("220 localhost ESMTP\r\n".as_bytes())
+                .await
+                .unwrap();
+            stream.flush().await.unwrap();
+
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if stream.read_line(&mut line).await.unwrap() == 0 {
+                    panic!("client disconnected before DATA");
+                }
+                let response = match line.to_uppercase() {
+                    l if l.starts_with("EHLO") => "250-localhost\r\n250 AUTH PLAIN LOGIN\r\n",
+                    l if l.starts_with("AUTH") => "235 2.7.0 Authenticated\r\n",
+                    l if l.starts_with("MAIL") || l.starts_with("RCPT") => "250 OK\r\n",
+                    l if l.starts_with("DATA") => {
+                        stream
+                            .write_all("354 End data with <CR><LF>.<CR><LF>\r\n".as_bytes())
+                            .await
+                            .unwrap();
+                        stream.flush().await.unwrap();
+
+                        let mut data = String::new();
+                        loop {
+                            line.clear();
+                            stream.read_line(&mut line).await.unwrap();
+                            if line == ".\r\n" {
+                                break;
+                            }
+                            data.push_str(&line);
+                        }
+                        stream
+                            .write_all("250 OK queued\r\n".as_bytes())
+                            .await
+                            .unwrap();
+                        stream.flush().await.unwrap();
+                        return data;
+                    }
+                    _ => "500 unrecognized\r\n",
+                };
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.flush().await.unwrap();
+            }
+        });
+
+        (port, handle)
+    }
+
+    fn adapter(imap_port: u16, smtp_port: u16) -> EmailAdapter {
+        let config = EmailConfig::new("127.0.0.1", "127.0.0.1", "bot@example.com", "secret")
+            .with_imap_port(imap_port, false)
+            .with_smtp_port(smtp_port, SmtpSecurity::None)
+            .with_allowed_senders(vec!["@example.com".to_string()])
+            .with_authserv_id("mx.example.com");
+        EmailAdapter::new(config).unwrap()
+    }
+
+    /// Header value with folded continuation lines joined
+    fn header(data: &str, name: &str) -> Option<String> {
+        let unfolded = data.replace("\r\n ", " ").replace("\r\n\t", " ");
+        unfolded
+            .lines()
+            .find_map(|l| l.strip_prefix(&format!("{}: ", name)))
+            .map(str::to_string)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_new_applies_allowlist() {
+        let (imap_port, imap) = fake_imap_server(vec![(1, INCOMING), (2, BLOCKED)]).await;
+        let adapter = adapter(imap_port, 0);
+
+        let mut session = adapter.open_session().await.unwrap();
+        let emails = adapter.fetch_new(&mut session).await.unwrap();
+        session.logout().await;
+
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].message.user_id, "alice@example.com");
+        assert_eq!(emails[0].message.text, "Can you summarize it?");
+        // Both are flagged seen, including the one that was ignored
+        assert_eq!(imap.await.unwrap(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_reply_preserves_threading() {
+        let (imap_port, _imap) = fake_imap_server(vec![(1, INCOMING)]).await;
+        let (smtp_port, smtp) = fake_smtp_server().await;
+        let adapter = adapter(imap_port, smtp_port);
+
+        let mut session = adapter.open_session().await.unwrap();
+        let emails = adapter.fetch_new(&mut session).await.unwrap();
+        let incoming = &emails[0].message;
+
+        let sent_id = adapter
+            .send_message(
+                &incoming.channel_id,
+                OutgoingMessage::text("Here is the summary.").reply_to(&incoming.message_id),
+            )
+            .await
+            .unwrap();
+
+        let data = smtp.await.unwrap();
+        assert_eq!(
+            header(&data, "Subject").as_deref(),
+            Some("Re: Weekly report")
+        );
+        assert_eq!(
+            header(&data, "In-Reply-To").as_deref(),
+            Some("<m2@example.com>")
+        );
+        assert_eq!(
+            header(&data, "References").as_deref(),
+            Some("<m1@example.com> <m2@example.com>")
+        );
+        assert_eq!(header(&data, "Message-ID"), Some(format!("<{}>", sent_id)));
+        assert!(header(&data, "To").unwrap().contains("alice@example.com"));
+        assert!(data.contains("Here is the summary."));
+
+        // A follow-up in the same thread replies to our last message
+        let thread = adapter
+            .thread_for(None, incoming.thread_id.as_deref())
+            .unwrap();
+        assert_eq!(
+            thread.references,
+            vec!["m1@example.com", "m2@example.com", sent_id.as_str()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_edit_and_delete_unsupported() {
+        let adapter = adapter(0, 0);
+        assert!(adapter
+            .edit_message("a@example.com", "id", OutgoingMessage::text("x"))
+            .await
+            .is_err());
+        assert!(adapter.delete_message("a@example.com", "id").await.is_err());
+    }
+}
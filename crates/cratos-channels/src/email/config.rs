@@ -0,0 +1,275 @@
+use crate::error::{Error, Result};
+use serde::Deserialize;
+
+/// How the SMTP connection is secured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Implicit TLS (usually port 465)
+    Tls,
+    /// Plain connection upgraded with STARTTLS (usually port 587)
+    StartTls,
+    /// No encryption (local relays and test servers only)
+    None,
+}
+
+impl SmtpSecurity {
+    fn parse(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "tls" | "ssl" => Ok(Self::Tls),
+            "starttls" => Ok(Self::StartTls),
+            "none" | "plain" => Ok(Self::None),
+            other => Err(Error::Config(format!(
+                "unknown EMAIL_SMTP_SECURITY '{}' (expected tls, starttls or none)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Email adapter configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailConfig {
+    /// IMAP server host
+    pub imap_host: String,
+    /// IMAP server port (993 for TLS)
+    #[serde(default = "default_imap_port")]
+    pub imap_port: u16,
+    /// Connect to IMAP over TLS (disable only for local servers)
+    #[serde(default = "default_true")]
+    pub imap_tls: bool,
+    /// Mailbox to watch
+    #[serde(default = "default_mailbox")]
+    pub mailbox: String,
+    /// SMTP server host
+    pub smtp_host: String,
+    /// SMTP server port
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    /// SMTP connection security
+    #[serde(default = "default_smtp_security")]
+    pub smtp_security: SmtpSecurity,
+    /// Login for IMAP and SMTP
+    pub username: String,
+    /// Password (or app password) for IMAP and SMTP
+    pub password: String,
+    /// Address replies are sent from (defaults to `username`)
+    #[serde(default)]
+    pub from_address: Option<String>,
+    /// Display name for outgoing mail
+    #[serde(default = "default_from_name")]
+    pub from_name: String,
+    /// Allowed senders: full addresses or `@domain` (empty = no one)
+    ///
+    /// Mail is only accepted when the receiving server also recorded a DKIM
+    /// or SPF pass for the sender's domain in `Authentication-Results`.
+    /// Requires [`Self::authserv_id`].
+    #[serde(default)]
+    pub allowed_senders: Vec<String>,
+    /// authserv-id of the receiving server whose `Authentication-Results`
+    /// are trusted
+    ///
+    /// Required when `allowed_senders` is set: any other header may have been
+    /// written by the sender. The server must strip incoming headers that
+    /// claim its authserv-id (RFC 8601 section 5).
+    #[serde(default)]
+    pub authserv_id: Option<String>,
+    /// Seconds between mailbox polls when IMAP IDLE is unavailable
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval_secs: u64,
+    /// Use IMAP IDLE for push delivery when the server supports it
+    #[serde(default = "default_true")]
+    pub use_idle: bool,
+}
+
+fn default_imap_port() -> u16 {
+    993
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_security() -> SmtpSecurity {
+    SmtpSecurity::StartTls
+}
+
+fn default_mailbox() -> String {
+    "INBOX".to_string()
+}
+
+fn default_from_name() -> String {
+    "Cratos".to_string()
+}
+
+fn default_poll_interval() -> u64 {
+    60
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl EmailConfig {
+    /// Create with server hosts and credentials, using default ports
+    #[must_use]
+    pub fn new(
+        imap_host: impl Into<String>,
+        smtp_host: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        Self {
+            imap_host: imap_host.into(),
+            imap_port: default_imap_port(),
+            imap_tls: true,
+            mailbox: default_mailbox(),
+            smtp_host: smtp_host.into(),
+            smtp_port: default_smtp_port(),
+            smtp_security: default_smtp_security(),
+            username: username.into(),
+            password: password.into(),
+            from_address: None,
+            from_name: default_from_name(),
+            allowed_senders: Vec::new(),
+            authserv_id: None,
+            poll_interval_secs: default_poll_interval(),
+            use_idle: true,
+        }
+    }
+
+    /// Create from EMAIL_* environment variables
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| {
+            std::env::var(name).map_err(|_| Error::Config(format!("{} not set", name)))
+        };
+        let port = |name: &str, default: u16| -> Result<u16> {
+            match std::env::var(name) {
+                Ok(s) => s
+                    .trim()
+                    .parse()
+                    .map_err(|_| Error::Config(format!("{} is not a valid port", name))),
+                Err(_) => Ok(default),
+            }
+        };
+
+        let mut config = Self::new(
+            var("EMAIL_IMAP_HOST")?,
+            var("EMAIL_SMTP_HOST")?,
+            var("EMAIL_USERNAME")?,
+            var("EMAIL_PASSWORD")?,
+        );
+        config.imap_port = port("EMAIL_IMAP_PORT", default_imap_port())?;
+        config.smtp_port = port("EMAIL_SMTP_PORT", default_smtp_port())?;
+        config.imap_tls = std::env::var("EMAIL_IMAP_TLS")
+            .map(|s| s != "false" && s != "0")
+            .unwrap_or(true);
+        if let Ok(security) = std::env::var("EMAIL_SMTP_SECURITY") {
+            config.smtp_security = SmtpSecurity::parse(&security)?;
+        }
+        if let Ok(mailbox) = std::env::var("EMAIL_MAILBOX") {
+            config.mailbox = mailbox;
+        }
+        config.from_address = std::env::var("EMAIL_FROM_ADDRESS").ok();
+        if let Ok(name) = std::env::var("EMAIL_FROM_NAME") {
+            config.from_name = name;
+        }
+        config.allowed_senders = std::env::var("EMAIL_ALLOWED_SENDERS")
+            .ok()
+            .map(|s| {
+                s.split(',')
+                    .map(|a| a.trim().to_string())
+                    .filter(|a| !a.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        config.authserv_id = std::env::var("EMAIL_AUTHSERV_ID").ok();
+        if let Some(secs) = std::env::var("EMAIL_POLL_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+        {
+            config.poll_interval_secs = secs;
+        }
+        config.use_idle = std::env::var("EMAIL_USE_IDLE")
+            .map(|s| s != "false" && s != "0")
+            .unwrap_or(true);
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that accepted mail can be authenticated
+    ///
+    /// # Errors
+    /// Returns an error if `allowed_senders` is set without `authserv_id`.
+    pub fn validate(&self) -> Result<()> {
+        let pinned = self
+            .authserv_id
+            .as_deref()
+            .is_some_and(|id| !id.trim().is_empty());
+        if !self.allowed_senders.is_empty() && !pinned {
+            return Err(Error::Config(
+                "EMAIL_AUTHSERV_ID is required when EMAIL_ALLOWED_SENDERS is set".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Set allowed senders
+    #[must_use]
+    pub fn with_allowed_senders(mut self, senders: Vec<String>) -> Self {
+        self.allowed_senders = senders;
+        self
+    }
+
+    /// Trust only `Authentication-Results` headers from this authserv-id
+    #[must_use]
+    pub fn with_authserv_id(mut self, authserv_id: impl Into<String>) -> Self {
+        self.authserv_id = Some(authserv_id.into());
+        self
+    }
+
+    /// Set the IMAP port and whether it uses TLS
+    #[must_use]
+    pub fn with_imap_port(mut self, port: u16, tls: bool) -> Self {
+        self.imap_port = port;
+        self.imap_tls = tls;
+        self
+    }
+
+    /// Set the SMTP port and connection security
+    #[must_use]
+    pub fn with_smtp_port(mut self, port: u16, security: SmtpSecurity) -> Self {
+        self.smtp_port = port;
+        self.smtp_security = security;
+        self
+    }
+
+    /// Set the address replies are sent from
+    #[must_use]
+    pub fn with_from_address(mut self, address: impl Into<String>) -> Self {
+        self.from_address = Some(address.into());
+        self
+    }
+
+    /// Address replies are sent from
+    pub fn from_address(&self) -> &str {
+        self.from_address.as_deref().unwrap_or(&self.username)
+    }
+
+    /// Check if a sender address is allowed (case-insensitive)
+    ///
+    /// This only checks the allowlist; the `From:` address still has to be
+    /// authenticated (see [`Self::allowed_senders`]).
+    pub fn is_sender_allowed(&self, address: &str) -> bool {
+        let address = address.to_lowercase();
+        self.allowed_senders.iter().any(|allowed| {
+            let allowed = allowed.to_lowercase();
+            if allowed.starts_with('@') {
+                address.ends_with(&allowed)
+            } else {
+                address == allowed
+            }
+        })
+    }
+}
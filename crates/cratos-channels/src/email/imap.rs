@@ -0,0 +1,435 @@
+//! Minimal IMAP4rev1 client
+//!
+//! Implements just what the email adapter needs: LOGIN, SELECT, searching for
+//! unseen messages, fetching and flagging them, and IDLE (RFC 2177).
+
+use crate::error::{Error, Result};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufStream};
+use tokio::net::TcpStream;
+use tracing::debug;
+
+/// Largest literal (e.g. a fetched message) the client accepts
+const MAX_LITERAL_BYTES: usize = 25 * 1024 * 1024;
+
+/// Longest response line the client accepts (long SEARCH results fit)
+const MAX_LINE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Byte stream an IMAP session runs over (plain TCP or TLS)
+pub(crate) trait ImapStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> ImapStream for T {}
+
+/// One untagged server response, with any literals it carried
+#[derive(Debug, Default)]
+pub(crate) struct Untagged {
+    /// Response text with literals elided
+    pub text: String,
+    /// Literal payloads in order of appearance
+    pub literals: Vec<Vec<u8>>,
+}
+
+/// Authenticated-or-not IMAP session
+pub(crate) struct ImapSession {
+    stream: BufStream<Box<dyn ImapStream>>,
+    next_tag: u32,
+    /// Bytes of a line not yet terminated, kept if a read is cancelled
+    partial_line: Vec<u8>,
+}
+
+impl ImapSession {
+    /// Connect and read the server greeting
+    pub async fn connect(host: &str, port: u16, tls: bool) -> Result<Self> {
+        let tcp = TcpStream::connect((host, port)).await.map_err(|e| {
+            Error::Email(format!("IMAP connect to {}:{} failed: {}", host, port, e))
+        })?;
+
+        let stream: Box<dyn ImapStream> = if tls {
+            Box::new(tls_connect(host, tcp).await?)
+        } else {
+            Box::new(tcp)
+        };
+        Self::from_stream(stream).await
+    }
+
+    /// Start a session over an established stream
+    pub async fn from_stream(stream: Box<dyn ImapStream>) -> Result<Self> {
+        let mut session = Self {
+            stream: BufStream::new(stream),
+            next_tag: 1,
+            partial_line: Vec::new(),
+        };
+        let greeting = session.read_line().await?;
+        if !greeting.starts_with("* OK") && !greeting.starts_with("* PREAUTH") {
+            return Err(Error::Email(format!(
+                "unexpected IMAP greeting: {}",
+                greeting.trim_end()
+            )));
+        }
+        Ok(session)
+    }
+
+    /// LOGIN with username and password
+    pub async fn login(&mut self, username: &str, password: &str) -> Result<()> {
+        self.command(&format!("LOGIN {} {}", quote(username), quote(password)))
+            .await
+            .map(|_| ())
+    }
+
+    /// Whether the server advertises a capability
+    pub async fn has_capability(&mut self, capability: &str) -> Result<bool> {
+        let responses = self.command("CAPABILITY").await?;
+        Ok(responses.iter().any(|r| {
+            r.text
+                .strip_prefix("CAPABILITY ")
+                .is_some_and(|caps| caps.split(' ').any(|c| c.eq_ignore_ascii_case(capability)))
+        }))
+    }
+
+    /// SELECT a mailbox
+    pub async fn select(&mut self, mailbox: &str) -> Result<()> {
+        self.command(&format!("SELECT {}", quote(mailbox)))
+            .await
+            .map(|_| ())
+    }
+
+    /// UIDs of messages without the \Seen flag
+    pub async fn search_unseen(&mut self) -> Result<Vec<u32>> {
+        let responses = self.command("UID SEARCH UNSEEN").await?;
+        Ok(responses
+            .iter()
+            .filter_map(|r| r.text.strip_prefix("SEARCH"))
+            .flat_map(|ids| ids.split_whitespace().filter_map(|id| id.parse().ok()))
+            .collect())
+    }
+
+    /// Fetch the full RFC 822 message without setting \Seen
+    pub async fn fetch(&mut self, uid: u32) -> Result<Vec<u8>> {
+        let responses = self
+            .command(&format!("UID FETCH {} (BODY.PEEK[])", uid))
+            .await?;
+        responses
+            .into_iter()
+            .find(|r| r.text.contains("FETCH") && !r.literals.is_empty())
+            .and_then(|mut r| r.literals.pop())
+            .ok_or_else(|| Error::Email(format!("message UID {} not found", uid)))
+    }
+
+    /// Set the \Seen flag
+    pub async fn mark_seen(&mut self, uid: u32) -> Result<()> {
+        self.command(&format!("UID STORE {} +FLAGS.SILENT (\\Seen)", uid))
+            .await
+            .map(|_| ())
+    }
+
+    /// Wait until the mailbox changes or `timeout` elapses
+    ///
+    /// Returns `true` if new messages arrived.
+    pub async fn idle(&mut self, timeout: Duration) -> Result<bool> {
+        let tag = self.send("IDLE").await?;
+        let line = self.read_line().await?;
+        if !line.starts_with('+') {
+            return Err(Error::Email(format!("IDLE rejected: {}", line.trim_end())));
+        }
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut new_mail = false;
+        while !new_mail {
+            match tokio::time::timeout_at(deadline, self.read_line()).await {
+                Ok(line) => new_mail = line?.contains("EXISTS"),
+                Err(_) => break,
+            }
+        }
+
+        self.write_raw(b"DONE\r\n").await?;
+        self.read_until_tagged(&tag).await?;
+        Ok(new_mail)
+    }
+
+    /// LOGOUT (errors are ignored; the connection is going away)
+    pub async fn logout(mut self) {
+        let _ = self.command("LOGOUT").await;
+    }
+
+    /// Send a command and collect its untagged responses
+    async fn command(&mut self, command: &str) -> Result<Vec<Untagged>> {
+        let tag = self.send(command).await?;
+        self.read_until_tagged(&tag).await
+    }
+
+    async fn send(&mut self, command: &str) -> Result<String> {
+        let tag = format!("A{}", self.next_tag);
+        self.next_tag += 1;
+        if command.starts_with("LOGIN") {
+            debug!(tag = %tag, "IMAP > LOGIN ***");
+        } else {
+            debug!(tag = %tag, command = %command, "IMAP >");
+        }
+        self.write_raw(format!("{} {}\r\n", tag, command).as_bytes())
+            .await?;
+        Ok(tag)
+    }
+
+    async fn write_raw(&mut self, bytes: &[u8]) -> Result<()> {
+        self.stream.write_all(bytes).await.map_err(io_error)?;
+        self.stream.flush().await.map_err(io_error)
+    }
+
+    async fn read_until_tagged(&mut self, tag: &str) -> Result<Vec<Untagged>> {
+        let mut responses = Vec::new();
+        loop {
+            let line = self.read_line().await?;
+            if let Some(status) = line.strip_prefix(tag).and_then(|s| s.strip_prefix(' ')) {
+                return if status.starts_with("OK") {
+                    Ok(responses)
+                } else {
+                    Err(Error::Email(format!("IMAP error: {}", status.trim_end())))
+                };
+            }
+            if let Some(rest) = line.strip_prefix("* ") {
+                let response = self.read_literals(rest.to_string()).await?;
+                responses.push(response);
+            }
+        }
+    }
+
+    /// Read the literals (`{n}\r\n<n bytes>`) continuing an untagged response
+    async fn read_literals(&mut self, mut line: String) -> Result<Untagged> {
+        let mut response = Untagged::default();
+        while let Some(len) = literal_len(&line) {
+            if len > MAX_LITERAL_BYTES {
+                return Err(Error::Email(format!(
+                    "IMAP literal of {} bytes exceeds the {} byte limit",
+                    len, MAX_LITERAL_BYTES
+                )));
+            }
+            let mut literal = vec![0u8; len];
+            self.stream
+                .read_exact(&mut literal)
+                .await
+                .map_err(io_error)?;
+            response.literals.push(literal);
+            if let Some(start) = line.rfind('{') {
+                response.text.push_str(line[..start].trim_end());
+            }
+            line = self.read_line().await?;
+        }
+        response.text.push_str(line.trim_end());
+        Ok(response)
+    }
+
+    /// Read one line, up to [`MAX_LINE_BYTES`]
+    ///
+    /// Cancel-safe: bytes read before a cancellation (e.g. the IDLE timeout)
+    /// are kept for the next call.
+    async fn read_line(&mut self) -> Result<String> {
+        loop {
+            let available = self.stream.fill_buf().await.map_err(io_error)?;
+            if available.is_empty() {
+                return Err(Error::Email("IMAP connection closed".to_string()));
+            }
+            let (chunk, complete) = match available.iter().position(|&b| b == b'\n') {
+                Some(end) => (&available[..=end], true),
+                None => (available, false),
+            };
+            if self.partial_line.len() + chunk.len() > MAX_LINE_BYTES {
+                return Err(Error::Email(format!(
+                    "IMAP response line exceeds the {} byte limit",
+                    MAX_LINE_BYTES
+                )));
+            }
+            self.partial_line.extend_from_slice(chunk);
+            let consumed = chunk.len();
+            self.stream.consume(consumed);
+            if complete {
+                let line = std::mem::take(&mut self.partial_line);
+                return Ok(String::from_utf8_lossy(&line).into_owned());
+            }
+        }
+    }
+}
+
+/// Length of the literal announced at the end of a line (`... {123}\r\n`)
+fn literal_len(line: &str) -> Option<usize> {
+    let line = line.trim_end();
+    let start = line.rfind('{')?;
+    line.strip_suffix('}')?[start + 1..].parse().ok()
+}
+
+/// Quote an IMAP string argument
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn io_error(e: std::io::Error) -> Error {
+    Error::Email(format!("IMAP I/O error: {}", e))
+}
+
+async fn tls_connect(
+    host: &str,
+    tcp: TcpStream,
+) -> Result<tokio_rustls::client::TlsStream<TcpStream>> {
+    let config = crate::util::tls_client_config()
+        .map_err(|e| Error::Email(format!("TLS setup failed: {}", e)))?;
+    let server_name = tokio_rustls::rustls::pki_types::ServerName::try_from(host.to_string())
+        .map_err(|e| Error::Email(format!("invalid IMAP host '{}': {}", host, e)))?;
+    tokio_rustls::TlsConnector::from(config)
+        .connect(server_name, tcp)
+        .await
+        .map_err(|e| Error::Email(format!("IMAP TLS handshake failed: {}", e)))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Scripted IMAP stand-in serving `messages` (UID → raw message).
+    ///
+    /// Returns the port and a handle resolving to the UIDs flagged \Seen.
+    pub(crate) async fn fake_imap_server(
+        messages: Vec<(u32, &'static str)>,
+    ) -> (u16, tokio::task::JoinHandle<Vec<u32>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = BufStream::new(socket);
+            let mut seen = Vec::new();
+            stream.write_all(b"* OK IMAP4rev1 ready\r\n").await.unwrap();
+            stream.flush().await.unwrap();
+
+            let mut line = String::new();
+            while {
+                line.clear();
+                stream.read_line(&mut line).await.unwrap() > 0
+            } {
+                let (tag, command) = line.trim_end().split_once(' ').unwrap();
+                let reply = match command {
+                    c if c.starts_with("LOGIN") => {
+                        if c == "LOGIN \"bot@example.com\" \"secret\"" {
+                            format!("{} OK LOGIN completed\r\n", tag)
+                        } else {
+                            format!("{} NO LOGIN failed\r\n", tag)
+                        }
+                    }
+                    "CAPABILITY" => format!("* CAPABILITY IMAP4rev1 IDLE\r\n{} OK\r\n", tag),
+                    c if c.starts_with("SELECT") => format!("* 2 EXISTS\r\n{} OK\r\n", tag),
+                    "UID SEARCH UNSEEN" => {
+                        let ids: Vec<String> = messages
+                            .iter()
+                            .filter(|(uid, _)| !seen.contains(uid))
+                            .map(|(uid, _)| uid.to_string())
+                            .collect();
+                        format!("* SEARCH {}\r\n{} OK\r\n", ids.join(" "), tag)
+                    }
+                    c if c.starts_with("UID FETCH") => {
+                        let uid: u32 = c.split(' ').nth(2).unwrap().parse().unwrap();
+                        let (_, raw) = messages.iter().find(|(u, _)| *u == uid).unwrap();
+                        format!(
+                            "* 1 FETCH (UID {} BODY[] {{{}}}\r\n{})\r\n{} OK\r\n",
+                            uid,
+                            raw.len(),
+                            raw,
+                            tag
+                        )
+                    }
+                    c if c.starts_with("UID STORE") => {
+                        seen.push(c.split(' ').nth(2).unwrap().parse().unwrap());
+                        format!("{} OK\r\n", tag)
+                    }
+                    "LOGOUT" => {
+                        let reply = format!("* BYE\r\n{} OK\r\n", tag);
+                        stream.write_all(reply.as_bytes()).await.unwrap();
+                        stream.flush().await.unwrap();
+                        break;
+                    }
+                    _ => format!("{} BAD unknown command\r\n", tag),
+                };
+                stream.write_all(reply.as_bytes()).await.unwrap();
+                stream.flush().await.unwrap();
+            }
+            seen
+        });
+
+        (port, handle)
+    }
+
+    const RAW: &str = "From: alice@example.com\r\nSubject: Hi\r\n\r\nHello {there}\r\n";
+
+    #[tokio::test]
+    async fn test_fetch_unseen_from_stand_in() {
+        let (port, server) = fake_imap_server(vec![(7, RAW), (9, RAW)]).await;
+
+        let mut session = ImapSession::connect("127.0.0.1", port, false)
+            .await
+            .unwrap();
+        session.login("bot@example.com", "secret").await.unwrap();
+        assert!(session.has_capability("IDLE").await.unwrap());
+        session.select("INBOX").await.unwrap();
+
+        assert_eq!(session.search_unseen().await.unwrap(), vec![7, 9]);
+        assert_eq!(session.fetch(7).await.unwrap(), RAW.as_bytes());
+        session.mark_seen(7).await.unwrap();
+        assert_eq!(session.search_unseen().await.unwrap(), vec![9]);
+        session.logout().await;
+
+        assert_eq!(server.await.unwrap(), vec![7]);
+    }
+
+    #[tokio::test]
+    async fn test_login_failure() {
+        let (port, _server) = fake_imap_server(Vec::new()).await;
+        let mut session = ImapSession::connect("127.0.0.1", port, false)
+            .await
+            .unwrap();
+        assert!(session.login("bot@example.com", "wrong").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_oversized_literal_rejected() {
+        let (client, _server) = tokio::io::duplex(64);
+        let mut session = ImapSession {
+            stream: BufStream::new(Box::new(client)),
+            next_tag: 1,
+            partial_line: Vec::new(),
+        };
+        let line = format!("1 FETCH (UID 7 BODY[] {{{}}}\r\n", MAX_LITERAL_BYTES + 1);
+        assert!(session.read_literals(line).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_line_limits_and_survives_timeout() {
+        let (client, mut server) = tokio::io::duplex(64 * 1024);
+        let mut session = ImapSession {
+            stream: BufStream::new(Box::new(client)),
+            next_tag: 1,
+            partial_line: Vec::new(),
+        };
+
+        // A line cut off by a timeout is completed by the next read
+        server.write_all(b"* 3 EX").await.unwrap();
+        let timeout = Duration::from_millis(50);
+        assert!(tokio::time::timeout(timeout, session.read_line())
+            .await
+            .is_err());
+        server.write_all(b"ISTS\r\n").await.unwrap();
+        assert_eq!(session.read_line().await.unwrap(), "* 3 EXISTS\r\n");
+
+        // An endless line is rejected instead of buffered
+        let writer = tokio::spawn(async move {
+            let chunk = vec![b'x'; 64 * 1024];
+            while server.write_all(&chunk).await.is_ok() {}
+        });
+        assert!(session.read_line().await.is_err());
+        writer.abort();
+    }
+
+    #[test]
+    fn test_literal_len_and_quote() {
+        assert_eq!(literal_len("* 1 FETCH (BODY[] {42}\r\n"), Some(42));
+        assert_eq!(literal_len("* SEARCH 1 2\r\n"), None);
+        assert_eq!(quote("pa\"ss\\"), "\"pa\\\"ss\\\\\"");
+    }
+}
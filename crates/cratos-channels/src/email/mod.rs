@@ -0,0 +1,98 @@
+//! Email - IMAP/SMTP adapter
+//!
+//! This module provides an email adapter: incoming mail is read from an IMAP
+//! mailbox (IMAP IDLE, or polling when the server lacks it) and replies are
+//! sent over SMTP with `In-Reply-To`/`References` so they stay in the
+//! sender's thread.
+//!
+//! Each sender address is a channel; the thread ID is the Message-ID of the
+//! first message in the conversation.
+//!
+//! ## Configuration
+//!
+//! ```toml
+//! [channels.email]
+//! enabled = true
+//! ```
+//!
+//! Credentials and servers come from `EMAIL_*` environment variables (see
+//! [`EmailConfig::from_env`]).
+
+/// Email adapter implementation.
+pub mod adapter;
+/// Email server credentials and configuration.
+pub mod config;
+mod imap;
+mod parse;
+
+pub use adapter::EmailAdapter;
+pub use config::{EmailConfig, SmtpSecurity};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sender_allowlist() {
+        let config = EmailConfig::new(
+            "imap.example.com",
+            "smtp.example.com",
+            "bot@example.com",
+            "x",
+        )
+        .with_allowed_senders(vec![
+            "Alice@Example.com".to_string(),
+            "@trusted.org".to_string(),
+        ]);
+
+        assert!(config.is_sender_allowed("alice@example.com"));
+        assert!(config.is_sender_allowed("bob@trusted.org"));
+        assert!(!config.is_sender_allowed("bob@example.com"));
+        assert!(!config.is_sender_allowed("bob@untrusted.org.evil"));
+
+        let empty = EmailConfig::new(
+            "imap.example.com",
+            "smtp.example.com",
+            "bot@example.com",
+            "x",
+        );
+        assert!(!empty.is_sender_allowed("anyone@anywhere.test"));
+    }
+
+    #[test]
+    fn test_allowlist_requires_authserv_id() {
+        let config = EmailConfig::new(
+            "imap.example.com",
+            "smtp.example.com",
+            "bot@example.com",
+            "x",
+        );
+        assert!(config.validate().is_ok());
+
+        let allowlisted = config.with_allowed_senders(vec!["@example.com".to_string()]);
+        assert!(allowlisted.validate().is_err());
+        assert!(allowlisted
+            .with_authserv_id("mx.example.com")
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config: EmailConfig = serde_json::from_value(serde_json::json!({
+            "imap_host": "imap.example.com",
+            "smtp_host": "smtp.example.com",
+            "username": "bot@example.com",
+            "password": "x",
+            "smtp_security": "tls",
+        }))
+        .unwrap();
+
+        assert_eq!(config.imap_port, 993);
+        assert!(config.imap_tls);
+        assert_eq!(config.mailbox, "INBOX");
+        assert_eq!(config.smtp_security, SmtpSecurity::Tls);
+        assert_eq!(config.from_address(), "bot@example.com");
+        assert!(config.use_idle);
+    }
+}
@@ -0,0 +1,410 @@
+//! RFC 5322 message normalization
+
+use super::config::EmailConfig;
+use crate::message::{Attachment, AttachmentType, ChannelType, NormalizedMessage};
+use chrono::{DateTime, Utc};
+use mail_parser::{HeaderValue, MessageParser, MimeHeaders};
+use tracing::{debug, warn};
+
+/// An incoming email, normalized
+#[derive(Debug)]
+pub(crate) struct ParsedEmail {
+    /// Normalized message (channel = sender address, thread = thread root)
+    pub message: NormalizedMessage,
+    /// Original subject
+    pub subject: String,
+    /// References chain of this message, oldest first, without angle brackets
+    pub references: Vec<String>,
+    /// Image attachments as (MIME type, bytes)
+    pub images: Vec<(String, Vec<u8>)>,
+}
+
+/// Parse a raw message, returning `None` for mail the adapter should ignore
+/// (unparseable, no sender, from ourselves, auto-generated, not allowed, or
+/// not authenticated).
+pub(crate) fn parse_email(raw: &[u8], config: &EmailConfig) -> Option<ParsedEmail> {
+    let parsed = MessageParser::default().parse(raw)?;
+
+    let from = parsed.from()?.first()?;
+    let sender = from.address()?.to_lowercase();
+    if sender.eq_ignore_ascii_case(config.from_address()) {
+        return None;
+    }
+    if is_auto_generated(&parsed) {
+        debug!(sender = %sender, "Email: ignoring auto-generated message");
+        return None;
+    }
+    if !config.is_sender_allowed(&sender) {
+        debug!(sender = %sender, "Email: ignoring message from non-allowed sender");
+        return None;
+    }
+    if !sender_authenticated(&parsed, &sender, config.authserv_id.as_deref()) {
+        warn!(
+            sender = %sender,
+            "Email: ignoring message without a DKIM or SPF pass for the sender"
+        );
+        return None;
+    }
+
+    let subject = parsed.subject().unwrap_or_default().to_string();
+    let message_id = parsed
+        .message_id()
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}@cratos.local", uuid::Uuid::new_v4()));
+
+    let mut references = header_ids(parsed.references());
+    if references.is_empty() {
+        references = header_ids(parsed.in_reply_to());
+    }
+    let thread_root = references
+        .first()
+        .cloned()
+        .unwrap_or_else(|| message_id.clone());
+    let is_reply = !references.is_empty();
+
+    let body = parsed
+        .body_text(0)
+        .map(|text| strip_quoted(&text))
+        .unwrap_or_default();
+    let text = if body.is_empty() {
+        strip_reply_prefix(&subject).to_string()
+    } else {
+        body
+    };
+
+    let mut message = NormalizedMessage::new(
+        ChannelType::Email,
+        sender.clone(),
+        sender.clone(),
+        message_id.clone(),
+        text,
+    )
+    .with_thread(thread_root);
+    if let Some(name) = from.name() {
+        message = message.with_user_name(name);
+    }
+    if is_reply {
+        message = message.as_reply();
+    }
+    if let Some(date) = parsed
+        .date()
+        .and_then(|d| DateTime::<Utc>::from_timestamp(d.to_timestamp(), 0))
+    {
+        message.timestamp = date;
+    }
+
+    let mut images = Vec::new();
+    for (index, part) in parsed.attachments().enumerate() {
+        let mime_type = part
+            .content_type()
+            .map(|ct| match ct.subtype() {
+                Some(sub) => format!("{}/{}", ct.ctype(), sub),
+                None => ct.ctype().to_string(),
+            })
+            .unwrap_or_else(|| "application/octet-stream".to_string())
+            .to_lowercase();
+        let attachment_type = attachment_type(&mime_type);
+        if attachment_type == AttachmentType::Image {
+            images.push((mime_type.clone(), part.contents().to_vec()));
+        }
+
+        message = message.with_attachment(Attachment {
+            attachment_type,
+            file_name: part.attachment_name().map(str::to_string),
+            mime_type: Some(mime_type),
+            file_size: Some(part.contents().len() as u64),
+            url: None,
+            file_id: Some(format!("{}#{}", message_id, index)),
+        });
+    }
+
+    message = message.with_raw_data(serde_json::json!({
+        "message_id": message_id,
+        "subject": subject,
+        "from": sender,
+    }));
+
+    if !references.contains(&message_id) {
+        references.push(message_id);
+    }
+
+    Some(ParsedEmail {
+        message,
+        subject,
+        references,
+        images,
+    })
+}
+
+/// Whether the receiving server recorded a DKIM or SPF pass for the
+/// sender's domain
+///
+/// Only headers from `authserv_id` are trusted: any other header may have
+/// been written by the sender. Without an authserv-id nothing is trusted.
+fn sender_authenticated(
+    message: &mail_parser::Message<'_>,
+    sender: &str,
+    authserv_id: Option<&str>,
+) -> bool {
+    let Some((_, domain)) = sender.rsplit_once('@') else {
+        return false;
+    };
+    let Some(authserv_id) = authserv_id.filter(|id| !id.trim().is_empty()) else {
+        return false;
+    };
+
+    message
+        .headers_raw()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("Authentication-Results"))
+        .map(|(_, value)| value)
+        .filter(|value| {
+            value
+                .split(';')
+                .next()
+                .and_then(|id_part| id_part.split_whitespace().next())
+                .is_some_and(|found| found.eq_ignore_ascii_case(authserv_id.trim()))
+        })
+        .any(|value| {
+            value
+                .split(';')
+                .skip(1)
+                .any(|result| passes_for_domain(result, domain))
+        })
+}
+
+/// Whether one `method=result property=value ...` entry is a DKIM or SPF
+/// pass for `domain` (or a parent domain of it)
+fn passes_for_domain(result: &str, domain: &str) -> bool {
+    let mut tokens = result.split_whitespace();
+    let passed = matches!(
+        tokens.next().map(str::to_lowercase).as_deref(),
+        Some("dkim=pass" | "spf=pass")
+    );
+    passed
+        && tokens.any(|token| {
+            let Some((property, value)) = token.split_once('=') else {
+                return false;
+            };
+            if !matches!(
+                property.to_lowercase().as_str(),
+                "header.d" | "header.i" | "smtp.mailfrom"
+            ) {
+                return false;
+            }
+            let authenticated = value
+                .rsplit('@')
+                .next()
+                .unwrap_or(value)
+                .trim_end_matches('.')
+                .to_lowercase();
+            !authenticated.is_empty()
+                && (domain == authenticated || domain.ends_with(&format!(".{authenticated}")))
+        })
+}
+
+/// Message IDs in a `References` / `In-Reply-To` header
+fn header_ids(value: &HeaderValue<'_>) -> Vec<String> {
+    match value {
+        HeaderValue::Text(id) => vec![id.to_string()],
+        HeaderValue::TextList(ids) => ids.iter().map(|id| id.to_string()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Bounces, vacation responders and mailing-list robots (RFC 3834)
+fn is_auto_generated(message: &mail_parser::Message<'_>) -> bool {
+    let header = |name: &str| message.header_raw(name).map(|v| v.trim().to_lowercase());
+    header("Auto-Submitted").is_some_and(|v| v != "no")
+        || header("Precedence").is_some_and(|v| matches!(v.as_str(), "bulk" | "junk" | "list"))
+}
+
+fn attachment_type(mime_type: &str) -> AttachmentType {
+    match mime_type.split('/').next().unwrap_or_default() {
+        "image" => AttachmentType::Image,
+        "audio" => AttachmentType::Audio,
+        "video" => AttachmentType::Video,
+        "application" | "text" => AttachmentType::Document,
+        _ => AttachmentType::Other,
+    }
+}
+
+/// Drop the quoted history below a reply
+fn strip_quoted(body: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("-----Original Message-----") || trimmed == "--" {
+            break;
+        }
+        if trimmed.starts_with('>') {
+            continue;
+        }
+        lines.push(line);
+    }
+
+    // "On <date>, <someone> wrote:" introduces the quote
+    if let Some(pos) = lines
+        .iter()
+        .rposition(|l| l.trim_start().starts_with("On ") && l.trim_end().ends_with("wrote:"))
+    {
+        lines.truncate(pos);
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+/// Remove any number of `Re:` / `Fwd:` prefixes from a subject
+pub(crate) fn strip_reply_prefix(subject: &str) -> &str {
+    let mut subject = subject.trim();
+    while let Some(prefix) = ["re:", "fwd:", "fw:"].iter().find(|p| {
+        subject
+            .get(..p.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(p))
+    }) {
+        subject = subject[prefix.len()..].trim_start();
+    }
+    subject
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> EmailConfig {
+        EmailConfig::new(
+            "imap.example.com",
+            "smtp.example.com",
+            "bot@example.com",
+            "x",
+        )
+        .with_allowed_senders(vec!["@example.com".to_string()])
+        .with_authserv_id("mx.example.com")
+    }
+
+    const REPLY: &str = "Authentication-Results: mx.example.com;\r\n \
+            dkim=pass (2048-bit key) header.d=example.com header.s=s1;\r\n \
+            spf=fail smtp.mailfrom=example.com\r\n\
+        From: Alice <Alice@Example.com>\r\n\
+        To: bot@example.com\r\n\
+        Subject: Re: Weekly report\r\n\
+        Message-ID: <m3@example.com>\r\n\
+        In-Reply-To: <m2@example.com>\r\n\
+        References: <m1@example.com> <m2@example.com>\r\n\
+        Date: Tue, 1 Sep 2026 10:00:00 +0000\r\n\
+        MIME-Version: 1.0\r\n\
+        Content-Type: multipart/mixed; boundary=\"b\"\r\n\
+        \r\n\
+        --b\r\n\
+        Content-Type: text/plain\r\n\
+        \r\n\
+        Please add the numbers.\r\n\
+        \r\n\
+        On Mon, 31 Aug 2026, Cratos wrote:\r\n\
+        > Here is the report\r\n\
+        --b\r\n\
+        Content-Type: image/png\r\n\
+        Content-Disposition: attachment; filename=\"chart.png\"\r\n\
+        Content-Transfer-Encoding: base64\r\n\
+        \r\n\
+        iVBORw0KGgo=\r\n\
+        --b--\r\n";
+
+    #[test]
+    fn test_parse_reply_threads_and_attachments() {
+        let email = parse_email(REPLY.as_bytes(), &config()).unwrap();
+        let msg = &email.message;
+
+        assert_eq!(msg.channel_type, ChannelType::Email);
+        assert_eq!(msg.channel_id, "alice@example.com");
+        assert_eq!(msg.user_name.as_deref(), Some("Alice"));
+        assert_eq!(msg.message_id, "m3@example.com");
+        assert_eq!(msg.thread_id.as_deref(), Some("m1@example.com"));
+        assert!(msg.is_reply);
+        assert_eq!(msg.text, "Please add the numbers.");
+        assert_eq!(msg.timestamp.to_rfc3339(), "2026-09-01T10:00:00+00:00");
+        assert_eq!(email.subject, "Re: Weekly report");
+        assert_eq!(
+            email.references,
+            vec!["m1@example.com", "m2@example.com", "m3@example.com"]
+        );
+
+        assert_eq!(msg.attachments.len(), 1);
+        let attachment = &msg.attachments[0];
+        assert_eq!(attachment.attachment_type, AttachmentType::Image);
+        assert_eq!(attachment.file_name.as_deref(), Some("chart.png"));
+        assert_eq!(attachment.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(email.images.len(), 1);
+        assert_eq!(email.images[0].1, b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn test_new_thread_uses_own_message_id() {
+        let raw =
+            "Authentication-Results: mx.example.com; spf=pass smtp.mailfrom=bob@example.com\r\n\
+            From: bob@example.com\r\nSubject: Hello\r\nMessage-ID: <new@example.com>\r\n\r\n";
+        let email = parse_email(raw.as_bytes(), &config()).unwrap();
+        assert_eq!(email.message.thread_id.as_deref(), Some("new@example.com"));
+        assert!(!email.message.is_reply);
+        // Empty body falls back to the subject
+        assert_eq!(email.message.text, "Hello");
+    }
+
+    #[test]
+    fn test_ignored_messages() {
+        let allowlisted = config().with_allowed_senders(vec!["@trusted.org".to_string()]);
+        let raw = "From: bob@example.com\r\nSubject: Hi\r\n\r\nhello\r\n";
+        assert!(parse_email(raw.as_bytes(), &allowlisted).is_none());
+
+        let own = "From: bot@example.com\r\nSubject: Hi\r\n\r\nhello\r\n";
+        assert!(parse_email(own.as_bytes(), &config()).is_none());
+
+        let vacation =
+            "From: bob@example.com\r\nAuto-Submitted: auto-replied\r\nSubject: Away\r\n\r\nOOO\r\n";
+        assert!(parse_email(vacation.as_bytes(), &config()).is_none());
+
+        let unlisted = config().with_allowed_senders(Vec::new());
+        assert!(parse_email(REPLY.as_bytes(), &unlisted).is_none());
+    }
+
+    #[test]
+    fn test_sender_must_be_authenticated() {
+        let parse = |auth: &str, config: &EmailConfig| {
+            let raw = format!("{auth}From: bob@example.com\r\nSubject: Hi\r\n\r\nhello\r\n");
+            parse_email(raw.as_bytes(), config).is_some()
+        };
+        let config = config();
+
+        // No results, a failure, or a pass for another domain
+        assert!(!parse("", &config));
+        assert!(!parse(
+            "Authentication-Results: mx.example.com; dkim=fail header.d=example.com\r\n",
+            &config
+        ));
+        assert!(!parse(
+            "Authentication-Results: mx.example.com; dkim=pass header.d=evil.test\r\n",
+            &config
+        ));
+        assert!(parse(
+            "Authentication-Results: mx.example.com; dkim=pass header.d=example.com\r\n",
+            &config
+        ));
+
+        // Only the configured server's results are read
+        let relayed = "Authentication-Results: relay.test; none\r\n\
+            Authentication-Results: mx.example.com; dkim=pass header.d=example.com\r\n";
+        assert!(parse(relayed, &config));
+        let forged = "Authentication-Results: mx.evil.test; dkim=pass header.d=example.com\r\n";
+        assert!(!parse(forged, &config));
+
+        // Without an authserv-id a forged topmost header is not trusted
+        let mut unpinned = config.clone();
+        unpinned.authserv_id = None;
+        assert!(!parse(forged, &unpinned));
+    }
+
+    #[test]
+    fn test_strip_reply_prefix() {
+        assert_eq!(strip_reply_prefix("Re: RE: Fwd: Plan"), "Plan");
+        assert_eq!(strip_reply_prefix("Plan"), "Plan");
+    }
+}
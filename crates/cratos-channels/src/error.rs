@@ -25,6 +25,14 @@ pub enum Error {
     #[error("twitter error: {0}")]
     Twitter(String),
 
+    /// Email (IMAP/SMTP) error
+    #[error("email error: {0}")]
+    Email(String),
+
+    /// Mattermost error
+    #[error("mattermost error: {0}")]
+    Mattermost(String),
+
     /// Configuration error
     #[error("configuration error: {0}")]
     Config(String),
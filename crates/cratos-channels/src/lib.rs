@@ -6,13 +6,17 @@
 //! - Discord (via serenity)
 //! - WhatsApp (via Baileys bridge or Business API)
 //! - Matrix (via matrix-sdk)
+//! - Email (IMAP + SMTP)
+//! - Mattermost (REST API v4 + WebSocket)
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
 pub mod discord;
+pub mod email;
 pub mod error;
 pub mod matrix;
+pub mod mattermost;
 pub mod message;
 pub mod slack;
 pub mod telegram;
@@ -48,3 +52,11 @@ pub use twitter::{TwitterAdapter, TwitterConfig};
 
 // Re-export Matrix adapter
 pub use matrix::{MatrixAdapter, MatrixConfig};
+
+// Re-export Email adapter
+pub use email::{EmailAdapter, EmailConfig, SmtpSecurity};
+
+// Re-export Mattermost adapter
+pub use mattermost::{
+    MattermostActionRequest, MattermostActionResponse, MattermostAdapter, MattermostConfig,
+};
@@ -0,0 +1,310 @@
+//! Interactive message buttons
+//!
+//! Callback [`MessageButton`]s become message attachment actions. When one is
+//! clicked, Mattermost POSTs a [`MattermostActionRequest`] to the configured
+//! callback URL with the context we attached: the button's callback data and
+//! an HMAC signature proving we created it.
+
+use super::adapter::MattermostAdapter;
+use super::config::MattermostConfig;
+use crate::message::{MessageButton, OutgoingMessage};
+use crate::util::sanitize_error_for_user;
+use cratos_core::{ApprovalRequest, ApprovalStatus, Orchestrator, OrchestratorInput};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+use std::sync::Arc;
+use tracing::{debug, error, warn};
+
+/// Context attached to a button and echoed back on click
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActionContext {
+    /// `MessageButton::callback_data`
+    #[serde(default)]
+    pub callback_data: String,
+    /// Hex HMAC-SHA256 of `callback_data`
+    #[serde(default)]
+    pub signature: String,
+}
+
+/// Button click sent by Mattermost to the callback URL
+#[derive(Debug, Clone, Deserialize)]
+pub struct MattermostActionRequest {
+    /// User who clicked
+    pub user_id: String,
+    /// Username of the user who clicked
+    #[serde(default)]
+    pub user_name: String,
+    /// Channel of the post
+    #[serde(default)]
+    pub channel_id: String,
+    /// Post the button belongs to
+    #[serde(default)]
+    pub post_id: String,
+    /// Context attached to the button
+    #[serde(default)]
+    pub context: ActionContext,
+}
+
+/// Replacement for the post the button belongs to
+#[derive(Debug, Clone, Serialize)]
+pub struct PostUpdate {
+    /// New message text
+    pub message: String,
+    /// New props (empty removes the buttons)
+    pub props: serde_json::Value,
+}
+
+/// Response to a button click
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MattermostActionResponse {
+    /// Update the post the button belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update: Option<PostUpdate>,
+    /// Message only the clicking user sees
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ephemeral_text: Option<String>,
+}
+
+impl MattermostActionResponse {
+    fn ephemeral(text: impl Into<String>) -> Self {
+        Self {
+            update: None,
+            ephemeral_text: Some(text.into()),
+        }
+    }
+
+    fn replace_post(message: impl Into<String>) -> Self {
+        Self {
+            update: Some(PostUpdate {
+                message: message.into(),
+                props: json!({}),
+            }),
+            ephemeral_text: None,
+        }
+    }
+}
+
+fn mac(secret: &str) -> Hmac<Sha256> {
+    Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length")
+}
+
+/// Sign button callback data
+pub(crate) fn sign_callback(secret: &str, data: &str) -> String {
+    let mut mac = mac(secret);
+    mac.update(data.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Verify a button callback signature (constant time)
+pub(crate) fn verify_callback(secret: &str, data: &str, signature: &str) -> bool {
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    let mut mac = mac(secret);
+    mac.update(data.as_bytes());
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Render buttons into post text and props
+///
+/// Callback buttons become attachment actions (only when a callback URL is
+/// configured); link buttons are appended to the text, since Mattermost
+/// actions cannot open URLs.
+pub(crate) fn build_button_props(
+    config: &MattermostConfig,
+    text: &str,
+    buttons: &[MessageButton],
+) -> (String, serde_json::Value) {
+    let links: Vec<String> = buttons
+        .iter()
+        .filter_map(|b| b.url.as_ref().map(|url| format!("[{}]({})", b.text, url)))
+        .collect();
+    let text = if links.is_empty() {
+        text.to_string()
+    } else {
+        format!("{}\n\n{}", text, links.join(" · "))
+    };
+
+    let callbacks: Vec<(&MessageButton, &String)> = buttons
+        .iter()
+        .filter_map(|b| b.callback_data.as_ref().map(|data| (b, data)))
+        .collect();
+    if callbacks.is_empty() {
+        return (text, json!({}));
+    }
+    let Some(url) = &config.callback_url else {
+        debug!("Mattermost: no callback URL configured, omitting buttons");
+        return (text, json!({}));
+    };
+
+    let actions: Vec<serde_json::Value> = callbacks
+        .iter()
+        .enumerate()
+        .map(|(i, (button, data))| {
+            json!({
+                // Action IDs must be alphanumeric
+                "id": format!("action{}", i),
+                "name": button.text,
+                "type": "button",
+                "integration": {
+                    "url": url,
+                    "context": ActionContext {
+                        callback_data: (*data).clone(),
+                        signature: sign_callback(&config.action_secret, data),
+                    },
+                },
+            })
+        })
+        .collect();
+
+    (text, json!({ "attachments": [{ "actions": actions }] }))
+}
+
+impl MattermostAdapter {
+    /// Handle a button click posted to the callback URL
+    ///
+    /// `approve:<id>` / `deny:<id>` resolve approval requests on behalf of the
+    /// clicking user; any other callback data is routed to the orchestrator
+    /// as `/action <data>` and answered in the post's thread.
+    pub async fn handle_action(
+        self: &Arc<Self>,
+        orchestrator: &Arc<Orchestrator>,
+        request: MattermostActionRequest,
+    ) -> MattermostActionResponse {
+        let data = &request.context.callback_data;
+        if !verify_callback(&self.config.action_secret, data, &request.context.signature) {
+            warn!(user_id = %request.user_id, "Mattermost: rejected unsigned button callback");
+            return MattermostActionResponse::ephemeral("This button is no longer valid.");
+        }
+        if !self.is_channel_allowed(&request.channel_id) {
+            return MattermostActionResponse::ephemeral("Actions are not allowed in this channel.");
+        }
+        debug!(user_id = %request.user_id, data = %data, "Mattermost button clicked");
+
+        if let Some(id) = data.strip_prefix("approve:") {
+            return resolve_approval(orchestrator, id, &request, true).await;
+        }
+        if let Some(id) = data.strip_prefix("deny:") {
+            return resolve_approval(orchestrator, id, &request, false).await;
+        }
+
+        let adapter = Arc::clone(self);
+        let orchestrator = Arc::clone(orchestrator);
+        tokio::spawn(async move {
+            adapter.run_action(&orchestrator, request).await;
+        });
+        MattermostActionResponse::ephemeral("Working on it…")
+    }
+
+    async fn run_action(&self, orchestrator: &Orchestrator, request: MattermostActionRequest) {
+        let thread_id = self.thread_root(&request.post_id).await;
+        let mut input = OrchestratorInput::new(
+            "mattermost",
+            &request.channel_id,
+            &request.user_id,
+            format!("/action {}", request.context.callback_data),
+        );
+        if let Some(thread_id) = &thread_id {
+            input = input.with_thread(thread_id);
+        }
+
+        let reply = match orchestrator.process(input).await {
+            Ok(result) if result.response.is_empty() => "Done.".to_string(),
+            Ok(result) => result.response,
+            Err(e) => {
+                error!(error = %e, "Mattermost action failed");
+                format!(
+                    "Sorry, I encountered an error: {}",
+                    sanitize_error_for_user(&e.to_string())
+                )
+            }
+        };
+
+        let mut message = OutgoingMessage::markdown(reply);
+        message.thread_id = thread_id;
+        if let Err(e) =
+            crate::message::ChannelAdapter::send_message(self, &request.channel_id, message).await
+        {
+            warn!(error = %e, "Failed to send Mattermost action response");
+        }
+    }
+
+    /// Root of the thread a post belongs to
+    async fn thread_root(&self, post_id: &str) -> Option<String> {
+        #[derive(Deserialize)]
+        struct PostRef {
+            id: String,
+            #[serde(default)]
+            root_id: String,
+        }
+
+        if post_id.is_empty() {
+            return None;
+        }
+        let post: PostRef = self
+            .request(
+                self.client
+                    .get(self.config.api_url(&format!("posts/{}", post_id))),
+            )
+            .await
+            .ok()?;
+        Some(if post.root_id.is_empty() {
+            post.id
+        } else {
+            post.root_id
+        })
+    }
+}
+
+async fn resolve_approval(
+    orchestrator: &Orchestrator,
+    id: &str,
+    request: &MattermostActionRequest,
+    approve: bool,
+) -> MattermostActionResponse {
+    let Ok(request_id) = uuid::Uuid::parse_str(id) else {
+        return MattermostActionResponse::ephemeral("Invalid request ID format.");
+    };
+    let Some(manager) = orchestrator.approval_manager() else {
+        return MattermostActionResponse::ephemeral("Approval manager not configured.");
+    };
+
+    let resolved = if approve {
+        manager.approve_by(request_id, &request.user_id).await
+    } else {
+        manager.reject_by(request_id, &request.user_id).await
+    };
+    approval_response(id, &request.user_name, approve, resolved.as_ref())
+}
+
+/// Reply to an approve/deny click given the request after the vote.
+pub(crate) fn approval_response(
+    id: &str,
+    user_name: &str,
+    approve: bool,
+    resolved: Option<&ApprovalRequest>,
+) -> MattermostActionResponse {
+    let Some(r) = resolved else {
+        return MattermostActionResponse::ephemeral(format!(
+            "Failed to {} `{}` (not found, not yours, or already resolved).",
+            if approve { "approve" } else { "deny" },
+            id
+        ));
+    };
+    match r.status {
+        ApprovalStatus::Pending if approve => MattermostActionResponse::ephemeral(format!(
+            "Approval recorded; {} more needed.",
+            r.approvals_remaining()
+        )),
+        ApprovalStatus::Approved => MattermostActionResponse::replace_post(format!(
+            "Approval request `{}` approved by @{}.",
+            id, user_name
+        )),
+        _ => MattermostActionResponse::replace_post(format!(
+            "Approval request `{}` denied by @{}.",
+            id, user_name
+        )),
+    }
+}
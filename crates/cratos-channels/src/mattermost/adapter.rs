@@ -0,0 +1,238 @@
+use super::actions::build_button_props;
+use super::config::MattermostConfig;
+use crate::error::{Error, Result};
+use crate::message::{ChannelAdapter, ChannelType, OutgoingAttachment, OutgoingMessage};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+/// Bot account as returned by `GET /users/me`
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct BotUser {
+    pub id: String,
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedPost {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadedFiles {
+    file_infos: Vec<UploadedFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadedFile {
+    id: String,
+}
+
+/// Mattermost bot adapter (REST API v4 + WebSocket events)
+pub struct MattermostAdapter {
+    pub(crate) config: MattermostConfig,
+    pub(crate) client: reqwest::Client,
+    pub(crate) bot: RwLock<Option<BotUser>>,
+}
+
+impl MattermostAdapter {
+    /// Create a new Mattermost adapter
+    #[must_use]
+    pub fn new(config: MattermostConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+            bot: RwLock::new(None),
+        }
+    }
+
+    /// Create from environment
+    pub fn from_env() -> Result<Self> {
+        let config = MattermostConfig::from_env()?;
+        Ok(Self::new(config))
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &MattermostConfig {
+        &self.config
+    }
+
+    /// Check if a channel is allowed
+    pub fn is_channel_allowed(&self, channel_id: &str) -> bool {
+        self.config.is_channel_allowed(channel_id)
+    }
+
+    /// Look up the bot account (`GET /users/me`) and remember it
+    pub async fn connect(&self) -> Result<()> {
+        let me: BotUser = self
+            .request(self.client.get(self.config.api_url("users/me")))
+            .await?;
+        debug!(user_id = %me.id, username = %me.username, "Mattermost: authenticated");
+        *self.bot.write().await = Some(me);
+        Ok(())
+    }
+
+    pub(crate) async fn bot_user(&self) -> Option<BotUser> {
+        self.bot.read().await.clone()
+    }
+
+    /// Send an authenticated request and decode the JSON response
+    pub(crate) async fn request<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T> {
+        let response = request
+            .bearer_auth(&self.config.bot_token)
+            .send()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            #[derive(Deserialize)]
+            struct ApiError {
+                message: String,
+            }
+            let message = response
+                .json::<ApiError>()
+                .await
+                .map(|e| e.message)
+                .unwrap_or_default();
+            return Err(Error::Mattermost(format!("{}: {}", status, message)));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| Error::Mattermost(format!("invalid API response: {}", e)))
+    }
+
+    /// Download a file attachment
+    pub(crate) async fn download_file(&self, file_id: &str) -> Result<Vec<u8>> {
+        let response = self
+            .client
+            .get(self.config.api_url(&format!("files/{}", file_id)))
+            .bearer_auth(&self.config.bot_token)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| Error::Network(e.to_string()))?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+        Ok(bytes.to_vec())
+    }
+
+    /// Create a post, returning its ID
+    pub(crate) async fn create_post(&self, post: serde_json::Value) -> Result<String> {
+        let created: CreatedPost = self
+            .request(self.client.post(self.config.api_url("posts")).json(&post))
+            .await?;
+        debug!("Mattermost: Created post {}", created.id);
+        Ok(created.id)
+    }
+}
+
+#[async_trait]
+impl ChannelAdapter for MattermostAdapter {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::Mattermost
+    }
+
+    async fn send_message(&self, channel_id: &str, message: OutgoingMessage) -> Result<String> {
+        let (text, props) = build_button_props(&self.config, &message.text, &message.buttons);
+        // Replies must reference the thread root; `thread_id` always is one
+        let root_id = message.thread_id.or(message.reply_to).unwrap_or_default();
+
+        self.create_post(json!({
+            "channel_id": channel_id,
+            "message": text,
+            "root_id": root_id,
+            "props": props,
+        }))
+        .await
+    }
+
+    async fn edit_message(
+        &self,
+        _channel_id: &str,
+        message_id: &str,
+        message: OutgoingMessage,
+    ) -> Result<()> {
+        let (text, props) = build_button_props(&self.config, &message.text, &message.buttons);
+        let _: serde_json::Value = self
+            .request(
+                self.client
+                    .put(self.config.api_url(&format!("posts/{}/patch", message_id)))
+                    .json(&json!({ "message": text, "props": props })),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_message(&self, _channel_id: &str, message_id: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .request(
+                self.client
+                    .delete(self.config.api_url(&format!("posts/{}", message_id))),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn send_typing(&self, channel_id: &str) -> Result<()> {
+        let user_id = self
+            .bot_user()
+            .await
+            .map(|b| b.id)
+            .unwrap_or_else(|| "me".to_string());
+        let _: serde_json::Value = self
+            .request(
+                self.client
+                    .post(self.config.api_url(&format!("users/{}/typing", user_id)))
+                    .json(&json!({ "channel_id": channel_id })),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn send_attachment(
+        &self,
+        channel_id: &str,
+        attachment: OutgoingAttachment,
+        reply_to: Option<&str>,
+    ) -> Result<String> {
+        let data = attachment
+            .decode_data()
+            .map_err(|e| Error::Mattermost(format!("Invalid base64 attachment data: {}", e)))?;
+
+        let part = reqwest::multipart::Part::bytes(data)
+            .file_name(attachment.filename.clone())
+            .mime_str(&attachment.mime_type)
+            .map_err(|e| Error::Mattermost(format!("Invalid MIME type: {}", e)))?;
+        let form = reqwest::multipart::Form::new()
+            .text("channel_id", channel_id.to_string())
+            .part("files", part);
+
+        let uploaded: UploadedFiles = self
+            .request(
+                self.client
+                    .post(self.config.api_url("files"))
+                    .multipart(form),
+            )
+            .await?;
+        let file_ids: Vec<String> = uploaded.file_infos.into_iter().map(|f| f.id).collect();
+
+        self.create_post(json!({
+            "channel_id": channel_id,
+            "message": attachment.caption.unwrap_or_default(),
+            "root_id": reply_to.unwrap_or_default(),
+            "file_ids": file_ids,
+        }))
+        .await
+    }
+}
@@ -0,0 +1,142 @@
+use crate::error::{Error, Result};
+use serde::Deserialize;
+
+/// Mattermost bot configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct MattermostConfig {
+    /// Server URL (e.g., "https://chat.example.com")
+    pub server_url: String,
+    /// Bot account access token
+    pub bot_token: String,
+    /// Allowed channel IDs (empty = allow all)
+    #[serde(default)]
+    pub allowed_channels: Vec<String>,
+    /// Whether to require @mention in public/private channels (DMs always respond)
+    #[serde(default = "default_true")]
+    pub require_mention: bool,
+    /// Notification channel ID for EventBus alerts (approval requests, failures)
+    #[serde(default)]
+    pub notify_channel_id: Option<String>,
+    /// Public URL Mattermost posts button clicks to
+    /// (e.g., "https://cratos.example.com/api/v1/webhooks/mattermost/actions").
+    /// Interactive buttons are omitted when unset.
+    #[serde(default)]
+    pub callback_url: Option<String>,
+    /// Secret used to sign button callbacks (random per process when unset)
+    #[serde(default = "default_action_secret")]
+    pub action_secret: String,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_action_secret() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+impl MattermostConfig {
+    /// Create from environment variables
+    pub fn from_env() -> Result<Self> {
+        let server_url = std::env::var("MATTERMOST_URL")
+            .map_err(|_| Error::Config("MATTERMOST_URL not set".to_string()))?;
+        let bot_token = std::env::var("MATTERMOST_BOT_TOKEN")
+            .map_err(|_| Error::Config("MATTERMOST_BOT_TOKEN not set".to_string()))?;
+
+        let allowed_channels = std::env::var("MATTERMOST_ALLOWED_CHANNELS")
+            .ok()
+            .map(|s| {
+                s.split(',')
+                    .map(|c| c.trim().to_string())
+                    .filter(|c| !c.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let require_mention = std::env::var("MATTERMOST_REQUIRE_MENTION")
+            .map(|s| s != "false" && s != "0")
+            .unwrap_or(true);
+
+        let non_empty = |name: &str| std::env::var(name).ok().filter(|s| !s.trim().is_empty());
+
+        Ok(Self {
+            server_url,
+            bot_token,
+            allowed_channels,
+            require_mention,
+            notify_channel_id: non_empty("MATTERMOST_NOTIFY_CHANNEL_ID"),
+            callback_url: non_empty("MATTERMOST_CALLBACK_URL"),
+            action_secret: non_empty("MATTERMOST_ACTION_SECRET")
+                .unwrap_or_else(default_action_secret),
+        })
+    }
+
+    /// Create with a server URL and bot token
+    #[must_use]
+    pub fn new(server_url: impl Into<String>, bot_token: impl Into<String>) -> Self {
+        Self {
+            server_url: server_url.into(),
+            bot_token: bot_token.into(),
+            allowed_channels: Vec::new(),
+            require_mention: true,
+            notify_channel_id: None,
+            callback_url: None,
+            action_secret: default_action_secret(),
+        }
+    }
+
+    /// Set allowed channels
+    #[must_use]
+    pub fn with_allowed_channels(mut self, channels: Vec<String>) -> Self {
+        self.allowed_channels = channels;
+        self
+    }
+
+    /// Set require mention mode
+    #[must_use]
+    pub fn with_require_mention(mut self, enabled: bool) -> Self {
+        self.require_mention = enabled;
+        self
+    }
+
+    /// Set the callback URL for interactive buttons
+    #[must_use]
+    pub fn with_callback_url(mut self, url: impl Into<String>) -> Self {
+        self.callback_url = Some(url.into());
+        self
+    }
+
+    /// Set the notification channel
+    #[must_use]
+    pub fn with_notify_channel(mut self, channel_id: impl Into<String>) -> Self {
+        self.notify_channel_id = Some(channel_id.into());
+        self
+    }
+
+    /// Check if a channel is allowed
+    pub fn is_channel_allowed(&self, channel_id: &str) -> bool {
+        self.allowed_channels.is_empty() || self.allowed_channels.iter().any(|c| c == channel_id)
+    }
+
+    /// REST API v4 URL for a path (e.g., "posts")
+    pub fn api_url(&self, path: &str) -> String {
+        format!(
+            "{}/api/v4/{}",
+            self.server_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+
+    /// WebSocket event stream URL
+    pub fn websocket_url(&self) -> String {
+        let base = self.server_url.trim_end_matches('/');
+        let base = if let Some(rest) = base.strip_prefix("https://") {
+            format!("wss://{}", rest)
+        } else if let Some(rest) = base.strip_prefix("http://") {
+            format!("ws://{}", rest)
+        } else {
+            base.to_string()
+        };
+        format!("{}/api/v4/websocket", base)
+    }
+}
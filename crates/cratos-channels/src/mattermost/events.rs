@@ -0,0 +1,403 @@
+//! WebSocket event stream and message handling
+
+use super::adapter::{BotUser, MattermostAdapter};
+use crate::error::{Error, Result};
+use crate::message::{
+    Attachment, AttachmentType, ChannelAdapter, ChannelType, MessageButton, NormalizedMessage,
+    OutgoingMessage,
+};
+use crate::util::{mask_for_logging, sanitize_error_for_user};
+use chrono::{DateTime, Utc};
+use cratos_core::event_bus::OrchestratorEvent;
+use cratos_core::{Orchestrator, OrchestratorInput};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+/// Largest image forwarded to the model
+const MAX_IMAGE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Upper bound for the reconnect backoff
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// WebSocket event envelope
+#[derive(Debug, Deserialize)]
+struct WsEvent {
+    #[serde(default)]
+    event: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+/// A post as delivered in `posted` events
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Post {
+    pub id: String,
+    #[serde(default)]
+    pub channel_id: String,
+    #[serde(default)]
+    pub user_id: String,
+    #[serde(default)]
+    pub root_id: String,
+    #[serde(default)]
+    pub message: String,
+    /// Empty for user posts, `system_*` for join/leave notices etc.
+    #[serde(default, rename = "type")]
+    pub post_type: String,
+    /// Milliseconds since the epoch
+    #[serde(default)]
+    pub create_at: i64,
+    #[serde(default)]
+    pub metadata: PostMetadata,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct PostMetadata {
+    #[serde(default)]
+    pub files: Vec<FileInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct FileInfo {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub mime_type: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// A `posted` event
+#[derive(Debug, Clone)]
+pub(crate) struct PostedEvent {
+    pub post: Post,
+    /// "D" (direct), "G" (group), "O" (public) or "P" (private)
+    pub channel_type: String,
+    /// "@username" of the author
+    pub sender_name: String,
+    /// User IDs mentioned in the post
+    pub mentions: Vec<String>,
+}
+
+impl PostedEvent {
+    /// Parse the `data` of a `posted` event (the post and mentions are JSON strings)
+    pub(crate) fn from_data(data: &serde_json::Value) -> Option<Self> {
+        let post = serde_json::from_str(data.get("post")?.as_str()?).ok()?;
+        let str_field = |name: &str| {
+            data.get(name)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let mentions = data
+            .get("mentions")
+            .and_then(|v| v.as_str())
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or_default();
+
+        Some(Self {
+            post,
+            channel_type: str_field("channel_type"),
+            sender_name: str_field("sender_name"),
+            mentions,
+        })
+    }
+}
+
+fn attachment_type(mime_type: &str) -> AttachmentType {
+    match mime_type.split('/').next().unwrap_or_default() {
+        "image" => AttachmentType::Image,
+        "audio" => AttachmentType::Audio,
+        "video" => AttachmentType::Video,
+        "application" | "text" => AttachmentType::Document,
+        _ => AttachmentType::Other,
+    }
+}
+
+impl MattermostAdapter {
+    /// Convert a `posted` event into a normalized message
+    ///
+    /// Returns `None` for the bot's own posts, system messages, disallowed
+    /// channels, and channel posts that don't mention the bot when
+    /// `require_mention` is set.
+    pub(crate) fn normalize_post(
+        &self,
+        event: &PostedEvent,
+        bot: &BotUser,
+    ) -> Option<NormalizedMessage> {
+        let post = &event.post;
+        if post.user_id == bot.id || !post.post_type.is_empty() {
+            return None;
+        }
+        if !self.is_channel_allowed(&post.channel_id) {
+            debug!(channel_id = %post.channel_id, "Channel not allowed");
+            return None;
+        }
+
+        let mention = format!("@{}", bot.username);
+        let is_dm = event.channel_type == "D";
+        let is_mentioned = event.mentions.contains(&bot.id) || post.message.contains(&mention);
+        if !is_dm && self.config.require_mention && !is_mentioned {
+            return None;
+        }
+
+        let text = post.message.replace(&mention, "").trim().to_string();
+        if text.is_empty() && post.metadata.files.is_empty() {
+            return None;
+        }
+
+        let thread_id = if post.root_id.is_empty() {
+            &post.id
+        } else {
+            &post.root_id
+        };
+        let mut msg = NormalizedMessage::new(
+            ChannelType::Mattermost,
+            &post.channel_id,
+            &post.user_id,
+            &post.id,
+            text,
+        )
+        .with_thread(thread_id);
+
+        let user_name = event.sender_name.trim_start_matches('@');
+        if !user_name.is_empty() {
+            msg = msg.with_user_name(user_name);
+        }
+        if !post.root_id.is_empty() {
+            msg = msg.as_reply();
+        }
+        if let Some(ts) = DateTime::<Utc>::from_timestamp_millis(post.create_at) {
+            msg.timestamp = ts;
+        }
+
+        for file in &post.metadata.files {
+            msg = msg.with_attachment(Attachment {
+                attachment_type: attachment_type(&file.mime_type),
+                file_name: Some(file.name.clone()),
+                mime_type: Some(file.mime_type.clone()),
+                file_size: Some(file.size),
+                url: Some(self.config.api_url(&format!("files/{}", file.id))),
+                file_id: Some(file.id.clone()),
+            });
+        }
+
+        Some(msg.with_raw_data(serde_json::json!({
+            "post_id": post.id,
+            "channel_id": post.channel_id,
+            "channel_type": event.channel_type,
+            "root_id": post.root_id,
+        })))
+    }
+
+    /// Run the Mattermost adapter
+    ///
+    /// Authenticates, then listens on the WebSocket event stream and
+    /// processes posts, reconnecting with backoff until shutdown.
+    ///
+    /// # Errors
+    /// Returns an error if the bot token is rejected at startup.
+    pub async fn run(
+        self: Arc<Self>,
+        orchestrator: Arc<Orchestrator>,
+        shutdown: CancellationToken,
+    ) -> Result<()> {
+        self.connect().await?;
+        info!("Mattermost adapter connected to {}", self.config.server_url);
+
+        if let (Some(channel_id), Some(bus)) = (
+            self.config.notify_channel_id.clone(),
+            orchestrator.event_bus().cloned(),
+        ) {
+            let adapter = self.clone();
+            let mut rx = bus.subscribe();
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move {
+                loop {
+                    let event = tokio::select! {
+                        event = rx.recv() => event,
+                        _ = shutdown.cancelled() => break,
+                    };
+                    match event {
+                        Ok(event) => adapter.notify(&channel_id, event).await,
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                            warn!("Mattermost EventBus listener lagged by {} events", n);
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+            info!("Mattermost EventBus notification listener started");
+        }
+
+        let mut backoff = Duration::from_secs(1);
+        loop {
+            let result = tokio::select! {
+                r = self.listen(&orchestrator, &mut backoff) => r,
+                _ = shutdown.cancelled() => break,
+            };
+            if let Err(e) = result {
+                warn!(error = %e, retry_in = ?backoff, "Mattermost WebSocket disconnected");
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(backoff) => {}
+                _ = shutdown.cancelled() => break,
+            }
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+
+        info!("Mattermost adapter shutting down");
+        Ok(())
+    }
+
+    /// One WebSocket session
+    async fn listen(
+        self: &Arc<Self>,
+        orchestrator: &Arc<Orchestrator>,
+        backoff: &mut Duration,
+    ) -> Result<()> {
+        let url = self.config.websocket_url();
+        let connector = if url.starts_with("wss://") {
+            let config = crate::util::tls_client_config()
+                .map_err(|e| Error::Mattermost(format!("TLS setup failed: {}", e)))?;
+            Some(tokio_tungstenite::Connector::Rustls(config))
+        } else {
+            None
+        };
+        let (mut ws, _) =
+            tokio_tungstenite::connect_async_tls_with_config(url.as_str(), None, false, connector)
+                .await
+                .map_err(|e| Error::Network(format!("WebSocket connect failed: {}", e)))?;
+
+        let auth = serde_json::json!({
+            "seq": 1,
+            "action": "authentication_challenge",
+            "data": { "token": self.config.bot_token },
+        });
+        ws.send(WsMessage::text(auth.to_string()))
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+
+        let bot = self
+            .bot_user()
+            .await
+            .ok_or_else(|| Error::Mattermost("bot user not loaded".to_string()))?;
+        *backoff = Duration::from_secs(1);
+        debug!("Mattermost WebSocket connected");
+
+        while let Some(frame) = ws.next().await {
+            let text = match frame.map_err(|e| Error::Network(e.to_string()))? {
+                WsMessage::Text(text) => text,
+                WsMessage::Close(_) => break,
+                _ => continue,
+            };
+            let Ok(event) = serde_json::from_str::<WsEvent>(&text) else {
+                continue;
+            };
+            if event.event != "posted" {
+                continue;
+            }
+            let Some(msg) = PostedEvent::from_data(&event.data)
+                .and_then(|posted| self.normalize_post(&posted, &bot))
+            else {
+                continue;
+            };
+
+            let adapter = Arc::clone(self);
+            let orchestrator = Arc::clone(orchestrator);
+            tokio::spawn(async move { adapter.handle_message(&orchestrator, msg).await });
+        }
+
+        Err(Error::Network("WebSocket closed".to_string()))
+    }
+
+    async fn handle_message(&self, orchestrator: &Orchestrator, msg: NormalizedMessage) {
+        info!(
+            channel_id = %msg.channel_id,
+            user_id = %msg.user_id,
+            text = %mask_for_logging(&msg.text),
+            "Received Mattermost message"
+        );
+        let _ = self.send_typing(&msg.channel_id).await;
+
+        let mut input =
+            OrchestratorInput::new("mattermost", &msg.channel_id, &msg.user_id, &msg.text);
+        if let Some(thread_id) = &msg.thread_id {
+            input = input.with_thread(thread_id);
+        }
+
+        let mut images = Vec::new();
+        for attachment in &msg.attachments {
+            let (AttachmentType::Image, Some(file_id)) =
+                (&attachment.attachment_type, &attachment.file_id)
+            else {
+                continue;
+            };
+            if attachment.file_size.unwrap_or(0) > MAX_IMAGE_BYTES {
+                continue;
+            }
+            match self.download_file(file_id).await {
+                Ok(data) => images.push(cratos_llm::ImageContent::new(
+                    attachment.mime_type.clone().unwrap_or_default(),
+                    data,
+                )),
+                Err(e) => warn!(error = %e, "Failed to download Mattermost file"),
+            }
+        }
+        if !images.is_empty() {
+            input = input.with_images(images);
+        }
+
+        let reply = match orchestrator.process(input).await {
+            Ok(result) if result.response.is_empty() => "Done.".to_string(),
+            Ok(result) => result.response,
+            Err(e) => {
+                error!(error = %e, "Failed to process Mattermost message");
+                format!(
+                    "Sorry, I encountered an error: {}",
+                    sanitize_error_for_user(&e.to_string())
+                )
+            }
+        };
+
+        let mut message = OutgoingMessage::markdown(reply);
+        message.thread_id = msg.thread_id.clone();
+        if let Err(e) = self.send_message(&msg.channel_id, message).await {
+            error!(error = %e, "Failed to send Mattermost response");
+        }
+    }
+
+    /// Post an EventBus notification (approval buttons, failures)
+    async fn notify(&self, channel_id: &str, event: OrchestratorEvent) {
+        let message = match event {
+            OrchestratorEvent::ApprovalRequired {
+                execution_id,
+                request_id,
+            } => OutgoingMessage::markdown(format!(
+                "**Approval required** for execution `{}`\nRequest ID: `{}`",
+                execution_id, request_id
+            ))
+            .with_buttons(vec![
+                MessageButton::callback("Approve", format!("approve:{}", request_id)),
+                MessageButton::callback("Deny", format!("deny:{}", request_id)),
+            ]),
+            OrchestratorEvent::ExecutionFailed {
+                execution_id,
+                error,
+            } => OutgoingMessage::markdown(format!(
+                "**Execution failed** `{}`\n{}",
+                execution_id, error
+            )),
+            _ => return,
+        };
+
+        if let Err(e) = self.send_message(channel_id, message).await {
+            warn!(error = %e, "Failed to send Mattermost notification");
+        }
+    }
+}
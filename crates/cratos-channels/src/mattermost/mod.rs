@@ -0,0 +1,33 @@
+//! Mattermost - REST API v4 + WebSocket adapter
+//!
+//! Posts arrive over the WebSocket event stream and replies are created via
+//! the REST API, threaded under the root post. Callback buttons become
+//! interactive message actions; Mattermost posts clicks to the callback URL
+//! served at `/api/v1/webhooks/mattermost/actions`, where approval buttons
+//! resolve pending requests.
+//!
+//! ## Configuration
+//!
+//! ```toml
+//! [channels.mattermost]
+//! enabled = true
+//! ```
+//!
+//! Server, token and options come from `MATTERMOST_*` environment variables
+//! (see [`MattermostConfig::from_env`]).
+
+/// Interactive message buttons and their callbacks.
+pub mod actions;
+/// Mattermost REST client and `ChannelAdapter` implementation.
+pub mod adapter;
+/// Mattermost server and bot configuration.
+pub mod config;
+/// WebSocket event stream and message handling.
+pub mod events;
+
+#[cfg(test)]
+mod tests;
+
+pub use actions::{MattermostActionRequest, MattermostActionResponse};
+pub use adapter::MattermostAdapter;
+pub use config::MattermostConfig;
@@ -0,0 +1,296 @@
+use super::actions::{approval_response, build_button_props, sign_callback, verify_callback};
+use super::adapter::BotUser;
+use super::events::PostedEvent;
+use super::*;
+use crate::message::{AttachmentType, ChannelAdapter, MessageButton, OutgoingMessage};
+use axum::{routing::get, routing::post, Json, Router};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+fn bot() -> BotUser {
+    BotUser {
+        id: "bot1".to_string(),
+        username: "cratos".to_string(),
+    }
+}
+
+fn posted(channel_type: &str, message: &str, root_id: &str, mentions: &[&str]) -> PostedEvent {
+    let post = json!({
+        "id": "p2",
+        "channel_id": "c1",
+        "user_id": "u1",
+        "root_id": root_id,
+        "message": message,
+        "type": "",
+        "create_at": 1_767_225_600_000_i64,
+        "metadata": {
+            "files": [{ "id": "f1", "name": "chart.png", "mime_type": "image/png", "size": 42 }]
+        },
+    });
+    PostedEvent::from_data(&json!({
+        "post": post.to_string(),
+        "channel_type": channel_type,
+        "sender_name": "@alice",
+        "mentions": serde_json::to_string(mentions).unwrap(),
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_mattermost_config() {
+    let config = MattermostConfig::new("https://chat.example.com/", "token")
+        .with_allowed_channels(vec!["c1".to_string()])
+        .with_require_mention(false);
+
+    assert_eq!(
+        config.api_url("posts"),
+        "https://chat.example.com/api/v4/posts"
+    );
+    assert_eq!(
+        config.websocket_url(),
+        "wss://chat.example.com/api/v4/websocket"
+    );
+    assert_eq!(
+        MattermostConfig::new("http://localhost:8065", "t").websocket_url(),
+        "ws://localhost:8065/api/v4/websocket"
+    );
+    assert!(config.is_channel_allowed("c1"));
+    assert!(!config.is_channel_allowed("c2"));
+    assert!(!config.require_mention);
+}
+
+#[test]
+fn test_normalize_channel_post_requires_mention() {
+    let adapter = MattermostAdapter::new(MattermostConfig::new("http://localhost", "t"));
+
+    assert!(adapter
+        .normalize_post(&posted("O", "hello everyone", "", &[]), &bot())
+        .is_none());
+
+    let msg = adapter
+        .normalize_post(
+            &posted("O", "@cratos summarize this", "", &["bot1"]),
+            &bot(),
+        )
+        .unwrap();
+    assert_eq!(msg.text, "summarize this");
+    assert_eq!(msg.channel_id, "c1");
+    assert_eq!(msg.user_id, "u1");
+    assert_eq!(msg.user_name.as_deref(), Some("alice"));
+    // A root post starts its own thread
+    assert_eq!(msg.thread_id.as_deref(), Some("p2"));
+    assert!(!msg.is_reply);
+    assert_eq!(msg.timestamp.timestamp(), 1_767_225_600);
+
+    assert_eq!(msg.attachments.len(), 1);
+    assert_eq!(msg.attachments[0].attachment_type, AttachmentType::Image);
+    assert_eq!(msg.attachments[0].file_id.as_deref(), Some("f1"));
+    assert_eq!(
+        msg.attachments[0].url.as_deref(),
+        Some("http://localhost/api/v4/files/f1")
+    );
+}
+
+#[test]
+fn test_normalize_dm_reply_in_thread() {
+    let adapter = MattermostAdapter::new(MattermostConfig::new("http://localhost", "t"));
+    let msg = adapter
+        .normalize_post(&posted("D", "and the totals?", "p1", &[]), &bot())
+        .unwrap();
+    assert_eq!(msg.thread_id.as_deref(), Some("p1"));
+    assert!(msg.is_reply);
+}
+
+#[test]
+fn test_normalize_skips_own_system_and_disallowed_posts() {
+    let adapter = MattermostAdapter::new(
+        MattermostConfig::new("http://localhost", "t").with_allowed_channels(vec!["c9".into()]),
+    );
+    assert!(adapter
+        .normalize_post(&posted("D", "hi", "", &[]), &bot())
+        .is_none());
+
+    let adapter = MattermostAdapter::new(MattermostConfig::new("http://localhost", "t"));
+    let mut own = posted("D", "hi", "", &[]);
+    own.post.user_id = "bot1".to_string();
+    assert!(adapter.normalize_post(&own, &bot()).is_none());
+
+    let mut system = posted("D", "alice joined", "", &[]);
+    system.post.post_type = "system_join_channel".to_string();
+    assert!(adapter.normalize_post(&system, &bot()).is_none());
+}
+
+#[test]
+fn test_callback_signature() {
+    let signature = sign_callback("secret", "approve:123");
+    assert!(verify_callback("secret", "approve:123", &signature));
+    assert!(!verify_callback("secret", "approve:456", &signature));
+    assert!(!verify_callback("other", "approve:123", &signature));
+    assert!(!verify_callback("secret", "approve:123", "not-hex"));
+}
+
+#[test]
+fn test_build_button_props() {
+    let buttons = vec![
+        MessageButton::callback("Approve", "approve:42"),
+        MessageButton::link("Docs", "https://example.com/docs"),
+    ];
+
+    // Without a callback URL only link buttons survive, as text
+    let config = MattermostConfig::new("http://localhost", "t");
+    let (text, props) = build_button_props(&config, "Run it?", &buttons);
+    assert_eq!(text, "Run it?\n\n[Docs](https://example.com/docs)");
+    assert_eq!(props, json!({}));
+
+    let config = config.with_callback_url("https://cratos.example.com/actions");
+    let (_, props) = build_button_props(&config, "Run it?", &buttons);
+    let actions = props["attachments"][0]["actions"].as_array().unwrap();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0]["name"], "Approve");
+    assert_eq!(
+        actions[0]["integration"]["url"],
+        "https://cratos.example.com/actions"
+    );
+    let context = &actions[0]["integration"]["context"];
+    assert_eq!(context["callback_data"], "approve:42");
+    assert!(verify_callback(
+        &config.action_secret,
+        "approve:42",
+        context["signature"].as_str().unwrap()
+    ));
+}
+
+#[test]
+fn test_action_request_deserialize() {
+    let request: MattermostActionRequest = serde_json::from_value(json!({
+        "user_id": "u1",
+        "user_name": "alice",
+        "channel_id": "c1",
+        "post_id": "p1",
+        "team_id": "t1",
+        "type": "",
+        "context": { "callback_data": "deny:42", "signature": "ab" },
+    }))
+    .unwrap();
+    assert_eq!(request.context.callback_data, "deny:42");
+
+    let response = serde_json::to_value(MattermostActionResponse {
+        update: None,
+        ephemeral_text: Some("ok".to_string()),
+    })
+    .unwrap();
+    assert_eq!(response, json!({ "ephemeral_text": "ok" }));
+}
+
+#[tokio::test]
+async fn test_deny_on_multi_approver_request() {
+    use cratos_core::tool_policy::{ApprovalPolicy, ApprovalRule, ApproverSpec};
+    use cratos_core::{ApprovalManager, ApprovalRequest};
+
+    let manager = ApprovalManager::new().with_policy(
+        ApprovalPolicy::new()
+            .with_group("ops", vec!["alice".to_string(), "bob".to_string()])
+            .with_rule(
+                ApprovalRule::new("exec")
+                    .with_required_approvals(2)
+                    .with_approvers(ApproverSpec::Group {
+                        group: "ops".to_string(),
+                    }),
+            ),
+    );
+    let request = ApprovalRequest::new(
+        uuid::Uuid::new_v4(),
+        "mattermost",
+        "c1",
+        "dev",
+        "exec",
+        "r",
+        60,
+    )
+    .with_tool("exec", json!({}));
+    let (request, _rx) = manager.submit(request, None).await;
+    let id = request.id.to_string();
+
+    let voted = manager.approve_by(request.id, "alice").await;
+    let response = approval_response(&id, "alice", true, voted.as_ref());
+    assert!(response.update.is_none());
+    assert_eq!(
+        response.ephemeral_text.as_deref(),
+        Some("Approval recorded; 1 more needed.")
+    );
+
+    let denied = manager.reject_by(request.id, "bob").await;
+    let response = approval_response(&id, "bob", false, denied.as_ref());
+    assert!(response.ephemeral_text.is_none());
+    assert_eq!(
+        response.update.unwrap().message,
+        format!("Approval request `{}` denied by @bob.", id)
+    );
+}
+
+/// Local stand-in for the Mattermost REST API recording created posts
+async fn fake_server() -> (String, Arc<Mutex<Vec<Value>>>) {
+    let posts = Arc::new(Mutex::new(Vec::new()));
+    let recorded = posts.clone();
+    let app = Router::new()
+        .route(
+            "/api/v4/users/me",
+            get(|| async { Json(json!({ "id": "bot1", "username": "cratos" })) }),
+        )
+        .route(
+            "/api/v4/posts",
+            post(move |Json(body): Json<Value>| {
+                let recorded = recorded.clone();
+                async move {
+                    recorded.lock().unwrap().push(body);
+                    Json(json!({ "id": "new-post" }))
+                }
+            }),
+        );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, posts)
+}
+
+#[tokio::test]
+async fn test_send_message_in_thread_with_buttons() {
+    let (url, posts) = fake_server().await;
+    let adapter = MattermostAdapter::new(
+        MattermostConfig::new(url, "t").with_callback_url("https://cratos.example.com/actions"),
+    );
+    adapter.connect().await.unwrap();
+    assert_eq!(adapter.bot_user().await.unwrap().username, "cratos");
+
+    let id = adapter
+        .send_message(
+            "c1",
+            OutgoingMessage::text("Approve?")
+                .in_thread("p1")
+                .with_button(MessageButton::callback("Approve", "approve:42")),
+        )
+        .await
+        .unwrap();
+    assert_eq!(id, "new-post");
+
+    let posts = posts.lock().unwrap();
+    assert_eq!(posts[0]["channel_id"], "c1");
+    assert_eq!(posts[0]["root_id"], "p1");
+    assert_eq!(posts[0]["message"], "Approve?");
+    assert_eq!(
+        posts[0]["props"]["attachments"][0]["actions"][0]["integration"]["context"]
+            ["callback_data"],
+        "approve:42"
+    );
+}
+
+#[tokio::test]
+async fn test_api_error_is_reported() {
+    let (url, _posts) = fake_server().await;
+    let adapter = MattermostAdapter::new(MattermostConfig::new(url, "t"));
+    assert!(adapter
+        .edit_message("c1", "p1", OutgoingMessage::text("x"))
+        .await
+        .is_err());
+}
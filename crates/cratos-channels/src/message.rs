@@ -23,6 +23,10 @@ pub enum ChannelType {
     Twitter,
     /// Matrix (decentralized messaging)
     Matrix,
+    /// Email (IMAP/SMTP)
+    Email,
+    /// Mattermost
+    Mattermost,
     /// Voice (local audio)
     Voice,
     /// CLI (command line)
@@ -42,6 +46,8 @@ impl ChannelType {
             Self::WhatsApp => "whatsapp",
             Self::Twitter => "twitter",
             Self::Matrix => "matrix",
+            Self::Email => "email",
+            Self::Mattermost => "mattermost",
             Self::Voice => "voice",
             Self::Cli => "cli",
             Self::Api => "api",
@@ -1,8 +1,8 @@
 //! Telegram slash command handlers
 
-use crate::util::sanitize_error_for_user;
+use crate::util::{escape_html, sanitize_error_for_user};
 use cratos_core::dev_sessions::DevSessionMonitor;
-use cratos_core::{Orchestrator, OrchestratorInput};
+use cratos_core::{Orchestrator, OrchestratorInput, SessionContext};
 use std::sync::Arc;
 use teloxide::{
     payloads::SendMessageSetters,
@@ -11,10 +11,12 @@ use teloxide::{
 };
 use tracing::info;
 
-/// Handle a slash command (e.g. /status, /sessions, /tools, /cancel, /approve)
+/// Handle a slash command (e.g. /status, /sessions, /tools, /prompts, /cancel, /approve, /compact)
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_slash_command(
     command: &str,
     args: &str,
+    user_id: &str,
     orchestrator: &Arc<Orchestrator>,
     dev_monitor: &Option<Arc<DevSessionMonitor>>,
     bot: &Bot,
@@ -93,6 +95,28 @@ pub async fn handle_slash_command(
                 lines.join("\n")
             }
         }
+        "/prompts" => {
+            let prompts = orchestrator.mcp_prompt_commands().await;
+            if prompts.is_empty() {
+                "No MCP prompts available.".to_string()
+            } else {
+                let mut lines = vec![format!("<b>MCP Prompts ({})</b>", prompts.len())];
+                for p in &prompts {
+                    let args = p
+                        .arguments
+                        .iter()
+                        .map(|a| format!(" {}=…", a))
+                        .collect::<String>();
+                    lines.push(format!(
+                        "  - <code>/{}{}</code> {}",
+                        escape_html(&p.command),
+                        escape_html(&args),
+                        escape_html(&p.description)
+                    ));
+                }
+                lines.join("\n")
+            }
+        }
         "/cancel" => {
             if args.is_empty() {
                 // No argument: cancel all active executions
@@ -122,6 +146,21 @@ pub async fn handle_slash_command(
                 "Invalid execution ID. Please provide a valid UUID.".to_string()
             }
         }
+        "/compact" => {
+            let session_key =
+                SessionContext::make_key("telegram", &chat_id.0.to_string(), user_id);
+            match orchestrator.compact_session(&session_key).await {
+                Ok(Some(report)) => format!(
+                    "Compacted {} message(s): {} → {} tokens.",
+                    report.messages_compacted, report.tokens_before, report.tokens_after
+                ),
+                Ok(None) => "Nothing to compact yet.".to_string(),
+                Err(e) => format!(
+                    "Compaction failed: {}",
+                    sanitize_error_for_user(&e.to_string())
+                ),
+            }
+        }
         "/approve" => {
             if args.is_empty() {
                 "Usage: /approve &lt;request_id&gt;".to_string()
@@ -6,6 +6,7 @@ use crate::error::Result;
 use crate::message::{ChannelAdapter, OutgoingAttachment};
 use crate::util::{markdown_to_html, mask_for_logging, sanitize_error_for_user};
 use cratos_core::dev_sessions::DevSessionMonitor;
+use cratos_core::event_bus::OrchestratorEvent;
 use cratos_core::{Orchestrator, OrchestratorInput};
 use cratos_llm::ImageContent;
 use std::sync::Arc;
@@ -15,7 +16,7 @@ use teloxide::{
     prelude::*,
     types::{
         ChatAction, ChatId, FileId, InlineKeyboardButton, InlineKeyboardMarkup,
-        Message as TelegramMessage, ParseMode, ReplyParameters,
+        Message as TelegramMessage, MessageId, ParseMode, ReplyParameters,
     },
 };
 use tracing::{debug, error, info, instrument, warn};
@@ -122,6 +123,34 @@ impl TelegramAdapter {
                                 warn!(error = %e, "Failed to send quota warning");
                             }
                         }
+                        Ok(cratos_core::event_bus::OrchestratorEvent::BudgetWarning {
+                            scope,
+                            name,
+                            period,
+                            spent_usd,
+                            limit_usd,
+                            exceeded,
+                        }) => {
+                            let (emoji, state) = if exceeded {
+                                ("🔴", "exceeded")
+                            } else {
+                                ("⚠️", "almost reached")
+                            };
+                            let text = format!(
+                                "{} <b>Budget Warning</b>\n\
+                                {} budget for {} <code>{}</code> {}\n\
+                                Spent: ${:.2} of ${:.2}",
+                                emoji, period, scope, name, state, spent_usd, limit_usd
+                            );
+
+                            if let Err(e) = notify_bot
+                                .send_message(chat_id, &text)
+                                .parse_mode(ParseMode::Html)
+                                .await
+                            {
+                                warn!(error = %e, "Failed to send budget warning");
+                            }
+                        }
                         Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                         Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
                             debug!(skipped = n, "EventBus notification listener lagged");
@@ -183,6 +212,7 @@ impl TelegramAdapter {
             if let Some(result) = handle_slash_command(
                 command,
                 args,
+                &normalized.user_id,
                 &orchestrator,
                 &dev_monitor,
                 &bot,
@@ -213,49 +243,23 @@ impl TelegramAdapter {
             .await;
 
         // Spawn progressive update task if EventBus is available
-        let progress_handle = if let (Ok(ref pm), Some(bus)) =
-            (&progress_msg, orchestrator.event_bus().cloned())
-        {
-            let bot_clone = bot.clone();
-            let chat_id = msg.chat.id;
-            let progress_msg_id = pm.id;
-            let mut rx = bus.subscribe();
-            Some(tokio::spawn(async move {
-                let mut last_edit = std::time::Instant::now();
-                let min_interval = std::time::Duration::from_secs(2);
-                let mut tool_count = 0u32;
-                while let Ok(event) = rx.recv().await {
-                    match event {
-                        cratos_core::event_bus::OrchestratorEvent::ToolStarted {
-                            tool_name,
-                            ..
-                        } => {
-                            tool_count += 1;
-                            let now = std::time::Instant::now();
-                            if now.duration_since(last_edit) >= min_interval {
-                                let text = format!(
-                                    "처리 중... [{}] 실행 중 ({}번째 도구)",
-                                    tool_name, tool_count
-                                );
-                                let _ = bot_clone
-                                    .edit_message_text(chat_id, progress_msg_id, &text)
-                                    .await;
-                                last_edit = now;
-                            }
-                        }
-                        cratos_core::event_bus::OrchestratorEvent::ExecutionCompleted {
-                            ..
-                        }
-                        | cratos_core::event_bus::OrchestratorEvent::ExecutionFailed { .. } => {
-                            break;
-                        }
-                        _ => {}
-                    }
-                }
-            }))
-        } else {
-            None
-        };
+        let progress_handle =
+            if let (Ok(ref pm), Some(bus)) = (&progress_msg, orchestrator.event_bus().cloned()) {
+                let session_key = cratos_core::SessionContext::make_key(
+                    "telegram",
+                    &normalized.channel_id,
+                    &normalized.user_id,
+                );
+                Some(tokio::spawn(show_progress(
+                    bot.clone(),
+                    msg.chat.id,
+                    pm.id,
+                    session_key,
+                    bus.subscribe(),
+                )))
+            } else {
+                None
+            };
 
         // Download images from attachments (multimodal support)
         let mut images = Vec::new();
@@ -439,3 +443,85 @@ impl TelegramAdapter {
         Ok(())
     }
 }
+
+/// Keep the progress message up to date while the execution of `session_key`
+/// runs: the tool being run, or the response text as it streams in
+async fn show_progress(
+    bot: Bot,
+    chat_id: ChatId,
+    progress_msg_id: MessageId,
+    session_key: String,
+    mut rx: tokio::sync::broadcast::Receiver<OrchestratorEvent>,
+) {
+    let mut last_edit = std::time::Instant::now();
+    let min_interval = std::time::Duration::from_secs(2);
+    let mut tool_count = 0u32;
+    let mut execution_id = None;
+    // Response text streamed so far in the current planning step
+    let mut streamed = String::new();
+    while let Ok(event) = rx.recv().await {
+        match event {
+            OrchestratorEvent::ExecutionStarted {
+                execution_id: id,
+                session_key: key,
+            } if key == session_key => {
+                execution_id = Some(id);
+            }
+            OrchestratorEvent::ChatDelta {
+                execution_id: id,
+                delta,
+                is_final: false,
+            } if Some(id) == execution_id => {
+                streamed.push_str(&delta);
+                let now = std::time::Instant::now();
+                if now.duration_since(last_edit) >= min_interval && !streamed.trim().is_empty() {
+                    let _ = bot
+                        .edit_message_text(chat_id, progress_msg_id, streaming_preview(&streamed))
+                        .await;
+                    last_edit = now;
+                }
+            }
+            OrchestratorEvent::ChatReset { execution_id: id } if Some(id) == execution_id => {
+                streamed.clear();
+            }
+            OrchestratorEvent::ToolStarted {
+                execution_id: id,
+                tool_name,
+                ..
+            } if Some(id) == execution_id => {
+                // Text before a tool call isn't the answer
+                streamed.clear();
+                tool_count += 1;
+                let now = std::time::Instant::now();
+                if now.duration_since(last_edit) >= min_interval {
+                    let text = format!(
+                        "처리 중... [{}] 실행 중 ({}번째 도구)",
+                        tool_name, tool_count
+                    );
+                    let _ = bot.edit_message_text(chat_id, progress_msg_id, &text).await;
+                    last_edit = now;
+                }
+            }
+            OrchestratorEvent::ExecutionCompleted { execution_id: id }
+            | OrchestratorEvent::ExecutionFailed {
+                execution_id: id, ..
+            } if Some(id) == execution_id => {
+                break;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Largest streamed preview shown in the progress message (Telegram allows 4096)
+const MAX_PREVIEW_CHARS: usize = 4000;
+
+/// Fit streamed text into the progress message, keeping its newest part
+pub(super) fn streaming_preview(text: &str) -> String {
+    let count = text.chars().count();
+    if count <= MAX_PREVIEW_CHARS {
+        return text.to_string();
+    }
+    let tail: String = text.chars().skip(count - MAX_PREVIEW_CHARS + 1).collect();
+    format!("…{}", tail)
+}
@@ -63,3 +63,16 @@ fn test_dm_policy_disabled() {
 }
 
 // Note: mask_for_logging and sanitize_error_for_user tests are in util.rs
+
+#[test]
+fn test_streaming_preview_keeps_newest_text() {
+    use super::handler::streaming_preview;
+
+    assert_eq!(streaming_preview("hello"), "hello");
+
+    let long = format!("{}end", "가".repeat(5000));
+    let preview = streaming_preview(&long);
+    assert_eq!(preview.chars().count(), 4000);
+    assert!(preview.starts_with('…'));
+    assert!(preview.ends_with("end"));
+}
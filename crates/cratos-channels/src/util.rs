@@ -258,7 +258,7 @@ pub fn markdown_to_html(text: &str) -> String {
 }
 
 /// Escape HTML special characters: `&`, `<`, `>`
-fn escape_html(text: &str) -> String {
+pub(crate) fn escape_html(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -325,6 +325,29 @@ fn convert_links(text: &str) -> String {
     result
 }
 
+// ============================================================================
+// TLS
+// ============================================================================
+
+/// rustls client configuration with the bundled web PKI roots
+///
+/// Built with an explicit crypto provider so adapters that open their own
+/// TLS connections (IMAP, Mattermost WebSocket) don't depend on a
+/// process-wide default provider being installed.
+pub(crate) fn tls_client_config(
+) -> Result<std::sync::Arc<tokio_rustls::rustls::ClientConfig>, tokio_rustls::rustls::Error> {
+    use tokio_rustls::rustls;
+
+    let roots = rustls::RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let config = rustls::ClientConfig::builder_with_provider(std::sync::Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    Ok(std::sync::Arc::new(config))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
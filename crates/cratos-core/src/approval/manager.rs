@@ -1,17 +1,71 @@
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use tokio::sync::{oneshot, RwLock};
+use tokio::sync::{broadcast, oneshot, RwLock};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use super::store::ApprovalStore;
 use super::types::{ApprovalError, ApprovalRequest, ApprovalStatus};
 use crate::auth::AuthContext;
 use crate::event_bus::{EventBus, OrchestratorEvent};
+use crate::tool_policy::{ApprovalPolicy, ApprovalRule};
+
+/// How long a grant for a resumed execution stays valid
+const GRANT_TTL_SECS: i64 = 600;
+
+/// Responder recorded for requests approved by an auto-approve window
+pub const AUTO_APPROVER: &str = "auto-approve";
+
+/// One-shot permission to run the tool call of an approved request
+struct Grant {
+    user_id: String,
+    tool_name: String,
+    /// SHA-256 of the approved arguments
+    args_hash: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Hash of tool arguments, so a grant only covers the call that was approved
+fn args_hash(args: &serde_json::Value) -> String {
+    format!("{:x}", Sha256::digest(args.to_string().as_bytes()))
+}
+
+/// What an auto-approve window covers: one requester calling one tool with
+/// the same arguments under the same rule
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct WindowKey {
+    user_id: String,
+    /// Index of the rule in [`ApprovalPolicy::rules`]
+    rule: usize,
+    tool_name: String,
+    args_hash: String,
+}
 
 /// Manager for approval requests
+///
+/// Requests are kept in memory and, when created with [`ApprovalManager::new_with_db`],
+/// mirrored to SQLite so pending approvals survive a restart. A request whose
+/// waiting execution no longer exists (because the process restarted) is
+/// published on [`ApprovalManager::subscribe_resumed`] once approved, so the
+/// parked execution can be started again.
+///
+/// An [`ApprovalPolicy`] decides who may answer a tool request, how many
+/// approvals it needs, and whether it is auto-approved after an earlier one.
 pub struct ApprovalManager {
     requests: RwLock<HashMap<Uuid, ApprovalRequest>>,
     /// oneshot senders keyed by request ID — resolvers notify waiters
     resolvers: RwLock<HashMap<Uuid, oneshot::Sender<ApprovalStatus>>>,
+    /// One-shot grants for resumed executions, keyed by approval request ID
+    grants: RwLock<HashMap<Uuid, Grant>>,
+    /// Approved requests that had no live waiter
+    resumed_tx: broadcast::Sender<ApprovalRequest>,
+    /// Rules for multi-approver and auto-approved requests
+    policy: ApprovalPolicy,
+    /// Auto-approve windows with their end time
+    auto_approvals: RwLock<HashMap<WindowKey, DateTime<Utc>>>,
+    /// Optional SQLite store for persistence
+    store: Option<ApprovalStore>,
     /// Default timeout in seconds
     default_timeout_secs: i64,
 }
@@ -26,23 +80,91 @@ impl ApprovalManager {
     /// Create a new approval manager
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            requests: RwLock::new(HashMap::new()),
-            resolvers: RwLock::new(HashMap::new()),
-            default_timeout_secs: 300, // 5 minutes
-        }
+        Self::with_timeout_secs(300) // 5 minutes
     }
 
     /// Create with custom timeout
     #[must_use]
     pub fn with_timeout_secs(timeout_secs: i64) -> Self {
+        let (resumed_tx, _) = broadcast::channel(64);
         Self {
             requests: RwLock::new(HashMap::new()),
             resolvers: RwLock::new(HashMap::new()),
+            grants: RwLock::new(HashMap::new()),
+            resumed_tx,
+            policy: ApprovalPolicy::default(),
+            auto_approvals: RwLock::new(HashMap::new()),
+            store: None,
             default_timeout_secs: timeout_secs,
         }
     }
 
+    /// Create an approval manager backed by SQLite, with the given default
+    /// timeout for new requests.
+    ///
+    /// Pending requests from a previous run are loaded back into memory;
+    /// those that expired while the server was down are rejected.
+    pub async fn new_with_db(
+        db: sqlx::Pool<sqlx::Sqlite>,
+        timeout_secs: i64,
+    ) -> Result<Self, sqlx::Error> {
+        let store = ApprovalStore::new(db).await?;
+
+        let mut requests = HashMap::new();
+        let mut expired = 0usize;
+        for mut request in store.load_pending().await? {
+            if request.is_expired() {
+                request.expire();
+                store.update_resolution(&request).await?;
+                expired += 1;
+            } else {
+                requests.insert(request.id, request);
+            }
+        }
+        debug!(
+            pending = requests.len(),
+            expired, "Loaded approval requests from SQLite"
+        );
+
+        let mut manager = Self::with_timeout_secs(timeout_secs);
+        manager.requests = RwLock::new(requests);
+        manager.store = Some(store);
+        Ok(manager)
+    }
+
+    /// Set the approval policy
+    #[must_use]
+    pub fn with_policy(mut self, policy: ApprovalPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Get the approval policy
+    #[must_use]
+    pub fn policy(&self) -> &ApprovalPolicy {
+        &self.policy
+    }
+
+    /// Check whether an approval rule applies to a tool call
+    #[must_use]
+    pub fn requires_approval(&self, tool_name: &str, args: &serde_json::Value) -> bool {
+        self.policy.rule_for(tool_name, args).is_some()
+    }
+
+    /// Default timeout for new requests
+    #[must_use]
+    pub fn default_timeout_secs(&self) -> i64 {
+        self.default_timeout_secs
+    }
+
+    /// Subscribe to approved requests whose waiting execution was lost.
+    ///
+    /// Each received request carries [`ApprovalRequest::parked`] input that
+    /// can be used to start the execution again.
+    pub fn subscribe_resumed(&self) -> broadcast::Receiver<ApprovalRequest> {
+        self.resumed_tx.subscribe()
+    }
+
     /// Create a new approval request
     pub async fn create_request(
         &self,
@@ -63,8 +185,8 @@ impl ApprovalManager {
             self.default_timeout_secs,
         );
 
-        let mut requests = self.requests.write().await;
-        requests.insert(request.id, request.clone());
+        let request = self.prepare(request).await;
+        self.insert(&request).await;
 
         request
     }
@@ -94,11 +216,27 @@ impl ApprovalManager {
             self.default_timeout_secs,
         );
 
+        self.submit(request, event_bus).await
+    }
+
+    /// Register a request and return it with a receiver for its decision.
+    ///
+    /// Use this instead of [`Self::create_request_async`] when the request needs
+    /// tool details or a [`super::ParkedExecution`] attached. Approval rules
+    /// are applied here; a request inside an auto-approve window comes back
+    /// already approved.
+    pub async fn submit(
+        &self,
+        request: ApprovalRequest,
+        event_bus: Option<&EventBus>,
+    ) -> (ApprovalRequest, oneshot::Receiver<ApprovalStatus>) {
         let (tx, rx) = oneshot::channel();
 
-        {
-            let mut requests = self.requests.write().await;
-            requests.insert(request.id, request.clone());
+        let request = self.prepare(request).await;
+        self.insert(&request).await;
+        if request.status == ApprovalStatus::Approved {
+            let _ = tx.send(request.status);
+            return (request, rx);
         }
         {
             let mut resolvers = self.resolvers.write().await;
@@ -108,7 +246,7 @@ impl ApprovalManager {
         // Publish ApprovalRequired event
         if let Some(bus) = event_bus {
             bus.publish(OrchestratorEvent::ApprovalRequired {
-                execution_id,
+                execution_id: request.execution_id,
                 request_id: request.id,
             });
         }
@@ -140,8 +278,9 @@ impl ApprovalManager {
             return Err(ApprovalError::InvalidNonce);
         }
 
-        // Check 2: Ownership (original user or Admin)
-        if request.user_id != responder.user_id && !responder.has_scope(&crate::auth::Scope::Admin)
+        // Check 2: Ownership (allowed approver or Admin)
+        if !request.can_respond(&responder.user_id)
+            && !responder.has_scope(&crate::auth::Scope::Admin)
         {
             return Err(ApprovalError::Unauthorized);
         }
@@ -151,20 +290,21 @@ impl ApprovalManager {
             return Err(ApprovalError::Expired);
         }
 
-        // Apply the decision
-        request.status = decision;
-        request.responder_id = Some(responder.user_id.clone());
-        request.responded_at = Some(Utc::now());
+        // Apply the decision (approvals may need more than one vote)
+        if decision == ApprovalStatus::Approved {
+            if !request.record_approval(&responder.user_id) {
+                return Err(ApprovalError::AlreadyApproved);
+            }
+        } else {
+            request.status = decision;
+            request.responder_id = Some(responder.user_id.clone());
+            request.responded_at = Some(Utc::now());
+        }
 
         let resolved = request.clone();
         drop(requests);
 
-        // Notify the waiter via oneshot
-        let mut resolvers = self.resolvers.write().await;
-        if let Some(tx) = resolvers.remove(&request_id) {
-            let _ = tx.send(decision);
-        }
-
+        self.finish(&resolved).await;
         Ok(resolved)
     }
 
@@ -186,41 +326,57 @@ impl ApprovalManager {
     }
 
     /// Get a request by ID
+    ///
+    /// Falls back to the database for requests no longer held in memory.
     pub async fn get(&self, id: Uuid) -> Option<ApprovalRequest> {
-        let requests = self.requests.read().await;
-        requests.get(&id).cloned()
+        {
+            let requests = self.requests.read().await;
+            if let Some(request) = requests.get(&id) {
+                return Some(request.clone());
+            }
+        }
+        let store = self.store.as_ref()?;
+        match store.get(id).await {
+            Ok(request) => request,
+            Err(e) => {
+                warn!(error = %e, "Failed to load approval request from SQLite");
+                None
+            }
+        }
     }
 
     /// Approve a request with responder verification
     ///
-    /// Returns Some(request) if approved, None if not found or not authorized
+    /// Returns Some(request) if the approval was recorded (the request may
+    /// still be pending if more approvers are required), None if not found,
+    /// not authorized, or already approved by this responder
     pub async fn approve_by(&self, id: Uuid, responder_id: &str) -> Option<ApprovalRequest> {
-        let mut requests = self.requests.write().await;
-        if let Some(request) = requests.get_mut(&id) {
-            if request.approve_by(responder_id) {
-                Some(request.clone())
-            } else {
-                None // Not authorized or not pending
+        let resolved = {
+            let mut requests = self.requests.write().await;
+            let request = requests.get_mut(&id)?;
+            if !request.approve_by(responder_id) {
+                return None; // Not authorized or not pending
             }
-        } else {
-            None
-        }
+            request.clone()
+        };
+        self.finish(&resolved).await;
+        Some(resolved)
     }
 
     /// Reject a request with responder verification
     ///
     /// Returns Some(request) if rejected, None if not found or not authorized
     pub async fn reject_by(&self, id: Uuid, responder_id: &str) -> Option<ApprovalRequest> {
-        let mut requests = self.requests.write().await;
-        if let Some(request) = requests.get_mut(&id) {
-            if request.reject_by(responder_id) {
-                Some(request.clone())
-            } else {
-                None // Not authorized or not pending
+        let resolved = {
+            let mut requests = self.requests.write().await;
+            let request = requests.get_mut(&id)?;
+            if !request.reject_by(responder_id) {
+                return None; // Not authorized or not pending
             }
-        } else {
-            None
-        }
+            request.clone()
+        };
+        self.finish(&resolved).await;
+        Some(resolved)
     }
 
     /// Approve a request (deprecated - use approve_by for security)
@@ -259,6 +415,21 @@ impl ApprovalManager {
             .collect()
     }
 
+    /// Get all pending requests a user is allowed to answer
+    ///
+    /// Unlike [`Self::pending_for_user`], this includes requests raised by
+    /// other users where `user_id` is one of the designated approvers.
+    pub async fn pending_for_approver(&self, user_id: &str) -> Vec<ApprovalRequest> {
+        let requests = self.requests.read().await;
+        let mut pending: Vec<_> = requests
+            .values()
+            .filter(|r| r.is_pending() && r.can_respond(user_id))
+            .cloned()
+            .collect();
+        pending.sort_by_key(|r| r.created_at);
+        pending
+    }
+
     /// Get all pending requests for an execution
     pub async fn pending_for_execution(&self, execution_id: Uuid) -> Vec<ApprovalRequest> {
         let requests = self.requests.read().await;
@@ -269,23 +440,98 @@ impl ApprovalManager {
             .collect()
     }
 
+    /// Get all pending requests (admin view)
+    pub async fn pending_all(&self) -> Vec<ApprovalRequest> {
+        let requests = self.requests.read().await;
+        let mut pending: Vec<_> = requests
+            .values()
+            .filter(|r| r.is_pending())
+            .cloned()
+            .collect();
+        pending.sort_by_key(|r| r.created_at);
+        pending
+    }
+
+    /// Consume the grant left by a resumed approval.
+    ///
+    /// Returns true if request `request_id` approved exactly this call (same
+    /// user, tool and arguments) for a parked execution that is now being
+    /// resumed, so it must not be asked again. A grant is used at most once.
+    pub async fn take_grant(
+        &self,
+        request_id: Uuid,
+        user_id: &str,
+        tool_name: &str,
+        args: &serde_json::Value,
+    ) -> bool {
+        let mut grants = self.grants.write().await;
+        let now = Utc::now();
+        grants.retain(|_, grant| grant.expires_at > now);
+        let matches = grants.get(&request_id).is_some_and(|grant| {
+            grant.user_id == user_id
+                && grant.tool_name == tool_name
+                && grant.args_hash == args_hash(args)
+        });
+        if matches {
+            grants.remove(&request_id);
+        }
+        matches
+    }
+
     /// Clean up expired requests
     pub async fn cleanup_expired(&self) -> usize {
         let mut requests = self.requests.write().await;
         let initial_count = requests.len();
 
         // Mark expired requests
+        let mut expired = Vec::new();
         for request in requests.values_mut() {
             if request.status == ApprovalStatus::Pending && request.is_expired() {
                 request.expire();
+                expired.push(request.clone());
             }
         }
 
         // Remove old requests (older than 1 hour regardless of status)
         let cutoff = Utc::now() - Duration::hours(1);
         requests.retain(|_, r| r.created_at > cutoff);
+        let removed = initial_count - requests.len();
+        drop(requests);
 
-        initial_count - requests.len()
+        if let Some(ref store) = self.store {
+            for request in &expired {
+                if let Err(e) = store.update_resolution(request).await {
+                    warn!(error = %e, "Failed to persist expired approval request");
+                }
+            }
+        }
+        {
+            let mut resolvers = self.resolvers.write().await;
+            for request in &expired {
+                if let Some(tx) = resolvers.remove(&request.id) {
+                    let _ = tx.send(request.status);
+                }
+            }
+        }
+
+        removed
+    }
+
+    /// Delete resolved requests older than `days` from the database
+    pub async fn prune_history(&self, days: i64) -> u64 {
+        let Some(ref store) = self.store else {
+            return 0;
+        };
+        match store
+            .delete_resolved_before(Utc::now() - Duration::days(days))
+            .await
+        {
+            Ok(count) => count,
+            Err(e) => {
+                warn!(error = %e, "Failed to prune approval history");
+                0
+            }
+        }
     }
 
     /// Wait for a request to be resolved
@@ -301,11 +547,14 @@ impl ApprovalManager {
                 ApprovalStatus::Pending => {
                     if request.is_expired() {
                         // Mark as expired and return
-                        let mut requests = self.requests.write().await;
-                        if let Some(r) = requests.get_mut(&id) {
+                        let expired = {
+                            let mut requests = self.requests.write().await;
+                            let r = requests.get_mut(&id)?;
                             r.expire();
-                            return Some(r.clone());
-                        }
+                            r.clone()
+                        };
+                        self.finish(&expired).await;
+                        return Some(expired);
                     }
                     tokio::time::sleep(tokio::time::Duration::from_millis(poll_interval_ms)).await;
                 }
@@ -313,4 +562,151 @@ impl ApprovalManager {
             }
         }
     }
+
+    /// Store a new request in memory and the database
+    async fn insert(&self, request: &ApprovalRequest) {
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.insert(request).await {
+                warn!(error = %e, "Failed to persist approval request to SQLite");
+            }
+        }
+        let mut requests = self.requests.write().await;
+        requests.insert(request.id, request.clone());
+    }
+
+    /// Apply the matching approval rule to a new request.
+    async fn prepare(&self, mut request: ApprovalRequest) -> ApprovalRequest {
+        let Some(ref tool_name) = request.tool_name else {
+            return request;
+        };
+        let Some((key, rule)) = self.window_key(&request) else {
+            return request;
+        };
+
+        request.required_approvals = rule.required_approvals.max(1);
+        request.allowed_approvers = self
+            .policy
+            .resolve_approvers(&rule.approvers, request.node_owner.as_deref());
+
+        if rule.auto_approve_window_secs > 0 {
+            let mut windows = self.auto_approvals.write().await;
+            let now = Utc::now();
+            windows.retain(|_, until| *until > now);
+            if windows.contains_key(&key) {
+                debug!(
+                    user = %request.user_id,
+                    tool = %tool_name,
+                    "Auto-approved within approval window"
+                );
+                request.status = ApprovalStatus::Approved;
+                request.responder_id = Some(AUTO_APPROVER.to_string());
+                request.responded_at = Some(now);
+            }
+        }
+
+        request
+    }
+
+    /// Persist a decision and, once the request is final, hand it to whoever
+    /// is waiting.
+    ///
+    /// If nobody is waiting (the execution was lost to a restart) and the
+    /// request was approved with parked input, a grant is recorded and the
+    /// request is published for resumption.
+    async fn finish(&self, resolved: &ApprovalRequest) {
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.update_resolution(resolved).await {
+                warn!(error = %e, "Failed to persist approval decision to SQLite");
+            }
+        }
+
+        // Partial approval: wait for the remaining approvers
+        if resolved.status == ApprovalStatus::Pending {
+            debug!(
+                request_id = %resolved.id,
+                remaining = resolved.approvals_remaining(),
+                "Approval recorded, waiting for more approvers"
+            );
+            return;
+        }
+
+        if resolved.status == ApprovalStatus::Approved {
+            self.open_auto_approve_window(resolved).await;
+        }
+
+        let waiter = {
+            let mut resolvers = self.resolvers.write().await;
+            resolvers.remove(&resolved.id)
+        };
+        if let Some(tx) = waiter {
+            if tx.send(resolved.status).is_ok() {
+                return;
+            }
+        }
+
+        if resolved.status != ApprovalStatus::Approved || resolved.parked.is_none() {
+            return;
+        }
+        if let Some(ref tool_name) = resolved.tool_name {
+            let mut grants = self.grants.write().await;
+            grants.insert(
+                resolved.id,
+                Grant {
+                    user_id: resolved.user_id.clone(),
+                    tool_name: tool_name.clone(),
+                    args_hash: args_hash(
+                        resolved
+                            .tool_args
+                            .as_ref()
+                            .unwrap_or(&serde_json::Value::Null),
+                    ),
+                    expires_at: Utc::now() + Duration::seconds(GRANT_TTL_SECS),
+                },
+            );
+        }
+        info!(
+            request_id = %resolved.id,
+            execution_id = %resolved.execution_id,
+            "Approved request has no live execution, resuming parked input"
+        );
+        let _ = self.resumed_tx.send(resolved.clone());
+    }
+
+    /// Start an auto-approve window after an approval, if its rule has one.
+    async fn open_auto_approve_window(&self, approved: &ApprovalRequest) {
+        // Auto-approvals don't extend the window they came from
+        if approved.responder_id.as_deref() == Some(AUTO_APPROVER) {
+            return;
+        }
+        let Some((key, rule)) = self.window_key(approved) else {
+            return;
+        };
+        if rule.auto_approve_window_secs == 0 {
+            return;
+        }
+        let mut windows = self.auto_approvals.write().await;
+        windows.insert(
+            key,
+            Utc::now() + Duration::seconds(rule.auto_approve_window_secs as i64),
+        );
+    }
+
+    /// The rule a tool request falls under, and the window it would use
+    fn window_key(&self, request: &ApprovalRequest) -> Option<(WindowKey, &ApprovalRule)> {
+        let tool_name = request.tool_name.as_ref()?;
+        let args = request.tool_args.clone().unwrap_or(serde_json::Value::Null);
+        let (index, rule) = self
+            .policy
+            .rules
+            .iter()
+            .enumerate()
+            .find(|(_, rule)| rule.matches(tool_name, &args))?;
+        let key = WindowKey {
+            user_id: request.user_id.clone(),
+            rule: index,
+            tool_name: tool_name.clone(),
+            args_hash: args_hash(&args),
+        };
+        Some((key, rule))
+    }
 }
@@ -3,19 +3,25 @@
 //! This module provides the approval system for high-risk operations.
 //! When a tool or action requires user confirmation, this system
 //! handles the approval workflow.
+//!
+//! Requests can be persisted to SQLite so that pending approvals, and the
+//! executions parked on them, survive a server restart.
 
 use std::sync::Arc;
 
 /// Approval manager implementation and request lifecycle.
 pub mod manager;
+/// SQLite persistence for approval requests.
+pub mod store;
 /// Approval callback trait for channel-specific approval UIs.
 pub mod traits;
 /// Approval request/response types and error definitions.
 pub mod types;
 
-pub use manager::ApprovalManager;
+pub use manager::{ApprovalManager, AUTO_APPROVER};
+pub use store::ApprovalStore;
 pub use traits::ApprovalCallback;
-pub use types::{ApprovalError, ApprovalRequest, ApprovalStatus};
+pub use types::{ApprovalError, ApprovalRequest, ApprovalStatus, ParkedExecution};
 
 /// Shared approval manager type
 pub type SharedApprovalManager = Arc<ApprovalManager>;
@@ -0,0 +1,194 @@
+//! SQLite persistence for approval requests
+//!
+//! Every request is written when it is created and updated when it is
+//! resolved or expires, so pending approvals (and the responder audit trail)
+//! survive a server restart.
+
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Row, Sqlite};
+use uuid::Uuid;
+
+use super::types::{ApprovalRequest, ApprovalStatus};
+
+/// SQLite-backed approval request store
+#[derive(Clone)]
+pub struct ApprovalStore {
+    pool: Pool<Sqlite>,
+}
+
+impl ApprovalStore {
+    /// Create the store, creating the table if needed
+    pub async fn new(pool: Pool<Sqlite>) -> Result<Self, sqlx::Error> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS approval_requests (
+                id TEXT PRIMARY KEY,
+                nonce TEXT NOT NULL,
+                execution_id TEXT NOT NULL,
+                channel_type TEXT NOT NULL,
+                channel_id TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                responder_id TEXT,
+                action TEXT NOT NULL,
+                tool_name TEXT,
+                tool_args TEXT,
+                risk_description TEXT NOT NULL,
+                status TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                expires_at TEXT NOT NULL,
+                responded_at TEXT,
+                parked TEXT,
+                required_approvals INTEGER NOT NULL DEFAULT 1,
+                approved_by TEXT NOT NULL DEFAULT '[]',
+                allowed_approvers TEXT,
+                node_owner TEXT
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_approval_requests_status
+             ON approval_requests(status, user_id)",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+
+    /// Insert a new request
+    pub async fn insert(&self, request: &ApprovalRequest) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO approval_requests (
+                id, nonce, execution_id, channel_type, channel_id, user_id, responder_id,
+                action, tool_name, tool_args, risk_description, status, created_at,
+                expires_at, responded_at, parked, required_approvals, approved_by,
+                allowed_approvers, node_owner
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(request.id.to_string())
+        .bind(request.nonce.to_string())
+        .bind(request.execution_id.to_string())
+        .bind(&request.channel_type)
+        .bind(&request.channel_id)
+        .bind(&request.user_id)
+        .bind(&request.responder_id)
+        .bind(&request.action)
+        .bind(&request.tool_name)
+        .bind(request.tool_args.as_ref().map(|v| v.to_string()))
+        .bind(&request.risk_description)
+        .bind(request.status.as_str())
+        .bind(request.created_at.to_rfc3339())
+        .bind(request.expires_at.to_rfc3339())
+        .bind(request.responded_at.map(|t| t.to_rfc3339()))
+        .bind(
+            request
+                .parked
+                .as_ref()
+                .and_then(|p| serde_json::to_string(p).ok()),
+        )
+        .bind(request.required_approvals as i64)
+        .bind(to_json(&request.approved_by))
+        .bind(request.allowed_approvers.as_deref().map(to_json))
+        .bind(&request.node_owner)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the outcome of a request (status, approvals and responder audit)
+    pub async fn update_resolution(&self, request: &ApprovalRequest) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE approval_requests
+             SET status = ?, responder_id = ?, responded_at = ?, approved_by = ?
+             WHERE id = ?",
+        )
+        .bind(request.status.as_str())
+        .bind(&request.responder_id)
+        .bind(request.responded_at.map(|t| t.to_rfc3339()))
+        .bind(to_json(&request.approved_by))
+        .bind(request.id.to_string())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Load every request still marked pending (including ones past expiry)
+    pub async fn load_pending(&self) -> Result<Vec<ApprovalRequest>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM approval_requests WHERE status = 'pending' ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().filter_map(row_to_request).collect())
+    }
+
+    /// Get a request by ID, regardless of status
+    pub async fn get(&self, id: Uuid) -> Result<Option<ApprovalRequest>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM approval_requests WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().and_then(row_to_request))
+    }
+
+    /// Delete resolved requests created before the cutoff
+    pub async fn delete_resolved_before(&self, cutoff: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM approval_requests WHERE status != 'pending' AND created_at < ?",
+        )
+        .bind(cutoff.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+fn to_json(users: &[String]) -> String {
+    serde_json::to_string(users).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn row_to_request(row: &sqlx::sqlite::SqliteRow) -> Option<ApprovalRequest> {
+    let id: String = row.try_get("id").ok()?;
+    let nonce: String = row.try_get("nonce").ok()?;
+    let execution_id: String = row.try_get("execution_id").ok()?;
+    let status: String = row.try_get("status").ok()?;
+    let created_at: String = row.try_get("created_at").ok()?;
+    let expires_at: String = row.try_get("expires_at").ok()?;
+    let responded_at: Option<String> = row.try_get("responded_at").ok()?;
+    let tool_args: Option<String> = row.try_get("tool_args").ok()?;
+    let parked: Option<String> = row.try_get("parked").ok()?;
+    let required_approvals: i64 = row.try_get("required_approvals").ok()?;
+    let approved_by: String = row.try_get("approved_by").ok()?;
+    let allowed_approvers: Option<String> = row.try_get("allowed_approvers").ok()?;
+
+    Some(ApprovalRequest {
+        id: Uuid::parse_str(&id).ok()?,
+        nonce: Uuid::parse_str(&nonce).ok()?,
+        execution_id: Uuid::parse_str(&execution_id).ok()?,
+        channel_type: row.try_get("channel_type").ok()?,
+        channel_id: row.try_get("channel_id").ok()?,
+        user_id: row.try_get("user_id").ok()?,
+        responder_id: row.try_get("responder_id").ok()?,
+        action: row.try_get("action").ok()?,
+        tool_name: row.try_get("tool_name").ok()?,
+        tool_args: tool_args.and_then(|s| serde_json::from_str(&s).ok()),
+        risk_description: row.try_get("risk_description").ok()?,
+        status: ApprovalStatus::parse(&status)?,
+        created_at: parse_time(&created_at)?,
+        expires_at: parse_time(&expires_at)?,
+        responded_at: responded_at.as_deref().and_then(parse_time),
+        parked: parked.and_then(|s| serde_json::from_str(&s).ok()),
+        required_approvals: required_approvals.max(1) as u32,
+        approved_by: serde_json::from_str(&approved_by).unwrap_or_default(),
+        allowed_approvers: allowed_approvers.and_then(|s| serde_json::from_str(&s).ok()),
+        node_owner: row.try_get("node_owner").ok()?,
+    })
+}
@@ -371,3 +371,250 @@ async fn test_approval_manager_cleanup_expired() {
     assert_eq!(expired.status, ApprovalStatus::Rejected);
     assert!(expired.is_denied());
 }
+
+#[tokio::test]
+async fn test_pending_request_survives_restart() {
+    let pool = memory_pool().await;
+
+    let request = {
+        let manager = ApprovalManager::new_with_db(pool.clone(), 300).await.unwrap();
+        let request = ApprovalRequest::new(
+            Uuid::new_v4(),
+            "telegram",
+            "123",
+            "456",
+            "Run tool 'exec'",
+            "Risky",
+            60,
+        )
+        .with_tool("exec", serde_json::json!({"command": "ls"}));
+        let _rx = manager.submit(request.clone(), None).await;
+        request
+    };
+
+    // A fresh manager on the same database sees the pending request
+    let manager = ApprovalManager::new_with_db(pool, 300).await.unwrap();
+    let pending = manager.pending_for_user("456").await;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, request.id);
+    assert_eq!(pending[0].nonce, request.nonce);
+    assert_eq!(pending[0].tool_name.as_deref(), Some("exec"));
+
+    // Responder audit is persisted
+    manager.reject_by(request.id, "456").await.unwrap();
+    let stored = manager.get(request.id).await.unwrap();
+    assert_eq!(stored.status, ApprovalStatus::Rejected);
+    assert_eq!(stored.responder_id.as_deref(), Some("456"));
+}
+
+#[tokio::test]
+async fn test_db_manager_uses_configured_timeout() {
+    let manager = ApprovalManager::new_with_db(memory_pool().await, 42)
+        .await
+        .unwrap();
+    assert_eq!(manager.default_timeout_secs(), 42);
+
+    let request = manager
+        .create_request(Uuid::new_v4(), "web", "c1", "u1", "Run tool", "risky")
+        .await;
+    assert_eq!(request.expires_at - request.created_at, chrono::Duration::seconds(42));
+}
+
+#[tokio::test]
+async fn test_approved_orphan_is_resumed_with_grant() {
+    use super::ParkedExecution;
+
+    let pool = memory_pool().await;
+    let parked = ParkedExecution {
+        channel_type: "telegram".to_string(),
+        channel_id: "123".to_string(),
+        user_id: "456".to_string(),
+        thread_id: None,
+        text: "clean up /tmp".to_string(),
+        history: vec![cratos_llm::Message::user("clean up /tmp")],
+        tool_results: vec![serde_json::json!({"files": 3})],
+    };
+
+    let request = {
+        let manager = ApprovalManager::new_with_db(pool.clone(), 300).await.unwrap();
+        let request = ApprovalRequest::new(
+            Uuid::new_v4(),
+            "telegram",
+            "123",
+            "456",
+            "Run tool 'exec'",
+            "Risky",
+            60,
+        )
+        .with_tool("exec", serde_json::json!({"command": "rm -rf /tmp/cache"}))
+        .with_parked(parked.clone());
+        let _rx = manager.submit(request.clone(), None).await;
+        request
+    };
+
+    // After a restart nobody is waiting on the request
+    let manager = ApprovalManager::new_with_db(pool, 300).await.unwrap();
+    let mut resumed = manager.subscribe_resumed();
+    manager.approve_by(request.id, "456").await.unwrap();
+
+    let resumed = resumed.try_recv().unwrap();
+    assert_eq!(resumed.id, request.id);
+    let resumed_parked = resumed.parked.unwrap();
+    assert_eq!(resumed_parked.text, parked.text);
+    assert_eq!(resumed_parked.history.len(), 1);
+    assert_eq!(resumed_parked.tool_results, parked.tool_results);
+
+    // The grant covers only the approved call, and only once
+    let args = serde_json::json!({"command": "rm -rf /tmp/cache"});
+    let other = serde_json::json!({"command": "rm -rf /"});
+    assert!(
+        !manager
+            .take_grant(Uuid::new_v4(), "456", "exec", &args)
+            .await
+    );
+    assert!(!manager.take_grant(request.id, "456", "exec", &other).await);
+    assert!(!manager.take_grant(request.id, "789", "exec", &args).await);
+    assert!(manager.take_grant(request.id, "456", "exec", &args).await);
+    assert!(!manager.take_grant(request.id, "456", "exec", &args).await);
+}
+
+#[tokio::test]
+async fn test_live_waiter_is_not_resumed() {
+    let manager = ApprovalManager::new();
+    let mut resumed = manager.subscribe_resumed();
+
+    let request = ApprovalRequest::new(Uuid::new_v4(), "telegram", "123", "456", "a", "r", 60)
+        .with_tool("exec", serde_json::json!({}));
+    let (_, rx) = manager.submit(request.clone(), None).await;
+    manager.approve_by(request.id, "456").await.unwrap();
+
+    assert_eq!(rx.await.unwrap(), ApprovalStatus::Approved);
+    assert!(resumed.try_recv().is_err());
+    assert!(
+        !manager
+            .take_grant(request.id, "456", "exec", &serde_json::json!({}))
+            .await
+    );
+}
+
+#[tokio::test]
+async fn test_group_rule_needs_two_distinct_approvers() {
+    use crate::tool_policy::{ApprovalPolicy, ApprovalRule, ApproverSpec};
+
+    let policy = ApprovalPolicy::new()
+        .with_group("admins", vec!["alice".to_string(), "bob".to_string()])
+        .with_rule(
+            ApprovalRule::new("git_push")
+                .with_arg("branch", "main")
+                .with_required_approvals(2)
+                .with_approvers(ApproverSpec::Group {
+                    group: "admins".to_string(),
+                }),
+        );
+    let manager = ApprovalManager::new().with_policy(policy);
+
+    let request = ApprovalRequest::new(Uuid::new_v4(), "slack", "c1", "dev", "push", "r", 60)
+        .with_tool("git_push", serde_json::json!({"branch": "main"}));
+    let (request, mut rx) = manager.submit(request, None).await;
+    assert_eq!(request.required_approvals, 2);
+
+    // The requester is not in the approver group
+    assert!(manager.approve_by(request.id, "dev").await.is_none());
+
+    let partial = manager.approve_by(request.id, "alice").await.unwrap();
+    assert_eq!(partial.status, ApprovalStatus::Pending);
+    assert_eq!(partial.approvals_remaining(), 1);
+    assert!(rx.try_recv().is_err());
+
+    // The same approver cannot count twice
+    assert!(manager.approve_by(request.id, "alice").await.is_none());
+
+    let done = manager.approve_by(request.id, "bob").await.unwrap();
+    assert_eq!(done.status, ApprovalStatus::Approved);
+    assert_eq!(done.approved_by, vec!["alice".to_string(), "bob".to_string()]);
+    assert_eq!(rx.await.unwrap(), ApprovalStatus::Approved);
+}
+
+#[tokio::test]
+async fn test_pending_for_approver_includes_designated_approvers() {
+    use crate::tool_policy::{ApprovalPolicy, ApprovalRule, ApproverSpec};
+
+    let manager = ApprovalManager::new().with_policy(ApprovalPolicy::new().with_rule(
+        ApprovalRule::new("exec").with_approvers(ApproverSpec::Users {
+            users: vec!["alice".to_string()],
+        }),
+    ));
+    let request = ApprovalRequest::new(Uuid::new_v4(), "web", "c1", "dev", "exec", "r", 60)
+        .with_tool("exec", serde_json::json!({}));
+    let (request, _rx) = manager.submit(request, None).await;
+
+    let for_alice = manager.pending_for_approver("alice").await;
+    assert_eq!(for_alice.len(), 1);
+    assert_eq!(for_alice[0].id, request.id);
+    assert!(manager.pending_for_approver("mallory").await.is_empty());
+    // The requester is not an approver under this rule
+    assert!(manager.pending_for_approver("dev").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_node_owner_rule() {
+    use crate::tool_policy::{ApprovalPolicy, ApprovalRule, ApproverSpec};
+
+    let manager = ApprovalManager::new().with_policy(
+        ApprovalPolicy::new()
+            .with_rule(ApprovalRule::new("exec").with_approvers(ApproverSpec::NodeOwner)),
+    );
+
+    let request = ApprovalRequest::new(Uuid::new_v4(), "web", "c1", "dev", "exec", "r", 60)
+        .with_tool("exec", serde_json::json!({"command": "ls"}))
+        .with_node_owner("ops");
+    let (request, _rx) = manager.submit(request, None).await;
+
+    assert!(manager.approve_by(request.id, "dev").await.is_none());
+    let approved = manager.approve_by(request.id, "ops").await.unwrap();
+    assert_eq!(approved.status, ApprovalStatus::Approved);
+}
+
+#[tokio::test]
+async fn test_auto_approve_window_after_first_approval() {
+    use crate::tool_policy::{ApprovalPolicy, ApprovalRule};
+
+    let manager = ApprovalManager::new().with_policy(
+        ApprovalPolicy::new().with_rule(ApprovalRule::new("exec").with_auto_approve_window(600)),
+    );
+    let new_request = || {
+        ApprovalRequest::new(Uuid::new_v4(), "telegram", "123", "456", "exec", "r", 60)
+            .with_tool("exec", serde_json::json!({}))
+    };
+
+    let (first, _rx) = manager.submit(new_request(), None).await;
+    assert_eq!(first.status, ApprovalStatus::Pending);
+    manager.approve_by(first.id, "456").await.unwrap();
+
+    let (second, rx) = manager.submit(new_request(), None).await;
+    assert_eq!(second.status, ApprovalStatus::Approved);
+    assert_eq!(second.responder_id.as_deref(), Some(AUTO_APPROVER));
+    assert_eq!(rx.await.unwrap(), ApprovalStatus::Approved);
+
+    // Nor is the same tool with other arguments
+    let other_args =
+        ApprovalRequest::new(Uuid::new_v4(), "telegram", "123", "456", "exec", "r", 60)
+            .with_tool("exec", serde_json::json!({"command": "rm -rf /"}));
+    let (other_args, _rx) = manager.submit(other_args, None).await;
+    assert_eq!(other_args.status, ApprovalStatus::Pending);
+
+    // Other users are not covered by the window
+    let other = ApprovalRequest::new(Uuid::new_v4(), "telegram", "123", "789", "exec", "r", 60)
+        .with_tool("exec", serde_json::json!({}));
+    let (other, _rx) = manager.submit(other, None).await;
+    assert_eq!(other.status, ApprovalStatus::Pending);
+}
+
+/// Single connection so every manager sees the same in-memory database
+async fn memory_pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
@@ -16,6 +16,56 @@ pub enum ApprovalStatus {
     Expired,
 }
 
+impl ApprovalStatus {
+    /// Storage representation
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Expired => "expired",
+        }
+    }
+
+    /// Parse the storage representation
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "approved" => Some(Self::Approved),
+            "rejected" => Some(Self::Rejected),
+            "expired" => Some(Self::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// State of an execution that was waiting on an approval.
+///
+/// Persisted with the request so an execution lost to a restart can be
+/// continued once the approval is granted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParkedExecution {
+    /// Channel type
+    pub channel_type: String,
+    /// Channel ID
+    pub channel_id: String,
+    /// User ID
+    pub user_id: String,
+    /// Thread ID (if any)
+    pub thread_id: Option<String>,
+    /// Original user input
+    pub text: String,
+    /// Conversation up to the assistant message whose tool calls were
+    /// waiting (empty for requests parked before this was recorded)
+    #[serde(default)]
+    pub history: Vec<cratos_llm::Message>,
+    /// Results of the calls in that round that finished before the parked one
+    #[serde(default)]
+    pub tool_results: Vec<serde_json::Value>,
+}
+
 /// An approval request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApprovalRequest {
@@ -49,6 +99,25 @@ pub struct ApprovalRequest {
     pub expires_at: DateTime<Utc>,
     /// When the user responded (if they did)
     pub responded_at: Option<DateTime<Utc>>,
+    /// Execution input to resume from after a restart
+    #[serde(default)]
+    pub parked: Option<ParkedExecution>,
+    /// Number of distinct approvals needed
+    #[serde(default = "default_required_approvals")]
+    pub required_approvals: u32,
+    /// Users who have approved so far
+    #[serde(default)]
+    pub approved_by: Vec<String>,
+    /// Users allowed to respond (`None` = only the requester)
+    #[serde(default)]
+    pub allowed_approvers: Option<Vec<String>>,
+    /// Owner of the node the tool runs on (for node-owner approval rules)
+    #[serde(default)]
+    pub node_owner: Option<String>,
+}
+
+fn default_required_approvals() -> u32 {
+    1
 }
 
 impl ApprovalRequest {
@@ -80,6 +149,11 @@ impl ApprovalRequest {
             created_at: now,
             expires_at: now + Duration::seconds(timeout_secs),
             responded_at: None,
+            parked: None,
+            required_approvals: 1,
+            approved_by: Vec::new(),
+            allowed_approvers: None,
+            node_owner: None,
         }
     }
 
@@ -91,6 +165,20 @@ impl ApprovalRequest {
         self
     }
 
+    /// Attach the execution input so the request can be resumed after a restart
+    #[must_use]
+    pub fn with_parked(mut self, parked: ParkedExecution) -> Self {
+        self.parked = Some(parked);
+        self
+    }
+
+    /// Set the owner of the node the tool runs on
+    #[must_use]
+    pub fn with_node_owner(mut self, owner: impl Into<String>) -> Self {
+        self.node_owner = Some(owner.into());
+        self
+    }
+
     /// Check if the request has expired
     #[must_use]
     pub fn is_expired(&self) -> bool {
@@ -105,17 +193,30 @@ impl ApprovalRequest {
 
     /// Check if a user is authorized to respond to this request
     ///
-    /// By default, only the original requester can approve/reject.
-    /// Override this for multi-user approval workflows.
+    /// By default, only the original requester can approve/reject. Approval
+    /// rules may replace this with a list of allowed approvers.
     #[must_use]
     pub fn can_respond(&self, responder_user_id: &str) -> bool {
-        // SECURITY: Only the original user can approve their own requests
-        self.user_id == responder_user_id
+        match self.allowed_approvers {
+            Some(ref approvers) => approvers.iter().any(|a| a == responder_user_id),
+            // SECURITY: Only the original user can approve their own requests
+            None => self.user_id == responder_user_id,
+        }
+    }
+
+    /// Number of further approvals needed
+    #[must_use]
+    pub fn approvals_remaining(&self) -> u32 {
+        self.required_approvals
+            .saturating_sub(self.approved_by.len() as u32)
     }
 
     /// Approve the request with responder verification
     ///
-    /// Returns true if approved, false if not authorized or not pending
+    /// With multi-approver rules, each call records one vote and the request
+    /// only becomes `Approved` once enough distinct users have approved.
+    /// Returns true if the vote was accepted, false if not authorized, not
+    /// pending, or the responder already approved.
     pub fn approve_by(&mut self, responder_id: &str) -> bool {
         if !self.is_pending() {
             return false;
@@ -125,14 +226,28 @@ impl ApprovalRequest {
             return false;
         }
 
-        self.status = ApprovalStatus::Approved;
+        self.record_approval(responder_id)
+    }
+
+    /// Record an approval vote without checking who the responder is.
+    ///
+    /// Callers must have authorized the responder (e.g. an Admin override).
+    pub(crate) fn record_approval(&mut self, responder_id: &str) -> bool {
+        if self.approved_by.iter().any(|a| a == responder_id) {
+            return false;
+        }
+        self.approved_by.push(responder_id.to_string());
         self.responder_id = Some(responder_id.to_string());
-        self.responded_at = Some(Utc::now());
+        if self.approvals_remaining() == 0 {
+            self.status = ApprovalStatus::Approved;
+            self.responded_at = Some(Utc::now());
+        }
         true
     }
 
     /// Reject the request with responder verification
     ///
+    /// A single rejection from any allowed approver rejects the request.
     /// Returns true if rejected, false if not authorized or not pending
     pub fn reject_by(&mut self, responder_id: &str) -> bool {
         if !self.is_pending() {
@@ -198,6 +313,8 @@ pub enum ApprovalError {
     InvalidNonce,
     /// Responder not authorized
     Unauthorized,
+    /// Responder already approved this request
+    AlreadyApproved,
     /// Request already resolved or expired
     Expired,
 }
@@ -208,6 +325,7 @@ impl std::fmt::Display for ApprovalError {
             Self::NotFound => write!(f, "approval request not found"),
             Self::InvalidNonce => write!(f, "invalid nonce (possible replay)"),
             Self::Unauthorized => write!(f, "unauthorized responder"),
+            Self::AlreadyApproved => write!(f, "responder already approved this request"),
             Self::Expired => write!(f, "approval request expired"),
         }
     }
@@ -3,21 +3,28 @@
 //! Provides:
 //! - API key and Bearer token authentication
 //! - Scope-based authorization
-//! - Token generation, validation, and revocation
+//! - Token generation, validation, rotation, and revocation
+//! - Optional SQLite persistence of (hashed) API keys
+//! - Bearer JWTs verified by external providers (OIDC)
 //! - Constant-time token comparison
 
 #![forbid(unsafe_code)]
 
 use crate::credentials::SecureString;
+use crate::external_auth::ExternalAuthRegistry;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use subtle::ConstantTimeEq;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+mod store;
+
+use store::ApiKeyStore;
+
 // ============================================================================
 // Error Types
 // ============================================================================
@@ -37,6 +44,10 @@ pub enum AuthError {
     #[error("Token revoked")]
     TokenRevoked,
 
+    /// Token has expired
+    #[error("Token expired")]
+    TokenExpired,
+
     /// Insufficient permissions
     #[error("Insufficient scope: requires {required}")]
     InsufficientScope {
@@ -102,6 +113,27 @@ impl std::fmt::Display for Scope {
     }
 }
 
+impl std::str::FromStr for Scope {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "admin" => Ok(Scope::Admin),
+            "session_read" => Ok(Scope::SessionRead),
+            "session_write" => Ok(Scope::SessionWrite),
+            "execution_read" => Ok(Scope::ExecutionRead),
+            "execution_write" => Ok(Scope::ExecutionWrite),
+            "approval_respond" => Ok(Scope::ApprovalRespond),
+            "config_read" => Ok(Scope::ConfigRead),
+            "config_write" => Ok(Scope::ConfigWrite),
+            "node_manage" => Ok(Scope::NodeManage),
+            "scheduler_read" => Ok(Scope::SchedulerRead),
+            "scheduler_write" => Ok(Scope::SchedulerWrite),
+            other => Err(format!("unknown scope '{}'", other)),
+        }
+    }
+}
+
 // ============================================================================
 // Auth Method
 // ============================================================================
@@ -160,6 +192,18 @@ impl AuthContext {
             })
         }
     }
+
+    /// Graph RAG memory visible to this caller
+    ///
+    /// Admins see every user's memory; everyone else sees their own plus
+    /// shared team memories.
+    pub fn memory_scope(&self) -> cratos_memory::MemoryScope {
+        if self.has_scope(&Scope::Admin) {
+            cratos_memory::MemoryScope::global()
+        } else {
+            cratos_memory::MemoryScope::user(&self.user_id)
+        }
+    }
 }
 
 // ============================================================================
@@ -179,20 +223,57 @@ struct StoredKey {
     label: String,
     /// When the key was created
     created_at: DateTime<Utc>,
+    /// When the key stops being accepted (`None` = never)
+    expires_at: Option<DateTime<Utc>>,
+    /// When the key was last used (as of the last database write)
+    last_used_at: Option<DateTime<Utc>>,
     /// Whether the key has been revoked
     revoked: bool,
 }
 
+impl StoredKey {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|t| t <= now)
+    }
+
+    fn info(&self, key_hash: &str) -> ApiKeyInfo {
+        ApiKeyInfo {
+            key_hash: key_hash.to_string(),
+            user_id: self.user_id.clone(),
+            label: self.label.clone(),
+            scopes: self.scopes.clone(),
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            last_used_at: self.last_used_at,
+            revoked: self.revoked,
+        }
+    }
+}
+
 // ============================================================================
 // Auth Store
 // ============================================================================
 
 /// Token storage and validation
+///
+/// Keys are validated against an in-memory map. When backed by SQLite
+/// ([`AuthStore::new_with_db`]) every change is written through, and
+/// [`AuthStore::sync`] picks up keys changed by other processes (e.g. the CLI).
 pub struct AuthStore {
     /// key_hash_hex → StoredKey
     keys: RwLock<HashMap<String, StoredKey>>,
+    /// key_hash_hex → last use not yet written to the database; kept apart
+    /// from `keys` so validation only needs a read lock
+    pending_uses: Mutex<HashMap<String, DateTime<Utc>>>,
+    /// Held while a key change is written to the database and `keys`, and by
+    /// [`AuthStore::sync`] across its reload, so a reload cannot undo a change
+    changes: tokio::sync::Mutex<()>,
     /// Whether auth is enabled
     enabled: bool,
+    /// Optional SQLite store for persistence
+    store: Option<ApiKeyStore>,
+    /// Providers for bearer tokens that are not Cratos API keys
+    external: Option<Arc<ExternalAuthRegistry>>,
 }
 
 impl AuthStore {
@@ -200,10 +281,40 @@ impl AuthStore {
     pub fn new(enabled: bool) -> Self {
         Self {
             keys: RwLock::new(HashMap::new()),
+            pending_uses: Mutex::new(HashMap::new()),
+            changes: tokio::sync::Mutex::new(()),
             enabled,
+            store: None,
+            external: None,
         }
     }
 
+    /// Create an auth store backed by SQLite, loading previously created keys
+    pub async fn new_with_db(
+        enabled: bool,
+        db: sqlx::Pool<sqlx::Sqlite>,
+    ) -> std::result::Result<Self, sqlx::Error> {
+        let store = ApiKeyStore::new(db).await?;
+        let keys: HashMap<String, StoredKey> = store.load_all().await?.into_iter().collect();
+        debug!(keys = keys.len(), "Loaded API keys from SQLite");
+
+        Ok(Self {
+            keys: RwLock::new(keys),
+            pending_uses: Mutex::new(HashMap::new()),
+            changes: tokio::sync::Mutex::new(()),
+            enabled,
+            store: Some(store),
+            external: None,
+        })
+    }
+
+    /// Also accept bearer tokens verified by external providers (e.g. OIDC)
+    #[must_use]
+    pub fn with_external_auth(mut self, registry: Arc<ExternalAuthRegistry>) -> Self {
+        self.external = Some(registry);
+        self
+    }
+
     /// Check if authentication is enabled
     pub fn is_enabled(&self) -> bool {
         self.enabled
@@ -224,15 +335,47 @@ impl AuthStore {
         hash.iter().map(|b| format!("{:02x}", b)).collect()
     }
 
-    /// Generate a new API key for a user
+    fn read_keys(&self) -> Result<RwLockReadGuard<'_, HashMap<String, StoredKey>>> {
+        self.keys
+            .read()
+            .map_err(|e| AuthError::Internal(format!("Lock poisoned: {}", e)))
+    }
+
+    fn write_keys(&self) -> Result<RwLockWriteGuard<'_, HashMap<String, StoredKey>>> {
+        self.keys
+            .write()
+            .map_err(|e| AuthError::Internal(format!("Lock poisoned: {}", e)))
+    }
+
+    fn lock_pending_uses(&self) -> Result<MutexGuard<'_, HashMap<String, DateTime<Utc>>>> {
+        self.pending_uses
+            .lock()
+            .map_err(|e| AuthError::Internal(format!("Lock poisoned: {}", e)))
+    }
+
+    /// Generate a new non-expiring API key for a user
     ///
     /// Returns the raw key (only shown once) and the key hash for reference.
-    pub fn generate_api_key(
+    pub async fn generate_api_key(
         &self,
         user_id: &str,
         scopes: Vec<Scope>,
         label: &str,
     ) -> Result<(SecureString, String)> {
+        let (key, info) = self.create_api_key(user_id, scopes, label, None).await?;
+        Ok((key, info.key_hash))
+    }
+
+    /// Create a new API key, optionally expiring at `expires_at`
+    ///
+    /// Returns the raw key (only shown once) and the key's listing entry.
+    pub async fn create_api_key(
+        &self,
+        user_id: &str,
+        scopes: Vec<Scope>,
+        label: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(SecureString, ApiKeyInfo)> {
         // Generate a random API key: cratos_<uuid>
         let raw_key = format!("cratos_{}", Uuid::new_v4().as_simple());
         let key_hash = Self::hash_key(&raw_key);
@@ -244,14 +387,20 @@ impl AuthStore {
             scopes,
             label: label.to_string(),
             created_at: Utc::now(),
+            expires_at,
+            last_used_at: None,
             revoked: false,
         };
 
-        let mut keys = self
-            .keys
-            .write()
-            .map_err(|e| AuthError::Internal(format!("Lock poisoned: {}", e)))?;
-        keys.insert(key_hash_hex.clone(), stored);
+        let _changes = self.changes.lock().await;
+        if let Some(ref store) = self.store {
+            store
+                .insert(&key_hash_hex, &stored)
+                .await
+                .map_err(db_error)?;
+        }
+        let info = stored.info(&key_hash_hex);
+        self.write_keys()?.insert(key_hash_hex, stored);
 
         info!(
             user_id = %user_id,
@@ -260,7 +409,7 @@ impl AuthStore {
             "API key generated"
         );
 
-        Ok((SecureString::new(raw_key), key_hash_hex))
+        Ok((SecureString::new(raw_key), info))
     }
 
     /// Validate a token/API key and return the auth context
@@ -283,10 +432,7 @@ impl AuthStore {
         let token_hash = Self::hash_key(token);
         let token_hash_hex = Self::hash_to_hex(&token_hash);
 
-        let keys = self
-            .keys
-            .read()
-            .map_err(|e| AuthError::Internal(format!("Lock poisoned: {}", e)))?;
+        let keys = self.read_keys()?;
 
         // Find the stored key by hash
         if let Some(stored) = keys.get(&token_hash_hex) {
@@ -300,6 +446,12 @@ impl AuthStore {
                 return Err(AuthError::TokenRevoked);
             }
 
+            let now = Utc::now();
+            if stored.is_expired(now) {
+                return Err(AuthError::TokenExpired);
+            }
+            self.lock_pending_uses()?.insert(token_hash_hex, now);
+
             debug!(user_id = %stored.user_id, label = %stored.label, "Token validated");
 
             let method = if token.starts_with("cratos_") {
@@ -321,13 +473,49 @@ impl AuthStore {
         }
     }
 
+    /// Authenticate a bearer token or API key
+    ///
+    /// Like [`Self::validate_token`], but JWTs are verified by the registered
+    /// external providers. Use this wherever the caller can await.
+    pub async fn authenticate(&self, token: &str) -> Result<AuthContext> {
+        let external = match &self.external {
+            Some(registry)
+                if self.enabled && looks_like_jwt(token) && registry.has_bearer_provider() =>
+            {
+                registry
+            }
+            _ => return self.validate_token(token),
+        };
+
+        match external.verify_bearer(token).await {
+            Ok((provider, result)) => {
+                debug!(user_id = %result.user_id, provider = %provider, "External token validated");
+                Ok(AuthContext {
+                    user_id: result.user_id,
+                    method: AuthMethod::ExternalAuth { provider },
+                    scopes: result.scopes,
+                    session_id: None,
+                    device_id: None,
+                })
+            }
+            Err(e) => {
+                warn!(error = %e, "External token rejected");
+                Err(AuthError::InvalidCredentials)
+            }
+        }
+    }
+
     /// Revoke a key by its hash
-    pub fn revoke_key(&self, key_hash_hex: &str) -> Result<()> {
-        let mut keys = self
-            .keys
-            .write()
-            .map_err(|e| AuthError::Internal(format!("Lock poisoned: {}", e)))?;
+    pub async fn revoke_key(&self, key_hash_hex: &str) -> Result<()> {
+        let _changes = self.changes.lock().await;
+        if !self.read_keys()?.contains_key(key_hash_hex) {
+            return Err(AuthError::InvalidCredentials);
+        }
+        if let Some(ref store) = self.store {
+            store.revoke(key_hash_hex).await.map_err(db_error)?;
+        }
 
+        let mut keys = self.write_keys()?;
         if let Some(stored) = keys.get_mut(key_hash_hex) {
             stored.revoked = true;
             info!(
@@ -335,39 +523,167 @@ impl AuthStore {
                 label = %stored.label,
                 "API key revoked"
             );
-            Ok(())
-        } else {
-            Err(AuthError::InvalidCredentials)
+        }
+        Ok(())
+    }
+
+    /// Replace a key with a new one carrying the same owner, scopes and label
+    ///
+    /// The old key keeps working for `grace` so clients can switch over. A key
+    /// with an expiry passes its lifetime on to the new key.
+    pub async fn rotate_key(
+        &self,
+        key_hash_hex: &str,
+        grace: chrono::Duration,
+    ) -> Result<(SecureString, ApiKeyInfo)> {
+        let old = self
+            .read_keys()?
+            .get(key_hash_hex)
+            .cloned()
+            .ok_or(AuthError::InvalidCredentials)?;
+        let now = Utc::now();
+        if old.revoked {
+            return Err(AuthError::TokenRevoked);
+        }
+        if old.is_expired(now) {
+            return Err(AuthError::TokenExpired);
+        }
+
+        let lifetime = old.expires_at.map(|t| t - old.created_at);
+        let (key, info) = self
+            .create_api_key(
+                &old.user_id,
+                old.scopes.clone(),
+                &old.label,
+                lifetime.map(|l| now + l),
+            )
+            .await?;
+
+        let grace_end = now + grace;
+        let old_expiry = Some(old.expires_at.map_or(grace_end, |t| t.min(grace_end)));
+        let changes = self.changes.lock().await;
+        if let Some(ref store) = self.store {
+            store
+                .set_expiry(key_hash_hex, old_expiry)
+                .await
+                .map_err(db_error)?;
+        }
+        if let Some(stored) = self.write_keys()?.get_mut(key_hash_hex) {
+            stored.expires_at = old_expiry;
+        }
+        drop(changes);
+
+        info!(
+            user_id = %old.user_id,
+            label = %old.label,
+            grace_secs = grace.num_seconds(),
+            "API key rotated"
+        );
+        Ok((key, info))
+    }
+
+    /// Resolve the full hash of a key from a unique prefix of it
+    ///
+    /// Returns `None` when no key or more than one key matches.
+    pub fn find_key(&self, hash_prefix: &str) -> Option<String> {
+        let keys = self.read_keys().ok()?;
+        let mut matches = keys.keys().filter(|h| h.starts_with(hash_prefix));
+        match (matches.next(), matches.next()) {
+            (Some(hash), None) if !hash_prefix.is_empty() => Some(hash.clone()),
+            _ => None,
         }
     }
 
-    /// List all keys (non-sensitive info only)
+    /// List all keys (non-sensitive info only), oldest first
     pub fn list_keys(&self) -> Result<Vec<ApiKeyInfo>> {
-        let keys = self
-            .keys
-            .read()
-            .map_err(|e| AuthError::Internal(format!("Lock poisoned: {}", e)))?;
+        let keys = self.read_keys()?;
+        let pending_uses = self.lock_pending_uses()?;
 
-        Ok(keys
+        let mut infos: Vec<ApiKeyInfo> = keys
             .iter()
-            .map(|(hash_hex, stored)| ApiKeyInfo {
-                key_hash: hash_hex.clone(),
-                user_id: stored.user_id.clone(),
-                label: stored.label.clone(),
-                scopes: stored.scopes.clone(),
-                created_at: stored.created_at,
-                revoked: stored.revoked,
+            .map(|(hash_hex, stored)| {
+                let mut info = stored.info(hash_hex);
+                if let Some(used_at) = pending_uses.get(hash_hex) {
+                    info.last_used_at = Some(*used_at);
+                }
+                info
             })
-            .collect())
+            .collect();
+        infos.sort_by_key(|k| k.created_at);
+        Ok(infos)
     }
 
-    /// Get count of active (non-revoked) keys
+    /// Get count of usable (non-revoked, unexpired) keys
     pub fn active_key_count(&self) -> usize {
+        let now = Utc::now();
         self.keys
             .read()
-            .map(|keys| keys.values().filter(|k| !k.revoked).count())
+            .map(|keys| {
+                keys.values()
+                    .filter(|k| !k.revoked && !k.is_expired(now))
+                    .count()
+            })
             .unwrap_or(0)
     }
+
+    /// Write pending last-used timestamps and reload keys from the database
+    ///
+    /// Picks up keys created, revoked or rotated by other processes, so a key
+    /// revoked elsewhere keeps working here until the next sync. Changes made
+    /// through this store apply at once. Does nothing without a database.
+    pub async fn sync(&self) -> Result<()> {
+        let Some(ref store) = self.store else {
+            return Ok(());
+        };
+
+        let touched: Vec<(String, DateTime<Utc>)> = std::mem::take(&mut *self.lock_pending_uses()?)
+            .into_iter()
+            .collect();
+        for (i, (hash, last_used_at)) in touched.iter().enumerate() {
+            if let Err(e) = store.touch(hash, *last_used_at).await {
+                // Retry the unwritten uses next time, unless newer ones came in
+                let mut pending_uses = self.lock_pending_uses()?;
+                for (hash, last_used_at) in &touched[i..] {
+                    pending_uses.entry(hash.clone()).or_insert(*last_used_at);
+                }
+                return Err(db_error(e));
+            }
+        }
+
+        // Uses recorded meanwhile stay pending until the next sync
+        let _changes = self.changes.lock().await;
+        let loaded: HashMap<String, StoredKey> =
+            store.load_all().await.map_err(db_error)?.into_iter().collect();
+        *self.write_keys()? = loaded;
+        Ok(())
+    }
+
+    /// Run [`AuthStore::sync`] every `interval` in the background
+    pub fn spawn_sync(self: &Arc<Self>, interval: std::time::Duration) {
+        if self.store.is_none() {
+            return;
+        }
+        let store = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                if let Err(e) = store.sync().await {
+                    warn!(error = %e, "Failed to sync API keys");
+                }
+            }
+        });
+    }
+}
+
+/// JWTs are three base64url segments; Cratos keys contain no dots
+fn looks_like_jwt(token: &str) -> bool {
+    token.split('.').count() == 3 && !token.starts_with("cratos_")
+}
+
+fn db_error(e: sqlx::Error) -> AuthError {
+    AuthError::Internal(format!("API key database error: {}", e))
 }
 
 /// Non-sensitive API key information for listing
@@ -383,6 +699,10 @@ pub struct ApiKeyInfo {
     pub scopes: Vec<Scope>,
     /// Creation time
     pub created_at: DateTime<Utc>,
+    /// Expiry time (`None` = never expires)
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Last successful use
+    pub last_used_at: Option<DateTime<Utc>>,
     /// Whether revoked
     pub revoked: bool,
 }
@@ -0,0 +1,143 @@
+//! SQLite persistence for API keys
+//!
+//! Only the SHA-256 hash of a key is stored, never the key itself. Keys are
+//! written when they are created, revoked or rotated, so they survive a
+//! server restart.
+
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Row, Sqlite};
+
+use super::{Scope, StoredKey};
+
+/// SQLite-backed API key store
+#[derive(Clone)]
+pub(super) struct ApiKeyStore {
+    pool: Pool<Sqlite>,
+}
+
+impl ApiKeyStore {
+    /// Create the store, creating the table if needed
+    pub(super) async fn new(pool: Pool<Sqlite>) -> Result<Self, sqlx::Error> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS api_keys (
+                key_hash TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                label TEXT NOT NULL,
+                scopes TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                expires_at TEXT,
+                last_used_at TEXT,
+                revoked INTEGER NOT NULL DEFAULT 0
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+
+    /// Insert a new key
+    pub(super) async fn insert(&self, key_hash: &str, key: &StoredKey) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO api_keys (
+                key_hash, user_id, label, scopes, created_at, expires_at, last_used_at, revoked
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(key_hash)
+        .bind(&key.user_id)
+        .bind(&key.label)
+        .bind(serde_json::to_string(&key.scopes).unwrap_or_else(|_| "[]".to_string()))
+        .bind(key.created_at.to_rfc3339())
+        .bind(key.expires_at.map(|t| t.to_rfc3339()))
+        .bind(key.last_used_at.map(|t| t.to_rfc3339()))
+        .bind(key.revoked)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark a key as revoked
+    pub(super) async fn revoke(&self, key_hash: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE api_keys SET revoked = 1 WHERE key_hash = ?")
+            .bind(key_hash)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Change when a key expires
+    pub(super) async fn set_expiry(
+        &self,
+        key_hash: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE api_keys SET expires_at = ? WHERE key_hash = ?")
+            .bind(expires_at.map(|t| t.to_rfc3339()))
+            .bind(key_hash)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Record when a key was last used
+    pub(super) async fn touch(
+        &self,
+        key_hash: &str,
+        last_used_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE api_keys SET last_used_at = ? WHERE key_hash = ?")
+            .bind(last_used_at.to_rfc3339())
+            .bind(key_hash)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Load every key, keyed by hash
+    pub(super) async fn load_all(&self) -> Result<Vec<(String, StoredKey)>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM api_keys ORDER BY created_at")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().filter_map(row_to_key).collect())
+    }
+}
+
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn row_to_key(row: &sqlx::sqlite::SqliteRow) -> Option<(String, StoredKey)> {
+    let key_hash: String = row.try_get("key_hash").ok()?;
+    let scopes: String = row.try_get("scopes").ok()?;
+    let created_at: String = row.try_get("created_at").ok()?;
+    let expires_at: Option<String> = row.try_get("expires_at").ok()?;
+    let last_used_at: Option<String> = row.try_get("last_used_at").ok()?;
+
+    let hash = hex_to_hash(&key_hash)?;
+    let scopes: Vec<Scope> = serde_json::from_str(&scopes).ok()?;
+    let key = StoredKey {
+        key_hash: hash,
+        user_id: row.try_get("user_id").ok()?,
+        scopes,
+        label: row.try_get("label").ok()?,
+        created_at: parse_time(&created_at)?,
+        expires_at: expires_at.as_deref().and_then(parse_time),
+        last_used_at: last_used_at.as_deref().and_then(parse_time),
+        revoked: row.try_get("revoked").ok()?,
+    };
+    Some((key_hash, key))
+}
+
+fn hex_to_hash(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(hash)
+}
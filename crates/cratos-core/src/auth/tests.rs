@@ -1,11 +1,12 @@
 
     use super::*;
 
-    #[test]
-    fn test_generate_and_validate_key() {
+    #[tokio::test]
+    async fn test_generate_and_validate_key() {
         let store = AuthStore::new(true);
         let (key, _hash) = store
             .generate_api_key("user1", default_user_scopes(), "test key")
+            .await
             .unwrap();
 
         let ctx = store.validate_token(key.expose()).unwrap();
@@ -28,18 +29,19 @@
         assert!(matches!(result, Err(AuthError::MissingCredentials)));
     }
 
-    #[test]
-    fn test_revoke_key() {
+    #[tokio::test]
+    async fn test_revoke_key() {
         let store = AuthStore::new(true);
         let (key, hash) = store
             .generate_api_key("user1", default_user_scopes(), "test")
+            .await
             .unwrap();
 
         // Should work before revocation
         assert!(store.validate_token(key.expose()).is_ok());
 
         // Revoke
-        store.revoke_key(&hash).unwrap();
+        store.revoke_key(&hash).await.unwrap();
 
         // Should fail after revocation
         let result = store.validate_token(key.expose());
@@ -99,14 +101,16 @@
         assert!(ctx.require_scope(&Scope::ConfigWrite).is_err());
     }
 
-    #[test]
-    fn test_list_keys() {
+    #[tokio::test]
+    async fn test_list_keys() {
         let store = AuthStore::new(true);
         store
             .generate_api_key("user1", default_user_scopes(), "key1")
+            .await
             .unwrap();
         store
             .generate_api_key("user2", admin_scopes(), "key2")
+            .await
             .unwrap();
 
         let keys = store.list_keys().unwrap();
@@ -147,18 +151,203 @@
         assert!(!scopes.contains(&Scope::SchedulerWrite));
     }
 
-    #[test]
-    fn test_active_key_count() {
+    #[tokio::test]
+    async fn test_active_key_count() {
         let store = AuthStore::new(true);
         let (_, hash) = store
             .generate_api_key("user1", default_user_scopes(), "key1")
+            .await
             .unwrap();
         store
             .generate_api_key("user2", admin_scopes(), "key2")
+            .await
             .unwrap();
 
         assert_eq!(store.active_key_count(), 2);
 
-        store.revoke_key(&hash).unwrap();
+        store.revoke_key(&hash).await.unwrap();
         assert_eq!(store.active_key_count(), 1);
     }
+
+    /// Single connection so every store sees the same in-memory database
+    async fn memory_pool() -> sqlx::SqlitePool {
+        sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_keys_survive_restart() {
+        let pool = memory_pool().await;
+        let store = AuthStore::new_with_db(true, pool.clone()).await.unwrap();
+        let (key, hash) = store
+            .generate_api_key("user1", default_user_scopes(), "phone")
+            .await
+            .unwrap();
+        store.validate_token(key.expose()).unwrap();
+        store.sync().await.unwrap();
+
+        let reopened = AuthStore::new_with_db(true, pool).await.unwrap();
+        let ctx = reopened.validate_token(key.expose()).unwrap();
+        assert_eq!(ctx.user_id, "user1");
+        let info = &reopened.list_keys().unwrap()[0];
+        assert_eq!(info.label, "phone");
+        assert!(info.last_used_at.is_some());
+
+        reopened.revoke_key(&hash).await.unwrap();
+        store.sync().await.unwrap();
+        assert!(matches!(
+            store.validate_token(key.expose()),
+            Err(AuthError::TokenRevoked)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validate_token_only_reads_keys() {
+        let store = AuthStore::new(true);
+        let (key, _) = store
+            .generate_api_key("user1", default_user_scopes(), "phone")
+            .await
+            .unwrap();
+
+        // Validation succeeds while another reader holds the key map
+        let keys = store.read_keys().unwrap();
+        store.validate_token(key.expose()).unwrap();
+        drop(keys);
+
+        assert!(store.list_keys().unwrap()[0].last_used_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_expired_key() {
+        let store = AuthStore::new(true);
+        let (key, _) = store
+            .create_api_key(
+                "user1",
+                default_user_scopes(),
+                "temp",
+                Some(Utc::now() - chrono::Duration::seconds(1)),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.validate_token(key.expose()),
+            Err(AuthError::TokenExpired)
+        ));
+        assert_eq!(store.active_key_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_key_with_grace() {
+        let store = AuthStore::new(true);
+        let (old_key, old_hash) = store
+            .generate_api_key("user1", vec![Scope::SessionRead], "ci")
+            .await
+            .unwrap();
+
+        let (new_key, info) = store
+            .rotate_key(&old_hash, chrono::Duration::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(info.label, "ci");
+        assert_eq!(info.scopes, vec![Scope::SessionRead]);
+        assert!(info.expires_at.is_none());
+
+        // Both keys work during the grace period
+        assert!(store.validate_token(old_key.expose()).is_ok());
+        assert!(store.validate_token(new_key.expose()).is_ok());
+
+        // Without grace the old key stops immediately
+        let (newest_key, _) = store
+            .rotate_key(&info.key_hash, chrono::Duration::zero())
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.validate_token(new_key.expose()),
+            Err(AuthError::TokenExpired)
+        ));
+        assert!(store.validate_token(newest_key.expose()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_find_key_by_prefix() {
+        let store = AuthStore::new(true);
+        let (_, hash) = store
+            .generate_api_key("user1", default_user_scopes(), "k")
+            .await
+            .unwrap();
+        assert_eq!(store.find_key(&hash[..8]), Some(hash.clone()));
+        assert_eq!(store.find_key("zz"), None);
+        assert_eq!(store.find_key(""), None);
+    }
+
+    #[test]
+    fn test_scope_parse_roundtrip() {
+        for scope in [Scope::Admin, Scope::ExecutionWrite, Scope::SchedulerRead] {
+            assert_eq!(scope.to_string().parse::<Scope>(), Ok(scope));
+        }
+        assert!("root".parse::<Scope>().is_err());
+    }
+
+    /// Accepts the bearer token "a.b.c" only
+    struct JwtStub;
+
+    #[async_trait::async_trait]
+    impl crate::external_auth::ExternalAuthVerifier for JwtStub {
+        fn provider_name(&self) -> &str {
+            "oidc"
+        }
+
+        fn accepts_bearer_tokens(&self) -> bool {
+            true
+        }
+
+        async fn verify(
+            &self,
+            token: &str,
+        ) -> std::result::Result<
+            crate::external_auth::ExternalAuthResult,
+            crate::external_auth::ExternalAuthError,
+        > {
+            if token != "a.b.c" {
+                return Err(crate::external_auth::ExternalAuthError::VerificationFailed(
+                    "bad signature".to_string(),
+                ));
+            }
+            Ok(crate::external_auth::ExternalAuthResult {
+                user_id: "alice".to_string(),
+                display_name: None,
+                scopes: vec![Scope::SessionRead],
+                metadata: HashMap::new(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_external_jwt() {
+        let mut registry = ExternalAuthRegistry::new();
+        registry.register(Box::new(JwtStub));
+        let store = AuthStore::new(true).with_external_auth(Arc::new(registry));
+
+        let ctx = store.authenticate("a.b.c").await.unwrap();
+        assert_eq!(ctx.user_id, "alice");
+        assert!(matches!(
+            ctx.method,
+            AuthMethod::ExternalAuth { ref provider } if provider == "oidc"
+        ));
+        assert!(!ctx.has_scope(&Scope::Admin));
+
+        assert!(matches!(
+            store.authenticate("x.y.z").await,
+            Err(AuthError::InvalidCredentials)
+        ));
+
+        // API keys still go through the key store
+        let (key, _) = store
+            .generate_api_key("bob", default_user_scopes(), "k")
+            .await
+            .unwrap();
+        assert_eq!(store.authenticate(key.expose()).await.unwrap().user_id, "bob");
+    }
@@ -32,6 +32,12 @@ pub enum OrchestratorEvent {
         /// Whether this is the final chunk
         is_final: bool,
     },
+    /// Text streamed so far is void (the provider failed mid-stream and a
+    /// fallback provider answers instead); discard it before new deltas
+    ChatReset {
+        /// Execution identifier
+        execution_id: Uuid,
+    },
     /// Tool execution started
     ToolStarted {
         /// Execution identifier
@@ -100,6 +106,23 @@ pub enum OrchestratorEvent {
         /// Seconds until quota resets (if known)
         reset_in_secs: Option<i64>,
     },
+    /// LLM spending budget close to or over its limit
+    ///
+    /// Emitted once per budget, period and level (warning / exceeded).
+    BudgetWarning {
+        /// "user" or "provider"
+        scope: String,
+        /// User ID or provider name
+        name: String,
+        /// "daily" or "monthly"
+        period: String,
+        /// Spent in the current period (USD)
+        spent_usd: f64,
+        /// Limit of the period (USD)
+        limit_usd: f64,
+        /// Whether the limit is exceeded (otherwise only close)
+        exceeded: bool,
+    },
 }
 
 impl OrchestratorEvent {
@@ -112,6 +135,7 @@ impl OrchestratorEvent {
             Self::ExecutionStarted { execution_id, .. }
             | Self::PlanningStarted { execution_id, .. }
             | Self::ChatDelta { execution_id, .. }
+            | Self::ChatReset { execution_id }
             | Self::ToolStarted { execution_id, .. }
             | Self::ToolCompleted { execution_id, .. }
             | Self::ApprovalRequired { execution_id, .. }
@@ -119,8 +143,8 @@ impl OrchestratorEvent {
             | Self::ExecutionFailed { execution_id, .. }
             | Self::ExecutionCancelled { execution_id } => *execution_id,
             Self::A2aMessageSent { message_id, .. } => *message_id,
-            // QuotaWarning has no execution context
-            Self::QuotaWarning { .. } => Uuid::nil(),
+            // Quota and budget warnings have no execution context
+            Self::QuotaWarning { .. } | Self::BudgetWarning { .. } => Uuid::nil(),
         }
     }
 }
@@ -2,6 +2,10 @@
 //!
 //! Provides a trait-based interface for plugging in external identity providers
 //! (Tailscale, Google, GitHub, etc.) following multi-auth pattern.
+//!
+//! Verifiers that accept bearer tokens (such as [`OidcVerifier`]) are also
+//! consulted by [`crate::auth::AuthStore::authenticate`] for tokens that are
+//! not Cratos API keys.
 
 #![forbid(unsafe_code)]
 
@@ -11,6 +15,10 @@ use serde::Deserialize;
 use std::collections::HashMap;
 use tracing::debug;
 
+mod oidc;
+
+pub use oidc::{OidcConfig, OidcVerifier};
+
 /// Result of a successful external authentication.
 #[derive(Debug, Clone)]
 pub struct ExternalAuthResult {
@@ -62,6 +70,11 @@ pub trait ExternalAuthVerifier: Send + Sync {
     /// - Tailscale: peer IP address
     /// - OAuth: access token
     async fn verify(&self, credential: &str) -> Result<ExternalAuthResult, ExternalAuthError>;
+
+    /// Whether `Authorization: Bearer` tokens may be verified by this provider.
+    fn accepts_bearer_tokens(&self) -> bool {
+        false
+    }
 }
 
 /// Registry of external authentication verifiers.
@@ -106,6 +119,40 @@ impl ExternalAuthRegistry {
 
         verifier.verify(credential).await
     }
+
+    /// Whether any registered provider accepts bearer tokens.
+    pub fn has_bearer_provider(&self) -> bool {
+        self.verifiers.values().any(|v| v.accepts_bearer_tokens())
+    }
+
+    /// Verify a bearer token against every provider that accepts them.
+    ///
+    /// Returns the name of the first provider that verified the token.
+    pub async fn verify_bearer(
+        &self,
+        token: &str,
+    ) -> Result<(String, ExternalAuthResult), ExternalAuthError> {
+        let mut names: Vec<&String> = self
+            .verifiers
+            .iter()
+            .filter(|(_, v)| v.accepts_bearer_tokens())
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+
+        let mut last_err =
+            ExternalAuthError::Unavailable("no bearer token provider registered".to_string());
+        for name in names {
+            match self.verifiers[name].verify(token).await {
+                Ok(result) => return Ok((name.clone(), result)),
+                Err(e) => {
+                    debug!(provider = %name, error = %e, "Bearer token rejected");
+                    last_err = e;
+                }
+            }
+        }
+        Err(last_err)
+    }
 }
 
 impl Default for ExternalAuthRegistry {
@@ -0,0 +1,495 @@
+//! Provider failover chains.
+//!
+//! A [`FailoverChain`] is an [`LlmProvider`] over an ordered list of
+//! providers. Each call goes to the first healthy provider in the chain;
+//! transient errors are retried with backoff before the next provider is
+//! tried.
+//!
+//! ## Health
+//!
+//! [`ProviderHealth`] is shared by all chains so a provider that fails for
+//! one task type is avoided for the others too:
+//! - a [`CircuitBreaker`] per provider opens after repeated failures
+//! - the [`QuotaTracker`] state parsed from rate limit headers marks a
+//!   provider as near its limit
+//!
+//! Unhealthy providers are skipped while a healthy one remains. When every
+//! provider is unhealthy the whole chain is tried in order anyway.
+//!
+//! ## Attribution
+//!
+//! Callers learn which provider actually served a call by running it inside
+//! [`track_served`].
+
+use crate::orchestrator::is_llm_fallback_eligible;
+use crate::utils::{retry_with_backoff, CircuitBreaker, CircuitBreakerConfig, RetryConfig};
+use chrono::Utc;
+use cratos_llm::{
+    global_quota_tracker, CompletionRequest, CompletionResponse, CompletionStream, Error,
+    LlmProvider, QuotaState, QuotaTracker, Result, TaskType, ToolCompletionRequest,
+    ToolCompletionResponse,
+};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+tokio::task_local! {
+    static SERVED_BY: RefCell<Option<String>>;
+}
+
+/// Run `fut` and return the provider that served the last LLM call inside it.
+///
+/// Returns `None` when no failover chain handled a call.
+pub async fn track_served<F: Future>(fut: F) -> (F::Output, Option<String>) {
+    SERVED_BY
+        .scope(RefCell::new(None), async {
+            let output = fut.await;
+            let served = SERVED_BY.with(|s| s.borrow_mut().take());
+            (output, served)
+        })
+        .await
+}
+
+fn set_served(provider: &str) {
+    let _ = SERVED_BY.try_with(|s| {
+        // A nested chain already recorded the concrete provider
+        let mut served = s.borrow_mut();
+        if served.is_none() {
+            *served = Some(provider.to_string());
+        }
+    });
+}
+
+fn clear_served() {
+    let _ = SERVED_BY.try_with(|s| s.borrow_mut().take());
+}
+
+/// Check whether an LLM error is transient enough to retry on the same provider
+#[must_use]
+pub fn is_retryable(e: &Error) -> bool {
+    matches!(
+        e,
+        Error::ServerError(_) | Error::Network(_) | Error::Timeout(_)
+    )
+}
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Failover configuration loaded from `[llm.failover]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailoverConfig {
+    /// Provider names tried in order for every task type without its own chain
+    #[serde(default)]
+    pub chain: Vec<String>,
+    /// Chains for specific task types (e.g. `summarization = ["groq", "openai"]`)
+    #[serde(default)]
+    pub tasks: HashMap<TaskType, Vec<String>>,
+    /// Attempts per provider for transient errors before failing over
+    #[serde(default = "default_retry_attempts")]
+    pub retry_attempts: u32,
+    /// Consecutive failures that open a provider's circuit
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Seconds an open circuit waits before letting a probe request through
+    #[serde(default = "default_reset_timeout_secs")]
+    pub reset_timeout_secs: u64,
+    /// Skip a provider when less than this percent of its rate limit remains
+    #[serde(default = "default_quota_threshold_pct")]
+    pub quota_threshold_pct: f64,
+}
+
+fn default_retry_attempts() -> u32 {
+    2
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+fn default_reset_timeout_secs() -> u64 {
+    60
+}
+
+fn default_quota_threshold_pct() -> f64 {
+    10.0
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            chain: Vec::new(),
+            tasks: HashMap::new(),
+            retry_attempts: default_retry_attempts(),
+            failure_threshold: default_failure_threshold(),
+            reset_timeout_secs: default_reset_timeout_secs(),
+            quota_threshold_pct: default_quota_threshold_pct(),
+        }
+    }
+}
+
+// ============================================================================
+// Provider health
+// ============================================================================
+
+/// Why a provider was skipped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Its circuit breaker is open
+    CircuitOpen,
+    /// Its rate limit is (nearly) exhausted
+    NearQuotaLimit,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CircuitOpen => write!(f, "circuit open"),
+            Self::NearQuotaLimit => write!(f, "near quota limit"),
+        }
+    }
+}
+
+/// Health of the providers behind the failover chains
+pub struct ProviderHealth {
+    breaker_config: CircuitBreakerConfig,
+    breakers: RwLock<HashMap<String, Arc<CircuitBreaker>>>,
+    quota: Arc<QuotaTracker>,
+    quota_threshold_pct: f64,
+}
+
+impl ProviderHealth {
+    /// Create a health tracker backed by the global quota tracker
+    #[must_use]
+    pub fn new(breaker_config: CircuitBreakerConfig) -> Self {
+        Self {
+            breaker_config,
+            breakers: RwLock::new(HashMap::new()),
+            quota: global_quota_tracker(),
+            quota_threshold_pct: default_quota_threshold_pct(),
+        }
+    }
+
+    /// Use a different quota tracker
+    #[must_use]
+    pub fn with_quota_tracker(mut self, quota: Arc<QuotaTracker>) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    /// Set the remaining-quota percentage below which a provider is skipped
+    #[must_use]
+    pub fn with_quota_threshold(mut self, pct: f64) -> Self {
+        self.quota_threshold_pct = pct;
+        self
+    }
+
+    /// Circuit breaker of a provider (created on first use)
+    pub fn breaker(&self, provider: &str) -> Arc<CircuitBreaker> {
+        if let Some(breaker) = self
+            .breakers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(provider)
+        {
+            return breaker.clone();
+        }
+        self.breakers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(provider.to_string())
+            .or_insert_with(|| Arc::new(CircuitBreaker::new(provider, self.breaker_config.clone())))
+            .clone()
+    }
+
+    /// Check whether a provider should receive requests
+    pub async fn check(&self, provider: &str) -> std::result::Result<(), SkipReason> {
+        if !self.breaker(provider).can_execute() {
+            return Err(SkipReason::CircuitOpen);
+        }
+        match self.quota.get_state(provider).await {
+            Some(state) if quota_exhausted(&state, self.quota_threshold_pct) => {
+                Err(SkipReason::NearQuotaLimit)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Record a successful call
+    pub fn record_success(&self, provider: &str) {
+        self.breaker(provider).record_success();
+    }
+
+    /// Record a failed call
+    pub fn record_failure(&self, provider: &str) {
+        self.breaker(provider).record_failure();
+    }
+}
+
+/// Quota state counts only until its window resets
+fn quota_exhausted(state: &QuotaState, threshold_pct: f64) -> bool {
+    if state.reset_at.is_some_and(|reset| reset <= Utc::now()) {
+        return false;
+    }
+    // A 429 with retry-after records zero remaining requests without a limit
+    state.requests_remaining == Some(0) || state.is_near_limit(threshold_pct)
+}
+
+// ============================================================================
+// Failover chain
+// ============================================================================
+
+/// An ordered list of providers tried until one succeeds
+pub struct FailoverChain {
+    providers: Vec<Arc<dyn LlmProvider>>,
+    health: Arc<ProviderHealth>,
+    retry: RetryConfig,
+}
+
+impl FailoverChain {
+    /// Create a chain; the first provider is the primary
+    #[must_use]
+    pub fn new(providers: Vec<Arc<dyn LlmProvider>>, health: Arc<ProviderHealth>) -> Self {
+        Self {
+            providers,
+            health,
+            retry: RetryConfig::default().with_max_attempts(default_retry_attempts()),
+        }
+    }
+
+    /// Set the retry policy used on each provider
+    #[must_use]
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Names of the providers in order
+    #[must_use]
+    pub fn provider_names(&self) -> Vec<&str> {
+        self.providers.iter().map(|p| p.name()).collect()
+    }
+
+    /// Healthy providers in chain order, or all of them when none is healthy
+    async fn candidates(&self) -> Vec<(usize, &Arc<dyn LlmProvider>)> {
+        let mut healthy = Vec::with_capacity(self.providers.len());
+        for (index, provider) in self.providers.iter().enumerate() {
+            match self.health.check(provider.name()).await {
+                Ok(()) => healthy.push((index, provider)),
+                Err(reason) => {
+                    debug!(provider = provider.name(), %reason, "Skipping provider");
+                }
+            }
+        }
+        if healthy.is_empty() {
+            warn!("No healthy provider in failover chain, trying all");
+            return self.providers.iter().enumerate().collect();
+        }
+        healthy
+    }
+
+    /// Model to request from a provider.
+    ///
+    /// The primary gets the requested model; later providers get it only if
+    /// they serve it, otherwise their own default model.
+    fn model_for(index: usize, provider: &dyn LlmProvider, requested: &str) -> String {
+        if !requested.is_empty()
+            && (index == 0 || provider.available_models().iter().any(|m| m == requested))
+        {
+            requested.to_string()
+        } else {
+            provider.default_model().to_string()
+        }
+    }
+
+    /// Run `call` on each candidate until one succeeds
+    async fn run<T, F, Fut>(&self, requested_model: &str, call: F) -> Result<T>
+    where
+        F: Fn(Arc<dyn LlmProvider>, String) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut last_error = None;
+        for (index, provider) in self.candidates().await {
+            let name = provider.name();
+            let model = Self::model_for(index, provider.as_ref(), requested_model);
+            clear_served();
+            let result = retry_with_backoff(
+                &self.retry,
+                || call(provider.clone(), model.clone()),
+                is_retryable,
+            )
+            .await;
+            match result {
+                Ok(response) => {
+                    self.health.record_success(name);
+                    set_served(name);
+                    return Ok(response);
+                }
+                Err(e) if is_llm_fallback_eligible(&e.last_error) => {
+                    self.health.record_failure(name);
+                    warn!(
+                        provider = name,
+                        attempts = e.attempts,
+                        error = %e.last_error,
+                        "Provider failed, trying next in failover chain"
+                    );
+                    last_error = Some(e.last_error);
+                }
+                // The request itself is at fault; another provider won't help
+                Err(e) => return Err(e.last_error),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| Error::NotConfigured("empty failover chain".into())))
+    }
+}
+
+#[async_trait::async_trait]
+impl LlmProvider for FailoverChain {
+    fn name(&self) -> &str {
+        "failover"
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.providers.first().is_some_and(|p| p.supports_tools())
+    }
+
+    fn available_models(&self) -> Vec<String> {
+        self.providers
+            .iter()
+            .flat_map(|p| p.available_models())
+            .collect()
+    }
+
+    fn default_model(&self) -> &str {
+        self.providers
+            .first()
+            .map(|p| p.default_model())
+            .unwrap_or_default()
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let requested = request.model.clone();
+        self.run(&requested, |provider, model| {
+            let mut request = request.clone();
+            request.model = model;
+            async move { provider.complete(request).await }
+        })
+        .await
+    }
+
+    async fn complete_with_tools(
+        &self,
+        request: ToolCompletionRequest,
+    ) -> Result<ToolCompletionResponse> {
+        let requested = request.request.model.clone();
+        self.run(&requested, |provider, model| {
+            let mut request = request.clone();
+            request.request.model = model;
+            async move { provider.complete_with_tools(request).await }
+        })
+        .await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.providers
+            .first()
+            .is_some_and(|p| p.supports_streaming())
+    }
+
+    /// Fails over only while opening the stream; errors mid-stream surface
+    /// to the caller.
+    async fn complete_stream(&self, request: ToolCompletionRequest) -> Result<CompletionStream> {
+        let requested = request.request.model.clone();
+        self.run(&requested, |provider, model| {
+            let mut request = request.clone();
+            request.request.model = model;
+            async move { provider.complete_stream(request).await }
+        })
+        .await
+    }
+}
+
+// ============================================================================
+// Per-task chains
+// ============================================================================
+
+/// Failover chains per task type sharing one [`ProviderHealth`]
+pub struct FailoverChains {
+    default: Arc<FailoverChain>,
+    tasks: HashMap<TaskType, Arc<FailoverChain>>,
+    health: Arc<ProviderHealth>,
+}
+
+impl FailoverChains {
+    /// Build the chains of `config`, resolving provider names with `lookup`.
+    ///
+    /// Unknown providers are left out. Returns `None` when the default chain
+    /// has no available provider.
+    pub fn from_config(
+        config: &FailoverConfig,
+        lookup: impl Fn(&str) -> Option<Arc<dyn LlmProvider>>,
+    ) -> Option<Self> {
+        let health = Arc::new(
+            ProviderHealth::new(
+                CircuitBreakerConfig::new()
+                    .with_failure_threshold(config.failure_threshold)
+                    .with_success_threshold(1)
+                    .with_reset_timeout(Duration::from_secs(config.reset_timeout_secs)),
+            )
+            .with_quota_threshold(config.quota_threshold_pct),
+        );
+        let retry = RetryConfig::default().with_max_attempts(config.retry_attempts.max(1));
+
+        let build = |names: &[String]| -> Option<Arc<FailoverChain>> {
+            let providers: Vec<_> = names
+                .iter()
+                .filter_map(|name| {
+                    let provider = lookup(name);
+                    if provider.is_none() {
+                        warn!(provider = %name, "Failover provider not available, skipping");
+                    }
+                    provider
+                })
+                .collect();
+            if providers.is_empty() {
+                return None;
+            }
+            Some(Arc::new(
+                FailoverChain::new(providers, health.clone()).with_retry(retry.clone()),
+            ))
+        };
+
+        let default = build(&config.chain)?;
+        let tasks = config
+            .tasks
+            .iter()
+            .filter_map(|(task, names)| build(names).map(|chain| (*task, chain)))
+            .collect();
+
+        Some(Self {
+            default,
+            tasks,
+            health,
+        })
+    }
+
+    /// Chain for a task type (the default chain unless one is configured)
+    #[must_use]
+    pub fn for_task(&self, task_type: TaskType) -> Arc<FailoverChain> {
+        self.tasks.get(&task_type).unwrap_or(&self.default).clone()
+    }
+
+    /// Shared provider health
+    #[must_use]
+    pub fn health(&self) -> &Arc<ProviderHealth> {
+        &self.health
+    }
+}
+
+#[cfg(test)]
+mod tests;
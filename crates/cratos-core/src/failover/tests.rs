@@ -0,0 +1,296 @@
+use super::*;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+/// Provider that fails with scripted errors, then answers with its name
+struct ScriptedProvider {
+    name: String,
+    models: Vec<String>,
+    errors: Mutex<VecDeque<Error>>,
+    calls: AtomicU32,
+    last_model: Mutex<String>,
+}
+
+impl ScriptedProvider {
+    fn new(name: &str, errors: Vec<Error>) -> Arc<Self> {
+        Arc::new(Self {
+            name: name.to_string(),
+            models: vec![format!("{name}-model")],
+            errors: Mutex::new(errors.into()),
+            calls: AtomicU32::new(0),
+            last_model: Mutex::new(String::new()),
+        })
+    }
+
+    fn calls(&self) -> u32 {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    fn last_model(&self) -> String {
+        self.last_model.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl LlmProvider for ScriptedProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn available_models(&self) -> Vec<String> {
+        self.models.clone()
+    }
+
+    fn default_model(&self) -> &str {
+        &self.models[0]
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        *self.last_model.lock().unwrap() = request.model.clone();
+        if let Some(e) = self.errors.lock().unwrap().pop_front() {
+            return Err(e);
+        }
+        Ok(CompletionResponse {
+            content: self.name.clone(),
+            usage: None,
+            finish_reason: Some("stop".to_string()),
+            model: request.model,
+        })
+    }
+
+    async fn complete_with_tools(
+        &self,
+        request: ToolCompletionRequest,
+    ) -> Result<ToolCompletionResponse> {
+        let response = self.complete(request.request).await?;
+        Ok(ToolCompletionResponse {
+            content: Some(response.content),
+            tool_calls: Vec::new(),
+            usage: None,
+            finish_reason: response.finish_reason,
+            model: response.model,
+        })
+    }
+}
+
+fn health() -> Arc<ProviderHealth> {
+    Arc::new(
+        ProviderHealth::new(CircuitBreakerConfig::new().with_failure_threshold(1))
+            .with_quota_tracker(Arc::new(QuotaTracker::new())),
+    )
+}
+
+fn no_retry() -> RetryConfig {
+    RetryConfig::default().with_max_attempts(1)
+}
+
+fn chain(providers: &[&Arc<ScriptedProvider>], health: Arc<ProviderHealth>) -> FailoverChain {
+    let providers = providers
+        .iter()
+        .map(|p| Arc::clone(p) as Arc<dyn LlmProvider>)
+        .collect();
+    FailoverChain::new(providers, health).with_retry(no_retry())
+}
+
+fn quota(provider: &str, remaining: u64, limit: u64) -> QuotaState {
+    QuotaState {
+        provider: provider.to_string(),
+        requests_remaining: Some(remaining),
+        requests_limit: Some(limit),
+        tokens_remaining: None,
+        tokens_limit: None,
+        reset_at: Some(Utc::now() + chrono::Duration::minutes(1)),
+        updated_at: Utc::now(),
+        source: cratos_llm::QuotaSource::ResponseHeaders,
+        remaining_fraction: None,
+        tier_label: None,
+    }
+}
+
+#[tokio::test]
+async fn test_primary_serves_when_healthy() {
+    let primary = ScriptedProvider::new("primary", vec![]);
+    let backup = ScriptedProvider::new("backup", vec![]);
+    let chain = chain(&[&primary, &backup], health());
+
+    let (response, served) =
+        track_served(chain.complete(CompletionRequest::new("primary-model"))).await;
+
+    assert_eq!(response.unwrap().content, "primary");
+    assert_eq!(served.as_deref(), Some("primary"));
+    assert_eq!(backup.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_fails_over_on_rate_limit() {
+    let primary = ScriptedProvider::new("primary", vec![Error::RateLimit]);
+    let backup = ScriptedProvider::new("backup", vec![]);
+    let chain = chain(&[&primary, &backup], health());
+
+    let (response, served) =
+        track_served(chain.complete(CompletionRequest::new("primary-model"))).await;
+
+    assert_eq!(response.unwrap().content, "backup");
+    assert_eq!(served.as_deref(), Some("backup"));
+    // The backup does not serve the primary's model, so it gets its own
+    assert_eq!(backup.last_model(), "backup-model");
+}
+
+#[tokio::test]
+async fn test_does_not_fail_over_on_request_errors() {
+    let primary = ScriptedProvider::new(
+        "primary",
+        vec![Error::InvalidResponse("bad tool schema".to_string())],
+    );
+    let backup = ScriptedProvider::new("backup", vec![]);
+    let chain = chain(&[&primary, &backup], health());
+
+    let result = chain
+        .complete(CompletionRequest::new("primary-model"))
+        .await;
+
+    assert!(matches!(result, Err(Error::InvalidResponse(_))));
+    assert_eq!(backup.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_retries_transient_errors_before_failing_over() {
+    let primary = ScriptedProvider::new("primary", vec![Error::Network("reset".to_string())]);
+    let backup = ScriptedProvider::new("backup", vec![]);
+    let chain = chain(&[&primary, &backup], health()).with_retry(
+        RetryConfig::default()
+            .with_max_attempts(2)
+            .with_initial_delay(Duration::from_millis(1)),
+    );
+
+    let (response, served) =
+        track_served(chain.complete(CompletionRequest::new("primary-model"))).await;
+
+    assert_eq!(response.unwrap().content, "primary");
+    assert_eq!(served.as_deref(), Some("primary"));
+    assert_eq!(primary.calls(), 2);
+    assert_eq!(backup.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_open_circuit_is_skipped() {
+    let health = health();
+    let primary = ScriptedProvider::new("primary", vec![Error::ServerError("503".to_string())]);
+    let backup = ScriptedProvider::new("backup", vec![]);
+    let chain = chain(&[&primary, &backup], health.clone());
+
+    chain
+        .complete(CompletionRequest::new("primary-model"))
+        .await
+        .unwrap();
+    assert_eq!(health.check("primary").await, Err(SkipReason::CircuitOpen));
+
+    // The next call goes straight to the backup
+    chain
+        .complete(CompletionRequest::new("primary-model"))
+        .await
+        .unwrap();
+    assert_eq!(primary.calls(), 1);
+    assert_eq!(backup.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_near_quota_limit_is_skipped() {
+    let tracker = Arc::new(QuotaTracker::new());
+    tracker.update_state(quota("primary", 2, 100)).await;
+    tracker.update_state(quota("backup", 90, 100)).await;
+    let health = Arc::new(
+        ProviderHealth::new(CircuitBreakerConfig::default()).with_quota_tracker(tracker.clone()),
+    );
+    let primary = ScriptedProvider::new("primary", vec![]);
+    let backup = ScriptedProvider::new("backup", vec![]);
+    let chain = chain(&[&primary, &backup], health.clone());
+
+    assert_eq!(
+        health.check("primary").await,
+        Err(SkipReason::NearQuotaLimit)
+    );
+    let (_, served) = track_served(chain.complete(CompletionRequest::new("primary-model"))).await;
+    assert_eq!(served.as_deref(), Some("backup"));
+
+    // Quota of an elapsed window no longer counts
+    let mut expired = quota("primary", 2, 100);
+    expired.reset_at = Some(Utc::now() - chrono::Duration::seconds(1));
+    tracker.update_state(expired).await;
+    assert_eq!(health.check("primary").await, Ok(()));
+}
+
+#[tokio::test]
+async fn test_all_unhealthy_tries_whole_chain() {
+    let health = health();
+    health.record_failure("primary");
+    health.record_failure("backup");
+    let primary = ScriptedProvider::new("primary", vec![]);
+    let backup = ScriptedProvider::new("backup", vec![]);
+    let chain = chain(&[&primary, &backup], health);
+
+    let (_, served) = track_served(chain.complete(CompletionRequest::new("primary-model"))).await;
+
+    assert_eq!(served.as_deref(), Some("primary"));
+}
+
+#[tokio::test]
+async fn test_returns_last_error_when_chain_exhausted() {
+    let primary = ScriptedProvider::new("primary", vec![Error::RateLimit]);
+    let backup = ScriptedProvider::new("backup", vec![Error::Timeout(1000)]);
+    let chain = chain(&[&primary, &backup], health());
+
+    let result = chain
+        .complete(CompletionRequest::new("primary-model"))
+        .await;
+
+    assert!(matches!(result, Err(Error::Timeout(1000))));
+}
+
+#[tokio::test]
+async fn test_nested_chain_reports_concrete_provider() {
+    let health = health();
+    let primary = ScriptedProvider::new("primary", vec![Error::RateLimit]);
+    let backup = ScriptedProvider::new("backup", vec![]);
+    let inner: Arc<dyn LlmProvider> = Arc::new(chain(&[&primary, &backup], health.clone()));
+    let outer = FailoverChain::new(vec![inner], health).with_retry(no_retry());
+
+    let (_, served) = track_served(outer.complete(CompletionRequest::new("primary-model"))).await;
+
+    assert_eq!(served.as_deref(), Some("backup"));
+}
+
+#[test]
+fn test_chains_from_config() {
+    let primary = ScriptedProvider::new("primary", vec![]);
+    let backup = ScriptedProvider::new("backup", vec![]);
+    let config: FailoverConfig = serde_json::from_value(serde_json::json!({
+        "chain": ["primary", "missing", "backup"],
+        "tasks": { "summarization": ["backup"] }
+    }))
+    .unwrap();
+    assert_eq!(config.retry_attempts, 2);
+
+    let chains = FailoverChains::from_config(&config, |name| match name {
+        "primary" => Some(primary.clone() as Arc<dyn LlmProvider>),
+        "backup" => Some(backup.clone() as Arc<dyn LlmProvider>),
+        _ => None,
+    })
+    .unwrap();
+
+    assert_eq!(
+        chains.for_task(TaskType::Planning).provider_names(),
+        vec!["primary", "backup"]
+    );
+    assert_eq!(
+        chains.for_task(TaskType::Summarization).provider_names(),
+        vec!["backup"]
+    );
+    assert!(FailoverChains::from_config(&FailoverConfig::default(), |_| None).is_none());
+}
@@ -7,6 +7,7 @@
 //! - Memory: Managing session and working memory contexts
 //! - Approval: Handling user approval flows for risky operations
 //! - Utils: Retry logic, circuit breaker, and other utilities
+//! - Failover: Provider failover chains with health tracking
 //! - Credentials: Secure credential storage
 //! - Security: Prompt injection defense
 //! - Pantheon: Persona preset system (Olympus OS)
@@ -29,6 +30,7 @@ pub mod discovery;
 pub mod error;
 pub mod event_bus;
 pub mod external_auth;
+pub mod failover;
 pub mod memory;
 /// Node management and permissions (secure remote execution graph).
 pub mod nodes;
@@ -53,7 +55,8 @@ pub mod utils;
 
 pub use a2a::{A2aMessage, A2aMessageSummary, A2aRouter};
 pub use approval::{
-    ApprovalError, ApprovalManager, ApprovalRequest, ApprovalStatus, SharedApprovalManager,
+    ApprovalError, ApprovalManager, ApprovalRequest, ApprovalStatus, ParkedExecution,
+    SharedApprovalManager,
 };
 pub use auth::{
     admin_scopes, default_user_scopes, ApiKeyInfo, AuthContext, AuthError, AuthMethod, AuthStore,
@@ -70,26 +73,31 @@ pub use device_auth::{
 pub use discovery::{DiscoveryConfig, DiscoveryService};
 pub use error::{format_error_for_chat, format_error_for_cli, Error, Result, UserFriendlyError};
 pub use event_bus::{EventBus, OrchestratorEvent};
+pub use failover::{
+    track_served, FailoverChain, FailoverChains, FailoverConfig, ProviderHealth, SkipReason,
+};
 pub use external_auth::{
     ExternalAuthError, ExternalAuthRegistry, ExternalAuthResult, ExternalAuthVerifier,
-    TailscaleVerifier,
+    OidcConfig, OidcVerifier, TailscaleVerifier,
 };
 pub use memory::{
-    MemoryStore, RedisStore, SessionBackend, SessionBackendConfig, SessionContext, SessionStore,
-    SqliteStore, ToolExecution, WorkingMemory,
+    CompactionConfig, CompactionReport, MemoryStore, RedisStore, SessionBackend,
+    SessionBackendConfig, SessionCompactor, SessionContext, SessionStore, SqliteStore,
+    ToolExecution, WorkingMemory,
 };
 pub use nodes::{
     Node, NodeError, NodeRegisterParams, NodeRegistry, NodeStatus, NodeSummary, Platform,
 };
 pub use orchestrator::{
-    ExecutionResult, ExecutionStatus, Orchestrator, OrchestratorConfig, OrchestratorInput,
-    SkillMatch, SkillRouting, ToolCallRecord,
+    ExecutionResult, ExecutionStatus, ForkRequest, ForkRun, McpPromptCommand, McpResourceRef,
+    Orchestrator, OrchestratorConfig, OrchestratorInput, ReplayRun, SkillMatch, SkillRouting,
+    ToolCallRecord,
 };
 pub use permissions::{
     ChannelPermissions, ChannelToolConfig, PermissionConfig, PermissionError, PermissionManager,
     PermissionStatus, TimeRestrictions, ToolPermissions,
 };
-pub use planner::{PlanResponse, PlanStep, Planner, PlannerConfig};
+pub use planner::{DeltaCallback, PlanResponse, PlanStep, Planner, PlannerConfig};
 pub use queue::{ExecutionQueue, QueueConfig, QueueMode, QueuePermit};
 pub use security::{
     sanitize_input, validate_tool_output, InjectionDetector, InjectionError, InjectionPattern,
@@ -97,8 +105,8 @@ pub use security::{
 };
 pub use session_manager::{SessionManager, SessionStatus, SessionSummary};
 pub use tool_policy::{
-    PolicyAction, PolicyContext, PolicyDenial, PolicyLevel, PolicyRule, ToolPolicy,
-    ToolSecurityPolicy,
+    ApprovalPolicy, ApprovalRule, ApproverSpec, PolicyAction, PolicyContext, PolicyDenial,
+    PolicyLevel, PolicyRule, ToolPolicy, ToolSecurityPolicy,
 };
 pub use utils::{
     metrics_global, retry_with_backoff, CircuitBreaker, CircuitBreakerConfig, CircuitState,
@@ -147,8 +155,9 @@ pub use decrees::{
 
 // Re-export scheduler module types
 pub use scheduler::{
-    Comparison, CronTrigger, FileEvent, FileTrigger, IntervalTrigger, OneTimeTrigger,
-    ScheduledTask, SchedulerConfig, SchedulerEngine, SchedulerEngineBuilder, SchedulerError,
-    SchedulerResult, SchedulerStore, SystemMetric, SystemTrigger, TaskAction, TaskExecution,
+    ChainStep, Comparison, CronSchedule, CronTrigger, FileEvent, FileTrigger, IntervalTrigger,
+    JsonOp, MisfirePolicy, OneTimeTrigger, OverlapPolicy, RunDispatch, ScheduledTask,
+    SchedulerConfig, SchedulerEngine, SchedulerEngineBuilder, SchedulerError, SchedulerResult,
+    SchedulerStore, StepCondition, SystemMetric, SystemTrigger, TaskAction, TaskExecution,
     TriggerType,
 };
@@ -0,0 +1,233 @@
+//! LLM-based conversation compaction
+//!
+//! Instead of silently dropping the oldest messages when a session grows too
+//! large, the compactor asks the configured provider to summarize the oldest
+//! span and pins the result as a "conversation so far" system message.
+
+use crate::error::{Error, Result};
+use cratos_llm::{CompletionRequest, LlmProvider, Message, MessageRole};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use super::SessionContext;
+
+/// Instructions given to the model when summarizing a span
+const COMPACTION_PROMPT: &str = "You compress chat history. Summarize the conversation \
+below so the assistant can continue it without the original messages. Keep decisions, \
+facts about the user, open tasks, file paths, commands and tool results that are still \
+relevant. Drop greetings and small talk. Write concise bullet points in the language \
+of the conversation.";
+
+/// Maximum characters of a single message included in the summarization prompt
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+/// Compaction settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactionConfig {
+    /// Compact automatically when the threshold is exceeded
+    pub enabled: bool,
+    /// Session token count that triggers automatic compaction
+    pub threshold_tokens: usize,
+    /// Number of most recent messages kept verbatim
+    pub keep_recent: usize,
+    /// Maximum tokens for the generated summary
+    pub max_summary_tokens: u32,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_tokens: 60_000,
+            keep_recent: 10,
+            max_summary_tokens: 1024,
+        }
+    }
+}
+
+impl CompactionConfig {
+    /// Create a new configuration
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable automatic compaction
+    #[must_use]
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the token threshold that triggers automatic compaction
+    #[must_use]
+    pub fn with_threshold(mut self, tokens: usize) -> Self {
+        self.threshold_tokens = tokens;
+        self
+    }
+
+    /// Set how many recent messages are kept verbatim
+    #[must_use]
+    pub fn with_keep_recent(mut self, count: usize) -> Self {
+        self.keep_recent = count;
+        self
+    }
+}
+
+/// Outcome of a compaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactionReport {
+    /// Number of messages replaced by the summary
+    pub messages_compacted: usize,
+    /// Token count before compaction
+    pub tokens_before: usize,
+    /// Token count after compaction
+    pub tokens_after: usize,
+    /// The generated summary
+    pub summary: String,
+}
+
+/// Summarizes old conversation spans through an LLM provider
+pub struct SessionCompactor {
+    provider: Arc<dyn LlmProvider>,
+    config: CompactionConfig,
+}
+
+impl SessionCompactor {
+    /// Create a new compactor
+    #[must_use]
+    pub fn new(provider: Arc<dyn LlmProvider>, config: CompactionConfig) -> Self {
+        Self { provider, config }
+    }
+
+    /// Get the configuration
+    #[must_use]
+    pub fn config(&self) -> &CompactionConfig {
+        &self.config
+    }
+
+    /// Check whether automatic compaction should run for this session
+    #[must_use]
+    pub fn should_compact(&self, session: &SessionContext) -> bool {
+        self.config.enabled && session.token_count() > self.config.threshold_tokens
+    }
+
+    /// Compact the session regardless of the threshold.
+    ///
+    /// Returns `Ok(None)` when there is nothing old enough to summarize.
+    pub async fn compact(&self, session: &mut SessionContext) -> Result<Option<CompactionReport>> {
+        let indices = session.compaction_candidates(self.config.keep_recent);
+        if indices.is_empty() {
+            debug!(session_key = %session.session_key, "Nothing to compact");
+            return Ok(None);
+        }
+
+        let tokens_before = session.token_count();
+        let transcript = Self::build_transcript(session.summary(), &indices, session);
+
+        let request = CompletionRequest {
+            model: self.provider.default_model().to_string(),
+            messages: vec![
+                Message::system(COMPACTION_PROMPT),
+                Message::user(transcript),
+            ],
+            max_tokens: Some(self.config.max_summary_tokens),
+            temperature: Some(0.2),
+            stop: None,
+        };
+        let response = self.provider.complete(request).await.map_err(Error::Llm)?;
+        let summary = response.content.trim().to_string();
+        if summary.is_empty() {
+            return Err(Error::Execution(
+                "compaction produced an empty summary".to_string(),
+            ));
+        }
+
+        let messages_compacted = session.apply_compaction(&indices, summary.clone());
+        let tokens_after = session.token_count();
+
+        info!(
+            session_key = %session.session_key,
+            messages_compacted,
+            tokens_before,
+            tokens_after,
+            "Session compacted"
+        );
+
+        Ok(Some(CompactionReport {
+            messages_compacted,
+            tokens_before,
+            tokens_after,
+            summary,
+        }))
+    }
+
+    /// Render the previous summary and the evicted span as plain text
+    fn build_transcript(
+        previous_summary: Option<&str>,
+        indices: &[usize],
+        session: &SessionContext,
+    ) -> String {
+        let mut out = String::new();
+        if let Some(previous) = previous_summary {
+            out.push_str("Earlier summary:\n");
+            out.push_str(previous);
+            out.push_str("\n\n");
+        }
+        out.push_str("Conversation:\n");
+        let messages = session.get_messages();
+        for &idx in indices {
+            let msg = &messages[idx];
+            let role = match msg.role {
+                MessageRole::System => "system",
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+                MessageRole::Tool => "tool",
+            };
+            let content = if msg.content.len() > MAX_MESSAGE_CHARS {
+                format!(
+                    "{}...",
+                    cratos_llm::util::truncate_safe(&msg.content, MAX_MESSAGE_CHARS)
+                )
+            } else {
+                msg.content.clone()
+            };
+            out.push_str(&format!("[{}] {}\n", role, content));
+            for call in &msg.tool_calls {
+                out.push_str(&format!("[{} -> {}] {}\n", role, call.name, call.arguments));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cratos_llm::MockProvider;
+
+    #[tokio::test]
+    async fn test_compact_replaces_old_span_with_summary() {
+        let compactor = SessionCompactor::new(
+            Arc::new(MockProvider::new()),
+            CompactionConfig::new().with_keep_recent(2),
+        );
+        let mut session = SessionContext::new("test:channel:user");
+        for i in 0..3 {
+            session.add_user_message(format!("question {}", i));
+            session.add_assistant_message(format!("answer {}", i));
+        }
+
+        let report = compactor.compact(&mut session).await.unwrap().unwrap();
+        assert_eq!(report.messages_compacted, 4);
+        assert_eq!(report.summary, "mock response");
+        assert_eq!(session.message_count(), 3);
+        assert_eq!(session.summary(), Some("mock response"));
+        assert_eq!(session.compactions.len(), 1);
+        assert_eq!(session.compactions[0].messages[0].content, "question 0");
+
+        // Remaining span is only the kept tail: nothing more to compact
+        assert!(compactor.compact(&mut session).await.unwrap().is_none());
+    }
+}
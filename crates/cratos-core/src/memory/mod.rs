@@ -2,6 +2,7 @@
 //!
 //! This module provides session and memory management for the orchestrator:
 //! - Session context (conversation history)
+//! - LLM-based compaction of old conversation spans
 //! - Working memory (temporary state during execution)
 //! - SQLite store (default, production-ready)
 //! - Redis store (optional, for high-scale scenarios)
@@ -17,6 +18,7 @@
 //! Redis is available for high-scale deployments but is entirely optional.
 
 mod cache;
+mod compaction;
 mod persistent;
 mod redis_store;
 mod session;
@@ -24,8 +26,9 @@ mod store;
 mod working;
 
 pub use cache::MemoryStore;
+pub use compaction::{CompactionConfig, CompactionReport, SessionCompactor};
 pub use persistent::{SessionBackend, SessionBackendConfig, SqliteStore};
 pub use redis_store::RedisStore;
-pub use session::SessionContext;
+pub use session::{CompactedSpan, SessionContext, COMPACTION_SUMMARY_PREFIX};
 pub use store::SessionStore;
 pub use working::{ToolExecution, WorkingMemory};
@@ -10,9 +10,18 @@
 //! - Default token budget: 100,000 tokens per session
 //! - Importance-weighted trimming: Preserves tool_result > user > assistant messages
 //! - System messages are always preserved
+//!
+//! ## Compaction
+//!
+//! As an alternative to dropping messages, the oldest span of the conversation
+//! can be replaced by an LLM-written summary (see [`super::SessionCompactor`]).
+//! The summary is pinned as a system message and the replaced messages are
+//! archived in [`SessionContext::compactions`].
 
 use chrono::{DateTime, Utc};
-use cratos_llm::{count_message_tokens, Message, MessageRole};
+use cratos_llm::{
+    count_message_tokens, count_message_tokens_for, Message, MessageRole, TokenizerKey,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{debug, warn};
@@ -24,6 +33,20 @@ const DEFAULT_MAX_CONTEXT_SIZE: usize = 50;
 /// Default maximum tokens per session context
 const DEFAULT_MAX_TOKENS: usize = 100_000;
 
+/// Prefix marking the pinned "conversation so far" summary message
+pub const COMPACTION_SUMMARY_PREFIX: &str = "[Conversation so far]";
+
+/// A span of conversation replaced by a summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactedSpan {
+    /// When the span was compacted
+    pub compacted_at: DateTime<Utc>,
+    /// Summary that replaced the span
+    pub summary: String,
+    /// Original messages (stored copy of what the summary replaced)
+    pub messages: Vec<Message>,
+}
+
 /// Message importance levels for trimming priority
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum MessageImportance {
@@ -73,6 +96,12 @@ pub struct SessionContext {
     /// Enable token-aware trimming (vs legacy message-count)
     #[serde(default = "default_token_aware")]
     pub token_aware_trimming: bool,
+    /// Spans replaced by summaries, oldest first
+    #[serde(default)]
+    pub compactions: Vec<CompactedSpan>,
+    /// Provider/model whose tokenizer counts the context (cl100k_base if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenizer: Option<TokenizerKey>,
 }
 
 fn default_max_context_size() -> usize {
@@ -101,6 +130,8 @@ impl SessionContext {
             max_tokens: DEFAULT_MAX_TOKENS,
             current_tokens: 0,
             token_aware_trimming: true,
+            compactions: Vec::new(),
+            tokenizer: None,
         }
     }
 
@@ -161,6 +192,13 @@ impl SessionContext {
         self.trim_if_needed();
     }
 
+    /// Add a prebuilt message (e.g., an assistant turn with tool calls)
+    pub fn add_message(&mut self, message: Message) {
+        self.messages.push(message);
+        self.last_activity = Utc::now();
+        self.trim_if_needed();
+    }
+
     /// Get messages for LLM context
     #[must_use]
     pub fn get_messages(&self) -> &[Message] {
@@ -178,6 +216,7 @@ impl SessionContext {
     /// Clear all messages
     pub fn clear(&mut self) {
         self.messages.clear();
+        self.compactions.clear();
         self.last_activity = Utc::now();
     }
 
@@ -192,6 +231,27 @@ impl SessionContext {
         self.metadata.get(key)
     }
 
+    /// Count tokens with the tokenizer of the model the session talks to
+    ///
+    /// Re-trims the context, since the same messages may now count higher.
+    pub fn set_tokenizer(&mut self, provider: impl Into<String>, model: impl Into<String>) {
+        let key = TokenizerKey::new(provider, model);
+        if self.tokenizer.as_ref() == Some(&key) {
+            return;
+        }
+        self.tokenizer = Some(key);
+        self.current_tokens = self.count_tokens(&self.messages);
+        self.trim_if_needed();
+    }
+
+    /// Count tokens in messages with the session's tokenizer
+    fn count_tokens(&self, messages: &[Message]) -> usize {
+        match &self.tokenizer {
+            Some(key) => count_message_tokens_for(&key.provider, &key.model, messages),
+            None => count_message_tokens(messages),
+        }
+    }
+
     /// Trim messages if exceeding limits
     ///
     /// Uses token-aware trimming by default, which:
@@ -221,7 +281,7 @@ impl SessionContext {
     /// at the same priority as user messages.
     fn trim_by_tokens(&mut self) {
         // Recalculate current token count
-        self.current_tokens = count_message_tokens(&self.messages);
+        self.current_tokens = self.count_tokens(&self.messages);
 
         if self.current_tokens <= self.max_tokens {
             return;
@@ -269,7 +329,7 @@ impl SessionContext {
         // Calculate sacred tokens (first system message only)
         let sacred_tokens = sacred_system
             .as_ref()
-            .map(|m| count_message_tokens(std::slice::from_ref(m)))
+            .map(|m| self.count_tokens(std::slice::from_ref(m)))
             .unwrap_or(0);
         let available_tokens = self.max_tokens.saturating_sub(sacred_tokens);
 
@@ -279,7 +339,7 @@ impl SessionContext {
 
         // Iterate from highest importance (end) to lowest (start)
         for (idx, msg) in trimmable.into_iter().rev() {
-            let msg_tokens = count_message_tokens(std::slice::from_ref(&msg));
+            let msg_tokens = self.count_tokens(std::slice::from_ref(&msg));
 
             if kept_tokens + msg_tokens <= available_tokens {
                 kept_tokens += msg_tokens;
@@ -305,7 +365,7 @@ impl SessionContext {
             .extend(kept_messages.into_iter().map(|(_, msg)| msg));
 
         // Update cached token count
-        self.current_tokens = count_message_tokens(&self.messages);
+        self.current_tokens = self.count_tokens(&self.messages);
 
         warn!(
             session_id = %self.id,
@@ -319,7 +379,7 @@ impl SessionContext {
     #[must_use]
     pub fn token_count(&self) -> usize {
         if self.current_tokens == 0 && !self.messages.is_empty() {
-            count_message_tokens(&self.messages)
+            self.count_tokens(&self.messages)
         } else {
             self.current_tokens
         }
@@ -334,7 +394,7 @@ impl SessionContext {
     /// Check if adding a message would exceed token budget
     #[must_use]
     pub fn would_exceed_budget(&self, message: &Message) -> bool {
-        let msg_tokens = count_message_tokens(std::slice::from_ref(message));
+        let msg_tokens = self.count_tokens(std::slice::from_ref(message));
         self.token_count() + msg_tokens > self.max_tokens
     }
 
@@ -367,7 +427,7 @@ impl SessionContext {
         }
 
         // Update cached token count
-        self.current_tokens = count_message_tokens(&self.messages);
+        self.current_tokens = self.count_tokens(&self.messages);
 
         debug!(
             session_id = %self.id,
@@ -394,7 +454,7 @@ impl SessionContext {
         for (i, msg) in context_messages.into_iter().enumerate() {
             self.messages.insert(insert_pos + i, msg);
         }
-        self.current_tokens = count_message_tokens(&self.messages);
+        self.current_tokens = self.count_tokens(&self.messages);
         self.trim_if_needed();
     }
 
@@ -409,7 +469,7 @@ impl SessionContext {
         });
         let removed = before - self.messages.len();
         if removed > 0 {
-            self.current_tokens = count_message_tokens(&self.messages);
+            self.current_tokens = self.count_tokens(&self.messages);
             debug!(
                 session_id = %self.id,
                 removed = removed,
@@ -424,6 +484,94 @@ impl SessionContext {
     pub fn message_count(&self) -> usize {
         self.messages.len()
     }
+
+    /// Get the pinned conversation summary (without its prefix), if any
+    #[must_use]
+    pub fn summary(&self) -> Option<&str> {
+        self.messages
+            .iter()
+            .find(|m| Self::is_summary_message(m))
+            .map(|m| {
+                m.content
+                    .strip_prefix(COMPACTION_SUMMARY_PREFIX)
+                    .unwrap_or(&m.content)
+                    .trim_start()
+            })
+    }
+
+    /// Messages that compaction would replace, keeping the last `keep_recent`.
+    ///
+    /// System messages and the existing summary are never included. The split
+    /// point is moved forward past tool results so a tool response is never
+    /// separated from the assistant message that requested it.
+    #[must_use]
+    pub fn compaction_candidates(&self, keep_recent: usize) -> Vec<usize> {
+        let mut split = self.messages.len().saturating_sub(keep_recent);
+        while split < self.messages.len() && self.messages[split].role == MessageRole::Tool {
+            split += 1;
+        }
+
+        (0..split)
+            .filter(|&i| self.messages[i].role != MessageRole::System)
+            .collect()
+    }
+
+    /// Replace the messages at `indices` with a pinned summary.
+    ///
+    /// An existing summary is replaced (the caller is expected to have folded
+    /// it into `summary`). The removed messages are archived in
+    /// [`Self::compactions`]. Returns the number of messages removed.
+    pub fn apply_compaction(&mut self, indices: &[usize], summary: impl Into<String>) -> usize {
+        if indices.is_empty() {
+            return 0;
+        }
+        let summary = summary.into();
+
+        let mut evicted = Vec::with_capacity(indices.len());
+        let mut kept = Vec::with_capacity(self.messages.len());
+        for (idx, msg) in self.messages.drain(..).enumerate() {
+            if Self::is_summary_message(&msg) {
+                continue;
+            }
+            if indices.binary_search(&idx).is_ok() {
+                evicted.push(msg);
+            } else {
+                kept.push(msg);
+            }
+        }
+
+        // Pin the summary right after the leading system messages
+        let insert_pos = kept
+            .iter()
+            .take_while(|m| m.role == MessageRole::System)
+            .count();
+        kept.insert(
+            insert_pos,
+            Message::system(format!("{}\n{}", COMPACTION_SUMMARY_PREFIX, summary)),
+        );
+        self.messages = kept;
+
+        let removed = evicted.len();
+        self.compactions.push(CompactedSpan {
+            compacted_at: Utc::now(),
+            summary,
+            messages: evicted,
+        });
+        self.current_tokens = self.count_tokens(&self.messages);
+        self.last_activity = Utc::now();
+
+        debug!(
+            session_id = %self.id,
+            removed = removed,
+            new_token_count = self.current_tokens,
+            "Session context compacted"
+        );
+        removed
+    }
+
+    fn is_summary_message(msg: &Message) -> bool {
+        msg.role == MessageRole::System && msg.content.starts_with(COMPACTION_SUMMARY_PREFIX)
+    }
 }
 
 #[cfg(test)]
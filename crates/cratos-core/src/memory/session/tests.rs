@@ -54,6 +54,35 @@
         assert!(ctx.token_count() > 0);
     }
 
+    #[test]
+    fn test_set_tokenizer_recounts_and_trims() {
+        let mut ctx = SessionContext::with_token_budget("test:key", 400);
+        for i in 0..20 {
+            ctx.add_user_message(format!("Question {} about the deployment pipeline", i));
+        }
+        let default_count = ctx.token_count();
+        let default_messages = ctx.message_count();
+
+        // Claude counts more tokens for the same text, so fewer messages fit
+        ctx.set_tokenizer("anthropic", "claude-sonnet-4-5");
+        assert!(ctx.token_count() <= 400);
+        assert!(ctx.message_count() <= default_messages);
+        assert_eq!(
+            ctx.token_count(),
+            cratos_llm::count_message_tokens_for(
+                "anthropic",
+                "claude-sonnet-4-5",
+                ctx.get_messages()
+            )
+        );
+        assert!(default_count > 0);
+
+        // The tokenizer survives serialization
+        let json = serde_json::to_string(&ctx).unwrap();
+        let restored: SessionContext = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.tokenizer, ctx.tokenizer);
+    }
+
     #[test]
     fn test_with_token_budget() {
         let ctx = SessionContext::with_token_budget("test:key", 50_000);
@@ -141,3 +170,39 @@
         assert!(MessageImportance::ToolResult > MessageImportance::User);
         assert!(MessageImportance::User > MessageImportance::Assistant);
     }
+
+    #[test]
+    fn test_compaction_keeps_tool_results_with_their_call() {
+        let mut ctx = SessionContext::new("test:key");
+        ctx.add_system_message("Relevant saved memories: none");
+        ctx.add_user_message("List files");
+        ctx.add_assistant_message("Running ls");
+        ctx.add_tool_message("a.txt b.txt", "call_1");
+        ctx.add_assistant_message("Two files");
+
+        // keep_recent = 2 would split at the tool result; it is pulled into the span
+        let indices = ctx.compaction_candidates(2);
+        assert_eq!(indices, vec![1, 2, 3]);
+
+        let removed = ctx.apply_compaction(&indices, "User listed files");
+        assert_eq!(removed, 3);
+        assert_eq!(ctx.message_count(), 3);
+        assert!(ctx.get_messages()[1]
+            .content
+            .starts_with(COMPACTION_SUMMARY_PREFIX));
+        assert_eq!(ctx.summary(), Some("User listed files"));
+        assert_eq!(ctx.compactions[0].messages.len(), 3);
+
+        // A second compaction replaces the pinned summary instead of stacking
+        ctx.add_user_message("Thanks");
+        let indices = ctx.compaction_candidates(0);
+        ctx.apply_compaction(&indices, "Merged summary");
+        assert_eq!(ctx.summary(), Some("Merged summary"));
+        assert_eq!(
+            ctx.get_messages()
+                .iter()
+                .filter(|m| m.content.starts_with(COMPACTION_SUMMARY_PREFIX))
+                .count(),
+            1
+        );
+    }
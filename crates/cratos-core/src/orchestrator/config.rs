@@ -4,8 +4,10 @@
 //! - `OrchestratorConfig` for orchestrator settings
 //! - `OrchestratorInput` for execution input
 
-use crate::memory::SessionContext;
+use crate::approval::ParkedExecution;
+use crate::memory::{CompactionConfig, SessionContext};
 use crate::planner::PlannerConfig;
+use cratos_memory::MemoryScope;
 use cratos_tools::RunnerConfig;
 
 /// Input for orchestration
@@ -25,6 +27,37 @@ pub struct OrchestratorInput {
     pub system_prompt_override: Option<String>,
     /// Inline images from the channel (e.g., Telegram photo messages)
     pub images: Vec<cratos_llm::ImageContent>,
+    /// MCP resources to read and attach as context
+    pub mcp_resources: Vec<McpResourceRef>,
+    /// Conversation to continue instead of the stored session (used by forks)
+    pub history: Option<Vec<cratos_llm::Message>>,
+    /// Recorded execution this input was forked from
+    pub forked_from: Option<ForkPoint>,
+    /// Persona to answer as, instead of routing on `@mentions`
+    pub persona: Option<String>,
+    /// Approved request this execution resumes; its grant covers the approved call
+    pub resumed_approval: Option<uuid::Uuid>,
+    /// Results of the calls that finished before the approval, when `history`
+    /// ends with the tool round the execution was parked in
+    pub resumed_tool_results: Option<Vec<serde_json::Value>>,
+}
+
+/// Event of a recorded execution that a fork starts from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ForkPoint {
+    /// Parent execution ID
+    pub execution_id: uuid::Uuid,
+    /// Sequence number of the event the fork starts after
+    pub sequence_num: i32,
+}
+
+/// Reference to a resource on a connected MCP server
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct McpResourceRef {
+    /// MCP server name
+    pub server: String,
+    /// Resource URI
+    pub uri: String,
 }
 
 impl OrchestratorInput {
@@ -44,6 +77,12 @@ impl OrchestratorInput {
             text: text.into(),
             system_prompt_override: None,
             images: Vec::new(),
+            mcp_resources: Vec::new(),
+            history: None,
+            forked_from: None,
+            persona: None,
+            resumed_approval: None,
+            resumed_tool_results: None,
         }
     }
 
@@ -61,6 +100,52 @@ impl OrchestratorInput {
         self
     }
 
+    /// Attach an MCP resource as context
+    #[must_use]
+    pub fn with_mcp_resource(mut self, server: impl Into<String>, uri: impl Into<String>) -> Self {
+        self.mcp_resources.push(McpResourceRef {
+            server: server.into(),
+            uri: uri.into(),
+        });
+        self
+    }
+
+    /// Continue a forked conversation
+    ///
+    /// An empty `text` continues from the history without a new user message.
+    #[must_use]
+    pub fn with_fork(mut self, point: ForkPoint, history: Vec<cratos_llm::Message>) -> Self {
+        self.forked_from = Some(point);
+        self.history = Some(history);
+        self
+    }
+
+    /// Answer as the given persona (e.g., "sindri")
+    ///
+    /// Unknown personas fall back to normal routing.
+    #[must_use]
+    pub fn with_persona(mut self, persona: impl Into<String>) -> Self {
+        self.persona = Some(persona.into());
+        self
+    }
+
+    /// Continue a conversation supplied by the caller instead of the stored session
+    ///
+    /// The last message of the conversation goes in `text`; `history` holds
+    /// everything before it.
+    #[must_use]
+    pub fn with_history(mut self, history: Vec<cratos_llm::Message>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Resume the execution parked on an approved request
+    #[must_use]
+    pub fn with_resumed_approval(mut self, request_id: uuid::Uuid) -> Self {
+        self.resumed_approval = Some(request_id);
+        self
+    }
+
     /// Override the system prompt (e.g., for workflow-driven execution)
     #[must_use]
     pub fn with_system_prompt_override(mut self, prompt: String) -> Self {
@@ -73,6 +158,33 @@ impl OrchestratorInput {
     pub fn session_key(&self) -> String {
         SessionContext::make_key(&self.channel_type, &self.channel_id, &self.user_id)
     }
+
+    /// Graph RAG memory scope of the requesting user in this channel
+    #[must_use]
+    pub fn memory_scope(&self) -> MemoryScope {
+        MemoryScope::user(&self.user_id).with_channel(MemoryScope::channel_key(
+            &self.channel_type,
+            &self.channel_id,
+        ))
+    }
+}
+
+impl From<ParkedExecution> for OrchestratorInput {
+    fn from(parked: ParkedExecution) -> Self {
+        let mut input = Self::new(
+            parked.channel_type,
+            parked.channel_id,
+            parked.user_id,
+            parked.text,
+        );
+        input.thread_id = parked.thread_id;
+        // Continue the parked tool round; older requests only have the text
+        if !parked.history.is_empty() {
+            input.history = Some(parked.history);
+            input.resumed_tool_results = Some(parked.tool_results);
+        }
+        input
+    }
 }
 
 /// Configuration for the orchestrator
@@ -94,6 +206,12 @@ pub struct OrchestratorConfig {
     pub max_total_failures: usize,
     /// Enable automatic skill pattern detection
     pub auto_skill_detection: bool,
+    /// Emit `ChatDelta` events as tokens stream in (when the provider supports it)
+    pub stream_responses: bool,
+    /// Summarize old conversation spans instead of dropping them
+    pub compaction: CompactionConfig,
+    /// Maximum low-risk tool calls of one turn that run concurrently (1 = sequential)
+    pub max_parallel_tools: usize,
 }
 
 impl Default for OrchestratorConfig {
@@ -107,6 +225,9 @@ impl Default for OrchestratorConfig {
             max_consecutive_failures: 3,
             max_total_failures: 6,
             auto_skill_detection: true,
+            stream_responses: true,
+            compaction: CompactionConfig::default(),
+            max_parallel_tools: 4,
         }
     }
 }
@@ -152,4 +273,25 @@ impl OrchestratorConfig {
         self.auto_skill_detection = enabled;
         self
     }
+
+    /// Set whether to stream response text as `ChatDelta` events
+    #[must_use]
+    pub fn with_streaming(mut self, enabled: bool) -> Self {
+        self.stream_responses = enabled;
+        self
+    }
+
+    /// Set conversation compaction configuration
+    #[must_use]
+    pub fn with_compaction(mut self, config: CompactionConfig) -> Self {
+        self.compaction = config;
+        self
+    }
+
+    /// Set how many low-risk tool calls of one turn may run concurrently
+    #[must_use]
+    pub fn with_max_parallel_tools(mut self, max: usize) -> Self {
+        self.max_parallel_tools = max;
+        self
+    }
 }
@@ -5,16 +5,18 @@
 use crate::agents::PersonaMapping;
 use crate::approval::SharedApprovalManager;
 use crate::event_bus::{EventBus, OrchestratorEvent};
+use crate::failover::FailoverChains;
 use crate::memory::{MemoryStore, SessionStore};
 use crate::olympus_hooks::OlympusHooks;
 use crate::planner::Planner;
 use crate::tool_policy::ToolSecurityPolicy;
-use cratos_llm::LlmProvider;
+use cratos_llm::{LlmProvider, TaskType};
 use cratos_memory::GraphMemory;
 use cratos_replay::EventStoreTrait;
-use cratos_tools::{ToolDoctor, ToolRegistry, ToolRunner};
+use cratos_tools::{McpClient, ToolDoctor, ToolRegistry, ToolRunner};
 use dashmap::DashMap;
 use std::sync::Arc;
+use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 use tracing::info;
 use uuid::Uuid;
@@ -33,6 +35,8 @@ pub struct Orchestrator {
     pub(crate) olympus_hooks: Option<OlympusHooks>,
     pub(crate) graph_memory: Option<Arc<GraphMemory>>,
     pub(crate) fallback_planner: Option<Planner>,
+    /// Per-task failover chains (the planner runs on the planning chain)
+    pub(crate) failover: Option<Arc<FailoverChains>>,
     pub(crate) persona_mapping: Option<PersonaMapping>,
     pub(crate) skill_router: Option<Arc<dyn SkillRouting>>,
     pub(crate) security_policy: Option<ToolSecurityPolicy>,
@@ -40,6 +44,8 @@ pub struct Orchestrator {
     pub(crate) persona_skill_store: Option<Arc<cratos_skills::PersonaSkillStore>>,
     /// Chronicle store for tracking persona quests and history
     pub(crate) chronicle_store: Option<Arc<crate::chronicles::ChronicleStore>>,
+    /// MCP client for prompt commands and resource attachments
+    pub(crate) mcp_client: Option<Arc<RwLock<McpClient>>>,
     pub(crate) doctor: ToolDoctor,
     pub(crate) config: OrchestratorConfig,
     /// Active executions with cancellation tokens for chat.cancel support
@@ -69,11 +75,13 @@ impl Orchestrator {
             olympus_hooks: None,
             graph_memory: None,
             fallback_planner: None,
+            failover: None,
             persona_mapping: None,
             skill_router: None,
             security_policy: None,
             persona_skill_store: None,
             chronicle_store: None,
+            mcp_client: None,
             doctor: ToolDoctor::new(),
             config,
             active_executions: Arc::new(DashMap::new()),
@@ -130,6 +138,19 @@ impl Orchestrator {
         self
     }
 
+    /// Route LLM calls through failover chains.
+    ///
+    /// Planning uses the chain for [`TaskType::Planning`] and session
+    /// compaction the one for [`TaskType::Summarization`]. The chains replace
+    /// the single fallback provider.
+    pub fn with_failover(mut self, chains: FailoverChains) -> Self {
+        let planning = chains.for_task(TaskType::Planning);
+        self.planner = Planner::new(planning, self.config.planner_config.clone());
+        self.fallback_planner = None;
+        self.failover = Some(Arc::new(chains));
+        self
+    }
+
     /// Set the persona mapping for @mention and LLM-based routing
     pub fn with_persona_mapping(mut self, mapping: PersonaMapping) -> Self {
         self.persona_mapping = Some(mapping);
@@ -163,6 +184,12 @@ impl Orchestrator {
         self
     }
 
+    /// Set the MCP client (enables prompt slash commands and resource attachments)
+    pub fn with_mcp_client(mut self, client: Arc<RwLock<McpClient>>) -> Self {
+        self.mcp_client = Some(client);
+        self
+    }
+
     /// Get the tool runner
     #[must_use]
     pub fn runner(&self) -> &ToolRunner {
@@ -175,6 +202,12 @@ impl Orchestrator {
         self.approval_manager.as_ref()
     }
 
+    /// Get the persona mapping (if configured)
+    #[must_use]
+    pub fn persona_mapping(&self) -> Option<&PersonaMapping> {
+        self.persona_mapping.as_ref()
+    }
+
     /// Get the active executions map (for chat.cancel support)
     #[must_use]
     pub fn active_executions(&self) -> &Arc<DashMap<Uuid, CancellationToken>> {
@@ -202,12 +235,7 @@ impl Orchestrator {
     /// List all registered tool names
     #[must_use]
     pub fn list_tool_names(&self) -> Vec<String> {
-        self.runner
-            .registry()
-            .list_names()
-            .iter()
-            .map(|s| s.to_string())
-            .collect()
+        self.runner.registry().all_names()
     }
 
     /// Cancel an active execution by ID
@@ -0,0 +1,247 @@
+//! Forking recorded executions
+//!
+//! A fork rebuilds the conversation of a recorded execution up to a chosen
+//! event, applies an edit (the next user message or one tool result) and
+//! continues from there as a new execution linked to its parent.
+
+use crate::error::{Error, Result};
+use crate::planner::Planner;
+use cratos_llm::{Message, ToolCall};
+use cratos_replay::cassette::tool_name;
+use cratos_replay::{Event, EventType, RecordedLlmResponse};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use uuid::Uuid;
+
+use super::config::{ForkPoint, OrchestratorInput};
+use super::core::Orchestrator;
+use super::types::ExecutionStatus;
+
+/// Where to fork a recorded execution and what to change
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForkRequest {
+    /// Sequence number of the event to fork at
+    pub sequence_num: i32,
+    /// Replaces the user message at the fork point; elsewhere it is sent as
+    /// the next user message
+    #[serde(default)]
+    pub user_message: Option<String>,
+    /// Replaces the output of the tool result at the fork point
+    #[serde(default)]
+    pub tool_result: Option<serde_json::Value>,
+}
+
+/// Outcome of a fork
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkRun {
+    /// Execution that was forked
+    pub parent_execution_id: Uuid,
+    /// Sequence number the fork starts from
+    pub sequence_num: i32,
+    /// New execution created by the fork
+    pub execution_id: Uuid,
+    /// Final status of the fork
+    pub status: ExecutionStatus,
+    /// Final response of the fork
+    pub response: String,
+}
+
+/// Conversation rebuilt for a fork
+#[derive(Debug)]
+pub(crate) struct ForkHistory {
+    /// Messages before the continuation
+    pub messages: Vec<Message>,
+    /// User message to continue with (empty to continue from a tool result)
+    pub text: String,
+    /// User of the original execution
+    pub user_id: String,
+}
+
+/// Rebuild the message history of `events` up to the fork point
+pub(crate) fn rebuild_history(events: &[Event], request: &ForkRequest) -> Result<ForkHistory> {
+    let fork_at = request.sequence_num;
+    let fork_event = events
+        .iter()
+        .find(|e| e.sequence_num == fork_at)
+        .ok_or_else(|| Error::NotFound(format!("event #{}", fork_at)))?;
+    if request.tool_result.is_some() && fork_event.event_type != EventType::ToolResult {
+        return Err(Error::InvalidState(format!(
+            "event #{} is not a tool result",
+            fork_at
+        )));
+    }
+
+    let user_id = events
+        .iter()
+        .find(|e| e.event_type == EventType::UserInput)
+        .and_then(|e| e.payload.get("user_id"))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| Error::InvalidState("execution has no recorded input".to_string()))?
+        .to_string();
+
+    let mut builder = HistoryBuilder::default();
+    for event in events.iter().filter(|e| e.sequence_num <= fork_at) {
+        let edited = (event.sequence_num == fork_at)
+            .then_some(request.tool_result.as_ref())
+            .flatten();
+        builder.apply(event, edited)?;
+    }
+
+    // Forking at the user input replaces it instead of following it
+    let mut text = request.user_message.clone().unwrap_or_default();
+    if fork_event.event_type == EventType::UserInput {
+        let original = builder
+            .messages
+            .pop()
+            .map(|m| m.content)
+            .unwrap_or_default();
+        if request.user_message.is_none() {
+            text = original;
+        }
+    }
+
+    // A fork inside a tool round keeps the round's remaining results so the
+    // history never ends with unanswered tool calls
+    for event in events.iter().filter(|e| e.sequence_num > fork_at) {
+        if builder.pending.is_empty() {
+            break;
+        }
+        match event.event_type {
+            EventType::ToolResult => builder.apply(event, None)?,
+            EventType::LlmResponse | EventType::FinalResponse => break,
+            _ => {}
+        }
+    }
+    builder.close_round();
+
+    Ok(ForkHistory {
+        messages: builder.messages,
+        text,
+        user_id,
+    })
+}
+
+#[derive(Default)]
+struct HistoryBuilder {
+    messages: Vec<Message>,
+    /// Tool calls of the current round still waiting for a result
+    pending: Vec<ToolCall>,
+}
+
+impl HistoryBuilder {
+    fn apply(&mut self, event: &Event, edited_output: Option<&serde_json::Value>) -> Result<()> {
+        let payload = &event.payload;
+        match event.event_type {
+            EventType::UserInput => {
+                self.close_round();
+                let text = payload.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                self.messages.push(Message::user(text));
+            }
+            EventType::LlmResponse => {
+                self.close_round();
+                let response = RecordedLlmResponse::from_payload(event.sequence_num, payload)?;
+                let content = response.content.unwrap_or_default();
+                if response.tool_calls.is_empty() {
+                    if !content.is_empty() {
+                        self.messages.push(Message::assistant(content));
+                    }
+                } else {
+                    let calls: Vec<ToolCall> = response
+                        .tool_calls
+                        .into_iter()
+                        .map(|call| ToolCall {
+                            id: call.id,
+                            name: call.name,
+                            arguments: call.arguments,
+                            thought_signature: None,
+                        })
+                        .collect();
+                    self.pending = calls.clone();
+                    self.messages
+                        .push(Message::assistant_with_tool_calls(content, calls));
+                }
+            }
+            EventType::ToolResult => {
+                let Some(name) = tool_name(payload) else {
+                    return Ok(());
+                };
+                let Some(index) = self.pending.iter().position(|c| c.name == name) else {
+                    return Ok(());
+                };
+                let call = self.pending.remove(index);
+                let output = edited_output
+                    .cloned()
+                    .or_else(|| payload.get("output").cloned())
+                    .unwrap_or(serde_json::Value::Null);
+                self.messages
+                    .extend(Planner::build_tool_result_messages(&[call], &[output]));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Answer tool calls that never got a recorded result (e.g. denied tools)
+    fn close_round(&mut self) {
+        let calls = std::mem::take(&mut self.pending);
+        let outputs: Vec<serde_json::Value> = calls
+            .iter()
+            .map(|_| serde_json::json!({"error": "No recorded result"}))
+            .collect();
+        self.messages
+            .extend(Planner::build_tool_result_messages(&calls, &outputs));
+    }
+}
+
+impl Orchestrator {
+    /// Fork a recorded execution at an event and continue it as a new execution
+    ///
+    /// The fork runs in its own session (`fork:<parent>:<user>`), so the
+    /// original conversation is left untouched.
+    pub async fn fork_execution(
+        &self,
+        execution_id: Uuid,
+        request: ForkRequest,
+    ) -> Result<ForkRun> {
+        let store = self
+            .event_store
+            .as_ref()
+            .ok_or_else(|| Error::InvalidState("forking requires an event store".to_string()))?;
+
+        let events = store.get_events(execution_id).await?;
+        if events.is_empty() {
+            return Err(Error::NotFound(format!("execution {}", execution_id)));
+        }
+        let history = rebuild_history(&events, &request)?;
+        if history.text.is_empty() && history.messages.is_empty() {
+            return Err(Error::InvalidState("nothing to continue from".to_string()));
+        }
+
+        info!(
+            execution_id = %execution_id,
+            sequence_num = request.sequence_num,
+            messages = history.messages.len(),
+            "Forking execution"
+        );
+        let point = ForkPoint {
+            execution_id,
+            sequence_num: request.sequence_num,
+        };
+        let input = OrchestratorInput::new(
+            "fork",
+            execution_id.to_string(),
+            history.user_id,
+            history.text,
+        )
+        .with_fork(point, history.messages);
+        let result = self.process(input).await?;
+
+        Ok(ForkRun {
+            parent_execution_id: execution_id,
+            sequence_num: request.sequence_num,
+            execution_id: result.execution_id,
+            status: result.status,
+            response: result.response,
+        })
+    }
+}
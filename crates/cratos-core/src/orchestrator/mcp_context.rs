@@ -0,0 +1,187 @@
+//! MCP resources and prompts for the Orchestrator
+//!
+//! Prompt templates from connected MCP servers are exposed as slash commands
+//! named like their tools (`/mcp_<server>_<prompt> key=value ...`). Resources
+//! can be attached to an input and are inserted into the session as context.
+
+use crate::memory::SessionContext;
+use cratos_llm::Message;
+use cratos_tools::mcp::McpPrompt;
+use std::collections::HashMap;
+use tracing::{debug, info, warn};
+
+use super::config::McpResourceRef;
+use super::core::Orchestrator;
+
+/// Maximum characters of a single attached resource kept in context
+const MAX_RESOURCE_CHARS: usize = 32_000;
+
+/// Marker prefix of the system message holding an attached resource
+const RESOURCE_PREFIX: &str = "Attached MCP resource";
+
+/// Slash command exposed for an MCP prompt template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpPromptCommand {
+    /// Command name without the leading slash (`mcp_<server>_<prompt>`)
+    pub command: String,
+    /// Prompt description
+    pub description: String,
+    /// Argument names (required ones first)
+    pub arguments: Vec<String>,
+}
+
+impl Orchestrator {
+    /// List MCP prompt templates as slash commands
+    pub async fn mcp_prompt_commands(&self) -> Vec<McpPromptCommand> {
+        let Some(client) = &self.mcp_client else {
+            return Vec::new();
+        };
+        let prompts = client.read().await.list_all_prompts().await;
+
+        let mut commands: Vec<McpPromptCommand> = prompts
+            .into_iter()
+            .map(|(server, prompt)| {
+                let mut arguments = prompt.arguments.clone();
+                arguments.sort_by_key(|a| !a.required);
+                McpPromptCommand {
+                    command: prompt_command(&server, &prompt.name),
+                    description: prompt.description.unwrap_or_default(),
+                    arguments: arguments.into_iter().map(|a| a.name).collect(),
+                }
+            })
+            .collect();
+        commands.sort_by(|a, b| a.command.cmp(&b.command));
+        commands
+    }
+
+    /// List resources from every connected MCP server as `(server, uri, name)`
+    pub async fn mcp_resources(&self) -> Vec<(String, String, String)> {
+        let Some(client) = &self.mcp_client else {
+            return Vec::new();
+        };
+        let client = client.read().await;
+
+        let mut resources = Vec::new();
+        for server in client.list_servers() {
+            if client
+                .capabilities(server)
+                .is_none_or(|c| c.resources.is_none())
+            {
+                continue;
+            }
+            match client.list_resources(server).await {
+                Ok(list) => resources.extend(
+                    list.into_iter()
+                        .map(|r| (server.to_string(), r.uri, r.name)),
+                ),
+                Err(e) => warn!(server = %server, error = %e, "Failed to list MCP resources"),
+            }
+        }
+        resources
+    }
+
+    /// Expand `/mcp_<server>_<prompt> args` into the rendered prompt text
+    ///
+    /// Returns `None` when the text is not an MCP prompt command.
+    pub(super) async fn expand_mcp_prompt(&self, text: &str) -> Option<String> {
+        let client = self.mcp_client.as_ref()?;
+        let rest = text.trim().strip_prefix('/')?;
+        let (command, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        if !command.starts_with("mcp_") {
+            return None;
+        }
+
+        let client = client.read().await;
+        let (server, prompt) = client
+            .list_all_prompts()
+            .await
+            .into_iter()
+            .find(|(server, prompt)| prompt_command(server, &prompt.name) == command)?;
+
+        let arguments = parse_prompt_args(&prompt, args);
+        match client.get_prompt(&server, &prompt.name, arguments).await {
+            Ok(result) => {
+                info!(server = %server, prompt = %prompt.name, "Expanded MCP prompt command");
+                Some(result.to_text())
+            }
+            Err(e) => {
+                warn!(server = %server, prompt = %prompt.name, error = %e, "MCP prompt failed");
+                None
+            }
+        }
+    }
+
+    /// Read attached MCP resources into the session as system context
+    pub(super) async fn attach_mcp_resources(
+        &self,
+        session: &mut SessionContext,
+        resources: &[McpResourceRef],
+    ) {
+        let Some(client) = &self.mcp_client else {
+            if !resources.is_empty() {
+                warn!("MCP resources attached but no MCP client is configured");
+            }
+            return;
+        };
+        let client = client.read().await;
+
+        for resource in resources {
+            let contents = match client.read_resource(&resource.server, &resource.uri).await {
+                Ok(contents) => contents,
+                Err(e) => {
+                    warn!(server = %resource.server, uri = %resource.uri, error = %e, "Failed to read MCP resource");
+                    continue;
+                }
+            };
+
+            let text: String = contents
+                .iter()
+                .filter_map(|c| c.text.as_deref())
+                .collect::<Vec<_>>()
+                .join("\n");
+            if text.is_empty() {
+                debug!(uri = %resource.uri, "MCP resource has no text contents, skipping");
+                continue;
+            }
+            let text: String = text.chars().take(MAX_RESOURCE_CHARS).collect();
+
+            let header = format!("{} {}", RESOURCE_PREFIX, resource.uri);
+            session.remove_system_messages_with_prefix(&header);
+            session
+                .insert_supplementary_context(vec![Message::system(format!("{header}:\n{text}"))]);
+            info!(server = %resource.server, uri = %resource.uri, "Attached MCP resource to context");
+        }
+    }
+}
+
+/// Slash command name for a prompt (matches MCP tool naming)
+fn prompt_command(server: &str, prompt: &str) -> String {
+    format!("mcp_{}_{}", server, prompt)
+}
+
+/// Parse `key=value` pairs; bare text fills the first required argument
+pub(super) fn parse_prompt_args(prompt: &McpPrompt, args: &str) -> HashMap<String, String> {
+    let mut parsed = HashMap::new();
+    let mut free_text = Vec::new();
+
+    for token in args.split_whitespace() {
+        match token.split_once('=') {
+            Some((key, value)) if prompt.arguments.iter().any(|a| a.name == key) => {
+                parsed.insert(key.to_string(), value.to_string());
+            }
+            _ => free_text.push(token),
+        }
+    }
+
+    if !free_text.is_empty() {
+        let target = prompt
+            .arguments
+            .iter()
+            .filter(|a| !parsed.contains_key(&a.name))
+            .min_by_key(|a| !a.required);
+        if let Some(arg) = target {
+            parsed.insert(arg.name.clone(), free_text.join(" "));
+        }
+    }
+    parsed
+}
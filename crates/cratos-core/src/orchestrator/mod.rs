@@ -8,20 +8,26 @@
 //! - `types`: Core types (SkillMatch, ExecutionResult, etc.)
 //! - `config`: Configuration types (OrchestratorConfig, OrchestratorInput)
 //! - `core`: Orchestrator struct and builder methods
+//! - `fork`: Forking recorded executions at an event
 //! - `process`: Main execution loop
 //! - `tool_execution`: Tool execution logic
 //! - `planning`: Planning methods (dispatch_plan, plan_with_fallback)
 //! - `multi_persona`: Multi-persona execution modes
 //! - `helpers`: Utility methods (emit, log_event)
+//! - `mcp_context`: MCP prompt slash commands and resource attachments
+//! - `replay`: Deterministic replay of recorded executions
 //! - `sanitize`: Sanitization and validation helpers
 
 mod config;
 mod core;
+mod fork;
 mod helpers;
+mod mcp_context;
 mod multi_persona;
 mod planning;
 mod post_execution;
 mod process;
+mod replay;
 mod result_builder;
 mod routing;
 mod sanitize;
@@ -33,8 +39,12 @@ mod types;
 mod tests;
 
 // Re-export public types
-pub use config::{OrchestratorConfig, OrchestratorInput};
+pub use config::{ForkPoint, McpResourceRef, OrchestratorConfig, OrchestratorInput};
 pub use core::Orchestrator;
+pub use fork::{ForkRequest, ForkRun};
+pub use mcp_context::McpPromptCommand;
+pub(crate) use sanitize::is_llm_fallback_eligible;
+pub use replay::{CassetteProvider, CassetteTool, ReplayRun, SharedCassette};
 pub use types::{
     ExecutionArtifact, ExecutionResult, ExecutionStatus, SkillMatch, SkillRouting, ToolCallRecord,
 };
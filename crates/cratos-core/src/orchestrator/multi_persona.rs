@@ -7,7 +7,7 @@
 
 use crate::agents::{ExecutionMode, MultiPersonaExtraction, PersonaMention};
 use crate::error::Result;
-use cratos_llm::Message;
+use cratos_llm::{Message, TokenUsage, UsageAttribution};
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 use uuid::Uuid;
@@ -26,6 +26,7 @@ pub(crate) struct PersonaExecutionResult {
     #[allow(dead_code)] // Reserved for future metrics/logging
     pub duration_ms: u64,
     pub model: Option<String>,
+    pub usage: TokenUsage,
 }
 
 impl Orchestrator {
@@ -147,6 +148,7 @@ impl Orchestrator {
                     iterations: 0,
                     duration_ms: start.elapsed().as_millis() as u64,
                     model: None,
+                    usage: TokenUsage::default(),
                 });
             }
         };
@@ -158,7 +160,7 @@ impl Orchestrator {
     /// Run a single persona with its own system prompt
     pub(crate) async fn run_single_persona(
         &self,
-        _execution_id: Uuid,
+        execution_id: Uuid,
         persona: &PersonaMention,
         task: &str,
         input: &OrchestratorInput,
@@ -196,14 +198,22 @@ impl Orchestrator {
         let tools = self.runner.registry().to_llm_tools();
 
         // Single LLM call (simplified - no tool loop for now)
-        let plan_result = match &system_prompt {
-            Some(sp) => {
-                self.planner
-                    .plan_step_with_system_prompt(&messages, &tools, sp, None)
-                    .await
-            }
-            None => self.planner.plan_step(&messages, &tools, None).await,
-        };
+        let attribution = UsageAttribution::user(&input.user_id)
+            .with_execution(execution_id.to_string())
+            .with_persona(&persona_name)
+            .with_channel(&input.channel_type);
+        let plan_result = attribution
+            .scope(async {
+                match &system_prompt {
+                    Some(sp) => {
+                        self.planner
+                            .plan_step_with_system_prompt(&messages, &tools, sp, None)
+                            .await
+                    }
+                    None => self.planner.plan_step(&messages, &tools, None).await,
+                }
+            })
+            .await;
 
         match plan_result {
             Ok(plan) => {
@@ -221,7 +231,10 @@ impl Orchestrator {
                         serde_json::from_str(&tc.arguments).unwrap_or(serde_json::Value::Null);
 
                     let tool_start = std::time::Instant::now();
-                    let result = self.runner.execute(&tc.name, args.clone()).await;
+                    let result = input
+                        .memory_scope()
+                        .scope(self.runner.execute(&tc.name, args.clone()))
+                        .await;
 
                     let (output, success) = match result {
                         Ok(exec_result) => (exec_result.result.output, exec_result.result.success),
@@ -245,6 +258,7 @@ impl Orchestrator {
                     success: true,
                     duration_ms: persona_start.elapsed().as_millis() as u64,
                     model: Some(plan.model),
+                    usage: plan.usage.unwrap_or_default(),
                 }
             }
             Err(e) => {
@@ -256,6 +270,7 @@ impl Orchestrator {
                     success: false,
                     duration_ms: persona_start.elapsed().as_millis() as u64,
                     model: None,
+                    usage: TokenUsage::default(),
                 }
             }
         }
@@ -297,6 +312,7 @@ impl Orchestrator {
 
         // Use the first successful model or None
         let model = results.iter().find_map(|r| r.model.clone());
+        let usage = total_usage(&results);
 
         info!(
             execution_id = %execution_id,
@@ -315,6 +331,7 @@ impl Orchestrator {
             iterations: results.len(),
             duration_ms: start.elapsed().as_millis() as u64,
             model,
+            usage,
         })
     }
 
@@ -444,6 +461,7 @@ impl Orchestrator {
         };
 
         let model = results.iter().find_map(|r| r.model.clone());
+        let usage = total_usage(&results);
 
         info!(
             execution_id = %execution_id,
@@ -462,6 +480,7 @@ impl Orchestrator {
             iterations: results.len(),
             duration_ms: start.elapsed().as_millis() as u64,
             model,
+            usage,
         })
     }
 
@@ -621,6 +640,7 @@ impl Orchestrator {
         };
 
         let model = results.iter().find_map(|r| r.model.clone());
+        let usage = total_usage(&results);
 
         info!(
             execution_id = %execution_id,
@@ -640,6 +660,16 @@ impl Orchestrator {
             iterations: results.len(),
             duration_ms: start.elapsed().as_millis() as u64,
             model,
+            usage,
         })
     }
 }
+
+/// Tokens consumed across persona executions
+fn total_usage(results: &[PersonaExecutionResult]) -> TokenUsage {
+    let mut usage = TokenUsage::default();
+    for r in results {
+        usage.accumulate(&r.usage);
+    }
+    usage
+}
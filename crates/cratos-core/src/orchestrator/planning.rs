@@ -5,9 +5,12 @@
 //! - `plan_with_fallback`: Plans with automatic fallback on transient errors
 //! - `try_final_summary`: Generates final summary when limits are reached
 
-use crate::planner::{PlanResponse, Planner};
+use crate::event_bus::OrchestratorEvent;
+use crate::planner::{DeltaCallback, PlanResponse, Planner};
 use cratos_llm::{Message, ToolDefinition};
+use std::sync::atomic::{AtomicBool, Ordering};
 use tracing::warn;
+use uuid::Uuid;
 
 use super::core::Orchestrator;
 use super::sanitize::is_fallback_eligible;
@@ -17,14 +20,27 @@ impl Orchestrator {
     ///
     /// Wraps the LLM call in a 120-second timeout to prevent indefinite hangs
     /// when a provider fails to respond (e.g. network stall, missing HTTP timeout).
+    /// When `on_delta` is set, text is streamed to it as the model generates.
     pub(crate) async fn dispatch_plan(
         planner: &Planner,
         messages: &[Message],
         tools: &[ToolDefinition],
         system_prompt_override: Option<&str>,
         override_model: Option<&str>,
+        on_delta: Option<DeltaCallback<'_>>,
     ) -> crate::error::Result<PlanResponse> {
         let fut = async move {
+            if let Some(on_delta) = on_delta {
+                return planner
+                    .plan_step_streaming(
+                        messages,
+                        tools,
+                        system_prompt_override,
+                        override_model,
+                        on_delta,
+                    )
+                    .await;
+            }
             match system_prompt_override {
                 Some(p) => {
                     planner
@@ -51,31 +67,65 @@ impl Orchestrator {
     /// (skipping the primary).  This prevents mixing tool calls from different
     /// providers within the same execution — critical for Gemini 3 thinking
     /// models that require `thought_signature` on every function call.
+    ///
+    /// If the primary fails after streaming text to `on_delta`, a `ChatReset`
+    /// event is emitted before the fallback streams its own answer.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn plan_with_fallback(
         &self,
+        execution_id: Uuid,
         messages: &[Message],
         tools: &[ToolDefinition],
         system_prompt_override: Option<&str>,
         override_model: Option<&str>,
         fallback_sticky: &mut bool,
+        on_delta: Option<DeltaCallback<'_>>,
     ) -> crate::error::Result<PlanResponse> {
         // If a previous iteration already fell back, keep using the fallback
         // to avoid mixing thought_signature-bearing and bare function calls.
         if *fallback_sticky {
             if let Some(fb) = self.fallback_planner.as_ref() {
-                return Self::dispatch_plan(fb, messages, tools, system_prompt_override, None).await;
+                return Self::dispatch_plan(
+                    fb,
+                    messages,
+                    tools,
+                    system_prompt_override,
+                    None,
+                    on_delta,
+                )
+                .await;
             }
         }
 
-        let result =
-            Self::dispatch_plan(&self.planner, messages, tools, system_prompt_override, override_model).await;
+        let streamed = AtomicBool::new(false);
+        let track_delta = |delta: &str| {
+            streamed.store(true, Ordering::Relaxed);
+            if let Some(on_delta) = on_delta {
+                on_delta(delta);
+            }
+        };
+        let primary_delta: Option<DeltaCallback<'_>> = on_delta.map(|_| &track_delta as _);
+
+        let result = Self::dispatch_plan(
+            &self.planner,
+            messages,
+            tools,
+            system_prompt_override,
+            override_model,
+            primary_delta,
+        )
+        .await;
         match result {
             Ok(resp) => Ok(resp),
             Err(ref e) if self.fallback_planner.is_some() && is_fallback_eligible(e) => {
                 warn!(error = %e, "Primary provider failed, trying fallback (sticky)");
                 *fallback_sticky = true;
+                if streamed.load(Ordering::Relaxed) {
+                    self.emit(OrchestratorEvent::ChatReset { execution_id });
+                }
                 let fb = self.fallback_planner.as_ref().unwrap();
-                Self::dispatch_plan(fb, messages, tools, system_prompt_override, None).await
+                Self::dispatch_plan(fb, messages, tools, system_prompt_override, None, on_delta)
+                    .await
             }
             Err(e) => Err(e),
         }
@@ -116,6 +166,7 @@ impl Orchestrator {
             &[], // empty tools → forces text-only response
             system_prompt_override,
             override_model,
+            None,
         )
         .await;
 
@@ -7,6 +7,7 @@ use super::core::Orchestrator;
 use super::sanitize::is_fake_tool_use_text;
 use super::types::ToolCallRecord;
 use cratos_llm::Message;
+use cratos_memory::MemoryScope;
 use std::sync::Arc;
 use tracing::{debug, warn};
 
@@ -80,13 +81,18 @@ impl Orchestrator {
     }
 
     /// Index session messages with Graph RAG (async, fire-and-forget)
-    pub(super) fn spawn_graph_rag_indexing(&self, session_key: &str, messages: &[Message]) {
+    pub(super) fn spawn_graph_rag_indexing(
+        &self,
+        scope: MemoryScope,
+        session_key: &str,
+        messages: &[Message],
+    ) {
         if let Some(gm) = &self.graph_memory {
             let gm = Arc::clone(gm);
             let sid = session_key.to_string();
             let msgs = messages.to_vec();
             tokio::spawn(async move {
-                match gm.index_session(&sid, &msgs).await {
+                match gm.index_session(&scope, &sid, &msgs).await {
                     Ok(count) if count > 0 => {
                         debug!(session_id = %sid, indexed = count, "Graph RAG indexing complete");
                     }
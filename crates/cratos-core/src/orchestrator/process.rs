@@ -6,8 +6,8 @@ use crate::agents::extract_all_persona_mentions;
 use crate::error::Result;
 use crate::event_bus::OrchestratorEvent;
 use crate::memory::WorkingMemory;
-use crate::planner::Planner;
-use cratos_llm::Message;
+use crate::planner::{DeltaCallback, Planner};
+use cratos_llm::{Message, MessageRole, TokenUsage, UsageAttribution};
 use cratos_replay::{EventType, Execution};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
@@ -24,8 +24,14 @@ impl Orchestrator {
         channel = %input.channel_type,
         user = %input.user_id
     ))]
-    pub async fn process(&self, input: OrchestratorInput) -> Result<ExecutionResult> {
+    pub async fn process(&self, mut input: OrchestratorInput) -> Result<ExecutionResult> {
         let start_time = std::time::Instant::now();
+
+        // MCP prompt templates are invoked as slash commands
+        if let Some(expanded) = self.expand_mcp_prompt(&input.text).await {
+            input.text = expanded;
+        }
+
         let execution_id = Uuid::new_v4();
         let session_key = input.session_key();
 
@@ -87,6 +93,14 @@ impl Orchestrator {
                 execution = execution.with_thread_id(thread_id);
             }
 
+            // Link forks to the execution they were branched from
+            if let Some(fork) = &input.forked_from {
+                execution = execution.with_metadata(serde_json::json!({
+                    "forked_from": fork.execution_id,
+                    "fork_sequence": fork.sequence_num
+                }));
+            }
+
             if let Err(e) = store.create_execution(&execution).await {
                 warn!(error = %e, "Failed to create execution record");
             }
@@ -121,11 +135,28 @@ impl Orchestrator {
         };
 
         let mut model_used: Option<String> = None;
+        let mut usage = TokenUsage::default();
 
         let (persona_system_prompt, effective_persona): (Option<String>, String) =
             if is_memory_request {
                 info!("Memory request detected, using cratos directly");
                 (None, "cratos".to_string())
+            } else if let Some((mapping, persona)) = self
+                .persona_mapping
+                .as_ref()
+                .zip(input.persona.as_deref())
+                .filter(|(mapping, persona)| mapping.is_persona(persona))
+            {
+                let persona = persona.to_lowercase();
+                info!(persona = %persona, "Persona selected by caller");
+                let prompt = mapping.get_system_prompt(&persona, &input.user_id).map(|p| {
+                    format!(
+                        "{}\n\n---\n## Active Persona\n{}",
+                        self.planner.config().system_prompt,
+                        p
+                    )
+                });
+                (prompt, persona)
             } else if let Some(mapping) = &self.persona_mapping {
                 let multi_extraction = extract_all_persona_mentions(&input.text, mapping);
 
@@ -190,6 +221,12 @@ impl Orchestrator {
             skill_hint,
         );
 
+        // LLM calls of this execution are billed to its user, persona and channel
+        let usage_attribution = UsageAttribution::user(&input.user_id)
+            .with_execution(execution_id.to_string())
+            .with_persona(&effective_persona)
+            .with_channel(&input.channel_type);
+
         // Create working memory
         let mut working_memory = WorkingMemory::with_execution_id(execution_id);
         let mut tool_call_records = Vec::new();
@@ -206,6 +243,38 @@ impl Orchestrator {
         // Get available tools
         let tools = self.runner.registry().to_llm_tools();
 
+        // A resumed execution first finishes the tool round it was parked in:
+        // calls that ran before the approval keep their results, and the rest,
+        // starting with the approved call, run now
+        if let Some(finished) = input.resumed_tool_results.take() {
+            let calls = messages
+                .last()
+                .filter(|message| message.role == MessageRole::Assistant)
+                .map(|message| message.tool_calls.clone())
+                .unwrap_or_default();
+            info!(
+                execution_id = %execution_id,
+                finished = finished.len(),
+                calls = calls.len(),
+                "Resuming parked tool round"
+            );
+            let (results, _) = self
+                .execute_tool_calls(
+                    execution_id,
+                    &input,
+                    &messages,
+                    &calls,
+                    finished,
+                    &mut working_memory,
+                    &mut tool_call_records,
+                    Some(effective_persona.as_str()),
+                    matched_skill_id,
+                    &mut steering_ctx,
+                )
+                .await?;
+            messages.extend(Planner::build_tool_result_messages(&calls, &results));
+        }
+
         // Main execution loop
         loop {
             iteration += 1;
@@ -215,13 +284,14 @@ impl Orchestrator {
                     iterations = %iteration,
                     "Max iterations reached, attempting final summary"
                 );
-                let summary = self
-                    .try_final_summary(
+                let summary = usage_attribution
+                    .clone()
+                    .scope(self.try_final_summary(
                         &messages,
                         effective_system_prompt.as_deref(),
                         model_used.as_deref(),
                         fallback_sticky,
-                    )
+                    ))
                     .await;
                 if !summary.is_empty() {
                     final_response = summary;
@@ -241,14 +311,17 @@ impl Orchestrator {
                 crate::utils::metrics_global::labeled_counter("cratos_executions_total")
                     .inc(&[("status", "cancelled")]);
                 crate::utils::metrics_global::gauge("cratos_active_executions").dec();
-                return Ok(self.build_cancelled_result(
-                    execution_id,
-                    None,
-                    tool_call_records,
-                    iteration,
-                    start_time.elapsed().as_millis() as u64,
-                    model_used,
-                ));
+                return Ok(ExecutionResult {
+                    usage,
+                    ..self.build_cancelled_result(
+                        execution_id,
+                        None,
+                        tool_call_records,
+                        iteration,
+                        start_time.elapsed().as_millis() as u64,
+                        model_used,
+                    )
+                });
             }
 
             // ── Steering check ────────────────────────────────────────
@@ -276,14 +349,17 @@ impl Orchestrator {
                         .inc(&[("status", "cancelled")]);
                     crate::utils::metrics_global::gauge("cratos_active_executions").dec();
 
-                    return Ok(self.build_cancelled_result(
-                        execution_id,
-                        reason,
-                        tool_call_records,
-                        iteration,
-                        start_time.elapsed().as_millis() as u64,
-                        model_used,
-                    ));
+                    return Ok(ExecutionResult {
+                        usage,
+                        ..self.build_cancelled_result(
+                            execution_id,
+                            reason,
+                            tool_call_records,
+                            iteration,
+                            start_time.elapsed().as_millis() as u64,
+                            model_used,
+                        )
+                    });
                 }
                 Ok(crate::steering::SteerDecision::Skip(_)) => {
                     // Skip implies skipping a tool call, but we are at the start of iteration.
@@ -315,13 +391,14 @@ impl Orchestrator {
                         "Execution timeout reached"
                     );
                     if final_response.is_empty() {
-                        let summary = self
-                            .try_final_summary(
+                        let summary = usage_attribution
+                            .clone()
+                            .scope(self.try_final_summary(
                                 &messages,
                                 effective_system_prompt.as_deref(),
                                 model_used.as_deref(),
                                 fallback_sticky,
-                            )
+                            ))
                             .await;
                         final_response = if summary.is_empty() {
                             "처리 시간이 초과되었습니다. 요청을 단순화하거나 다시 시도해주세요."
@@ -366,34 +443,57 @@ impl Orchestrator {
                 iteration,
             });
 
-            // Plan the next step (with fallback and optional system prompt override)
-            let plan_response = match self
-                .plan_with_fallback(
+            // Plan the next step (with fallback and optional system prompt override).
+            // Text is streamed as non-final ChatDelta events; the final event
+            // below still carries the complete response.
+            let emit_delta = |delta: &str| {
+                self.emit(OrchestratorEvent::ChatDelta {
+                    execution_id,
+                    delta: delta.to_string(),
+                    is_final: false,
+                });
+            };
+            let on_delta: Option<DeltaCallback<'_>> = if self.config.stream_responses {
+                Some(&emit_delta)
+            } else {
+                None
+            };
+            let plan_response = match usage_attribution
+                .clone()
+                .scope(self.plan_with_fallback(
+                    execution_id,
                     &messages,
                     &tools,
                     effective_system_prompt.as_deref(),
                     model_used.as_deref(),
                     &mut fallback_sticky,
-                )
+                    on_delta,
+                ))
                 .await
             {
                 Ok(response) => response,
                 Err(e) => {
                     self.active_executions.remove(&execution_id);
-                    return Ok(self
-                        .build_planning_failure_result(
-                            execution_id,
-                            &e,
-                            tool_call_records,
-                            iteration,
-                            start_time.elapsed().as_millis() as u64,
-                            model_used,
-                        )
-                        .await);
+                    return Ok(ExecutionResult {
+                        usage,
+                        ..self
+                            .build_planning_failure_result(
+                                execution_id,
+                                &e,
+                                tool_call_records,
+                                iteration,
+                                start_time.elapsed().as_millis() as u64,
+                                model_used,
+                            )
+                            .await
+                    });
                 }
             };
 
             model_used = Some(plan_response.model.clone());
+            if let Some(call_usage) = &plan_response.usage {
+                usage.accumulate(call_usage);
+            }
 
             // Log LLM response event
             self.log_event(
@@ -401,8 +501,9 @@ impl Orchestrator {
                 EventType::LlmResponse,
                 &serde_json::json!({
                     "content": plan_response.content,
-                    "tool_calls": plan_response.tool_calls.len(),
+                    "tool_calls": plan_response.tool_calls,
                     "model": plan_response.model,
+                    "provider": plan_response.provider,
                     "is_final": plan_response.is_final
                 }),
             )
@@ -530,7 +631,10 @@ impl Orchestrator {
                 let (results, steering_messages) = match self
                     .execute_tool_calls(
                         execution_id,
+                        &input,
+                        &messages,
                         &filtered_calls,
+                        Vec::new(),
                         &mut working_memory,
                         &mut tool_call_records,
                         Some(effective_persona.as_str()),
@@ -551,6 +655,7 @@ impl Orchestrator {
                             iterations: iteration,
                             duration_ms: start_time.elapsed().as_millis() as u64,
                             model: model_used,
+                            usage,
                         });
                     }
                     Err(e) => return Err(e),
@@ -677,7 +782,7 @@ impl Orchestrator {
         self.run_post_execution_hooks(&effective_persona, &final_response);
 
         // Graph RAG: index this session's messages asynchronously
-        self.spawn_graph_rag_indexing(&session_key, &messages);
+        self.spawn_graph_rag_indexing(input.memory_scope(), &session_key, &messages);
 
         let duration_ms = start_time.elapsed().as_millis() as u64;
 
@@ -732,6 +837,7 @@ impl Orchestrator {
             iterations: iteration,
             duration_ms,
             model: model_used,
+            usage,
         })
     }
 }
@@ -0,0 +1,262 @@
+//! Deterministic replay of recorded executions
+//!
+//! Re-drives the orchestrator loop from an execution's recorded events. The
+//! `LlmResponse` events are played back through [`CassetteProvider`] and the
+//! `ToolResult` events through [`CassetteTool`]s, so a full cassette replay
+//! makes no network calls. [`ReplayMode`] selects which side runs live.
+
+use crate::error::{Error, Result};
+use cratos_llm::{
+    CompletionRequest, CompletionResponse, LlmProvider, ToolCall, ToolCompletionRequest,
+    ToolCompletionResponse,
+};
+use cratos_replay::{Cassette, ReplayMode};
+use cratos_tools::{RiskLevel, Tool, ToolDefinition, ToolRegistry, ToolResult};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::config::{OrchestratorConfig, OrchestratorInput};
+use super::core::Orchestrator;
+use super::types::ExecutionStatus;
+
+/// Outcome of replaying a recorded execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayRun {
+    /// Execution that was replayed
+    pub original_execution_id: Uuid,
+    /// Execution created by the replay
+    pub replay_execution_id: Uuid,
+    /// Replay mode
+    pub mode: ReplayMode,
+    /// Final status of the replay
+    pub status: ExecutionStatus,
+    /// Final response of the replay
+    pub response: String,
+    /// Recorded LLM responses that were not consumed
+    pub unused_llm_responses: usize,
+}
+
+/// Cassette shared between the replay provider and tools
+pub type SharedCassette = Arc<Mutex<Cassette>>;
+
+fn lock(cassette: &SharedCassette) -> MutexGuard<'_, Cassette> {
+    cassette.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// LLM provider that plays back recorded responses in order
+pub struct CassetteProvider {
+    cassette: SharedCassette,
+}
+
+impl CassetteProvider {
+    /// Create a provider playing back the given cassette
+    #[must_use]
+    pub fn new(cassette: SharedCassette) -> Self {
+        Self { cassette }
+    }
+
+    fn next_response(&self) -> cratos_llm::Result<ToolCompletionResponse> {
+        let recorded = lock(&self.cassette).next_llm_response().ok_or_else(|| {
+            cratos_llm::Error::Provider("replay cassette has no more LLM responses".to_string())
+        })?;
+
+        Ok(ToolCompletionResponse {
+            content: recorded.content,
+            tool_calls: recorded
+                .tool_calls
+                .into_iter()
+                .map(|call| ToolCall {
+                    id: call.id,
+                    name: call.name,
+                    arguments: call.arguments,
+                    thought_signature: None,
+                })
+                .collect(),
+            usage: None,
+            finish_reason: Some("replay".to_string()),
+            model: recorded.model,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl LlmProvider for CassetteProvider {
+    fn name(&self) -> &str {
+        "replay"
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn available_models(&self) -> Vec<String> {
+        vec!["replay".to_string()]
+    }
+
+    fn default_model(&self) -> &str {
+        "replay"
+    }
+
+    async fn complete(
+        &self,
+        _request: CompletionRequest,
+    ) -> cratos_llm::Result<CompletionResponse> {
+        let response = self.next_response()?;
+        Ok(CompletionResponse {
+            content: response.content.unwrap_or_default(),
+            usage: None,
+            finish_reason: response.finish_reason,
+            model: response.model,
+        })
+    }
+
+    async fn complete_with_tools(
+        &self,
+        _request: ToolCompletionRequest,
+    ) -> cratos_llm::Result<ToolCompletionResponse> {
+        self.next_response()
+    }
+}
+
+/// Tool that returns the recorded result for its calls
+pub struct CassetteTool {
+    definition: ToolDefinition,
+    cassette: SharedCassette,
+}
+
+impl CassetteTool {
+    /// Create a recorded stand-in for the tool described by `definition`
+    #[must_use]
+    pub fn new(definition: ToolDefinition, cassette: SharedCassette) -> Self {
+        // Recorded results are harmless, so no approval or enablement gates
+        let definition = definition
+            .with_risk_level(RiskLevel::Low)
+            .with_enabled(true);
+        Self {
+            definition,
+            cassette,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Tool for CassetteTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(&self, input: serde_json::Value) -> cratos_tools::Result<ToolResult> {
+        let recorded = lock(&self.cassette).take_tool_result(&self.definition.name, &input);
+        Ok(match recorded {
+            Some(recorded) => ToolResult {
+                success: recorded.success,
+                output: recorded.output,
+                error: recorded.error,
+                duration_ms: 0,
+            },
+            None => ToolResult::failure(
+                format!("No recorded result for tool '{}'", self.definition.name),
+                0,
+            ),
+        })
+    }
+}
+
+impl Orchestrator {
+    /// Replay a recorded execution
+    ///
+    /// The replay runs on a fresh session and is logged as a new execution in
+    /// the same event store, so it can be compared with the original via
+    /// `ExecutionViewer::compare_executions`.
+    pub async fn replay_execution(
+        &self,
+        execution_id: Uuid,
+        mode: ReplayMode,
+    ) -> Result<ReplayRun> {
+        let store = self
+            .event_store
+            .clone()
+            .ok_or_else(|| Error::InvalidState("replay requires an event store".to_string()))?;
+
+        let events = store.get_events(execution_id).await?;
+        if events.is_empty() {
+            return Err(Error::NotFound(format!("execution {}", execution_id)));
+        }
+        let cassette = Cassette::from_events(&events)?;
+        let recorded = cassette
+            .input()
+            .cloned()
+            .ok_or_else(|| Error::InvalidState("execution has no recorded input".to_string()))?;
+        let recorded_tools = cassette.tool_names();
+        let cassette = Arc::new(Mutex::new(cassette));
+
+        let provider: Arc<dyn LlmProvider> = match mode {
+            ReplayMode::LiveLlm => self.planner.provider_arc(),
+            ReplayMode::Cassette | ReplayMode::LiveTools => {
+                Arc::new(CassetteProvider::new(cassette.clone()))
+            }
+        };
+
+        let registry = match mode {
+            ReplayMode::LiveTools => self.runner.registry_arc(),
+            ReplayMode::Cassette | ReplayMode::LiveLlm => {
+                // Keep the original tool definitions so a live LLM sees the same tools
+                let mut registry = ToolRegistry::new();
+                let mut definitions = self.runner.registry().all_definitions();
+                for name in recorded_tools {
+                    if !definitions.iter().any(|d| d.name == name) {
+                        definitions.push(ToolDefinition::new(name, "Recorded tool"));
+                    }
+                }
+                for definition in definitions {
+                    registry.register(Arc::new(CassetteTool::new(definition, cassette.clone())));
+                }
+                Arc::new(registry)
+            }
+        };
+
+        // No side channels: streaming, skill detection and compaction would
+        // call the provider outside the recorded chain
+        let mut config = OrchestratorConfig {
+            stream_responses: false,
+            auto_skill_detection: false,
+            ..self.config.clone()
+        };
+        config.compaction.enabled = false;
+
+        let mut replay = Orchestrator::new(provider, registry, config).with_event_store(store);
+        if mode == ReplayMode::LiveTools {
+            replay.approval_manager = self.approval_manager.clone();
+            replay.security_policy = self.security_policy.clone();
+        }
+
+        info!(execution_id = %execution_id, mode = ?mode, "Replaying execution");
+        let input = OrchestratorInput::new(
+            recorded.channel_type,
+            recorded.channel_id,
+            recorded.user_id,
+            recorded.text,
+        );
+        let result = replay.process(input).await?;
+
+        let unused_llm_responses = lock(&cassette).remaining_llm_responses();
+        if mode != ReplayMode::LiveLlm && unused_llm_responses > 0 {
+            warn!(
+                execution_id = %execution_id,
+                unused = unused_llm_responses,
+                "Replay finished before consuming all recorded LLM responses"
+            );
+        }
+
+        Ok(ReplayRun {
+            original_execution_id: execution_id,
+            replay_execution_id: result.execution_id,
+            mode,
+            status: result.status,
+            response: result.response,
+            unused_llm_responses,
+        })
+    }
+}
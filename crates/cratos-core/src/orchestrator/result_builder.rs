@@ -6,6 +6,7 @@ use super::core::Orchestrator;
 use super::sanitize::sanitize_error_for_user;
 use super::types::{ExecutionResult, ExecutionStatus, ToolCallRecord};
 use crate::event_bus::OrchestratorEvent;
+use cratos_llm::TokenUsage;
 use cratos_replay::EventType;
 use tracing::{error, warn};
 use uuid::Uuid;
@@ -61,6 +62,7 @@ impl Orchestrator {
             iterations: iteration,
             duration_ms,
             model: model_used,
+            usage: TokenUsage::default(),
         }
     }
 
@@ -116,6 +118,7 @@ impl Orchestrator {
             iterations: iteration,
             duration_ms,
             model: model_used,
+            usage: TokenUsage::default(),
         }
     }
 }
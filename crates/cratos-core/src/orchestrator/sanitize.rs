@@ -37,16 +37,18 @@ pub fn is_auth_or_permission_error(msg: &str) -> bool {
 
 /// Check if an LLM error is eligible for automatic fallback to a secondary provider.
 pub fn is_fallback_eligible(e: &crate::error::Error) -> bool {
+    matches!(e, crate::error::Error::Llm(e) if is_llm_fallback_eligible(e))
+}
+
+/// Check if a provider error is eligible for fallback to another provider.
+pub fn is_llm_fallback_eligible(e: &cratos_llm::Error) -> bool {
     matches!(
         e,
-        crate::error::Error::Llm(cratos_llm::Error::RateLimit)
-            | crate::error::Error::Llm(cratos_llm::Error::ServerError(_))
-            | crate::error::Error::Llm(cratos_llm::Error::Network(_))
-            | crate::error::Error::Llm(cratos_llm::Error::Timeout(_))
-    ) || matches!(
-        e,
-        crate::error::Error::Llm(cratos_llm::Error::Api(msg)) if is_auth_or_permission_error(msg)
-    )
+        cratos_llm::Error::RateLimit
+            | cratos_llm::Error::ServerError(_)
+            | cratos_llm::Error::Network(_)
+            | cratos_llm::Error::Timeout(_)
+    ) || matches!(e, cratos_llm::Error::Api(msg) if is_auth_or_permission_error(msg))
 }
 
 /// Detect if the model's first response is a refusal to use tools.
@@ -1,10 +1,12 @@
 //! Session context management for the Orchestrator
 //!
-//! Contains helper methods for loading sessions with RAG context enrichment.
+//! Contains helper methods for loading sessions with RAG context enrichment
+//! and LLM-based compaction.
 
-use crate::memory::SessionContext;
-use cratos_llm::Message;
-use cratos_memory::GraphMemory;
+use crate::error::Result;
+use crate::memory::{CompactionReport, SessionCompactor, SessionContext};
+use cratos_llm::{LlmProvider, Message, TaskType};
+use cratos_memory::{GraphMemory, MemoryScope};
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
@@ -18,28 +20,62 @@ impl Orchestrator {
         session_key: &str,
         input: &OrchestratorInput,
     ) -> Vec<Message> {
-        let mut session = match self.memory.get(session_key).await {
-            Ok(Some(s)) => {
-                debug!(session_key = %session_key, messages = s.get_messages().len(), "Session loaded");
-                s
+        let mut session = if let Some(history) = &input.history {
+            debug!(session_key = %session_key, messages = history.len(), "Session rebuilt from fork history");
+            let mut session = SessionContext::new(session_key);
+            for message in history {
+                session.add_message(message.clone());
             }
-            Ok(None) => {
-                debug!(session_key = %session_key, "No existing session, creating new");
-                SessionContext::new(session_key)
-            }
-            Err(e) => {
-                warn!(session_key = %session_key, error = %e, "Failed to load session, creating new");
-                SessionContext::new(session_key)
+            session
+        } else {
+            match self.memory.get(session_key).await {
+                Ok(Some(s)) => {
+                    debug!(session_key = %session_key, messages = s.get_messages().len(), "Session loaded");
+                    s
+                }
+                Ok(None) => {
+                    debug!(session_key = %session_key, "No existing session, creating new");
+                    SessionContext::new(session_key)
+                }
+                Err(e) => {
+                    warn!(session_key = %session_key, error = %e, "Failed to load session, creating new");
+                    SessionContext::new(session_key)
+                }
             }
         };
 
-        session.add_user_message(&input.text);
+        // Count the context with the tokenizer of the model that reads it
+        let provider = self.planner.provider();
+        session.set_tokenizer(provider.name(), provider.default_model());
+
+        // Forks may continue straight from an edited tool result, and resumed
+        // executions from the tool round they were parked in
+        if input.resumed_tool_results.is_none()
+            && (input.history.is_none() || !input.text.is_empty())
+        {
+            session.add_user_message(&input.text);
+        }
+
+        // Summarize old turns before trimming would start dropping them
+        let compactor = self.compactor();
+        if compactor.should_compact(&session) {
+            if let Err(e) = compactor.compact(&mut session).await {
+                warn!(session_key = %session_key, error = %e, "Automatic compaction failed");
+            }
+        }
+
+        // Attached MCP resources
+        if !input.mcp_resources.is_empty() {
+            self.attach_mcp_resources(&mut session, &input.mcp_resources)
+                .await;
+        }
 
         // Graph RAG: always-on context enrichment
         if let Some(gm) = &self.graph_memory {
-            self.enrich_with_graph_rag(&mut session, &input.text, gm)
+            let scope = input.memory_scope();
+            self.enrich_with_graph_rag(&mut session, &scope, &input.text, gm)
                 .await;
-            self.inject_explicit_memories(&mut session, &input.text, gm)
+            self.inject_explicit_memories(&mut session, &scope, &input.text, gm)
                 .await;
         }
 
@@ -74,10 +110,35 @@ impl Orchestrator {
         msgs
     }
 
-    /// Enrich session with Graph RAG context
+    /// Build a compactor backed by the summarization chain or the planner's provider
+    fn compactor(&self) -> SessionCompactor {
+        let provider: Arc<dyn LlmProvider> = match &self.failover {
+            Some(chains) => chains.for_task(TaskType::Summarization),
+            None => self.planner.provider_arc(),
+        };
+        SessionCompactor::new(provider, self.config.compaction.clone())
+    }
+
+    /// Compact a stored session now, regardless of the token threshold.
+    ///
+    /// Returns `Ok(None)` if the session does not exist or is too short.
+    pub async fn compact_session(&self, session_key: &str) -> Result<Option<CompactionReport>> {
+        let Some(mut session) = self.memory.get(session_key).await? else {
+            return Ok(None);
+        };
+
+        let report = self.compactor().compact(&mut session).await?;
+        if report.is_some() {
+            self.memory.save(&session).await?;
+        }
+        Ok(report)
+    }
+
+    /// Enrich session with Graph RAG context visible in `scope`
     async fn enrich_with_graph_rag(
         &self,
         session: &mut SessionContext,
+        scope: &MemoryScope,
         query: &str,
         gm: &Arc<GraphMemory>,
     ) {
@@ -92,7 +153,7 @@ impl Orchestrator {
                 "Token budget tight, replacing with Graph RAG context"
             );
             let budget = (session.max_tokens / 2) as u32;
-            match gm.retrieve(query, 20, budget).await {
+            match gm.retrieve(scope, query, 20, budget).await {
                 Ok(turns) if !turns.is_empty() => {
                     let retrieved_msgs = GraphMemory::turns_to_messages(&turns);
                     session.replace_with_retrieved(retrieved_msgs);
@@ -107,7 +168,7 @@ impl Orchestrator {
         } else {
             // Normal: ADD supplementary context
             let rag_budget = std::cmp::min((session.max_tokens / 10) as u32, 8000);
-            match gm.retrieve(query, 5, rag_budget).await {
+            match gm.retrieve(scope, query, 5, rag_budget).await {
                 Ok(turns) if !turns.is_empty() => {
                     let retrieved_msgs = GraphMemory::turns_to_messages(&turns);
                     session.insert_supplementary_context(retrieved_msgs);
@@ -131,6 +192,7 @@ impl Orchestrator {
     async fn inject_explicit_memories(
         &self,
         session: &mut SessionContext,
+        scope: &MemoryScope,
         query: &str,
         gm: &Arc<GraphMemory>,
     ) {
@@ -142,7 +204,7 @@ impl Orchestrator {
         // Score reference: exact name match = 10.0, vector similarity ~0.3-0.9,
         // entity link = 0.5, LIKE match = 0.3. Threshold 0.6 requires at least
         // a decent vector similarity or multiple weak signal sources.
-        match gm.recall_memories_filtered(query, 3, 0.6).await {
+        match gm.recall_memories_filtered(scope, query, 3, 0.6).await {
             Ok(memories) if !memories.is_empty() => {
                 let memory_names: Vec<&str> = memories.iter().map(|m| m.name.as_str()).collect();
                 let memory_context = memories
@@ -3,6 +3,9 @@
 #[cfg(test)]
 mod tests {
     use super::super::config::{OrchestratorConfig, OrchestratorInput};
+    use super::super::fork::{rebuild_history, ForkRequest};
+    use super::super::mcp_context::parse_prompt_args;
+    use super::super::replay::{CassetteProvider, CassetteTool};
     use super::super::sanitize::{
         is_fake_tool_use_text, is_fallback_eligible, is_tool_refusal, sanitize_error_for_user,
         sanitize_for_session_memory,
@@ -184,6 +187,104 @@ mod tests {
         assert!(!is_fallback_eligible(&err));
     }
 
+    /// Streaming provider that sends `text`, then fails if `fail` is set
+    struct StreamingProvider {
+        text: &'static str,
+        fail: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl cratos_llm::LlmProvider for StreamingProvider {
+        fn name(&self) -> &str {
+            "streaming"
+        }
+        fn supports_tools(&self) -> bool {
+            true
+        }
+        fn available_models(&self) -> Vec<String> {
+            vec!["m".to_string()]
+        }
+        fn default_model(&self) -> &str {
+            "m"
+        }
+        async fn complete(
+            &self,
+            _request: cratos_llm::CompletionRequest,
+        ) -> cratos_llm::Result<cratos_llm::CompletionResponse> {
+            unimplemented!("only streaming is used")
+        }
+        async fn complete_with_tools(
+            &self,
+            _request: cratos_llm::ToolCompletionRequest,
+        ) -> cratos_llm::Result<cratos_llm::ToolCompletionResponse> {
+            unimplemented!("only streaming is used")
+        }
+        fn supports_streaming(&self) -> bool {
+            true
+        }
+        async fn complete_stream(
+            &self,
+            _request: cratos_llm::ToolCompletionRequest,
+        ) -> cratos_llm::Result<cratos_llm::CompletionStream> {
+            use futures::StreamExt;
+
+            let mut chunks = vec![Ok(cratos_llm::StreamChunk::TextDelta(
+                self.text.to_string(),
+            ))];
+            if self.fail {
+                chunks.push(Err(cratos_llm::Error::Network("reset".into())));
+            }
+            Ok(futures::stream::iter(chunks).boxed())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fallback_after_partial_stream_resets_deltas() {
+        use crate::event_bus::{EventBus, OrchestratorEvent};
+        use std::sync::{Arc, Mutex};
+
+        let bus = Arc::new(EventBus::new(64));
+        let mut rx = bus.subscribe();
+        let orchestrator = super::super::core::Orchestrator::new(
+            Arc::new(StreamingProvider {
+                text: "half",
+                fail: true,
+            }),
+            Arc::new(cratos_tools::ToolRegistry::new()),
+            OrchestratorConfig::new().with_logging(false),
+        )
+        .with_fallback_provider(Arc::new(StreamingProvider {
+            text: "whole",
+            fail: false,
+        }))
+        .with_event_bus(bus);
+
+        let execution_id = uuid::Uuid::new_v4();
+        let deltas = Mutex::new(Vec::new());
+        let on_delta = |delta: &str| deltas.lock().unwrap().push(delta.to_string());
+        let mut sticky = false;
+        let response = orchestrator
+            .plan_with_fallback(
+                execution_id,
+                &[cratos_llm::Message::user("hi")],
+                &[],
+                None,
+                None,
+                &mut sticky,
+                Some(&on_delta),
+            )
+            .await
+            .unwrap();
+
+        assert!(sticky);
+        assert_eq!(response.content.as_deref(), Some("whole"));
+        assert_eq!(*deltas.lock().unwrap(), ["half", "whole"]);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(OrchestratorEvent::ChatReset { execution_id: id }) if id == execution_id
+        ));
+    }
+
     // ── Fake tool-use text detection ────────────────────────────────
 
     #[test]
@@ -213,6 +314,36 @@ mod tests {
         assert!(!is_fake_tool_use_text(&long));
     }
 
+    // ── MCP prompt commands ──────────────────────────────────────────
+
+    #[test]
+    fn test_parse_prompt_args() {
+        let prompt: cratos_tools::McpPrompt = serde_json::from_value(serde_json::json!({
+            "name": "review",
+            "arguments": [
+                {"name": "style"},
+                {"name": "code", "required": true}
+            ]
+        }))
+        .unwrap();
+
+        let args = parse_prompt_args(&prompt, "style=terse fn main() {}");
+        assert_eq!(args.get("style").map(String::as_str), Some("terse"));
+        assert_eq!(args.get("code").map(String::as_str), Some("fn main() {}"));
+
+        // Unknown keys are treated as free text
+        let args = parse_prompt_args(&prompt, "a=b");
+        assert_eq!(args.get("code").map(String::as_str), Some("a=b"));
+    }
+
+    #[test]
+    fn test_orchestrator_input_mcp_resources() {
+        let input = OrchestratorInput::new("websocket", "s1", "u1", "Summarize")
+            .with_mcp_resource("docs", "file:///README.md");
+        assert_eq!(input.mcp_resources.len(), 1);
+        assert_eq!(input.mcp_resources[0].server, "docs");
+    }
+
     // ── Config defaults ──────────────────────────────────────────────
 
     #[test]
@@ -220,4 +351,320 @@ mod tests {
         let config = OrchestratorConfig::default();
         assert_eq!(config.max_execution_secs, 180);
     }
+
+    // ── Replay ───────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_cassette_provider_and_tool() {
+        use cratos_llm::LlmProvider;
+        use cratos_replay::{Cassette, Event, EventType};
+        use cratos_tools::{Tool, ToolDefinition};
+        use std::sync::{Arc, Mutex};
+
+        let id = uuid::Uuid::new_v4();
+        let events = vec![
+            Event::new(id, 1, EventType::LlmResponse).with_payload(serde_json::json!({
+                "content": null,
+                "tool_calls": [{"id": "c1", "name": "exec", "arguments": "{\"command\":\"ls\"}"}],
+                "model": "gpt"
+            })),
+            Event::new(id, 2, EventType::ToolCall).with_payload(
+                serde_json::json!({"tool": "exec", "arguments": "{\"command\":\"ls\"}"}),
+            ),
+            Event::new(id, 3, EventType::ToolResult).with_payload(
+                serde_json::json!({"tool": "exec", "success": true, "output": "a.txt"}),
+            ),
+        ];
+        let cassette = Arc::new(Mutex::new(Cassette::from_events(&events).unwrap()));
+
+        let provider = CassetteProvider::new(cassette.clone());
+        let request = cratos_llm::ToolCompletionRequest::new(
+            cratos_llm::CompletionRequest::default(),
+            vec![],
+        );
+        let response = provider.complete_with_tools(request.clone()).await.unwrap();
+        assert_eq!(response.tool_calls[0].name, "exec");
+        assert_eq!(response.model, "gpt");
+        // An exhausted cassette is an error, not a fabricated answer
+        assert!(provider.complete_with_tools(request).await.is_err());
+
+        let tool = CassetteTool::new(ToolDefinition::new("exec", "Run"), cassette);
+        let result = tool.execute(serde_json::json!({"command": "ls"})).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, serde_json::json!("a.txt"));
+        assert!(!tool.execute(serde_json::json!({})).await.unwrap().success);
+    }
+
+    // ── Parallel tool calls ──────────────────────────────────────────
+
+    /// Tool that sleeps, then echoes its name
+    struct SlowTool(cratos_tools::ToolDefinition);
+
+    #[async_trait::async_trait]
+    impl cratos_tools::Tool for SlowTool {
+        fn definition(&self) -> &cratos_tools::ToolDefinition {
+            &self.0
+        }
+
+        async fn execute(
+            &self,
+            _input: serde_json::Value,
+        ) -> cratos_tools::Result<cratos_tools::ToolResult> {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            Ok(cratos_tools::ToolResult::success(
+                serde_json::json!(self.0.name),
+                200,
+            ))
+        }
+    }
+
+    fn parallel_orchestrator(max_parallel_tools: usize) -> super::super::core::Orchestrator {
+        use cratos_tools::{RiskLevel, ToolDefinition, ToolRegistry};
+        use std::sync::{Arc, Mutex};
+
+        let mut registry = ToolRegistry::new();
+        for (name, risk) in [
+            ("read_a", RiskLevel::Low),
+            ("read_b", RiskLevel::Low),
+            ("read_c", RiskLevel::Low),
+            ("write", RiskLevel::Medium),
+        ] {
+            registry.register(Arc::new(SlowTool(
+                ToolDefinition::new(name, name).with_risk_level(risk),
+            )));
+        }
+        let provider = super::super::replay::CassetteProvider::new(Arc::new(Mutex::new(
+            cratos_replay::Cassette::default(),
+        )));
+        let config = OrchestratorConfig::new()
+            .with_logging(false)
+            .with_max_parallel_tools(max_parallel_tools);
+        super::super::core::Orchestrator::new(Arc::new(provider), Arc::new(registry), config)
+    }
+
+    fn calls(names: &[&str]) -> Vec<cratos_llm::ToolCall> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| cratos_llm::ToolCall {
+                id: format!("call_{i}"),
+                name: name.to_string(),
+                arguments: "{}".to_string(),
+                thought_signature: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parallel_batches_stop_at_risky_tools() {
+        let orchestrator = parallel_orchestrator(4);
+        let calls = calls(&["read_a", "read_b", "write", "read_c"]);
+        assert_eq!(orchestrator.parallel_batch_len(&calls), 2);
+        assert_eq!(orchestrator.parallel_batch_len(&calls[2..]), 1);
+        assert_eq!(orchestrator.parallel_batch_len(&calls[3..]), 1);
+
+        let sequential = parallel_orchestrator(1);
+        assert_eq!(sequential.parallel_batch_len(&calls), 1);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_tool_calls_keep_order() {
+        use crate::memory::WorkingMemory;
+        use crate::steering::SteeringContext;
+
+        let orchestrator = parallel_orchestrator(4);
+        let calls = calls(&["read_a", "read_b", "read_c"]);
+        let execution_id = uuid::Uuid::new_v4();
+        let input = OrchestratorInput::new("cli", "c1", "u1", "read");
+        let mut working_memory = WorkingMemory::new();
+        let mut records = Vec::new();
+        let mut steering = SteeringContext::new(execution_id);
+
+        let start = std::time::Instant::now();
+        let (results, _) = orchestrator
+            .execute_tool_calls(
+                execution_id,
+                &input,
+                &[],
+                &calls,
+                Vec::new(),
+                &mut working_memory,
+                &mut records,
+                None,
+                None,
+                &mut steering,
+            )
+            .await
+            .unwrap();
+
+        // Three 200ms calls finish together instead of one after another
+        assert!(start.elapsed() < std::time::Duration::from_millis(500));
+        assert_eq!(
+            results,
+            vec![
+                serde_json::json!("read_a"),
+                serde_json::json!("read_b"),
+                serde_json::json!("read_c")
+            ]
+        );
+        let names: Vec<_> = records.iter().map(|r| r.tool_name.as_str()).collect();
+        assert_eq!(names, ["read_a", "read_b", "read_c"]);
+    }
+
+    #[tokio::test]
+    async fn test_resumed_round_runs_only_unfinished_calls() {
+        use crate::approval::ParkedExecution;
+        use crate::memory::WorkingMemory;
+        use crate::steering::SteeringContext;
+
+        let calls = calls(&["read_a", "read_b"]);
+        let parked = ParkedExecution {
+            channel_type: "telegram".to_string(),
+            channel_id: "c1".to_string(),
+            user_id: "u1".to_string(),
+            thread_id: None,
+            text: "read both".to_string(),
+            history: vec![
+                cratos_llm::Message::user("read both"),
+                cratos_llm::Message::assistant_with_tool_calls("", calls.clone()),
+            ],
+            tool_results: vec![serde_json::json!("before restart")],
+        };
+        let mut input = OrchestratorInput::from(parked);
+        assert_eq!(input.history.as_ref().map(Vec::len), Some(2));
+        let finished = input.resumed_tool_results.take().unwrap();
+
+        let orchestrator = parallel_orchestrator(1);
+        let execution_id = uuid::Uuid::new_v4();
+        let mut records = Vec::new();
+        let (results, _) = orchestrator
+            .execute_tool_calls(
+                execution_id,
+                &input,
+                input.history.as_deref().unwrap(),
+                &calls,
+                finished,
+                &mut WorkingMemory::new(),
+                &mut records,
+                None,
+                None,
+                &mut SteeringContext::new(execution_id),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                serde_json::json!("before restart"),
+                serde_json::json!("read_b")
+            ]
+        );
+        let names: Vec<_> = records.iter().map(|r| r.tool_name.as_str()).collect();
+        assert_eq!(names, ["read_b"]);
+    }
+
+    #[tokio::test]
+    async fn test_direct_tool_call_applies_security_policy() {
+        use crate::tool_policy::{PolicyAction, PolicyLevel, PolicyRule, ToolSecurityPolicy};
+
+        let mut policy = ToolSecurityPolicy::new();
+        policy.add_rule(PolicyRule {
+            level: PolicyLevel::Global,
+            scope: "*".to_string(),
+            tool_pattern: "write".to_string(),
+            action: PolicyAction::Deny,
+        });
+        let orchestrator = parallel_orchestrator(1).with_security_policy(policy);
+        let input = OrchestratorInput::new("mcp", "u1", "u1", "");
+        let scope = input.memory_scope();
+
+        let result = orchestrator
+            .execute_tool_direct(&input, "read_a", serde_json::json!({}), &scope)
+            .await
+            .unwrap();
+        assert_eq!(result.result.output, serde_json::json!("read_a"));
+
+        let denied = orchestrator
+            .execute_tool_direct(&input, "write", serde_json::json!({}), &scope)
+            .await;
+        assert!(matches!(denied, Err(crate::error::Error::Unauthorized(_))));
+    }
+
+    // ── Fork ─────────────────────────────────────────────────────────
+
+    fn recorded_events() -> Vec<cratos_replay::Event> {
+        use cratos_replay::{Event, EventType};
+        use serde_json::json;
+
+        let id = uuid::Uuid::new_v4();
+        vec![
+            Event::new(id, 1, EventType::UserInput).with_payload(json!({
+                "channel_type": "cli", "channel_id": "c1", "user_id": "u1", "text": "check"
+            })),
+            Event::new(id, 2, EventType::LlmResponse).with_payload(json!({
+                "content": null,
+                "tool_calls": [
+                    {"id": "c1", "name": "exec", "arguments": "{}"},
+                    {"id": "c2", "name": "http_get", "arguments": "{}"}
+                ],
+                "model": "gpt"
+            })),
+            Event::new(id, 3, EventType::ToolCall).with_payload(json!({"tool": "exec"})),
+            Event::new(id, 4, EventType::ToolResult)
+                .with_payload(json!({"tool": "exec", "success": true, "output": "ok"})),
+            Event::new(id, 5, EventType::ToolCall).with_payload(json!({"tool": "http_get"})),
+            Event::new(id, 6, EventType::ToolResult)
+                .with_payload(json!({"tool": "http_get", "success": true, "output": "page"})),
+            Event::new(id, 7, EventType::LlmResponse)
+                .with_payload(json!({"content": "done", "tool_calls": [], "model": "gpt"})),
+        ]
+    }
+
+    #[test]
+    fn test_fork_history_with_edited_tool_result() {
+        let request = ForkRequest {
+            sequence_num: 4,
+            tool_result: Some(serde_json::json!("edited")),
+            ..Default::default()
+        };
+        let history = rebuild_history(&recorded_events(), &request).unwrap();
+
+        // The rest of the tool round is kept so no call is left unanswered
+        assert_eq!(history.messages.len(), 4);
+        assert_eq!(history.messages[1].tool_calls.len(), 2);
+        assert_eq!(history.messages[2].content, "\"edited\"");
+        assert_eq!(history.messages[3].tool_call_id.as_deref(), Some("c2"));
+        assert!(history.text.is_empty());
+        assert_eq!(history.user_id, "u1");
+
+        // Tool result edits only apply to tool result events
+        let request = ForkRequest {
+            sequence_num: 2,
+            tool_result: Some(serde_json::json!("edited")),
+            ..Default::default()
+        };
+        assert!(rebuild_history(&recorded_events(), &request).is_err());
+    }
+
+    #[test]
+    fn test_fork_history_at_user_input() {
+        let request = ForkRequest {
+            sequence_num: 1,
+            user_message: Some("check again".to_string()),
+            ..Default::default()
+        };
+        let history = rebuild_history(&recorded_events(), &request).unwrap();
+        assert!(history.messages.is_empty());
+        assert_eq!(history.text, "check again");
+
+        let request = ForkRequest {
+            sequence_num: 7,
+            user_message: Some("and now?".to_string()),
+            ..Default::default()
+        };
+        let history = rebuild_history(&recorded_events(), &request).unwrap();
+        assert_eq!(history.messages.last().unwrap().content, "done");
+        assert_eq!(history.text, "and now?");
+    }
 }
@@ -1,16 +1,27 @@
 //! Orchestrator tool execution
 //!
 //! Contains the tool execution logic for the Orchestrator:
-//! - `execute_tool_calls`: Executes a list of tool calls
+//! - `execute_tool_calls`: Executes a list of tool calls, running consecutive
+//!   low-risk calls concurrently
+//! - `execute_tool_direct`: Runs a single tool call made outside a conversation
+//!   through the same security checks
+//! - `await_tool_approval`: Parks a tool call until the user approves it
 
+use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalStatus, ParkedExecution};
 use crate::event_bus::OrchestratorEvent;
 use crate::memory::WorkingMemory;
 use crate::tool_policy::{PolicyAction, PolicyContext};
-use cratos_llm::ToolCall;
+use cratos_llm::{Message, ToolCall};
+use cratos_memory::MemoryScope;
 use cratos_replay::EventType;
+use cratos_tools::registry::RiskLevel;
+use cratos_tools::ExecutionResult;
+use futures::StreamExt;
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use super::config::OrchestratorInput;
 use super::core::Orchestrator;
 use super::types::ToolCallRecord;
 use crate::steering::{SteerDecision, SteeringContext};
@@ -22,75 +33,86 @@ impl Orchestrator {
     ///
     /// When `matched_skill_id` is provided, records persona-skill metrics
     /// via `PersonaSkillStore` and checks for auto-assignment eligibility.
+    ///
+    /// `messages` is the conversation ending with the assistant message that
+    /// made the calls; it is saved with approval requests so the execution
+    /// can be continued after a restart. `finished` holds the results of
+    /// leading calls that already ran (a resumed round); those are not run
+    /// again. The returned results cover every call.
     #[allow(clippy::too_many_arguments)]
     pub(crate) async fn execute_tool_calls(
         &self,
         execution_id: Uuid,
+        input: &OrchestratorInput,
+        messages: &[Message],
         tool_calls: &[ToolCall],
+        finished: Vec<serde_json::Value>,
         working_memory: &mut WorkingMemory,
         records: &mut Vec<ToolCallRecord>,
         active_persona: Option<&str>,
         matched_skill_id: Option<Uuid>,
         steering_ctx: &mut SteeringContext,
     ) -> crate::error::Result<(Vec<serde_json::Value>, Vec<String>)> {
-        let mut results = Vec::with_capacity(tool_calls.len());
+        let mut results = finished;
+        results.truncate(tool_calls.len());
         let mut steering_messages = Vec::new();
+        let memory_scope = input.memory_scope();
+        let fan_out = self.config.max_parallel_tools.max(1);
 
-        for call in tool_calls {
-            // Steering Check
-            match steering_ctx.check_before_tool().await? {
-                SteerDecision::Abort(reason) => {
-                    return Err(crate::error::Error::Aborted(
-                        reason.unwrap_or_else(|| "User aborted".to_string()),
-                    ));
-                }
-                SteerDecision::Skip(id) if id == call.id => {
-                    info!(
-                        execution_id = %execution_id,
-                        tool_call_id = %call.id,
-                        "Skipping tool by steering"
-                    );
-                    continue;
-                }
-                _ => {}
-            }
-
-            info!(
-                execution_id = %execution_id,
-                tool = %call.name,
-                args = %call.arguments,
-                "Executing tool"
-            );
+        let mut remaining = &tool_calls[results.len()..];
+        while !remaining.is_empty() {
+            let first_index = tool_calls.len() - remaining.len();
+            let (batch, rest) = remaining.split_at(self.parallel_batch_len(remaining));
+            remaining = rest;
 
-            // Emit tool started event
-            self.emit(OrchestratorEvent::ToolStarted {
-                execution_id,
-                tool_name: call.name.clone(),
-                tool_call_id: call.id.clone(),
-            });
+            // Steering, replay events and approval run in call order before the
+            // batch starts, so sequence numbers match the model's call order
+            let mut gates = Vec::with_capacity(batch.len());
+            for (offset, call) in batch.iter().enumerate() {
+                let round = ToolRound {
+                    messages,
+                    results: &results,
+                };
+                gates.push(
+                    self.gate_tool_call(
+                        execution_id,
+                        input,
+                        call,
+                        first_index + offset,
+                        round,
+                        steering_ctx,
+                    )
+                    .await?,
+                );
+            }
 
-            // Log tool call event
-            self.log_event(
-                execution_id,
-                EventType::ToolCall,
-                &serde_json::json!({
-                    "tool": call.name,
-                    "arguments": call.arguments
-                }),
-            )
-            .await;
+            // Cleared calls run concurrently; `buffered` yields in call order.
+            // The futures are collected first so no closure ends up in the
+            // stream type, which would keep the execution future from being Send
+            let runnable: Vec<_> = batch
+                .iter()
+                .zip(&gates)
+                .filter_map(|(call, gate)| match gate {
+                    ToolGate::Run(args) => Some(self.run_tool(call, args.clone(), &memory_scope)),
+                    _ => None,
+                })
+                .collect();
+            let mut outcomes = futures::stream::iter(runnable)
+                .buffered(fan_out)
+                .collect::<Vec<_>>()
+                .await
+                .into_iter();
 
-            // 6-Level security policy check
-            if let Some(ref policy) = self.security_policy {
-                let ctx = PolicyContext::default();
-                match policy.resolve_or_default(&call.name, &ctx) {
-                    PolicyAction::Deny => {
-                        warn!(
-                            execution_id = %execution_id,
-                            tool = %call.name,
-                            "Tool denied by security policy"
-                        );
-                        let output = serde_json::json!({"error": format!("Tool '{}' denied by security policy", call.name)});
+            for ((offset, call), gate) in batch.iter().enumerate().zip(gates) {
+                let input = match gate {
+                    ToolGate::Skipped => {
+                        // Every call needs a result, or the history no longer
+                        // pairs results with calls
+                        results.push(serde_json::json!({"error": "Skipped by user"}));
+                        continue;
+                    }
+                    ToolGate::Denied(reason) => {
+                        let output = serde_json::json!({"error": reason});
                         self.emit(OrchestratorEvent::ToolCompleted {
                             execution_id,
                             tool_call_id: call.id.clone(),
@@ -109,235 +131,515 @@ impl Orchestrator {
                         results.push(output);
                         continue;
                     }
-                    PolicyAction::RequireApproval => {
-                        // If approval manager exists, request approval; otherwise deny
-                        if let Some(ref _am) = self.approval_manager {
-                            debug!(
-                                execution_id = %execution_id,
-                                tool = %call.name,
-                                "Tool requires approval per security policy"
-                            );
-                            // Approval flow is handled downstream — proceed for now
-                        } else {
-                            warn!(
-                                execution_id = %execution_id,
-                                tool = %call.name,
-                                "Tool requires approval but no approval manager configured"
-                            );
-                        }
-                    }
-                    PolicyAction::Allow => {}
-                }
-            }
-
-            // Parse arguments, fallback to empty object if malformed
-            let input: serde_json::Value =
-                serde_json::from_str(&call.arguments).unwrap_or_else(|e| {
-                    warn!(
-                        tool = %call.name,
-                        error = %e,
-                        arguments = %call.arguments,
-                        "Failed to parse tool arguments, using empty object"
-                    );
-                    serde_json::json!({})
-                });
+                    ToolGate::Run(args) => args,
+                };
+                let Some((result, elapsed)) = outcomes.next() else {
+                    break;
+                };
+                let duration_ms = elapsed.as_millis() as u64;
+                let duration_secs = elapsed.as_secs_f64();
 
-            let start = std::time::Instant::now();
-            let result = self.runner.execute(&call.name, input.clone()).await;
-            let duration_ms = start.elapsed().as_millis() as u64;
-            let duration_secs = start.elapsed().as_secs_f64();
-
-            let (output, success, error) = match result {
-                Ok(exec_result) => {
-                    let mut output = exec_result.result.output.clone();
-                    let success = exec_result.result.success;
-                    let error = exec_result.result.error.clone();
-                    // When tool returns failure with null output, embed error in
-                    // the output JSON so downstream consumers (LLM conversation,
-                    // fallback error messages) can access the reason.
-                    if !success && output.is_null() {
-                        if let Some(ref err_msg) = error {
-                            output = serde_json::json!({"error": err_msg});
+                let (output, success, error) = match result {
+                    Ok(exec_result) => {
+                        let mut output = exec_result.result.output.clone();
+                        let success = exec_result.result.success;
+                        let error = exec_result.result.error.clone();
+                        // When tool returns failure with null output, embed error in
+                        // the output JSON so downstream consumers (LLM conversation,
+                        // fallback error messages) can access the reason.
+                        if !success && output.is_null() {
+                            if let Some(ref err_msg) = error {
+                                output = serde_json::json!({"error": err_msg});
+                            }
                         }
-                    }
-                    // Tool Doctor: diagnose soft failures (success=false from tool)
-                    // Inject diagnosis into the output so the LLM can see alternatives
-                    if !success {
-                        let err_msg = error.as_deref().unwrap_or("unknown tool error");
-                        let diagnosis = self.doctor.diagnose(&call.name, err_msg);
-                        if diagnosis.confidence > 0.3 {
-                            debug!(
-                                tool = %call.name,
-                                category = %diagnosis.category.display_name(),
-                                confidence = %format!("{:.0}%", diagnosis.confidence * 100.0),
-                                "Tool Doctor soft-failure diagnosis"
-                            );
-                            // Build hint with alternatives for the LLM
-                            let alternatives: Vec<&str> = diagnosis.alternatives
-                                .iter().filter_map(|a| a.tool_name.as_deref()).collect();
-                            let hint = format!(
-                                "\n[diagnosis: {} ({:.0}%)] {}{}",
-                                diagnosis.category.display_name(),
-                                diagnosis.confidence * 100.0,
-                                diagnosis.checklist.first()
-                                    .map(|c| c.instruction.as_str()).unwrap_or(""),
-                                if alternatives.is_empty() { String::new() }
-                                else { format!(" | alternatives: {}", alternatives.join(", ")) }
-                            );
-                            // Append hint to output so the LLM can read it
-                            if let Some(obj) = output.as_object_mut() {
-                                obj.insert("_diagnosis".to_string(), serde_json::json!(hint));
+                        // Tool Doctor: diagnose soft failures (success=false from tool)
+                        // Inject diagnosis into the output so the LLM can see alternatives
+                        if !success {
+                            let err_msg = error.as_deref().unwrap_or("unknown tool error");
+                            let diagnosis = self.doctor.diagnose(&call.name, err_msg);
+                            if diagnosis.confidence > 0.3 {
+                                debug!(
+                                    tool = %call.name,
+                                    category = %diagnosis.category.display_name(),
+                                    confidence = %format!("{:.0}%", diagnosis.confidence * 100.0),
+                                    "Tool Doctor soft-failure diagnosis"
+                                );
+                                // Build hint with alternatives for the LLM
+                                let alternatives: Vec<&str> = diagnosis.alternatives
+                                    .iter().filter_map(|a| a.tool_name.as_deref()).collect();
+                                let hint = format!(
+                                    "\n[diagnosis: {} ({:.0}%)] {}{}",
+                                    diagnosis.category.display_name(),
+                                    diagnosis.confidence * 100.0,
+                                    diagnosis.checklist.first()
+                                        .map(|c| c.instruction.as_str()).unwrap_or(""),
+                                    if alternatives.is_empty() { String::new() }
+                                    else { format!(" | alternatives: {}", alternatives.join(", ")) }
+                                );
+                                // Append hint to output so the LLM can read it
+                                if let Some(obj) = output.as_object_mut() {
+                                    obj.insert("_diagnosis".to_string(), serde_json::json!(hint));
+                                }
                             }
                         }
+                        (output, success, error)
                     }
-                    (output, success, error)
-                }
-                Err(e) => {
-                    error!(
-                        execution_id = %execution_id,
-                        tool = %call.name,
-                        error = %e,
-                        "Tool execution failed"
-                    );
+                    Err(e) => {
+                        error!(
+                            execution_id = %execution_id,
+                            tool = %call.name,
+                            error = %e,
+                            "Tool execution failed"
+                        );
 
-                    // Tool Doctor: auto-diagnose failure
-                    let error_str = e.to_string();
-                    let diagnosis = self.doctor.diagnose(&call.name, &error_str);
-                    let hint = self.doctor.format_diagnosis(&diagnosis);
-                    debug!(
-                        tool = %call.name,
-                        category = %diagnosis.category.display_name(),
-                        confidence = %format!("{:.0}%", diagnosis.confidence * 100.0),
-                        "Tool Doctor diagnosis"
-                    );
+                        // Tool Doctor: auto-diagnose failure
+                        let error_str = e.to_string();
+                        let diagnosis = self.doctor.diagnose(&call.name, &error_str);
+                        let hint = self.doctor.format_diagnosis(&diagnosis);
+                        debug!(
+                            tool = %call.name,
+                            category = %diagnosis.category.display_name(),
+                            confidence = %format!("{:.0}%", diagnosis.confidence * 100.0),
+                            "Tool Doctor diagnosis"
+                        );
 
-                    let enriched_error = format!(
-                        "{}\n\n[Diagnosis: {} (confidence: {:.0}%)]\nSuggested fix: {}",
-                        error_str,
-                        diagnosis.category.display_name(),
-                        diagnosis.confidence * 100.0,
-                        diagnosis
-                            .checklist
-                            .first()
-                            .map(|c| c.instruction.as_str())
-                            .unwrap_or("Check logs for details"),
-                    );
+                        let enriched_error = format!(
+                            "{}\n\n[Diagnosis: {} (confidence: {:.0}%)]\nSuggested fix: {}",
+                            error_str,
+                            diagnosis.category.display_name(),
+                            diagnosis.confidence * 100.0,
+                            diagnosis
+                                .checklist
+                                .first()
+                                .map(|c| c.instruction.as_str())
+                                .unwrap_or("Check logs for details"),
+                        );
 
-                    // Log diagnosis hint in event store
-                    self.log_event(
-                        execution_id,
-                        EventType::Error,
-                        &serde_json::json!({
-                            "tool": call.name,
-                            "error": error_str,
-                            "diagnosis": hint,
-                        }),
-                    )
-                    .await;
+                        // Log diagnosis hint in event store
+                        self.log_event(
+                            execution_id,
+                            EventType::Error,
+                            &serde_json::json!({
+                                "tool": call.name,
+                                "error": error_str,
+                                "diagnosis": hint,
+                            }),
+                        )
+                        .await;
 
-                    (
-                        serde_json::json!({"error": enriched_error}),
-                        false,
-                        Some(error_str),
-                    )
-                }
-            };
+                        (
+                            serde_json::json!({"error": enriched_error}),
+                            false,
+                            Some(error_str),
+                        )
+                    }
+                };
 
-            // Log tool result event
-            self.log_event(
-                execution_id,
-                EventType::ToolResult,
-                &serde_json::json!({
+                // Log tool result event, with the diff of file-changing tools
+                // (file_edit) at the top level for replay viewers. The calls of
+                // a batch are all logged before their results, so `call_index`
+                // pairs each result with its call
+                let mut payload = serde_json::json!({
                     "tool": call.name,
+                    "call_index": first_index + offset,
                     "success": success,
                     "output": output,
                     "error": error,
                     "duration_ms": duration_ms
-                }),
-            )
-            .await;
+                });
+                if let Some(diff) = output.get("diff").filter(|diff| diff.is_string()) {
+                    payload["diff"] = diff.clone();
+                }
+                self.log_event(execution_id, EventType::ToolResult, &payload).await;
 
-            info!(
-                execution_id = %execution_id,
-                tool = %call.name,
-                success = %success,
-                duration_ms = %duration_ms,
-                "Tool completed"
-            );
+                info!(
+                    execution_id = %execution_id,
+                    tool = %call.name,
+                    success = %success,
+                    duration_ms = %duration_ms,
+                    "Tool completed"
+                );
 
-            // Emit tool completed event
-            self.emit(OrchestratorEvent::ToolCompleted {
-                execution_id,
-                tool_call_id: call.id.clone(),
-                tool_name: call.name.clone(),
-                success,
-                duration_ms,
-            });
+                // Emit tool completed event
+                self.emit(OrchestratorEvent::ToolCompleted {
+                    execution_id,
+                    tool_call_id: call.id.clone(),
+                    tool_name: call.name.clone(),
+                    success,
+                    duration_ms,
+                });
 
-            // Record labeled metrics
-            {
-                let status_label = if success { "ok" } else { "error" };
-                crate::utils::metrics_global::labeled_counter("cratos_tool_executions_total")
-                    .inc(&[("tool_name", &call.name), ("status", status_label)]);
-                crate::utils::metrics_global::labeled_histogram("cratos_tool_duration_seconds")
-                    .observe(&[("tool_name", &call.name)], duration_secs);
+                // Record labeled metrics
+                {
+                    let status_label = if success { "ok" } else { "error" };
+                    crate::utils::metrics_global::labeled_counter("cratos_tool_executions_total")
+                        .inc(&[("tool_name", &call.name), ("status", status_label)]);
+                    crate::utils::metrics_global::labeled_histogram("cratos_tool_duration_seconds")
+                        .observe(&[("tool_name", &call.name)], duration_secs);
+                }
+
+                // Record in working memory
+                working_memory.record_tool_execution(
+                    &call.name,
+                    input.clone(),
+                    Some(output.clone()),
+                    success,
+                    error,
+                );
+
+                // Record for return
+                records.push(ToolCallRecord {
+                    tool_name: call.name.clone(),
+                    input,
+                    output: output.clone(),
+                    success,
+                    duration_ms,
+                    persona_name: active_persona.map(String::from),
+                });
+
+                // Phase 8: Record persona-skill metrics if skill was matched
+                if let (Some(store), Some(persona), Some(skill_id)) =
+                    (&self.persona_skill_store, active_persona, matched_skill_id)
+                {
+                    let config = cratos_skills::AutoAssignmentConfig::default();
+                    if let Err(e) = store
+                        .record_execution(persona, skill_id, success, Some(duration_ms))
+                        .await
+                    {
+                        warn!(
+                            persona = %persona,
+                            skill_id = %skill_id,
+                            error = %e,
+                            "Failed to record persona-skill execution"
+                        );
+                    }
+                    if let Err(e) = store
+                        .check_auto_assignment(persona, skill_id, &config)
+                        .await
+                    {
+                        warn!(
+                            persona = %persona,
+                            skill_id = %skill_id,
+                            error = %e,
+                            "Failed to check auto-assignment"
+                        );
+                    }
+                }
+
+                results.push(output);
+
+                if let Some(msg) = steering_ctx.apply_after_tool().await {
+                    steering_messages.push(msg);
+                }
             }
+        }
 
-            // Record in working memory
-            working_memory.record_tool_execution(
-                &call.name,
-                input.clone(),
-                Some(output.clone()),
-                success,
-                error,
+        Ok((results, steering_messages))
+    }
+
+    /// Number of leading calls that may run together as one batch
+    ///
+    /// Only consecutive low-risk (read-only) tools are batched; any other call
+    /// runs on its own.
+    pub(super) fn parallel_batch_len(&self, calls: &[ToolCall]) -> usize {
+        if self.config.max_parallel_tools <= 1 {
+            return 1;
+        }
+        let registry = self.runner.registry();
+        calls
+            .iter()
+            .take_while(|call| {
+                registry
+                    .get_definition(&call.name)
+                    .is_some_and(|def| def.risk_level == RiskLevel::Low)
+            })
+            .count()
+            .max(1)
+    }
+
+    /// Run the steering check, log the call and apply the security policy
+    ///
+    /// `call_index` is the position of the call in the model's response.
+    async fn gate_tool_call(
+        &self,
+        execution_id: Uuid,
+        input: &OrchestratorInput,
+        call: &ToolCall,
+        call_index: usize,
+        round: ToolRound<'_>,
+        steering_ctx: &mut SteeringContext,
+    ) -> crate::error::Result<ToolGate> {
+        match steering_ctx.check_before_tool().await? {
+            SteerDecision::Abort(reason) => {
+                return Err(crate::error::Error::Aborted(
+                    reason.unwrap_or_else(|| "User aborted".to_string()),
+                ));
+            }
+            SteerDecision::Skip(id) if id == call.id => {
+                info!(
+                    execution_id = %execution_id,
+                    tool_call_id = %call.id,
+                    "Skipping tool by steering"
+                );
+                return Ok(ToolGate::Skipped);
+            }
+            _ => {}
+        }
+
+        info!(
+            execution_id = %execution_id,
+            tool = %call.name,
+            args = %call.arguments,
+            "Executing tool"
+        );
+
+        // Emit tool started event
+        self.emit(OrchestratorEvent::ToolStarted {
+            execution_id,
+            tool_name: call.name.clone(),
+            tool_call_id: call.id.clone(),
+        });
+
+        // Log tool call event
+        self.log_event(
+            execution_id,
+            EventType::ToolCall,
+            &serde_json::json!({
+                "tool": call.name,
+                "call_index": call_index,
+                "arguments": call.arguments
+            }),
+        )
+        .await;
+
+        if let Some(reason) = self
+            .check_tool_policy(execution_id, input, call, Some(round))
+            .await
+        {
+            return Ok(ToolGate::Denied(reason));
+        }
+
+        // Parse arguments, fallback to empty object if malformed
+        let args = serde_json::from_str(&call.arguments).unwrap_or_else(|e| {
+            warn!(
+                tool = %call.name,
+                error = %e,
+                arguments = %call.arguments,
+                "Failed to parse tool arguments, using empty object"
             );
+            serde_json::json!({})
+        });
+        Ok(ToolGate::Run(args))
+    }
 
-            // Record for return
-            records.push(ToolCallRecord {
-                tool_name: call.name.clone(),
-                input,
-                output: output.clone(),
-                success,
-                duration_ms,
-                persona_name: active_persona.map(String::from),
-            });
+    /// Run a single tool call on a user's behalf outside a conversation
+    /// (e.g. `tools/call` from an MCP client)
+    ///
+    /// The call goes through the same security policy and approval rules as
+    /// tool calls made by the model. A refused call returns
+    /// [`crate::error::Error::Unauthorized`].
+    pub async fn execute_tool_direct(
+        &self,
+        input: &OrchestratorInput,
+        name: &str,
+        arguments: serde_json::Value,
+        memory_scope: &MemoryScope,
+    ) -> crate::error::Result<ExecutionResult> {
+        let execution_id = Uuid::new_v4();
+        let call = ToolCall {
+            id: execution_id.to_string(),
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            thought_signature: None,
+        };
 
-            // Phase 8: Record persona-skill metrics if skill was matched
-            if let (Some(store), Some(persona), Some(skill_id)) =
-                (&self.persona_skill_store, active_persona, matched_skill_id)
-            {
-                let config = cratos_skills::AutoAssignmentConfig::default();
-                if let Err(e) = store
-                    .record_execution(persona, skill_id, success, Some(duration_ms))
-                    .await
-                {
-                    warn!(
-                        persona = %persona,
-                        skill_id = %skill_id,
-                        error = %e,
-                        "Failed to record persona-skill execution"
-                    );
+        // The caller waits for the decision itself; nothing to resume after a restart
+        if let Some(reason) = self
+            .check_tool_policy(execution_id, input, &call, None)
+            .await
+        {
+            return Err(crate::error::Error::Unauthorized(reason));
+        }
+        let (result, _) = self.run_tool(&call, arguments, memory_scope).await;
+        result.map_err(Into::into)
+    }
+
+    /// Apply the security policy and approval rules to a tool call
+    ///
+    /// Returns the denial reason, or `None` if the call may proceed. With the
+    /// tool `round` the call belongs to, a pending approval request carries
+    /// the execution state so it can be resumed after a restart.
+    async fn check_tool_policy(
+        &self,
+        execution_id: Uuid,
+        input: &OrchestratorInput,
+        call: &ToolCall,
+        round: Option<ToolRound<'_>>,
+    ) -> Option<String> {
+        // 6-Level security policy check, then approval rules
+        let mut action = self
+            .security_policy
+            .as_ref()
+            .map_or(PolicyAction::Allow, |policy| {
+                policy.resolve_or_default(&call.name, &PolicyContext::default())
+            });
+        if action == PolicyAction::Allow {
+            if let Some(ref am) = self.approval_manager {
+                let args = serde_json::from_str(&call.arguments)
+                    .unwrap_or_else(|_| serde_json::json!({}));
+                if am.requires_approval(&call.name, &args) {
+                    action = PolicyAction::RequireApproval;
                 }
-                if let Err(e) = store
-                    .check_auto_assignment(persona, skill_id, &config)
-                    .await
-                {
+            }
+        }
+
+        match action {
+            PolicyAction::Deny => {
+                warn!(
+                    execution_id = %execution_id,
+                    tool = %call.name,
+                    "Tool denied by security policy"
+                );
+                Some(format!("Tool '{}' denied by security policy", call.name))
+            }
+            PolicyAction::RequireApproval => {
+                // If approval manager exists, request approval; otherwise proceed
+                if self.approval_manager.is_some() {
+                    self.await_tool_approval(execution_id, call, input, round)
+                        .await
+                } else {
                     warn!(
-                        persona = %persona,
-                        skill_id = %skill_id,
-                        error = %e,
-                        "Failed to check auto-assignment"
+                        execution_id = %execution_id,
+                        tool = %call.name,
+                        "Tool requires approval but no approval manager configured"
                     );
+                    None
                 }
             }
+            PolicyAction::Allow => None,
+        }
+    }
 
-            results.push(output);
+    /// Run one tool on the caller's behalf and time it
+    ///
+    /// Tools acting on Graph RAG memory (e.g. `memory`) see the caller's scope.
+    async fn run_tool(
+        &self,
+        call: &ToolCall,
+        args: serde_json::Value,
+        memory_scope: &MemoryScope,
+    ) -> (cratos_tools::Result<ExecutionResult>, Duration) {
+        let start = Instant::now();
+        let result = memory_scope
+            .clone()
+            .scope(self.runner.execute(&call.name, args))
+            .await;
+        (result, start.elapsed())
+    }
 
-            if let Some(msg) = steering_ctx.apply_after_tool().await {
-                steering_messages.push(msg);
+    /// Ask the user to approve a tool call and wait for the decision.
+    ///
+    /// With the tool `round`, the request carries the execution state so that,
+    /// if the server restarts while waiting, the execution can be continued
+    /// from this call once the request is approved. Returns the denial reason,
+    /// or `None` if the call may proceed.
+    async fn await_tool_approval(
+        &self,
+        execution_id: Uuid,
+        call: &ToolCall,
+        input: &OrchestratorInput,
+        round: Option<ToolRound<'_>>,
+    ) -> Option<String> {
+        let manager = self.approval_manager.as_ref()?;
+
+        let args = serde_json::from_str(&call.arguments).unwrap_or_else(|_| serde_json::json!({}));
+
+        // A resumed execution already holds the user's approval for this call
+        if let Some(request_id) = input.resumed_approval {
+            if manager
+                .take_grant(request_id, &input.user_id, &call.name, &args)
+                .await
+            {
+                debug!(
+                    execution_id = %execution_id,
+                    tool = %call.name,
+                    request_id = %request_id,
+                    "Using approval granted before restart"
+                );
+                return None;
             }
         }
 
-        Ok((results, steering_messages))
+        let timeout_secs = manager.default_timeout_secs();
+        let mut request = ApprovalRequest::new(
+            execution_id,
+            &input.channel_type,
+            &input.channel_id,
+            &input.user_id,
+            format!("Run tool '{}'", call.name),
+            "Tool requires approval per security policy",
+            timeout_secs,
+        )
+        .with_tool(&call.name, args);
+        // Tools run on this machine, so its owner is the node owner
+        if let Some(owner) = &manager.policy().host_owner {
+            request = request.with_node_owner(owner);
+        }
+        if let Some(round) = round {
+            request = request.with_parked(ParkedExecution {
+                channel_type: input.channel_type.clone(),
+                channel_id: input.channel_id.clone(),
+                user_id: input.user_id.clone(),
+                thread_id: input.thread_id.clone(),
+                text: input.text.clone(),
+                history: round.messages.to_vec(),
+                tool_results: round.results.to_vec(),
+            });
+        }
+        let request_id = request.id;
+
+        info!(
+            execution_id = %execution_id,
+            tool = %call.name,
+            request_id = %request_id,
+            "Waiting for tool approval"
+        );
+        let (_, rx) = manager.submit(request, self.event_bus.as_deref()).await;
+        let decision = ApprovalManager::wait_async(
+            rx,
+            Duration::from_secs(timeout_secs.max(0) as u64),
+        )
+        .await;
+
+        if decision == ApprovalStatus::Approved {
+            None
+        } else {
+            warn!(
+                execution_id = %execution_id,
+                tool = %call.name,
+                request_id = %request_id,
+                "Tool approval denied or timed out"
+            );
+            Some(format!("Tool '{}' was not approved by the user", call.name))
+        }
     }
 }
+
+/// Tool round a call belongs to, as saved when the call waits for approval
+#[derive(Clone, Copy)]
+struct ToolRound<'a> {
+    /// Conversation ending with the assistant message that made the calls
+    messages: &'a [Message],
+    /// Results of the round's calls that already finished
+    results: &'a [serde_json::Value],
+}
+
+/// Outcome of the checks that run before a tool call
+enum ToolGate {
+    /// Skipped by steering
+    Skipped,
+    /// Refused by the security policy or the user, with the reason
+    Denied(String),
+    /// Cleared to run with the parsed arguments
+    Run(serde_json::Value),
+}
@@ -5,6 +5,7 @@
 //! - `ExecutionStatus`, `ExecutionResult`, `ExecutionArtifact` for execution results
 //! - `ToolCallRecord` for tool call tracking
 
+use cratos_llm::TokenUsage;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -66,6 +67,9 @@ pub struct ExecutionResult {
     pub duration_ms: u64,
     /// Model used
     pub model: Option<String>,
+    /// Tokens consumed across all LLM calls of the execution
+    #[serde(default)]
+    pub usage: TokenUsage,
 }
 
 /// Artifact generated during execution
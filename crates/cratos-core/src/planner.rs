@@ -5,13 +5,16 @@
 
 use crate::error::{Error, Result};
 use cratos_llm::{
-    CompletionRequest, LlmProvider, Message, ToolCall, ToolChoice, ToolCompletionRequest,
-    ToolCompletionResponse, ToolDefinition,
+    CompletionRequest, LlmProvider, Message, StreamChunk, TokenUsage, ToolCall, ToolChoice,
+    ToolCompletionRequest, ToolCompletionResponse, ToolDefinition,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{debug, instrument};
 
+/// Callback receiving text deltas while a plan step streams
+pub type DeltaCallback<'a> = &'a (dyn Fn(&str) + Send + Sync);
+
 /// Default system prompt for the planner
 pub const DEFAULT_SYSTEM_PROMPT: &str = include_str!("prompts/default_system_prompt.md");
 
@@ -222,6 +225,11 @@ pub struct PlanResponse {
     pub finish_reason: Option<String>,
     /// Model used
     pub model: String,
+    /// Provider that served the call
+    #[serde(default)]
+    pub provider: String,
+    /// Tokens consumed by the call (when the provider reports them)
+    pub usage: Option<TokenUsage>,
 }
 
 impl PlanResponse {
@@ -257,6 +265,12 @@ impl Planner {
         self.provider.as_ref()
     }
 
+    /// Get a shared handle to the underlying LLM provider
+    #[must_use]
+    pub fn provider_arc(&self) -> Arc<dyn LlmProvider> {
+        Arc::clone(&self.provider)
+    }
+
     /// Create with default configuration
     #[must_use]
     pub fn with_defaults(provider: Arc<dyn LlmProvider>) -> Self {
@@ -300,7 +314,7 @@ impl Planner {
     ) -> Result<PlanResponse> {
         let mut full_messages = vec![Message::system(&self.config.system_prompt)];
         full_messages.extend(messages.iter().cloned());
-        self.plan_step_impl(full_messages, tools, override_model).await
+        self.plan_step_impl(full_messages, tools, override_model, None).await
     }
 
     /// Plan a single step with a custom system prompt override
@@ -314,7 +328,49 @@ impl Planner {
     ) -> Result<PlanResponse> {
         let mut full_messages = vec![Message::system(system_prompt)];
         full_messages.extend(messages.iter().cloned());
-        self.plan_step_impl(full_messages, tools, override_model).await
+        self.plan_step_impl(full_messages, tools, override_model, None).await
+    }
+
+    /// Plan a single step, forwarding text deltas to `on_delta` as they arrive.
+    ///
+    /// Falls back to a regular request when the provider does not stream
+    /// natively; `on_delta` is then not called.
+    #[instrument(skip(self, messages, tools, system_prompt, on_delta))]
+    pub async fn plan_step_streaming(
+        &self,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        system_prompt: Option<&str>,
+        override_model: Option<&str>,
+        on_delta: DeltaCallback<'_>,
+    ) -> Result<PlanResponse> {
+        let system_prompt = system_prompt.unwrap_or(&self.config.system_prompt);
+        let mut full_messages = vec![Message::system(system_prompt)];
+        full_messages.extend(messages.iter().cloned());
+        self.plan_step_impl(full_messages, tools, override_model, Some(on_delta)).await
+    }
+
+    /// Record request, latency and token metrics for one LLM call
+    fn record_llm_metrics(
+        provider_name: &str,
+        model: &str,
+        usage: Option<&TokenUsage>,
+        llm_secs: f64,
+    ) {
+        crate::utils::metrics_global::labeled_counter("cratos_llm_requests_total")
+            .inc(&[("provider", provider_name), ("model", model)]);
+        crate::utils::metrics_global::labeled_histogram("cratos_llm_duration_seconds")
+            .observe(&[("provider", provider_name)], llm_secs);
+        if let Some(usage) = usage {
+            crate::utils::metrics_global::labeled_counter("cratos_llm_tokens_total").inc_by(
+                &[("provider", provider_name), ("direction", "input")],
+                u64::from(usage.prompt_tokens),
+            );
+            crate::utils::metrics_global::labeled_counter("cratos_llm_tokens_total").inc_by(
+                &[("provider", provider_name), ("direction", "output")],
+                u64::from(usage.completion_tokens),
+            );
+        }
     }
 
     /// Common planning implementation
@@ -323,12 +379,68 @@ impl Planner {
         full_messages: Vec<Message>,
         tools: &[ToolDefinition],
         override_model: Option<&str>,
+        on_delta: Option<DeltaCallback<'_>>,
     ) -> Result<PlanResponse> {
         let model = override_model
             .map(|s| s.to_string())
             .or_else(|| self.config.default_model.clone())
             .unwrap_or_else(|| self.provider.default_model().to_string());
 
+        if let Some(on_delta) = on_delta.filter(|_| self.provider.supports_streaming()) {
+            let tools = if self.config.include_tools {
+                tools.to_vec()
+            } else {
+                Vec::new()
+            };
+            let request = ToolCompletionRequest {
+                request: CompletionRequest {
+                    messages: full_messages,
+                    model: model.clone(),
+                    max_tokens: self.config.max_tokens,
+                    temperature: self.config.temperature,
+                    stop: None,
+                },
+                tools,
+                tool_choice: ToolChoice::Auto,
+            };
+
+            debug!(
+                tool_count = request.tools.len(),
+                "Making streaming completion request"
+            );
+
+            let llm_start = std::time::Instant::now();
+            let (stream, served_by) =
+                crate::failover::track_served(self.provider.complete_stream(request)).await;
+            let stream = stream.map_err(Error::Llm)?;
+            let provider = served_by.unwrap_or_else(|| self.provider.name().to_string());
+            let response = cratos_llm::collect_stream(stream, model, |chunk| {
+                if let StreamChunk::TextDelta(text) = chunk {
+                    on_delta(text);
+                }
+            })
+            .await
+            .map_err(Error::Llm)?;
+            Self::record_llm_metrics(
+                &provider,
+                &response.model,
+                response.usage.as_ref(),
+                llm_start.elapsed().as_secs_f64(),
+            );
+
+            let is_final = response.tool_calls.is_empty();
+
+            return Ok(PlanResponse {
+                content: response.content,
+                tool_calls: response.tool_calls,
+                is_final,
+                finish_reason: response.finish_reason,
+                model: response.model,
+                provider,
+                usage: response.usage,
+            });
+        }
+
         if tools.is_empty() || !self.config.include_tools {
             // Simple completion without tools
             let request = CompletionRequest {
@@ -342,25 +454,14 @@ impl Planner {
             debug!("Making completion request without tools");
 
             let llm_start = std::time::Instant::now();
-            let response = self.provider.complete(request).await.map_err(Error::Llm)?;
+            let (response, served_by) =
+                crate::failover::track_served(self.provider.complete(request)).await;
+            let response = response.map_err(Error::Llm)?;
             let llm_secs = llm_start.elapsed().as_secs_f64();
+            let provider = served_by.unwrap_or_else(|| self.provider.name().to_string());
 
             // Record LLM metrics
-            let provider_name = self.provider.name();
-            crate::utils::metrics_global::labeled_counter("cratos_llm_requests_total")
-                .inc(&[("provider", provider_name), ("model", &response.model)]);
-            crate::utils::metrics_global::labeled_histogram("cratos_llm_duration_seconds")
-                .observe(&[("provider", provider_name)], llm_secs);
-            if let Some(ref usage) = response.usage {
-                crate::utils::metrics_global::labeled_counter("cratos_llm_tokens_total").inc_by(
-                    &[("provider", provider_name), ("direction", "input")],
-                    u64::from(usage.prompt_tokens),
-                );
-                crate::utils::metrics_global::labeled_counter("cratos_llm_tokens_total").inc_by(
-                    &[("provider", provider_name), ("direction", "output")],
-                    u64::from(usage.completion_tokens),
-                );
-            }
+            Self::record_llm_metrics(&provider, &response.model, response.usage.as_ref(), llm_secs);
 
             Ok(PlanResponse {
                 content: Some(response.content),
@@ -368,6 +469,8 @@ impl Planner {
                 is_final: true,
                 finish_reason: response.finish_reason,
                 model: response.model,
+                provider,
+                usage: response.usage,
             })
         } else {
             // Completion with tools
@@ -389,29 +492,14 @@ impl Planner {
             );
 
             let llm_start = std::time::Instant::now();
-            let response = self
-                .provider
-                .complete_with_tools(request)
-                .await
-                .map_err(Error::Llm)?;
+            let (response, served_by) =
+                crate::failover::track_served(self.provider.complete_with_tools(request)).await;
+            let response = response.map_err(Error::Llm)?;
             let llm_secs = llm_start.elapsed().as_secs_f64();
+            let provider = served_by.unwrap_or_else(|| self.provider.name().to_string());
 
             // Record LLM metrics
-            let provider_name = self.provider.name();
-            crate::utils::metrics_global::labeled_counter("cratos_llm_requests_total")
-                .inc(&[("provider", provider_name), ("model", &response.model)]);
-            crate::utils::metrics_global::labeled_histogram("cratos_llm_duration_seconds")
-                .observe(&[("provider", provider_name)], llm_secs);
-            if let Some(ref usage) = response.usage {
-                crate::utils::metrics_global::labeled_counter("cratos_llm_tokens_total").inc_by(
-                    &[("provider", provider_name), ("direction", "input")],
-                    u64::from(usage.prompt_tokens),
-                );
-                crate::utils::metrics_global::labeled_counter("cratos_llm_tokens_total").inc_by(
-                    &[("provider", provider_name), ("direction", "output")],
-                    u64::from(usage.completion_tokens),
-                );
-            }
+            Self::record_llm_metrics(&provider, &response.model, response.usage.as_ref(), llm_secs);
 
             let is_final = response.tool_calls.is_empty();
 
@@ -421,6 +509,8 @@ impl Planner {
                 is_final,
                 finish_reason: response.finish_reason,
                 model: response.model,
+                provider,
+                usage: response.usage,
             })
         }
     }
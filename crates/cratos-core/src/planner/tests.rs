@@ -21,6 +21,8 @@
             is_final: true,
             finish_reason: Some("stop".to_string()),
             model: "test".to_string(),
+            provider: "mock".to_string(),
+            usage: None,
         };
 
         assert!(response.is_text_only());
@@ -40,3 +42,71 @@
         let messages = Planner::build_tool_result_messages(&calls, &results);
         assert_eq!(messages.len(), 1);
     }
+
+    struct StreamingProvider;
+
+    #[async_trait::async_trait]
+    impl LlmProvider for StreamingProvider {
+        fn name(&self) -> &str {
+            "streaming"
+        }
+
+        fn supports_tools(&self) -> bool {
+            true
+        }
+
+        fn available_models(&self) -> Vec<String> {
+            vec!["stream-model".to_string()]
+        }
+
+        fn default_model(&self) -> &str {
+            "stream-model"
+        }
+
+        async fn complete(
+            &self,
+            _request: CompletionRequest,
+        ) -> cratos_llm::Result<cratos_llm::CompletionResponse> {
+            unreachable!("streaming path should be used")
+        }
+
+        async fn complete_with_tools(
+            &self,
+            _request: ToolCompletionRequest,
+        ) -> cratos_llm::Result<ToolCompletionResponse> {
+            unreachable!("streaming path should be used")
+        }
+
+        fn supports_streaming(&self) -> bool {
+            true
+        }
+
+        async fn complete_stream(
+            &self,
+            _request: ToolCompletionRequest,
+        ) -> cratos_llm::Result<cratos_llm::CompletionStream> {
+            use futures::StreamExt;
+            let chunks = vec![
+                Ok(StreamChunk::TextDelta("Hel".to_string())),
+                Ok(StreamChunk::TextDelta("lo".to_string())),
+            ];
+            Ok(futures::stream::iter(chunks).boxed())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plan_step_streaming_forwards_deltas() {
+        let planner = Planner::with_defaults(Arc::new(StreamingProvider));
+        let deltas = std::sync::Mutex::new(Vec::new());
+        let on_delta = |d: &str| deltas.lock().unwrap().push(d.to_string());
+
+        let response = planner
+            .plan_step_streaming(&[Message::user("hi")], &[], None, None, &on_delta)
+            .await
+            .unwrap();
+
+        assert_eq!(*deltas.lock().unwrap(), vec!["Hel", "lo"]);
+        assert_eq!(response.content.as_deref(), Some("Hello"));
+        assert_eq!(response.model, "stream-model");
+        assert!(response.is_final);
+    }
@@ -0,0 +1,293 @@
+//! Task chains
+//!
+//! A [`TaskAction::Chain`] runs its steps in order. Each step may carry a
+//! condition on the output of the previous step, so a task can check
+//! something and notify only when the check finds a problem:
+//!
+//! ```json
+//! {"type": "chain", "steps": [
+//!   {"action": {"type": "tool_call", "tool": "http_get", "args": {"url": "https://status.example.com/api/v2/status.json"}}},
+//!   {"action": {"type": "notification", "channel": "telegram", "channel_id": "42",
+//!               "message": "Status page reports: {{output}}"},
+//!    "when": {"type": "json_path", "path": "status.indicator", "op": "not_equals", "value": "none"}}
+//! ]}
+//! ```
+//!
+//! Steps see the latest output as `{{output}}` and the output of step N
+//! (1-based) as `{{output_N}}`, next to the trigger's event variables.
+//! A step whose condition does not hold is skipped; a failing step stops the
+//! chain.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::types::{Result, SchedulerError, TaskAction};
+
+/// Maximum number of steps in a chain
+pub const MAX_CHAIN_STEPS: usize = 20;
+
+/// Output passed to the yes/no classifier is cut to this many characters
+const MAX_CLASSIFY_CHARS: usize = 8_000;
+
+/// One step of a task chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainStep {
+    /// Action to run (any action except another chain)
+    pub action: TaskAction,
+    /// Run the step only if this holds for the previous step's output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<StepCondition>,
+    /// Run the step only if the condition does *not* hold
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub negate: bool,
+}
+
+impl ChainStep {
+    /// Create an unconditional step
+    pub fn new(action: TaskAction) -> Self {
+        Self {
+            action,
+            when: None,
+            negate: false,
+        }
+    }
+
+    /// Run the step only if `condition` holds
+    pub fn when(mut self, condition: StepCondition) -> Self {
+        self.when = Some(condition);
+        self.negate = false;
+        self
+    }
+
+    /// Run the step only if `condition` does not hold
+    pub fn unless(mut self, condition: StepCondition) -> Self {
+        self.when = Some(condition);
+        self.negate = true;
+        self
+    }
+}
+
+/// Predicate on the output of the previous step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StepCondition {
+    /// The output contains a match of the regular expression
+    Regex {
+        /// Regular expression
+        pattern: String,
+    },
+    /// A value in the JSON output compares to `value`
+    JsonPath {
+        /// Dotted path, e.g. `status.indicator` or `$.components[0].status`
+        path: String,
+        /// Comparison
+        #[serde(default)]
+        op: JsonOp,
+        /// Value to compare with (unused by `exists`)
+        #[serde(default)]
+        value: Value,
+    },
+    /// An LLM answers the yes/no question about the output with yes
+    Classify {
+        /// Question about the output, e.g. "Does this report an outage?"
+        question: String,
+    },
+}
+
+/// Comparison of a [`StepCondition::JsonPath`] condition
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonOp {
+    /// Equal (numbers compare numerically)
+    #[default]
+    Equals,
+    /// Not equal
+    NotEquals,
+    /// Greater than (numbers, or numeric strings)
+    GreaterThan,
+    /// Less than (numbers, or numeric strings)
+    LessThan,
+    /// String contains the value, or array contains the element
+    Contains,
+    /// The path exists and is not null
+    Exists,
+}
+
+impl StepCondition {
+    /// Check that the condition can be evaluated
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            StepCondition::Regex { pattern } => Regex::new(pattern)
+                .map(|_| ())
+                .map_err(|e| invalid(format!("invalid regex '{pattern}': {e}"))),
+            StepCondition::JsonPath { path, .. } if path.trim().is_empty() => {
+                Err(invalid("empty JSON path".to_string()))
+            }
+            StepCondition::Classify { question } if question.trim().is_empty() => {
+                Err(invalid("empty classification question".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Evaluate a regex or JSON path condition
+    ///
+    /// [`StepCondition::Classify`] needs an LLM and is evaluated by the engine.
+    pub(super) fn evaluate(&self, output: &str) -> Result<bool> {
+        match self {
+            StepCondition::Regex { pattern } => Regex::new(pattern)
+                .map(|re| re.is_match(output))
+                .map_err(|e| invalid(format!("invalid regex '{pattern}': {e}"))),
+            StepCondition::JsonPath { path, op, value } => {
+                let json = parse_json_output(output)?;
+                Ok(compare(lookup(&json, path), *op, value))
+            }
+            StepCondition::Classify { .. } => Err(invalid(
+                "classification is evaluated by the scheduler engine".to_string(),
+            )),
+        }
+    }
+}
+
+fn invalid(message: String) -> SchedulerError {
+    SchedulerError::InvalidConfig(format!("chain condition: {message}"))
+}
+
+/// Check the steps of a chain
+pub(super) fn validate_steps(steps: &[ChainStep]) -> Result<()> {
+    if steps.is_empty() {
+        return Err(SchedulerError::InvalidConfig(
+            "chain has no steps".to_string(),
+        ));
+    }
+    if steps.len() > MAX_CHAIN_STEPS {
+        return Err(SchedulerError::InvalidConfig(format!(
+            "chain has {} steps, at most {MAX_CHAIN_STEPS} allowed",
+            steps.len()
+        )));
+    }
+    for step in steps {
+        if matches!(step.action, TaskAction::Chain { .. }) {
+            return Err(SchedulerError::InvalidConfig(
+                "chains cannot be nested".to_string(),
+            ));
+        }
+        step.action.validate()?;
+        if let Some(condition) = &step.when {
+            condition.validate()?;
+        }
+    }
+    Ok(())
+}
+
+/// Parse step output as JSON, also accepting JSON embedded in prose or a code fence
+fn parse_json_output(output: &str) -> Result<Value> {
+    let trimmed = output.trim();
+    if let Ok(json) = serde_json::from_str(trimmed) {
+        return Ok(json);
+    }
+    let start = trimmed.find(['{', '[']);
+    let end = trimmed.rfind(['}', ']']);
+    if let (Some(start), Some(end)) = (start, end) {
+        if start < end {
+            if let Ok(json) = serde_json::from_str(&trimmed[start..=end]) {
+                return Ok(json);
+            }
+        }
+    }
+    Err(SchedulerError::Execution(
+        "step output is not JSON".to_string(),
+    ))
+}
+
+/// Look up a dotted path (`a.b[0].c`, optional leading `$.`)
+fn lookup<'a>(json: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim().trim_start_matches('$').trim_start_matches('.');
+    let mut current = json;
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (key, indexes) = segment.split_at(segment.find('[').unwrap_or(segment.len()));
+        if !key.is_empty() {
+            current = match current {
+                Value::Object(map) => map.get(key)?,
+                Value::Array(items) => items.get(key.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        for index in indexes.split('[').skip(1) {
+            let index = index.strip_suffix(']')?.trim().parse::<usize>().ok()?;
+            current = current.get(index)?;
+        }
+    }
+    Some(current)
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn equals(found: &Value, expected: &Value) -> bool {
+    if found == expected {
+        return true;
+    }
+    match (found, expected) {
+        (Value::Number(_), _) | (_, Value::Number(_)) => {
+            matches!((as_number(found), as_number(expected)), (Some(a), Some(b)) if a == b)
+        }
+        _ => false,
+    }
+}
+
+/// Compare the value found at the path; a missing path only satisfies `not_equals`
+fn compare(found: Option<&Value>, op: JsonOp, expected: &Value) -> bool {
+    let Some(found) = found else {
+        return op == JsonOp::NotEquals;
+    };
+    match op {
+        JsonOp::Equals => equals(found, expected),
+        JsonOp::NotEquals => !equals(found, expected),
+        JsonOp::GreaterThan => {
+            matches!((as_number(found), as_number(expected)), (Some(a), Some(b)) if a > b)
+        }
+        JsonOp::LessThan => {
+            matches!((as_number(found), as_number(expected)), (Some(a), Some(b)) if a < b)
+        }
+        JsonOp::Contains => match (found, expected) {
+            (Value::String(s), Value::String(needle)) => s.contains(needle.as_str()),
+            (Value::Array(items), _) => items.iter().any(|item| equals(item, expected)),
+            _ => false,
+        },
+        JsonOp::Exists => !found.is_null(),
+    }
+}
+
+/// Prompt asking an LLM the yes/no question of a classify condition
+pub(super) fn classify_prompt(question: &str, output: &str) -> String {
+    let output: String = output.chars().take(MAX_CLASSIFY_CHARS).collect();
+    format!("Answer with only \"yes\" or \"no\".\n\nQuestion: {question}\n\nText:\n{output}")
+}
+
+/// Read the yes/no answer of the classifier
+pub(super) fn parse_yes_no(answer: &str) -> Result<bool> {
+    let word: String = answer
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_alphabetic())
+        .collect::<String>()
+        .to_lowercase();
+    match word.as_str() {
+        "yes" | "true" => Ok(true),
+        "no" | "false" => Ok(false),
+        _ => Err(SchedulerError::Execution(format!(
+            "classifier answered '{}' instead of yes or no",
+            answer.trim().chars().take(80).collect::<String>()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests;
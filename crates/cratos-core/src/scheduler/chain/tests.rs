@@ -0,0 +1,129 @@
+use super::*;
+use serde_json::json;
+
+fn json_path(path: &str, op: JsonOp, value: Value) -> StepCondition {
+    StepCondition::JsonPath {
+        path: path.to_string(),
+        op,
+        value,
+    }
+}
+
+#[test]
+fn test_regex_condition() {
+    let condition = StepCondition::Regex {
+        pattern: r"(?i)\b(down|outage)\b".to_string(),
+    };
+    assert!(condition.evaluate("API: Major Outage").unwrap());
+    assert!(!condition.evaluate("All systems operational").unwrap());
+
+    let invalid = StepCondition::Regex {
+        pattern: "(".to_string(),
+    };
+    assert!(invalid.validate().is_err());
+    assert!(invalid.evaluate("anything").is_err());
+}
+
+#[test]
+fn test_json_path_lookup() {
+    let json =
+        json!({"status": {"indicator": "minor"}, "components": [{"name": "api", "up": false}]});
+
+    assert_eq!(lookup(&json, "status.indicator"), Some(&json!("minor")));
+    assert_eq!(lookup(&json, "$.components[0].name"), Some(&json!("api")));
+    assert_eq!(lookup(&json, "components.0.up"), Some(&json!(false)));
+    assert_eq!(lookup(&json, "components[1]"), None);
+    assert_eq!(lookup(&json, "status.missing"), None);
+}
+
+#[test]
+fn test_json_path_operators() {
+    let output = r#"{"status": "down", "latency_ms": 950, "regions": ["eu", "us"], "load": "0.5"}"#;
+    let holds =
+        |path: &str, op: JsonOp, value: Value| json_path(path, op, value).evaluate(output).unwrap();
+
+    assert!(holds("status", JsonOp::Equals, json!("down")));
+    assert!(holds("status", JsonOp::NotEquals, json!("up")));
+    assert!(holds("latency_ms", JsonOp::GreaterThan, json!(500)));
+    assert!(!holds("latency_ms", JsonOp::LessThan, json!(500)));
+    assert!(holds("latency_ms", JsonOp::Equals, json!("950")));
+    assert!(holds("load", JsonOp::LessThan, json!(1)));
+    assert!(holds("regions", JsonOp::Contains, json!("us")));
+    assert!(holds("status", JsonOp::Contains, json!("ow")));
+    assert!(holds("status", JsonOp::Exists, Value::Null));
+
+    // A missing value only satisfies not_equals
+    assert!(!holds("error", JsonOp::Exists, Value::Null));
+    assert!(!holds("error", JsonOp::Equals, Value::Null));
+    assert!(holds("error", JsonOp::NotEquals, json!("x")));
+}
+
+#[test]
+fn test_json_embedded_in_text() {
+    let output = "Here is the result:\n```json\n{\"ok\": false}\n```";
+    assert!(json_path("ok", JsonOp::Equals, json!(false))
+        .evaluate(output)
+        .unwrap());
+    assert!(json_path("ok", JsonOp::Equals, json!(false))
+        .evaluate("no json here")
+        .is_err());
+}
+
+#[test]
+fn test_parse_yes_no() {
+    assert!(parse_yes_no("Yes").unwrap());
+    assert!(parse_yes_no("  yes, the API is down").unwrap());
+    assert!(parse_yes_no("TRUE").unwrap());
+    assert!(!parse_yes_no("No.").unwrap());
+    assert!(parse_yes_no("Maybe").is_err());
+    assert!(parse_yes_no("").is_err());
+
+    let prompt = classify_prompt("Is it down?", &"x".repeat(MAX_CLASSIFY_CHARS + 100));
+    assert!(prompt.contains("Question: Is it down?"));
+    assert!(prompt.len() < MAX_CLASSIFY_CHARS + 200);
+}
+
+#[test]
+fn test_validate_steps() {
+    let notify = ChainStep::new(TaskAction::notification("telegram", "42", "hi"));
+
+    assert!(validate_steps(&[]).is_err());
+    assert!(validate_steps(std::slice::from_ref(&notify)).is_ok());
+    assert!(validate_steps(&vec![notify.clone(); MAX_CHAIN_STEPS + 1]).is_err());
+
+    let nested = ChainStep::new(TaskAction::chain(vec![notify.clone()]));
+    assert!(validate_steps(&[notify.clone(), nested]).is_err());
+
+    let empty_question = notify.clone().when(StepCondition::Classify {
+        question: " ".to_string(),
+    });
+    assert!(validate_steps(&[empty_question]).is_err());
+}
+
+#[test]
+fn test_chain_serde() {
+    let config = json!({"type": "chain", "steps": [
+        {"action": {"type": "tool_call", "tool": "http_get", "args": {"url": "https://example.com"}}},
+        {"action": {"type": "notification", "channel": "telegram", "channel_id": "42", "message": "{{output}}"},
+         "when": {"type": "json_path", "path": "status", "value": "down"}},
+        {"action": {"type": "notification", "channel": "telegram", "channel_id": "42", "message": "ok"},
+         "when": {"type": "classify", "question": "Is it down?"}, "negate": true}
+    ]});
+
+    let action: TaskAction = serde_json::from_value(config).unwrap();
+    let TaskAction::Chain { steps } = &action else {
+        panic!("Unexpected action: {:?}", action);
+    };
+    assert_eq!(steps.len(), 3);
+    assert_eq!(steps[0].when, None);
+    assert_eq!(
+        steps[1].when,
+        Some(json_path("status", JsonOp::Equals, json!("down")))
+    );
+    assert!(steps[2].negate);
+    assert!(action.validate().is_ok());
+
+    let roundtrip: TaskAction =
+        serde_json::from_str(&serde_json::to_string(&action).unwrap()).unwrap();
+    assert_eq!(roundtrip, action);
+}
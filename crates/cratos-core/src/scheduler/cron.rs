@@ -0,0 +1,94 @@
+//! Cron expression evaluation
+//!
+//! Supports standard 5-field expressions (`minute hour day month weekday`)
+//! and 6-field expressions with a leading seconds field. Fields accept
+//! ranges (`1-5`), steps (`*/15`), lists (`1,15`) and names (`MON-FRI`,
+//! `JAN`), plus the extensions:
+//! - `L`: last day of the month (`L` in day), last weekday of the month (`5L`)
+//! - `W`: weekday nearest to a day of the month (`15W`)
+//! - `#`: nth weekday of the month (`1#2` = second Monday)
+//!
+//! Fire times are evaluated in the trigger's IANA timezone, so
+//! "0 9 * * MON-FRI" in `Asia/Seoul` fires at 09:00 Seoul time. Across DST
+//! changes, a time skipped by spring-forward fires right after the gap and a
+//! time repeated by fall-back fires once.
+
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use croner::Cron;
+
+use super::types::{Result, SchedulerError};
+
+/// Searches restarted after landing on the first instance of a repeated local time
+const MAX_FOLD_RETRIES: usize = 3;
+
+/// Parsed cron expression bound to a timezone
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    cron: Cron,
+    timezone: Tz,
+}
+
+impl CronSchedule {
+    /// Parse `expression`, evaluated in `timezone` (IANA name, default UTC)
+    pub fn parse(expression: &str, timezone: Option<&str>) -> Result<Self> {
+        let fields = expression.split_whitespace().count();
+        if !(5..=6).contains(&fields) {
+            return Err(SchedulerError::TriggerParse(format!(
+                "cron expression '{expression}' must have 5 or 6 fields, found {fields}"
+            )));
+        }
+
+        let cron = Cron::new(expression)
+            .with_seconds_optional()
+            .parse()
+            .map_err(|e| {
+                SchedulerError::TriggerParse(format!("invalid cron expression '{expression}': {e}"))
+            })?;
+
+        let timezone = match timezone {
+            Some(name) => name
+                .parse::<Tz>()
+                .map_err(|_| SchedulerError::TriggerParse(format!("unknown timezone '{name}'")))?,
+            None => Tz::UTC,
+        };
+
+        Ok(Self { cron, timezone })
+    }
+
+    /// Timezone the expression is evaluated in
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// Next fire time strictly after `from`
+    ///
+    /// Returns `None` if the expression never matches again.
+    pub fn next_after(&self, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut start = from.with_timezone(&self.timezone);
+        for _ in 0..MAX_FOLD_RETRIES {
+            let next = self
+                .cron
+                .find_next_occurrence(&start, false)
+                .ok()?
+                .with_timezone(&Utc);
+            if next > from {
+                return Some(next);
+            }
+            // A repeated local time resolves to its first instance, which lies
+            // before `from` during the second pass: search past the fold.
+            start += Duration::hours(1);
+        }
+        None
+    }
+
+    /// The next `count` fire times after `from`
+    pub fn upcoming(&self, from: DateTime<Utc>, count: usize) -> Vec<DateTime<Utc>> {
+        std::iter::successors(self.next_after(from), |prev| self.next_after(*prev))
+            .take(count)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests;
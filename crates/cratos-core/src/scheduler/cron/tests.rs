@@ -0,0 +1,126 @@
+use super::*;
+use chrono::TimeZone;
+
+fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+}
+
+#[test]
+fn test_every_fifteen_minutes() {
+    let schedule = CronSchedule::parse("*/15 * * * *", None).unwrap();
+    let runs = schedule.upcoming(utc(2026, 3, 2, 10, 7), 3);
+    assert_eq!(
+        runs,
+        vec![
+            utc(2026, 3, 2, 10, 15),
+            utc(2026, 3, 2, 10, 30),
+            utc(2026, 3, 2, 10, 45)
+        ]
+    );
+}
+
+#[test]
+fn test_next_is_strictly_after() {
+    let schedule = CronSchedule::parse("0 9 * * *", None).unwrap();
+    assert_eq!(
+        schedule.next_after(utc(2026, 3, 2, 9, 0)),
+        Some(utc(2026, 3, 3, 9, 0))
+    );
+}
+
+#[test]
+fn test_weekdays_in_timezone() {
+    // 09:00 in Seoul (UTC+9) is 00:00 UTC; 2026-03-06 is a Friday
+    let schedule = CronSchedule::parse("0 9 * * MON-FRI", Some("Asia/Seoul")).unwrap();
+    let runs = schedule.upcoming(utc(2026, 3, 5, 12, 0), 2);
+    assert_eq!(runs, vec![utc(2026, 3, 6, 0, 0), utc(2026, 3, 9, 0, 0)]);
+}
+
+#[test]
+fn test_ranges_and_lists() {
+    let schedule = CronSchedule::parse("30 8-10 1,15 * *", None).unwrap();
+    let runs = schedule.upcoming(utc(2026, 3, 1, 9, 0), 4);
+    assert_eq!(
+        runs,
+        vec![
+            utc(2026, 3, 1, 9, 30),
+            utc(2026, 3, 1, 10, 30),
+            utc(2026, 3, 15, 8, 30),
+            utc(2026, 3, 15, 9, 30)
+        ]
+    );
+}
+
+#[test]
+fn test_seconds_field() {
+    let schedule = CronSchedule::parse("30 0 12 * * *", None).unwrap();
+    assert_eq!(
+        schedule.next_after(utc(2026, 3, 2, 11, 0)),
+        Some(Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 30).unwrap())
+    );
+}
+
+#[test]
+fn test_last_day_and_nth_weekday() {
+    let last_day = CronSchedule::parse("0 0 L * *", None).unwrap();
+    assert_eq!(
+        last_day.next_after(utc(2026, 2, 10, 0, 0)),
+        Some(utc(2026, 2, 28, 0, 0))
+    );
+
+    // Second Monday of March 2026
+    let second_monday = CronSchedule::parse("0 0 * * 1#2", None).unwrap();
+    assert_eq!(
+        second_monday.next_after(utc(2026, 3, 1, 0, 0)),
+        Some(utc(2026, 3, 9, 0, 0))
+    );
+
+    // Last Friday of March 2026
+    let last_friday = CronSchedule::parse("0 0 * * 5L", None).unwrap();
+    assert_eq!(
+        last_friday.next_after(utc(2026, 3, 1, 0, 0)),
+        Some(utc(2026, 3, 27, 0, 0))
+    );
+}
+
+#[test]
+fn test_nearest_weekday() {
+    // 2026-03-15 is a Sunday: the nearest weekday is Monday the 16th
+    let schedule = CronSchedule::parse("0 0 15W * *", None).unwrap();
+    assert_eq!(
+        schedule.next_after(utc(2026, 3, 1, 0, 0)),
+        Some(utc(2026, 3, 16, 0, 0))
+    );
+}
+
+#[test]
+fn test_dst_keeps_local_time() {
+    // New York switches to EDT on 2026-03-08: 09:00 local moves from 14:00 to 13:00 UTC
+    let schedule = CronSchedule::parse("0 9 * * *", Some("America/New_York")).unwrap();
+    let runs = schedule.upcoming(utc(2026, 3, 7, 0, 0), 2);
+    assert_eq!(runs, vec![utc(2026, 3, 7, 14, 0), utc(2026, 3, 8, 13, 0)]);
+}
+
+#[test]
+fn test_dst_gap_and_fold() {
+    let schedule = CronSchedule::parse("30 2 * * *", Some("America/New_York")).unwrap();
+    // 02:30 does not exist on 2026-03-08: fires at 03:00 EDT instead
+    assert_eq!(
+        schedule.next_after(utc(2026, 3, 8, 0, 0)),
+        Some(utc(2026, 3, 8, 7, 0))
+    );
+
+    let schedule = CronSchedule::parse("30 1 * * *", Some("America/New_York")).unwrap();
+    // 01:30 happens twice on 2026-11-01 (EDT, then EST): fires once
+    let runs = schedule.upcoming(utc(2026, 11, 1, 0, 0), 2);
+    assert_eq!(runs, vec![utc(2026, 11, 1, 5, 30), utc(2026, 11, 2, 6, 30)]);
+}
+
+#[test]
+fn test_invalid_expressions() {
+    assert!(CronSchedule::parse("* * * *", None).is_err());
+    assert!(CronSchedule::parse("0 0 0 * * * 2026", None).is_err());
+    assert!(CronSchedule::parse("61 * * * *", None).is_err());
+    assert!(CronSchedule::parse("0 25 * * *", None).is_err());
+    assert!(CronSchedule::parse("0 9 * * *", Some("Mars/Olympus")).is_err());
+}
@@ -3,19 +3,32 @@
 //! Manages the execution of scheduled tasks with:
 //! - Cron scheduling
 //! - Interval-based execution
+//! - File-watch and system-metric triggers
+//! - Missed-run catch-up, overlap policies and jitter
 //! - Graceful shutdown support
 //! - Retry logic
-
-use chrono::{DateTime, Duration, Utc};
-use std::collections::HashMap;
+//!
+//! Tasks run in the background: the loop keeps checking triggers while a
+//! long task is running, and the task's overlap policy decides what happens
+//! when it fires again before finishing.
+
+use chrono::{DateTime, Utc};
+use cratos_llm::{CompletionRequest, LlmProvider, Message};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, RwLock};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
+use uuid::Uuid;
 
+use super::chain::{classify_prompt, parse_yes_no, ChainStep, StepCondition};
+use super::file_watch::FileWatchers;
+use super::policy::{apply_jitter, plan_catch_up, OverlapPolicy};
 use super::store::SchedulerStore;
-use super::triggers::{IntervalTrigger, TriggerType};
-use super::types::{Result, ScheduledTask, SchedulerError, TaskAction};
+use super::system_monitor::SystemMonitors;
+use super::triggers::{TriggerEvent, TriggerType};
+use super::types::{Result, ScheduledTask, SchedulerError, TaskAction, TaskExecution};
 
 /// Callback type for executing task actions
 pub type TaskExecutor = Arc<dyn Fn(TaskAction) -> TaskExecutionFuture + Send + Sync>;
@@ -35,6 +48,14 @@ pub struct SchedulerConfig {
     pub max_concurrent: usize,
     /// Enable execution logging
     pub logging_enabled: bool,
+    /// System metric sampling interval in seconds (for system triggers)
+    pub sample_interval_secs: u64,
+    /// How late a run may start before it counts as missed (seconds, at
+    /// least the check interval); missed runs follow the task's misfire policy
+    pub misfire_grace_secs: u64,
+    /// Most runs that may wait behind a running task (overlap policy
+    /// `queue`); further runs are recorded as skipped
+    pub max_queued_runs: usize,
 }
 
 impl Default for SchedulerConfig {
@@ -44,6 +65,9 @@ impl Default for SchedulerConfig {
             retry_delay_secs: 30,
             max_concurrent: 10,
             logging_enabled: true,
+            sample_interval_secs: 10,
+            misfire_grace_secs: 120,
+            max_queued_runs: 10,
         }
     }
 }
@@ -71,21 +95,77 @@ impl SchedulerConfig {
         self.max_concurrent = max;
         self
     }
+
+    /// Set system metric sampling interval
+    pub fn with_sample_interval(mut self, secs: u64) -> Self {
+        self.sample_interval_secs = secs;
+        self
+    }
+
+    /// Set misfire grace period
+    pub fn with_misfire_grace(mut self, secs: u64) -> Self {
+        self.misfire_grace_secs = secs;
+        self
+    }
+
+    /// Set the most runs that may wait behind a running task
+    pub fn with_max_queued_runs(mut self, max: usize) -> Self {
+        self.max_queued_runs = max;
+        self
+    }
+
+    fn misfire_grace(&self) -> chrono::Duration {
+        let secs = self.misfire_grace_secs.max(self.check_interval_secs);
+        chrono::Duration::seconds(i64::try_from(secs).unwrap_or(i64::MAX / 1000))
+    }
+}
+
+/// Outcome of starting a task run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunDispatch {
+    /// The run started
+    Started,
+    /// Queued behind the run in progress (overlap policy `queue`)
+    Queued,
+    /// Dropped because the task is still running (overlap policy `skip`)
+    Skipped,
+    /// Started after cancelling the run in progress (overlap policy `cancel_previous`)
+    Replaced,
 }
 
 /// Internal state for running tasks
 #[allow(dead_code)]
 struct RunningTask {
-    task_id: uuid::Uuid,
+    task_id: Uuid,
     started_at: DateTime<Utc>,
+    /// Identifies the worker running the task, so a cancelled worker
+    /// does not touch the state of the run that replaced it
+    worker: Uuid,
+    cancel: CancellationToken,
+    /// Template variables of the runs waiting for this one to finish
+    queued: VecDeque<HashMap<String, String>>,
 }
 
+type RunningTasks = Arc<RwLock<HashMap<Uuid, RunningTask>>>;
+
 /// Scheduler engine for executing scheduled tasks
 pub struct SchedulerEngine {
     store: Arc<SchedulerStore>,
     config: SchedulerConfig,
-    running_tasks: Arc<RwLock<HashMap<uuid::Uuid, RunningTask>>>,
+    running_tasks: RunningTasks,
     executor: Option<TaskExecutor>,
+    classifier: Option<Arc<dyn LlmProvider>>,
+}
+
+/// What a background worker needs to run tasks
+#[derive(Clone)]
+struct TaskRunner {
+    store: Arc<SchedulerStore>,
+    running_tasks: RunningTasks,
+    executor: Option<TaskExecutor>,
+    classifier: Option<Arc<dyn LlmProvider>>,
+    logging_enabled: bool,
 }
 
 impl SchedulerEngine {
@@ -96,6 +176,7 @@ impl SchedulerEngine {
             config,
             running_tasks: Arc::new(RwLock::new(HashMap::new())),
             executor: None,
+            classifier: None,
         }
     }
 
@@ -105,6 +186,15 @@ impl SchedulerEngine {
         self
     }
 
+    /// Set the LLM that answers [`StepCondition::Classify`] questions
+    ///
+    /// It is called without tools, so a chain's output cannot make the
+    /// classifier act on its behalf.
+    pub fn with_classifier(mut self, provider: Arc<dyn LlmProvider>) -> Self {
+        self.classifier = Some(provider);
+        self
+    }
+
     /// Start the scheduler loop
     pub async fn run(&self, shutdown: CancellationToken) -> Result<()> {
         info!("Scheduler engine starting");
@@ -112,14 +202,38 @@ impl SchedulerEngine {
         // Load and schedule all enabled tasks
         self.initialize_tasks().await?;
 
-        let check_interval = tokio::time::Duration::from_secs(self.config.check_interval_secs);
+        // Event-driven triggers
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let mut file_watchers = FileWatchers::new(event_tx);
+        let mut system_monitors = SystemMonitors::new();
+        self.sync_event_triggers(&mut file_watchers, &mut system_monitors)
+            .await;
+
+        let check_period = tokio::time::Duration::from_secs(self.config.check_interval_secs);
+        let mut check_interval =
+            tokio::time::interval_at(tokio::time::Instant::now() + check_period, check_period);
+        let sample_period =
+            tokio::time::Duration::from_secs(self.config.sample_interval_secs.max(1));
+        let mut sample_interval =
+            tokio::time::interval_at(tokio::time::Instant::now() + sample_period, sample_period);
+        sample_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
         loop {
             tokio::select! {
-                _ = tokio::time::sleep(check_interval) => {
+                _ = check_interval.tick() => {
                     if let Err(e) = self.check_and_execute().await {
                         error!("Scheduler check failed: {}", e);
                     }
+                    self.sync_event_triggers(&mut file_watchers, &mut system_monitors)
+                        .await;
+                }
+                _ = sample_interval.tick() => {
+                    for event in system_monitors.poll() {
+                        self.fire_event(event).await;
+                    }
+                }
+                Some(event) = event_rx.recv() => {
+                    self.fire_event(event).await;
                 }
                 _ = shutdown.cancelled() => {
                     info!("Scheduler engine shutting down");
@@ -143,7 +257,7 @@ impl SchedulerEngine {
         for mut task in tasks {
             // Calculate next run time if not set
             if task.next_run_at.is_none() {
-                task.next_run_at = self.calculate_next_run(&task.trigger, now);
+                self.schedule_next(&mut task, now);
                 self.store.update_task(&task).await?;
             }
         }
@@ -155,9 +269,52 @@ impl SchedulerEngine {
         Ok(())
     }
 
+    /// Watch the file and system triggers of the enabled tasks
+    async fn sync_event_triggers(
+        &self,
+        file_watchers: &mut FileWatchers,
+        system_monitors: &mut SystemMonitors,
+    ) {
+        match self.store.list_enabled_tasks().await {
+            Ok(tasks) => {
+                file_watchers.sync(&tasks);
+                system_monitors.sync(&tasks);
+            }
+            Err(e) => warn!("Failed to load tasks for event triggers: {}", e),
+        }
+    }
+
+    /// Run the task of a fired file or system trigger
+    async fn fire_event(&self, event: TriggerEvent) {
+        let task = match self.store.get_task(event.task_id).await {
+            Ok(task) if task.enabled => task,
+            Ok(_) => return,
+            Err(e) => {
+                warn!("Triggered task {} not found: {}", event.task_id, e);
+                return;
+            }
+        };
+
+        let running_count = self.running_tasks.read().await.len();
+        if running_count >= self.config.max_concurrent {
+            warn!(
+                "Max concurrent tasks reached ({}/{}), dropping trigger of {}",
+                running_count, self.config.max_concurrent, task.name
+            );
+            return;
+        }
+
+        debug!(task = %task.name, variables = ?event.variables, "Event trigger fired");
+        self.dispatch(task, vec![event.variables]).await;
+    }
+
     /// Check for due tasks and execute them
     async fn check_and_execute(&self) -> Result<()> {
-        let now = Utc::now();
+        self.execute_due(Utc::now()).await
+    }
+
+    /// Execute the tasks due at `now`
+    async fn execute_due(&self, now: DateTime<Utc>) -> Result<()> {
         let due_tasks = self.store.get_due_tasks(now).await?;
 
         if due_tasks.is_empty() {
@@ -182,122 +339,148 @@ impl SchedulerEngine {
 
         debug!("Executing {} due tasks", tasks_to_run.len());
 
+        let grace = self.config.misfire_grace();
         for task in tasks_to_run {
-            self.execute_task(task).await;
+            let Some(scheduled) = task.next_run_at else {
+                continue;
+            };
+            // Plan from the nominal schedule, shifted by this run's jitter,
+            // so jitter neither accumulates nor pushes runs into misfires
+            let offset = task
+                .fire_at
+                .map_or(chrono::Duration::zero(), |at| at - scheduled);
+            let plan = plan_catch_up(
+                &task.trigger,
+                task.misfire_policy,
+                scheduled,
+                now - offset,
+                grace,
+            );
+            let fire_at = plan
+                .next_run_at
+                .map(|at| apply_jitter(at, task.jitter_secs));
+            // Advance the schedule before running, so the next check does not
+            // pick up the same run while it is still in progress
+            self.store
+                .set_next_run(task.id, plan.next_run_at, fire_at)
+                .await?;
+
+            if plan.missed > 0 {
+                warn!(
+                    "Task {} missed {} scheduled runs, running {} (misfire policy: {:?})",
+                    task.name, plan.missed, plan.runs, task.misfire_policy
+                );
+            }
+            if plan.runs > 0 {
+                let runs = vec![HashMap::new(); plan.runs as usize];
+                self.dispatch(task, runs).await;
+            }
         }
 
         Ok(())
     }
 
-    /// Execute a single task
-    async fn execute_task(&self, mut task: ScheduledTask) {
-        let task_id = task.id;
-        let task_name = task.name.clone();
-
-        // Mark as running
-        {
-            let mut running = self.running_tasks.write().await;
-            running.insert(
-                task_id,
-                RunningTask {
-                    task_id,
-                    started_at: Utc::now(),
-                },
-            );
-        }
-
-        if self.config.logging_enabled {
-            info!("Executing scheduled task: {} ({})", task_name, task_id);
-        }
-
-        // Record execution start
-        let execution_id = match self.store.record_execution_start(task_id, 1).await {
-            Ok(id) => id,
-            Err(e) => {
-                error!("Failed to record execution start: {}", e);
-                self.cleanup_running_task(task_id).await;
-                return;
-            }
+    /// Start the runs of a task, or apply its overlap policy if it is already running
+    ///
+    /// Each entry of `runs` holds the template variables of one run; runs
+    /// after the first wait for the previous one to finish.
+    async fn dispatch(
+        &self,
+        task: ScheduledTask,
+        runs: Vec<HashMap<String, String>>,
+    ) -> RunDispatch {
+        let mut runs = VecDeque::from(runs);
+        let Some(first) = runs.pop_front() else {
+            return RunDispatch::Skipped;
         };
 
-        // Execute the action
-        let result = self.execute_action(&task.action).await;
-
-        // Record result
-        let (status, result_str) = match &result {
-            Ok(output) => ("success", Some(output.as_str())),
-            Err(e) => {
-                error!("Task {} failed: {}", task_name, e);
-                ("failed", None)
-            }
+        let mut running = self.running_tasks.write().await;
+        let outcome = match running.get_mut(&task.id) {
+            None => RunDispatch::Started,
+            Some(current) => match task.overlap_policy {
+                OverlapPolicy::Queue => {
+                    runs.push_front(first);
+                    let room = self
+                        .config
+                        .max_queued_runs
+                        .saturating_sub(current.queued.len());
+                    let overflow = runs.split_off(room.min(runs.len()));
+                    let queued = !runs.is_empty();
+                    current.queued.extend(runs);
+                    drop(running);
+
+                    if !overflow.is_empty() {
+                        warn!(
+                            "Task {} can queue {} runs, skipping {} more",
+                            task.name,
+                            self.config.max_queued_runs,
+                            overflow.len()
+                        );
+                        self.record_skipped(task.id, overflow.len(), "Run queue is full")
+                            .await;
+                    }
+                    if !queued {
+                        return RunDispatch::Skipped;
+                    }
+                    debug!("Task {} is running, queued the next run", task.name);
+                    return RunDispatch::Queued;
+                }
+                OverlapPolicy::Skip => {
+                    drop(running);
+                    info!("Task {} is still running, skipping run", task.name);
+                    self.record_skipped(task.id, runs.len() + 1, "Previous run still in progress")
+                        .await;
+                    return RunDispatch::Skipped;
+                }
+                OverlapPolicy::CancelPrevious => {
+                    info!("Task {} is still running, cancelling it", task.name);
+                    current.cancel.cancel();
+                    RunDispatch::Replaced
+                }
+            },
         };
 
-        if let Err(e) = self
-            .store
-            .record_execution_complete(execution_id, status, result_str)
-            .await
-        {
-            error!("Failed to record execution complete: {}", e);
-        }
-
-        // Update task statistics and next run time
-        let now = Utc::now();
-        let next_run = self.calculate_next_run(&task.trigger, now);
-        task.last_run_at = Some(now);
-        task.next_run_at = next_run;
-
-        if let Err(e) = self
-            .store
-            .update_task_stats(task_id, now, next_run, result.is_ok())
-            .await
-        {
-            error!("Failed to update task stats: {}", e);
-        }
-
-        if self.config.logging_enabled {
-            info!("Task {} completed with status: {}", task_name, status);
+        let overflow = runs.split_off(self.config.max_queued_runs.min(runs.len()));
+        let worker = Uuid::new_v4();
+        let cancel = CancellationToken::new();
+        running.insert(
+            task.id,
+            RunningTask {
+                task_id: task.id,
+                started_at: Utc::now(),
+                worker,
+                cancel: cancel.clone(),
+                queued: runs,
+            },
+        );
+        drop(running);
+        if !overflow.is_empty() {
+            warn!(
+                "Task {} can queue {} runs, skipping {} more",
+                task.name,
+                self.config.max_queued_runs,
+                overflow.len()
+            );
+            self.record_skipped(task.id, overflow.len(), "Run queue is full")
+                .await;
         }
 
-        self.cleanup_running_task(task_id).await;
+        let runner = TaskRunner {
+            store: self.store.clone(),
+            running_tasks: self.running_tasks.clone(),
+            executor: self.executor.clone(),
+            classifier: self.classifier.clone(),
+            logging_enabled: self.config.logging_enabled,
+        };
+        tokio::spawn(runner.run_worker(task, worker, cancel, first));
+        outcome
     }
 
-    /// Execute task action
-    async fn execute_action(&self, action: &TaskAction) -> Result<String> {
-        if let Some(executor) = &self.executor {
-            executor(action.clone()).await
-        } else {
-            // Default stub implementation for testing
-            match action {
-                TaskAction::NaturalLanguage { prompt, .. } => {
-                    debug!("Would execute prompt: {}", prompt);
-                    Ok(format!("Executed: {}", prompt))
-                }
-                TaskAction::ToolCall { tool, args } => {
-                    debug!("Would call tool: {} with args: {}", tool, args);
-                    Ok(format!("Called tool: {}", tool))
-                }
-                TaskAction::Notification {
-                    channel, message, ..
-                } => {
-                    debug!("Would send to {}: {}", channel, message);
-                    Ok(format!("Notified: {}", channel))
-                }
-                TaskAction::Shell { command, .. } => {
-                    debug!("Would execute: {}", command);
-                    Ok(format!("Executed: {}", command))
-                }
-                TaskAction::Webhook { url, method, .. } => {
-                    debug!("Would call {} {}", method, url);
-                    Ok(format!("Called: {} {}", method, url))
-                }
-                TaskAction::RunSkillAnalysis { dry_run } => {
-                    debug!("Would analyze skills (dry_run: {})", dry_run);
-                    Ok(format!("Analyzed skills (dry_run: {})", dry_run))
-                }
-                TaskAction::PruneStaleSkills { days } => {
-                    debug!("Would prune skills older than {} days", days);
-                    Ok(format!("Pruned skills older than {} days", days))
-                }
+    /// Record `count` runs of a task as skipped
+    async fn record_skipped(&self, task_id: Uuid, count: usize, reason: &str) {
+        for _ in 0..count {
+            if let Err(e) = self.store.record_execution_skipped(task_id, reason).await {
+                error!("Failed to record skipped run: {}", e);
             }
         }
     }
@@ -308,69 +491,15 @@ impl SchedulerEngine {
         trigger: &TriggerType,
         from: DateTime<Utc>,
     ) -> Option<DateTime<Utc>> {
-        match trigger {
-            TriggerType::Cron(cron) => self.calculate_next_cron(&cron.expression, from),
-            TriggerType::Interval(IntervalTrigger { seconds, .. }) => {
-                Some(from + Duration::seconds(*seconds as i64))
-            }
-            TriggerType::OneTime(one_time) => {
-                if one_time.at > from {
-                    Some(one_time.at)
-                } else {
-                    None // Already passed
-                }
-            }
-            TriggerType::File(_) | TriggerType::System(_) => {
-                // Event-driven triggers don't have a fixed next run time
-                None
-            }
-        }
+        trigger.next_run_after(from)
     }
 
-    /// Calculate next cron execution time (simplified implementation)
-    fn calculate_next_cron(&self, expression: &str, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
-        // Parse simple cron expressions
-        // Format: "minute hour day month weekday"
-        let parts: Vec<&str> = expression.split_whitespace().collect();
-        if parts.len() < 5 {
-            warn!("Invalid cron expression: {}", expression);
-            return None;
-        }
-
-        // For now, use a simplified approach:
-        // Just add appropriate duration based on the pattern
-        let minute = parts[0];
-        let hour = parts[1];
-        let day = parts[2];
-
-        // Every minute
-        if minute.starts_with("*/") && hour == "*" && day == "*" {
-            if let Ok(interval) = minute.trim_start_matches("*/").parse::<i64>() {
-                return Some(from + Duration::minutes(interval));
-            }
-        }
-
-        // Every hour
-        if minute != "*" && hour.starts_with("*/") && day == "*" {
-            if let Ok(interval) = hour.trim_start_matches("*/").parse::<i64>() {
-                return Some(from + Duration::hours(interval));
-            }
-        }
-
-        // Daily (specific hour)
-        if minute != "*" && hour != "*" && day == "*" {
-            // Add 24 hours for daily tasks
-            return Some(from + Duration::hours(24));
-        }
-
-        // Default: check again in 1 hour
-        Some(from + Duration::hours(1))
-    }
-
-    /// Clean up running task record
-    async fn cleanup_running_task(&self, task_id: uuid::Uuid) {
-        let mut running = self.running_tasks.write().await;
-        running.remove(&task_id);
+    /// Schedule the next run of a task after `from` and draw its jitter
+    fn schedule_next(&self, task: &mut ScheduledTask, from: DateTime<Utc>) {
+        task.next_run_at = self.calculate_next_run(&task.trigger, from);
+        task.fire_at = task
+            .next_run_at
+            .map(|at| apply_jitter(at, task.jitter_secs));
     }
 
     /// Wait for all running tasks to complete
@@ -403,10 +532,15 @@ impl SchedulerEngine {
     }
 
     /// Add a new task
+    ///
+    /// Fails if the trigger cannot be scheduled (e.g. an invalid cron expression)
+    /// or the action is invalid (e.g. a chain condition with a bad regex).
     pub async fn add_task(&self, task: ScheduledTask) -> Result<()> {
+        task.trigger.validate()?;
+        task.action.validate()?;
         let mut task = task;
         if task.next_run_at.is_none() {
-            task.next_run_at = self.calculate_next_run(&task.trigger, Utc::now());
+            self.schedule_next(&mut task, Utc::now());
         }
         self.store.create_task(&task).await
     }
@@ -421,7 +555,7 @@ impl SchedulerEngine {
         let mut task = self.store.get_task(task_id).await?;
         task.enabled = enabled;
         if enabled && task.next_run_at.is_none() {
-            task.next_run_at = self.calculate_next_run(&task.trigger, Utc::now());
+            self.schedule_next(&mut task, Utc::now());
         }
         self.store.update_task(&task).await
     }
@@ -435,6 +569,261 @@ impl SchedulerEngine {
     pub async fn get_task(&self, task_id: uuid::Uuid) -> Result<ScheduledTask> {
         self.store.get_task(task_id).await
     }
+
+    /// Run a task now, outside its schedule
+    ///
+    /// The task's overlap policy applies if it is already running; its next
+    /// scheduled run is unchanged.
+    pub async fn run_now(&self, task_id: Uuid) -> Result<RunDispatch> {
+        let task = self.store.get_task(task_id).await?;
+        Ok(self.dispatch(task, vec![HashMap::new()]).await)
+    }
+
+    /// Recent executions of a task, newest first
+    pub async fn task_executions(&self, task_id: Uuid, limit: i64) -> Result<Vec<TaskExecution>> {
+        self.store.get_task(task_id).await?;
+        self.store.get_task_executions(task_id, limit).await
+    }
+
+    /// Preview the next `count` run times of a task
+    ///
+    /// Starts at the task's pending next run; event-driven tasks have none.
+    pub async fn preview_runs(
+        &self,
+        task_id: uuid::Uuid,
+        count: usize,
+    ) -> Result<Vec<DateTime<Utc>>> {
+        let task = self.store.get_task(task_id).await?;
+        let now = Utc::now();
+        let first = task
+            .next_run_at
+            .filter(|next| *next > now)
+            .or_else(|| task.trigger.next_run_after(now));
+        Ok(
+            std::iter::successors(first, |prev| task.trigger.next_run_after(*prev))
+                .take(count)
+                .collect(),
+        )
+    }
+}
+
+impl TaskRunner {
+    /// Run a task, then the runs queued behind it
+    async fn run_worker(
+        self,
+        task: ScheduledTask,
+        worker: Uuid,
+        cancel: CancellationToken,
+        mut variables: HashMap<String, String>,
+    ) {
+        loop {
+            self.execute_task(&task, &variables, &cancel).await;
+
+            let mut running = self.running_tasks.write().await;
+            match running.get_mut(&task.id) {
+                Some(current) if current.worker == worker => match current.queued.pop_front() {
+                    Some(next) => {
+                        current.started_at = Utc::now();
+                        variables = next;
+                    }
+                    None => {
+                        running.remove(&task.id);
+                        return;
+                    }
+                },
+                // Replaced by a newer run (overlap policy `cancel_previous`)
+                _ => return,
+            }
+        }
+    }
+
+    /// Execute a single task, filling the action's template `variables`
+    async fn execute_task(
+        &self,
+        task: &ScheduledTask,
+        variables: &HashMap<String, String>,
+        cancel: &CancellationToken,
+    ) {
+        let task_id = task.id;
+        let task_name = &task.name;
+
+        if self.logging_enabled {
+            info!("Executing scheduled task: {} ({})", task_name, task_id);
+        }
+
+        // Record execution start
+        let execution_id = match self.store.record_execution_start(task_id, 1).await {
+            Ok(id) => id,
+            Err(e) => {
+                error!("Failed to record execution start: {}", e);
+                return;
+            }
+        };
+
+        // Execute the action
+        let result = tokio::select! {
+            result = self.run_action(&task.action, variables) => Some(result),
+            _ = cancel.cancelled() => None,
+        };
+
+        // Record result
+        let (status, result_str) = match &result {
+            Some(Ok(output)) => ("success", Some(output.clone())),
+            Some(Err(e)) => {
+                error!("Task {} failed: {}", task_name, e);
+                ("failed", Some(e.to_string()))
+            }
+            None => ("cancelled", Some("Cancelled by a newer run".to_string())),
+        };
+
+        if let Err(e) = self
+            .store
+            .record_execution_complete(execution_id, status, result_str.as_deref())
+            .await
+        {
+            error!("Failed to record execution complete: {}", e);
+        }
+
+        // Update task statistics (the next run was scheduled when this one started)
+        if let Some(result) = &result {
+            if let Err(e) = self
+                .store
+                .record_run_stats(task_id, Utc::now(), result.is_ok())
+                .await
+            {
+                error!("Failed to update task stats: {}", e);
+            }
+        }
+
+        if self.logging_enabled {
+            info!("Task {} completed with status: {}", task_name, status);
+        }
+    }
+
+    /// Run a task's action, filling its template `variables`
+    async fn run_action(
+        &self,
+        action: &TaskAction,
+        variables: &HashMap<String, String>,
+    ) -> Result<String> {
+        match action {
+            TaskAction::Chain { steps } => self.run_chain(steps, variables).await,
+            action => self.execute_action(&action.with_variables(variables)).await,
+        }
+    }
+
+    /// Run the steps of a chain, returning a per-step report and the last output
+    async fn run_chain(
+        &self,
+        steps: &[ChainStep],
+        variables: &HashMap<String, String>,
+    ) -> Result<String> {
+        let mut variables = variables.clone();
+        let mut output: Option<String> = None;
+        let mut report = Vec::with_capacity(steps.len());
+
+        for (index, step) in steps.iter().enumerate() {
+            let number = index + 1;
+            if let Some(condition) = &step.when {
+                let holds = self
+                    .check_condition(condition, output.as_deref().unwrap_or(""))
+                    .await
+                    .map_err(|e| {
+                        SchedulerError::Execution(format!("step {number} condition: {e}"))
+                    })?;
+                if holds == step.negate {
+                    debug!("Chain step {} skipped: condition not met", number);
+                    report.push(format!("step {number}: skipped (condition not met)"));
+                    continue;
+                }
+            }
+
+            let result = self
+                .execute_action(&step.action.with_variables(&variables))
+                .await
+                .map_err(|e| SchedulerError::Execution(format!("step {number}: {e}")))?;
+            report.push(format!("step {number}: ok"));
+            variables.insert("output".to_string(), result.clone());
+            variables.insert(format!("output_{number}"), result.clone());
+            output = Some(result);
+        }
+
+        Ok(format!(
+            "{}\n\n{}",
+            report.join("\n"),
+            output.unwrap_or_default()
+        ))
+    }
+
+    /// Evaluate a chain step condition on the previous step's output
+    async fn check_condition(&self, condition: &StepCondition, output: &str) -> Result<bool> {
+        match condition {
+            StepCondition::Classify { question } => {
+                let provider = self.classifier.as_ref().ok_or_else(|| {
+                    SchedulerError::InvalidConfig(
+                        "classify conditions need an LLM provider".to_string(),
+                    )
+                })?;
+                let request = CompletionRequest {
+                    model: provider.default_model().to_string(),
+                    messages: vec![Message::user(classify_prompt(question, output))],
+                    max_tokens: Some(16),
+                    temperature: Some(0.0),
+                    stop: None,
+                };
+                let answer = provider
+                    .complete(request)
+                    .await
+                    .map_err(|e| SchedulerError::Execution(format!("classifier: {e}")))?;
+                parse_yes_no(&answer.content)
+            }
+            other => other.evaluate(output),
+        }
+    }
+
+    /// Execute task action
+    async fn execute_action(&self, action: &TaskAction) -> Result<String> {
+        if let Some(executor) = &self.executor {
+            executor(action.clone()).await
+        } else {
+            // Default stub implementation for testing
+            match action {
+                TaskAction::NaturalLanguage { prompt, .. } => {
+                    debug!("Would execute prompt: {}", prompt);
+                    Ok(format!("Executed: {}", prompt))
+                }
+                TaskAction::ToolCall { tool, args } => {
+                    debug!("Would call tool: {} with args: {}", tool, args);
+                    Ok(format!("Called tool: {}", tool))
+                }
+                TaskAction::Notification {
+                    channel, message, ..
+                } => {
+                    debug!("Would send to {}: {}", channel, message);
+                    Ok(format!("Notified: {}", channel))
+                }
+                TaskAction::Shell { command, .. } => {
+                    debug!("Would execute: {}", command);
+                    Ok(format!("Executed: {}", command))
+                }
+                TaskAction::Webhook { url, method, .. } => {
+                    debug!("Would call {} {}", method, url);
+                    Ok(format!("Called: {} {}", method, url))
+                }
+                TaskAction::RunSkillAnalysis { dry_run } => {
+                    debug!("Would analyze skills (dry_run: {})", dry_run);
+                    Ok(format!("Analyzed skills (dry_run: {})", dry_run))
+                }
+                TaskAction::PruneStaleSkills { days } => {
+                    debug!("Would prune skills older than {} days", days);
+                    Ok(format!("Pruned skills older than {} days", days))
+                }
+                TaskAction::Chain { .. } => Err(SchedulerError::InvalidConfig(
+                    "chains cannot be nested".to_string(),
+                )),
+            }
+        }
+    }
 }
 
 /// Builder for creating SchedulerEngine
@@ -442,6 +831,7 @@ pub struct SchedulerEngineBuilder {
     store: Option<Arc<SchedulerStore>>,
     config: SchedulerConfig,
     executor: Option<TaskExecutor>,
+    classifier: Option<Arc<dyn LlmProvider>>,
 }
 
 impl SchedulerEngineBuilder {
@@ -451,6 +841,7 @@ impl SchedulerEngineBuilder {
             store: None,
             config: SchedulerConfig::default(),
             executor: None,
+            classifier: None,
         }
     }
 
@@ -472,6 +863,12 @@ impl SchedulerEngineBuilder {
         self
     }
 
+    /// Set the classifier LLM
+    pub fn classifier(mut self, provider: Arc<dyn LlmProvider>) -> Self {
+        self.classifier = Some(provider);
+        self
+    }
+
     /// Build the engine
     pub fn build(self) -> Result<SchedulerEngine> {
         let store = self
@@ -482,6 +879,9 @@ impl SchedulerEngineBuilder {
         if let Some(executor) = self.executor {
             engine = engine.with_executor(executor);
         }
+        if let Some(classifier) = self.classifier {
+            engine = engine.with_classifier(classifier);
+        }
 
         Ok(engine)
     }
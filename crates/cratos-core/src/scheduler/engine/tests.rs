@@ -1,5 +1,7 @@
 
     use super::*;
+    use crate::scheduler::{JsonOp, MisfirePolicy};
+    use cratos_llm::{CompletionResponse, ToolCompletionRequest, ToolCompletionResponse};
     use tempfile::TempDir;
 
     struct TestContext {
@@ -97,3 +99,431 @@
         let ctx = create_test_context().await;
         assert_eq!(ctx.engine.running_count().await, 0);
     }
+
+    #[tokio::test]
+    async fn test_add_task_rejects_invalid_cron() {
+        let ctx = create_test_context().await;
+
+        let task = ScheduledTask::new(
+            "bad_cron",
+            TriggerType::cron_in("0 9 * * *", "Nowhere/City"),
+            TaskAction::natural_language("Test"),
+        );
+
+        let result = ctx.engine.add_task(task).await;
+        assert!(matches!(result, Err(SchedulerError::TriggerParse(_))));
+        assert!(ctx.engine.list_tasks().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_preview_runs() {
+        let ctx = create_test_context().await;
+
+        let task = ScheduledTask::new(
+            "weekday_report",
+            TriggerType::cron_in("0 9 * * MON-FRI", "Asia/Seoul"),
+            TaskAction::natural_language("Daily report"),
+        );
+        let task_id = task.id;
+        ctx.engine.add_task(task).await.unwrap();
+
+        let runs = ctx.engine.preview_runs(task_id, 5).await.unwrap();
+        assert_eq!(runs.len(), 5);
+        assert!(runs.windows(2).all(|w| w[0] < w[1]));
+        let seoul: chrono_tz::Tz = "Asia/Seoul".parse().unwrap();
+        for run in runs {
+            let local = run.with_timezone(&seoul);
+            assert_eq!(local.format("%H:%M").to_string(), "09:00");
+            assert!(!matches!(
+                chrono::Datelike::weekday(&local),
+                chrono::Weekday::Sat | chrono::Weekday::Sun
+            ));
+        }
+    }
+
+    /// Executor whose runs take `millis`
+    fn slow_executor(millis: u64) -> TaskExecutor {
+        Arc::new(move |_action| {
+            Box::pin(async move {
+                tokio::time::sleep(tokio::time::Duration::from_millis(millis)).await;
+                Ok("done".to_string())
+            })
+        })
+    }
+
+    async fn wait_until_idle(engine: &SchedulerEngine) {
+        for _ in 0..100 {
+            if engine.running_count().await == 0 {
+                return;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+        }
+        panic!("Tasks still running");
+    }
+
+    async fn statuses(engine: &SchedulerEngine, task_id: Uuid) -> Vec<String> {
+        let mut statuses: Vec<String> = engine
+            .task_executions(task_id, 100)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|execution| execution.status)
+            .collect();
+        statuses.sort();
+        statuses
+    }
+
+    #[tokio::test]
+    async fn test_misfire_catch_up() {
+        let ctx = create_test_context().await;
+        let scheduled = Utc::now() - chrono::Duration::minutes(210);
+
+        let mut run_all = ScheduledTask::new(
+            "run_all",
+            TriggerType::interval(3600),
+            TaskAction::natural_language("Catch up"),
+        )
+        .with_misfire_policy(MisfirePolicy::RunAll { max_runs: 10 });
+        run_all.next_run_at = Some(scheduled);
+        let mut skip = ScheduledTask::new(
+            "skip",
+            TriggerType::interval(3600),
+            TaskAction::natural_language("Skip"),
+        )
+        .with_misfire_policy(MisfirePolicy::Skip);
+        skip.next_run_at = Some(scheduled);
+        let (run_all_id, skip_id) = (run_all.id, skip.id);
+        ctx.engine.add_task(run_all).await.unwrap();
+        ctx.engine.add_task(skip).await.unwrap();
+
+        ctx.engine.check_and_execute().await.unwrap();
+        wait_until_idle(&ctx.engine).await;
+
+        // Missed at -3.5h, -2.5h, -1.5h and -0.5h
+        assert_eq!(statuses(&ctx.engine, run_all_id).await, vec!["success"; 4]);
+        assert!(statuses(&ctx.engine, skip_id).await.is_empty());
+
+        for task_id in [run_all_id, skip_id] {
+            let task = ctx.engine.get_task(task_id).await.unwrap();
+            assert_eq!(
+                task.next_run_at,
+                Some(scheduled + chrono::Duration::hours(4))
+            );
+        }
+        assert_eq!(ctx.engine.get_task(run_all_id).await.unwrap().run_count, 4);
+    }
+
+    #[tokio::test]
+    async fn test_jitter_does_not_drift_schedule() {
+        let ctx = create_test_context().await;
+
+        // Jitter longer than the interval must neither drift nor skip runs
+        let task = ScheduledTask::new(
+            "jittered",
+            TriggerType::interval(60),
+            TaskAction::natural_language("Jitter"),
+        )
+        .with_jitter(90);
+        let task_id = task.id;
+        ctx.engine.add_task(task).await.unwrap();
+        let first = ctx.engine.get_task(task_id).await.unwrap().next_run_at.unwrap();
+
+        for run in 0..5 {
+            let task = ctx.engine.get_task(task_id).await.unwrap();
+            let scheduled = first + chrono::Duration::seconds(60 * run);
+            assert_eq!(task.next_run_at, Some(scheduled));
+            let fire_at = task.fire_at.unwrap();
+            assert!(fire_at >= scheduled && fire_at <= scheduled + chrono::Duration::seconds(90));
+
+            ctx.engine.execute_due(fire_at).await.unwrap();
+            wait_until_idle(&ctx.engine).await;
+        }
+
+        let task = ctx.engine.get_task(task_id).await.unwrap();
+        assert_eq!(task.run_count, 5);
+        assert_eq!(task.next_run_at, Some(first + chrono::Duration::seconds(300)));
+        assert_eq!(statuses(&ctx.engine, task_id).await, vec!["success"; 5]);
+    }
+
+    #[tokio::test]
+    async fn test_overlap_policies() {
+        let ctx = create_test_context().await;
+        let engine = SchedulerEngine::new(ctx._store.clone(), SchedulerConfig::new())
+            .with_executor(slow_executor(200));
+
+        let mut expected = Vec::new();
+        for (policy, outcome, statuses) in [
+            (
+                OverlapPolicy::Skip,
+                RunDispatch::Skipped,
+                vec!["skipped", "success"],
+            ),
+            (
+                OverlapPolicy::Queue,
+                RunDispatch::Queued,
+                vec!["success", "success"],
+            ),
+            (
+                OverlapPolicy::CancelPrevious,
+                RunDispatch::Replaced,
+                vec!["cancelled", "success"],
+            ),
+        ] {
+            let task = ScheduledTask::new(
+                policy.as_str(),
+                TriggerType::interval(3600),
+                TaskAction::natural_language("Slow"),
+            )
+            .with_overlap_policy(policy);
+            let task_id = task.id;
+            engine.add_task(task).await.unwrap();
+
+            assert_eq!(engine.run_now(task_id).await.unwrap(), RunDispatch::Started);
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            assert_eq!(engine.run_now(task_id).await.unwrap(), outcome);
+            expected.push((task_id, statuses));
+        }
+
+        wait_until_idle(&engine).await;
+        for (task_id, statuses_expected) in expected {
+            assert_eq!(statuses(&engine, task_id).await, statuses_expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queue_is_bounded() {
+        let ctx = create_test_context().await;
+        let engine = SchedulerEngine::new(
+            ctx._store.clone(),
+            SchedulerConfig::new().with_max_queued_runs(1),
+        )
+        .with_executor(slow_executor(200));
+
+        let task = ScheduledTask::new(
+            "queue",
+            TriggerType::interval(3600),
+            TaskAction::natural_language("Slow"),
+        )
+        .with_overlap_policy(OverlapPolicy::Queue);
+        let task_id = task.id;
+        engine.add_task(task).await.unwrap();
+
+        assert_eq!(engine.run_now(task_id).await.unwrap(), RunDispatch::Started);
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        assert_eq!(engine.run_now(task_id).await.unwrap(), RunDispatch::Queued);
+        assert_eq!(engine.run_now(task_id).await.unwrap(), RunDispatch::Skipped);
+
+        wait_until_idle(&engine).await;
+        assert_eq!(
+            statuses(&engine, task_id).await,
+            vec!["skipped", "success", "success"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_now_keeps_schedule() {
+        let ctx = create_test_context().await;
+
+        let task = ScheduledTask::new(
+            "manual",
+            TriggerType::interval(3600),
+            TaskAction::natural_language("Now"),
+        );
+        let task_id = task.id;
+        ctx.engine.add_task(task).await.unwrap();
+        let next_run_at = ctx.engine.get_task(task_id).await.unwrap().next_run_at;
+
+        ctx.engine.run_now(task_id).await.unwrap();
+        wait_until_idle(&ctx.engine).await;
+
+        let executions = ctx.engine.task_executions(task_id, 10).await.unwrap();
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].status, "success");
+        assert_eq!(
+            executions[0].result.as_deref(),
+            Some("Executed: Now")
+        );
+
+        let task = ctx.engine.get_task(task_id).await.unwrap();
+        assert_eq!(task.next_run_at, next_run_at);
+        assert_eq!(task.run_count, 1);
+
+        assert!(ctx.engine.run_now(Uuid::new_v4()).await.is_err());
+        assert!(ctx.engine.task_executions(Uuid::new_v4(), 10).await.is_err());
+    }
+
+    /// Classifier that answers "Yes." and rejects tool calls
+    struct YesProvider;
+
+    #[async_trait::async_trait]
+    impl LlmProvider for YesProvider {
+        fn name(&self) -> &str {
+            "yes"
+        }
+
+        fn supports_tools(&self) -> bool {
+            false
+        }
+
+        fn available_models(&self) -> Vec<String> {
+            vec!["yes-model".to_string()]
+        }
+
+        fn default_model(&self) -> &str {
+            "yes-model"
+        }
+
+        async fn complete(
+            &self,
+            request: CompletionRequest,
+        ) -> cratos_llm::Result<CompletionResponse> {
+            Ok(CompletionResponse {
+                content: "Yes.".to_string(),
+                usage: None,
+                finish_reason: Some("stop".to_string()),
+                model: request.model,
+            })
+        }
+
+        async fn complete_with_tools(
+            &self,
+            _request: ToolCompletionRequest,
+        ) -> cratos_llm::Result<ToolCompletionResponse> {
+            panic!("classifier must not be offered tools");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_conditions() {
+        let ctx = create_test_context().await;
+        let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sent_by_executor = sent.clone();
+        // Status check output and a recording notifier
+        let executor: TaskExecutor = Arc::new(move |action| {
+            let sent = sent_by_executor.clone();
+            Box::pin(async move {
+                match action {
+                    TaskAction::ToolCall { .. } => {
+                        Ok(r#"{"status": {"indicator": "major"}}"#.to_string())
+                    }
+                    TaskAction::Notification { message, .. } => {
+                        sent.lock().unwrap().push(message);
+                        Ok("sent".to_string())
+                    }
+                    other => Err(SchedulerError::Execution(format!("{:?}", other))),
+                }
+            })
+        });
+        let engine = SchedulerEngine::new(ctx._store.clone(), SchedulerConfig::new())
+            .with_executor(executor)
+            .with_classifier(Arc::new(YesProvider));
+
+        let notify = |message: &str| TaskAction::notification("telegram", "42", message);
+        let task = ScheduledTask::new(
+            "status_page",
+            TriggerType::interval(600),
+            TaskAction::chain(vec![
+                ChainStep::new(TaskAction::tool_call(
+                    "http_get",
+                    serde_json::json!({"url": "https://status.example.com"}),
+                )),
+                ChainStep::new(notify("Down: {{output}}")).when(StepCondition::JsonPath {
+                    path: "status.indicator".to_string(),
+                    op: JsonOp::NotEquals,
+                    value: serde_json::json!("none"),
+                }),
+                ChainStep::new(notify("Healthy")).when(StepCondition::Regex {
+                    pattern: "^sent$".to_string(),
+                }),
+                ChainStep::new(notify("Outage confirmed")).when(StepCondition::Classify {
+                    question: "Is the service down?".to_string(),
+                }),
+                ChainStep::new(notify("Never")).unless(StepCondition::Classify {
+                    question: "Is it down?".to_string(),
+                }),
+            ]),
+        );
+        let task_id = task.id;
+        engine.add_task(task).await.unwrap();
+        engine.run_now(task_id).await.unwrap();
+        wait_until_idle(&engine).await;
+
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![
+                r#"Down: {"status": {"indicator": "major"}}"#.to_string(),
+                "Healthy".to_string(),
+                "Outage confirmed".to_string(),
+            ]
+        );
+        let executions = engine.task_executions(task_id, 10).await.unwrap();
+        assert_eq!(executions[0].status, "success");
+        let report = executions[0].result.as_deref().unwrap();
+        assert!(report.contains("step 5: skipped (condition not met)"));
+
+        // Conditions are validated when the task is added
+        let invalid = ScheduledTask::new(
+            "invalid",
+            TriggerType::interval(600),
+            TaskAction::chain(vec![ChainStep::new(notify("x")).when(
+                StepCondition::Regex {
+                    pattern: "(".to_string(),
+                },
+            )]),
+        );
+        assert!(engine.add_task(invalid).await.is_err());
+    }
+
+    #[test]
+    fn test_action_template_variables() {
+        let variables = HashMap::from([
+            ("path".to_string(), "/tmp/it's; rm -rf ~".to_string()),
+            ("event".to_string(), "created".to_string()),
+        ]);
+
+        let action = TaskAction::natural_language("File {{path}} was {{event}} ({{other}})")
+            .with_variables(&variables);
+        match action {
+            TaskAction::NaturalLanguage { prompt, .. } => {
+                assert_eq!(prompt, "File /tmp/it's; rm -rf ~ was created ({{other}})")
+            }
+            other => panic!("Unexpected action: {:?}", other),
+        }
+
+        let action = TaskAction::tool_call("file_read", serde_json::json!({"path": "{{path}}"}))
+            .with_variables(&variables);
+        match action {
+            TaskAction::ToolCall { args, .. } => assert_eq!(args["path"], "/tmp/it's; rm -rf ~"),
+            other => panic!("Unexpected action: {:?}", other),
+        }
+
+        // Shell commands get the value as one separate argument
+        let action = TaskAction::Shell {
+            command: "wc -l {{path}} --total=never".to_string(),
+            args: vec!["{{event}}".to_string()],
+            cwd: None,
+        }
+        .with_variables(&variables);
+        match action {
+            TaskAction::Shell { command, args, .. } => {
+                assert_eq!(command, "wc -l");
+                assert_eq!(args, ["/tmp/it's; rm -rf ~", "--total=never", "created"]);
+            }
+            other => panic!("Unexpected action: {:?}", other),
+        }
+
+        // Placeholders that would be spliced into command text are rejected
+        for command in [
+            "cat \"{{path}}\"",
+            "cat '{{path}}'",
+            "cat --file={{path}}",
+            "{{path}}",
+        ] {
+            let action = TaskAction::Shell {
+                command: command.to_string(),
+                args: Vec::new(),
+                cwd: None,
+            };
+            assert!(action.validate().is_err(), "{command}");
+        }
+    }
@@ -0,0 +1,261 @@
+//! File system triggers
+//!
+//! Watches the paths of file triggers (inotify on Linux) and fires a task
+//! once its matching events have been quiet for the trigger's `debounce_ms`,
+//! so an editor saving a file in several writes fires the task once.
+//!
+//! A glob path is watched from its longest literal directory prefix;
+//! patterns spanning subdirectories (e.g. `/data/**/*.csv`) are watched
+//! recursively.
+//!
+//! A trigger that cannot be watched yet (e.g. its directory does not exist)
+//! is retried each time the watchers are synced, on every scheduler check.
+
+use globset::{GlobBuilder, GlobMatcher};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use tokio::time::{sleep_until, Duration, Instant};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::triggers::{FileEvent, FileTrigger, TriggerEvent, TriggerType};
+use super::types::{Result, ScheduledTask, SchedulerError};
+
+/// Matching event of a watched task, before debouncing
+#[derive(Debug)]
+struct RawFileEvent {
+    task_id: Uuid,
+    path: PathBuf,
+    event: FileEvent,
+    debounce_ms: u64,
+}
+
+/// What to watch for a trigger path
+#[derive(Debug)]
+pub(super) struct WatchSpec {
+    /// Directory (or file) registered with the OS watcher
+    pub root: PathBuf,
+    pub mode: RecursiveMode,
+    /// Paths that fire the trigger
+    pub matcher: GlobMatcher,
+}
+
+impl WatchSpec {
+    /// Split a trigger path into a watch root and a path matcher
+    ///
+    /// Relative paths are resolved against the working directory.
+    pub(super) fn parse(path: &str) -> Result<Self> {
+        let path = std::path::absolute(path)
+            .map_err(|e| SchedulerError::TriggerParse(format!("invalid path '{path}': {e}")))?;
+        let path = path.as_path();
+        let is_glob = |s: &str| s.contains(['*', '?', '[', '{']);
+
+        let mut root = PathBuf::new();
+        let mut pattern_parts = 0;
+        for component in path.components() {
+            let part = component.as_os_str().to_string_lossy();
+            if pattern_parts > 0 || is_glob(&part) {
+                pattern_parts += 1;
+            } else {
+                root.push(component);
+            }
+        }
+
+        let (root, pattern, mode) = if pattern_parts == 0 {
+            if path.is_dir() {
+                // Any entry directly inside the directory
+                (root, path.join("*"), RecursiveMode::NonRecursive)
+            } else {
+                // Watch the parent: editors replace files on save, which
+                // would drop a watch on the file itself
+                let parent = path.parent().unwrap_or(path).to_path_buf();
+                (parent, path.to_path_buf(), RecursiveMode::NonRecursive)
+            }
+        } else {
+            let recursive = pattern_parts > 1 || path.to_string_lossy().contains("**");
+            let mode = if recursive {
+                RecursiveMode::Recursive
+            } else {
+                RecursiveMode::NonRecursive
+            };
+            (root, path.to_path_buf(), mode)
+        };
+
+        let matcher = GlobBuilder::new(&pattern.to_string_lossy())
+            .literal_separator(true)
+            .build()
+            .map_err(|e| {
+                SchedulerError::TriggerParse(format!(
+                    "invalid path pattern '{}': {e}",
+                    pattern.display()
+                ))
+            })?
+            .compile_matcher();
+
+        Ok(Self {
+            root,
+            mode,
+            matcher,
+        })
+    }
+
+    /// Whether an event path fires the trigger
+    pub(super) fn matches(&self, path: &Path) -> bool {
+        self.matcher.is_match(path)
+    }
+}
+
+/// Map an OS event to the trigger's event kind
+fn file_event(kind: &EventKind) -> Option<FileEvent> {
+    match kind {
+        EventKind::Create(_) => Some(FileEvent::Created),
+        EventKind::Modify(ModifyKind::Name(_)) => Some(FileEvent::Renamed),
+        EventKind::Modify(ModifyKind::Metadata(_)) => None,
+        EventKind::Modify(_) => Some(FileEvent::Modified),
+        EventKind::Remove(_) => Some(FileEvent::Deleted),
+        _ => None,
+    }
+}
+
+/// Watchers of all enabled file triggers
+pub(super) struct FileWatchers {
+    watchers: HashMap<Uuid, (FileTrigger, RecommendedWatcher)>,
+    /// Triggers that could not be watched, retried on the next sync
+    failed: HashMap<Uuid, FileTrigger>,
+    raw_tx: mpsc::UnboundedSender<RawFileEvent>,
+}
+
+impl FileWatchers {
+    /// Create watchers that send debounced firings to `events`
+    pub(super) fn new(events: mpsc::UnboundedSender<TriggerEvent>) -> Self {
+        let (raw_tx, raw_rx) = mpsc::unbounded_channel();
+        tokio::spawn(debounce(raw_rx, events));
+        Self {
+            watchers: HashMap::new(),
+            failed: HashMap::new(),
+            raw_tx,
+        }
+    }
+
+    /// Watch the file triggers of `tasks` (the enabled tasks), dropping others
+    pub(super) fn sync(&mut self, tasks: &[ScheduledTask]) {
+        let wanted: HashMap<Uuid, &FileTrigger> = tasks
+            .iter()
+            .filter_map(|task| match &task.trigger {
+                TriggerType::File(trigger) => Some((task.id, trigger)),
+                _ => None,
+            })
+            .collect();
+
+        self.watchers
+            .retain(|id, (trigger, _)| wanted.get(id).is_some_and(|wanted| *wanted == trigger));
+        self.failed
+            .retain(|id, trigger| wanted.get(id).is_some_and(|wanted| *wanted == trigger));
+
+        for (id, trigger) in wanted {
+            if self.watchers.contains_key(&id) {
+                continue;
+            }
+            // Only the first failure is worth a warning; retries fail quietly
+            let retry = self.failed.contains_key(&id);
+            match self.watch(id, trigger) {
+                Ok(watcher) => {
+                    if retry {
+                        info!(task_id = %id, path = %trigger.path, "Watching file trigger after retry");
+                    } else {
+                        debug!(task_id = %id, path = %trigger.path, "Watching file trigger");
+                    }
+                    self.failed.remove(&id);
+                    self.watchers.insert(id, (trigger.clone(), watcher));
+                }
+                Err(e) if retry => {
+                    debug!(task_id = %id, path = %trigger.path, error = %e, "File trigger still cannot be watched");
+                }
+                Err(e) => {
+                    warn!(task_id = %id, path = %trigger.path, error = %e, "Cannot watch file trigger, will retry");
+                    self.failed.insert(id, trigger.clone());
+                }
+            }
+        }
+    }
+
+    fn watch(&self, task_id: Uuid, trigger: &FileTrigger) -> Result<RecommendedWatcher> {
+        let spec = WatchSpec::parse(&trigger.path)?;
+        let events = trigger.events.clone();
+        let debounce_ms = trigger.debounce_ms;
+        let raw_tx = self.raw_tx.clone();
+        let root = spec.root.clone();
+        let mode = spec.mode;
+
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            let Ok(event) = res else {
+                return;
+            };
+            let Some(kind) = file_event(&event.kind).filter(|kind| events.contains(kind)) else {
+                return;
+            };
+            for path in event.paths.iter().filter(|path| spec.matches(path)) {
+                let _ = raw_tx.send(RawFileEvent {
+                    task_id,
+                    path: path.clone(),
+                    event: kind,
+                    debounce_ms,
+                });
+            }
+        })
+        .map_err(|e| SchedulerError::InvalidConfig(format!("file watcher: {e}")))?;
+        watcher.watch(&root, mode).map_err(|e| {
+            SchedulerError::InvalidConfig(format!("cannot watch {}: {e}", root.display()))
+        })?;
+        Ok(watcher)
+    }
+}
+
+/// Fire each task once its events have been quiet for its debounce time
+async fn debounce(
+    mut raw: mpsc::UnboundedReceiver<RawFileEvent>,
+    events: mpsc::UnboundedSender<TriggerEvent>,
+) {
+    let mut pending: HashMap<Uuid, (RawFileEvent, Instant)> = HashMap::new();
+    loop {
+        let next_deadline = pending.values().map(|(_, deadline)| *deadline).min();
+        tokio::select! {
+            received = raw.recv() => match received {
+                Some(event) => {
+                    let deadline = Instant::now() + Duration::from_millis(event.debounce_ms);
+                    pending.insert(event.task_id, (event, deadline));
+                }
+                None => break,
+            },
+            _ = sleep_until(next_deadline.unwrap_or_else(Instant::now)), if next_deadline.is_some() => {}
+        }
+
+        let now = Instant::now();
+        let due: Vec<Uuid> = pending
+            .iter()
+            .filter(|(_, (_, deadline))| *deadline <= now)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in due {
+            let Some((event, _)) = pending.remove(&id) else {
+                continue;
+            };
+            let fired = TriggerEvent {
+                task_id: event.task_id,
+                variables: HashMap::from([
+                    ("path".to_string(), event.path.display().to_string()),
+                    ("event".to_string(), event.event.as_str().to_string()),
+                ]),
+            };
+            if events.send(fired).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
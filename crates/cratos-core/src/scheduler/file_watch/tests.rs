@@ -0,0 +1,123 @@
+use super::*;
+use crate::scheduler::TaskAction;
+use tempfile::TempDir;
+
+#[test]
+fn test_watch_spec_glob() {
+    let spec = WatchSpec::parse("/data/inbox/*.csv").unwrap();
+    assert_eq!(spec.root, PathBuf::from("/data/inbox"));
+    assert_eq!(spec.mode, RecursiveMode::NonRecursive);
+    assert!(spec.matches(Path::new("/data/inbox/report.csv")));
+    assert!(!spec.matches(Path::new("/data/inbox/report.txt")));
+    assert!(!spec.matches(Path::new("/data/inbox/old/report.csv")));
+
+    let spec = WatchSpec::parse("/data/**/*.csv").unwrap();
+    assert_eq!(spec.root, PathBuf::from("/data"));
+    assert_eq!(spec.mode, RecursiveMode::Recursive);
+    assert!(spec.matches(Path::new("/data/inbox/old/report.csv")));
+}
+
+#[test]
+fn test_watch_spec_plain_paths() {
+    let dir = TempDir::new().unwrap();
+
+    // A directory fires for its entries
+    let spec = WatchSpec::parse(dir.path().to_str().unwrap()).unwrap();
+    assert_eq!(spec.root, dir.path());
+    assert!(spec.matches(&dir.path().join("notes.txt")));
+
+    // A file is watched through its directory
+    let file = dir.path().join("notes.txt");
+    let spec = WatchSpec::parse(file.to_str().unwrap()).unwrap();
+    assert_eq!(spec.root, dir.path());
+    assert!(spec.matches(&file));
+    assert!(!spec.matches(&dir.path().join("other.txt")));
+}
+
+#[test]
+fn test_file_event_mapping() {
+    use notify::event::{CreateKind, DataChange, MetadataKind, RemoveKind, RenameMode};
+
+    assert_eq!(
+        file_event(&EventKind::Create(CreateKind::File)),
+        Some(FileEvent::Created)
+    );
+    assert_eq!(
+        file_event(&EventKind::Modify(ModifyKind::Data(DataChange::Content))),
+        Some(FileEvent::Modified)
+    );
+    assert_eq!(
+        file_event(&EventKind::Modify(ModifyKind::Name(RenameMode::To))),
+        Some(FileEvent::Renamed)
+    );
+    assert_eq!(
+        file_event(&EventKind::Remove(RemoveKind::File)),
+        Some(FileEvent::Deleted)
+    );
+    assert_eq!(
+        file_event(&EventKind::Modify(ModifyKind::Metadata(MetadataKind::Any))),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_debounced_event_fires_once() {
+    let dir = TempDir::new().unwrap();
+    let pattern = dir.path().join("*.txt");
+    let mut task = ScheduledTask::new(
+        "on_txt",
+        TriggerType::file(pattern.to_str().unwrap()),
+        TaskAction::natural_language("Summarize {{path}}"),
+    );
+    if let TriggerType::File(trigger) = &mut task.trigger {
+        trigger.events = vec![FileEvent::Created, FileEvent::Modified];
+        trigger.debounce_ms = 200;
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watchers = FileWatchers::new(tx);
+    watchers.sync(std::slice::from_ref(&task));
+    assert_eq!(watchers.watchers.len(), 1);
+
+    let file = dir.path().join("notes.txt");
+    std::fs::write(&file, "one").unwrap();
+    std::fs::write(&file, "two").unwrap();
+    std::fs::write(dir.path().join("ignored.md"), "x").unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("file event")
+        .unwrap();
+    assert_eq!(event.task_id, task.id);
+    assert_eq!(event.variables["path"], file.display().to_string());
+
+    // Both writes were debounced into one firing
+    let again = tokio::time::timeout(Duration::from_millis(500), rx.recv()).await;
+    assert!(again.is_err());
+
+    watchers.sync(&[]);
+    assert!(watchers.watchers.is_empty());
+}
+
+#[tokio::test]
+async fn test_unwatchable_trigger_is_retried() {
+    let dir = TempDir::new().unwrap();
+    let inbox = dir.path().join("inbox");
+    let task = ScheduledTask::new(
+        "on_inbox",
+        TriggerType::file(inbox.join("*.txt").to_str().unwrap()),
+        TaskAction::natural_language("Summarize {{path}}"),
+    );
+
+    let (tx, _rx) = mpsc::unbounded_channel();
+    let mut watchers = FileWatchers::new(tx);
+    watchers.sync(std::slice::from_ref(&task));
+    assert!(watchers.watchers.is_empty());
+    assert!(watchers.failed.contains_key(&task.id));
+
+    // The directory shows up later: the next sync picks the trigger up
+    std::fs::create_dir(&inbox).unwrap();
+    watchers.sync(std::slice::from_ref(&task));
+    assert!(watchers.watchers.contains_key(&task.id));
+    assert!(watchers.failed.is_empty());
+}
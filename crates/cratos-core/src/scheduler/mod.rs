@@ -3,12 +3,28 @@
 //! This module provides a comprehensive task scheduling system for Cratos,
 //! enabling automated execution of tasks based on various triggers:
 //!
-//! - **Cron triggers**: Time-based scheduling using cron expressions
+//! - **Cron triggers**: Time-based scheduling using cron expressions in any
+//!   IANA timezone
 //! - **Interval triggers**: Fixed-interval repeating tasks
 //! - **One-time triggers**: Single execution at a specific time
-//! - **File triggers**: React to file system changes
+//! - **File triggers**: React to file system changes (glob paths, debounced)
 //! - **System triggers**: React to system resource thresholds
 //!
+//! File and system triggers pass the triggering event to the action as
+//! `{{name}}` template variables (`{{path}}`, `{{event}}`, `{{metric}}`,
+//! `{{value}}`, `{{threshold}}`).
+//!
+//! Each task also has a misfire policy (how to catch up on runs missed while
+//! the scheduler was down), an overlap policy (what to do when it fires while
+//! still running) and an optional jitter that spreads its scheduled times.
+//! Every run, including skipped and cancelled ones, is kept as a
+//! [`TaskExecution`] in the run history.
+//!
+//! A [`TaskAction::Chain`] runs several actions in order, each optionally
+//! conditioned on the previous output (regex, JSON path comparison or an LLM
+//! yes/no classification), e.g. to check a status page and notify only when
+//! something is down.
+//!
 //! # Architecture
 //!
 //! ```text
@@ -61,12 +77,22 @@
 //! engine.run(shutdown_token).await?;
 //! ```
 
+mod chain;
+mod cron;
 mod engine;
+mod file_watch;
+mod policy;
 mod store;
+mod system_monitor;
 mod triggers;
 mod types;
 
-pub use engine::{SchedulerConfig, SchedulerEngine, SchedulerEngineBuilder, TaskExecutor};
+pub use chain::{ChainStep, JsonOp, StepCondition, MAX_CHAIN_STEPS};
+pub use cron::CronSchedule;
+pub use engine::{
+    RunDispatch, SchedulerConfig, SchedulerEngine, SchedulerEngineBuilder, TaskExecutor,
+};
+pub use policy::{MisfirePolicy, OverlapPolicy, MAX_JITTER_SECS};
 pub use store::SchedulerStore;
 pub use triggers::{
     Comparison, CronTrigger, FileEvent, FileTrigger, IntervalTrigger, OneTimeTrigger, SystemMetric,
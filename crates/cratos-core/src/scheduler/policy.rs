@@ -0,0 +1,132 @@
+//! Misfire and overlap policies
+//!
+//! A run is *misfired* when the scheduler was not running (or was busy) at
+//! its scheduled time and notices it later than the misfire grace period.
+//! The task's [`MisfirePolicy`] decides how many of those runs to catch up.
+//! Its [`OverlapPolicy`] decides what happens when a task fires while its
+//! previous run is still in progress.
+
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::triggers::TriggerType;
+
+/// Upper bound on the missed runs counted for one task, so a short interval
+/// after a long downtime does not walk millions of occurrences
+const MAX_MISSED_SCAN: usize = 10_000;
+
+/// Upper bound on the jitter added to a scheduled time (one day)
+pub const MAX_JITTER_SECS: u64 = 86_400;
+
+/// What to do with runs missed while the scheduler was down
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MisfirePolicy {
+    /// Drop missed runs and wait for the next scheduled time
+    Skip,
+    /// Run once for any number of missed runs
+    #[default]
+    RunOnce,
+    /// Run every missed run, up to `max_runs`
+    RunAll {
+        /// Maximum catch-up runs
+        max_runs: u32,
+    },
+}
+
+impl MisfirePolicy {
+    /// Number of runs to execute for `missed` misfired runs, plus one if a
+    /// run is due within the grace period
+    fn runs(self, missed: usize, on_time: bool) -> u32 {
+        let missed = u32::try_from(missed).unwrap_or(u32::MAX);
+        let on_time = u32::from(on_time);
+        match self {
+            MisfirePolicy::Skip => on_time,
+            MisfirePolicy::RunOnce => u32::from(missed > 0).max(on_time),
+            MisfirePolicy::RunAll { max_runs } => missed.min(max_runs) + on_time,
+        }
+    }
+}
+
+/// What to do when a task fires while its previous run is still in progress
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapPolicy {
+    /// Drop the new run (recorded as `skipped`)
+    #[default]
+    Skip,
+    /// Run after the previous run finishes
+    Queue,
+    /// Cancel the previous run (recorded as `cancelled`) and start the new one
+    CancelPrevious,
+}
+
+impl OverlapPolicy {
+    /// Name used in the API and logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverlapPolicy::Skip => "skip",
+            OverlapPolicy::Queue => "queue",
+            OverlapPolicy::CancelPrevious => "cancel_previous",
+        }
+    }
+}
+
+/// Runs of a due task and its next scheduled time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct CatchUp {
+    /// Runs to execute now
+    pub runs: u32,
+    /// Scheduled times older than the grace period
+    pub missed: usize,
+    /// Next scheduled time after `now` (before jitter)
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+/// Plan the runs of a task scheduled at `scheduled` (<= `now`)
+pub(super) fn plan_catch_up(
+    trigger: &TriggerType,
+    policy: MisfirePolicy,
+    scheduled: DateTime<Utc>,
+    now: DateTime<Utc>,
+    grace: Duration,
+) -> CatchUp {
+    let mut missed = 0;
+    let mut on_time = false;
+    let mut next = Some(scheduled);
+    for _ in 0..MAX_MISSED_SCAN {
+        let Some(at) = next.filter(|at| *at <= now) else {
+            break;
+        };
+        if now - at > grace {
+            missed += 1;
+        } else {
+            on_time = true;
+        }
+        next = trigger.next_run_after(at);
+    }
+    // Scan limit reached: resume from now
+    if next.is_some_and(|at| at <= now) {
+        next = trigger.next_run_after(now).filter(|at| *at > now);
+    }
+
+    CatchUp {
+        runs: policy.runs(missed, on_time),
+        missed,
+        next_run_at: next,
+    }
+}
+
+/// Delay `at` by a random 0..=`jitter_secs` seconds (at most [`MAX_JITTER_SECS`])
+pub(super) fn apply_jitter(at: DateTime<Utc>, jitter_secs: u64) -> DateTime<Utc> {
+    let jitter_secs = jitter_secs.min(MAX_JITTER_SECS);
+    if jitter_secs == 0 {
+        return at;
+    }
+    let delay = rand::thread_rng().gen_range(0..=jitter_secs);
+    at + Duration::seconds(delay as i64)
+}
+
+#[cfg(test)]
+mod tests;
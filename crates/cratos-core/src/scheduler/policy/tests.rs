@@ -0,0 +1,109 @@
+use super::*;
+use chrono::TimeZone;
+
+fn utc(h: u32, mi: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 2, h, mi, 0).unwrap()
+}
+
+fn grace() -> Duration {
+    Duration::seconds(120)
+}
+
+#[test]
+fn test_on_time_run() {
+    let trigger = TriggerType::interval(3600);
+    let plan = plan_catch_up(
+        &trigger,
+        MisfirePolicy::Skip,
+        utc(10, 0),
+        utc(10, 1),
+        grace(),
+    );
+    assert_eq!(
+        plan,
+        CatchUp {
+            runs: 1,
+            missed: 0,
+            next_run_at: Some(utc(11, 0)),
+        }
+    );
+}
+
+#[test]
+fn test_misfire_policies() {
+    // Hourly task, scheduler down from 09:30 to 13:30: 10:00-13:00 missed
+    let trigger = TriggerType::cron("0 * * * *");
+    let plan = |policy| plan_catch_up(&trigger, policy, utc(10, 0), utc(13, 30), grace());
+
+    let skip = plan(MisfirePolicy::Skip);
+    assert_eq!(skip.runs, 0);
+    assert_eq!(skip.missed, 4);
+    assert_eq!(skip.next_run_at, Some(utc(14, 0)));
+
+    assert_eq!(plan(MisfirePolicy::RunOnce).runs, 1);
+    assert_eq!(plan(MisfirePolicy::RunAll { max_runs: 10 }).runs, 4);
+    assert_eq!(plan(MisfirePolicy::RunAll { max_runs: 2 }).runs, 2);
+}
+
+#[test]
+fn test_missed_and_on_time() {
+    // 12:00 was missed, 13:00 is within the grace period
+    let trigger = TriggerType::cron("0 * * * *");
+    let plan = |policy| plan_catch_up(&trigger, policy, utc(12, 0), utc(13, 1), grace());
+
+    assert_eq!(plan(MisfirePolicy::Skip).runs, 1);
+    assert_eq!(plan(MisfirePolicy::RunOnce).runs, 1);
+    assert_eq!(plan(MisfirePolicy::RunAll { max_runs: 5 }).runs, 2);
+}
+
+#[test]
+fn test_missed_one_time() {
+    let trigger = TriggerType::one_time(utc(9, 0));
+    let plan = plan_catch_up(
+        &trigger,
+        MisfirePolicy::Skip,
+        utc(9, 0),
+        utc(12, 0),
+        grace(),
+    );
+    assert_eq!(plan.runs, 0);
+    assert_eq!(plan.next_run_at, None);
+}
+
+#[test]
+fn test_scan_limit() {
+    // One-second interval after a week of downtime
+    let trigger = TriggerType::interval(1);
+    let now = utc(12, 0);
+    let plan = plan_catch_up(
+        &trigger,
+        MisfirePolicy::RunAll { max_runs: 3 },
+        now - Duration::days(7),
+        now,
+        grace(),
+    );
+    assert_eq!(plan.runs, 3);
+    assert_eq!(plan.missed, MAX_MISSED_SCAN);
+    assert_eq!(plan.next_run_at, Some(now + Duration::seconds(1)));
+}
+
+#[test]
+fn test_jitter_bounds() {
+    let at = utc(10, 0);
+    assert_eq!(apply_jitter(at, 0), at);
+    for _ in 0..50 {
+        let jittered = apply_jitter(at, 30);
+        assert!(jittered >= at && jittered <= at + Duration::seconds(30));
+    }
+}
+
+#[test]
+fn test_policy_serde() {
+    let policy: MisfirePolicy =
+        serde_json::from_value(serde_json::json!({"type": "run_all", "max_runs": 5})).unwrap();
+    assert_eq!(policy, MisfirePolicy::RunAll { max_runs: 5 });
+
+    let overlap: OverlapPolicy = serde_json::from_str("\"cancel_previous\"").unwrap();
+    assert_eq!(overlap, OverlapPolicy::CancelPrevious);
+    assert_eq!(overlap.as_str(), "cancel_previous");
+}
@@ -48,6 +48,28 @@ impl SchedulerStore {
         .await
         .map_err(|e| SchedulerError::Transaction(format!("Migration failed (task_executions): {}", e)))?;
 
+        // Task policies (added after the initial schema)
+        for (column, definition) in [
+            ("misfire_json", r#"TEXT NOT NULL DEFAULT '{"type":"run_once"}'"#),
+            ("overlap_policy", "TEXT NOT NULL DEFAULT 'skip'"),
+            ("jitter_secs", "INTEGER NOT NULL DEFAULT 0"),
+            ("fire_at", "TIMESTAMP"),
+        ] {
+            let exists: bool = sqlx::query_scalar::<_, i32>(
+                "SELECT COUNT(*) FROM pragma_table_info('scheduled_tasks') WHERE name = ?",
+            )
+            .bind(column)
+            .fetch_one(&mut *tx)
+            .await?
+                > 0;
+            if !exists {
+                sqlx::query(&format!("ALTER TABLE scheduled_tasks ADD COLUMN {column} {definition}"))
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| SchedulerError::Transaction(format!("Migration failed ({}): {}", column, e)))?;
+            }
+        }
+
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_tasks_enabled ON scheduled_tasks(enabled)")
             .execute(&mut *tx)
             .await
@@ -8,14 +8,16 @@ impl SchedulerStore {
     pub async fn create_task(&self, task: &ScheduledTask) -> Result<()> {
         let trigger_json = serde_json::to_string(&task.trigger)?;
         let action_json = serde_json::to_string(&task.action)?;
+        let misfire_json = serde_json::to_string(&task.misfire_policy)?;
 
         sqlx::query(
             r#"
             INSERT INTO scheduled_tasks (
                 id, name, description, trigger_json, action_json,
                 enabled, priority, max_retries, created_at, updated_at,
-                last_run_at, next_run_at, run_count, failure_count
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                last_run_at, next_run_at, run_count, failure_count,
+                misfire_json, overlap_policy, jitter_secs, fire_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(task.id.to_string())
@@ -32,6 +34,10 @@ impl SchedulerStore {
         .bind(task.next_run_at)
         .bind(task.run_count)
         .bind(task.failure_count)
+        .bind(misfire_json)
+        .bind(task.overlap_policy.as_str())
+        .bind(task.jitter_secs as i64)
+        .bind(task.fire_at)
         .execute(&self.pool)
         .await?;
 
@@ -53,13 +59,15 @@ impl SchedulerStore {
     pub async fn update_task(&self, task: &ScheduledTask) -> Result<()> {
         let trigger_json = serde_json::to_string(&task.trigger)?;
         let action_json = serde_json::to_string(&task.action)?;
+        let misfire_json = serde_json::to_string(&task.misfire_policy)?;
 
         let result = sqlx::query(
             r#"
             UPDATE scheduled_tasks SET
                 name = ?, description = ?, trigger_json = ?, action_json = ?,
                 enabled = ?, priority = ?, max_retries = ?, updated_at = ?,
-                last_run_at = ?, next_run_at = ?, run_count = ?, failure_count = ?
+                last_run_at = ?, next_run_at = ?, run_count = ?, failure_count = ?,
+                misfire_json = ?, overlap_policy = ?, jitter_secs = ?, fire_at = ?
             WHERE id = ?
             "#,
         )
@@ -75,6 +83,10 @@ impl SchedulerStore {
         .bind(task.next_run_at)
         .bind(task.run_count)
         .bind(task.failure_count)
+        .bind(misfire_json)
+        .bind(task.overlap_policy.as_str())
+        .bind(task.jitter_secs as i64)
+        .bind(task.fire_at)
         .bind(task.id.to_string())
         .execute(&self.pool)
         .await?;
@@ -126,7 +138,8 @@ impl SchedulerStore {
         let rows: Vec<TaskRow> = sqlx::query_as(
             r#"
             SELECT * FROM scheduled_tasks
-            WHERE enabled = TRUE AND next_run_at IS NOT NULL AND next_run_at <= ?
+            WHERE enabled = TRUE AND next_run_at IS NOT NULL
+                AND COALESCE(fire_at, next_run_at) <= ?
             ORDER BY priority DESC, next_run_at ASC
             "#,
         )
@@ -182,6 +195,28 @@ impl SchedulerStore {
         Ok(())
     }
 
+    /// Record a run that did not execute (e.g. skipped because the task was still running)
+    pub async fn record_execution_skipped(&self, task_id: Uuid, reason: &str) -> Result<Uuid> {
+        let execution_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO task_executions (id, task_id, started_at, finished_at, status, result, attempt)
+            VALUES (?, ?, ?, ?, 'skipped', ?, 1)
+            "#,
+        )
+        .bind(execution_id.to_string())
+        .bind(task_id.to_string())
+        .bind(now)
+        .bind(now)
+        .bind(reason)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(execution_id)
+    }
+
     /// Get recent executions for a task
     pub async fn get_task_executions(
         &self,
@@ -205,6 +240,53 @@ impl SchedulerStore {
         rows.into_iter().map(|r| r.try_into()).collect()
     }
 
+    /// Set the next scheduled run of a task and the time it fires at
+    pub async fn set_next_run(
+        &self,
+        id: Uuid,
+        next_run_at: Option<DateTime<Utc>>,
+        fire_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE scheduled_tasks SET next_run_at = ?, fire_at = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(next_run_at)
+        .bind(fire_at)
+        .bind(Utc::now())
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Update task run statistics, leaving the next scheduled run unchanged
+    pub async fn record_run_stats(
+        &self,
+        id: Uuid,
+        last_run_at: DateTime<Utc>,
+        success: bool,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE scheduled_tasks SET
+                last_run_at = ?,
+                run_count = run_count + 1,
+                failure_count = failure_count + ?,
+                updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(last_run_at)
+        .bind(i64::from(!success))
+        .bind(Utc::now())
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Update task run statistics
     pub async fn update_task_stats(
         &self,
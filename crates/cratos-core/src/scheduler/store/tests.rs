@@ -1,6 +1,7 @@
     use super::*;
     use crate::scheduler::triggers::TriggerType;
     use crate::scheduler::types::{ScheduledTask, TaskAction};
+    use crate::scheduler::{MisfirePolicy, OverlapPolicy};
     use tempfile::TempDir;
 
     struct TestContext {
@@ -126,6 +127,88 @@
         assert_eq!(executions[0].result, Some("Completed".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_task_policies_roundtrip() {
+        let ctx = create_test_context().await;
+        let store = &ctx.store;
+
+        let task = ScheduledTask::new(
+            "policy_test",
+            TriggerType::cron("0 * * * *"),
+            TaskAction::natural_language("Hello"),
+        )
+        .with_misfire_policy(MisfirePolicy::RunAll { max_runs: 3 })
+        .with_overlap_policy(OverlapPolicy::CancelPrevious)
+        .with_jitter(30);
+        store.create_task(&task).await.unwrap();
+
+        let retrieved = store.get_task(task.id).await.unwrap();
+        assert_eq!(
+            retrieved.misfire_policy,
+            MisfirePolicy::RunAll { max_runs: 3 }
+        );
+        assert_eq!(retrieved.overlap_policy, OverlapPolicy::CancelPrevious);
+        assert_eq!(retrieved.jitter_secs, 30);
+
+        store.record_execution_skipped(task.id, "busy").await.unwrap();
+        let executions = store.get_task_executions(task.id, 10).await.unwrap();
+        assert_eq!(executions[0].status, "skipped");
+        assert!(executions[0].finished_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_migrates_tasks_without_policies() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("old_scheduler.db");
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .connect(&format!("sqlite:{}?mode=rwc", path.display()))
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"
+            CREATE TABLE scheduled_tasks (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                description TEXT,
+                trigger_json TEXT NOT NULL,
+                action_json TEXT NOT NULL,
+                enabled BOOLEAN NOT NULL DEFAULT TRUE,
+                priority INTEGER NOT NULL DEFAULT 0,
+                max_retries INTEGER NOT NULL DEFAULT 3,
+                created_at TIMESTAMP NOT NULL,
+                updated_at TIMESTAMP NOT NULL,
+                last_run_at TIMESTAMP,
+                next_run_at TIMESTAMP,
+                run_count INTEGER NOT NULL DEFAULT 0,
+                failure_count INTEGER NOT NULL DEFAULT 0
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let id = uuid::Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO scheduled_tasks (id, name, trigger_json, action_json, created_at, updated_at)
+            VALUES (?, 'old', '{"type":"interval","seconds":60}', '{"type":"natural_language","prompt":"Hi","channel":null}', ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(chrono::Utc::now())
+        .bind(chrono::Utc::now())
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool.close().await;
+
+        let store = SchedulerStore::from_path(&path).await.unwrap();
+        let task = store.get_task(id).await.unwrap();
+        assert_eq!(task.misfire_policy, MisfirePolicy::RunOnce);
+        assert_eq!(task.overlap_policy, OverlapPolicy::Skip);
+        assert_eq!(task.jitter_secs, 0);
+    }
+
     #[test]
     fn test_task_action_natural_language() {
         let action = TaskAction::natural_language("Test prompt");
@@ -202,10 +285,11 @@
     fn test_task_action_shell() {
         let action = TaskAction::Shell {
             command: "ls -la".to_string(),
+            args: Vec::new(),
             cwd: Some("/tmp".to_string()),
         };
         match action {
-            TaskAction::Shell { command, cwd } => {
+            TaskAction::Shell { command, cwd, .. } => {
                 assert_eq!(command, "ls -la");
                 assert_eq!(cwd, Some("/tmp".to_string()));
             }
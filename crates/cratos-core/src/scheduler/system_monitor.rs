@@ -0,0 +1,251 @@
+//! System metric triggers
+//!
+//! Samples CPU, memory, disk and network usage and fires system triggers
+//! whose threshold condition held for the trigger's duration. After firing,
+//! a trigger re-arms only once the value has crossed back past the threshold
+//! by the hysteresis margin, and never fires again within its cooldown, so a
+//! metric hovering around the threshold does not fire on every sample.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use sysinfo::{Disks, Networks, System};
+use tracing::debug;
+use uuid::Uuid;
+
+use super::triggers::{Comparison, SystemMetric, SystemTrigger, TriggerEvent, TriggerType};
+use super::types::ScheduledTask;
+
+/// Default hysteresis margin, as a fraction of the threshold
+const DEFAULT_HYSTERESIS_FRACTION: f32 = 0.05;
+
+/// One sample of every metric
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct MetricSample {
+    /// CPU usage percentage
+    pub cpu_usage: f32,
+    /// Memory usage percentage
+    pub memory_usage: f32,
+    /// Usage percentage of the fullest disk
+    pub disk_usage: f32,
+    /// Bytes received per second over all interfaces
+    pub network_rx: f32,
+    /// Bytes sent per second over all interfaces
+    pub network_tx: f32,
+}
+
+impl MetricSample {
+    fn value(&self, metric: SystemMetric) -> f32 {
+        match metric {
+            SystemMetric::CpuUsage => self.cpu_usage,
+            SystemMetric::MemoryUsage => self.memory_usage,
+            SystemMetric::DiskUsage => self.disk_usage,
+            SystemMetric::NetworkRx => self.network_rx,
+            SystemMetric::NetworkTx => self.network_tx,
+        }
+    }
+}
+
+/// Reads system metrics
+struct MetricSampler {
+    system: System,
+    disks: Disks,
+    networks: Networks,
+    networks_refreshed: Instant,
+}
+
+impl MetricSampler {
+    fn new() -> Self {
+        let mut system = System::new();
+        // CPU usage is measured between two refreshes
+        system.refresh_cpu_usage();
+        Self {
+            system,
+            disks: Disks::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+            networks_refreshed: Instant::now(),
+        }
+    }
+
+    fn sample(&mut self) -> MetricSample {
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        self.disks.refresh(true);
+        self.networks.refresh(true);
+
+        let elapsed = self.networks_refreshed.elapsed().as_secs_f32().max(1.0);
+        self.networks_refreshed = Instant::now();
+        let (rx, tx) = self.networks.values().fold((0u64, 0u64), |(rx, tx), data| {
+            (rx + data.received(), tx + data.transmitted())
+        });
+
+        MetricSample {
+            cpu_usage: self.system.global_cpu_usage(),
+            memory_usage: percent(self.system.used_memory(), self.system.total_memory()),
+            disk_usage: self
+                .disks
+                .list()
+                .iter()
+                .map(|disk| {
+                    let used = disk.total_space().saturating_sub(disk.available_space());
+                    percent(used, disk.total_space())
+                })
+                .fold(0.0, f32::max),
+            network_rx: rx as f32 / elapsed,
+            network_tx: tx as f32 / elapsed,
+        }
+    }
+}
+
+fn percent(used: u64, total: u64) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        (used as f64 / total as f64 * 100.0) as f32
+    }
+}
+
+/// Threshold state of one system trigger
+#[derive(Debug)]
+pub(super) struct ThresholdMonitor {
+    trigger: SystemTrigger,
+    /// When the condition started to hold continuously
+    breached_since: Option<Instant>,
+    /// False after firing, until the value crosses back past the hysteresis margin
+    armed: bool,
+    last_fired: Option<Instant>,
+}
+
+impl ThresholdMonitor {
+    pub(super) fn new(trigger: SystemTrigger) -> Self {
+        Self {
+            trigger,
+            breached_since: None,
+            armed: true,
+            last_fired: None,
+        }
+    }
+
+    /// Feed one observation; returns true if the trigger fires
+    pub(super) fn observe(&mut self, value: f32, now: Instant) -> bool {
+        if !self.armed {
+            if !self.cleared(value) {
+                return false;
+            }
+            self.armed = true;
+        }
+
+        if !self.trigger.comparison.check(value, self.trigger.threshold) {
+            self.breached_since = None;
+            return false;
+        }
+        let since = *self.breached_since.get_or_insert(now);
+        if now.duration_since(since) < Duration::from_secs(self.trigger.duration_secs) {
+            return false;
+        }
+        let cooldown = Duration::from_secs(self.trigger.cooldown_secs);
+        if self
+            .last_fired
+            .is_some_and(|last| now.duration_since(last) < cooldown)
+        {
+            return false;
+        }
+
+        self.armed = false;
+        self.breached_since = None;
+        self.last_fired = Some(now);
+        true
+    }
+
+    /// Whether the value is back on the safe side of the threshold
+    fn cleared(&self, value: f32) -> bool {
+        let threshold = self.trigger.threshold;
+        let margin = self
+            .trigger
+            .hysteresis
+            .unwrap_or(threshold.abs() * DEFAULT_HYSTERESIS_FRACTION);
+        match self.trigger.comparison {
+            Comparison::GreaterThan => value <= threshold - margin,
+            Comparison::LessThan => value >= threshold + margin,
+            Comparison::Equal => {
+                !Comparison::Equal.check(value, threshold) && (value - threshold).abs() >= margin
+            }
+        }
+    }
+}
+
+/// Monitors of all enabled system triggers
+pub(super) struct SystemMonitors {
+    monitors: HashMap<Uuid, ThresholdMonitor>,
+    /// Created when the first system trigger is registered
+    sampler: Option<MetricSampler>,
+}
+
+impl SystemMonitors {
+    pub(super) fn new() -> Self {
+        Self {
+            monitors: HashMap::new(),
+            sampler: None,
+        }
+    }
+
+    /// Monitor the system triggers of `tasks` (the enabled tasks), dropping others
+    pub(super) fn sync(&mut self, tasks: &[ScheduledTask]) {
+        let mut wanted = HashMap::new();
+        for task in tasks {
+            if let TriggerType::System(trigger) = &task.trigger {
+                wanted.insert(task.id, trigger);
+            }
+        }
+
+        self.monitors.retain(|id, monitor| {
+            wanted
+                .get(id)
+                .is_some_and(|trigger| **trigger == monitor.trigger)
+        });
+        for (id, trigger) in wanted {
+            self.monitors.entry(id).or_insert_with(|| {
+                debug!(task_id = %id, metric = trigger.metric.as_str(), "Monitoring system metric");
+                ThresholdMonitor::new(trigger.clone())
+            });
+        }
+
+        if self.monitors.is_empty() {
+            self.sampler = None;
+        } else if self.sampler.is_none() {
+            self.sampler = Some(MetricSampler::new());
+        }
+    }
+
+    /// Sample the metrics and return the triggers that fire
+    pub(super) fn poll(&mut self) -> Vec<TriggerEvent> {
+        let Some(sampler) = self.sampler.as_mut() else {
+            return Vec::new();
+        };
+        let sample = sampler.sample();
+        self.evaluate(&sample, Instant::now())
+    }
+
+    fn evaluate(&mut self, sample: &MetricSample, now: Instant) -> Vec<TriggerEvent> {
+        self.monitors
+            .iter_mut()
+            .filter_map(|(id, monitor)| {
+                let metric = monitor.trigger.metric;
+                let value = sample.value(metric);
+                monitor.observe(value, now).then(|| TriggerEvent {
+                    task_id: *id,
+                    variables: HashMap::from([
+                        ("metric".to_string(), metric.as_str().to_string()),
+                        ("value".to_string(), format!("{value:.1}")),
+                        (
+                            "threshold".to_string(),
+                            format!("{:.1}", monitor.trigger.threshold),
+                        ),
+                    ]),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,100 @@
+use super::*;
+use crate::scheduler::TaskAction;
+
+fn trigger(comparison: Comparison, duration_secs: u64, cooldown_secs: u64) -> SystemTrigger {
+    SystemTrigger {
+        metric: SystemMetric::CpuUsage,
+        threshold: 80.0,
+        comparison,
+        duration_secs,
+        cooldown_secs,
+        hysteresis: None,
+    }
+}
+
+fn secs(start: Instant, n: u64) -> Instant {
+    start + Duration::from_secs(n)
+}
+
+#[test]
+fn test_fires_after_duration() {
+    let start = Instant::now();
+    let mut monitor = ThresholdMonitor::new(trigger(Comparison::GreaterThan, 60, 0));
+
+    assert!(!monitor.observe(90.0, start));
+    assert!(!monitor.observe(90.0, secs(start, 30)));
+    assert!(monitor.observe(95.0, secs(start, 60)));
+}
+
+#[test]
+fn test_dip_resets_duration() {
+    let start = Instant::now();
+    let mut monitor = ThresholdMonitor::new(trigger(Comparison::GreaterThan, 60, 0));
+
+    assert!(!monitor.observe(90.0, start));
+    assert!(!monitor.observe(50.0, secs(start, 30)));
+    assert!(!monitor.observe(90.0, secs(start, 60)));
+    assert!(monitor.observe(90.0, secs(start, 120)));
+}
+
+#[test]
+fn test_hysteresis_rearms_below_margin() {
+    let start = Instant::now();
+    let mut monitor = ThresholdMonitor::new(trigger(Comparison::GreaterThan, 0, 0));
+
+    assert!(monitor.observe(85.0, start));
+    // Still breached: fires once
+    assert!(!monitor.observe(85.0, secs(start, 10)));
+    // Dips below the threshold but not past the 5% margin (76)
+    assert!(!monitor.observe(78.0, secs(start, 20)));
+    assert!(!monitor.observe(85.0, secs(start, 30)));
+    // Clears the margin, then breaches again
+    assert!(!monitor.observe(70.0, secs(start, 40)));
+    assert!(monitor.observe(85.0, secs(start, 50)));
+}
+
+#[test]
+fn test_cooldown() {
+    let start = Instant::now();
+    let mut monitor = ThresholdMonitor::new(trigger(Comparison::LessThan, 0, 300));
+
+    assert!(monitor.observe(10.0, start));
+    assert!(!monitor.observe(90.0, secs(start, 10)));
+    // Re-armed, but within the cooldown
+    assert!(!monitor.observe(10.0, secs(start, 20)));
+    assert!(monitor.observe(10.0, secs(start, 300)));
+}
+
+#[test]
+fn test_sync_and_event_variables() {
+    let task = ScheduledTask::new(
+        "cpu_alert",
+        TriggerType::cpu_threshold(80.0),
+        TaskAction::natural_language("CPU at {{value}}%"),
+    );
+    let other = ScheduledTask::new(
+        "hourly",
+        TriggerType::interval(3600),
+        TaskAction::natural_language("Hourly"),
+    );
+    let mut monitors = SystemMonitors::new();
+    monitors.sync(&[task.clone(), other]);
+    assert_eq!(monitors.monitors.len(), 1);
+
+    let start = Instant::now();
+    let sample = MetricSample {
+        cpu_usage: 93.26,
+        ..MetricSample::default()
+    };
+    assert!(monitors.evaluate(&sample, start).is_empty());
+    let events = monitors.evaluate(&sample, secs(start, 60));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].task_id, task.id);
+    assert_eq!(events[0].variables["metric"], "cpu_usage");
+    assert_eq!(events[0].variables["value"], "93.3");
+    assert_eq!(events[0].variables["threshold"], "80.0");
+
+    monitors.sync(&[]);
+    assert!(monitors.monitors.is_empty());
+    assert!(monitors.sampler.is_none());
+}
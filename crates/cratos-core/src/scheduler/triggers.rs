@@ -7,8 +7,15 @@
 //! - File: triggered by file system changes
 //! - System: triggered by system resource thresholds
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::cron::CronSchedule;
+use super::file_watch::WatchSpec;
+use super::types::Result;
 
 /// Trigger types for scheduled tasks
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +42,14 @@ impl TriggerType {
         })
     }
 
+    /// Create a new cron trigger evaluated in an IANA timezone (e.g. "Asia/Seoul")
+    pub fn cron_in(expression: impl Into<String>, timezone: impl Into<String>) -> Self {
+        Self::Cron(CronTrigger {
+            expression: expression.into(),
+            timezone: Some(timezone.into()),
+        })
+    }
+
     /// Create a new interval trigger
     pub fn interval(seconds: u64) -> Self {
         Self::Interval(IntervalTrigger {
@@ -64,6 +79,8 @@ impl TriggerType {
             threshold: threshold_percent,
             comparison: Comparison::GreaterThan,
             duration_secs: 60,
+            cooldown_secs: default_cooldown(),
+            hysteresis: None,
         })
     }
 
@@ -74,6 +91,8 @@ impl TriggerType {
             threshold: threshold_percent,
             comparison: Comparison::GreaterThan,
             duration_secs: 60,
+            cooldown_secs: default_cooldown(),
+            hysteresis: None,
         })
     }
 
@@ -84,25 +103,66 @@ impl TriggerType {
             threshold: threshold_percent,
             comparison: Comparison::GreaterThan,
             duration_secs: 0,
+            cooldown_secs: default_cooldown(),
+            hysteresis: None,
         })
     }
+
+    /// Check that the trigger can be scheduled (e.g. the cron expression or
+    /// path pattern parses)
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            TriggerType::Cron(cron) => cron.schedule().map(|_| ()),
+            TriggerType::File(file) => WatchSpec::parse(&file.path).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Next run time strictly after `from`
+    ///
+    /// Event-driven triggers (file, system) and passed one-time triggers
+    /// have no next run time.
+    pub fn next_run_after(&self, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            TriggerType::Cron(cron) => match cron.schedule() {
+                Ok(schedule) => schedule.next_after(from),
+                Err(e) => {
+                    warn!("Invalid cron trigger: {}", e);
+                    None
+                }
+            },
+            TriggerType::Interval(IntervalTrigger { seconds, .. }) => {
+                Some(from + Duration::seconds(*seconds as i64))
+            }
+            TriggerType::OneTime(one_time) => (one_time.at > from).then_some(one_time.at),
+            TriggerType::File(_) | TriggerType::System(_) => None,
+        }
+    }
 }
 
 /// Cron-based trigger
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CronTrigger {
     /// Cron expression (5 or 6 fields)
-    /// Format: "minute hour day month weekday [year]"
+    /// Format: "[second] minute hour day month weekday"
     /// Examples:
     ///   "0 9 * * *" - Every day at 9:00 AM
     ///   "*/15 * * * *" - Every 15 minutes
     ///   "0 0 * * 1" - Every Monday at midnight
+    ///   "0 18 L * *" - Last day of every month at 6:00 PM
     pub expression: String,
-    /// Optional timezone (default: UTC)
+    /// Optional IANA timezone, e.g. "Asia/Seoul" (default: UTC)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timezone: Option<String>,
 }
 
+impl CronTrigger {
+    /// Parse the expression in the trigger's timezone
+    pub fn schedule(&self) -> Result<CronSchedule> {
+        CronSchedule::parse(&self.expression, self.timezone.as_deref())
+    }
+}
+
 /// Interval-based trigger
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntervalTrigger {
@@ -121,9 +181,11 @@ pub struct OneTimeTrigger {
 }
 
 /// File system trigger
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Fires with the template variables `{{path}}` and `{{event}}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FileTrigger {
-    /// Path to watch (supports glob patterns)
+    /// Path to watch (supports glob patterns, e.g. "/data/inbox/**/*.csv")
     pub path: String,
     /// Events to watch for
     #[serde(default = "default_file_events")]
@@ -155,8 +217,23 @@ pub enum FileEvent {
     Renamed,
 }
 
+impl FileEvent {
+    /// Name used in configs and template variables
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileEvent::Created => "created",
+            FileEvent::Modified => "modified",
+            FileEvent::Deleted => "deleted",
+            FileEvent::Renamed => "renamed",
+        }
+    }
+}
+
 /// System resource trigger
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Fires with the template variables `{{metric}}`, `{{value}}` and
+/// `{{threshold}}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SystemTrigger {
     /// Metric to monitor
     pub metric: SystemMetric,
@@ -167,12 +244,23 @@ pub struct SystemTrigger {
     /// Duration in seconds the condition must be true
     #[serde(default = "default_duration")]
     pub duration_secs: u64,
+    /// Minimum seconds between two firings
+    #[serde(default = "default_cooldown")]
+    pub cooldown_secs: u64,
+    /// How far the value must cross back past the threshold before the
+    /// trigger can fire again (default: 5% of the threshold)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hysteresis: Option<f32>,
 }
 
 fn default_duration() -> u64 {
     60
 }
 
+fn default_cooldown() -> u64 {
+    300
+}
+
 /// System metrics
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -189,6 +277,19 @@ pub enum SystemMetric {
     NetworkTx,
 }
 
+impl SystemMetric {
+    /// Name used in configs and template variables
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SystemMetric::CpuUsage => "cpu_usage",
+            SystemMetric::MemoryUsage => "memory_usage",
+            SystemMetric::DiskUsage => "disk_usage",
+            SystemMetric::NetworkRx => "network_rx",
+            SystemMetric::NetworkTx => "network_tx",
+        }
+    }
+}
+
 /// Comparison operators
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -213,6 +314,15 @@ impl Comparison {
     }
 }
 
+/// Firing of an event-driven (file or system) trigger
+#[derive(Debug, Clone)]
+pub(super) struct TriggerEvent {
+    /// Task whose trigger fired
+    pub task_id: Uuid,
+    /// Template variables describing the event
+    pub variables: HashMap<String, String>,
+}
+
 #[cfg(test)]
 mod tests;
 
@@ -67,6 +67,20 @@
         assert!(!Comparison::Equal.check(81.0, 80.0));
     }
 
+    #[test]
+    fn test_next_run_after() {
+        let from = Utc::now();
+        let next = TriggerType::cron_in("0 9 * * *", "Asia/Seoul")
+            .next_run_after(from)
+            .unwrap();
+        assert!(next > from);
+        assert!((next - from).num_hours() < 24);
+
+        assert!(TriggerType::one_time(from).next_run_after(from).is_none());
+        assert!(TriggerType::file("/tmp/x").next_run_after(from).is_none());
+        assert!(TriggerType::cron("not a cron").validate().is_err());
+    }
+
     #[test]
     fn test_trigger_serialization() {
         let trigger = TriggerType::cron("0 9 * * *");
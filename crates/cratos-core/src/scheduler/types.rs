@@ -3,11 +3,16 @@
 //! Contains the core types used by the scheduler system.
 
 use chrono::{DateTime, Utc};
+use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::FromRow;
+use std::collections::HashMap;
+use std::sync::LazyLock;
 use uuid::Uuid;
 
+use super::chain::{validate_steps, ChainStep};
+use super::policy::{MisfirePolicy, OverlapPolicy};
 use super::triggers::TriggerType;
 
 pub use crate::error::SchedulerStoreError as SchedulerError;
@@ -42,12 +47,27 @@ pub struct ScheduledTask {
     pub updated_at: DateTime<Utc>,
     /// Last execution timestamp
     pub last_run_at: Option<DateTime<Utc>>,
-    /// Next scheduled execution
+    /// Next scheduled execution (before jitter)
     pub next_run_at: Option<DateTime<Utc>>,
+    /// When the next run actually fires: `next_run_at` plus jitter
+    ///
+    /// Later runs are scheduled from `next_run_at`, so jitter never
+    /// accumulates. `None` fires at `next_run_at`.
+    #[serde(default)]
+    pub fire_at: Option<DateTime<Utc>>,
     /// Total execution count
     pub run_count: i64,
     /// Failure count
     pub failure_count: i64,
+    /// Runs to catch up after missing scheduled times
+    #[serde(default)]
+    pub misfire_policy: MisfirePolicy,
+    /// Behavior when the task fires while still running
+    #[serde(default)]
+    pub overlap_policy: OverlapPolicy,
+    /// Random delay of up to this many seconds added to each scheduled time
+    #[serde(default)]
+    pub jitter_secs: u64,
 }
 
 impl ScheduledTask {
@@ -67,8 +87,12 @@ impl ScheduledTask {
             updated_at: now,
             last_run_at: None,
             next_run_at: None,
+            fire_at: None,
             run_count: 0,
             failure_count: 0,
+            misfire_policy: MisfirePolicy::default(),
+            overlap_policy: OverlapPolicy::default(),
+            jitter_secs: 0,
         }
     }
 
@@ -89,10 +113,28 @@ impl ScheduledTask {
         self.max_retries = max_retries;
         self
     }
+
+    /// Set the misfire policy
+    pub fn with_misfire_policy(mut self, policy: MisfirePolicy) -> Self {
+        self.misfire_policy = policy;
+        self
+    }
+
+    /// Set the overlap policy
+    pub fn with_overlap_policy(mut self, policy: OverlapPolicy) -> Self {
+        self.overlap_policy = policy;
+        self
+    }
+
+    /// Set the scheduling jitter
+    pub fn with_jitter(mut self, secs: u64) -> Self {
+        self.jitter_secs = secs;
+        self
+    }
 }
 
 /// Action to execute when triggered
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum TaskAction {
     /// Execute a natural language prompt
@@ -122,6 +164,9 @@ pub enum TaskAction {
     Shell {
         /// Command to execute
         command: String,
+        /// Arguments appended to the command, each passed as one argument
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
         /// Working directory
         cwd: Option<String>,
     },
@@ -146,6 +191,11 @@ pub enum TaskAction {
         /// Retention days
         days: u32,
     },
+    /// Run actions in order, each optionally conditioned on the previous output
+    Chain {
+        /// Steps to run
+        steps: Vec<ChainStep>,
+    },
 }
 
 impl TaskAction {
@@ -187,6 +237,128 @@ impl TaskAction {
     pub fn prune_stale_skills(days: u32) -> Self {
         Self::PruneStaleSkills { days }
     }
+
+    /// Create a chain of actions
+    pub fn chain(steps: Vec<ChainStep>) -> Self {
+        Self::Chain { steps }
+    }
+
+    /// Check that the action can run (e.g. chain conditions compile)
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            TaskAction::Chain { steps } => validate_steps(steps),
+            TaskAction::Shell { command, args, .. } => validate_shell_placeholders(command, args),
+            _ => Ok(()),
+        }
+    }
+
+    /// Replace `{{name}}` placeholders with the trigger's event variables
+    ///
+    /// A shell command never has values spliced into its text: from the
+    /// first placeholder on, its words move to `args` and each placeholder
+    /// becomes exactly one argument, so a file name cannot add arguments or
+    /// shell syntax. Unknown placeholders are kept.
+    pub fn with_variables(&self, variables: &HashMap<String, String>) -> Self {
+        if variables.is_empty() {
+            return self.clone();
+        }
+        match self {
+            TaskAction::Shell { command, args, cwd } => {
+                let mut words: Vec<&str> = command.split_whitespace().collect();
+                let split = words
+                    .iter()
+                    .skip(1)
+                    .position(|word| substitute_word(word, variables).is_some())
+                    .map_or(words.len(), |i| i + 1);
+                let moved = words.split_off(split);
+                TaskAction::Shell {
+                    command: if moved.is_empty() {
+                        command.clone()
+                    } else {
+                        words.join(" ")
+                    },
+                    args: moved
+                        .into_iter()
+                        .chain(args.iter().map(String::as_str))
+                        .map(|word| {
+                            substitute_word(word, variables).unwrap_or_else(|| word.to_string())
+                        })
+                        .collect(),
+                    cwd: cwd
+                        .as_deref()
+                        .map(|cwd| substitute(cwd, variables, str::to_string)),
+                }
+            }
+            _ => serde_json::to_value(self)
+                .map(|value| substitute_json(value, variables))
+                .and_then(serde_json::from_value)
+                .unwrap_or_else(|_| self.clone()),
+        }
+    }
+}
+
+static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{(\w+)\}\}").expect("valid placeholder regex"));
+
+/// Replace `{{name}}` placeholders in `text`, transforming each value with `quote`
+fn substitute(
+    text: &str,
+    variables: &HashMap<String, String>,
+    quote: impl Fn(&str) -> String,
+) -> String {
+    PLACEHOLDER
+        .replace_all(text, |caps: &Captures<'_>| match variables.get(&caps[1]) {
+            Some(value) => quote(value),
+            None => caps[0].to_string(),
+        })
+        .into_owned()
+}
+
+fn substitute_json(value: Value, variables: &HashMap<String, String>) -> Value {
+    match value {
+        Value::String(s) => Value::String(substitute(&s, variables, str::to_string)),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| substitute_json(item, variables))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, substitute_json(v, variables)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Value of a word that is exactly one known `{{name}}` placeholder
+fn substitute_word(word: &str, variables: &HashMap<String, String>) -> Option<String> {
+    PLACEHOLDER
+        .captures(word)
+        .filter(|caps| caps[0].len() == word.len())
+        .and_then(|caps| variables.get(&caps[1]).cloned())
+}
+
+/// Shell placeholders must be whole, unquoted arguments after the program
+///
+/// Anything else (`"{{path}}"`, `--file={{path}}`, `{{cmd}} -x`) would need
+/// the value spliced into command text, where it could be re-parsed.
+fn validate_shell_placeholders(command: &str, args: &[String]) -> Result<()> {
+    let words = command
+        .split_whitespace()
+        .chain(args.iter().map(String::as_str));
+    for (index, word) in words.enumerate() {
+        let Some(found) = PLACEHOLDER.find(word) else {
+            continue;
+        };
+        if index == 0 || found.len() != word.len() {
+            return Err(SchedulerError::InvalidConfig(format!(
+                "shell placeholder '{word}' must be a separate, unquoted argument"
+            )));
+        }
+    }
+    Ok(())
 }
 
 /// Task execution record
@@ -254,8 +426,12 @@ pub(super) struct TaskRow {
     pub updated_at: DateTime<Utc>,
     pub last_run_at: Option<DateTime<Utc>>,
     pub next_run_at: Option<DateTime<Utc>>,
+    pub fire_at: Option<DateTime<Utc>>,
     pub run_count: i64,
     pub failure_count: i64,
+    pub misfire_json: String,
+    pub overlap_policy: String,
+    pub jitter_secs: i64,
 }
 
 impl TryFrom<TaskRow> for ScheduledTask {
@@ -284,8 +460,16 @@ impl TryFrom<TaskRow> for ScheduledTask {
             updated_at: row.updated_at,
             last_run_at: row.last_run_at,
             next_run_at: row.next_run_at,
+            fire_at: row.fire_at,
             run_count: row.run_count,
             failure_count: row.failure_count,
+            misfire_policy: serde_json::from_str(&row.misfire_json).unwrap_or_else(|e| {
+                tracing::warn!("Failed to parse misfire policy for task {}: {}", row.id, e);
+                MisfirePolicy::default()
+            }),
+            overlap_policy: serde_json::from_value(Value::String(row.overlap_policy))
+                .unwrap_or_default(),
+            jitter_secs: u64::try_from(row.jitter_secs).unwrap_or(0),
         })
     }
 }
@@ -3,8 +3,12 @@
 //! Follows the pattern of dual-gating:
 //! 1. Platform-level denylist (always blocks dangerous commands)
 //! 2. Node-declared command list (node must declare what it can run)
+//!
+//! It also holds the 6-level [`ToolSecurityPolicy`] and the [`ApprovalPolicy`]
+//! rules that govern how `RequireApproval` calls are approved.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Reason a command was denied.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -313,6 +317,185 @@ impl ToolSecurityPolicy {
     }
 }
 
+// ────────────────────────────────────────────────────────────────────
+// Approval rules
+// ────────────────────────────────────────────────────────────────────
+//
+// Where a `PolicyRule` decides *whether* a tool call needs approval, an
+// `ApprovalRule` decides *how* it is approved: who may answer, how many
+// approvals are needed, and whether later calls are auto-approved for a
+// while after the first approval. Rules are enforced by `ApprovalManager`.
+
+/// Who may answer an approval request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ApproverSpec {
+    /// The user who triggered the execution
+    #[default]
+    Requester,
+    /// Any member of a named approver group
+    Group {
+        /// Group name (see [`ApprovalPolicy::groups`])
+        group: String,
+    },
+    /// An explicit list of users
+    Users {
+        /// User IDs allowed to approve
+        users: Vec<String>,
+    },
+    /// The owner of the node the tool runs on
+    NodeOwner,
+}
+
+/// Approval requirements for tool calls matching a pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRule {
+    /// Tool name pattern (same syntax as [`PolicyRule::tool_pattern`])
+    pub tool_pattern: String,
+    /// Argument patterns that must all match, keyed by top-level argument name
+    /// (e.g. `{ branch = "main" }`). Missing arguments never match.
+    #[serde(default)]
+    pub args: HashMap<String, String>,
+    /// Number of distinct approvals needed
+    #[serde(default = "default_required_approvals")]
+    pub required_approvals: u32,
+    /// Who may approve
+    #[serde(default)]
+    pub approvers: ApproverSpec,
+    /// After an approval, auto-approve the same call (same tool and
+    /// arguments) for the same requester for this many seconds (0 disables)
+    #[serde(default)]
+    pub auto_approve_window_secs: u64,
+}
+
+fn default_required_approvals() -> u32 {
+    1
+}
+
+impl ApprovalRule {
+    /// Create a rule requiring a single approval from the requester.
+    pub fn new(tool_pattern: impl Into<String>) -> Self {
+        Self {
+            tool_pattern: tool_pattern.into(),
+            args: HashMap::new(),
+            required_approvals: 1,
+            approvers: ApproverSpec::Requester,
+            auto_approve_window_secs: 0,
+        }
+    }
+
+    /// Only match calls whose argument `name` matches `pattern`.
+    #[must_use]
+    pub fn with_arg(mut self, name: impl Into<String>, pattern: impl Into<String>) -> Self {
+        self.args.insert(name.into(), pattern.into());
+        self
+    }
+
+    /// Set the number of distinct approvals needed.
+    #[must_use]
+    pub fn with_required_approvals(mut self, count: u32) -> Self {
+        self.required_approvals = count.max(1);
+        self
+    }
+
+    /// Set who may approve.
+    #[must_use]
+    pub fn with_approvers(mut self, approvers: ApproverSpec) -> Self {
+        self.approvers = approvers;
+        self
+    }
+
+    /// Set the auto-approve window.
+    #[must_use]
+    pub fn with_auto_approve_window(mut self, secs: u64) -> Self {
+        self.auto_approve_window_secs = secs;
+        self
+    }
+
+    /// Check whether this rule applies to a tool call.
+    pub fn matches(&self, tool_name: &str, args: &serde_json::Value) -> bool {
+        if !matches_pattern(&self.tool_pattern, tool_name) {
+            return false;
+        }
+        self.args.iter().all(|(name, pattern)| {
+            let value = match args.get(name) {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(serde_json::Value::Null) | None => return false,
+                Some(other) => other.to_string(),
+            };
+            matches_pattern(pattern, &value)
+        })
+    }
+}
+
+/// Approval rules and the approver groups they refer to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalPolicy {
+    /// Named approver groups (group name → user IDs)
+    #[serde(default)]
+    pub groups: HashMap<String, Vec<String>>,
+    /// Rules, checked in order; the first match wins
+    #[serde(default)]
+    pub rules: Vec<ApprovalRule>,
+    /// Owner of the machine Cratos runs on. Tools run here (locally or in
+    /// its sandbox), so this user is the node owner for their approvals.
+    #[serde(default)]
+    pub host_owner: Option<String>,
+}
+
+impl ApprovalPolicy {
+    /// Create an empty policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define an approver group.
+    #[must_use]
+    pub fn with_group(mut self, name: impl Into<String>, members: Vec<String>) -> Self {
+        self.groups.insert(name.into(), members);
+        self
+    }
+
+    /// Append a rule.
+    #[must_use]
+    pub fn with_rule(mut self, rule: ApprovalRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Set the owner of the machine Cratos runs on.
+    #[must_use]
+    pub fn with_host_owner(mut self, owner: impl Into<String>) -> Self {
+        self.host_owner = Some(owner.into());
+        self
+    }
+
+    /// Find the first rule that applies to a tool call.
+    pub fn rule_for(&self, tool_name: &str, args: &serde_json::Value) -> Option<&ApprovalRule> {
+        self.rules.iter().find(|r| r.matches(tool_name, args))
+    }
+
+    /// Resolve the users allowed to approve under `spec`.
+    ///
+    /// Returns `None` when only the requester may approve. An unknown group or
+    /// a missing node owner resolves to an empty list, so only an Admin can
+    /// answer (fail-safe).
+    pub fn resolve_approvers(
+        &self,
+        spec: &ApproverSpec,
+        node_owner: Option<&str>,
+    ) -> Option<Vec<String>> {
+        match spec {
+            ApproverSpec::Requester => None,
+            ApproverSpec::Group { group } => {
+                Some(self.groups.get(group).cloned().unwrap_or_default())
+            }
+            ApproverSpec::Users { users } => Some(users.clone()),
+            ApproverSpec::NodeOwner => Some(node_owner.map(String::from).into_iter().collect()),
+        }
+    }
+}
+
 /// Check if a tool name matches a pattern (simple glob: "*" = all, "foo*" = prefix, exact otherwise)
 fn matches_pattern(pattern: &str, name: &str) -> bool {
     if pattern == "*" {
@@ -255,3 +255,83 @@
         assert!(matches_pattern("file_*", "file_write"));
         assert!(!matches_pattern("file_*", "exec"));
     }
+
+    #[test]
+    fn test_approval_rule_matches_args() {
+        let rule = ApprovalRule::new("git_push").with_arg("branch", "main");
+
+        let to_main = serde_json::json!({"remote": "origin", "branch": "main"});
+        let to_feature = serde_json::json!({"branch": "feature-x"});
+        let no_branch = serde_json::json!({"remote": "origin"});
+
+        assert!(rule.matches("git_push", &to_main));
+        assert!(!rule.matches("git_push", &to_feature));
+        assert!(!rule.matches("git_push", &no_branch));
+        assert!(!rule.matches("git_commit", &to_main));
+    }
+
+    #[test]
+    fn test_approval_policy_first_match_and_approvers() {
+        let policy = ApprovalPolicy::new()
+            .with_group("admins", vec!["alice".to_string(), "bob".to_string()])
+            .with_rule(
+                ApprovalRule::new("git_push")
+                    .with_arg("branch", "main")
+                    .with_required_approvals(2)
+                    .with_approvers(ApproverSpec::Group {
+                        group: "admins".to_string(),
+                    }),
+            )
+            .with_rule(ApprovalRule::new("exec").with_approvers(ApproverSpec::NodeOwner));
+
+        let rule = policy
+            .rule_for("git_push", &serde_json::json!({"branch": "main"}))
+            .unwrap();
+        assert_eq!(rule.required_approvals, 2);
+        assert_eq!(
+            policy.resolve_approvers(&rule.approvers, None),
+            Some(vec!["alice".to_string(), "bob".to_string()])
+        );
+
+        let exec = policy.rule_for("exec", &serde_json::json!({})).unwrap();
+        assert_eq!(
+            policy.resolve_approvers(&exec.approvers, Some("carol")),
+            Some(vec!["carol".to_string()])
+        );
+        // No node owner known: nobody but an Admin may approve
+        assert_eq!(policy.resolve_approvers(&exec.approvers, None), Some(vec![]));
+        assert_eq!(
+            policy.resolve_approvers(&ApproverSpec::Requester, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_approval_rule_from_toml() {
+        let policy: ApprovalPolicy = toml::from_str(
+            r#"
+            host_owner = "ops"
+
+            [groups]
+            admins = ["alice", "bob"]
+
+            [[rules]]
+            tool_pattern = "git_push"
+            args = { branch = "main" }
+            required_approvals = 2
+            approvers = { kind = "group", group = "admins" }
+            auto_approve_window_secs = 600
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.host_owner.as_deref(), Some("ops"));
+        assert_eq!(policy.rules.len(), 1);
+        assert_eq!(policy.rules[0].auto_approve_window_secs, 600);
+        assert_eq!(
+            policy.rules[0].approvers,
+            ApproverSpec::Group {
+                group: "admins".to_string()
+            }
+        );
+    }
@@ -7,7 +7,7 @@ use crate::tools::{ToolCall, ToolChoice, ToolDefinition};
 use serde::{Deserialize, Serialize};
 
 /// Token usage information
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TokenUsage {
     /// Prompt tokens
     pub prompt_tokens: u32,
@@ -17,6 +17,17 @@ pub struct TokenUsage {
     pub total_tokens: u32,
 }
 
+impl TokenUsage {
+    /// Add the tokens of another call
+    pub fn accumulate(&mut self, other: &TokenUsage) {
+        self.prompt_tokens = self.prompt_tokens.saturating_add(other.prompt_tokens);
+        self.completion_tokens = self
+            .completion_tokens
+            .saturating_add(other.completion_tokens);
+        self.total_tokens = self.total_tokens.saturating_add(other.total_tokens);
+    }
+}
+
 /// Completion request
 #[derive(Debug, Clone, Default)]
 pub struct CompletionRequest {
@@ -155,6 +166,21 @@ mod tests {
         assert_eq!(request.temperature, Some(0.7));
     }
 
+    #[test]
+    fn test_token_usage_accumulate() {
+        let mut usage = TokenUsage::default();
+        let call = TokenUsage {
+            prompt_tokens: 10,
+            completion_tokens: 5,
+            total_tokens: 15,
+        };
+        usage.accumulate(&call);
+        usage.accumulate(&call);
+        assert_eq!(usage.prompt_tokens, 20);
+        assert_eq!(usage.completion_tokens, 10);
+        assert_eq!(usage.total_tokens, 30);
+    }
+
     #[test]
     fn test_tool_completion_request() {
         let request = CompletionRequest::new("gpt-4");
@@ -0,0 +1,68 @@
+//! Usage Attribution
+//!
+//! Providers do not know who an LLM call is made for. Callers run their
+//! calls inside [`UsageAttribution::scope`], and every usage record created
+//! inside that future is attributed to the execution, user, persona and
+//! channel of the scope.
+
+use std::future::Future;
+
+tokio::task_local! {
+    static CURRENT_ATTRIBUTION: UsageAttribution;
+}
+
+/// Who an LLM call is made for
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageAttribution {
+    /// Execution ID
+    pub execution_id: Option<String>,
+    /// User ID
+    pub user_id: Option<String>,
+    /// Persona name
+    pub persona: Option<String>,
+    /// Channel type (e.g. "telegram", "api")
+    pub channel: Option<String>,
+}
+
+impl UsageAttribution {
+    /// Attribution for a user
+    pub fn user(user_id: impl Into<String>) -> Self {
+        Self {
+            user_id: Some(user_id.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Set the execution ID
+    #[must_use]
+    pub fn with_execution(mut self, execution_id: impl Into<String>) -> Self {
+        self.execution_id = Some(execution_id.into());
+        self
+    }
+
+    /// Set the persona
+    #[must_use]
+    pub fn with_persona(mut self, persona: impl Into<String>) -> Self {
+        self.persona = Some(persona.into());
+        self
+    }
+
+    /// Set the channel type
+    #[must_use]
+    pub fn with_channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = Some(channel.into());
+        self
+    }
+
+    /// Attribution of the current task (empty outside a scope)
+    pub fn current() -> Self {
+        CURRENT_ATTRIBUTION
+            .try_with(Clone::clone)
+            .unwrap_or_default()
+    }
+
+    /// Run `fut` with this attribution
+    pub async fn scope<F: Future>(self, fut: F) -> F::Output {
+        CURRENT_ATTRIBUTION.scope(self, fut).await
+    }
+}
@@ -0,0 +1,241 @@
+//! Budgets
+//!
+//! Daily and monthly spending limits per user and per provider. Crossing
+//! `warn_at_pct` of a limit raises a warning; once a limit is exceeded the
+//! configured [`BudgetAction`] applies to further calls.
+
+use crate::router::ModelTier;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What happens to calls once a budget is exceeded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetAction {
+    /// Only raise an alert
+    #[default]
+    Warn,
+    /// Force calls down to `downgrade_tier`
+    Downgrade,
+    /// Reject calls until the period resets
+    Block,
+}
+
+/// Spending limits in USD
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetLimit {
+    /// Limit per UTC day
+    #[serde(default)]
+    pub daily_usd: Option<f64>,
+    /// Limit per UTC calendar month
+    #[serde(default)]
+    pub monthly_usd: Option<f64>,
+}
+
+impl BudgetLimit {
+    /// Configured limits with their period
+    pub fn periods(&self) -> impl Iterator<Item = (BudgetPeriod, f64)> {
+        [
+            (BudgetPeriod::Daily, self.daily_usd),
+            (BudgetPeriod::Monthly, self.monthly_usd),
+        ]
+        .into_iter()
+        .filter_map(|(period, limit)| limit.map(|l| (period, l)))
+    }
+}
+
+/// Budget configuration (`[llm.budget]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BudgetConfig {
+    /// Action once a limit is exceeded
+    pub action: BudgetAction,
+    /// Tier calls are forced down to by [`BudgetAction::Downgrade`]
+    pub downgrade_tier: ModelTier,
+    /// Percentage of a limit at which a warning is raised
+    pub warn_at_pct: f64,
+    /// Limit for users without an entry in `users`
+    pub default_user: Option<BudgetLimit>,
+    /// Limits per user ID
+    pub users: HashMap<String, BudgetLimit>,
+    /// Limits per provider name
+    pub providers: HashMap<String, BudgetLimit>,
+}
+
+impl Default for BudgetConfig {
+    fn default() -> Self {
+        Self {
+            action: BudgetAction::Warn,
+            downgrade_tier: ModelTier::Fast,
+            warn_at_pct: 80.0,
+            default_user: None,
+            users: HashMap::new(),
+            providers: HashMap::new(),
+        }
+    }
+}
+
+impl BudgetConfig {
+    /// Limit that applies to a user
+    #[must_use]
+    pub fn user_limit(&self, user_id: &str) -> Option<&BudgetLimit> {
+        self.users.get(user_id).or(self.default_user.as_ref())
+    }
+
+    /// Limit that applies to a provider
+    #[must_use]
+    pub fn provider_limit(&self, provider: &str) -> Option<&BudgetLimit> {
+        self.providers.get(provider)
+    }
+
+    /// Compare spending against a limit
+    ///
+    /// Returns an alert when the warning threshold is reached.
+    #[must_use]
+    pub fn evaluate(
+        &self,
+        scope: BudgetScope,
+        name: &str,
+        period: BudgetPeriod,
+        limit_usd: f64,
+        spent_usd: f64,
+    ) -> Option<BudgetAlert> {
+        let exceeded = spent_usd >= limit_usd;
+        if !exceeded && spent_usd < limit_usd * self.warn_at_pct / 100.0 {
+            return None;
+        }
+        Some(BudgetAlert {
+            scope,
+            name: name.to_string(),
+            period,
+            spent_usd,
+            limit_usd,
+            exceeded,
+            action: self.action,
+        })
+    }
+
+    /// Decision for calls covered by an alert
+    #[must_use]
+    pub fn decision_for(&self, alert: &BudgetAlert) -> BudgetDecision {
+        if !alert.exceeded {
+            return BudgetDecision::Allow;
+        }
+        match self.action {
+            BudgetAction::Warn => BudgetDecision::Allow,
+            BudgetAction::Downgrade => BudgetDecision::Downgrade(self.downgrade_tier),
+            BudgetAction::Block => BudgetDecision::Block(alert.message()),
+        }
+    }
+}
+
+/// What a budget limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetScope {
+    /// Spending of one user
+    User,
+    /// Spending on one provider
+    Provider,
+}
+
+impl std::fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::User => write!(f, "user"),
+            Self::Provider => write!(f, "provider"),
+        }
+    }
+}
+
+/// Budget period (UTC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPeriod {
+    /// Calendar day
+    Daily,
+    /// Calendar month
+    Monthly,
+}
+
+impl BudgetPeriod {
+    /// Start of the period containing `now`
+    #[must_use]
+    pub fn start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let day = match self {
+            Self::Daily => now.date_naive(),
+            Self::Monthly => now.date_naive().with_day(1).unwrap_or(now.date_naive()),
+        };
+        Utc.from_utc_datetime(&day.and_hms_opt(0, 0, 0).unwrap_or_default())
+    }
+}
+
+impl std::fmt::Display for BudgetPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Daily => write!(f, "daily"),
+            Self::Monthly => write!(f, "monthly"),
+        }
+    }
+}
+
+/// A budget that is close to or over its limit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetAlert {
+    /// What the budget limits
+    pub scope: BudgetScope,
+    /// User ID or provider name
+    pub name: String,
+    /// Budget period
+    pub period: BudgetPeriod,
+    /// Spent in the current period (USD)
+    pub spent_usd: f64,
+    /// Limit of the period (USD)
+    pub limit_usd: f64,
+    /// Whether the limit is exceeded (otherwise only close)
+    pub exceeded: bool,
+    /// Action applied once exceeded
+    pub action: BudgetAction,
+}
+
+impl BudgetAlert {
+    /// Human-readable description
+    #[must_use]
+    pub fn message(&self) -> String {
+        let state = if self.exceeded {
+            "exceeded"
+        } else {
+            "almost reached"
+        };
+        format!(
+            "{} budget for {} '{}' {}: ${:.2} of ${:.2}",
+            self.period, self.scope, self.name, state, self.spent_usd, self.limit_usd
+        )
+    }
+}
+
+/// How a call is treated under the current budgets
+#[derive(Debug, Clone, PartialEq)]
+pub enum BudgetDecision {
+    /// Proceed as requested
+    Allow,
+    /// Proceed with the default model of this tier
+    Downgrade(ModelTier),
+    /// Reject the call
+    Block(String),
+}
+
+impl BudgetDecision {
+    /// The stricter of two decisions
+    #[must_use]
+    pub fn stricter(self, other: Self) -> Self {
+        match (&self, &other) {
+            (Self::Block(_), _) => self,
+            (_, Self::Block(_)) => other,
+            (Self::Downgrade(a), Self::Downgrade(b)) => Self::Downgrade(a.constrain_to(b)),
+            (Self::Downgrade(_), _) => self,
+            _ => other,
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Cost Ledger - SQLite persistence for usage records
+//!
+//! Every recorded LLM call is appended to the `llm_usage` table, so spending
+//! survives a restart and can be summarized over any period.
+
+use super::record::UsageRecord;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Row, Sqlite};
+
+/// Dimension a cost summary is grouped by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostGroup {
+    /// UTC day (`YYYY-MM-DD`)
+    Day,
+    /// Provider name
+    #[default]
+    Provider,
+    /// Model name
+    Model,
+    /// User ID
+    User,
+    /// Persona name
+    Persona,
+    /// Channel type
+    Channel,
+}
+
+impl CostGroup {
+    /// SQL expression of the group key
+    fn column(&self) -> &'static str {
+        match self {
+            Self::Day => "substr(timestamp, 1, 10)",
+            Self::Provider => "provider",
+            Self::Model => "model",
+            Self::User => "COALESCE(user_id, '')",
+            Self::Persona => "COALESCE(persona, '')",
+            Self::Channel => "COALESCE(channel, '')",
+        }
+    }
+}
+
+impl std::fmt::Display for CostGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Day => "day",
+            Self::Provider => "provider",
+            Self::Model => "model",
+            Self::User => "user",
+            Self::Persona => "persona",
+            Self::Channel => "channel",
+        };
+        f.write_str(name)
+    }
+}
+
+impl std::str::FromStr for CostGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "provider" => Ok(Self::Provider),
+            "model" => Ok(Self::Model),
+            "user" => Ok(Self::User),
+            "persona" => Ok(Self::Persona),
+            "channel" => Ok(Self::Channel),
+            other => Err(format!(
+                "unknown group '{other}' (expected day, provider, model, user, persona or channel)"
+            )),
+        }
+    }
+}
+
+/// Filter and grouping of a cost summary
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CostQuery {
+    /// Only records at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only records before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Grouping dimension
+    pub group_by: CostGroup,
+    /// Only records of this user
+    pub user_id: Option<String>,
+    /// Only records of this provider
+    pub provider: Option<String>,
+}
+
+/// One group of a cost summary
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostSummaryRow {
+    /// Group key (empty when the record has no value for the dimension)
+    pub key: String,
+    /// Number of LLM calls
+    pub requests: u64,
+    /// Input tokens
+    pub input_tokens: u64,
+    /// Output tokens
+    pub output_tokens: u64,
+    /// Estimated cost (USD)
+    pub cost_usd: f64,
+}
+
+/// SQLite-backed usage ledger
+#[derive(Debug, Clone)]
+pub struct CostLedger {
+    pool: Pool<Sqlite>,
+}
+
+/// Fixed-width timestamps so they compare correctly as text
+fn timestamp(t: DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+impl CostLedger {
+    /// Create the ledger, creating the table if needed
+    pub async fn new(pool: Pool<Sqlite>) -> Result<Self, sqlx::Error> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS llm_usage (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                execution_id TEXT,
+                provider TEXT NOT NULL,
+                model TEXT NOT NULL,
+                user_id TEXT,
+                persona TEXT,
+                channel TEXT,
+                input_tokens INTEGER NOT NULL,
+                output_tokens INTEGER NOT NULL,
+                cost_usd REAL NOT NULL,
+                latency_ms INTEGER NOT NULL,
+                success INTEGER NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_llm_usage_timestamp ON llm_usage (timestamp)")
+            .execute(&pool)
+            .await?;
+
+        Ok(Self { pool })
+    }
+
+    /// Append a usage record
+    pub async fn insert(&self, record: &UsageRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO llm_usage (
+                timestamp, execution_id, provider, model, user_id, persona, channel,
+                input_tokens, output_tokens, cost_usd, latency_ms, success
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(timestamp(record.timestamp))
+        .bind(&record.execution_id)
+        .bind(&record.provider)
+        .bind(&record.model)
+        .bind(&record.user_id)
+        .bind(&record.persona)
+        .bind(&record.channel)
+        .bind(record.input_tokens)
+        .bind(record.output_tokens)
+        .bind(record.estimated_cost)
+        .bind(i64::try_from(record.latency_ms).unwrap_or(i64::MAX))
+        .bind(record.success)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Total cost since a point in time, optionally for one user or provider
+    pub async fn total_cost(
+        &self,
+        since: DateTime<Utc>,
+        user_id: Option<&str>,
+        provider: Option<&str>,
+    ) -> Result<f64, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT COALESCE(SUM(cost_usd), 0.0) FROM llm_usage
+             WHERE timestamp >= ?
+               AND (? IS NULL OR user_id = ?)
+               AND (? IS NULL OR provider = ?)",
+        )
+        .bind(timestamp(since))
+        .bind(user_id)
+        .bind(user_id)
+        .bind(provider)
+        .bind(provider)
+        .fetch_one(&self.pool)
+        .await?;
+        row.try_get(0)
+    }
+
+    /// Summarize usage grouped by one dimension
+    ///
+    /// Days are returned in chronological order, every other grouping by
+    /// descending cost.
+    pub async fn summarize(&self, query: &CostQuery) -> Result<Vec<CostSummaryRow>, sqlx::Error> {
+        let order = if query.group_by == CostGroup::Day {
+            "key ASC"
+        } else {
+            "cost_usd DESC, key ASC"
+        };
+        let sql = format!(
+            "SELECT {} AS key, COUNT(*) AS requests,
+                    COALESCE(SUM(input_tokens), 0) AS input_tokens,
+                    COALESCE(SUM(output_tokens), 0) AS output_tokens,
+                    COALESCE(SUM(cost_usd), 0.0) AS cost_usd
+             FROM llm_usage
+             WHERE (? IS NULL OR timestamp >= ?)
+               AND (? IS NULL OR timestamp < ?)
+               AND (? IS NULL OR user_id = ?)
+               AND (? IS NULL OR provider = ?)
+             GROUP BY key
+             ORDER BY {}",
+            query.group_by.column(),
+            order
+        );
+
+        let since = query.since.map(timestamp);
+        let until = query.until.map(timestamp);
+        let rows = sqlx::query(&sql)
+            .bind(&since)
+            .bind(&since)
+            .bind(&until)
+            .bind(&until)
+            .bind(&query.user_id)
+            .bind(&query.user_id)
+            .bind(&query.provider)
+            .bind(&query.provider)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(CostSummaryRow {
+                    key: row.try_get("key")?,
+                    requests: row.try_get::<i64, _>("requests")?.max(0) as u64,
+                    input_tokens: row.try_get::<i64, _>("input_tokens")?.max(0) as u64,
+                    output_tokens: row.try_get::<i64, _>("output_tokens")?.max(0) as u64,
+                    cost_usd: row.try_get("cost_usd")?,
+                })
+            })
+            .collect()
+    }
+}
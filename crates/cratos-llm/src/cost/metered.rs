@@ -0,0 +1,270 @@
+//! Metered Provider - usage recording and budget enforcement
+//!
+//! [`MeteredProvider`] wraps another provider. Before each call it checks
+//! the budgets of the provider and the attributed user, downgrading the
+//! model or rejecting the call as configured; after each call it records
+//! the token usage with the [`CostTracker`]. Providers that report no usage
+//! are metered with the model's tokenizer instead.
+
+use super::attribution::UsageAttribution;
+use super::budget::BudgetDecision;
+use super::tracker::CostTracker;
+use crate::completion::{
+    CompletionRequest, CompletionResponse, TokenUsage, ToolCompletionRequest,
+    ToolCompletionResponse,
+};
+use crate::error::{Error, Result};
+use crate::message::Message;
+use crate::router::LlmProvider;
+use crate::stream::{CompletionStream, StreamChunk};
+use crate::token::TokenCounter;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::info;
+
+/// Provider wrapper that records usage and enforces budgets
+pub struct MeteredProvider {
+    inner: Arc<dyn LlmProvider>,
+    tracker: Arc<CostTracker>,
+}
+
+impl MeteredProvider {
+    /// Wrap a provider
+    #[must_use]
+    pub fn new(inner: Arc<dyn LlmProvider>, tracker: Arc<CostTracker>) -> Self {
+        Self { inner, tracker }
+    }
+
+    /// Apply the budgets to a request, returning the model to use
+    async fn enforce_budget(&self, model: String) -> Result<String> {
+        let attribution = UsageAttribution::current();
+        let decision = self
+            .tracker
+            .check_budget(self.inner.name(), attribution.user_id.as_deref())
+            .await;
+        match decision {
+            BudgetDecision::Allow => Ok(model),
+            BudgetDecision::Block(reason) => Err(Error::BudgetExceeded(reason)),
+            BudgetDecision::Downgrade(tier) => match tier.model_for(self.inner.name()) {
+                Some(cheaper) if cheaper != model => {
+                    info!(
+                        provider = self.inner.name(),
+                        from = %model,
+                        to = cheaper,
+                        "Budget exceeded, downgrading model"
+                    );
+                    Ok(cheaper.to_string())
+                }
+                _ => Ok(model),
+            },
+        }
+    }
+
+    async fn record(&self, model: &str, usage: Option<&TokenUsage>, started: Instant, ok: bool) {
+        let (input, output) = usage.map_or((0, 0), |u| (u.prompt_tokens, u.completion_tokens));
+        self.tracker
+            .record_usage(
+                self.inner.name(),
+                model,
+                input,
+                output,
+                started.elapsed().as_millis() as u64,
+                ok,
+                None,
+            )
+            .await;
+    }
+}
+
+#[async_trait::async_trait]
+impl LlmProvider for MeteredProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.inner.supports_tools()
+    }
+
+    fn available_models(&self) -> Vec<String> {
+        self.inner.available_models()
+    }
+
+    fn default_model(&self) -> &str {
+        self.inner.default_model()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    async fn complete(&self, mut request: CompletionRequest) -> Result<CompletionResponse> {
+        request.model = self.enforce_budget(request.model).await?;
+        let requested = request.model.clone();
+        let prompt = request.messages.clone();
+        let started = Instant::now();
+
+        let result = self.inner.complete(request).await;
+        match &result {
+            Ok(response) => {
+                let usage = response.usage.clone().unwrap_or_else(|| {
+                    estimate_usage(self.name(), &response.model, &prompt, &response.content)
+                });
+                self.record(&response.model, Some(&usage), started, true)
+                    .await
+            }
+            Err(_) => self.record(&requested, None, started, false).await,
+        }
+        result
+    }
+
+    async fn complete_with_tools(
+        &self,
+        mut request: ToolCompletionRequest,
+    ) -> Result<ToolCompletionResponse> {
+        request.request.model = self.enforce_budget(request.request.model).await?;
+        let requested = request.request.model.clone();
+        let prompt = request.request.messages.clone();
+        let started = Instant::now();
+
+        let result = self.inner.complete_with_tools(request).await;
+        match &result {
+            Ok(response) => {
+                let usage = response.usage.clone().unwrap_or_else(|| {
+                    let mut output = response.content.clone().unwrap_or_default();
+                    for call in &response.tool_calls {
+                        output.push_str(&call.name);
+                        output.push_str(&call.arguments);
+                    }
+                    estimate_usage(self.name(), &response.model, &prompt, &output)
+                });
+                self.record(&response.model, Some(&usage), started, true)
+                    .await
+            }
+            Err(_) => self.record(&requested, None, started, false).await,
+        }
+        result
+    }
+
+    async fn complete_stream(
+        &self,
+        mut request: ToolCompletionRequest,
+    ) -> Result<CompletionStream> {
+        request.request.model = self.enforce_budget(request.request.model).await?;
+        let requested = request.request.model.clone();
+        let prompt = request.request.messages.clone();
+        let started = Instant::now();
+
+        let stream = match self.inner.complete_stream(request).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                self.record(&requested, None, started, false).await;
+                return Err(e);
+            }
+        };
+
+        // Usage arrives with the last chunks; record it once the stream is
+        // finished or dropped.
+        let mut meter = StreamMeter {
+            tracker: self.tracker.clone(),
+            provider: self.inner.name().to_string(),
+            model: requested,
+            usage: None,
+            prompt,
+            output: String::new(),
+            success: true,
+            started,
+            attribution: UsageAttribution::current(),
+        };
+        Ok(stream
+            .map(move |chunk| {
+                meter.observe(&chunk);
+                chunk
+            })
+            .boxed())
+    }
+}
+
+/// Collects the usage of a streamed completion and records it on drop
+struct StreamMeter {
+    tracker: Arc<CostTracker>,
+    provider: String,
+    model: String,
+    usage: Option<TokenUsage>,
+    prompt: Vec<Message>,
+    output: String,
+    success: bool,
+    started: Instant,
+    attribution: UsageAttribution,
+}
+
+impl StreamMeter {
+    fn observe(&mut self, chunk: &Result<StreamChunk>) {
+        match chunk {
+            Ok(StreamChunk::Metadata { model, usage, .. }) => {
+                if let Some(model) = model {
+                    self.model.clone_from(model);
+                }
+                if usage.is_some() {
+                    self.usage.clone_from(usage);
+                }
+            }
+            Ok(StreamChunk::TextDelta(text)) => self.output.push_str(text),
+            Ok(StreamChunk::ToolCallDelta {
+                name, arguments, ..
+            }) => {
+                if let Some(name) = name {
+                    self.output.push_str(name);
+                }
+                self.output.push_str(arguments);
+            }
+            Err(_) => self.success = false,
+        }
+    }
+}
+
+impl Drop for StreamMeter {
+    fn drop(&mut self) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let tracker = self.tracker.clone();
+        let provider = std::mem::take(&mut self.provider);
+        let model = std::mem::take(&mut self.model);
+        let usage = self.usage.take();
+        let prompt = std::mem::take(&mut self.prompt);
+        let output = std::mem::take(&mut self.output);
+        let latency_ms = self.started.elapsed().as_millis() as u64;
+        let success = self.success;
+        let attribution = std::mem::take(&mut self.attribution);
+        runtime.spawn(async move {
+            let usage =
+                usage.unwrap_or_else(|| estimate_usage(&provider, &model, &prompt, &output));
+            tracker
+                .record_attributed(
+                    &provider,
+                    &model,
+                    usage.prompt_tokens,
+                    usage.completion_tokens,
+                    latency_ms,
+                    success,
+                    attribution,
+                )
+                .await;
+        });
+    }
+}
+
+/// Usage counted with the model's tokenizer, for providers that report none
+fn estimate_usage(provider: &str, model: &str, prompt: &[Message], output: &str) -> TokenUsage {
+    let counter = TokenCounter::for_model(provider, model);
+    let prompt_tokens =
+        u32::try_from(counter.count_conversation_tokens(prompt)).unwrap_or(u32::MAX);
+    let completion_tokens = u32::try_from(counter.count_tokens(output)).unwrap_or(u32::MAX);
+    TokenUsage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens.saturating_add(completion_tokens),
+    }
+}
@@ -10,8 +10,16 @@
 //! - `tracker`: CostTracker implementation
 //! - `report`: Cost reports and savings analysis
 //! - `global`: Global tracker singleton
+//! - `attribution`: Task-local user/persona/channel attribution
+//! - `ledger`: SQLite persistence and summaries
+//! - `budget`: Daily/monthly budgets and alerts
+//! - `metered`: Provider wrapper recording usage and enforcing budgets
 
+mod attribution;
+mod budget;
 mod global;
+mod ledger;
+mod metered;
 mod pricing;
 mod record;
 mod report;
@@ -21,7 +29,14 @@ mod tracker;
 mod tests;
 
 // Re-export public types
+pub use attribution::UsageAttribution;
+pub use budget::{
+    BudgetAction, BudgetAlert, BudgetConfig, BudgetDecision, BudgetLimit, BudgetPeriod,
+    BudgetScope,
+};
 pub use global::global_tracker;
+pub use ledger::{CostGroup, CostLedger, CostQuery, CostSummaryRow};
+pub use metered::MeteredProvider;
 pub use pricing::{default_pricing, ModelPricing};
 pub use record::{ModelStats, ProviderStats, UsageRecord, UsageStats};
 pub use report::{CostReport, SavingsPotential};
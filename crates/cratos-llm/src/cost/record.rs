@@ -15,6 +15,15 @@ pub struct UsageRecord {
     pub timestamp: DateTime<Utc>,
     /// Execution ID (if available)
     pub execution_id: Option<String>,
+    /// User the call was made for (if known)
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Persona that made the call (if known)
+    #[serde(default)]
+    pub persona: Option<String>,
+    /// Channel type the request came from (if known)
+    #[serde(default)]
+    pub channel: Option<String>,
     /// Provider name
     pub provider: String,
     /// Model name
@@ -1,7 +1,14 @@
 //! Tests for cost module
 
 use super::*;
+use crate::completion::{
+    CompletionRequest, CompletionResponse, TokenUsage, ToolCompletionRequest,
+    ToolCompletionResponse,
+};
+use crate::message::Message;
+use crate::router::{LlmProvider, ModelTier};
 use chrono::Utc;
+use std::sync::Arc;
 
 #[test]
 fn test_model_pricing_calculation() {
@@ -107,6 +114,24 @@ async fn test_estimate_cost() {
     assert!(cost > 0.0);
 }
 
+#[tokio::test]
+async fn test_estimate_request_cost() {
+    let tracker = CostTracker::new();
+    let messages = vec![Message::user(
+        "Summarize the release notes in three bullets.",
+    )];
+    let prompt_tokens =
+        crate::token::count_message_tokens_for("anthropic", "claude-sonnet-4.5", &messages);
+
+    let cost = tracker
+        .estimate_request_cost("anthropic", "claude-sonnet-4.5", &messages, 1_000)
+        .await;
+    let expected = tracker
+        .estimate_cost("claude-sonnet-4.5", prompt_tokens as u32, 1_000)
+        .await;
+    assert!((cost - expected).abs() < 1e-12);
+}
+
 #[tokio::test]
 async fn test_get_execution_records() {
     let tracker = CostTracker::new();
@@ -203,3 +228,344 @@ fn test_global_tracker() {
     // Should be the same instance
     assert!(std::sync::Arc::ptr_eq(&tracker1, &tracker2));
 }
+
+#[tokio::test]
+async fn test_record_usage_uses_current_attribution() {
+    let tracker = CostTracker::new();
+
+    let attribution = UsageAttribution::user("alice")
+        .with_execution("exec-1")
+        .with_persona("sindri")
+        .with_channel("telegram");
+    let record = attribution
+        .scope(tracker.record_usage("openai", "gpt-5", 100, 50, 10, true, None))
+        .await;
+    assert_eq!(record.user_id.as_deref(), Some("alice"));
+    assert_eq!(record.persona.as_deref(), Some("sindri"));
+    assert_eq!(record.channel.as_deref(), Some("telegram"));
+    assert_eq!(record.execution_id.as_deref(), Some("exec-1"));
+
+    let record = tracker
+        .record_usage("openai", "gpt-5", 100, 50, 10, true, None)
+        .await;
+    assert!(record.user_id.is_none());
+}
+
+/// Single connection so every query sees the same in-memory database
+async fn memory_ledger() -> CostLedger {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    CostLedger::new(pool).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_ledger_persists_and_summarizes() {
+    let ledger = memory_ledger().await;
+    let tracker = CostTracker::new();
+    tracker.set_ledger(ledger.clone()).await;
+
+    UsageAttribution::user("alice")
+        .scope(tracker.record_usage("openai", "gpt-5", 1_000_000, 0, 10, true, None))
+        .await;
+    UsageAttribution::user("bob")
+        .with_persona("athena")
+        .scope(tracker.record_usage(
+            "anthropic",
+            "claude-haiku-4-5-20251001",
+            1_000_000,
+            0,
+            10,
+            true,
+            None,
+        ))
+        .await;
+    tracker
+        .record_usage("openai", "gpt-5", 1_000_000, 0, 10, false, None)
+        .await;
+
+    // A fresh tracker on the same database sees the spending
+    let restarted = CostTracker::new();
+    restarted.set_ledger(ledger.clone()).await;
+    let since = Utc::now() - chrono::Duration::hours(1);
+    assert!((restarted.spent_since(since, None, Some("openai")).await - 2.5).abs() < 1e-9);
+    assert!((restarted.spent_since(since, Some("bob"), None).await - 1.0).abs() < 1e-9);
+
+    let by_provider = ledger.summarize(&CostQuery::default()).await.unwrap();
+    assert_eq!(by_provider.len(), 2);
+    assert_eq!(by_provider[0].key, "openai");
+    assert_eq!(by_provider[0].requests, 2);
+    assert_eq!(by_provider[0].input_tokens, 2_000_000);
+
+    let by_persona = ledger
+        .summarize(&CostQuery {
+            group_by: CostGroup::Persona,
+            ..CostQuery::default()
+        })
+        .await
+        .unwrap();
+    let keys: Vec<&str> = by_persona.iter().map(|r| r.key.as_str()).collect();
+    assert_eq!(keys, vec!["", "athena"]);
+
+    let by_day = ledger
+        .summarize(&CostQuery {
+            group_by: CostGroup::Day,
+            user_id: Some("alice".to_string()),
+            ..CostQuery::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(by_day.len(), 1);
+    assert_eq!(by_day[0].key, Utc::now().format("%Y-%m-%d").to_string());
+    assert_eq!(by_day[0].requests, 1);
+
+    let future = ledger
+        .summarize(&CostQuery {
+            since: Some(Utc::now() + chrono::Duration::hours(1)),
+            ..CostQuery::default()
+        })
+        .await
+        .unwrap();
+    assert!(future.is_empty());
+}
+
+#[test]
+fn test_cost_group_parse() {
+    assert_eq!("day".parse::<CostGroup>(), Ok(CostGroup::Day));
+    assert_eq!("persona".parse::<CostGroup>(), Ok(CostGroup::Persona));
+    assert!("week".parse::<CostGroup>().is_err());
+    assert_eq!(CostGroup::Channel.to_string(), "channel");
+}
+
+#[test]
+fn test_budget_evaluate() {
+    let config = BudgetConfig::default();
+
+    assert!(config
+        .evaluate(BudgetScope::User, "alice", BudgetPeriod::Daily, 10.0, 7.9)
+        .is_none());
+
+    let warning = config
+        .evaluate(BudgetScope::User, "alice", BudgetPeriod::Daily, 10.0, 8.0)
+        .unwrap();
+    assert!(!warning.exceeded);
+    assert_eq!(config.decision_for(&warning), BudgetDecision::Allow);
+
+    let exceeded = config
+        .evaluate(BudgetScope::User, "alice", BudgetPeriod::Daily, 10.0, 10.0)
+        .unwrap();
+    assert!(exceeded.exceeded);
+    assert_eq!(
+        exceeded.message(),
+        "daily budget for user 'alice' exceeded: $10.00 of $10.00"
+    );
+
+    let block = BudgetConfig {
+        action: BudgetAction::Block,
+        ..BudgetConfig::default()
+    };
+    assert!(matches!(
+        block.decision_for(&exceeded),
+        BudgetDecision::Block(_)
+    ));
+}
+
+#[test]
+fn test_budget_config_deserialize() {
+    let config: BudgetConfig = serde_json::from_value(serde_json::json!({
+        "action": "downgrade",
+        "downgrade_tier": "ultra_budget",
+        "default_user": { "daily_usd": 1.0 },
+        "users": { "alice": { "monthly_usd": 50.0 } },
+        "providers": { "openai": { "daily_usd": 5.0, "monthly_usd": 100.0 } }
+    }))
+    .unwrap();
+
+    assert_eq!(config.action, BudgetAction::Downgrade);
+    assert_eq!(config.downgrade_tier, ModelTier::UltraBudget);
+    assert!((config.warn_at_pct - 80.0).abs() < f64::EPSILON);
+    assert_eq!(config.user_limit("alice").unwrap().monthly_usd, Some(50.0));
+    assert_eq!(config.user_limit("bob").unwrap().daily_usd, Some(1.0));
+    assert_eq!(
+        config.provider_limit("openai").unwrap().periods().count(),
+        2
+    );
+    assert!(config.provider_limit("groq").is_none());
+}
+
+#[test]
+fn test_budget_period_start() {
+    let now = chrono::DateTime::parse_from_rfc3339("2026-10-17T15:30:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+    assert_eq!(
+        BudgetPeriod::Daily.start(now).to_rfc3339(),
+        "2026-10-17T00:00:00+00:00"
+    );
+    assert_eq!(
+        BudgetPeriod::Monthly.start(now).to_rfc3339(),
+        "2026-10-01T00:00:00+00:00"
+    );
+}
+
+#[test]
+fn test_budget_decision_stricter() {
+    let downgrade = BudgetDecision::Downgrade(ModelTier::Fast);
+    assert_eq!(BudgetDecision::Allow.stricter(downgrade.clone()), downgrade);
+    assert_eq!(
+        downgrade
+            .clone()
+            .stricter(BudgetDecision::Downgrade(ModelTier::UltraBudget)),
+        BudgetDecision::Downgrade(ModelTier::UltraBudget)
+    );
+    assert!(matches!(
+        downgrade.stricter(BudgetDecision::Block("over".to_string())),
+        BudgetDecision::Block(_)
+    ));
+}
+
+/// Provider that answers with the requested model and fixed usage
+struct EchoProvider;
+
+#[async_trait::async_trait]
+impl LlmProvider for EchoProvider {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn available_models(&self) -> Vec<String> {
+        vec!["gpt-5".to_string()]
+    }
+
+    fn default_model(&self) -> &str {
+        "gpt-5"
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> crate::Result<CompletionResponse> {
+        Ok(CompletionResponse {
+            content: "ok".to_string(),
+            usage: Some(TokenUsage {
+                prompt_tokens: 1_000_000,
+                completion_tokens: 0,
+                total_tokens: 1_000_000,
+            }),
+            finish_reason: Some("stop".to_string()),
+            model: request.model,
+        })
+    }
+
+    async fn complete_with_tools(
+        &self,
+        request: ToolCompletionRequest,
+    ) -> crate::Result<ToolCompletionResponse> {
+        let response = self.complete(request.request).await?;
+        Ok(ToolCompletionResponse {
+            content: Some(response.content),
+            tool_calls: Vec::new(),
+            usage: response.usage,
+            finish_reason: response.finish_reason,
+            model: response.model,
+        })
+    }
+}
+
+fn request(model: &str) -> CompletionRequest {
+    CompletionRequest::new(model).with_message(Message::user("hi"))
+}
+
+#[tokio::test]
+async fn test_metered_provider_records_usage() {
+    let tracker = Arc::new(CostTracker::new());
+    let provider = MeteredProvider::new(Arc::new(EchoProvider), tracker.clone());
+
+    UsageAttribution::user("alice")
+        .scope(provider.complete(request("gpt-5")))
+        .await
+        .unwrap();
+
+    let stream = UsageAttribution::user("bob")
+        .scope(provider.complete_stream(ToolCompletionRequest::new(request("gpt-5"), Vec::new())))
+        .await
+        .unwrap();
+    crate::collect_stream(stream, "gpt-5", |_| {})
+        .await
+        .unwrap();
+    // The streamed call is recorded by a spawned task once the stream is dropped
+    tokio::task::yield_now().await;
+
+    let records = tracker.get_recent_records(10).await;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].provider, "openai");
+    assert_eq!(records[0].user_id.as_deref(), Some("alice"));
+    assert_eq!(records[0].input_tokens, 1_000_000);
+    assert_eq!(records[1].user_id.as_deref(), Some("bob"));
+    assert_eq!(records[1].input_tokens, 1_000_000);
+}
+
+#[tokio::test]
+async fn test_metered_provider_enforces_budgets() {
+    let tracker = Arc::new(CostTracker::new());
+    let provider = MeteredProvider::new(Arc::new(EchoProvider), tracker.clone());
+    let mut alerts = tracker.subscribe_alerts();
+
+    let mut config = BudgetConfig {
+        action: BudgetAction::Downgrade,
+        ..BudgetConfig::default()
+    };
+    config.users.insert(
+        "alice".to_string(),
+        BudgetLimit {
+            daily_usd: Some(1.0),
+            monthly_usd: None,
+        },
+    );
+    tracker.set_budgets(Some(config.clone())).await;
+
+    // $1.25 of gpt-5 input exceeds alice's daily budget
+    let first = UsageAttribution::user("alice")
+        .scope(provider.complete(request("gpt-5")))
+        .await
+        .unwrap();
+    assert_eq!(first.model, "gpt-5");
+
+    let second = UsageAttribution::user("alice")
+        .scope(provider.complete(request("gpt-5")))
+        .await
+        .unwrap();
+    assert_eq!(second.model, ModelTier::Fast.default_model("openai"));
+
+    let alert = alerts.try_recv().unwrap();
+    assert_eq!(alert.scope, BudgetScope::User);
+    assert_eq!(alert.name, "alice");
+    assert!(alert.exceeded);
+
+    // Other users are not affected
+    let other = UsageAttribution::user("bob")
+        .scope(provider.complete(request("gpt-5")))
+        .await
+        .unwrap();
+    assert_eq!(other.model, "gpt-5");
+
+    config.action = BudgetAction::Block;
+    tracker.set_budgets(Some(config)).await;
+    let blocked = UsageAttribution::user("alice")
+        .scope(provider.complete(request("gpt-5")))
+        .await;
+    assert!(matches!(blocked, Err(crate::Error::BudgetExceeded(_))));
+
+    // The same alert is raised only once per period
+    assert!(alerts.try_recv().is_err());
+}
+
+#[test]
+fn test_model_tier_model_for() {
+    assert_eq!(ModelTier::Fast.model_for("openai"), Some("gpt-5-nano"));
+    assert_eq!(ModelTier::Fast.model_for("unknown"), None);
+    assert_eq!(ModelTier::Fast.default_model("unknown"), "gpt-5");
+}
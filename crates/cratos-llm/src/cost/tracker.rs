@@ -1,20 +1,31 @@
 //! Cost Tracker - Usage monitoring
 //!
-//! This module contains the CostTracker for monitoring LLM usage.
+//! This module contains the CostTracker for monitoring LLM usage. Recent
+//! records are kept in memory; with a [`CostLedger`] attached every record
+//! is also persisted, and budgets are checked against the ledger.
 
+use super::attribution::UsageAttribution;
+use super::budget::{BudgetAlert, BudgetConfig, BudgetDecision, BudgetScope};
+use super::ledger::CostLedger;
 use super::pricing::{
     default_pricing, ModelPricing, DEFAULT_INPUT_COST_PER_MILLION, DEFAULT_OUTPUT_COST_PER_MILLION,
 };
 use super::record::{ModelStats, ProviderStats, UsageRecord, UsageStats};
 use super::report::{calculate_savings_potential, CostReport};
+use crate::message::Message;
+use crate::token::count_message_tokens_for;
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
+use tracing::warn;
 
 /// Maximum records to keep in memory by default
 const DEFAULT_MAX_RECORDS: usize = 10_000;
 
+/// Capacity of the budget alert channel
+const ALERT_CHANNEL_CAPACITY: usize = 64;
+
 /// Cost tracker for monitoring LLM usage
 #[derive(Debug)]
 pub struct CostTracker {
@@ -26,6 +37,14 @@ pub struct CostTracker {
     next_id: AtomicU64,
     /// Maximum records to keep in memory
     max_records: usize,
+    /// Persistent ledger (if attached)
+    ledger: RwLock<Option<CostLedger>>,
+    /// Budgets (if configured)
+    budgets: RwLock<Option<BudgetConfig>>,
+    /// Budget alert broadcaster
+    alerts: broadcast::Sender<BudgetAlert>,
+    /// Alerts already raised, so each is sent once per period
+    raised_alerts: std::sync::Mutex<HashSet<String>>,
 }
 
 impl Default for CostTracker {
@@ -43,6 +62,10 @@ impl CostTracker {
             records: RwLock::new(Vec::new()),
             next_id: AtomicU64::new(1),
             max_records: DEFAULT_MAX_RECORDS,
+            ledger: RwLock::new(None),
+            budgets: RwLock::new(None),
+            alerts: broadcast::channel(ALERT_CHANNEL_CAPACITY).0,
+            raised_alerts: std::sync::Mutex::new(HashSet::new()),
         }
     }
 
@@ -53,6 +76,31 @@ impl CostTracker {
         self
     }
 
+    /// Persist records to a ledger from now on
+    pub async fn set_ledger(&self, ledger: CostLedger) {
+        *self.ledger.write().await = Some(ledger);
+    }
+
+    /// The attached ledger
+    pub async fn ledger(&self) -> Option<CostLedger> {
+        self.ledger.read().await.clone()
+    }
+
+    /// Replace the budgets (`None` disables budget checks)
+    pub async fn set_budgets(&self, budgets: Option<BudgetConfig>) {
+        *self.budgets.write().await = budgets;
+    }
+
+    /// The configured budgets
+    pub async fn budgets(&self) -> Option<BudgetConfig> {
+        self.budgets.read().await.clone()
+    }
+
+    /// Subscribe to budget alerts
+    pub fn subscribe_alerts(&self) -> broadcast::Receiver<BudgetAlert> {
+        self.alerts.subscribe()
+    }
+
     /// Update pricing for a model
     pub async fn update_pricing(&self, model: &str, pricing: ModelPricing) {
         let mut prices = self.pricing.write().await;
@@ -77,7 +125,30 @@ impl CostTracker {
         }
     }
 
+    /// Estimate the cost of a request before sending it
+    ///
+    /// The prompt is counted with the tokenizer of the provider/model pair;
+    /// the output is assumed to use all of `max_output_tokens`.
+    pub async fn estimate_request_cost(
+        &self,
+        provider: &str,
+        model: &str,
+        messages: &[Message],
+        max_output_tokens: u32,
+    ) -> f64 {
+        let input_tokens = count_message_tokens_for(provider, model, messages);
+        self.estimate_cost(
+            model,
+            u32::try_from(input_tokens).unwrap_or(u32::MAX),
+            max_output_tokens,
+        )
+        .await
+    }
+
     /// Record a usage event
+    ///
+    /// The record is attributed to the [`UsageAttribution`] of the current
+    /// task; `execution_id` overrides its execution ID.
     #[allow(clippy::too_many_arguments)]
     pub async fn record_usage(
         &self,
@@ -88,6 +159,34 @@ impl CostTracker {
         latency_ms: u64,
         success: bool,
         execution_id: Option<String>,
+    ) -> UsageRecord {
+        let mut attribution = UsageAttribution::current();
+        if execution_id.is_some() {
+            attribution.execution_id = execution_id;
+        }
+        self.record_attributed(
+            provider,
+            model,
+            input_tokens,
+            output_tokens,
+            latency_ms,
+            success,
+            attribution,
+        )
+        .await
+    }
+
+    /// Record a usage event with an explicit attribution
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_attributed(
+        &self,
+        provider: &str,
+        model: &str,
+        input_tokens: u32,
+        output_tokens: u32,
+        latency_ms: u64,
+        success: bool,
+        attribution: UsageAttribution,
     ) -> UsageRecord {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let estimated_cost = self.estimate_cost(model, input_tokens, output_tokens).await;
@@ -95,7 +194,10 @@ impl CostTracker {
         let record = UsageRecord {
             id,
             timestamp: Utc::now(),
-            execution_id,
+            execution_id: attribution.execution_id,
+            user_id: attribution.user_id,
+            persona: attribution.persona,
+            channel: attribution.channel,
             provider: provider.to_string(),
             model: model.to_string(),
             input_tokens,
@@ -105,6 +207,12 @@ impl CostTracker {
             success,
         };
 
+        if let Some(ledger) = self.ledger().await {
+            if let Err(e) = ledger.insert(&record).await {
+                warn!(error = %e, "Failed to persist LLM usage record");
+            }
+        }
+
         let mut records = self.records.write().await;
         records.push(record.clone());
 
@@ -117,6 +225,90 @@ impl CostTracker {
         record
     }
 
+    /// Total cost since a point in time, optionally for one user or provider
+    ///
+    /// Uses the ledger when attached, otherwise the in-memory records.
+    pub async fn spent_since(
+        &self,
+        since: DateTime<Utc>,
+        user_id: Option<&str>,
+        provider: Option<&str>,
+    ) -> f64 {
+        if let Some(ledger) = self.ledger().await {
+            match ledger.total_cost(since, user_id, provider).await {
+                Ok(total) => return total,
+                Err(e) => warn!(error = %e, "Failed to read LLM spending from the ledger"),
+            }
+        }
+
+        let records = self.records.read().await;
+        records
+            .iter()
+            .filter(|r| r.timestamp >= since)
+            .filter(|r| user_id.is_none_or(|u| r.user_id.as_deref() == Some(u)))
+            .filter(|r| provider.is_none_or(|p| r.provider == p))
+            .map(|r| r.estimated_cost)
+            .sum()
+    }
+
+    /// Check the budgets of a call to `provider` made for `user_id`
+    ///
+    /// Raises an alert (once per budget, period and level) for every budget
+    /// that is close to or over its limit, and returns the strictest action
+    /// of the exceeded ones.
+    pub async fn check_budget(&self, provider: &str, user_id: Option<&str>) -> BudgetDecision {
+        let Some(config) = self.budgets().await else {
+            return BudgetDecision::Allow;
+        };
+
+        let mut limits = Vec::new();
+        if let Some(limit) = user_id.and_then(|u| config.user_limit(u)) {
+            limits.push((BudgetScope::User, user_id.unwrap_or_default(), limit));
+        }
+        if let Some(limit) = config.provider_limit(provider) {
+            limits.push((BudgetScope::Provider, provider, limit));
+        }
+
+        let now = Utc::now();
+        let mut decision = BudgetDecision::Allow;
+        for (scope, name, limit) in limits {
+            for (period, limit_usd) in limit.periods() {
+                let since = period.start(now);
+                let spent = match scope {
+                    BudgetScope::User => self.spent_since(since, Some(name), None).await,
+                    BudgetScope::Provider => self.spent_since(since, None, Some(name)).await,
+                };
+                let Some(alert) = config.evaluate(scope, name, period, limit_usd, spent) else {
+                    continue;
+                };
+                decision = decision.stricter(config.decision_for(&alert));
+                self.raise_alert(alert, since);
+            }
+        }
+        decision
+    }
+
+    /// Broadcast an alert unless it was already raised in this period
+    fn raise_alert(&self, alert: BudgetAlert, period_start: DateTime<Utc>) {
+        let key = format!(
+            "{}:{}:{}:{}:{}",
+            alert.scope,
+            alert.name,
+            alert.period,
+            period_start.timestamp(),
+            alert.exceeded
+        );
+        let first = self
+            .raised_alerts
+            .lock()
+            .map(|mut raised| raised.insert(key))
+            .unwrap_or(true);
+        if first {
+            warn!("{}", alert.message());
+            let _ = self.alerts.send(alert);
+        }
+    }
+
     /// Get usage statistics for a time range
     pub async fn get_stats(&self, since: Option<DateTime<Utc>>) -> UsageStats {
         let records = self.records.read().await;
@@ -44,6 +44,10 @@ pub enum Error {
     /// OAuth error
     #[error("oauth error: {0}")]
     OAuth(String),
+
+    /// Spending budget exceeded and configured to block
+    #[error("budget exceeded: {0}")]
+    BudgetExceeded(String),
 }
 
 /// Result type alias
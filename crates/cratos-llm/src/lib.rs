@@ -14,6 +14,7 @@
 //! - DeepSeek: Ultra-low-cost provider ($0.03 ~ $0.55/1M tokens)
 //! - SiliconFlow: Cheapest provider ($0.03 ~ $0.09/1M tokens)
 //! - Fireworks: Fast inference for open-source models
+//! - Stream: Token-level streaming (text deltas + tool-call fragments)
 //! - Embeddings: Vector embeddings for semantic search (feature: embeddings)
 
 #![forbid(unsafe_code)]
@@ -48,13 +49,15 @@ pub mod quota;
 pub use providers::qwen;
 pub mod router;
 pub use providers::siliconflow;
+pub mod stream;
 pub mod token;
 pub mod tools;
 pub mod util;
 
 pub use cost::{
-    global_tracker, CostReport, CostTracker, ModelPricing, SavingsPotential, UsageRecord,
-    UsageStats,
+    global_tracker, BudgetAction, BudgetAlert, BudgetConfig, BudgetLimit, CostGroup, CostLedger,
+    CostQuery, CostReport, CostSummaryRow, CostTracker, MeteredProvider, ModelPricing,
+    SavingsPotential, UsageAttribution, UsageRecord, UsageStats,
 };
 pub use error::{Error, Result};
 pub use gemini_quota::start_gemini_quota_poller;
@@ -70,6 +73,13 @@ pub use router::{
     TokenUsage, ToolCall, ToolChoice, ToolCompletionRequest, ToolCompletionResponse,
     ToolDefinition, TOKEN_COUNTER,
 };
+pub use stream::{
+    collect_stream, CompletionStream, SseEvent, SseParser, StreamAccumulator, StreamChunk,
+};
+pub use token::{
+    count_message_tokens_for, global_tokenizers, tokenizer_for, Tokenizer, TokenizerKey,
+    TokenizerRegistry,
+};
 
 // Re-export provider types
 pub use anthropic::{AnthropicConfig, AnthropicProvider};
@@ -8,6 +8,8 @@ pub mod convert;
 pub mod provider;
 /// Security and sanitization utilities
 pub mod security;
+/// Streaming response decoding
+pub(crate) mod stream;
 /// API types and configuration
 pub mod types;
 
@@ -1,3 +1,4 @@
+use futures::StreamExt;
 use reqwest::Client;
 use tracing::{debug, instrument};
 use crate::error::{Error, Result};
@@ -5,12 +6,14 @@ use crate::router::{
     CompletionRequest, CompletionResponse, LlmProvider, TokenUsage, ToolCall,
     ToolCompletionRequest, ToolCompletionResponse,
 };
+use crate::stream::{sse_stream, CompletionStream};
 use super::types::{
     AnthropicConfig, AnthropicRequest, AnthropicResponse, AnthropicError,
     ResponseContentBlock, API_VERSION, MODELS,
 };
 use super::convert::{convert_messages, convert_tool, convert_tool_choice};
 use super::security::sanitize_api_error;
+use super::stream::StreamDecoder;
 
 /// Anthropic Claude provider
 pub struct AnthropicProvider {
@@ -35,8 +38,8 @@ impl AnthropicProvider {
         Self::new(config)
     }
 
-    /// Send request to Anthropic API
-    async fn send_request(&self, request: AnthropicRequest) -> Result<AnthropicResponse> {
+    /// POST to the Messages API, returning the response once the status is OK
+    async fn post_messages(&self, request: &AnthropicRequest) -> Result<reqwest::Response> {
         let url = format!("{}/v1/messages", self.config.base_url);
 
         debug!("Sending request to Anthropic: {}", url);
@@ -47,7 +50,7 @@ impl AnthropicProvider {
             .header("x-api-key", &self.config.api_key)
             .header("anthropic-version", API_VERSION)
             .header("content-type", "application/json")
-            .json(&request)
+            .json(request)
             .send()
             .await
             .map_err(|e| Error::Network(e.to_string()))?;
@@ -58,29 +61,40 @@ impl AnthropicProvider {
             .await;
 
         let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
         let body = response
             .text()
             .await
             .map_err(|e| Error::Network(e.to_string()))?;
 
-        if !status.is_success() {
-            // Try to parse error response
-            if let Ok(error) = serde_json::from_str::<AnthropicError>(&body) {
-                if status.as_u16() == 429 {
-                    return Err(Error::RateLimit);
-                }
-                // SECURITY: Sanitize error messages
-                return Err(Error::Api(sanitize_api_error(&format!(
-                    "{}: {}",
-                    error.error.r#type, error.error.message
-                ))));
+        // Try to parse error response
+        if let Ok(error) = serde_json::from_str::<AnthropicError>(&body) {
+            if status.as_u16() == 429 {
+                return Err(Error::RateLimit);
             }
-            // SECURITY: Don't expose raw HTTP response body
+            // SECURITY: Sanitize error messages
             return Err(Error::Api(sanitize_api_error(&format!(
-                "HTTP {}: {}",
-                status, body
+                "{}: {}",
+                error.error.r#type, error.error.message
             ))));
         }
+        // SECURITY: Don't expose raw HTTP response body
+        Err(Error::Api(sanitize_api_error(&format!(
+            "HTTP {}: {}",
+            status, body
+        ))))
+    }
+
+    /// Send request to Anthropic API
+    async fn send_request(&self, request: AnthropicRequest) -> Result<AnthropicResponse> {
+        let response = self.post_messages(&request).await?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
 
         serde_json::from_str(&body).map_err(|e| Error::InvalidResponse(e.to_string()))
     }
@@ -122,6 +136,7 @@ impl LlmProvider for AnthropicProvider {
             temperature: request.temperature,
             tools: None,
             tool_choice: None,
+            stream: false,
         };
 
         let response = self.send_request(anthropic_request).await?;
@@ -177,6 +192,7 @@ impl LlmProvider for AnthropicProvider {
             temperature: request.request.temperature,
             tools: Some(tools),
             tool_choice: convert_tool_choice(&request.tool_choice),
+            stream: false,
         };
 
         let response = self.send_request(anthropic_request).await?;
@@ -216,4 +232,55 @@ impl LlmProvider for AnthropicProvider {
             model: response.model,
         })
     }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    #[instrument(skip(self, request), fields(model = %request.request.model, tools = request.tools.len()))]
+    async fn complete_stream(&self, request: ToolCompletionRequest) -> Result<CompletionStream> {
+        let model = if request.request.model.is_empty() {
+            &self.config.default_model
+        } else {
+            &request.request.model
+        };
+
+        let (system, messages) = convert_messages(&request.request.messages);
+
+        let (tools, tool_choice) = if request.tools.is_empty() {
+            (None, None)
+        } else {
+            (
+                Some(request.tools.iter().map(convert_tool).collect()),
+                convert_tool_choice(&request.tool_choice),
+            )
+        };
+
+        let anthropic_request = AnthropicRequest {
+            model: model.to_string(),
+            max_tokens: request
+                .request
+                .max_tokens
+                .unwrap_or(self.config.default_max_tokens),
+            system,
+            messages,
+            temperature: request.request.temperature,
+            tools,
+            tool_choice,
+            stream: true,
+        };
+
+        let response = self.post_messages(&anthropic_request).await?;
+
+        Ok(sse_stream(response)
+            .scan(StreamDecoder::default(), |decoder, event| {
+                let chunks = match event.and_then(|e| decoder.decode(&e.data)) {
+                    Ok(chunks) => chunks.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                futures::future::ready(Some(futures::stream::iter(chunks)))
+            })
+            .flatten()
+            .boxed())
+    }
 }
@@ -0,0 +1,81 @@
+use super::security::sanitize_api_error;
+use super::types::{AnthropicStreamEvent, StreamContentBlock, StreamDelta};
+use crate::error::{Error, Result};
+use crate::router::TokenUsage;
+use crate::stream::StreamChunk;
+
+/// Decodes Anthropic Messages API stream events into [`StreamChunk`]s.
+///
+/// Input tokens are only reported in `message_start` and output tokens in
+/// `message_delta`, so the decoder keeps the former until the latter arrives.
+#[derive(Debug, Default)]
+pub(crate) struct StreamDecoder {
+    input_tokens: u32,
+}
+
+impl StreamDecoder {
+    /// Decode the `data` payload of one SSE event
+    pub fn decode(&mut self, data: &str) -> Result<Vec<StreamChunk>> {
+        let event: AnthropicStreamEvent =
+            serde_json::from_str(data).map_err(|e| Error::InvalidResponse(e.to_string()))?;
+
+        let chunks = match event {
+            AnthropicStreamEvent::MessageStart { message } => {
+                self.input_tokens = message.usage.map(|u| u.input_tokens).unwrap_or(0);
+                vec![StreamChunk::Metadata {
+                    model: Some(message.model),
+                    finish_reason: None,
+                    usage: None,
+                }]
+            }
+            AnthropicStreamEvent::ContentBlockStart {
+                index,
+                content_block: StreamContentBlock::ToolUse { id, name },
+            } => vec![StreamChunk::ToolCallDelta {
+                index,
+                id: Some(id),
+                name: Some(name),
+                arguments: String::new(),
+                thought_signature: None,
+            }],
+            AnthropicStreamEvent::ContentBlockDelta { index, delta } => match delta {
+                StreamDelta::TextDelta { text } => vec![StreamChunk::TextDelta(text)],
+                StreamDelta::InputJsonDelta { partial_json } => {
+                    vec![StreamChunk::ToolCallDelta {
+                        index,
+                        id: None,
+                        name: None,
+                        arguments: partial_json,
+                        thought_signature: None,
+                    }]
+                }
+                StreamDelta::Other => Vec::new(),
+            },
+            AnthropicStreamEvent::MessageDelta { delta, usage } => {
+                let output_tokens = usage.map(|u| u.output_tokens).unwrap_or(0);
+                vec![StreamChunk::Metadata {
+                    model: None,
+                    finish_reason: delta.stop_reason,
+                    usage: Some(TokenUsage {
+                        prompt_tokens: self.input_tokens,
+                        completion_tokens: output_tokens,
+                        total_tokens: self.input_tokens + output_tokens,
+                    }),
+                }]
+            }
+            AnthropicStreamEvent::Error { error } => {
+                if error.r#type == "overloaded_error" || error.r#type == "rate_limit_error" {
+                    return Err(Error::RateLimit);
+                }
+                return Err(Error::Api(sanitize_api_error(&format!(
+                    "{}: {}",
+                    error.r#type, error.message
+                ))));
+            }
+            AnthropicStreamEvent::ContentBlockStart { .. } | AnthropicStreamEvent::Other => {
+                Vec::new()
+            }
+        };
+        Ok(chunks)
+    }
+}
@@ -1,7 +1,9 @@
 use super::types::{AnthropicConfig, MODELS};
 use super::convert::convert_messages;
 use super::security::sanitize_api_error;
+use super::stream::StreamDecoder;
 use crate::router::Message;
+use crate::stream::StreamChunk;
 use crate::util::mask_api_key;
 use std::time::Duration;
 
@@ -70,3 +72,44 @@ fn test_config_debug_masks_key() {
     assert!(!debug_str.contains("1234567890"));
     assert!(debug_str.contains("sk-a...ghij"));
 }
+
+#[test]
+fn test_stream_decoder() {
+    let mut decoder = StreamDecoder::default();
+
+    decoder
+        .decode(r#"{"type":"message_start","message":{"model":"claude-sonnet-4","usage":{"input_tokens":20,"output_tokens":1}}}"#)
+        .unwrap();
+    let chunks = decoder
+        .decode(r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#)
+        .unwrap();
+    assert_eq!(chunks, vec![StreamChunk::TextDelta("Hi".to_string())]);
+
+    let chunks = decoder
+        .decode(r#"{"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"exec","input":{}}}"#)
+        .unwrap();
+    assert!(matches!(
+        &chunks[0],
+        StreamChunk::ToolCallDelta { index: 1, id: Some(id), .. } if id == "toolu_1"
+    ));
+    let chunks = decoder
+        .decode(r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"cmd\":"}}"#)
+        .unwrap();
+    assert!(matches!(
+        &chunks[0],
+        StreamChunk::ToolCallDelta { index: 1, arguments, .. } if arguments == "{\"cmd\":"
+    ));
+
+    let chunks = decoder
+        .decode(r#"{"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":5}}"#)
+        .unwrap();
+    assert!(matches!(
+        &chunks[0],
+        StreamChunk::Metadata { usage: Some(u), .. } if u.prompt_tokens == 20 && u.total_tokens == 25
+    ));
+
+    assert!(decoder.decode(r#"{"type":"ping"}"#).unwrap().is_empty());
+    assert!(decoder
+        .decode(r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#)
+        .is_err());
+}
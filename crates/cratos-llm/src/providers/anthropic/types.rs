@@ -129,6 +129,8 @@ pub(crate) struct AnthropicRequest {
     pub tools: Option<Vec<AnthropicTool>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_choice: Option<AnthropicToolChoice>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stream: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -221,3 +223,80 @@ pub(crate) struct AnthropicErrorDetail {
     pub r#type: String,
     pub message: String,
 }
+
+// ============================================================================
+// Streaming Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum AnthropicStreamEvent {
+    MessageStart {
+        message: StreamMessageStart,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: StreamContentBlock,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: StreamDelta,
+    },
+    MessageDelta {
+        delta: StreamMessageDelta,
+        #[serde(default)]
+        usage: Option<StreamUsage>,
+    },
+    Error {
+        error: AnthropicErrorDetail,
+    },
+    /// `content_block_stop`, `message_stop`, `ping` and future event types
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct StreamMessageStart {
+    pub model: String,
+    #[serde(default)]
+    pub usage: Option<StreamUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum StreamContentBlock {
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    /// Text and thinking blocks start empty; content arrives as deltas
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum StreamDelta {
+    TextDelta {
+        text: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct StreamMessageDelta {
+    #[serde(default)]
+    pub stop_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct StreamUsage {
+    #[serde(default)]
+    pub input_tokens: u32,
+    #[serde(default)]
+    pub output_tokens: u32,
+}
@@ -7,6 +7,7 @@ mod convert;
 mod provider;
 mod schema;
 mod security;
+mod stream;
 mod types;
 
 #[cfg(test)]
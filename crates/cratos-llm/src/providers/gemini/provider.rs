@@ -10,6 +10,9 @@ use crate::router::{
     CompletionRequest, CompletionResponse, LlmProvider, TokenUsage, ToolCall,
     ToolCompletionRequest, ToolCompletionResponse,
 };
+use super::stream::StreamDecoder;
+use crate::stream::{sse_stream, CompletionStream};
+use futures::StreamExt;
 use reqwest::Client;
 use tracing::{debug, instrument};
 
@@ -361,23 +364,47 @@ impl GeminiProvider {
         model: &str,
         request: &GeminiRequest,
     ) -> Result<GeminiResponse> {
+        let response = self.post_once(model, request, false).await?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+
+        serde_json::from_str(&body).map_err(|e| Error::InvalidResponse(format!("{}: {}", e, body)))
+    }
+
+    /// POST to `generateContent` (or `streamGenerateContent` as SSE when
+    /// `stream` is set), returning the response once the status is OK.
+    async fn post_once(
+        &self,
+        model: &str,
+        request: &GeminiRequest,
+        stream: bool,
+    ) -> Result<reqwest::Response> {
         // SECURITY: Don't log the full URL (may contain API key)
         debug!(
-            "Sending request to Gemini model: {} (auth_source={:?})",
-            model, self.config.auth_source
+            "Sending request to Gemini model: {} (auth_source={:?}, stream={})",
+            model, self.config.auth_source, stream
         );
 
+        let method = if stream {
+            "streamGenerateContent?alt=sse"
+        } else {
+            "generateContent"
+        };
+
         let current_auth = self.current_auth();
         let mut request_builder = match &current_auth {
             GeminiAuth::ApiKey(key) => {
+                let sep = if stream { '&' } else { '?' };
                 let url = format!(
-                    "{}/models/{}:generateContent?key={}",
-                    self.config.base_url, model, key
+                    "{}/models/{}:{}{}key={}",
+                    self.config.base_url, model, method, sep, key
                 );
                 self.client.post(&url)
             }
             GeminiAuth::OAuth(token) => {
-                let url = format!("{}/models/{}:generateContent", self.config.base_url, model);
+                let url = format!("{}/models/{}:{}", self.config.base_url, model, method);
                 let mut rb = self
                     .client
                     .post(&url)
@@ -404,100 +431,100 @@ impl GeminiProvider {
             .await;
 
         let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
         let body = response
             .text()
             .await
             .map_err(|e| Error::Network(e.to_string()))?;
 
-        if !status.is_success() {
-            // Log raw status for debugging (no sensitive data in status code)
-            tracing::warn!(status = %status, "Gemini API error response");
-            if let Ok(error) = serde_json::from_str::<GeminiError>(&body) {
-                // Log error status/code/message for debugging (no API keys in these fields)
+        // Log raw status for debugging (no sensitive data in status code)
+        tracing::warn!(status = %status, "Gemini API error response");
+        if let Ok(error) = serde_json::from_str::<GeminiError>(&body) {
+            // Log error status/code/message for debugging (no API keys in these fields)
+            tracing::warn!(
+                error_status = %error.error.status,
+                error_code = error.error.code,
+                error_message = %error.error.message,
+                "Gemini API error detail"
+            );
+            if status.as_u16() == 403
+                && error
+                    .error
+                    .message
+                    .contains("insufficient authentication scopes")
+            {
                 tracing::warn!(
-                    error_status = %error.error.status,
-                    error_code = error.error.code,
-                    error_message = %error.error.message,
-                    "Gemini API error detail"
+                    auth_source = ?self.config.auth_source,
+                    "OAuth token has insufficient scopes — will attempt authentication fallback"
                 );
-                if status.as_u16() == 403
-                    && error
-                        .error
-                        .message
-                        .contains("insufficient authentication scopes")
-                {
-                    tracing::warn!(
-                        auth_source = ?self.config.auth_source,
-                        "OAuth token has insufficient scopes — will attempt authentication fallback"
-                    );
-                    // Return error with "authentication" keyword to trigger
-                    // retry+fallback in send_request() (CratosOAuth → Gemini CLI)
-                    return Err(Error::Api(
-                        "authentication failed: insufficient scopes for Gemini API".to_string(),
-                    ));
-                }
-                if status.as_u16() == 429 {
-                    let mut retry_secs: u64 = 0;
-                    // Parse retryDelay from Gemini error details if present
-                    if let Some(details) = error.error.details.as_ref() {
-                        for detail in details {
-                            if let Some(delay) = detail.get("retryDelay").and_then(|v| v.as_str()) {
-                                if let Some(secs_str) = delay.strip_suffix('s') {
-                                    if let Ok(secs) = secs_str.parse::<u64>() {
-                                        retry_secs = secs;
-                                    }
+                // Return error with "authentication" keyword to trigger
+                // retry+fallback in send_request() (CratosOAuth → Gemini CLI)
+                return Err(Error::Api(
+                    "authentication failed: insufficient scopes for Gemini API".to_string(),
+                ));
+            }
+            if status.as_u16() == 429 {
+                let mut retry_secs: u64 = 0;
+                // Parse retryDelay from Gemini error details if present
+                if let Some(details) = error.error.details.as_ref() {
+                    for detail in details {
+                        if let Some(delay) = detail.get("retryDelay").and_then(|v| v.as_str()) {
+                            if let Some(secs_str) = delay.strip_suffix('s') {
+                                if let Ok(secs) = secs_str.parse::<u64>() {
+                                    retry_secs = secs;
                                 }
                             }
                         }
                     }
-                    // Also parse from message: "Your quota will reset after Xs."
-                    if retry_secs == 0 {
-                        if let Some(after_pos) = error.error.message.find("reset after ") {
-                            let rest = &error.error.message[after_pos + 12..];
-                            if let Some(s_pos) = rest.find('s') {
-                                if let Ok(secs) = rest[..s_pos].trim().parse::<u64>() {
-                                    retry_secs = secs;
-                                }
+                }
+                // Also parse from message: "Your quota will reset after Xs."
+                if retry_secs == 0 {
+                    if let Some(after_pos) = error.error.message.find("reset after ") {
+                        let rest = &error.error.message[after_pos + 12..];
+                        if let Some(s_pos) = rest.find('s') {
+                            if let Ok(secs) = rest[..s_pos].trim().parse::<u64>() {
+                                retry_secs = secs;
                             }
                         }
                     }
-                    if retry_secs > 0 {
-                        self.last_retry_after
-                            .store(retry_secs, std::sync::atomic::Ordering::Relaxed);
-                        crate::quota::global_quota_tracker()
-                            .update_from_retry_after("gemini", retry_secs)
-                            .await;
-                    }
-                    return Err(Error::RateLimit);
                 }
-                // 5xx server errors — retryable
-                if status.is_server_error() {
-                    return Err(Error::ServerError(sanitize_api_error(&format!(
-                        "{}: {}",
-                        error.error.status, error.error.message
-                    ))));
+                if retry_secs > 0 {
+                    self.last_retry_after
+                        .store(retry_secs, std::sync::atomic::Ordering::Relaxed);
+                    crate::quota::global_quota_tracker()
+                        .update_from_retry_after("gemini", retry_secs)
+                        .await;
                 }
-                // SECURITY: Sanitize error messages
-                return Err(Error::Api(sanitize_api_error(&format!(
-                    "{}: {}",
-                    error.error.status, error.error.message
-                ))));
+                return Err(Error::RateLimit);
             }
-            // 5xx without parseable error body — still retryable
+            // 5xx server errors — retryable
             if status.is_server_error() {
                 return Err(Error::ServerError(sanitize_api_error(&format!(
-                    "HTTP {}",
-                    status
+                    "{}: {}",
+                    error.error.status, error.error.message
                 ))));
             }
-            // SECURITY: Don't expose raw HTTP response body
+            // SECURITY: Sanitize error messages
             return Err(Error::Api(sanitize_api_error(&format!(
-                "HTTP {}: {}",
-                status, body
+                "{}: {}",
+                error.error.status, error.error.message
             ))));
         }
-
-        serde_json::from_str(&body).map_err(|e| Error::InvalidResponse(format!("{}: {}", e, body)))
+        // 5xx without parseable error body — still retryable
+        if status.is_server_error() {
+            return Err(Error::ServerError(sanitize_api_error(&format!(
+                "HTTP {}",
+                status
+            ))));
+        }
+        // SECURITY: Don't expose raw HTTP response body
+        Err(Error::Api(sanitize_api_error(&format!(
+            "HTTP {}: {}",
+            status, body
+        ))))
     }
 }
 
@@ -673,4 +700,69 @@ impl LlmProvider for GeminiProvider {
             model: actual_model,
         })
     }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    #[instrument(skip(self, request), fields(model = %request.request.model, tools = request.tools.len()))]
+    async fn complete_stream(&self, request: ToolCompletionRequest) -> Result<CompletionStream> {
+        let model = if request.request.model.is_empty() {
+            &self.config.default_model
+        } else {
+            &request.request.model
+        };
+
+        let (system_instruction, contents) = convert_messages(&request.request.messages);
+
+        let generation_config = Some(GenerationConfig {
+            temperature: request.request.temperature,
+            max_output_tokens: request
+                .request
+                .max_tokens
+                .or(Some(self.config.default_max_tokens)),
+            stop_sequences: request.request.stop.clone(),
+        });
+
+        let (tools, tool_config) = if request.tools.is_empty() {
+            (None, None)
+        } else {
+            (
+                Some(convert_tools(&request.tools)),
+                convert_tool_choice(&request.tool_choice, &request.tools),
+            )
+        };
+
+        let gemini_request = GeminiRequest {
+            contents,
+            system_instruction,
+            generation_config,
+            tools,
+            tool_config,
+        };
+
+        // No retry/downgrade loop here: once tokens have been emitted a retry
+        // would duplicate output, so rate limits surface to the caller.
+        let response = match self.post_once(model, &gemini_request, true).await {
+            Err(Error::Api(msg)) if msg.contains("authentication") => {
+                if !self.try_refresh_cli_token().await {
+                    return Err(Error::Api(msg));
+                }
+                tracing::info!("Retrying stream after token refresh");
+                self.post_once(model, &gemini_request, true).await?
+            }
+            other => other?,
+        };
+
+        Ok(sse_stream(response)
+            .scan(StreamDecoder::new(model), |decoder, event| {
+                let chunks = match event.and_then(|e| decoder.decode(&e.data)) {
+                    Ok(chunks) => chunks.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                futures::future::ready(Some(futures::stream::iter(chunks)))
+            })
+            .flatten()
+            .boxed())
+    }
 }
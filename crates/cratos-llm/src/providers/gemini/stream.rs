@@ -0,0 +1,77 @@
+//! Gemini streaming response decoding
+
+use super::types::{GeminiPart, GeminiResponse};
+use crate::error::{Error, Result};
+use crate::router::TokenUsage;
+use crate::stream::StreamChunk;
+
+/// Decodes `streamGenerateContent` SSE payloads into [`StreamChunk`]s.
+///
+/// Each payload is a partial `GenerateContentResponse`. Function calls arrive
+/// whole and without IDs, so the decoder numbers them across payloads.
+#[derive(Debug)]
+pub(crate) struct StreamDecoder {
+    model: String,
+    next_tool_index: usize,
+}
+
+impl StreamDecoder {
+    pub(crate) fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            next_tool_index: 0,
+        }
+    }
+
+    /// Decode the `data` payload of one SSE event
+    pub(crate) fn decode(&mut self, data: &str) -> Result<Vec<StreamChunk>> {
+        let response: GeminiResponse =
+            serde_json::from_str(data).map_err(|e| Error::InvalidResponse(e.to_string()))?;
+
+        let mut chunks = Vec::new();
+        let mut finish_reason = None;
+
+        if let Some(candidate) = response.candidates.into_iter().next() {
+            for part in candidate.content.parts {
+                match part {
+                    GeminiPart::Text { text } if !text.is_empty() => {
+                        chunks.push(StreamChunk::TextDelta(text));
+                    }
+                    GeminiPart::FunctionCall {
+                        function_call,
+                        thought_signature,
+                    } => {
+                        let index = self.next_tool_index;
+                        self.next_tool_index += 1;
+                        chunks.push(StreamChunk::ToolCallDelta {
+                            index,
+                            id: Some(uuid::Uuid::new_v4().to_string()), // Gemini doesn't provide IDs
+                            name: Some(function_call.name),
+                            arguments: serde_json::to_string(&function_call.args)
+                                .unwrap_or_else(|_| "{}".to_string()),
+                            thought_signature,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+            finish_reason = candidate.finish_reason;
+        }
+
+        let usage = response.usage_metadata.map(|u| TokenUsage {
+            prompt_tokens: u.prompt_token_count,
+            completion_tokens: u.candidates_token_count.unwrap_or(0),
+            total_tokens: u.total_token_count,
+        });
+
+        if finish_reason.is_some() || usage.is_some() {
+            chunks.push(StreamChunk::Metadata {
+                model: Some(self.model.clone()),
+                finish_reason,
+                usage,
+            });
+        }
+
+        Ok(chunks)
+    }
+}
@@ -4,8 +4,10 @@ use super::config::{downgrade_model, GeminiAuth, GeminiConfig, DEFAULT_BASE_URL,
 use super::convert::convert_tools;
 use super::schema::strip_unsupported_schema_fields;
 use super::security::sanitize_api_error;
+use super::stream::StreamDecoder;
 use crate::cli_auth::AuthSource;
 use crate::router::{Message, ToolDefinition};
+use crate::stream::StreamChunk;
 use crate::util::mask_api_key;
 use std::time::Duration;
 
@@ -185,3 +187,29 @@ fn test_convert_tools_strips_unsupported_fields() {
     // type should remain
     assert_eq!(params["properties"]["count"]["type"], "integer");
 }
+
+#[test]
+fn test_stream_decoder() {
+    let mut decoder = StreamDecoder::new("gemini-2.5-flash");
+
+    let chunks = decoder
+        .decode(r#"{"candidates":[{"content":{"role":"model","parts":[{"text":"Hel"}]}}]}"#)
+        .unwrap();
+    assert_eq!(chunks, vec![StreamChunk::TextDelta("Hel".to_string())]);
+
+    let chunks = decoder
+        .decode(
+            r#"{"candidates":[{"content":{"role":"model","parts":[{"functionCall":{"name":"exec","args":{"cmd":"ls"}},"thoughtSignature":"sig"}]},"finishReason":"STOP"}],"usageMetadata":{"promptTokenCount":7,"candidatesTokenCount":2,"totalTokenCount":9}}"#,
+        )
+        .unwrap();
+    assert_eq!(chunks.len(), 2);
+    assert!(matches!(
+        &chunks[0],
+        StreamChunk::ToolCallDelta { index: 0, name: Some(name), thought_signature: Some(sig), .. }
+            if name == "exec" && sig == "sig"
+    ));
+    assert!(matches!(
+        &chunks[1],
+        StreamChunk::Metadata { usage: Some(u), .. } if u.total_tokens == 9
+    ));
+}
@@ -119,6 +119,8 @@ pub(crate) struct FunctionCallingConfig {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct GeminiResponse {
+    /// Absent on usage-only stream chunks
+    #[serde(default)]
     pub candidates: Vec<Candidate>,
     #[serde(default)]
     pub usage_metadata: Option<UsageMetadata>,
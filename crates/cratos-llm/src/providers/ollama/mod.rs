@@ -8,6 +8,7 @@
 pub mod convert;
 pub mod provider;
 pub mod security;
+pub(crate) mod stream;
 pub mod types;
 
 #[cfg(test)]
@@ -5,11 +5,14 @@ use crate::router::{
 };
 use crate::providers::ollama::{
     convert, security,
+    stream::StreamDecoder,
     types::{
         OllamaChatRequest, OllamaChatResponse, OllamaConfig, OllamaError, OllamaOptions,
         OllamaTagsResponse, SUGGESTED_MODELS,
     },
 };
+use crate::stream::{line_stream, CompletionStream};
+use futures::StreamExt;
 use reqwest::Client;
 use tracing::{debug, instrument};
 
@@ -86,8 +89,8 @@ impl OllamaProvider {
         Ok(models)
     }
 
-    /// Send request to Ollama API
-    async fn send_request(&self, request: OllamaChatRequest) -> Result<OllamaChatResponse> {
+    /// POST to the chat endpoint, returning the response once the status is OK
+    async fn post_chat(&self, request: &OllamaChatRequest) -> Result<reqwest::Response> {
         let url = format!("{}/api/chat", self.config.base_url);
 
         debug!("Sending request to Ollama: {}", request.model);
@@ -95,7 +98,7 @@ impl OllamaProvider {
         let response = self
             .client
             .post(&url)
-            .json(&request)
+            .json(request)
             .send()
             .await
             .map_err(|e| {
@@ -112,22 +115,33 @@ impl OllamaProvider {
             })?;
 
         let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
         let body = response
             .text()
             .await
             .map_err(|e| Error::Network(e.to_string()))?;
 
-        if !status.is_success() {
-            if let Ok(error) = serde_json::from_str::<OllamaError>(&body) {
-                // SECURITY: Sanitize error messages
-                return Err(Error::Api(security::sanitize_api_error(&error.error)));
-            }
-            // SECURITY: Don't expose raw HTTP response body
-            return Err(Error::Api(security::sanitize_api_error(&format!(
-                "HTTP {}: {}",
-                status, body
-            ))));
+        if let Ok(error) = serde_json::from_str::<OllamaError>(&body) {
+            // SECURITY: Sanitize error messages
+            return Err(Error::Api(security::sanitize_api_error(&error.error)));
         }
+        // SECURITY: Don't expose raw HTTP response body
+        Err(Error::Api(security::sanitize_api_error(&format!(
+            "HTTP {}: {}",
+            status, body
+        ))))
+    }
+
+    /// Send request to Ollama API
+    async fn send_request(&self, request: OllamaChatRequest) -> Result<OllamaChatResponse> {
+        let response = self.post_chat(&request).await?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
 
         serde_json::from_str(&body).map_err(|e| Error::InvalidResponse(format!("{}: {}", e, body)))
     }
@@ -277,4 +291,54 @@ impl LlmProvider for OllamaProvider {
             model: response.model,
         })
     }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    #[instrument(skip(self, request), fields(model = %request.request.model, tools = request.tools.len()))]
+    async fn complete_stream(&self, request: ToolCompletionRequest) -> Result<CompletionStream> {
+        let model = if request.request.model.is_empty() {
+            &self.config.default_model
+        } else {
+            &request.request.model
+        };
+
+        let messages = convert::convert_messages(&request.request.messages);
+        let tools = if request.tools.is_empty() {
+            None
+        } else {
+            Some(convert::convert_tools(&request.tools))
+        };
+
+        let options = Some(OllamaOptions {
+            temperature: request.request.temperature,
+            num_predict: request
+                .request
+                .max_tokens
+                .or(Some(self.config.default_max_tokens)),
+            stop: request.request.stop.clone(),
+        });
+
+        let ollama_request = OllamaChatRequest {
+            model: model.to_string(),
+            messages,
+            options,
+            stream: true,
+            tools,
+        };
+
+        let response = self.post_chat(&ollama_request).await?;
+
+        Ok(line_stream(response)
+            .scan(StreamDecoder::default(), |decoder, line| {
+                let chunks = match line.and_then(|l| decoder.decode(&l)) {
+                    Ok(chunks) => chunks.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                futures::future::ready(Some(futures::stream::iter(chunks)))
+            })
+            .flatten()
+            .boxed())
+    }
 }
@@ -0,0 +1,68 @@
+use crate::error::{Error, Result};
+use crate::providers::ollama::{security, types::OllamaChatResponse};
+use crate::router::TokenUsage;
+use crate::stream::StreamChunk;
+
+/// Decodes Ollama's newline-delimited JSON stream into [`StreamChunk`]s.
+///
+/// Ollama sends tool calls whole rather than as argument fragments, and never
+/// assigns IDs, so the decoder numbers them across lines.
+#[derive(Debug, Default)]
+pub(crate) struct StreamDecoder {
+    next_tool_index: usize,
+}
+
+impl StreamDecoder {
+    /// Decode one line of the response body
+    pub fn decode(&mut self, line: &str) -> Result<Vec<StreamChunk>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Errors mid-stream arrive as `{"error": "..."}`
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(line) {
+            if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+                return Err(Error::Api(security::sanitize_api_error(error)));
+            }
+        }
+
+        let response: OllamaChatResponse =
+            serde_json::from_str(line).map_err(|e| Error::InvalidResponse(e.to_string()))?;
+
+        let mut chunks = Vec::new();
+        if !response.message.content.is_empty() {
+            chunks.push(StreamChunk::TextDelta(response.message.content));
+        }
+        for tc in response.message.tool_calls.unwrap_or_default() {
+            let index = self.next_tool_index;
+            self.next_tool_index += 1;
+            chunks.push(StreamChunk::ToolCallDelta {
+                index,
+                id: Some(format!("call_{}", index)), // Ollama doesn't provide IDs
+                name: Some(tc.function.name),
+                arguments: serde_json::to_string(&tc.function.arguments)
+                    .unwrap_or_else(|_| "{}".to_string()),
+                thought_signature: None,
+            });
+        }
+
+        if response.done {
+            let usage = match (response.prompt_eval_count, response.eval_count) {
+                (Some(prompt), Some(completion)) => Some(TokenUsage {
+                    prompt_tokens: prompt,
+                    completion_tokens: completion,
+                    total_tokens: prompt + completion,
+                }),
+                _ => None,
+            };
+            chunks.push(StreamChunk::Metadata {
+                model: Some(response.model),
+                finish_reason: response.done_reason,
+                usage,
+            });
+        }
+
+        Ok(chunks)
+    }
+}
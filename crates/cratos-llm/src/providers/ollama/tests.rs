@@ -1,7 +1,9 @@
 use super::convert;
 use super::security::sanitize_api_error;
+use super::stream::StreamDecoder;
 use super::types::{OllamaConfig, DEFAULT_BASE_URL, DEFAULT_MODEL};
 use crate::router::Message;
+use crate::stream::StreamChunk;
 use std::time::Duration;
 
 #[test]
@@ -43,6 +45,36 @@ fn test_message_conversion() {
     assert_eq!(converted[2].role, "assistant");
 }
 
+#[test]
+fn test_stream_decoder() {
+    let mut decoder = StreamDecoder::default();
+
+    let chunks = decoder
+        .decode(r#"{"model":"qwen2.5:7b","message":{"role":"assistant","content":"Hel"},"done":false}"#)
+        .unwrap();
+    assert_eq!(chunks, vec![StreamChunk::TextDelta("Hel".to_string())]);
+
+    let chunks = decoder
+        .decode(r#"{"model":"qwen2.5:7b","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"exec","arguments":{"cmd":"ls"}}}]},"done":false}"#)
+        .unwrap();
+    assert!(matches!(
+        &chunks[0],
+        StreamChunk::ToolCallDelta { index: 0, id: Some(id), arguments, .. }
+            if id == "call_0" && arguments == r#"{"cmd":"ls"}"#
+    ));
+
+    let chunks = decoder
+        .decode(r#"{"model":"qwen2.5:7b","message":{"role":"assistant","content":""},"done":true,"done_reason":"stop","prompt_eval_count":12,"eval_count":3}"#)
+        .unwrap();
+    assert!(matches!(
+        &chunks[0],
+        StreamChunk::Metadata { usage: Some(u), finish_reason: Some(r), .. }
+            if u.total_tokens == 15 && r == "stop"
+    ));
+
+    assert!(decoder.decode(r#"{"error":"model 'x' not found"}"#).is_err());
+}
+
 // Security tests
 
 #[test]
@@ -8,6 +8,7 @@ use crate::router::{
     CompletionRequest, CompletionResponse, LlmProvider, Message, MessageRole, TokenUsage, ToolCall,
     ToolChoice, ToolCompletionRequest, ToolCompletionResponse, ToolDefinition,
 };
+use crate::stream::{CompletionStream, StreamChunk};
 use crate::util::mask_api_key;
 use async_openai::{
     config::OpenAIConfig,
@@ -18,12 +19,14 @@ use async_openai::{
         ChatCompletionRequestSystemMessage, ChatCompletionRequestSystemMessageContent,
         ChatCompletionRequestToolMessage, ChatCompletionRequestToolMessageContent,
         ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageContent,
-        ChatCompletionRequestUserMessageContentPart, ChatCompletionTool,
-        ChatCompletionToolChoiceOption, ChatCompletionTools, CreateChatCompletionRequest,
-        FunctionObject, ImageUrl, StopConfiguration, ToolChoiceOptions,
+        ChatCompletionRequestUserMessageContentPart, ChatCompletionStreamOptions,
+        ChatCompletionTool, ChatCompletionToolChoiceOption, ChatCompletionTools,
+        CreateChatCompletionRequest, CreateChatCompletionStreamResponse, FunctionObject, ImageUrl,
+        StopConfiguration, ToolChoiceOptions,
     },
     Client,
 };
+use futures::StreamExt;
 use std::fmt;
 use std::time::Duration;
 use tracing::{debug, instrument};
@@ -350,6 +353,46 @@ impl OpenAiProvider {
             ToolChoice::Tool(_) => ChatCompletionToolChoiceOption::Mode(ToolChoiceOptions::Auto),
         }
     }
+
+    /// Convert one streamed chunk into provider-neutral stream chunks
+    pub(crate) fn convert_stream_chunk(chunk: CreateChatCompletionStreamResponse) -> Vec<StreamChunk> {
+        let mut out = Vec::new();
+        let mut finish_reason = None;
+
+        if let Some(choice) = chunk.choices.into_iter().next() {
+            if let Some(text) = choice.delta.content.filter(|t| !t.is_empty()) {
+                out.push(StreamChunk::TextDelta(text));
+            }
+            for tc in choice.delta.tool_calls.unwrap_or_default() {
+                let (name, arguments) = tc
+                    .function
+                    .map(|f| (f.name, f.arguments.unwrap_or_default()))
+                    .unwrap_or_default();
+                out.push(StreamChunk::ToolCallDelta {
+                    index: tc.index as usize,
+                    id: tc.id,
+                    name,
+                    arguments,
+                    thought_signature: None,
+                });
+            }
+            finish_reason = choice.finish_reason.as_ref().map(|r| format!("{:?}", r));
+        }
+
+        let usage = chunk.usage.map(|u| TokenUsage {
+            prompt_tokens: u.prompt_tokens,
+            completion_tokens: u.completion_tokens,
+            total_tokens: u.total_tokens,
+        });
+        if finish_reason.is_some() || usage.is_some() {
+            out.push(StreamChunk::Metadata {
+                model: Some(chunk.model),
+                finish_reason,
+                usage,
+            });
+        }
+        out
+    }
 }
 
 #[async_trait::async_trait]
@@ -502,6 +545,74 @@ impl LlmProvider for OpenAiProvider {
             model: response.model,
         })
     }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    #[instrument(skip(self, request), fields(model = %request.request.model, tools = request.tools.len()))]
+    async fn complete_stream(&self, request: ToolCompletionRequest) -> Result<CompletionStream> {
+        let model = if request.request.model.is_empty() {
+            self.default_model.clone()
+        } else {
+            request.request.model
+        };
+
+        let messages: Vec<ChatCompletionRequestMessage> = request
+            .request
+            .messages
+            .into_iter()
+            .map(Self::convert_message)
+            .collect::<Result<_>>()?;
+
+        let (tools, tool_choice) = if request.tools.is_empty() {
+            (None, None)
+        } else {
+            let tools: Vec<ChatCompletionTools> = request
+                .tools
+                .into_iter()
+                .map(|tool| ChatCompletionTools::Function(Self::convert_tool(tool)))
+                .collect();
+            (
+                Some(tools),
+                Some(Self::convert_tool_choice(&request.tool_choice)),
+            )
+        };
+
+        let openai_request = CreateChatCompletionRequest {
+            model,
+            messages,
+            tools,
+            tool_choice,
+            max_completion_tokens: request.request.max_tokens,
+            temperature: request.request.temperature,
+            stop: request.request.stop.map(StopConfiguration::StringArray),
+            stream_options: Some(ChatCompletionStreamOptions {
+                include_usage: Some(true),
+                include_obfuscation: None,
+            }),
+            ..Default::default()
+        };
+
+        debug!("Sending streaming request to OpenAI");
+
+        let stream = self.client.chat().create_stream(openai_request).await.map_err(
+            |e: async_openai::error::OpenAIError| Error::Api(sanitize_api_error(&e.to_string())),
+        )?;
+
+        Ok(stream
+            .flat_map(|item| {
+                let chunks: Vec<Result<StreamChunk>> = match item {
+                    Ok(chunk) => Self::convert_stream_chunk(chunk)
+                        .into_iter()
+                        .map(Ok)
+                        .collect(),
+                    Err(e) => vec![Err(Error::Api(sanitize_api_error(&e.to_string())))],
+                };
+                futures::stream::iter(chunks)
+            })
+            .boxed())
+    }
 }
 
 #[cfg(test)]
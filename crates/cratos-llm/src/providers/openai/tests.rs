@@ -63,3 +63,52 @@ fn test_config_debug_masks_key() {
     assert!(!debug_str.contains("1234567890abcdefghijkl"));
     assert!(debug_str.contains("sk-1...mnop"));
 }
+
+#[test]
+fn test_convert_stream_chunk() {
+    let chunk: CreateChatCompletionStreamResponse = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 1,
+        "model": "gpt-5",
+        "choices": [{
+            "index": 0,
+            "delta": {
+                "content": "Hi",
+                "tool_calls": [{
+                    "index": 0,
+                    "id": "call_1",
+                    "type": "function",
+                    "function": {"name": "http_get", "arguments": "{\"url\""}
+                }]
+            },
+            "finish_reason": null
+        }]
+    }))
+    .unwrap();
+
+    let chunks = OpenAiProvider::convert_stream_chunk(chunk);
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0], StreamChunk::TextDelta("Hi".to_string()));
+    assert!(matches!(
+        &chunks[1],
+        StreamChunk::ToolCallDelta { index: 0, id: Some(id), name: Some(name), arguments, .. }
+            if id == "call_1" && name == "http_get" && arguments == "{\"url\""
+    ));
+
+    let usage_chunk: CreateChatCompletionStreamResponse =
+        serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 1,
+            "model": "gpt-5",
+            "choices": [],
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+        }))
+        .unwrap();
+    let chunks = OpenAiProvider::convert_stream_chunk(usage_chunk);
+    assert!(matches!(
+        &chunks[0],
+        StreamChunk::Metadata { usage: Some(u), .. } if u.total_tokens == 15
+    ));
+}
@@ -6,6 +6,7 @@ use crate::completion::{
     CompletionRequest, CompletionResponse, ToolCompletionRequest, ToolCompletionResponse,
 };
 use crate::error::Result;
+use crate::stream::{stream_from_response, CompletionStream};
 
 /// Trait for LLM providers
 #[async_trait::async_trait]
@@ -30,4 +31,30 @@ pub trait LlmProvider: Send + Sync {
         &self,
         request: ToolCompletionRequest,
     ) -> Result<ToolCompletionResponse>;
+
+    /// Check if the provider streams tokens natively
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    /// Stream a conversation as incremental chunks (text deltas and tool-call
+    /// fragments). An empty `tools` list requests a text-only completion.
+    ///
+    /// The default implementation waits for the full response and replays it
+    /// as a single batch of chunks.
+    async fn complete_stream(&self, request: ToolCompletionRequest) -> Result<CompletionStream> {
+        let response = if request.tools.is_empty() {
+            let response = self.complete(request.request).await?;
+            ToolCompletionResponse {
+                content: Some(response.content),
+                tool_calls: Vec::new(),
+                usage: response.usage,
+                finish_reason: response.finish_reason,
+                model: response.model,
+            }
+        } else {
+            self.complete_with_tools(request).await?
+        };
+        Ok(stream_from_response(response))
+    }
 }
@@ -9,8 +9,12 @@ use super::types::TaskType;
 use crate::completion::{
     CompletionRequest, CompletionResponse, ToolCompletionRequest, ToolCompletionResponse,
 };
+use crate::cost::{CostTracker, MeteredProvider};
 use crate::error::{Error, Result};
-use crate::tools::ToolChoice;
+use crate::message::Message;
+use crate::stream::CompletionStream;
+use crate::token::TokenCounter;
+use crate::tools::{ToolChoice, ToolDefinition};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, info, instrument};
@@ -20,6 +24,7 @@ pub struct LlmRouter {
     providers: HashMap<String, Arc<dyn LlmProvider>>,
     default_provider: String,
     routing_rules: RoutingRules,
+    cost_tracker: Option<Arc<CostTracker>>,
 }
 
 impl LlmRouter {
@@ -30,6 +35,7 @@ impl LlmRouter {
             providers: HashMap::new(),
             default_provider: default_provider.into(),
             routing_rules: RoutingRules::default(),
+            cost_tracker: None,
         }
     }
 
@@ -51,10 +57,26 @@ impl LlmRouter {
         &self.routing_rules
     }
 
+    /// Meter every provider with a cost tracker
+    ///
+    /// Calls through any registered provider (including ones handed out by
+    /// [`Self::get`]) record their usage and are subject to the tracker's
+    /// budgets. Call this once.
+    pub fn set_cost_tracker(&mut self, tracker: Arc<CostTracker>) {
+        for provider in self.providers.values_mut() {
+            *provider = Arc::new(MeteredProvider::new(provider.clone(), tracker.clone()));
+        }
+        self.cost_tracker = Some(tracker);
+    }
+
     /// Register a provider
     pub fn register(&mut self, name: impl Into<String>, provider: Arc<dyn LlmProvider>) {
         let name = name.into();
         debug!(provider = %name, "Registering LLM provider");
+        let provider: Arc<dyn LlmProvider> = match &self.cost_tracker {
+            Some(tracker) => Arc::new(MeteredProvider::new(provider, tracker.clone())),
+            None => provider,
+        };
         self.providers.insert(name, provider);
     }
 
@@ -269,6 +291,9 @@ impl LlmRouter {
     /// - Planning: 3000 tokens (detailed plans)
     /// - CodeReview: 3000 tokens (analysis)
     /// - CodeGeneration: 4096 tokens (full implementations)
+    ///
+    /// The budget is reduced if the prompt leaves less room in the model's
+    /// context window.
     #[instrument(skip(self, messages))]
     pub async fn complete_for_task(
         &self,
@@ -288,11 +313,14 @@ impl LlmRouter {
             temperature = budget.temperature,
             "Applying task-specific token budget"
         );
+        let max_tokens = self
+            .fit_output_budget(provider.name(), &model, &messages, &[], budget.max_tokens)
+            .await?;
 
         let request = CompletionRequest {
             model,
             messages,
-            max_tokens: Some(budget.max_tokens),
+            max_tokens: Some(max_tokens),
             temperature: Some(budget.temperature),
             stop: None,
         };
@@ -331,12 +359,21 @@ impl LlmRouter {
             temperature = budget.temperature,
             "Applying task-specific token budget for tool completion"
         );
+        let max_tokens = self
+            .fit_output_budget(
+                provider.name(),
+                &model,
+                &messages,
+                &tools,
+                budget.max_tokens,
+            )
+            .await?;
 
         let request = ToolCompletionRequest {
             request: CompletionRequest {
                 model,
                 messages,
-                max_tokens: Some(budget.max_tokens),
+                max_tokens: Some(max_tokens),
                 temperature: Some(budget.temperature),
                 stop: None,
             },
@@ -347,6 +384,53 @@ impl LlmRouter {
         provider.complete_with_tools(request).await
     }
 
+    /// Fit an output token budget into the model's context window
+    ///
+    /// The prompt is counted with the model's tokenizer. The context window
+    /// comes from the cost tracker's pricing table; without a tracker or
+    /// pricing for the model the budget is returned unchanged.
+    async fn fit_output_budget(
+        &self,
+        provider: &str,
+        model: &str,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        max_tokens: u32,
+    ) -> Result<u32> {
+        let Some(tracker) = &self.cost_tracker else {
+            return Ok(max_tokens);
+        };
+        let Some(pricing) = tracker.get_pricing(model).await else {
+            return Ok(max_tokens);
+        };
+
+        let counter = TokenCounter::for_model(provider, model);
+        let prompt_tokens = counter.count_conversation_tokens(messages)
+            + tools
+                .iter()
+                .map(|t| counter.count_tool_tokens(t))
+                .sum::<usize>();
+        let prompt_tokens = u32::try_from(prompt_tokens).unwrap_or(u32::MAX);
+        let available = pricing.context_window.saturating_sub(prompt_tokens);
+        if available == 0 {
+            return Err(Error::Api(format!(
+                "prompt of {} tokens exceeds the {} token context window of {}",
+                prompt_tokens, pricing.context_window, model
+            )));
+        }
+        if available < max_tokens {
+            debug!(
+                model,
+                prompt_tokens,
+                max_tokens,
+                available,
+                tokenizer = counter.tokenizer_name(),
+                "Reducing output budget to fit the context window"
+            );
+        }
+        Ok(max_tokens.min(available))
+    }
+
     /// Estimate cost for a task (relative units)
     #[must_use]
     pub fn estimate_cost(&self, task_type: TaskType, estimated_tokens: u32) -> f32 {
@@ -396,4 +480,24 @@ impl LlmProvider for LlmRouter {
     ) -> Result<ToolCompletionResponse> {
         LlmRouter::complete_with_tools(self, request).await
     }
+
+    fn supports_streaming(&self) -> bool {
+        self.default_provider()
+            .map(|p| p.supports_streaming())
+            .unwrap_or(false)
+    }
+
+    async fn complete_stream(&self, request: ToolCompletionRequest) -> Result<CompletionStream> {
+        let provider = self.default_provider().ok_or_else(|| {
+            if self.default_provider == "auto" {
+                Error::NotConfigured(
+                    "auto (no provider resolved — check API keys or run `cratos init`)".into(),
+                )
+            } else {
+                Error::NotConfigured(self.default_provider.clone())
+            }
+        })?;
+
+        provider.complete_stream(request).await
+    }
 }
@@ -301,3 +301,73 @@ fn test_model_routing_config_estimate_monthly_cost() {
     // Expected: 0 + 0 + 0.21 + 0.137 = ~$0.35
     assert!(cost < 1.0, "Free tier should be under $1 for 3.1M tokens");
 }
+
+#[tokio::test]
+async fn test_router_meters_registered_providers() {
+    let tracker = std::sync::Arc::new(crate::cost::CostTracker::new());
+    let mut router = LlmRouter::new("mock");
+    router.register("mock", std::sync::Arc::new(MockProvider::new()));
+    router.set_cost_tracker(tracker.clone());
+    router.register("other", std::sync::Arc::new(MockProvider::new()));
+
+    router
+        .complete(CompletionRequest::new("mock-model").with_message(Message::user("hi")))
+        .await
+        .unwrap();
+    // Providers handed out directly (e.g. as fallback) are metered too
+    router
+        .get("other")
+        .unwrap()
+        .complete(CompletionRequest::new("mock-model"))
+        .await
+        .unwrap();
+
+    let records = tracker.get_recent_records(10).await;
+    assert_eq!(records.len(), 2);
+    assert!(records.iter().all(|r| r.provider == "mock" && r.success));
+    // The mock reports no usage, so it is counted with the tokenizer
+    assert!(records
+        .iter()
+        .all(|r| r.input_tokens > 0 && r.output_tokens > 0));
+}
+
+#[tokio::test]
+async fn test_router_checks_context_window() {
+    let tracker = std::sync::Arc::new(crate::cost::CostTracker::new());
+    tracker
+        .update_pricing(
+            "tiny-model",
+            crate::cost::ModelPricing {
+                model: "tiny-model".to_string(),
+                provider: "mock".to_string(),
+                input_cost_per_million: 1.0,
+                output_cost_per_million: 1.0,
+                context_window: 50,
+                updated_at: chrono::Utc::now(),
+            },
+        )
+        .await;
+
+    let mut rules = RoutingRules::default();
+    rules
+        .task_providers
+        .insert(TaskType::Conversation, "mock".to_string());
+    rules
+        .task_models
+        .insert(TaskType::Conversation, "tiny-model".to_string());
+    let mut router = LlmRouter::new("mock").with_routing_rules(rules);
+    router.register("mock", std::sync::Arc::new(MockProvider::new()));
+    router.set_cost_tracker(tracker);
+
+    router
+        .complete_for_task(TaskType::Conversation, vec![Message::user("hi")])
+        .await
+        .unwrap();
+
+    let long_prompt = "word ".repeat(200);
+    let err = router
+        .complete_for_task(TaskType::Conversation, vec![Message::user(long_prompt)])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("context window"));
+}
@@ -131,9 +131,19 @@ impl ModelTier {
     /// - Fast: GPT-5 nano, Gemini 2.5 Flash, Groq GPT-OSS
     /// - Standard: Claude Sonnet 4.5, GPT-5, Gemini 2.5 Pro
     /// - Premium: Claude Opus 4.5
+    ///
+    /// Unknown providers get `gpt-5`.
     #[must_use]
     pub fn default_model(&self, provider: &str) -> &'static str {
-        match (self, provider) {
+        self.model_for(provider).unwrap_or("gpt-5")
+    }
+
+    /// Get the model of a known provider at this tier
+    ///
+    /// Returns `None` for providers without a tier mapping.
+    #[must_use]
+    pub fn model_for(&self, provider: &str) -> Option<&'static str> {
+        let model = match (self, provider) {
             // ================================================================
             // DeepSeek - Ultra-low-cost leader ($0.03 ~ $0.55/M tokens)
             // ================================================================
@@ -235,11 +245,9 @@ impl ModelTier {
             // ================================================================
             (_, "ollama") => "qwen2.5:7b",
 
-            // ================================================================
-            // Default fallback
-            // ================================================================
-            _ => "gpt-5",
-        }
+            _ => return None,
+        };
+        Some(model)
     }
 
     /// Estimated cost multiplier relative to Fast tier
@@ -0,0 +1,454 @@
+//! Streaming completion types
+//!
+//! Providers that stream natively yield [`StreamChunk`]s as the model
+//! generates: text deltas and tool-call fragments whose JSON arguments arrive
+//! piece by piece. [`StreamAccumulator`] folds the chunks back into a regular
+//! [`ToolCompletionResponse`], so callers can render progressive output and
+//! still get the complete response once the stream ends.
+
+use crate::completion::{TokenUsage, ToolCompletionResponse};
+use crate::error::{Error, Result};
+use crate::tools::ToolCall;
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::BTreeMap;
+
+/// A single incremental piece of a streamed completion
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamChunk {
+    /// Text generated by the model
+    TextDelta(String),
+    /// Fragment of a tool call.
+    ///
+    /// Fragments sharing an `index` belong to the same call: `id` and `name`
+    /// are usually sent once, `arguments` is appended in order.
+    ToolCallDelta {
+        /// Position of the tool call within the response
+        index: usize,
+        /// Tool call ID (if sent in this fragment)
+        id: Option<String>,
+        /// Tool name (if sent in this fragment)
+        name: Option<String>,
+        /// Partial JSON arguments to append
+        arguments: String,
+        /// Gemini 3+ thought signature (if sent in this fragment)
+        thought_signature: Option<String>,
+    },
+    /// Response metadata; later values override earlier ones
+    Metadata {
+        /// Model that served the request
+        model: Option<String>,
+        /// Finish reason
+        finish_reason: Option<String>,
+        /// Token usage
+        usage: Option<TokenUsage>,
+    },
+}
+
+/// Stream of completion chunks returned by [`crate::LlmProvider::complete_stream`]
+pub type CompletionStream = BoxStream<'static, Result<StreamChunk>>;
+
+/// Folds [`StreamChunk`]s into a [`ToolCompletionResponse`]
+#[derive(Debug, Default)]
+pub struct StreamAccumulator {
+    content: String,
+    tool_calls: BTreeMap<usize, ToolCall>,
+    model: String,
+    finish_reason: Option<String>,
+    usage: Option<TokenUsage>,
+}
+
+impl StreamAccumulator {
+    /// Create an accumulator; `model` is used unless the stream reports one
+    #[must_use]
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Apply a chunk
+    pub fn push(&mut self, chunk: &StreamChunk) {
+        match chunk {
+            StreamChunk::TextDelta(text) => self.content.push_str(text),
+            StreamChunk::ToolCallDelta {
+                index,
+                id,
+                name,
+                arguments,
+                thought_signature,
+            } => {
+                let call = self.tool_calls.entry(*index).or_insert_with(|| ToolCall {
+                    id: String::new(),
+                    name: String::new(),
+                    arguments: String::new(),
+                    thought_signature: None,
+                });
+                if let Some(id) = id {
+                    call.id.clone_from(id);
+                }
+                if let Some(name) = name {
+                    call.name.clone_from(name);
+                }
+                call.arguments.push_str(arguments);
+                if thought_signature.is_some() {
+                    call.thought_signature.clone_from(thought_signature);
+                }
+            }
+            StreamChunk::Metadata {
+                model,
+                finish_reason,
+                usage,
+            } => {
+                if let Some(model) = model {
+                    self.model.clone_from(model);
+                }
+                if finish_reason.is_some() {
+                    self.finish_reason.clone_from(finish_reason);
+                }
+                if usage.is_some() {
+                    self.usage.clone_from(usage);
+                }
+            }
+        }
+    }
+
+    /// Text received so far
+    #[must_use]
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Build the final response
+    #[must_use]
+    pub fn finish(self) -> ToolCompletionResponse {
+        let tool_calls = self
+            .tool_calls
+            .into_values()
+            .enumerate()
+            .map(|(i, mut call)| {
+                if call.id.is_empty() {
+                    call.id = format!("call_{}", i);
+                }
+                if call.arguments.trim().is_empty() {
+                    call.arguments = "{}".to_string();
+                }
+                call
+            })
+            .collect();
+
+        ToolCompletionResponse {
+            content: if self.content.is_empty() {
+                None
+            } else {
+                Some(self.content)
+            },
+            tool_calls,
+            usage: self.usage,
+            finish_reason: self.finish_reason,
+            model: self.model,
+        }
+    }
+}
+
+/// Drain a stream into a complete response, calling `on_chunk` for each chunk
+pub async fn collect_stream<F>(
+    mut stream: CompletionStream,
+    model: impl Into<String>,
+    mut on_chunk: F,
+) -> Result<ToolCompletionResponse>
+where
+    F: FnMut(&StreamChunk),
+{
+    let mut acc = StreamAccumulator::new(model);
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        on_chunk(&chunk);
+        acc.push(&chunk);
+    }
+    Ok(acc.finish())
+}
+
+/// Replay a complete response as a stream (used by non-streaming providers)
+#[must_use]
+pub fn stream_from_response(response: ToolCompletionResponse) -> CompletionStream {
+    let mut chunks = Vec::with_capacity(response.tool_calls.len() + 2);
+    if let Some(content) = response.content.filter(|c| !c.is_empty()) {
+        chunks.push(Ok(StreamChunk::TextDelta(content)));
+    }
+    for (index, call) in response.tool_calls.into_iter().enumerate() {
+        chunks.push(Ok(StreamChunk::ToolCallDelta {
+            index,
+            id: Some(call.id),
+            name: Some(call.name),
+            arguments: call.arguments,
+            thought_signature: call.thought_signature,
+        }));
+    }
+    chunks.push(Ok(StreamChunk::Metadata {
+        model: Some(response.model),
+        finish_reason: response.finish_reason,
+        usage: response.usage,
+    }));
+    stream::iter(chunks).boxed()
+}
+
+// ============================================================================
+// Wire framing helpers (shared by provider implementations)
+// ============================================================================
+
+/// Split an HTTP response body into lines (LF or CRLF terminated).
+///
+/// A trailing line without a newline is yielded when the body ends.
+pub(crate) fn line_stream(response: reqwest::Response) -> BoxStream<'static, Result<String>> {
+    let bytes = response.bytes_stream();
+    stream::unfold(
+        (bytes, Vec::<u8>::new(), false),
+        |(mut bytes, mut buf, mut eof)| async move {
+            loop {
+                if let Some(pos) = buf.iter().position(|b| *b == b'\n') {
+                    let mut line: Vec<u8> = buf.drain(..=pos).collect();
+                    line.pop();
+                    if line.last() == Some(&b'\r') {
+                        line.pop();
+                    }
+                    let line = String::from_utf8_lossy(&line).into_owned();
+                    return Some((Ok(line), (bytes, buf, eof)));
+                }
+                if eof {
+                    if buf.is_empty() {
+                        return None;
+                    }
+                    let line = String::from_utf8_lossy(&buf).into_owned();
+                    buf.clear();
+                    return Some((Ok(line), (bytes, buf, eof)));
+                }
+                match bytes.next().await {
+                    Some(Ok(chunk)) => buf.extend_from_slice(&chunk),
+                    Some(Err(e)) => {
+                        eof = true;
+                        buf.clear();
+                        return Some((Err(Error::Network(e.to_string())), (bytes, buf, eof)));
+                    }
+                    None => eof = true,
+                }
+            }
+        },
+    )
+    .boxed()
+}
+
+/// A server-sent event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SseEvent {
+    /// Event name (`event:` field), if any
+    pub event: Option<String>,
+    /// Last event ID (`id:` field) seen on the stream, if any
+    pub id: Option<String>,
+    /// Event payload (`data:` lines joined with newlines)
+    pub data: String,
+}
+
+/// Incremental SSE parser
+///
+/// Fed either whole lines ([`Self::feed`]) or raw body bytes
+/// ([`Self::push`]); bytes are buffered until a line is complete, so a
+/// multi-byte character split across chunks is decoded intact.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    last_event_id: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Feed raw bytes; returns every event completed by them
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let raw: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&raw);
+            events.extend(self.feed(line.trim_end_matches(['\n', '\r'])));
+        }
+        events
+    }
+
+    /// Last event ID seen, for resuming with `Last-Event-ID`
+    ///
+    /// Also set by events without data, which are never returned.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// Feed a line; returns an event when a blank line completes one
+    pub fn feed(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            if self.data.is_empty() {
+                self.event = None;
+                return None;
+            }
+            return Some(SseEvent {
+                event: self.event.take(),
+                id: self.last_event_id.clone(),
+                data: std::mem::take(&mut self.data).join("\n"),
+            });
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((f, v)) => (f, v.strip_prefix(' ').unwrap_or(v)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "id" => self.last_event_id = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    /// Flush a pending event at end of stream
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        self.feed("")
+    }
+}
+
+/// Parse an HTTP response body as server-sent events
+pub(crate) fn sse_stream(response: reqwest::Response) -> BoxStream<'static, Result<SseEvent>> {
+    let lines = line_stream(response).map(Some).chain(stream::iter([None]));
+    lines
+        .scan(SseParser::default(), |parser, line| {
+            let out = match line {
+                Some(Ok(line)) => parser.feed(&line).map(Ok),
+                Some(Err(e)) => Some(Err(e)),
+                None => parser.finish().map(Ok),
+            };
+            futures::future::ready(Some(out))
+        })
+        .filter_map(futures::future::ready)
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_parser() {
+        let mut parser = SseParser::default();
+        assert!(parser.feed(": keep-alive").is_none());
+        assert!(parser.feed("event: content_block_delta").is_none());
+        assert!(parser.feed("data: {\"a\":").is_none());
+        assert!(parser.feed("data: 1}").is_none());
+        let event = parser.feed("").unwrap();
+        assert_eq!(event.event.as_deref(), Some("content_block_delta"));
+        assert_eq!(event.data, "{\"a\":\n1}");
+
+        assert!(parser.feed("data:[DONE]").is_none());
+        let event = parser.finish().unwrap();
+        assert_eq!(event.event, None);
+        assert_eq!(event.data, "[DONE]");
+        assert!(parser.finish().is_none());
+    }
+
+    #[test]
+    fn test_sse_parser_push_splits_events_across_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b"id: 7\r\ndata: {\"a\":").is_empty());
+
+        let events = parser.push(b"1}\r\n\r\n: keepalive\n\nid: 8\n\ndata: x\ndata: y\n\n");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id.as_deref(), Some("7"));
+        assert_eq!(events[0].data, "{\"a\":1}");
+        // An ID-only event is not returned but still moves the last event ID
+        assert_eq!(events[1].id.as_deref(), Some("8"));
+        assert_eq!(events[1].data, "x\ny");
+        assert_eq!(parser.last_event_id(), Some("8"));
+    }
+
+    #[test]
+    fn test_sse_parser_push_keeps_split_characters() {
+        let mut parser = SseParser::default();
+        let bytes = "data: héllo\r\n\r\n".as_bytes();
+        // Split inside "é" and between "\r" and "\n"
+        let split = bytes.iter().position(|&b| b == 0xc3).unwrap() + 1;
+        assert!(parser.push(&bytes[..split]).is_empty());
+        assert!(parser.push(&bytes[split..bytes.len() - 1]).is_empty());
+
+        let events = parser.push(&bytes[bytes.len() - 1..]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "héllo");
+    }
+
+    #[test]
+    fn test_accumulator_assembles_tool_arguments() {
+        let mut acc = StreamAccumulator::new("m");
+        acc.push(&StreamChunk::TextDelta("Let me ".to_string()));
+        acc.push(&StreamChunk::TextDelta("check.".to_string()));
+        acc.push(&StreamChunk::ToolCallDelta {
+            index: 0,
+            id: Some("call_a".to_string()),
+            name: Some("http_get".to_string()),
+            arguments: "{\"url\":".to_string(),
+            thought_signature: None,
+        });
+        acc.push(&StreamChunk::ToolCallDelta {
+            index: 1,
+            id: None,
+            name: Some("file_read".to_string()),
+            arguments: String::new(),
+            thought_signature: None,
+        });
+        acc.push(&StreamChunk::ToolCallDelta {
+            index: 0,
+            id: None,
+            name: None,
+            arguments: "\"https://x\"}".to_string(),
+            thought_signature: None,
+        });
+        acc.push(&StreamChunk::Metadata {
+            model: Some("served-model".to_string()),
+            finish_reason: Some("tool_calls".to_string()),
+            usage: None,
+        });
+        assert_eq!(acc.content(), "Let me check.");
+
+        let response = acc.finish();
+        assert_eq!(response.content.as_deref(), Some("Let me check."));
+        assert_eq!(response.model, "served-model");
+        assert_eq!(response.tool_calls.len(), 2);
+        assert_eq!(response.tool_calls[0].id, "call_a");
+        assert_eq!(response.tool_calls[0].arguments, "{\"url\":\"https://x\"}");
+        assert_eq!(response.tool_calls[1].id, "call_1");
+        assert_eq!(response.tool_calls[1].arguments, "{}");
+    }
+
+    #[tokio::test]
+    async fn test_stream_from_response_roundtrip() {
+        let response = ToolCompletionResponse {
+            content: Some("hello".to_string()),
+            tool_calls: vec![ToolCall {
+                id: "c1".to_string(),
+                name: "exec".to_string(),
+                arguments: "{\"cmd\":\"ls\"}".to_string(),
+                thought_signature: None,
+            }],
+            usage: None,
+            finish_reason: Some("stop".to_string()),
+            model: "m".to_string(),
+        };
+        let mut seen = 0;
+        let collected = collect_stream(stream_from_response(response), "", |_| seen += 1)
+            .await
+            .unwrap();
+        assert_eq!(seen, 3);
+        assert_eq!(collected.content.as_deref(), Some("hello"));
+        assert_eq!(collected.tool_calls[0].name, "exec");
+        assert_eq!(collected.finish_reason.as_deref(), Some("stop"));
+    }
+}
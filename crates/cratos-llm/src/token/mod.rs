@@ -1,18 +1,22 @@
 //! Token counting and budget management
 //!
-//! This module provides token counting utilities using tiktoken's cl100k_base encoding,
-//! which is compatible with most modern LLMs.
+//! Token counts are model-specific: [`TokenCounter::for_model`] counts with
+//! the tokenizer the [`TokenizerRegistry`] resolves for a provider/model pair.
+//! Without a model, tiktoken's cl100k_base encoding is used.
+
+mod tokenizer;
+
+#[cfg(feature = "hf-tokenizers")]
+pub use tokenizer::HuggingFaceTokenizer;
+pub use tokenizer::{
+    builtin_tokenizer, global_tokenizers, tokenizer_for, BpeTokenizer, CalibratedTokenizer,
+    Tokenizer, TokenizerKey, TokenizerRegistry,
+};
 
 use crate::message::Message;
 use crate::tools::ToolDefinition;
 use serde::{Deserialize, Serialize};
-use std::sync::LazyLock;
-use tiktoken_rs::{cl100k_base, CoreBPE};
-
-/// Global tokenizer instance (initialized once, thread-safe)
-static TOKENIZER: LazyLock<CoreBPE> = LazyLock::new(|| {
-    cl100k_base().expect("cl100k_base tokenizer is a compile-time constant and should never fail")
-});
+use std::sync::Arc;
 
 // ============================================================================
 // Token Counter
@@ -20,29 +24,45 @@ static TOKENIZER: LazyLock<CoreBPE> = LazyLock::new(|| {
 
 /// Token counter for estimating message token usage
 ///
-/// Uses tiktoken's cl100k_base encoding (GPT-5.2, Claude 3, etc.)
-/// for accurate token estimation across modern LLMs.
-///
-/// This is a zero-cost wrapper around the global tokenizer instance.
-#[derive(Clone, Copy)]
-pub struct TokenCounter;
+/// Counts with one [`Tokenizer`]; cloning is cheap.
+#[derive(Clone)]
+pub struct TokenCounter {
+    tokenizer: Arc<dyn Tokenizer>,
+}
 
 impl TokenCounter {
     /// Create a new token counter
     ///
     /// Uses cl100k_base encoding which is compatible with:
-    /// - OpenAI GPT-4, GPT-5.2, GPT-3.5-turbo
-    /// - Anthropic Claude 3.x (approximate)
-    /// - Most modern LLMs
+    /// - OpenAI GPT-4, GPT-3.5-turbo
+    /// - Most modern LLMs (approximate)
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_tokenizer(BpeTokenizer::cl100k())
+    }
+
+    /// Create a token counter for a provider/model pair
+    #[must_use]
+    pub fn for_model(provider: &str, model: &str) -> Self {
+        Self::with_tokenizer(tokenizer_for(provider, model))
+    }
+
+    /// Create a token counter using a specific tokenizer
     #[must_use]
-    pub const fn new() -> Self {
-        Self
+    pub fn with_tokenizer(tokenizer: Arc<dyn Tokenizer>) -> Self {
+        Self { tokenizer }
+    }
+
+    /// Name of the tokenizer in use
+    #[must_use]
+    pub fn tokenizer_name(&self) -> &str {
+        self.tokenizer.name()
     }
 
     /// Count tokens in a string
     #[must_use]
     pub fn count_tokens(&self, text: &str) -> usize {
-        TOKENIZER.encode_with_special_tokens(text).len()
+        self.tokenizer.count_tokens(text)
     }
 
     /// Count tokens in a message (includes role overhead)
@@ -102,6 +122,12 @@ pub fn count_message_tokens(messages: &[Message]) -> usize {
     TOKEN_COUNTER.count_conversation_tokens(messages)
 }
 
+/// Count tokens in messages with the tokenizer of a provider/model pair
+#[must_use]
+pub fn count_message_tokens_for(provider: &str, model: &str, messages: &[Message]) -> usize {
+    TokenCounter::for_model(provider, model).count_conversation_tokens(messages)
+}
+
 // ============================================================================
 // Token Budget
 // ============================================================================
@@ -215,6 +241,22 @@ mod tests {
         assert!(msg_tokens > tokens); // Should include overhead
     }
 
+    #[test]
+    fn test_token_counter_for_model() {
+        let text = "Summarize the quarterly report and list the three largest risks.";
+        let gpt = TokenCounter::for_model("openai", "gpt-5");
+        let claude = TokenCounter::for_model("anthropic", "claude-sonnet-4-5");
+        assert_eq!(gpt.tokenizer_name(), "o200k_base");
+        assert_eq!(claude.tokenizer_name(), "claude-estimate");
+        assert!(claude.count_tokens(text) > TokenCounter::new().count_tokens(text));
+
+        let messages = vec![Message::user(text)];
+        assert_eq!(
+            count_message_tokens_for("openai", "gpt-5", &messages),
+            gpt.count_conversation_tokens(&messages)
+        );
+    }
+
     #[test]
     fn test_token_budget_default() {
         let budget = TokenBudget::default();
@@ -0,0 +1,462 @@
+//! Per-model tokenizers
+//!
+//! Token counts depend on the model's vocabulary: `cl100k_base` is exact for
+//! GPT-4 but undercounts Claude and overcounts Qwen or GLM. The
+//! [`TokenizerRegistry`] resolves a tokenizer for each provider/model pair:
+//!
+//! 1. A tokenizer registered explicitly for the model
+//! 2. A local HuggingFace `tokenizer.json` in the tokenizer directory
+//!    (`<dir>/<model>/tokenizer.json`, requires the `hf-tokenizers` feature)
+//! 3. `o200k_base` for newer OpenAI models, `cl100k_base` for older ones
+//! 4. A calibrated estimate (scaled BPE count) for other known families
+//! 5. `cl100k_base`
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+use tiktoken_rs::{cl100k_base, o200k_base, CoreBPE};
+use tracing::debug;
+
+static CL100K: LazyLock<Arc<BpeTokenizer>> = LazyLock::new(|| {
+    Arc::new(BpeTokenizer {
+        name: "cl100k_base",
+        bpe: cl100k_base()
+            .expect("cl100k_base tokenizer is a compile-time constant and should never fail"),
+    })
+});
+
+static O200K: LazyLock<Arc<BpeTokenizer>> = LazyLock::new(|| {
+    Arc::new(BpeTokenizer {
+        name: "o200k_base",
+        bpe: o200k_base()
+            .expect("o200k_base tokenizer is a compile-time constant and should never fail"),
+    })
+});
+
+static REGISTRY: LazyLock<TokenizerRegistry> = LazyLock::new(TokenizerRegistry::new);
+
+/// Counts the tokens of a text for one model family
+pub trait Tokenizer: Send + Sync {
+    /// Tokenizer name (e.g. "o200k_base")
+    fn name(&self) -> &str;
+
+    /// Count the tokens in a text
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+/// tiktoken BPE encoding
+pub struct BpeTokenizer {
+    name: &'static str,
+    bpe: CoreBPE,
+}
+
+impl BpeTokenizer {
+    /// `cl100k_base` (GPT-4, GPT-3.5)
+    #[must_use]
+    pub fn cl100k() -> Arc<dyn Tokenizer> {
+        CL100K.clone()
+    }
+
+    /// `o200k_base` (GPT-4o, GPT-4.1, GPT-5, o-series)
+    #[must_use]
+    pub fn o200k() -> Arc<dyn Tokenizer> {
+        O200K.clone()
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        self.bpe.encode_with_special_tokens(text).len()
+    }
+}
+
+/// Estimate derived from another tokenizer's count
+///
+/// Used for models whose tokenizer is not available locally. The ratio
+/// approximates the model's token count relative to the base count; it is
+/// an estimate, so budgets keep some headroom.
+pub struct CalibratedTokenizer {
+    name: String,
+    base: Arc<dyn Tokenizer>,
+    ratio: f64,
+}
+
+impl CalibratedTokenizer {
+    /// Create an estimate scaling `base` by `ratio`
+    #[must_use]
+    pub fn new(name: impl Into<String>, base: Arc<dyn Tokenizer>, ratio: f64) -> Self {
+        Self {
+            name: name.into(),
+            base,
+            ratio,
+        }
+    }
+
+    /// Ratio applied to the base count
+    #[must_use]
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+}
+
+impl Tokenizer for CalibratedTokenizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        let base = self.base.count_tokens(text);
+        (base as f64 * self.ratio).ceil() as usize
+    }
+}
+
+/// HuggingFace tokenizer loaded from a local `tokenizer.json`
+#[cfg(feature = "hf-tokenizers")]
+pub struct HuggingFaceTokenizer {
+    name: String,
+    tokenizer: tokenizers::Tokenizer,
+}
+
+#[cfg(feature = "hf-tokenizers")]
+impl HuggingFaceTokenizer {
+    /// Load a `tokenizer.json` file
+    pub fn from_file(name: impl Into<String>, path: &Path) -> crate::Result<Self> {
+        let tokenizer = tokenizers::Tokenizer::from_file(path).map_err(|e| {
+            crate::Error::Provider(format!(
+                "failed to load tokenizer {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(Self {
+            name: name.into(),
+            tokenizer,
+        })
+    }
+}
+
+#[cfg(feature = "hf-tokenizers")]
+impl Tokenizer for HuggingFaceTokenizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        match self.tokenizer.encode(text, false) {
+            Ok(encoding) => encoding.len(),
+            // Fall back to the default encoding rather than reporting zero
+            Err(_) => CL100K.count_tokens(text),
+        }
+    }
+}
+
+/// Provider and model a token count is made for
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TokenizerKey {
+    /// Provider name
+    pub provider: String,
+    /// Model name
+    pub model: String,
+}
+
+impl TokenizerKey {
+    /// Create a key
+    pub fn new(provider: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            model: model.into(),
+        }
+    }
+}
+
+/// Resolves and caches tokenizers per provider/model
+pub struct TokenizerRegistry {
+    local_dir: RwLock<Option<PathBuf>>,
+    overrides: RwLock<HashMap<String, Arc<dyn Tokenizer>>>,
+    cache: RwLock<HashMap<TokenizerKey, Arc<dyn Tokenizer>>>,
+}
+
+impl Default for TokenizerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenizerRegistry {
+    /// Create a registry without a local tokenizer directory
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            local_dir: RwLock::new(None),
+            overrides: RwLock::new(HashMap::new()),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Set the directory searched for HuggingFace `tokenizer.json` files
+    pub fn set_local_dir(&self, dir: Option<PathBuf>) {
+        *self.local_dir.write().unwrap_or_else(|e| e.into_inner()) = dir;
+        self.cache
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Use a tokenizer for a model, regardless of provider
+    pub fn register(&self, model: impl Into<String>, tokenizer: Arc<dyn Tokenizer>) {
+        self.overrides
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(model.into(), tokenizer);
+        self.cache
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Tokenizer for a provider/model pair
+    #[must_use]
+    pub fn resolve(&self, provider: &str, model: &str) -> Arc<dyn Tokenizer> {
+        if let Some(tokenizer) = self
+            .overrides
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(model)
+        {
+            return tokenizer.clone();
+        }
+
+        let key = TokenizerKey::new(provider, model);
+        if let Some(tokenizer) = self
+            .cache
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&key)
+        {
+            return tokenizer.clone();
+        }
+
+        let tokenizer = self
+            .load_local(model)
+            .unwrap_or_else(|| builtin_tokenizer(provider, model));
+        debug!(
+            provider,
+            model,
+            tokenizer = tokenizer.name(),
+            "Resolved tokenizer"
+        );
+        self.cache
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, tokenizer.clone());
+        tokenizer
+    }
+
+    #[cfg(feature = "hf-tokenizers")]
+    fn load_local(&self, model: &str) -> Option<Arc<dyn Tokenizer>> {
+        let dir = self
+            .local_dir
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()?;
+        let path = local_tokenizer_path(&dir, model)?;
+        match HuggingFaceTokenizer::from_file(model, &path) {
+            Ok(tokenizer) => Some(Arc::new(tokenizer)),
+            Err(e) => {
+                tracing::warn!(model, error = %e, "Ignoring local tokenizer");
+                None
+            }
+        }
+    }
+
+    #[cfg(not(feature = "hf-tokenizers"))]
+    fn load_local(&self, _model: &str) -> Option<Arc<dyn Tokenizer>> {
+        None
+    }
+}
+
+/// Path of the local `tokenizer.json` for a model, if present
+///
+/// Looks for `<dir>/<model>/tokenizer.json`, then without the Ollama tag
+/// (`llama3.2:3b` → `llama3.2`).
+#[cfg_attr(not(feature = "hf-tokenizers"), allow(dead_code))]
+fn local_tokenizer_path(dir: &Path, model: &str) -> Option<PathBuf> {
+    if model.is_empty() || model.contains("..") || model.starts_with('/') {
+        return None;
+    }
+    let untagged = model.split(':').next().unwrap_or(model);
+    [model, untagged]
+        .into_iter()
+        .map(|name| dir.join(name).join("tokenizer.json"))
+        .find(|path| path.is_file())
+}
+
+/// Tokenizer for a model without a local tokenizer file
+#[must_use]
+pub fn builtin_tokenizer(provider: &str, model: &str) -> Arc<dyn Tokenizer> {
+    let model = model.to_ascii_lowercase();
+    // Strip routing prefixes such as "openai/gpt-oss-20b" or "meta-llama/..."
+    let name = model.rsplit('/').next().unwrap_or(&model);
+
+    const O200K_PREFIXES: &[&str] = &[
+        "gpt-4o", "gpt-4.1", "gpt-4.5", "gpt-5", "gpt-oss", "chatgpt-", "o1", "o3", "o4",
+    ];
+    if O200K_PREFIXES.iter().any(|p| name.starts_with(p)) {
+        return BpeTokenizer::o200k();
+    }
+    if name.starts_with("gpt-4") || name.starts_with("gpt-3.5") {
+        return BpeTokenizer::cl100k();
+    }
+
+    let estimate = |family: &str, base: Arc<dyn Tokenizer>, ratio: f64| -> Arc<dyn Tokenizer> {
+        Arc::new(CalibratedTokenizer::new(
+            format!("{family}-estimate"),
+            base,
+            ratio,
+        ))
+    };
+    if name.contains("claude") || provider == "anthropic" {
+        return estimate("claude", BpeTokenizer::cl100k(), 1.15);
+    }
+    if name.contains("gemini") || name.contains("gemma") || provider == "gemini" {
+        return estimate("gemini", BpeTokenizer::o200k(), 1.05);
+    }
+    if name.contains("qwen") || provider == "qwen" {
+        return estimate("qwen", BpeTokenizer::o200k(), 1.0);
+    }
+    if name.contains("glm") || provider == "glm" {
+        return estimate("glm", BpeTokenizer::o200k(), 0.95);
+    }
+    if name.contains("llama-3") || name.contains("llama3") || name.contains("llama-4") {
+        return estimate("llama3", BpeTokenizer::o200k(), 1.05);
+    }
+    if name.contains("llama") {
+        return estimate("llama", BpeTokenizer::cl100k(), 1.25);
+    }
+    if name.contains("mistral") || name.contains("mixtral") {
+        return estimate("mistral", BpeTokenizer::cl100k(), 1.1);
+    }
+
+    BpeTokenizer::cl100k()
+}
+
+/// Global tokenizer registry
+#[must_use]
+pub fn global_tokenizers() -> &'static TokenizerRegistry {
+    &REGISTRY
+}
+
+/// Tokenizer for a provider/model pair from the global registry
+#[must_use]
+pub fn tokenizer_for(provider: &str, model: &str) -> Arc<dyn Tokenizer> {
+    REGISTRY.resolve(provider, model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openai_encodings() {
+        assert_eq!(builtin_tokenizer("openai", "gpt-5.2").name(), "o200k_base");
+        assert_eq!(
+            builtin_tokenizer("openai", "gpt-4o-mini").name(),
+            "o200k_base"
+        );
+        assert_eq!(
+            builtin_tokenizer("groq", "openai/gpt-oss-20b").name(),
+            "o200k_base"
+        );
+        assert_eq!(
+            builtin_tokenizer("openai", "gpt-4-turbo").name(),
+            "cl100k_base"
+        );
+        assert_eq!(
+            builtin_tokenizer("deepseek", "deepseek-chat").name(),
+            "cl100k_base"
+        );
+    }
+
+    #[test]
+    fn test_calibrated_families() {
+        assert_eq!(
+            builtin_tokenizer("anthropic", "claude-sonnet-4-5-20250929").name(),
+            "claude-estimate"
+        );
+        assert_eq!(
+            builtin_tokenizer("gemini", "gemini-2.5-flash").name(),
+            "gemini-estimate"
+        );
+        assert_eq!(
+            builtin_tokenizer("qwen", "qwen-plus").name(),
+            "qwen-estimate"
+        );
+        assert_eq!(
+            builtin_tokenizer("glm", "glm-4.7-flash").name(),
+            "glm-estimate"
+        );
+        assert_eq!(
+            builtin_tokenizer("ollama", "llama3.2:3b").name(),
+            "llama3-estimate"
+        );
+        // Provider decides when the model name is not recognized
+        assert_eq!(
+            builtin_tokenizer("anthropic", "custom").name(),
+            "claude-estimate"
+        );
+    }
+
+    #[test]
+    fn test_calibrated_count() {
+        let text = "The quick brown fox jumps over the lazy dog.";
+        let base = BpeTokenizer::cl100k();
+        let estimate = CalibratedTokenizer::new("test", base.clone(), 1.5);
+        let expected = (base.count_tokens(text) as f64 * 1.5).ceil() as usize;
+        assert_eq!(estimate.count_tokens(text), expected);
+        assert_eq!(estimate.count_tokens(""), 0);
+    }
+
+    #[test]
+    fn test_registry_override() {
+        struct Fixed;
+        impl Tokenizer for Fixed {
+            fn name(&self) -> &str {
+                "fixed"
+            }
+            fn count_tokens(&self, _text: &str) -> usize {
+                42
+            }
+        }
+
+        let registry = TokenizerRegistry::new();
+        assert_eq!(registry.resolve("ollama", "my-model").name(), "cl100k_base");
+
+        registry.register("my-model", Arc::new(Fixed));
+        let tokenizer = registry.resolve("ollama", "my-model");
+        assert_eq!(tokenizer.name(), "fixed");
+        assert_eq!(tokenizer.count_tokens("anything"), 42);
+        // Other models are unaffected
+        assert_eq!(registry.resolve("openai", "gpt-5").name(), "o200k_base");
+    }
+
+    #[test]
+    fn test_local_tokenizer_path() {
+        let dir = std::env::temp_dir().join(format!("cratos-tok-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("llama3.2")).unwrap();
+        std::fs::write(dir.join("llama3.2").join("tokenizer.json"), "{}").unwrap();
+
+        assert_eq!(
+            local_tokenizer_path(&dir, "llama3.2:3b"),
+            Some(dir.join("llama3.2").join("tokenizer.json"))
+        );
+        assert_eq!(local_tokenizer_path(&dir, "qwen2.5"), None);
+        assert_eq!(local_tokenizer_path(&dir, "../etc"), None);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
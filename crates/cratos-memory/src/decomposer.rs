@@ -53,6 +53,8 @@ pub fn decompose(session_id: &str, messages: &[Message], skip_before: Option<u32
                     turn_index,
                     token_count,
                     created_at: Utc::now(),
+                    owner_id: String::new(),
+                    channel_id: String::new(),
                 });
                 turn_index += 1;
                 i += 1;
@@ -92,6 +94,8 @@ pub fn decompose(session_id: &str, messages: &[Message], skip_before: Option<u32
                     turn_index,
                     token_count,
                     created_at: Utc::now(),
+                    owner_id: String::new(),
+                    channel_id: String::new(),
                 });
                 turn_index += 1;
                 i = j;
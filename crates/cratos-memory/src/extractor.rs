@@ -1,13 +1,36 @@
-//! Rule-based entity extraction — no LLM calls.
+//! Entity extraction.
 //!
-//! Extracts entities from turn content using regex patterns and keyword
-//! dictionaries. Each entity gets a relevance score based on position.
+//! [`extract`] is the rule-based extractor — no LLM calls. It finds entities
+//! in turn content using regex patterns and keyword dictionaries; each
+//! entity gets a relevance score based on position.
+//!
+//! Indexing goes through the [`EntityExtractor`] trait so a smarter extractor
+//! (e.g. [`LlmExtractor`](crate::llm_extractor::LlmExtractor)) can be plugged
+//! in. [`RuleBasedExtractor`] is the default and works offline.
 
 use crate::types::{EntityKind, ExtractedEntity, ExtractedRelation, RelationKind};
 use regex::Regex;
 use std::collections::HashSet;
 use std::sync::LazyLock;
 
+/// Pluggable entity and relation extractor.
+#[async_trait::async_trait]
+pub trait EntityExtractor: Send + Sync {
+    /// Extract entities and relations from a turn's content.
+    async fn extract(&self, content: &str) -> crate::Result<ExtractionResult>;
+}
+
+/// The default extractor: regex patterns and keyword dictionaries.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuleBasedExtractor;
+
+#[async_trait::async_trait]
+impl EntityExtractor for RuleBasedExtractor {
+    async fn extract(&self, content: &str) -> crate::Result<ExtractionResult> {
+        Ok(extract(content))
+    }
+}
+
 // ── Compiled patterns ───────────────────────────────────────────
 
 static RE_ACRONYM: LazyLock<Regex> = LazyLock::new(|| {
@@ -30,6 +53,33 @@ static RE_CRATE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(cratos-\w+)\
 static RE_ERROR: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?:error\[E\d+\]|Error::\w+|panic!?\b|unwrap\(\))").unwrap());
 
+static RE_URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bhttps?://[^\s<>()\[\]{}"'`]+"#).unwrap());
+
+static RE_DATE: LazyLock<Regex> = LazyLock::new(|| {
+    // ISO dates (2026-03-14) and month-day dates (March 14, Mar 14th 2026)
+    Regex::new(
+        r"\b(?:\d{4}-\d{2}-\d{2}|(?:Jan(?:uary)?|Feb(?:ruary)?|Mar(?:ch)?|Apr(?:il)?|May|June?|July?|Aug(?:ust)?|Sep(?:t(?:ember)?)?|Oct(?:ober)?|Nov(?:ember)?|Dec(?:ember)?)\.? \d{1,2}(?:st|nd|rd|th)?(?:,? \d{4})?)\b",
+    )
+    .unwrap()
+});
+
+static RE_PERSON: LazyLock<Regex> = LazyLock::new(|| {
+    // Names introduced by a title (Dr. Kim, Ms Jane Doe)
+    Regex::new(r"\b(?:Mr|Mrs|Ms|Dr|Prof)\.? ([A-Z][a-z]+(?: [A-Z][a-z]+)?)").unwrap()
+});
+
+static RE_ORGANIZATION: LazyLock<Regex> = LazyLock::new(|| {
+    // Capitalized names ending in a legal or institutional suffix
+    Regex::new(
+        r"\b((?:[A-Z][\w&-]* ){1,3}(?:Inc|Corp|Corporation|Ltd|LLC|GmbH|Foundation|University|Labs))\b",
+    )
+    .unwrap()
+});
+
+static RE_PROJECT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[Pp]roject ([A-Z][\w-]+)").unwrap());
+
 /// Technical concept keywords.
 static CONCEPT_KEYWORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
     [
@@ -253,6 +303,51 @@ pub fn extract(content: &str) -> ExtractionResult {
         }
     }
 
+    // ── URLs ────────────────────────────────────────────────
+    for mat in RE_URL.find_iter(content) {
+        let url = mat
+            .as_str()
+            .trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        if seen.insert(("url", url.to_string())) {
+            entities.push(ExtractedEntity {
+                name: url.to_string(),
+                kind: EntityKind::Url,
+                relevance: position_relevance(mat.start(), first_line_end),
+            });
+        }
+    }
+
+    // ── Dates ───────────────────────────────────────────────
+    for mat in RE_DATE.find_iter(content) {
+        let name = mat.as_str().to_string();
+        if seen.insert(("event", name.clone())) {
+            entities.push(ExtractedEntity {
+                name,
+                kind: EntityKind::Event,
+                relevance: position_relevance(mat.start(), first_line_end),
+            });
+        }
+    }
+
+    // ── People, organizations, projects ─────────────────────
+    for (re, kind, key) in [
+        (&*RE_PERSON, EntityKind::Person, "person"),
+        (&*RE_ORGANIZATION, EntityKind::Organization, "organization"),
+        (&*RE_PROJECT, EntityKind::Project, "project"),
+    ] {
+        for cap in re.captures_iter(content) {
+            let name = cap[1].to_string();
+            if seen.insert((key, name.clone())) {
+                let pos = cap.get(0).map(|m| m.start()).unwrap_or(usize::MAX);
+                entities.push(ExtractedEntity {
+                    name,
+                    kind,
+                    relevance: position_relevance(pos, first_line_end),
+                });
+            }
+        }
+    }
+
     // ── Tools ───────────────────────────────────────────────
     for tool in TOOL_NAMES.iter() {
         if content_lower.contains(tool) && seen.insert(("tool", (*tool).to_string())) {
@@ -436,4 +531,48 @@ mod tests {
     fn test_empty_content() {
         assert!(extract("").entities.is_empty());
     }
+
+    fn names_of(result: &ExtractionResult, kind: EntityKind) -> Vec<&str> {
+        result
+            .entities
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| e.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_urls() {
+        let result = extract("Docs are at https://docs.rs/tokio/latest. Thanks!");
+        assert_eq!(
+            names_of(&result, EntityKind::Url),
+            vec!["https://docs.rs/tokio/latest"]
+        );
+    }
+
+    #[test]
+    fn test_extract_dates() {
+        let result = extract("The review moved from 2026-03-14 to March 21st, 2026");
+        let dates = names_of(&result, EntityKind::Event);
+        assert!(dates.contains(&"2026-03-14"));
+        assert!(dates.contains(&"March 21st, 2026"));
+    }
+
+    #[test]
+    fn test_extract_people_organizations_projects() {
+        let result = extract("Dr. Jane Doe from Acme Robotics Inc joined project Apollo last week");
+        assert_eq!(names_of(&result, EntityKind::Person), vec!["Jane Doe"]);
+        assert_eq!(
+            names_of(&result, EntityKind::Organization),
+            vec!["Acme Robotics Inc"]
+        );
+        assert_eq!(names_of(&result, EntityKind::Project), vec!["Apollo"]);
+    }
+
+    #[tokio::test]
+    async fn test_rule_based_extractor_matches_extract() {
+        let content = "Fix orchestrator.rs in cratos-core";
+        let result = RuleBasedExtractor.extract(content).await.unwrap();
+        assert_eq!(result.entities.len(), extract(content).entities.len());
+    }
 }
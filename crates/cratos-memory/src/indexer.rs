@@ -2,16 +2,19 @@
 //!
 //! After each orchestrator execution, the indexer:
 //! 1. Decomposes messages into turns (skipping already-indexed ones)
-//! 2. Extracts entities from each new turn
+//! 2. Extracts entities from each new turn (rule-based unless another
+//!    [`EntityExtractor`] is plugged in)
 //! 3. Persists turns, entities, and edges to GraphStore
 //! 4. Embeds turn summaries and adds them to the VectorIndex
 
 use crate::decomposer;
-use crate::extractor;
+use crate::extractor::{self, EntityExtractor, RuleBasedExtractor};
+use crate::scope::MemoryScope;
 use crate::store::GraphStore;
 use crate::types::{Entity, EntityKind, EntityRelation, TurnEntityEdge};
 use chrono::Utc;
 use cratos_llm::Message;
+use std::sync::Arc;
 use tracing::{debug, warn};
 use uuid::Uuid;
 
@@ -20,6 +23,8 @@ pub struct TurnIndexer {
     store: GraphStore,
     /// Optional: embed callback. If None, vector indexing is skipped.
     embedder: Option<Box<dyn EmbedAndStore>>,
+    /// Entity extractor (rule-based unless replaced)
+    extractor: Arc<dyn EntityExtractor>,
 }
 
 /// Trait for embedding a text and storing the vector.
@@ -37,6 +42,7 @@ impl TurnIndexer {
         Self {
             store,
             embedder: None,
+            extractor: Arc::new(RuleBasedExtractor),
         }
     }
 
@@ -45,20 +51,36 @@ impl TurnIndexer {
         Self {
             store,
             embedder: Some(embedder),
+            extractor: Arc::new(RuleBasedExtractor),
         }
     }
 
+    /// Use a different entity extractor.
+    ///
+    /// If it fails on a turn, the rule-based extractor is used instead.
+    #[must_use]
+    pub fn with_extractor(mut self, extractor: Arc<dyn EntityExtractor>) -> Self {
+        self.extractor = extractor;
+        self
+    }
+
     /// Index new turns from a completed session.
     ///
     /// Only turns with `turn_index` greater than the previously indexed max
-    /// are processed (incremental).
+    /// are processed (incremental). New turns are attributed to the owner and
+    /// channel of `scope`.
     pub async fn index_session(
         &self,
+        scope: &MemoryScope,
         session_id: &str,
         messages: &[Message],
     ) -> crate::Result<u32> {
         let existing_max = self.store.max_turn_index(session_id).await?;
-        let turns = decomposer::decompose(session_id, messages, existing_max);
+        let mut turns = decomposer::decompose(session_id, messages, existing_max);
+        for turn in &mut turns {
+            turn.owner_id = scope.owner_id().unwrap_or_default().to_string();
+            turn.channel_id = scope.channel_id().unwrap_or_default().to_string();
+        }
 
         if turns.is_empty() {
             debug!(session_id, "No new turns to index");
@@ -71,7 +93,13 @@ impl TurnIndexer {
             self.store.insert_turn(turn).await?;
 
             // 2. Extract entities and relations
-            let extracted = extractor::extract(&turn.content);
+            let extracted = match self.extractor.extract(&turn.content).await {
+                Ok(extracted) => extracted,
+                Err(e) => {
+                    warn!(turn_id = %turn.id, error = %e, "Entity extraction failed, using rules");
+                    extractor::extract(&turn.content)
+                }
+            };
 
             // 3. Persist entities + edges
             let mut entity_ids = Vec::with_capacity(extracted.entities.len());
@@ -97,6 +125,7 @@ impl TurnIndexer {
                         from_entity_id: from.id,
                         to_entity_id: to.id,
                         kind: rel_ext.kind,
+                        source_id: turn.id.clone(),
                     };
                     self.store.insert_relation(&rel).await?;
                 }
@@ -157,7 +186,10 @@ mod tests {
             Message::user("Also check store.rs"),
         ];
 
-        let count = indexer.index_session("s1", &messages).await.unwrap();
+        let count = indexer
+            .index_session(&MemoryScope::global(), "s1", &messages)
+            .await
+            .unwrap();
         assert_eq!(count, 3); // system skipped
 
         // Verify graph
@@ -177,7 +209,10 @@ mod tests {
         let indexer = TurnIndexer::new(store.clone());
 
         let messages1 = vec![Message::user("Hello"), Message::assistant("Hi")];
-        let count1 = indexer.index_session("s1", &messages1).await.unwrap();
+        let count1 = indexer
+            .index_session(&MemoryScope::global(), "s1", &messages1)
+            .await
+            .unwrap();
         assert_eq!(count1, 2);
 
         // Add more messages and re-index — only new turns should be indexed
@@ -186,7 +221,10 @@ mod tests {
             Message::assistant("Hi"),
             Message::user("What's new?"),
         ];
-        let count2 = indexer.index_session("s1", &messages2).await.unwrap();
+        let count2 = indexer
+            .index_session(&MemoryScope::global(), "s1", &messages2)
+            .await
+            .unwrap();
         assert_eq!(count2, 1); // only the new user message
 
         assert_eq!(store.turn_count().await.unwrap(), 3);
@@ -201,7 +239,10 @@ mod tests {
             Message::user("Look at orchestrator.rs"),
             Message::assistant("Checking orchestrator.rs now"),
         ];
-        indexer.index_session("s1", &messages).await.unwrap();
+        indexer
+            .index_session(&MemoryScope::global(), "s1", &messages)
+            .await
+            .unwrap();
 
         let entity = store
             .get_entity_by_name("orchestrator.rs")
@@ -210,4 +251,32 @@ mod tests {
             .unwrap();
         assert!(entity.mention_count >= 2);
     }
+
+    /// Extractor that always fails
+    struct FailingExtractor;
+
+    #[async_trait::async_trait]
+    impl EntityExtractor for FailingExtractor {
+        async fn extract(&self, _content: &str) -> crate::Result<extractor::ExtractionResult> {
+            Err(crate::Error::Extraction("model unavailable".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_extractor_falls_back_to_rules() {
+        let store = GraphStore::in_memory().await.unwrap();
+        let indexer = TurnIndexer::new(store.clone()).with_extractor(Arc::new(FailingExtractor));
+
+        let messages = vec![Message::user("Look at orchestrator.rs")];
+        indexer
+            .index_session(&MemoryScope::global(), "s1", &messages)
+            .await
+            .unwrap();
+
+        assert!(store
+            .get_entity_by_name("orchestrator.rs")
+            .await
+            .unwrap()
+            .is_some());
+    }
 }
@@ -29,19 +29,24 @@ pub mod decomposer;
 pub mod error;
 pub mod extractor;
 pub mod indexer;
+pub mod llm_extractor;
 pub mod retriever;
+pub mod scope;
 pub mod scorer;
 pub mod store;
 pub mod types;
 
 pub use error::{Error, Result};
+pub use extractor::{EntityExtractor, ExtractionResult, RuleBasedExtractor};
 pub use indexer::{EmbedAndStore, TurnIndexer};
+pub use llm_extractor::LlmExtractor;
 pub use retriever::{GraphRagRetriever, VectorSearch};
+pub use scope::MemoryScope;
 pub use scorer::ScoringWeights;
 pub use store::GraphStore;
 pub use types::{
-    Entity, EntityKind, EntityRelation, ExplicitMemory, ExtractedEntity, RelationKind,
-    RetrievedTurn, Turn, TurnEntityEdge, TurnRole,
+    Entity, EntityKind, EntityRelation, ExplicitMemory, ExtractedEntity, MemoryVisibility,
+    RelationKind, RetrievedTurn, Turn, TurnEntityEdge, TurnRole,
 };
 
 #[cfg(feature = "embeddings")]
@@ -65,6 +70,10 @@ use uuid::Uuid;
 /// - **Graph-only** (`from_path` / `in_memory`): entity graph search, no embeddings.
 /// - **With embeddings** (`with_vector_bridge`): hybrid search combining embedding
 ///   similarity + entity graph traversal for better recall.
+///
+/// Every read and write takes a [`MemoryScope`]: turns and memories are
+/// attributed to the scope's owner and channel, and only what the scope may
+/// see is returned.
 pub struct GraphMemory {
     store: GraphStore,
     /// When set, indexing also embeds turn summaries and retrieval uses vector seeds.
@@ -75,6 +84,8 @@ pub struct GraphMemory {
     explicit_embed: Option<Arc<dyn EmbedAndStore + Send + Sync>>,
     /// Search bridge for explicit memories.
     explicit_search: Option<Arc<dyn VectorSearch>>,
+    /// Entity extractor used when indexing (rule-based if None).
+    extractor: Option<Arc<dyn EntityExtractor>>,
 }
 
 impl GraphMemory {
@@ -87,6 +98,7 @@ impl GraphMemory {
             vector_search: None,
             explicit_embed: None,
             explicit_search: None,
+            extractor: None,
         })
     }
 
@@ -99,6 +111,7 @@ impl GraphMemory {
             vector_search: None,
             explicit_embed: None,
             explicit_search: None,
+            extractor: None,
         })
     }
 
@@ -121,12 +134,24 @@ impl GraphMemory {
         self
     }
 
-    /// Index new turns from a completed session.
+    /// Use a custom entity extractor (e.g. [`LlmExtractor`]) when indexing.
+    #[must_use]
+    pub fn with_extractor(mut self, extractor: Arc<dyn EntityExtractor>) -> Self {
+        self.extractor = Some(extractor);
+        self
+    }
+
+    /// Index new turns from a completed session, attributed to `scope`.
     ///
     /// If a vector bridge is attached, turn summaries are also embedded.
     /// Returns the number of newly indexed turns.
-    pub async fn index_session(&self, session_id: &str, messages: &[Message]) -> Result<u32> {
-        let indexer = if let Some(bridge) = &self.vector_bridge {
+    pub async fn index_session(
+        &self,
+        scope: &MemoryScope,
+        session_id: &str,
+        messages: &[Message],
+    ) -> Result<u32> {
+        let mut indexer = if let Some(bridge) = &self.vector_bridge {
             TurnIndexer::with_embedder(
                 self.store.clone(),
                 Box::new(BridgeAdapter(Arc::clone(bridge))),
@@ -134,17 +159,21 @@ impl GraphMemory {
         } else {
             TurnIndexer::new(self.store.clone())
         };
-        let count = indexer.index_session(session_id, messages).await?;
+        if let Some(extractor) = &self.extractor {
+            indexer = indexer.with_extractor(Arc::clone(extractor));
+        }
+        let count = indexer.index_session(scope, session_id, messages).await?;
         debug!(session_id, count, "GraphMemory indexed session");
         Ok(count)
     }
 
-    /// Retrieve relevant past turns for a query.
+    /// Retrieve relevant past turns visible in `scope` for a query.
     ///
     /// Uses hybrid search (embedding + graph) if a vector bridge is attached,
     /// otherwise falls back to entity-graph-only retrieval.
     pub async fn retrieve(
         &self,
+        scope: &MemoryScope,
         query: &str,
         max_turns: usize,
         max_tokens: u32,
@@ -157,7 +186,9 @@ impl GraphMemory {
         } else {
             GraphRagRetriever::new(self.store.clone())
         };
-        retriever.retrieve(query, max_turns, max_tokens).await
+        retriever
+            .retrieve(scope, query, max_turns, max_tokens)
+            .await
     }
 
     /// Convert retrieved turns into LLM messages for context injection.
@@ -185,33 +216,48 @@ impl GraphMemory {
 
     // ── Graph Data Export API ────────────────────────────────────
 
-    /// List all entities (for graph visualization).
-    pub async fn list_entities(&self, limit: u32) -> Result<Vec<Entity>> {
-        self.store.list_all_entities(limit).await
+    /// List entities mentioned by turns or memories visible in `scope`
+    /// (for graph visualization).
+    pub async fn list_entities(&self, scope: &MemoryScope, limit: u32) -> Result<Vec<Entity>> {
+        self.store.list_entities(scope, limit).await
     }
 
-    /// List all co-occurrence edges (for graph visualization).
+    /// List co-occurrence edges between entities visible in `scope`
+    /// (for graph visualization).
     /// Returns tuples of (entity_id_a, entity_id_b, cooccurrence_count).
-    pub async fn list_cooccurrences(&self, limit: u32) -> Result<Vec<(String, String, u32)>> {
-        self.store.list_all_cooccurrences(limit).await
+    pub async fn list_cooccurrences(
+        &self,
+        scope: &MemoryScope,
+        limit: u32,
+    ) -> Result<Vec<(String, String, u32)>> {
+        self.store.list_cooccurrences(scope, limit).await
     }
 
-    /// List all entity relations (for graph visualization).
-    pub async fn list_relations(&self, limit: u32) -> Result<Vec<EntityRelation>> {
-        self.store.list_all_relations(limit).await
+    /// List relations between entities visible in `scope` (for graph visualization).
+    pub async fn list_relations(
+        &self,
+        scope: &MemoryScope,
+        limit: u32,
+    ) -> Result<Vec<EntityRelation>> {
+        self.store.list_relations(scope, limit).await
     }
 
     // ── Explicit Memory API ──────────────────────────────────────
 
     /// Save an explicit memory with entity extraction and optional embedding.
     ///
-    /// Returns the memory ID (new UUID or existing if name already exists).
+    /// The memory belongs to the owner of `scope` and is bound to its channel;
+    /// [`MemoryVisibility::Team`] shares it with everyone.
+    /// Returns the memory ID (new UUID or existing if the owner already has
+    /// a memory with this name).
     pub async fn save_memory(
         &self,
+        scope: &MemoryScope,
         name: &str,
         content: &str,
         category: &str,
         tags: &[String],
+        visibility: MemoryVisibility,
     ) -> Result<String> {
         let now = Utc::now();
         let mem = ExplicitMemory {
@@ -223,6 +269,9 @@ impl GraphMemory {
             created_at: now,
             updated_at: now,
             access_count: 0,
+            owner_id: scope.owner_id().unwrap_or_default().to_string(),
+            channel_id: scope.channel_id().unwrap_or_default().to_string(),
+            visibility,
         };
 
         // 1. Persist to SQLite
@@ -231,7 +280,7 @@ impl GraphMemory {
         // Re-fetch to get the canonical ID (upsert may keep old ID)
         let saved = self
             .store
-            .get_explicit_by_name(name)
+            .get_explicit_by_owner(&mem.owner_id, name)
             .await?
             .ok_or_else(|| Error::Internal("Memory not found after save".into()))?;
         let mem_id = saved.id.clone();
@@ -265,6 +314,7 @@ impl GraphMemory {
                     from_entity_id: from.id,
                     to_entity_id: to.id,
                     kind: rel_ext.kind,
+                    source_id: mem_id.clone(),
                 };
                 self.store.insert_relation(&rel).await?;
             }
@@ -281,18 +331,19 @@ impl GraphMemory {
         Ok(mem_id)
     }
 
-    /// Hybrid recall of explicit memories.
+    /// Hybrid recall of explicit memories visible in `scope`.
     ///
     /// Combines: exact name match, vector search, entity graph, LIKE search.
     pub async fn recall_memories(
         &self,
+        scope: &MemoryScope,
         query: &str,
         max_results: usize,
     ) -> Result<Vec<ExplicitMemory>> {
         let mut scored: HashMap<String, (ExplicitMemory, f32)> = HashMap::new();
 
         // 1. Exact name match (highest score)
-        if let Some(mem) = self.store.get_explicit_by_name(query).await? {
+        if let Some(mem) = self.store.get_explicit_by_name(scope, query).await? {
             scored.insert(mem.id.clone(), (mem, 10.0));
         }
 
@@ -301,7 +352,8 @@ impl GraphMemory {
             match vs.search(query, max_results * 2).await {
                 Ok(results) => {
                     for (mem_id, sim) in results {
-                        if let Some(mem) = self.store.get_explicit_by_id(&mem_id).await? {
+                        let mem = self.store.get_explicit_by_id(&mem_id).await?;
+                        if let Some(mem) = mem.filter(|m| scope.allows_memory(m)) {
                             scored
                                 .entry(mem.id.clone())
                                 .and_modify(|(_, s)| *s += sim)
@@ -319,7 +371,7 @@ impl GraphMemory {
             if let Some(entity) = self.store.get_entity_by_name(&ext.name).await? {
                 match self.store.get_explicit_by_entity(&entity.id).await {
                     Ok(mems) => {
-                        for mem in mems {
+                        for mem in mems.into_iter().filter(|m| scope.allows_memory(m)) {
                             scored
                                 .entry(mem.id.clone())
                                 .and_modify(|(_, s)| *s += 0.5)
@@ -345,7 +397,7 @@ impl GraphMemory {
         for term in &search_terms {
             match self
                 .store
-                .search_explicit(term, None, max_results as u32)
+                .search_explicit(scope, term, None, max_results as u32)
                 .await
             {
                 Ok(mems) => {
@@ -385,6 +437,7 @@ impl GraphMemory {
     /// would pollute the LLM context.
     pub async fn recall_memories_filtered(
         &self,
+        scope: &MemoryScope,
         query: &str,
         max_results: usize,
         min_score: f32,
@@ -392,7 +445,7 @@ impl GraphMemory {
         let mut scored: HashMap<String, (ExplicitMemory, f32)> = HashMap::new();
 
         // 1. Exact name match (highest score)
-        if let Some(mem) = self.store.get_explicit_by_name(query).await? {
+        if let Some(mem) = self.store.get_explicit_by_name(scope, query).await? {
             scored.insert(mem.id.clone(), (mem, 10.0));
         }
 
@@ -401,7 +454,8 @@ impl GraphMemory {
             match vs.search(query, max_results * 2).await {
                 Ok(results) => {
                     for (mem_id, sim) in results {
-                        if let Some(mem) = self.store.get_explicit_by_id(&mem_id).await? {
+                        let mem = self.store.get_explicit_by_id(&mem_id).await?;
+                        if let Some(mem) = mem.filter(|m| scope.allows_memory(m)) {
                             scored
                                 .entry(mem.id.clone())
                                 .and_modify(|(_, s)| *s += sim)
@@ -419,7 +473,7 @@ impl GraphMemory {
             if let Some(entity) = self.store.get_entity_by_name(&ext.name).await? {
                 match self.store.get_explicit_by_entity(&entity.id).await {
                     Ok(mems) => {
-                        for mem in mems {
+                        for mem in mems.into_iter().filter(|m| scope.allows_memory(m)) {
                             scored
                                 .entry(mem.id.clone())
                                 .and_modify(|(_, s)| *s += 0.5)
@@ -452,23 +506,34 @@ impl GraphMemory {
         Ok(results.into_iter().map(|(m, _)| m).collect())
     }
 
-    /// List explicit memories, optionally filtered by category.
+    /// List explicit memories visible in `scope`, optionally filtered by category.
     pub async fn list_memories(
         &self,
+        scope: &MemoryScope,
         category: Option<&str>,
         limit: u32,
     ) -> Result<Vec<ExplicitMemory>> {
-        self.store.list_explicit(category, limit).await
+        self.store.list_explicit(scope, category, limit).await
     }
 
-    /// Delete an explicit memory by name.
-    pub async fn delete_memory(&self, name: &str) -> Result<bool> {
-        self.store.delete_explicit(name).await
+    /// Get an explicit memory visible in `scope` by name.
+    pub async fn get_memory(
+        &self,
+        scope: &MemoryScope,
+        name: &str,
+    ) -> Result<Option<ExplicitMemory>> {
+        self.store.get_explicit_by_name(scope, name).await
     }
 
-    /// Update an explicit memory (partial update).
+    /// Delete an explicit memory by name (only the owner's own memories).
+    pub async fn delete_memory(&self, scope: &MemoryScope, name: &str) -> Result<bool> {
+        self.store.delete_explicit(scope, name).await
+    }
+
+    /// Update an explicit memory (partial update, only the owner's own memories).
     pub async fn update_memory(
         &self,
+        scope: &MemoryScope,
         name: &str,
         content: Option<&str>,
         category: Option<&str>,
@@ -476,8 +541,9 @@ impl GraphMemory {
     ) -> Result<()> {
         let existing = self
             .store
-            .get_explicit_by_name(name)
+            .get_explicit_by_name(scope, name)
             .await?
+            .filter(|m| scope.can_modify(m))
             .ok_or_else(|| Error::Internal(format!("Memory '{name}' not found")))?;
 
         let updated = ExplicitMemory {
@@ -489,6 +555,9 @@ impl GraphMemory {
             created_at: existing.created_at,
             updated_at: Utc::now(),
             access_count: existing.access_count,
+            owner_id: existing.owner_id,
+            channel_id: existing.channel_id,
+            visibility: existing.visibility,
         };
 
         self.store.save_explicit_memory(&updated).await?;
@@ -519,7 +588,10 @@ impl GraphMemory {
                 return Ok(0);
             }
         };
-        let all = self.store.list_explicit(None, 1000).await?;
+        let all = self
+            .store
+            .list_explicit(&MemoryScope::global(), None, 1000)
+            .await?;
         info!(total = all.len(), "Reindexing explicit memories");
         let mut count = 0;
         for mem in &all {
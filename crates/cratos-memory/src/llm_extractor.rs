@@ -0,0 +1,302 @@
+//! LLM-assisted entity extraction.
+//!
+//! [`LlmExtractor`] asks a (cheap) model for the people, projects,
+//! organizations, places, events and typed relations in a turn, and merges
+//! them with the rule-based result. Indexing runs in the background, so the
+//! extra call does not delay responses.
+
+use crate::extractor::{self, EntityExtractor, ExtractionResult};
+use crate::types::{EntityKind, ExtractedEntity, ExtractedRelation, RelationKind};
+use crate::{Error, Result};
+use cratos_llm::{CompletionRequest, LlmProvider, Message};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Instructions for the extraction model
+const EXTRACTION_PROMPT: &str = "\
+Extract the named entities and the relations between them from the user's text.
+Entity kinds: person, project, organization, place, event (dates, deadlines, meetings), url, concept, tool, file.
+Relation kinds: works_on, member_of, located_in, attends, related.
+Only include entities that are explicitly mentioned. Respond with JSON only:
+{\"entities\": [{\"name\": \"...\", \"kind\": \"...\"}], \"relations\": [{\"from\": \"...\", \"to\": \"...\", \"kind\": \"...\"}]}";
+
+/// Turns shorter than this are left to the rule-based extractor
+const MIN_CONTENT_CHARS: usize = 20;
+
+/// Names longer than this are sentences, not entities
+const MAX_NAME_CHARS: usize = 80;
+
+/// Relevance of entities found only by the model
+const LLM_ENTITY_RELEVANCE: f32 = 0.8;
+
+/// Extractor that adds LLM-found entities to the rule-based ones.
+pub struct LlmExtractor {
+    provider: Arc<dyn LlmProvider>,
+    model: String,
+    max_input_chars: usize,
+}
+
+impl LlmExtractor {
+    /// Create an extractor calling `model` on `provider`.
+    pub fn new(provider: Arc<dyn LlmProvider>, model: impl Into<String>) -> Self {
+        Self {
+            provider,
+            model: model.into(),
+            max_input_chars: 4000,
+        }
+    }
+
+    /// Truncate turn content sent to the model to this many characters.
+    #[must_use]
+    pub fn with_max_input_chars(mut self, max_input_chars: usize) -> Self {
+        self.max_input_chars = max_input_chars;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl EntityExtractor for LlmExtractor {
+    async fn extract(&self, content: &str) -> Result<ExtractionResult> {
+        let mut result = extractor::extract(content);
+        if content.trim().chars().count() < MIN_CONTENT_CHARS {
+            return Ok(result);
+        }
+
+        let text: String = content.chars().take(self.max_input_chars).collect();
+        let request = CompletionRequest::new(&self.model)
+            .with_messages(vec![
+                Message::system(EXTRACTION_PROMPT),
+                Message::user(text),
+            ])
+            .with_max_tokens(800)
+            .with_temperature(0.0);
+        let response = self
+            .provider
+            .complete(request)
+            .await
+            .map_err(|e| Error::Extraction(e.to_string()))?;
+
+        merge(&mut result, parse_response(&response.content)?);
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmExtraction {
+    #[serde(default)]
+    entities: Vec<LlmEntity>,
+    #[serde(default)]
+    relations: Vec<LlmRelation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmEntity {
+    name: String,
+    kind: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmRelation {
+    from: String,
+    to: String,
+    kind: String,
+}
+
+/// Parse the model's JSON answer (tolerating code fences and chatter around it).
+fn parse_response(text: &str) -> Result<ExtractionResult> {
+    let json = match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &text[start..=end],
+        _ => {
+            return Err(Error::Extraction(
+                "no JSON object in model response".to_string(),
+            ))
+        }
+    };
+    let parsed: LlmExtraction = serde_json::from_str(json)?;
+
+    let entities: Vec<ExtractedEntity> = parsed
+        .entities
+        .into_iter()
+        .filter_map(|e| {
+            let name = e.name.trim();
+            (!name.is_empty() && name.chars().count() <= MAX_NAME_CHARS).then(|| ExtractedEntity {
+                name: name.to_string(),
+                kind: EntityKind::from_str_lossy(&e.kind.to_lowercase()),
+                relevance: LLM_ENTITY_RELEVANCE,
+            })
+        })
+        .collect();
+    let relations = parsed
+        .relations
+        .into_iter()
+        .map(|r| ExtractedRelation {
+            from_entity: r.from.trim().to_string(),
+            to_entity: r.to.trim().to_string(),
+            kind: RelationKind::from_str_lossy(&r.kind.to_lowercase()),
+        })
+        .collect();
+
+    Ok(ExtractionResult {
+        entities,
+        relations,
+    })
+}
+
+/// Add the model's entities to `result`.
+///
+/// A rule-based duplicate is kept, unless the rules only knew it as a
+/// generic concept (e.g. a capitalized word) and the model knows its kind.
+/// Relations are kept only between entities of the merged result.
+fn merge(result: &mut ExtractionResult, llm: ExtractionResult) {
+    let mut names: HashSet<String> = result
+        .entities
+        .iter()
+        .map(|e| e.name.to_lowercase())
+        .collect();
+    for entity in llm.entities {
+        if names.insert(entity.name.to_lowercase()) {
+            result.entities.push(entity);
+        } else if entity.kind != EntityKind::Concept {
+            if let Some(existing) = result.entities.iter_mut().find(|e| {
+                e.kind == EntityKind::Concept && e.name.eq_ignore_ascii_case(&entity.name)
+            }) {
+                existing.name = entity.name;
+                existing.kind = entity.kind;
+                existing.relevance = existing.relevance.max(entity.relevance);
+            }
+        }
+    }
+
+    // Relations must point at the stored spelling of an entity name
+    let canonical = |name: &str| {
+        result
+            .entities
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(name))
+            .map(|e| e.name.clone())
+    };
+    let relations: Vec<ExtractedRelation> = llm
+        .relations
+        .into_iter()
+        .filter_map(|r| {
+            let from_entity = canonical(&r.from_entity)?;
+            let to_entity = canonical(&r.to_entity)?;
+            (from_entity != to_entity).then_some(ExtractedRelation {
+                from_entity,
+                to_entity,
+                kind: r.kind,
+            })
+        })
+        .collect();
+    result.relations.extend(relations);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cratos_llm::{CompletionResponse, ToolCompletionRequest, ToolCompletionResponse};
+
+    /// Provider answering every completion with a fixed text
+    struct FixedProvider(String);
+
+    #[async_trait::async_trait]
+    impl LlmProvider for FixedProvider {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn supports_tools(&self) -> bool {
+            false
+        }
+
+        fn available_models(&self) -> Vec<String> {
+            vec!["fixed-model".to_string()]
+        }
+
+        fn default_model(&self) -> &str {
+            "fixed-model"
+        }
+
+        async fn complete(
+            &self,
+            request: CompletionRequest,
+        ) -> cratos_llm::Result<CompletionResponse> {
+            Ok(CompletionResponse {
+                content: self.0.clone(),
+                usage: None,
+                finish_reason: Some("stop".to_string()),
+                model: request.model,
+            })
+        }
+
+        async fn complete_with_tools(
+            &self,
+            _request: ToolCompletionRequest,
+        ) -> cratos_llm::Result<ToolCompletionResponse> {
+            Err(cratos_llm::Error::NotConfigured("tools".to_string()))
+        }
+    }
+
+    fn extractor(answer: &str) -> LlmExtractor {
+        LlmExtractor::new(Arc::new(FixedProvider(answer.to_string())), "fixed-model")
+    }
+
+    #[tokio::test]
+    async fn test_merges_llm_entities_and_relations() {
+        let answer = r#"```json
+{"entities": [
+  {"name": "Jane Doe", "kind": "person"},
+  {"name": "Apollo", "kind": "Project"},
+  {"name": "Berlin", "kind": "place"}
+],
+"relations": [
+  {"from": "jane doe", "to": "Apollo", "kind": "works_on"},
+  {"from": "Apollo", "to": "Nowhere", "kind": "located_in"}
+]}
+```"#;
+        let result = extractor(answer)
+            .extract("Dr. Jane Doe leads the Apollo launch in Berlin next month")
+            .await
+            .unwrap();
+
+        let kind_of = |name: &str| {
+            result
+                .entities
+                .iter()
+                .find(|e| e.name == name)
+                .map(|e| e.kind)
+        };
+        // Found by the rules and the model: kept once
+        assert_eq!(
+            result
+                .entities
+                .iter()
+                .filter(|e| e.name == "Jane Doe")
+                .count(),
+            1
+        );
+        assert_eq!(kind_of("Apollo"), Some(EntityKind::Project));
+        assert_eq!(kind_of("Berlin"), Some(EntityKind::Place));
+
+        // The relation to an unknown entity is dropped
+        assert_eq!(result.relations.len(), 1);
+        assert_eq!(result.relations[0].from_entity, "Jane Doe");
+        assert_eq!(result.relations[0].to_entity, "Apollo");
+        assert_eq!(result.relations[0].kind, RelationKind::WorksOn);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_answer_is_an_error() {
+        let result = extractor("I could not find any entities.")
+            .extract("Meeting with the Berlin office about the launch plan")
+            .await;
+        assert!(matches!(result, Err(Error::Extraction(_))));
+    }
+
+    #[tokio::test]
+    async fn test_short_content_skips_model() {
+        let result = extractor("not json").extract("hi there").await.unwrap();
+        assert!(result.relations.is_empty());
+    }
+}
@@ -8,6 +8,7 @@
 //! 5. Select top turns within the token budget
 
 use crate::extractor;
+use crate::scope::MemoryScope;
 use crate::scorer::{self, ScoringWeights};
 use crate::store::GraphStore;
 use crate::types::RetrievedTurn;
@@ -62,10 +63,12 @@ impl GraphRagRetriever {
 
     /// Retrieve relevant turns for a query.
     ///
+    /// - `scope`: only turns visible in this scope are considered
     /// - `max_turns`: maximum number of turns to return
     /// - `max_tokens`: token budget (stops adding turns when exceeded)
     pub async fn retrieve(
         &self,
+        scope: &MemoryScope,
         query: &str,
         max_turns: usize,
         max_tokens: u32,
@@ -96,6 +99,18 @@ impl GraphRagRetriever {
             }
         }
 
+        // Drop seeds outside the caller's scope before they steer the traversal
+        let seed_candidates: Vec<String> = seed_scores.keys().cloned().collect();
+        let visible_seeds: HashSet<String> = self
+            .store
+            .get_turns_by_ids(&seed_candidates)
+            .await?
+            .into_iter()
+            .filter(|t| scope.allows_turn(t))
+            .map(|t| t.id)
+            .collect();
+        seed_scores.retain(|id, _| visible_seeds.contains(id));
+
         if seed_scores.is_empty() {
             debug!("No seed turns found for query");
             return Ok(Vec::new());
@@ -117,7 +132,8 @@ impl GraphRagRetriever {
 
         // 4. Load candidate turns and score them
         let all_ids: Vec<String> = candidate_ids.into_iter().collect();
-        let turns = self.store.get_turns_by_ids(&all_ids).await?;
+        let mut turns = self.store.get_turns_by_ids(&all_ids).await?;
+        turns.retain(|t| scope.allows_turn(t));
 
         // Find a representative seed for proximity computation
         let seed_turn = if !seed_ids.is_empty() {
@@ -208,12 +224,20 @@ mod tests {
             Message::user("Now update store.rs with new fields"),
             Message::assistant("Updated store.rs successfully"),
         ];
-        indexer.index_session("s1", &messages).await.unwrap();
+        indexer
+            .index_session(&MemoryScope::global(), "s1", &messages)
+            .await
+            .unwrap();
 
         // Query about orchestrator
         let retriever = GraphRagRetriever::new(store);
         let results = retriever
-            .retrieve("problem in orchestrator.rs", 10, 10000)
+            .retrieve(
+                &MemoryScope::global(),
+                "problem in orchestrator.rs",
+                10,
+                10000,
+            )
             .await
             .unwrap();
 
@@ -235,11 +259,17 @@ mod tests {
             Message::user("Also fix orchestrator.rs error handling"),
             Message::assistant("Done with orchestrator.rs"),
         ];
-        indexer.index_session("s1", &messages).await.unwrap();
+        indexer
+            .index_session(&MemoryScope::global(), "s1", &messages)
+            .await
+            .unwrap();
 
         let retriever = GraphRagRetriever::new(store);
         // Very tight token budget
-        let results = retriever.retrieve("orchestrator.rs", 10, 5).await.unwrap();
+        let results = retriever
+            .retrieve(&MemoryScope::global(), "orchestrator.rs", 10, 5)
+            .await
+            .unwrap();
         assert!(results.len() <= 2); // budget limits results
     }
 
@@ -247,7 +277,10 @@ mod tests {
     async fn test_retrieve_empty() {
         let store = GraphStore::in_memory().await.unwrap();
         let retriever = GraphRagRetriever::new(store);
-        let results = retriever.retrieve("something", 10, 10000).await.unwrap();
+        let results = retriever
+            .retrieve(&MemoryScope::global(), "something", 10, 10000)
+            .await
+            .unwrap();
         assert!(results.is_empty());
     }
 
@@ -261,11 +294,14 @@ mod tests {
             Message::assistant("Working on orchestrator.rs"),
             Message::user("Second fix for orchestrator.rs"),
         ];
-        indexer.index_session("s1", &messages).await.unwrap();
+        indexer
+            .index_session(&MemoryScope::global(), "s1", &messages)
+            .await
+            .unwrap();
 
         let retriever = GraphRagRetriever::new(store);
         let results = retriever
-            .retrieve("orchestrator.rs", 10, 10000)
+            .retrieve(&MemoryScope::global(), "orchestrator.rs", 10, 10000)
             .await
             .unwrap();
 
@@ -274,4 +310,51 @@ mod tests {
             assert!(w[0].turn.turn_index <= w[1].turn.turn_index);
         }
     }
+
+    #[tokio::test]
+    async fn test_retrieve_scoped_to_owner() {
+        let store = GraphStore::in_memory().await.unwrap();
+        let indexer = TurnIndexer::new(store.clone());
+
+        let alice = MemoryScope::user("alice").with_channel("slack:C1");
+        let bob = MemoryScope::user("bob").with_channel("slack:C1");
+        indexer
+            .index_session(
+                &alice,
+                "alice-session",
+                &[Message::user("alice edits orchestrator.rs")],
+            )
+            .await
+            .unwrap();
+        indexer
+            .index_session(
+                &bob,
+                "bob-session",
+                &[Message::user("bob edits orchestrator.rs")],
+            )
+            .await
+            .unwrap();
+
+        let retriever = GraphRagRetriever::new(store);
+        let results = retriever
+            .retrieve(&alice, "orchestrator.rs", 10, 10000)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].turn.owner_id, "alice");
+
+        // Same owner, different channel
+        let elsewhere = MemoryScope::user("alice").with_channel("slack:D9");
+        let results = retriever
+            .retrieve(&elsewhere, "orchestrator.rs", 10, 10000)
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+
+        let results = retriever
+            .retrieve(&MemoryScope::global(), "orchestrator.rs", 10, 10000)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+    }
 }
@@ -0,0 +1,232 @@
+//! Memory scoping — who may see which turns and explicit memories.
+//!
+//! Turns and explicit memories record the user they belong to and the
+//! channel they came from. A [`MemoryScope`] describes what a caller may see:
+//! their own memories (optionally limited to one channel) plus shared team
+//! memories. [`MemoryScope::global`] sees everything and is meant for
+//! administration and maintenance.
+//!
+//! The same scope attributes newly indexed turns and saved memories to the
+//! caller.
+
+use crate::types::{ExplicitMemory, MemoryVisibility, Turn};
+use std::future::Future;
+
+tokio::task_local! {
+    static CURRENT_SCOPE: MemoryScope;
+}
+
+/// Visibility filter and attribution for memory operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryScope {
+    owner_id: Option<String>,
+    channel_id: Option<String>,
+    include_team: bool,
+}
+
+impl MemoryScope {
+    /// Unrestricted scope: every owner, every channel.
+    pub fn global() -> Self {
+        Self {
+            owner_id: None,
+            channel_id: None,
+            include_team: true,
+        }
+    }
+
+    /// Scope of a single user: their own memories plus team memories.
+    pub fn user(owner_id: impl Into<String>) -> Self {
+        Self {
+            owner_id: Some(owner_id.into()),
+            channel_id: None,
+            include_team: true,
+        }
+    }
+
+    /// Limit the scope to one channel (`<type>:<id>`, see [`Self::channel_key`]).
+    #[must_use]
+    pub fn with_channel(mut self, channel_id: impl Into<String>) -> Self {
+        self.channel_id = Some(channel_id.into());
+        self
+    }
+
+    /// Hide team memories owned by other users.
+    #[must_use]
+    pub fn without_team(mut self) -> Self {
+        self.include_team = false;
+        self
+    }
+
+    /// Channel identifier stored on turns and memories.
+    pub fn channel_key(channel_type: &str, channel_id: &str) -> String {
+        format!("{channel_type}:{channel_id}")
+    }
+
+    /// Derive the scope of an orchestrator session key
+    /// (`<channel_type>:<channel_id>:<user_id>`).
+    ///
+    /// Channel IDs may contain `:` (Matrix rooms are `!room:server`), so the
+    /// user ID is taken from the right. Matrix user IDs (`@user:server`)
+    /// contain one as well and are found by their `@` sigil.
+    ///
+    /// Returns `None` for keys that do not follow that layout.
+    pub fn from_session_key(session_key: &str) -> Option<Self> {
+        let (channel_type, rest) = session_key.split_once(':')?;
+        let split = if channel_type == "matrix" {
+            rest.rfind(":@")?
+        } else {
+            rest.rfind(':')?
+        };
+        let (channel_id, user_id) = (&rest[..split], &rest[split + 1..]);
+        if channel_id.is_empty() || user_id.is_empty() {
+            return None;
+        }
+        Some(Self::user(user_id).with_channel(Self::channel_key(channel_type, channel_id)))
+    }
+
+    /// Whether this scope sees every memory.
+    pub fn is_global(&self) -> bool {
+        self.owner_id.is_none() && self.channel_id.is_none()
+    }
+
+    /// Owner this scope is restricted to.
+    pub fn owner_id(&self) -> Option<&str> {
+        self.owner_id.as_deref()
+    }
+
+    /// Channel this scope is restricted to.
+    pub fn channel_id(&self) -> Option<&str> {
+        self.channel_id.as_deref()
+    }
+
+    /// Whether team memories of other users are visible.
+    pub fn include_team(&self) -> bool {
+        self.include_team
+    }
+
+    /// Whether a turn is visible in this scope.
+    pub fn allows_turn(&self, turn: &Turn) -> bool {
+        self.owner_id().is_none_or(|o| turn.owner_id == o)
+            && self.channel_id().is_none_or(|c| turn.channel_id == c)
+    }
+
+    /// Whether an explicit memory is visible in this scope.
+    ///
+    /// Team memories ignore the channel restriction.
+    pub fn allows_memory(&self, memory: &ExplicitMemory) -> bool {
+        let owned = self.owner_id().is_none_or(|o| memory.owner_id == o);
+        match memory.visibility {
+            MemoryVisibility::Team => owned || self.include_team,
+            MemoryVisibility::Private => {
+                owned
+                    && self
+                        .channel_id()
+                        .is_none_or(|c| memory.channel_id.is_empty() || memory.channel_id == c)
+            }
+        }
+    }
+
+    /// Whether an explicit memory may be updated or deleted in this scope.
+    pub fn can_modify(&self, memory: &ExplicitMemory) -> bool {
+        self.owner_id().is_none_or(|o| memory.owner_id == o) && self.allows_memory(memory)
+    }
+
+    /// Run `future` with this scope as the [current](Self::current) scope.
+    ///
+    /// Lets tools that are not handed the caller explicitly (such as the
+    /// memory tool) act on the caller's behalf.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT_SCOPE.scope(self, future).await
+    }
+
+    /// Scope set by an enclosing [`Self::scope`] call, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT_SCOPE.try_with(Clone::clone).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn turn(owner: &str, channel: &str) -> Turn {
+        Turn {
+            id: "t1".into(),
+            session_id: "s1".into(),
+            role: crate::types::TurnRole::User,
+            content: "hello".into(),
+            summary: "hello".into(),
+            turn_index: 0,
+            token_count: 1,
+            created_at: Utc::now(),
+            owner_id: owner.into(),
+            channel_id: channel.into(),
+        }
+    }
+
+    fn memory(owner: &str, channel: &str, visibility: MemoryVisibility) -> ExplicitMemory {
+        ExplicitMemory {
+            id: "m1".into(),
+            name: "note".into(),
+            content: "content".into(),
+            category: "general".into(),
+            tags: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            access_count: 0,
+            owner_id: owner.into(),
+            channel_id: channel.into(),
+            visibility,
+        }
+    }
+
+    #[test]
+    fn test_turn_visibility() {
+        let scope = MemoryScope::user("alice").with_channel("slack:C1");
+        assert!(scope.allows_turn(&turn("alice", "slack:C1")));
+        assert!(!scope.allows_turn(&turn("alice", "slack:D1")));
+        assert!(!scope.allows_turn(&turn("bob", "slack:C1")));
+        assert!(MemoryScope::global().allows_turn(&turn("bob", "slack:C1")));
+    }
+
+    #[test]
+    fn test_memory_visibility() {
+        let scope = MemoryScope::user("alice").with_channel("slack:C1");
+        assert!(scope.allows_memory(&memory("alice", "", MemoryVisibility::Private)));
+        assert!(!scope.allows_memory(&memory("alice", "slack:D1", MemoryVisibility::Private)));
+        assert!(!scope.allows_memory(&memory("bob", "slack:C1", MemoryVisibility::Private)));
+
+        let team = memory("bob", "slack:D2", MemoryVisibility::Team);
+        assert!(scope.allows_memory(&team));
+        assert!(!scope.can_modify(&team));
+        assert!(!scope.clone().without_team().allows_memory(&team));
+        assert!(MemoryScope::global().can_modify(&team));
+    }
+
+    #[test]
+    fn test_from_session_key() {
+        let scope = MemoryScope::from_session_key("telegram:42:alice").unwrap();
+        assert_eq!(scope.owner_id(), Some("alice"));
+        assert_eq!(scope.channel_id(), Some("telegram:42"));
+        assert!(MemoryScope::from_session_key("session-1").is_none());
+        assert!(MemoryScope::from_session_key("telegram:42:").is_none());
+
+        let scope =
+            MemoryScope::from_session_key("matrix:!room:example.org:@alice:example.org").unwrap();
+        assert_eq!(scope.owner_id(), Some("@alice:example.org"));
+        assert_eq!(scope.channel_id(), Some("matrix:!room:example.org"));
+
+        let scope = MemoryScope::from_session_key("email:thread:1:bob@example.com").unwrap();
+        assert_eq!(scope.owner_id(), Some("bob@example.com"));
+        assert_eq!(scope.channel_id(), Some("email:thread:1"));
+    }
+
+    #[tokio::test]
+    async fn test_current_scope() {
+        assert!(MemoryScope::current().is_none());
+        let scope = MemoryScope::user("alice");
+        let current = scope.clone().scope(async { MemoryScope::current() }).await;
+        assert_eq!(current, Some(scope));
+    }
+}
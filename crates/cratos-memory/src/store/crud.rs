@@ -1,6 +1,9 @@
 use super::GraphStore;
 use crate::error::Result;
-use crate::types::{Entity, EntityKind, ExplicitMemory, Turn, TurnEntityEdge, TurnRole};
+use crate::scope::MemoryScope;
+use crate::types::{
+    Entity, EntityKind, ExplicitMemory, MemoryVisibility, Turn, TurnEntityEdge, TurnRole,
+};
 use chrono::{DateTime, Utc};
 use sqlx::Row;
 
@@ -11,8 +14,9 @@ impl GraphStore {
     pub async fn insert_turn(&self, turn: &Turn) -> Result<()> {
         sqlx::query(
             "INSERT OR IGNORE INTO turns
-             (id, session_id, role, content, summary, turn_index, token_count, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+             (id, session_id, role, content, summary, turn_index, token_count, created_at,
+              owner_id, channel_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
         )
         .bind(&turn.id)
         .bind(&turn.session_id)
@@ -22,6 +26,8 @@ impl GraphStore {
         .bind(turn.turn_index)
         .bind(turn.token_count)
         .bind(turn.created_at.to_rfc3339())
+        .bind(&turn.owner_id)
+        .bind(&turn.channel_id)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -30,7 +36,8 @@ impl GraphStore {
     /// Get all turns for a session, ordered by turn_index.
     pub async fn get_turns_by_session(&self, session_id: &str) -> Result<Vec<Turn>> {
         let rows = sqlx::query(
-            "SELECT id, session_id, role, content, summary, turn_index, token_count, created_at
+            "SELECT id, session_id, role, content, summary, turn_index, token_count, created_at,
+                    owner_id, channel_id
              FROM turns WHERE session_id = ?1 ORDER BY turn_index",
         )
         .bind(session_id)
@@ -43,7 +50,8 @@ impl GraphStore {
     /// Get a turn by ID.
     pub async fn get_turn(&self, id: &str) -> Result<Option<Turn>> {
         let row = sqlx::query(
-            "SELECT id, session_id, role, content, summary, turn_index, token_count, created_at
+            "SELECT id, session_id, role, content, summary, turn_index, token_count, created_at,
+                    owner_id, channel_id
              FROM turns WHERE id = ?1",
         )
         .bind(id)
@@ -97,6 +105,8 @@ impl GraphStore {
             created_at: DateTime::parse_from_rfc3339(&created_str)
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now()),
+            owner_id: row.try_get("owner_id")?,
+            channel_id: row.try_get("channel_id")?,
         })
     }
 
@@ -191,17 +201,22 @@ impl GraphStore {
     // ── Explicit Memories ─────────────────────────────────────
 
     /// Save an explicit memory (INSERT OR REPLACE).
+    ///
+    /// Names are unique per owner; saving an existing name of the same owner
+    /// updates it in place.
     pub async fn save_explicit_memory(&self, mem: &ExplicitMemory) -> Result<()> {
         let tags_str = mem.tags.join(",");
         sqlx::query(
             "INSERT INTO explicit_memories
-             (id, name, content, category, tags, created_at, updated_at, access_count)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
-             ON CONFLICT(name) DO UPDATE SET
+             (id, name, content, category, tags, created_at, updated_at, access_count,
+              owner_id, channel_id, visibility)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+             ON CONFLICT(owner_id, name) DO UPDATE SET
                 content = excluded.content,
                 category = excluded.category,
                 tags = excluded.tags,
-                updated_at = excluded.updated_at",
+                updated_at = excluded.updated_at,
+                visibility = excluded.visibility",
         )
         .bind(&mem.id)
         .bind(&mem.name)
@@ -211,17 +226,61 @@ impl GraphStore {
         .bind(mem.created_at.to_rfc3339())
         .bind(mem.updated_at.to_rfc3339())
         .bind(mem.access_count)
+        .bind(&mem.owner_id)
+        .bind(&mem.channel_id)
+        .bind(mem.visibility.to_string())
         .execute(&self.pool)
         .await?;
         Ok(())
     }
 
-    /// Get an explicit memory by name.
-    pub async fn get_explicit_by_name(&self, name: &str) -> Result<Option<ExplicitMemory>> {
+    /// Get an explicit memory by name, as visible in `scope`.
+    ///
+    /// When several owners use the same name, the scope owner's own memory
+    /// wins over team memories.
+    pub async fn get_explicit_by_name(
+        &self,
+        scope: &MemoryScope,
+        name: &str,
+    ) -> Result<Option<ExplicitMemory>> {
+        let rows = sqlx::query(
+            "SELECT id, name, content, category, tags, created_at, updated_at, access_count,
+                    owner_id, channel_id, visibility
+             FROM explicit_memories WHERE name = ?1
+             ORDER BY updated_at DESC",
+        )
+        .bind(name)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut visible = Vec::new();
+        for row in &rows {
+            let mem = Self::row_to_explicit_memory(row)?;
+            if scope.allows_memory(&mem) {
+                visible.push(mem);
+            }
+        }
+        let own = visible
+            .iter()
+            .position(|m| scope.owner_id() == Some(m.owner_id.as_str()));
+        Ok(match own {
+            Some(index) => Some(visible.swap_remove(index)),
+            None => visible.into_iter().next(),
+        })
+    }
+
+    /// Get the explicit memory an owner saved under `name`.
+    pub async fn get_explicit_by_owner(
+        &self,
+        owner_id: &str,
+        name: &str,
+    ) -> Result<Option<ExplicitMemory>> {
         let row = sqlx::query(
-            "SELECT id, name, content, category, tags, created_at, updated_at, access_count
-             FROM explicit_memories WHERE name = ?1",
+            "SELECT id, name, content, category, tags, created_at, updated_at, access_count,
+                    owner_id, channel_id, visibility
+             FROM explicit_memories WHERE owner_id = ?1 AND name = ?2",
         )
+        .bind(owner_id)
         .bind(name)
         .fetch_optional(&self.pool)
         .await?;
@@ -232,7 +291,8 @@ impl GraphStore {
     /// Get an explicit memory by ID.
     pub async fn get_explicit_by_id(&self, id: &str) -> Result<Option<ExplicitMemory>> {
         let row = sqlx::query(
-            "SELECT id, name, content, category, tags, created_at, updated_at, access_count
+            "SELECT id, name, content, category, tags, created_at, updated_at, access_count,
+                    owner_id, channel_id, visibility
              FROM explicit_memories WHERE id = ?1",
         )
         .bind(id)
@@ -243,20 +303,26 @@ impl GraphStore {
     }
 
     /// Delete an explicit memory by name. Returns true if a row was deleted.
-    pub async fn delete_explicit(&self, name: &str) -> Result<bool> {
+    ///
+    /// Only memories the scope may modify are deleted.
+    pub async fn delete_explicit(&self, scope: &MemoryScope, name: &str) -> Result<bool> {
         // First get the ID for cascade cleanup
-        let id = sqlx::query("SELECT id FROM explicit_memories WHERE name = ?1")
-            .bind(name)
-            .fetch_optional(&self.pool)
-            .await?;
+        let mem = self
+            .get_explicit_by_name(scope, name)
+            .await?
+            .filter(|m| scope.can_modify(m));
 
-        if let Some(row) = id {
-            let mem_id: String = row.try_get("id")?;
+        if let Some(mem) = mem {
+            let mem_id = mem.id;
             // Delete entity edges first
             sqlx::query("DELETE FROM memory_entity_edges WHERE memory_id = ?1")
                 .bind(&mem_id)
                 .execute(&self.pool)
                 .await?;
+            sqlx::query("DELETE FROM entity_relations WHERE source_id = ?1")
+                .bind(&mem_id)
+                .execute(&self.pool)
+                .await?;
             // Delete the memory
             let result = sqlx::query("DELETE FROM explicit_memories WHERE id = ?1")
                 .bind(&mem_id)
@@ -317,6 +383,9 @@ impl GraphStore {
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now()),
             access_count: u32::try_from(row.try_get::<i32, _>("access_count")?).unwrap_or(0),
+            owner_id: row.try_get("owner_id")?,
+            channel_id: row.try_get("channel_id")?,
+            visibility: MemoryVisibility::from_str_lossy(row.try_get("visibility")?),
         })
     }
 }
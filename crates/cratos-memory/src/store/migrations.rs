@@ -1,5 +1,13 @@
 use super::GraphStore;
 use crate::error::Result;
+use crate::scope::MemoryScope;
+use sqlx::{Connection, Row, SqliteConnection};
+use tracing::info;
+
+/// Schema version that added owner/channel scoping (`PRAGMA user_version`).
+const SCOPING_VERSION: i64 = 1;
+/// Schema version that records the source of each entity relation.
+const RELATION_SOURCE_VERSION: i64 = 2;
 
 impl GraphStore {
     // ── Migrations ──────────────────────────────────────────────
@@ -133,6 +141,195 @@ impl GraphStore {
         .execute(&self.pool)
         .await?;
 
+        let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(&self.pool)
+            .await?;
+        if version < SCOPING_VERSION {
+            self.migrate_scoping().await?;
+        }
+        if version < RELATION_SOURCE_VERSION {
+            self.migrate_relation_sources().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Key entity relations by the turn or memory they were extracted from.
+    ///
+    /// The source of existing relations is unknown, so they keep an empty
+    /// source and are only visible to the global scope.
+    async fn migrate_relation_sources(&self) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "CREATE TABLE entity_relations_sourced (
+                from_entity_id TEXT NOT NULL REFERENCES entities(id),
+                to_entity_id   TEXT NOT NULL REFERENCES entities(id),
+                kind           TEXT NOT NULL,
+                source_id      TEXT NOT NULL DEFAULT '',
+                PRIMARY KEY (from_entity_id, to_entity_id, kind, source_id)
+            )",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let migrated = sqlx::query(
+            "INSERT INTO entity_relations_sourced (from_entity_id, to_entity_id, kind)
+             SELECT from_entity_id, to_entity_id, kind FROM entity_relations",
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query("DROP TABLE entity_relations")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("ALTER TABLE entity_relations_sourced RENAME TO entity_relations")
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_entity_relations_to
+             ON entity_relations(to_entity_id)",
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_entity_relations_source
+             ON entity_relations(source_id)",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(&format!("PRAGMA user_version = {RELATION_SOURCE_VERSION}"))
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        info!(migrated, "Migrated entity relations to per-source records");
+        Ok(())
+    }
+
+    /// Add owner/channel columns to turns and explicit memories.
+    ///
+    /// Existing turns are attributed from their session key
+    /// (`<channel_type>:<channel_id>:<user_id>`); turns with other keys stay
+    /// unowned and are only visible to the global scope. Existing explicit
+    /// memories were visible to everyone, so they become team memories.
+    async fn migrate_scoping(&self) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+
+        // Rebuilding explicit_memories must not trip memory_entity_edges' foreign key
+        let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+            .fetch_one(&mut *conn)
+            .await?;
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await?;
+        let result = Self::migrate_scoping_on(&mut conn).await;
+        if foreign_keys != 0 {
+            sqlx::query("PRAGMA foreign_keys = ON")
+                .execute(&mut *conn)
+                .await?;
+        }
+        result
+    }
+
+    async fn migrate_scoping_on(conn: &mut SqliteConnection) -> Result<()> {
+        let mut tx = conn.begin().await?;
+
+        // ── Turns ────────────────────────────────────────────────
+        sqlx::query("ALTER TABLE turns ADD COLUMN owner_id TEXT NOT NULL DEFAULT ''")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("ALTER TABLE turns ADD COLUMN channel_id TEXT NOT NULL DEFAULT ''")
+            .execute(&mut *tx)
+            .await?;
+
+        let sessions = sqlx::query("SELECT DISTINCT session_id FROM turns")
+            .fetch_all(&mut *tx)
+            .await?;
+        let mut attributed = 0usize;
+        for row in &sessions {
+            let session_id: String = row.try_get("session_id")?;
+            let Some(scope) = MemoryScope::from_session_key(&session_id) else {
+                continue;
+            };
+            sqlx::query("UPDATE turns SET owner_id = ?1, channel_id = ?2 WHERE session_id = ?3")
+                .bind(scope.owner_id())
+                .bind(scope.channel_id())
+                .bind(&session_id)
+                .execute(&mut *tx)
+                .await?;
+            attributed += 1;
+        }
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_turns_owner ON turns(owner_id, channel_id)")
+            .execute(&mut *tx)
+            .await?;
+
+        // ── Explicit memories: names become unique per owner ─────
+        sqlx::query(
+            "CREATE TABLE explicit_memories_scoped (
+                id           TEXT PRIMARY KEY,
+                name         TEXT NOT NULL,
+                content      TEXT NOT NULL,
+                category     TEXT NOT NULL DEFAULT 'general',
+                tags         TEXT NOT NULL DEFAULT '',
+                created_at   TEXT NOT NULL,
+                updated_at   TEXT NOT NULL,
+                access_count INTEGER NOT NULL DEFAULT 0,
+                owner_id     TEXT NOT NULL DEFAULT '',
+                channel_id   TEXT NOT NULL DEFAULT '',
+                visibility   TEXT NOT NULL DEFAULT 'private',
+                UNIQUE (owner_id, name)
+            )",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO explicit_memories_scoped
+             (id, name, content, category, tags, created_at, updated_at, access_count,
+              owner_id, channel_id, visibility)
+             SELECT id, name, content, category, tags, created_at, updated_at, access_count,
+                    '', '', 'team'
+             FROM explicit_memories",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DROP TABLE explicit_memories")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("ALTER TABLE explicit_memories_scoped RENAME TO explicit_memories")
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_explicit_name ON explicit_memories(name)")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_explicit_category ON explicit_memories(category)",
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_explicit_owner
+             ON explicit_memories(owner_id, visibility)",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(&format!("PRAGMA user_version = {SCOPING_VERSION}"))
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        info!(
+            sessions = sessions.len(),
+            attributed, "Migrated graph memory to per-user scoping"
+        );
         Ok(())
     }
 }
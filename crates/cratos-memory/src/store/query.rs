@@ -1,21 +1,57 @@
 use super::GraphStore;
 use crate::error::Result;
+use crate::scope::MemoryScope;
 use crate::types::{Entity, EntityKind, EntityRelation, ExplicitMemory, RelationKind};
 use chrono::{DateTime, Utc};
 use sqlx::Row;
 
+/// SQL mirror of [`MemoryScope::allows_memory`].
+///
+/// Binds `?1` = owner, `?2` = include team, `?3` = channel.
+const EXPLICIT_SCOPE_FILTER: &str = "(?1 IS NULL OR owner_id = ?1 OR (?2 AND visibility = 'team'))
+               AND (?3 IS NULL OR visibility = 'team' OR channel_id IN ('', ?3))";
+
+/// Turns and explicit memories in scope, as a subquery of IDs.
+///
+/// Binds `?1`-`?3` like [`EXPLICIT_SCOPE_FILTER`].
+fn visible_sources() -> String {
+    format!(
+        "SELECT id FROM turns
+         WHERE (?1 IS NULL OR owner_id = ?1) AND (?3 IS NULL OR channel_id = ?3)
+         UNION
+         SELECT id FROM explicit_memories WHERE {EXPLICIT_SCOPE_FILTER}"
+    )
+}
+
+/// `visible(entity_id)` CTE: entities mentioned by turns or memories in scope.
+///
+/// Binds `?1`-`?3` like [`EXPLICIT_SCOPE_FILTER`].
+fn visible_entities_cte() -> String {
+    format!(
+        "WITH visible(entity_id) AS (
+             SELECT te.entity_id FROM turn_entity_edges te
+             JOIN turns t ON t.id = te.turn_id
+             WHERE (?1 IS NULL OR t.owner_id = ?1) AND (?3 IS NULL OR t.channel_id = ?3)
+             UNION
+             SELECT entity_id FROM memory_entity_edges
+             WHERE memory_id IN (SELECT id FROM explicit_memories WHERE {EXPLICIT_SCOPE_FILTER})
+         )"
+    )
+}
+
 impl GraphStore {
     // ── Entity Relations ────────────────────────────────────────
 
     /// Insert an entity relation. No-op if already exists.
     pub async fn insert_relation(&self, rel: &EntityRelation) -> Result<()> {
         sqlx::query(
-            "INSERT OR IGNORE INTO entity_relations (from_entity_id, to_entity_id, kind)
-             VALUES (?1, ?2, ?3)",
+            "INSERT OR IGNORE INTO entity_relations (from_entity_id, to_entity_id, kind, source_id)
+             VALUES (?1, ?2, ?3, ?4)",
         )
         .bind(&rel.from_entity_id)
         .bind(&rel.to_entity_id)
         .bind(rel.kind.to_string())
+        .bind(&rel.source_id)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -24,7 +60,7 @@ impl GraphStore {
     /// Get all relations where the given entity is the source.
     pub async fn get_relations_from_entity(&self, entity_id: &str) -> Result<Vec<EntityRelation>> {
         let rows = sqlx::query(
-            "SELECT from_entity_id, to_entity_id, kind
+            "SELECT from_entity_id, to_entity_id, kind, source_id
              FROM entity_relations WHERE from_entity_id = ?1",
         )
         .bind(entity_id)
@@ -37,6 +73,7 @@ impl GraphStore {
                 from_entity_id: r.get("from_entity_id"),
                 to_entity_id: r.get("to_entity_id"),
                 kind: RelationKind::from_str_lossy(r.get("kind")),
+                source_id: r.get("source_id"),
             })
             .collect())
     }
@@ -44,7 +81,7 @@ impl GraphStore {
     /// Get all relations where the given entity is the target.
     pub async fn get_relations_to_entity(&self, entity_id: &str) -> Result<Vec<EntityRelation>> {
         let rows = sqlx::query(
-            "SELECT from_entity_id, to_entity_id, kind
+            "SELECT from_entity_id, to_entity_id, kind, source_id
              FROM entity_relations WHERE to_entity_id = ?1",
         )
         .bind(entity_id)
@@ -57,20 +94,36 @@ impl GraphStore {
                 from_entity_id: r.get("from_entity_id"),
                 to_entity_id: r.get("to_entity_id"),
                 kind: RelationKind::from_str_lossy(r.get("kind")),
+                source_id: r.get("source_id"),
             })
             .collect())
     }
 
-    /// List all entity relations (for graph visualization).
-    pub async fn list_all_relations(&self, limit: u32) -> Result<Vec<EntityRelation>> {
-        let rows = sqlx::query(
-            "SELECT from_entity_id, to_entity_id, kind
+    /// List relations extracted from turns or memories visible in `scope`
+    /// (for graph visualization).
+    ///
+    /// A relation stated in several places is listed once.
+    pub async fn list_relations(
+        &self,
+        scope: &MemoryScope,
+        limit: u32,
+    ) -> Result<Vec<EntityRelation>> {
+        let sql = format!(
+            "SELECT from_entity_id, to_entity_id, kind, MIN(source_id) AS source_id
              FROM entity_relations
-             LIMIT ?1",
-        )
-        .bind(i32::try_from(limit).unwrap_or(i32::MAX))
-        .fetch_all(&self.pool)
-        .await?;
+             WHERE ?4 OR source_id IN ({})
+             GROUP BY from_entity_id, to_entity_id, kind
+             LIMIT ?5",
+            visible_sources()
+        );
+        let rows = sqlx::query(&sql)
+            .bind(scope.owner_id())
+            .bind(scope.include_team())
+            .bind(scope.channel_id())
+            .bind(scope.is_global())
+            .bind(i32::try_from(limit).unwrap_or(i32::MAX))
+            .fetch_all(&self.pool)
+            .await?;
 
         Ok(rows
             .iter()
@@ -78,6 +131,7 @@ impl GraphStore {
                 from_entity_id: r.get("from_entity_id"),
                 to_entity_id: r.get("to_entity_id"),
                 kind: RelationKind::from_str_lossy(r.get("kind")),
+                source_id: r.get("source_id"),
             })
             .collect())
     }
@@ -158,17 +212,26 @@ impl GraphStore {
 
     // ── Graph Data Export ─────────────────────────────────────
 
-    /// List all entities (for graph visualization).
-    pub async fn list_all_entities(&self, limit: u32) -> Result<Vec<Entity>> {
-        let rows = sqlx::query(
-            "SELECT id, name, kind, first_seen, mention_count
+    /// List entities mentioned by turns or memories visible in `scope`
+    /// (for graph visualization).
+    pub async fn list_entities(&self, scope: &MemoryScope, limit: u32) -> Result<Vec<Entity>> {
+        let sql = format!(
+            "{}
+             SELECT id, name, kind, first_seen, mention_count
              FROM entities
+             WHERE ?4 OR id IN (SELECT entity_id FROM visible)
              ORDER BY mention_count DESC
-             LIMIT ?1",
-        )
-        .bind(i32::try_from(limit).unwrap_or(i32::MAX))
-        .fetch_all(&self.pool)
-        .await?;
+             LIMIT ?5",
+            visible_entities_cte()
+        );
+        let rows = sqlx::query(&sql)
+            .bind(scope.owner_id())
+            .bind(scope.include_team())
+            .bind(scope.channel_id())
+            .bind(scope.is_global())
+            .bind(i32::try_from(limit).unwrap_or(i32::MAX))
+            .fetch_all(&self.pool)
+            .await?;
 
         rows.iter()
             .map(|r| {
@@ -188,17 +251,31 @@ impl GraphStore {
             .collect()
     }
 
-    /// Get all co-occurrence edges (for graph visualization).
-    pub async fn list_all_cooccurrences(&self, limit: u32) -> Result<Vec<(String, String, u32)>> {
-        let rows = sqlx::query(
-            "SELECT entity_a, entity_b, cooccurrence_count
+    /// List co-occurrence edges between entities visible in `scope`
+    /// (for graph visualization).
+    pub async fn list_cooccurrences(
+        &self,
+        scope: &MemoryScope,
+        limit: u32,
+    ) -> Result<Vec<(String, String, u32)>> {
+        let sql = format!(
+            "{}
+             SELECT entity_a, entity_b, cooccurrence_count
              FROM entity_cooccurrence
+             WHERE ?4 OR (entity_a IN (SELECT entity_id FROM visible)
+                          AND entity_b IN (SELECT entity_id FROM visible))
              ORDER BY cooccurrence_count DESC
-             LIMIT ?1",
-        )
-        .bind(i32::try_from(limit).unwrap_or(i32::MAX))
-        .fetch_all(&self.pool)
-        .await?;
+             LIMIT ?5",
+            visible_entities_cte()
+        );
+        let rows = sqlx::query(&sql)
+            .bind(scope.owner_id())
+            .bind(scope.include_team())
+            .bind(scope.channel_id())
+            .bind(scope.is_global())
+            .bind(i32::try_from(limit).unwrap_or(i32::MAX))
+            .fetch_all(&self.pool)
+            .await?;
 
         Ok(rows
             .iter()
@@ -211,73 +288,60 @@ impl GraphStore {
             .collect())
     }
 
-    /// Search explicit memories by name/content LIKE matching.
+    /// Search explicit memories visible in `scope` by name/content LIKE matching.
     pub async fn search_explicit(
         &self,
+        scope: &MemoryScope,
         query: &str,
         category: Option<&str>,
         limit: u32,
     ) -> Result<Vec<ExplicitMemory>> {
         let pattern = format!("%{query}%");
-        let rows = if let Some(cat) = category {
-            sqlx::query(
-                "SELECT id, name, content, category, tags, created_at, updated_at, access_count
-                 FROM explicit_memories
-                 WHERE category = ?1 AND (name LIKE ?2 OR content LIKE ?2)
-                 ORDER BY access_count DESC, updated_at DESC
-                 LIMIT ?3",
-            )
-            .bind(cat)
-            .bind(&pattern)
-            .bind(limit)
-            .fetch_all(&self.pool)
-            .await?
-        } else {
-            sqlx::query(
-                "SELECT id, name, content, category, tags, created_at, updated_at, access_count
-                 FROM explicit_memories
-                 WHERE name LIKE ?1 OR content LIKE ?1
-                 ORDER BY access_count DESC, updated_at DESC
-                 LIMIT ?2",
-            )
+        let sql = format!(
+            "SELECT id, name, content, category, tags, created_at, updated_at, access_count,
+                    owner_id, channel_id, visibility
+             FROM explicit_memories
+             WHERE {EXPLICIT_SCOPE_FILTER}
+               AND (?4 IS NULL OR category = ?4) AND (name LIKE ?5 OR content LIKE ?5)
+             ORDER BY access_count DESC, updated_at DESC
+             LIMIT ?6"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(scope.owner_id())
+            .bind(scope.include_team())
+            .bind(scope.channel_id())
+            .bind(category)
             .bind(&pattern)
             .bind(limit)
             .fetch_all(&self.pool)
-            .await?
-        };
+            .await?;
 
         rows.iter().map(Self::row_to_explicit_memory).collect()
     }
 
-    /// List explicit memories, optionally filtered by category.
+    /// List explicit memories visible in `scope`, optionally filtered by category.
     pub async fn list_explicit(
         &self,
+        scope: &MemoryScope,
         category: Option<&str>,
         limit: u32,
     ) -> Result<Vec<ExplicitMemory>> {
-        let rows = if let Some(cat) = category {
-            sqlx::query(
-                "SELECT id, name, content, category, tags, created_at, updated_at, access_count
-                 FROM explicit_memories
-                 WHERE category = ?1
-                 ORDER BY updated_at DESC
-                 LIMIT ?2",
-            )
-            .bind(cat)
-            .bind(limit)
-            .fetch_all(&self.pool)
-            .await?
-        } else {
-            sqlx::query(
-                "SELECT id, name, content, category, tags, created_at, updated_at, access_count
-                 FROM explicit_memories
-                 ORDER BY updated_at DESC
-                 LIMIT ?1",
-            )
+        let sql = format!(
+            "SELECT id, name, content, category, tags, created_at, updated_at, access_count,
+                    owner_id, channel_id, visibility
+             FROM explicit_memories
+             WHERE {EXPLICIT_SCOPE_FILTER} AND (?4 IS NULL OR category = ?4)
+             ORDER BY updated_at DESC
+             LIMIT ?5"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(scope.owner_id())
+            .bind(scope.include_team())
+            .bind(scope.channel_id())
+            .bind(category)
             .bind(limit)
             .fetch_all(&self.pool)
-            .await?
-        };
+            .await?;
 
         rows.iter().map(Self::row_to_explicit_memory).collect()
     }
@@ -286,7 +350,8 @@ impl GraphStore {
     pub async fn get_explicit_by_entity(&self, entity_id: &str) -> Result<Vec<ExplicitMemory>> {
         let rows = sqlx::query(
             "SELECT m.id, m.name, m.content, m.category, m.tags,
-                    m.created_at, m.updated_at, m.access_count
+                    m.created_at, m.updated_at, m.access_count,
+                    m.owner_id, m.channel_id, m.visibility
              FROM explicit_memories m
              JOIN memory_entity_edges me ON me.memory_id = m.id
              WHERE me.entity_id = ?1
@@ -1,4 +1,5 @@
 use super::GraphStore;
+use crate::scope::MemoryScope;
 use crate::types::*;
 use chrono::Utc;
 
@@ -16,6 +17,8 @@ fn make_turn(id: &str, session: &str, idx: u32, role: TurnRole) -> Turn {
         turn_index: idx,
         token_count: 10,
         created_at: Utc::now(),
+        owner_id: String::new(),
+        channel_id: String::new(),
     }
 }
 
@@ -209,6 +212,9 @@ fn make_explicit(name: &str, content: &str) -> ExplicitMemory {
         created_at: Utc::now(),
         updated_at: Utc::now(),
         access_count: 0,
+        owner_id: String::new(),
+        channel_id: String::new(),
+        visibility: MemoryVisibility::Private,
     }
 }
 
@@ -219,7 +225,7 @@ async fn test_save_and_get_explicit_memory() {
     store.save_explicit_memory(&mem).await.unwrap();
 
     let got = store
-        .get_explicit_by_name("my-note")
+        .get_explicit_by_name(&MemoryScope::global(), "my-note")
         .await
         .unwrap()
         .unwrap();
@@ -240,7 +246,11 @@ async fn test_explicit_upsert_by_name() {
     store.save_explicit_memory(&updated).await.unwrap();
 
     // Should have been updated (not duplicated)
-    let got = store.get_explicit_by_name("note").await.unwrap().unwrap();
+    let got = store
+        .get_explicit_by_name(&MemoryScope::global(), "note")
+        .await
+        .unwrap()
+        .unwrap();
     assert_eq!(got.content, "version 2");
 }
 
@@ -256,13 +266,16 @@ async fn test_search_explicit() {
         .await
         .unwrap();
 
-    let results = store.search_explicit("API", None, 10).await.unwrap();
+    let results = store
+        .search_explicit(&MemoryScope::global(), "API", None, 10)
+        .await
+        .unwrap();
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].name, "api-key");
 
     // Search by category
     let results = store
-        .search_explicit("API", Some("knowledge"), 10)
+        .search_explicit(&MemoryScope::global(), "API", Some("knowledge"), 10)
         .await
         .unwrap();
     assert!(results.is_empty()); // category mismatch
@@ -280,7 +293,10 @@ async fn test_list_explicit() {
         .await
         .unwrap();
 
-    let all = store.list_explicit(None, 10).await.unwrap();
+    let all = store
+        .list_explicit(&MemoryScope::global(), None, 10)
+        .await
+        .unwrap();
     assert_eq!(all.len(), 2);
 }
 
@@ -292,14 +308,20 @@ async fn test_delete_explicit() {
         .await
         .unwrap();
 
-    assert!(store.delete_explicit("to-delete").await.unwrap());
     assert!(store
-        .get_explicit_by_name("to-delete")
+        .delete_explicit(&MemoryScope::global(), "to-delete")
+        .await
+        .unwrap());
+    assert!(store
+        .get_explicit_by_name(&MemoryScope::global(), "to-delete")
         .await
         .unwrap()
         .is_none());
     // Deleting again returns false
-    assert!(!store.delete_explicit("to-delete").await.unwrap());
+    assert!(!store
+        .delete_explicit(&MemoryScope::global(), "to-delete")
+        .await
+        .unwrap());
 }
 
 #[tokio::test]
@@ -314,7 +336,7 @@ async fn test_increment_access_count() {
     store.increment_access_count("em-counted").await.unwrap();
 
     let got = store
-        .get_explicit_by_name("counted")
+        .get_explicit_by_name(&MemoryScope::global(), "counted")
         .await
         .unwrap()
         .unwrap();
@@ -342,3 +364,219 @@ async fn test_memory_entity_edges() {
     assert_eq!(mems.len(), 1);
     assert_eq!(mems[0].name, "orch-fix");
 }
+
+#[tokio::test]
+async fn test_explicit_memory_scoping() {
+    let store = test_store().await;
+    let alice = MemoryScope::user("alice").with_channel("slack:C1");
+    let bob = MemoryScope::user("bob").with_channel("slack:C1");
+
+    let mut private = make_explicit("deploy", "alice deploys with make ship");
+    private.owner_id = "alice".into();
+    private.channel_id = "slack:C1".into();
+    store.save_explicit_memory(&private).await.unwrap();
+
+    // Names are unique per owner, so bob can reuse one as a team memory
+    let mut team = make_explicit("deploy", "the team deploys on fridays");
+    team.id = "em-deploy-team".into();
+    team.owner_id = "bob".into();
+    team.visibility = MemoryVisibility::Team;
+    store.save_explicit_memory(&team).await.unwrap();
+
+    // Own memory wins over a team memory with the same name
+    let got = store
+        .get_explicit_by_name(&alice, "deploy")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(got.owner_id, "alice");
+    let got = store
+        .get_explicit_by_name(&bob, "deploy")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(got.owner_id, "bob");
+
+    let results = store
+        .search_explicit(&bob, "deploy", None, 10)
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].visibility, MemoryVisibility::Team);
+    let results = store
+        .search_explicit(&bob.clone().without_team(), "make ship", None, 10)
+        .await
+        .unwrap();
+    assert!(results.is_empty());
+
+    // Private memories stay in the channel they were saved from
+    let dm = MemoryScope::user("alice").with_channel("slack:D1");
+    let owners: Vec<String> = store
+        .list_explicit(&dm, None, 10)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.owner_id)
+        .collect();
+    assert_eq!(owners, vec!["bob"]);
+
+    // Team memories can only be deleted by their owner
+    assert!(store.delete_explicit(&alice, "deploy").await.unwrap());
+    assert!(!store.delete_explicit(&alice, "deploy").await.unwrap());
+    assert!(store.delete_explicit(&bob, "deploy").await.unwrap());
+    assert!(store
+        .get_explicit_by_owner("bob", "deploy")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_scoping_migration() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("memory.db");
+
+    // Database created before scoping existed
+    {
+        let pool = sqlx::SqlitePool::connect(&format!("sqlite:{}?mode=rwc", path.display()))
+            .await
+            .unwrap();
+        for sql in [
+            "CREATE TABLE turns (
+                id TEXT PRIMARY KEY, session_id TEXT NOT NULL, role TEXT NOT NULL,
+                content TEXT NOT NULL, summary TEXT NOT NULL, turn_index INTEGER NOT NULL,
+                token_count INTEGER NOT NULL, created_at TEXT NOT NULL)",
+            "CREATE TABLE explicit_memories (
+                id TEXT PRIMARY KEY, name TEXT NOT NULL UNIQUE, content TEXT NOT NULL,
+                category TEXT NOT NULL DEFAULT 'general', tags TEXT NOT NULL DEFAULT '',
+                created_at TEXT NOT NULL, updated_at TEXT NOT NULL,
+                access_count INTEGER NOT NULL DEFAULT 0)",
+            "INSERT INTO turns VALUES
+                ('t1', 'telegram:42:alice', 'user', 'hi', 'hi', 0, 1, '2026-01-01T00:00:00Z'),
+                ('t2', 'legacy-session', 'user', 'yo', 'yo', 0, 1, '2026-01-01T00:00:00Z')",
+            "INSERT INTO explicit_memories VALUES
+                ('m1', 'wifi', 'password is hunter2', 'general', '',
+                 '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z', 0)",
+            "CREATE TABLE entities (
+                id TEXT PRIMARY KEY, name TEXT NOT NULL UNIQUE, kind TEXT NOT NULL,
+                first_seen TEXT NOT NULL, mention_count INTEGER NOT NULL DEFAULT 1)",
+            "INSERT INTO entities VALUES
+                ('e1', 'main.rs', 'file', '2026-01-01T00:00:00Z', 1),
+                ('e2', 'lib.rs', 'file', '2026-01-01T00:00:00Z', 1)",
+            "CREATE TABLE entity_relations (
+                from_entity_id TEXT NOT NULL, to_entity_id TEXT NOT NULL, kind TEXT NOT NULL,
+                PRIMARY KEY (from_entity_id, to_entity_id, kind))",
+            "INSERT INTO entity_relations VALUES ('e1', 'e2', 'calls')",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        pool.close().await;
+    }
+
+    let store = GraphStore::from_path(&path).await.unwrap();
+
+    let turn = store.get_turn("t1").await.unwrap().unwrap();
+    assert_eq!(turn.owner_id, "alice");
+    assert_eq!(turn.channel_id, "telegram:42");
+    let turn = store.get_turn("t2").await.unwrap().unwrap();
+    assert!(turn.owner_id.is_empty());
+
+    // Pre-scoping memories were visible to everyone, so they become team memories
+    let mem = store
+        .get_explicit_by_name(&MemoryScope::user("bob"), "wifi")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(mem.visibility, MemoryVisibility::Team);
+
+    // Relations of unknown source are only visible globally
+    let relations = store
+        .list_relations(&MemoryScope::global(), 10)
+        .await
+        .unwrap();
+    assert_eq!(relations.len(), 1);
+    assert!(relations[0].source_id.is_empty());
+    assert!(store
+        .list_relations(&MemoryScope::user("alice"), 10)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Re-opening does not migrate again
+    drop(store);
+    let store = GraphStore::from_path(&path).await.unwrap();
+    assert_eq!(store.turn_count().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_graph_export_scoping() {
+    let store = test_store().await;
+    for (turn_id, owner, entity_id) in [("t1", "alice", "e1"), ("t2", "bob", "e2")] {
+        let mut turn = make_turn(turn_id, "s1", 0, TurnRole::User);
+        turn.owner_id = owner.into();
+        store.insert_turn(&turn).await.unwrap();
+        store
+            .upsert_entity(&make_entity(entity_id, entity_id, EntityKind::File))
+            .await
+            .unwrap();
+        store
+            .insert_edge(&TurnEntityEdge {
+                turn_id: turn_id.into(),
+                entity_id: entity_id.into(),
+                relevance: 1.0,
+            })
+            .await
+            .unwrap();
+    }
+    store
+        .update_cooccurrence(&["e1".into(), "e2".into()])
+        .await
+        .unwrap();
+    store
+        .insert_relation(&EntityRelation {
+            from_entity_id: "e1".into(),
+            to_entity_id: "e2".into(),
+            kind: RelationKind::Calls,
+            source_id: "t2".into(),
+        })
+        .await
+        .unwrap();
+
+    let alice = MemoryScope::user("alice");
+    let entities = store.list_entities(&alice, 10).await.unwrap();
+    assert_eq!(entities.len(), 1);
+    assert_eq!(entities[0].id, "e1");
+    assert!(store
+        .list_cooccurrences(&alice, 10)
+        .await
+        .unwrap()
+        .is_empty());
+    // Bob's turn stated the relation, even though alice knows one endpoint
+    assert!(store.list_relations(&alice, 10).await.unwrap().is_empty());
+    let bob = MemoryScope::user("bob");
+    assert_eq!(store.list_relations(&bob, 10).await.unwrap().len(), 1);
+
+    let global = MemoryScope::global();
+    assert_eq!(store.list_entities(&global, 10).await.unwrap().len(), 2);
+    assert_eq!(store.list_entities(&global, 1).await.unwrap().len(), 1);
+    assert_eq!(
+        store.list_cooccurrences(&global, 10).await.unwrap().len(),
+        1
+    );
+    assert_eq!(store.list_relations(&global, 10).await.unwrap().len(), 1);
+
+    // Once alice states it too, she sees it (listed once globally)
+    store
+        .insert_relation(&EntityRelation {
+            from_entity_id: "e1".into(),
+            to_entity_id: "e2".into(),
+            kind: RelationKind::Calls,
+            source_id: "t1".into(),
+        })
+        .await
+        .unwrap();
+    let relations = store.list_relations(&alice, 10).await.unwrap();
+    assert_eq!(relations.len(), 1);
+    assert_eq!(relations[0].source_id, "t1");
+    assert_eq!(store.list_relations(&global, 10).await.unwrap().len(), 1);
+}
@@ -1,7 +1,8 @@
 //! Core data types for the graph memory system.
 //!
 //! The graph connects **turns** (conversation messages) to **entities**
-//! (files, functions, crates, tools, errors, concepts) extracted from them.
+//! (files, functions, crates, tools, errors, concepts, but also people,
+//! projects, organizations, places, events and URLs) extracted from them.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -25,6 +26,12 @@ pub struct Turn {
     pub token_count: u32,
     /// When this turn was recorded
     pub created_at: DateTime<Utc>,
+    /// User who took part in the conversation (empty if unknown)
+    #[serde(default)]
+    pub owner_id: String,
+    /// Channel the conversation happened in (`<type>:<id>`, empty if unknown)
+    #[serde(default)]
+    pub channel_id: String,
 }
 
 /// Role of a turn (subset of LLM MessageRole, excluding Tool/System).
@@ -79,6 +86,18 @@ pub enum EntityKind {
     Concept,
     /// Configuration key
     Config,
+    /// Person (e.g. a colleague or contact)
+    Person,
+    /// Project or product
+    Project,
+    /// Company, team or other organization
+    Organization,
+    /// City, country, address or venue
+    Place,
+    /// Date, deadline, meeting or other event
+    Event,
+    /// Web address
+    Url,
 }
 
 impl std::fmt::Display for EntityKind {
@@ -91,6 +110,12 @@ impl std::fmt::Display for EntityKind {
             Self::Error => write!(f, "error"),
             Self::Concept => write!(f, "concept"),
             Self::Config => write!(f, "config"),
+            Self::Person => write!(f, "person"),
+            Self::Project => write!(f, "project"),
+            Self::Organization => write!(f, "organization"),
+            Self::Place => write!(f, "place"),
+            Self::Event => write!(f, "event"),
+            Self::Url => write!(f, "url"),
         }
     }
 }
@@ -106,6 +131,12 @@ impl EntityKind {
             "error" => Self::Error,
             "concept" => Self::Concept,
             "config" => Self::Config,
+            "person" => Self::Person,
+            "project" => Self::Project,
+            "organization" => Self::Organization,
+            "place" => Self::Place,
+            "event" | "date" => Self::Event,
+            "url" => Self::Url,
             _ => Self::Concept, // fallback
         }
     }
@@ -113,7 +144,7 @@ impl EntityKind {
 
 /// Type of relation between two entities.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 pub enum RelationKind {
     /// Entity A defines Entity B (e.g. File -> Function)
     Defines,
@@ -123,6 +154,14 @@ pub enum RelationKind {
     Imports,
     /// General relationship
     Related,
+    /// Person works on a project (e.g. Person -> Project)
+    WorksOn,
+    /// Entity belongs to an organization (e.g. Person -> Organization)
+    MemberOf,
+    /// Entity is located in or takes place at a place (e.g. Event -> Place)
+    LocatedIn,
+    /// Person takes part in an event (e.g. Person -> Event)
+    Attends,
 }
 
 impl std::fmt::Display for RelationKind {
@@ -132,6 +171,10 @@ impl std::fmt::Display for RelationKind {
             Self::Calls => write!(f, "calls"),
             Self::Imports => write!(f, "imports"),
             Self::Related => write!(f, "related"),
+            Self::WorksOn => write!(f, "works_on"),
+            Self::MemberOf => write!(f, "member_of"),
+            Self::LocatedIn => write!(f, "located_in"),
+            Self::Attends => write!(f, "attends"),
         }
     }
 }
@@ -144,6 +187,10 @@ impl RelationKind {
             "calls" => Self::Calls,
             "imports" => Self::Imports,
             "related" => Self::Related,
+            "works_on" => Self::WorksOn,
+            "member_of" => Self::MemberOf,
+            "located_in" => Self::LocatedIn,
+            "attends" => Self::Attends,
             _ => Self::Related,
         }
     }
@@ -169,6 +216,10 @@ pub struct EntityRelation {
     pub to_entity_id: String,
     /// Type of relation
     pub kind: RelationKind,
+    /// Turn or explicit memory the relation was extracted from; decides who
+    /// may see it (empty for relations stored before sources were recorded)
+    #[serde(default)]
+    pub source_id: String,
 }
 
 /// A turn retrieved by the graph search, with scoring metadata.
@@ -204,6 +255,45 @@ pub struct ExplicitMemory {
     pub updated_at: DateTime<Utc>,
     /// Number of times this memory was recalled
     pub access_count: u32,
+    /// User who saved the memory (empty for memories saved before scoping)
+    #[serde(default)]
+    pub owner_id: String,
+    /// Channel the memory was saved from (`<type>:<id>`, empty if any)
+    #[serde(default)]
+    pub channel_id: String,
+    /// Who besides the owner may recall the memory
+    #[serde(default)]
+    pub visibility: MemoryVisibility,
+}
+
+/// Visibility of an explicit memory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryVisibility {
+    /// Only the owner (in the channel it was saved from, if any)
+    #[default]
+    Private,
+    /// Everyone sharing the memory store
+    Team,
+}
+
+impl std::fmt::Display for MemoryVisibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Private => write!(f, "private"),
+            Self::Team => write!(f, "team"),
+        }
+    }
+}
+
+impl MemoryVisibility {
+    /// Parse from string.
+    pub fn from_str_lossy(s: &str) -> Self {
+        match s {
+            "team" => Self::Team,
+            _ => Self::Private,
+        }
+    }
 }
 
 /// An extracted entity with its relevance score (before persistence).
@@ -248,6 +338,12 @@ mod tests {
             EntityKind::Error,
             EntityKind::Concept,
             EntityKind::Config,
+            EntityKind::Person,
+            EntityKind::Project,
+            EntityKind::Organization,
+            EntityKind::Place,
+            EntityKind::Event,
+            EntityKind::Url,
         ] {
             let s = kind.to_string();
             assert_eq!(EntityKind::from_str_lossy(&s), kind);
@@ -259,6 +355,23 @@ mod tests {
         assert_eq!(EntityKind::from_str_lossy("unknown"), EntityKind::Concept);
     }
 
+    #[test]
+    fn test_relation_kind_roundtrip() {
+        for kind in [
+            RelationKind::Defines,
+            RelationKind::Calls,
+            RelationKind::Imports,
+            RelationKind::Related,
+            RelationKind::WorksOn,
+            RelationKind::MemberOf,
+            RelationKind::LocatedIn,
+            RelationKind::Attends,
+        ] {
+            let s = kind.to_string();
+            assert_eq!(RelationKind::from_str_lossy(&s), kind);
+        }
+    }
+
     #[test]
     fn test_turn_serialization() {
         let turn = Turn {
@@ -270,10 +383,25 @@ mod tests {
             turn_index: 0,
             token_count: 5,
             created_at: Utc::now(),
+            owner_id: "u1".into(),
+            channel_id: "cli:local".into(),
         };
         let json = serde_json::to_string(&turn).unwrap();
         let back: Turn = serde_json::from_str(&json).unwrap();
         assert_eq!(back.id, "abc");
         assert_eq!(back.role, TurnRole::User);
+        assert_eq!(back.owner_id, "u1");
+    }
+
+    #[test]
+    fn test_memory_visibility_roundtrip() {
+        for visibility in [MemoryVisibility::Private, MemoryVisibility::Team] {
+            let s = visibility.to_string();
+            assert_eq!(MemoryVisibility::from_str_lossy(&s), visibility);
+        }
+        assert_eq!(
+            MemoryVisibility::from_str_lossy("unknown"),
+            MemoryVisibility::Private
+        );
     }
 }
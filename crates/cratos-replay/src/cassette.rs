@@ -0,0 +1,385 @@
+//! Cassette - Recorded LLM and tool responses for deterministic replay
+//!
+//! A cassette is built from the `LlmResponse`, `ToolCall` and `ToolResult`
+//! events of a finished execution. During replay the LLM responses are played
+//! back in order and tool results are looked up by tool name and arguments, so
+//! the orchestrator sees exactly what the original run saw without touching
+//! the network.
+
+use crate::error::{Error, Result};
+use crate::event::{Event, EventType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Tool call requested by a recorded LLM response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedToolCall {
+    /// Provider-assigned call ID
+    #[serde(default)]
+    pub id: String,
+    /// Tool name
+    pub name: String,
+    /// Arguments as a JSON string
+    #[serde(default)]
+    pub arguments: String,
+}
+
+/// LLM response captured from an `LlmResponse` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedLlmResponse {
+    /// Text content
+    pub content: Option<String>,
+    /// Tool calls requested by the model
+    pub tool_calls: Vec<RecordedToolCall>,
+    /// Model that produced the response
+    pub model: String,
+}
+
+/// Tool result captured from a `ToolCall`/`ToolResult` event pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedToolResult {
+    /// Tool name
+    pub tool_name: String,
+    /// Arguments the tool was called with
+    pub arguments: serde_json::Value,
+    /// Whether the tool succeeded
+    pub success: bool,
+    /// Tool output
+    pub output: serde_json::Value,
+    /// Error message (if failed)
+    pub error: Option<String>,
+}
+
+/// User input captured from the `UserInput` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedInput {
+    /// Channel type
+    pub channel_type: String,
+    /// Channel ID
+    pub channel_id: String,
+    /// User ID
+    pub user_id: String,
+    /// Input text
+    pub text: String,
+}
+
+/// Recorded responses of one execution
+#[derive(Debug, Clone, Default)]
+pub struct Cassette {
+    input: Option<RecordedInput>,
+    llm_responses: VecDeque<RecordedLlmResponse>,
+    tool_results: Vec<RecordedToolResult>,
+}
+
+impl Cassette {
+    /// Build a cassette from an execution's events (in sequence order)
+    ///
+    /// Fails when an `LlmResponse` event only recorded the number of tool
+    /// calls, since such an execution cannot be re-driven.
+    pub fn from_events(events: &[Event]) -> Result<Self> {
+        let mut cassette = Self::default();
+        // ToolCall events waiting for their ToolResult, per tool name and
+        // call index (none for events recorded without one)
+        let mut pending: HashMap<(String, Option<u64>), VecDeque<serde_json::Value>> =
+            HashMap::new();
+
+        for event in events {
+            let payload = &event.payload;
+            match event.event_type {
+                EventType::UserInput if cassette.input.is_none() => {
+                    cassette.input = serde_json::from_value(payload.clone()).ok();
+                }
+                EventType::LlmResponse => {
+                    cassette
+                        .llm_responses
+                        .push_back(RecordedLlmResponse::from_payload(
+                            event.sequence_num,
+                            payload,
+                        )?);
+                }
+                EventType::ToolCall => {
+                    let Some(tool) = tool_name(payload) else {
+                        continue;
+                    };
+                    pending
+                        .entry((tool.to_string(), call_index(payload)))
+                        .or_default()
+                        .push_back(tool_arguments(payload));
+                }
+                EventType::ToolResult => {
+                    let Some(tool) = tool_name(payload) else {
+                        continue;
+                    };
+                    let index = call_index(payload);
+                    // A denied call has no result, so with an index the latest
+                    // call is the one answered; without one, calls and results
+                    // alternate and the oldest is
+                    let arguments = pending
+                        .get_mut(&(tool.to_string(), index))
+                        .and_then(|calls| match index {
+                            Some(_) => calls.pop_back(),
+                            None => calls.pop_front(),
+                        })
+                        .unwrap_or(serde_json::Value::Null);
+                    cassette.tool_results.push(RecordedToolResult {
+                        tool_name: tool.to_string(),
+                        arguments,
+                        success: payload
+                            .get("success")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false),
+                        output: strip_diagnosis(payload.get("output").cloned()),
+                        error: payload
+                            .get("error")
+                            .and_then(|v| v.as_str())
+                            .map(String::from),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(cassette)
+    }
+
+    /// Input of the recorded execution
+    pub fn input(&self) -> Option<&RecordedInput> {
+        self.input.as_ref()
+    }
+
+    /// Play back the next recorded LLM response
+    pub fn next_llm_response(&mut self) -> Option<RecordedLlmResponse> {
+        self.llm_responses.pop_front()
+    }
+
+    /// Number of LLM responses not yet played back
+    pub fn remaining_llm_responses(&self) -> usize {
+        self.llm_responses.len()
+    }
+
+    /// Take the recorded result for a tool call
+    ///
+    /// Prefers a result recorded with identical arguments and falls back to
+    /// the oldest remaining result of the same tool.
+    pub fn take_tool_result(
+        &mut self,
+        tool_name: &str,
+        arguments: &serde_json::Value,
+    ) -> Option<RecordedToolResult> {
+        let index = self
+            .tool_results
+            .iter()
+            .position(|r| r.tool_name == tool_name && &r.arguments == arguments)
+            .or_else(|| {
+                self.tool_results
+                    .iter()
+                    .position(|r| r.tool_name == tool_name)
+            })?;
+        Some(self.tool_results.remove(index))
+    }
+
+    /// Names of the tools with recorded results
+    pub fn tool_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .tool_results
+            .iter()
+            .map(|r| r.tool_name.clone())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+impl RecordedLlmResponse {
+    /// Parse an `LlmResponse` event payload
+    ///
+    /// Fails when the event only recorded the number of tool calls.
+    pub fn from_payload(sequence: i32, payload: &serde_json::Value) -> Result<Self> {
+        let tool_calls = match payload.get("tool_calls") {
+            Some(calls @ serde_json::Value::Array(_)) => serde_json::from_value(calls.clone())
+                .map_err(|e| Error::Serialization(e.to_string()))?,
+            // Older events only stored a count (orchestrator) or a flag (recorder)
+            other => {
+                let had_calls = other.and_then(|v| v.as_u64()).unwrap_or(0) > 0
+                    || payload.get("has_tool_calls").and_then(|v| v.as_bool()) == Some(true);
+                if had_calls {
+                    return Err(Error::NotReplayable(format!(
+                        "LLM response #{} recorded without tool call details",
+                        sequence
+                    )));
+                }
+                Vec::new()
+            }
+        };
+
+        Ok(Self {
+            content: payload
+                .get("content")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            tool_calls,
+            model: payload
+                .get("model")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        })
+    }
+}
+
+/// Tool name from a `ToolCall`/`ToolResult` payload
+///
+/// The orchestrator records `tool`, the `EventRecorder` records `tool_name`.
+pub fn tool_name(payload: &serde_json::Value) -> Option<&str> {
+    payload
+        .get("tool")
+        .or_else(|| payload.get("tool_name"))
+        .and_then(|v| v.as_str())
+}
+
+/// Position of the call in its LLM response from a `ToolCall`/`ToolResult`
+/// payload
+fn call_index(payload: &serde_json::Value) -> Option<u64> {
+    payload.get("call_index").and_then(|v| v.as_u64())
+}
+
+/// Tool arguments from a `ToolCall` payload, parsed when stored as a string
+pub fn tool_arguments(payload: &serde_json::Value) -> serde_json::Value {
+    match payload.get("arguments").or_else(|| payload.get("input")) {
+        Some(serde_json::Value::String(s)) => {
+            serde_json::from_str(s).unwrap_or(serde_json::Value::String(s.clone()))
+        }
+        Some(value) => value.clone(),
+        None => serde_json::Value::Null,
+    }
+}
+
+/// Remove the orchestrator's failure diagnosis, which is added on top of the
+/// tool's own output
+fn strip_diagnosis(output: Option<serde_json::Value>) -> serde_json::Value {
+    match output {
+        Some(serde_json::Value::Object(mut map)) => {
+            map.remove("_diagnosis");
+            serde_json::Value::Object(map)
+        }
+        Some(value) => value,
+        None => serde_json::Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn events() -> Vec<Event> {
+        let id = Uuid::new_v4();
+        vec![
+            Event::new(id, 1, EventType::UserInput).with_payload(json!({
+                "channel_type": "cli", "channel_id": "c1", "user_id": "u1", "text": "list files"
+            })),
+            Event::new(id, 2, EventType::LlmResponse).with_payload(json!({
+                "content": null,
+                "tool_calls": [{"id": "call_1", "name": "exec", "arguments": "{\"command\":\"ls\"}"}],
+                "model": "mock",
+                "is_final": false
+            })),
+            Event::new(id, 3, EventType::ToolCall)
+                .with_payload(json!({"tool": "exec", "arguments": "{\"command\":\"ls\"}"})),
+            Event::new(id, 4, EventType::ToolResult).with_payload(json!({
+                "tool": "exec", "success": true,
+                "output": {"stdout": "a.txt", "_diagnosis": "x"}, "error": null
+            })),
+            Event::new(id, 5, EventType::LlmResponse).with_payload(json!({
+                "content": "a.txt", "tool_calls": [], "model": "mock", "is_final": true
+            })),
+        ]
+    }
+
+    #[test]
+    fn test_cassette_from_events() {
+        let mut cassette = Cassette::from_events(&events()).unwrap();
+        assert_eq!(cassette.input().unwrap().text, "list files");
+        assert_eq!(cassette.remaining_llm_responses(), 2);
+        assert_eq!(cassette.tool_names(), vec!["exec"]);
+
+        let first = cassette.next_llm_response().unwrap();
+        assert_eq!(first.tool_calls[0].name, "exec");
+
+        assert!(cassette
+            .take_tool_result("exec", &json!({"command": "pwd"}))
+            .is_some_and(|r| r.output == json!({"stdout": "a.txt"})));
+        assert!(cassette.take_tool_result("exec", &json!({})).is_none());
+
+        let second = cassette.next_llm_response().unwrap();
+        assert_eq!(second.content.as_deref(), Some("a.txt"));
+        assert!(cassette.next_llm_response().is_none());
+    }
+
+    #[test]
+    fn test_cassette_prefers_matching_arguments() {
+        let id = Uuid::new_v4();
+        let mut events = Vec::new();
+        for (seq, cmd) in [(1, "ls"), (3, "pwd")] {
+            events.push(
+                Event::new(id, seq, EventType::ToolCall)
+                    .with_payload(json!({"tool": "exec", "arguments": {"command": cmd}})),
+            );
+            events.push(
+                Event::new(id, seq + 1, EventType::ToolResult)
+                    .with_payload(json!({"tool": "exec", "success": true, "output": cmd})),
+            );
+        }
+
+        let mut cassette = Cassette::from_events(&events).unwrap();
+        let result = cassette
+            .take_tool_result("exec", &json!({"command": "pwd"}))
+            .unwrap();
+        assert_eq!(result.output, json!("pwd"));
+    }
+
+    #[test]
+    fn test_cassette_pairs_batched_calls_by_index() {
+        let id = Uuid::new_v4();
+        let call = |seq, index, cmd| {
+            Event::new(id, seq, EventType::ToolCall).with_payload(
+                json!({"tool": "exec", "call_index": index, "arguments": {"command": cmd}}),
+            )
+        };
+        let result = |seq, index, out| {
+            Event::new(id, seq, EventType::ToolResult).with_payload(
+                json!({"tool": "exec", "call_index": index, "success": true, "output": out}),
+            )
+        };
+        // One batch: both calls are logged before either result, and the
+        // results come back in reverse order
+        let events = vec![
+            call(1, 0, "ls"),
+            call(2, 1, "pwd"),
+            result(3, 1, "/tmp"),
+            result(4, 0, "a.txt"),
+        ];
+
+        let mut cassette = Cassette::from_events(&events).unwrap();
+        let pwd = cassette
+            .take_tool_result("exec", &json!({"command": "pwd"}))
+            .unwrap();
+        assert_eq!(pwd.output, json!("/tmp"));
+        let ls = cassette
+            .take_tool_result("exec", &json!({"command": "ls"}))
+            .unwrap();
+        assert_eq!(ls.output, json!("a.txt"));
+    }
+
+    #[test]
+    fn test_cassette_rejects_legacy_tool_call_count() {
+        let event = Event::new(Uuid::new_v4(), 1, EventType::LlmResponse)
+            .with_payload(json!({"content": null, "tool_calls": 2, "model": "mock"}));
+        assert!(matches!(
+            Cassette::from_events(&[event]),
+            Err(Error::NotReplayable(_))
+        ));
+    }
+}
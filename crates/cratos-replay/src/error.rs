@@ -20,6 +20,10 @@ pub enum Error {
     /// Serialization error
     #[error("serialization error: {0}")]
     Serialization(String),
+
+    /// Execution cannot be replayed deterministically
+    #[error("execution not replayable: {0}")]
+    NotReplayable(String),
 }
 
 /// Result type alias
@@ -270,6 +270,13 @@ impl Execution {
         self.completed_at = Some(Utc::now());
         self.updated_at = Utc::now();
     }
+
+    /// Execution this one was forked from, with the forked sequence number
+    pub fn forked_from(&self) -> Option<(Uuid, i32)> {
+        let parent = self.metadata.get("forked_from")?.as_str()?.parse().ok()?;
+        let sequence = self.metadata.get("fork_sequence")?.as_i64()?;
+        Some((parent, i32::try_from(sequence).ok()?))
+    }
 }
 
 /// An event in the execution timeline
@@ -425,6 +432,9 @@ pub struct ToolCallPayload {
     pub risk_level: String,
     /// Whether approval was required
     pub requires_approval: bool,
+    /// Position of the call in the LLM response, repeated on its `ToolResult`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_index: Option<usize>,
 }
 
 /// Payload for ToolResult events
@@ -438,6 +448,12 @@ pub struct ToolResultPayload {
     pub output: Option<serde_json::Value>,
     /// Error message (if failed)
     pub error: Option<String>,
+    /// Unified diff of the file changes made by the tool (e.g. `file_edit`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    /// `call_index` of the `ToolCall` this result answers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_index: Option<usize>,
 }
 
 /// Payload for Error events
@@ -537,6 +553,17 @@ mod tests {
         assert_eq!(exec.output_text, Some("Done!".to_string()));
     }
 
+    #[test]
+    fn test_execution_forked_from() {
+        let parent = Uuid::new_v4();
+        let exec = Execution::new("fork", parent.to_string(), "user1", "")
+            .with_metadata(serde_json::json!({"forked_from": parent, "fork_sequence": 4}));
+        assert_eq!(exec.forked_from(), Some((parent, 4)));
+
+        let exec = Execution::new("telegram", "12345", "user1", "Hello");
+        assert!(exec.forked_from().is_none());
+    }
+
     #[test]
     fn test_event_creation() {
         let execution_id = Uuid::new_v4();
@@ -559,4 +586,30 @@ mod tests {
         let parsed: EventType = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed, EventType::ToolCall);
     }
+
+    #[test]
+    fn test_tool_result_payload_diff() {
+        let payload: ToolResultPayload = serde_json::from_value(serde_json::json!({
+            "tool_name": "file_read",
+            "success": true,
+            "output": {"content": "hi"},
+            "error": null
+        }))
+        .unwrap();
+        assert!(payload.diff.is_none());
+        assert!(serde_json::to_value(&payload)
+            .unwrap()
+            .get("diff")
+            .is_none());
+
+        let payload: ToolResultPayload = serde_json::from_value(serde_json::json!({
+            "tool_name": "file_edit",
+            "success": true,
+            "output": null,
+            "error": null,
+            "diff": "--- a/x\n+++ b/x\n"
+        }))
+        .unwrap();
+        assert_eq!(payload.diff.as_deref(), Some("--- a/x\n+++ b/x\n"));
+    }
 }
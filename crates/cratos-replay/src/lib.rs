@@ -4,11 +4,13 @@
 //! - Event: Event types and schemas
 //! - Store: Event persistence (SQLite)
 //! - Viewer: Event query and replay API
+//! - Cassette: Recorded LLM/tool responses for deterministic replay
 //! - Search: Semantic search over execution history (feature: search)
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+pub mod cassette;
 pub mod error;
 pub mod event;
 #[cfg(feature = "search")]
@@ -16,14 +18,17 @@ pub mod search;
 pub mod store;
 pub mod viewer;
 
+pub use cassette::{
+    Cassette, RecordedInput, RecordedLlmResponse, RecordedToolCall, RecordedToolResult,
+};
 pub use error::{Error, Result};
 pub use event::{Event, EventType, Execution, ExecutionStatus, TimelineEntry};
 pub use store::{
     default_data_dir, default_db_path, EventRecorder, EventStore, EventStoreTrait, ExecutionQuery,
 };
 pub use viewer::{
-    ExecutionDetail, ExecutionStats, ExecutionSummary, ExecutionViewer, ReplayOptions,
-    ReplayResult, ReplayStep,
+    diff_event_chains, ChainDivergence, ExecutionDetail, ExecutionDiff, ExecutionStats,
+    ExecutionSummary, ExecutionViewer, ReplayMode, ReplayOptions, ReplayResult, ReplayStep,
 };
 
 // Re-export search types when feature is enabled
@@ -1,8 +1,10 @@
 //! Viewer Diff - Execution comparison and diffing
 
 use super::mod_impl::ExecutionViewer;
-use super::types::{ExecutionComparison, ExecutionDiff};
+use super::types::{ChainDivergence, ExecutionComparison, ExecutionDiff};
+use crate::cassette::{tool_arguments, tool_name};
 use crate::error::Result;
+use crate::event::{Event, EventType};
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -12,6 +14,8 @@ impl ExecutionViewer {
     pub async fn compare_executions(&self, id1: Uuid, id2: Uuid) -> Result<ExecutionComparison> {
         let detail1 = self.get_execution_detail(id1).await?;
         let detail2 = self.get_execution_detail(id2).await?;
+        let events1 = self.store.get_execution_events(id1).await?;
+        let events2 = self.store.get_execution_events(id2).await?;
 
         let diff = ExecutionDiff {
             input_same: detail1.execution.input_text == detail2.execution.input_text,
@@ -26,6 +30,7 @@ impl ExecutionViewer {
                 .total_duration_ms
                 .zip(detail2.stats.total_duration_ms)
                 .map(|(d1, d2)| d1 as i64 - d2 as i64),
+            chain_divergence: diff_event_chains(&events1, &events2),
         };
 
         Ok(ExecutionComparison {
@@ -35,3 +40,52 @@ impl ExecutionViewer {
         })
     }
 }
+
+/// Find the first step where two executions' event chains diverge
+///
+/// The chain is the ordered sequence of tool calls (with arguments), tool
+/// outcomes and the final response; timings and IDs are ignored.
+pub fn diff_event_chains(original: &[Event], replay: &[Event]) -> Option<ChainDivergence> {
+    let original = chain_steps(original);
+    let replay = chain_steps(replay);
+
+    (0..original.len().max(replay.len()))
+        .find(|&i| original.get(i) != replay.get(i))
+        .map(|index| ChainDivergence {
+            index,
+            original: original.get(index).cloned(),
+            replay: replay.get(index).cloned(),
+        })
+}
+
+fn chain_steps(events: &[Event]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|event| {
+            let payload = &event.payload;
+            match event.event_type {
+                EventType::ToolCall => Some(format!(
+                    "tool_call {} {}",
+                    tool_name(payload).unwrap_or_default(),
+                    tool_arguments(payload)
+                )),
+                EventType::ToolResult => Some(format!(
+                    "tool_result {} success={}",
+                    tool_name(payload).unwrap_or_default(),
+                    payload
+                        .get("success")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false)
+                )),
+                EventType::FinalResponse => Some(format!(
+                    "final_response {}",
+                    payload
+                        .get("response")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                )),
+                _ => None,
+            }
+        })
+        .collect()
+}
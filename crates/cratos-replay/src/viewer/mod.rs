@@ -21,9 +21,10 @@ use chrono::{DateTime, Utc};
 use tracing::instrument;
 use uuid::Uuid;
 
+pub use diff::diff_event_chains;
 pub use types::{
-    EventChain, ExecutionComparison, ExecutionDetail, ExecutionDiff, ExecutionStats,
-    ExecutionSummary, ReplayOptions, ReplayResult, ReplayStep,
+    ChainDivergence, EventChain, ExecutionComparison, ExecutionDetail, ExecutionDiff,
+    ExecutionStats, ExecutionSummary, ReplayMode, ReplayOptions, ReplayResult, ReplayStep,
 };
 
 #[cfg(test)]
@@ -1,6 +1,10 @@
 #[cfg(test)]
 mod tests {
+    use crate::event::{Event, EventType};
+    use crate::viewer::diff_event_chains;
     use crate::viewer::types::{truncate, ReplayOptions};
+    use serde_json::json;
+    use uuid::Uuid;
 
     #[test]
     fn test_truncate() {
@@ -20,4 +24,30 @@ mod tests {
         assert_eq!(opts.to_sequence, Some(10));
         assert_eq!(opts.skip_tools, vec!["exec"]);
     }
+
+    fn chain(command: &str, response: &str) -> Vec<Event> {
+        let id = Uuid::new_v4();
+        vec![
+            Event::new(id, 1, EventType::ToolCall)
+                .with_payload(json!({"tool": "exec", "arguments": {"command": command}})),
+            Event::new(id, 2, EventType::ToolResult)
+                .with_payload(json!({"tool": "exec", "success": true, "duration_ms": 5})),
+            Event::new(id, 3, EventType::FinalResponse).with_payload(json!({"response": response})),
+        ]
+    }
+
+    #[test]
+    fn test_diff_event_chains() {
+        assert!(diff_event_chains(&chain("ls", "done"), &chain("ls", "done")).is_none());
+
+        let divergence = diff_event_chains(&chain("ls", "done"), &chain("pwd", "done")).unwrap();
+        assert_eq!(divergence.index, 0);
+        assert!(divergence.replay.unwrap().contains("pwd"));
+
+        let mut shorter = chain("ls", "done");
+        shorter.pop();
+        let divergence = diff_event_chains(&chain("ls", "done"), &shorter).unwrap();
+        assert_eq!(divergence.index, 2);
+        assert!(divergence.replay.is_none());
+    }
 }
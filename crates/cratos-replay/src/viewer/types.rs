@@ -107,6 +107,33 @@ pub struct ExecutionDiff {
     pub llm_request_count_diff: i32,
     /// Difference in duration
     pub duration_diff_ms: Option<i64>,
+    /// First point where the tool/response chains differ (None if identical)
+    #[serde(default)]
+    pub chain_divergence: Option<ChainDivergence>,
+}
+
+/// First differing step between two event chains
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainDivergence {
+    /// Index of the step in the chain
+    pub index: usize,
+    /// Step in the first execution (None if its chain ended earlier)
+    pub original: Option<String>,
+    /// Step in the second execution (None if its chain ended earlier)
+    pub replay: Option<String>,
+}
+
+/// Which side of an execution is re-run live during replay
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayMode {
+    /// Recorded LLM responses and recorded tool results (no network)
+    #[default]
+    Cassette,
+    /// Live LLM against recorded tool results
+    LiveLlm,
+    /// Recorded LLM responses against live tools
+    LiveTools,
 }
 
 /// Result of a replay operation
@@ -1,6 +1,7 @@
 //! Bash tool configuration types
 
 use super::constants::*;
+use crate::sandbox::SandboxConfig;
 use std::path::PathBuf;
 
 /// Security mode for the bash tool.
@@ -47,6 +48,8 @@ pub struct BashConfig {
     pub max_commands_per_minute: u32,
     /// Allow network commands (curl, wget, etc.). Default: false.
     pub allow_network_commands: bool,
+    /// Run the shell inside the native Linux sandbox (None = unsandboxed).
+    pub sandbox: Option<SandboxConfig>,
 }
 
 impl Default for BashConfig {
@@ -73,6 +76,7 @@ impl Default for BashConfig {
             env_whitelist: ENV_WHITELIST.iter().map(|s| (*s).to_string()).collect(),
             max_commands_per_minute: MAX_COMMANDS_PER_MINUTE,
             allow_network_commands: false,
+            sandbox: None,
         }
     }
 }
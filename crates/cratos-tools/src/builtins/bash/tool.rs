@@ -15,6 +15,7 @@ use super::security::{is_informational_exit, SecurityAnalyzer};
 use super::session::{PtySession, SessionStatus};
 use crate::error::{Error, Result};
 use crate::registry::{RiskLevel, Tool, ToolCategory, ToolDefinition, ToolResult};
+use crate::sandbox::NativeSpec;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
@@ -203,9 +204,23 @@ impl BashTool {
             .map_err(|e| Error::Execution(format!("Failed to open PTY: {}", e)))?;
 
         // Build PTY command (builder pattern — each method consumes self)
-        let mut pty_cmd = pty_process::Command::new(&shell)
-            .args(["-c", command])
-            .env_clear();
+        let mut pty_cmd = match &self.config.sandbox {
+            Some(sandbox) => {
+                let limits = sandbox
+                    .default_limits
+                    .clone()
+                    .with_timeout(std::time::Duration::from_secs(timeout_secs));
+                let argv = vec![shell.clone(), "-c".to_string(), command.to_string()];
+                let mut spec = NativeSpec::new(sandbox, argv).with_limits(limits);
+                if let Some(dir) = &working_dir {
+                    spec = spec.with_cwd(dir);
+                }
+                let (launcher, args) = spec.launcher_command()?;
+                pty_process::Command::new(launcher).args(args)
+            }
+            None => pty_process::Command::new(&shell).args(["-c", command]),
+        }
+        .env_clear();
 
         // Apply environment whitelist
         for (key, value) in &env_vars {
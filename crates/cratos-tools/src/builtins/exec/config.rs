@@ -1,3 +1,5 @@
+use crate::sandbox::{ContainerRuntime, SandboxConfig};
+
 /// Default maximum timeout in seconds
 pub const DEFAULT_MAX_TIMEOUT_SECS: u64 = 60;
 
@@ -30,7 +32,7 @@ pub enum ExecMode {
 pub enum ExecHost {
     /// Execute on the local machine (default)
     Local,
-    /// Execute inside a Docker sandbox container
+    /// Execute inside the configured sandbox runtime (Docker by default)
     Sandbox,
 }
 
@@ -61,6 +63,11 @@ pub struct ExecConfig {
     pub sandbox_memory_limit: String,
     /// CPU limit for sandbox containers (e.g. "1.0")
     pub sandbox_cpu_limit: String,
+    /// Runtime used for `host: "sandbox"` (Docker or LinuxNative)
+    pub sandbox_runtime: ContainerRuntime,
+    /// Policy, mounts and limits for the native Linux sandbox
+    /// (`SandboxConfig::default()` when unset)
+    pub sandbox: Option<SandboxConfig>,
 }
 
 impl Default for ExecConfig {
@@ -78,6 +85,8 @@ impl Default for ExecConfig {
             sandbox_image: "alpine:latest".to_string(),
             sandbox_memory_limit: "256m".to_string(),
             sandbox_cpu_limit: "1.0".to_string(),
+            sandbox_runtime: ContainerRuntime::Docker,
+            sandbox: None,
         }
     }
 }
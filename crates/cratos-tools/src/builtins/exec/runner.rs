@@ -1,6 +1,8 @@
 use super::config::{ExecConfig, ExecHost};
 use crate::error::{Error, Result};
+use crate::sandbox::{ContainerRuntime, NativeSpec, NATIVE_DEFAULT_PATH};
 use std::process::Stdio;
+use std::time::Duration;
 use tokio::process::Command;
 
 pub async fn run_command(
@@ -20,6 +22,31 @@ pub async fn run_command(
             }
             c
         }
+        ExecHost::Sandbox if config.sandbox_runtime == ContainerRuntime::LinuxNative => {
+            let sandbox = config.sandbox.clone().unwrap_or_default();
+            let limits = sandbox
+                .default_limits
+                .clone()
+                .with_timeout(Duration::from_secs(timeout_secs));
+            let argv = std::iter::once(command.to_string())
+                .chain(args.iter().cloned())
+                .collect();
+            let mut spec = NativeSpec::new(&sandbox, argv).with_limits(limits);
+            if let Some(dir) = cwd {
+                spec = spec.with_cwd(dir);
+            }
+            let mut c = Command::from(spec.to_command()?);
+            c.env_clear()
+                .env("PATH", NATIVE_DEFAULT_PATH)
+                .env("HOME", "/tmp")
+                .kill_on_drop(true);
+            c
+        }
+        ExecHost::Sandbox if config.sandbox_runtime == ContainerRuntime::None => {
+            return Err(Error::Execution(
+                "No sandbox runtime available on this host".to_string(),
+            ));
+        }
         ExecHost::Sandbox => {
             let mut c = Command::new("docker");
             c.arg("run")
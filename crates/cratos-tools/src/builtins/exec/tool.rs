@@ -76,7 +76,7 @@ impl ExecTool {
                     "host": {
                         "type": "string",
                         "enum": ["local", "sandbox"],
-                        "description": "Execution target: 'local' (default) runs on the host, 'sandbox' runs in an isolated Docker container"
+                        "description": "Execution target: 'local' (default) runs on the host, 'sandbox' runs isolated (Docker container or native Linux sandbox)"
                     }
                 },
                 "required": ["command"]
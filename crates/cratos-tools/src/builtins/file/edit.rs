@@ -0,0 +1,346 @@
+use super::patch;
+use super::security;
+use crate::error::{Error, Result};
+use crate::registry::{RiskLevel, Tool, ToolCategory, ToolDefinition, ToolResult};
+use similar::{ChangeTag, TextDiff};
+use std::time::Instant;
+use tracing::{debug, warn};
+
+/// Largest file the tool edits
+const MAX_EDIT_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Diffs longer than this are truncated in the result
+const MAX_DIFF_CHARS: usize = 20_000;
+
+/// Tool for targeted edits of an existing file
+pub struct FileEditTool {
+    definition: ToolDefinition,
+}
+
+impl FileEditTool {
+    /// Create a new file edit tool
+    #[must_use]
+    pub fn new() -> Self {
+        let definition = ToolDefinition::new(
+            "file_edit",
+            "Edit part of an existing file instead of rewriting it, and return a unified diff of the change. \
+             Use exactly one mode: \
+             (1) old_string + new_string replaces text that must appear exactly once (set replace_all=true to replace every occurrence); \
+             (2) start_line + end_line + new_string replaces that 1-based inclusive line range (end_line = start_line - 1 inserts before start_line); \
+             (3) patch applies a unified diff to the file (context is matched fuzzily, so line numbers may be off). \
+             Security: blocked for sensitive files and system paths. \
+             Example: {\"path\": \"src/main.rs\", \"old_string\": \"let x = 1;\", \"new_string\": \"let x = 2;\"}"
+        )
+            .with_category(ToolCategory::File)
+            .with_risk_level(RiskLevel::Medium)
+            .with_parameters(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path to the file to edit"
+                    },
+                    "old_string": {
+                        "type": "string",
+                        "description": "Exact text to replace (include enough context to make it unique)"
+                    },
+                    "new_string": {
+                        "type": "string",
+                        "description": "Replacement text (for old_string or the line range)"
+                    },
+                    "replace_all": {
+                        "type": "boolean",
+                        "description": "Replace every occurrence of old_string",
+                        "default": false
+                    },
+                    "start_line": {
+                        "type": "integer",
+                        "description": "First line to replace (1-based)"
+                    },
+                    "end_line": {
+                        "type": "integer",
+                        "description": "Last line to replace (inclusive, defaults to start_line)"
+                    },
+                    "patch": {
+                        "type": "string",
+                        "description": "Unified diff for this file (hunks starting with @@)"
+                    }
+                },
+                "required": ["path"]
+            }));
+
+        Self { definition }
+    }
+}
+
+impl Default for FileEditTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Requested edit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Edit {
+    /// Replace `old` (unique unless `all`) with `new`
+    Replace { old: String, new: String, all: bool },
+    /// Replace lines `start..=end` (1-based) with `new`
+    Lines {
+        start: usize,
+        end: usize,
+        new: String,
+    },
+    /// Apply a unified diff
+    Patch(String),
+}
+
+impl Edit {
+    pub(super) fn from_input(input: &serde_json::Value) -> Result<Self> {
+        let str_param = |name: &str| input.get(name).and_then(|v| v.as_str());
+        let line_param = |name: &str| -> Result<Option<usize>> {
+            match input.get(name) {
+                None | Some(serde_json::Value::Null) => Ok(None),
+                Some(v) => v
+                    .as_u64()
+                    .map(|n| Some(n as usize))
+                    .ok_or_else(|| Error::InvalidInput(format!("'{name}' must be a line number"))),
+            }
+        };
+        let new_string = || {
+            str_param("new_string")
+                .map(String::from)
+                .ok_or_else(|| Error::InvalidInput("Missing 'new_string' parameter".to_string()))
+        };
+
+        let old = str_param("old_string");
+        let start = line_param("start_line")?;
+        let patch = str_param("patch");
+        let modes = [old.is_some(), start.is_some(), patch.is_some()];
+        if modes.iter().filter(|set| **set).count() != 1 {
+            return Err(Error::InvalidInput(
+                "Give exactly one of 'old_string', 'start_line' or 'patch'".to_string(),
+            ));
+        }
+
+        if let Some(old) = old {
+            if old.is_empty() {
+                return Err(Error::InvalidInput(
+                    "'old_string' must not be empty".to_string(),
+                ));
+            }
+            let all = input
+                .get("replace_all")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            return Ok(Edit::Replace {
+                old: old.to_string(),
+                new: new_string()?,
+                all,
+            });
+        }
+        if let Some(start) = start {
+            let end = line_param("end_line")?.unwrap_or(start);
+            return Ok(Edit::Lines {
+                start,
+                end,
+                new: new_string()?,
+            });
+        }
+        Ok(Edit::Patch(patch.unwrap_or_default().to_string()))
+    }
+
+    fn mode(&self) -> &'static str {
+        match self {
+            Edit::Replace { .. } => "replace",
+            Edit::Lines { .. } => "lines",
+            Edit::Patch(_) => "patch",
+        }
+    }
+
+    /// Edited content of `content`
+    pub(super) fn apply(&self, content: &str) -> Result<String> {
+        match self {
+            Edit::Replace { old, new, all } => replace(content, old, new, *all),
+            Edit::Lines { start, end, new } => {
+                let mut text = Lines::split(content);
+                let len = text.lines.len();
+                if *start == 0 || *start > len + 1 || *end + 1 < *start || *end > len {
+                    return Err(Error::InvalidInput(format!(
+                        "Invalid line range {start}-{end}: the file has {len} lines"
+                    )));
+                }
+                text.lines
+                    .splice(start - 1..*end, new.lines().map(String::from));
+                Ok(text.join())
+            }
+            Edit::Patch(patch) => {
+                let hunks = patch::parse_patch(patch)?;
+                let mut text = Lines::split(content);
+                patch::apply_patch(&mut text.lines, &hunks)?;
+                Ok(text.join())
+            }
+        }
+    }
+}
+
+/// Replace `old` with `new`, requiring a unique match unless `all`
+fn replace(content: &str, old: &str, new: &str, all: bool) -> Result<String> {
+    // Let `\n` in the edit match a file with CRLF line endings
+    let (old, new) = if !content.contains(old) && content.contains("\r\n") && !old.contains('\r') {
+        (old.replace('\n', "\r\n"), new.replace('\n', "\r\n"))
+    } else {
+        (old.to_string(), new.to_string())
+    };
+
+    match content.matches(old.as_str()).count() {
+        0 => Err(Error::InvalidInput(
+            "'old_string' was not found in the file; re-read the file and copy the text exactly, \
+             including whitespace and indentation"
+                .to_string(),
+        )),
+        1 => Ok(content.replacen(old.as_str(), &new, 1)),
+        _ if all => Ok(content.replace(old.as_str(), &new)),
+        count => Err(Error::InvalidInput(format!(
+            "'old_string' appears {count} times; add surrounding lines to make it unique \
+             or set replace_all=true"
+        ))),
+    }
+}
+
+/// File content as lines, remembering its line ending
+struct Lines {
+    lines: Vec<String>,
+    newline: &'static str,
+    trailing_newline: bool,
+}
+
+impl Lines {
+    fn split(content: &str) -> Self {
+        Self {
+            lines: content.lines().map(String::from).collect(),
+            newline: if content.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            },
+            trailing_newline: content.is_empty() || content.ends_with('\n'),
+        }
+    }
+
+    fn join(&self) -> String {
+        let mut content = self.lines.join(self.newline);
+        if self.trailing_newline && !self.lines.is_empty() {
+            content.push_str(self.newline);
+        }
+        content
+    }
+}
+
+#[async_trait::async_trait]
+impl Tool for FileEditTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    async fn execute(&self, input: serde_json::Value) -> Result<ToolResult> {
+        let start = Instant::now();
+
+        let path = input
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::InvalidInput("Missing 'path' parameter".to_string()))?;
+
+        // SECURITY: Validate path
+        let file_path = security::validate_path(path)?;
+
+        // SECURITY: Block editing sensitive file locations
+        if security::is_sensitive_file(&file_path) {
+            warn!(path = %path, "Attempt to edit potentially sensitive file");
+            return Err(Error::PermissionDenied(format!(
+                "Editing '{}' is restricted - file appears to be sensitive",
+                file_path.file_name().unwrap_or_default().to_string_lossy()
+            )));
+        }
+
+        let edit = Edit::from_input(&input)?;
+        debug!(path = %path, mode = %edit.mode(), "Editing file");
+
+        let metadata = tokio::fs::metadata(&file_path).await.map_err(Error::Io)?;
+        if !metadata.is_file() {
+            return Err(Error::InvalidInput(format!("'{}' is not a file", path)));
+        }
+        if metadata.len() > MAX_EDIT_BYTES {
+            return Err(Error::InvalidInput(format!(
+                "'{}' is too large to edit ({} bytes, limit {})",
+                path,
+                metadata.len(),
+                MAX_EDIT_BYTES
+            )));
+        }
+        let bytes = tokio::fs::read(&file_path).await.map_err(Error::Io)?;
+        let original = String::from_utf8(bytes)
+            .map_err(|_| Error::InvalidInput(format!("'{}' is not a UTF-8 text file", path)))?;
+
+        let edited = edit.apply(&original)?;
+
+        let diff = TextDiff::from_lines(&original, &edited);
+        let (mut lines_added, mut lines_removed, mut inserted) = (0, 0, String::new());
+        for change in diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Insert => {
+                    lines_added += 1;
+                    inserted.push_str(change.value());
+                }
+                ChangeTag::Delete => lines_removed += 1,
+                ChangeTag::Equal => {}
+            }
+        }
+
+        // SECURITY: Check if the new text contains secrets
+        if security::content_appears_sensitive(&inserted) {
+            warn!(path = %path, "Attempt to edit in content containing potential secrets");
+            return Err(Error::PermissionDenied(
+                "Content appears to contain sensitive data (API keys, passwords, tokens). \
+                 Writing secrets to files is blocked for security."
+                    .to_string(),
+            ));
+        }
+
+        let changed = edited != original;
+        if changed {
+            tokio::fs::write(&file_path, &edited)
+                .await
+                .map_err(Error::Io)?;
+        }
+
+        let mut unified = diff
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("a/{path}"), &format!("b/{path}"))
+            .to_string();
+        let diff_truncated = unified.len() > MAX_DIFF_CHARS;
+        if diff_truncated {
+            let mut cut = MAX_DIFF_CHARS;
+            while !unified.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            unified.truncate(cut);
+            unified.push_str("\n... (diff truncated)\n");
+        }
+
+        let duration = start.elapsed().as_millis() as u64;
+
+        Ok(ToolResult::success(
+            serde_json::json!({
+                "path": path,
+                "mode": edit.mode(),
+                "changed": changed,
+                "lines_added": lines_added,
+                "lines_removed": lines_removed,
+                "diff": unified,
+                "diff_truncated": diff_truncated
+            }),
+            duration,
+        ))
+    }
+}
@@ -1,12 +1,15 @@
-//! File tools - Read, write, and list files
+//! File tools - Read, write, edit, and list files
 
+pub mod edit;
 pub mod list;
+mod patch;
 pub mod read;
 pub mod security;
 #[cfg(test)]
 mod tests;
 pub mod write;
 
+pub use edit::FileEditTool;
 pub use list::FileListTool;
 pub use read::FileReadTool;
 pub use write::FileWriteTool;
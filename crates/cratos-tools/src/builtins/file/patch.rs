@@ -0,0 +1,275 @@
+//! Unified diff application with fuzzy context matching
+//!
+//! A hunk is located by its context and removed lines rather than trusted
+//! line numbers: it is tried at the position its header gives (shifted by
+//! earlier hunks), then at the nearest position where its lines match,
+//! first exactly, then ignoring trailing whitespace, then ignoring all
+//! whitespace differences. If it still does not match, up to [`MAX_FUZZ`]
+//! context lines are dropped from each end, like `patch --fuzz`.
+
+use crate::error::{Error, Result};
+
+/// Context lines that may be dropped from each end of a hunk
+const MAX_FUZZ: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+/// One `@@` hunk of a unified diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Hunk {
+    /// First old line (1-based) from the header, if it has one
+    old_start: Option<usize>,
+    lines: Vec<HunkLine>,
+}
+
+impl Hunk {
+    /// Lines the hunk expects in the file
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                HunkLine::Context(text) | HunkLine::Remove(text) => Some(text.as_str()),
+                HunkLine::Add(_) => None,
+            })
+            .collect()
+    }
+
+    fn leading_context(&self) -> usize {
+        self.lines
+            .iter()
+            .take_while(|line| matches!(line, HunkLine::Context(_)))
+            .count()
+    }
+
+    fn trailing_context(&self) -> usize {
+        self.lines
+            .iter()
+            .rev()
+            .take_while(|line| matches!(line, HunkLine::Context(_)))
+            .count()
+    }
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidInput(message.into())
+}
+
+/// Parse the hunks of a single-file unified diff
+///
+/// `diff --git`, `index` and `---`/`+++` header lines are skipped; a patch
+/// with headers for more than one file is rejected. Until the line counts of
+/// its `@@` header are used up, a hunk's `--- `/`+++ ` lines are removed and
+/// added lines rather than a file header.
+pub(super) fn parse_patch(patch: &str) -> Result<Vec<Hunk>> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let mut file_headers = 0;
+    let mut in_hunk = false;
+    // Old and new lines left in the current hunk, if its header has counts
+    let mut remaining: Option<(usize, usize)> = None;
+    let mut lines = patch.trim_end().lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.starts_with("@@") {
+            hunks.push(Hunk {
+                old_start: parse_hunk_header(line),
+                lines: Vec::new(),
+            });
+            in_hunk = true;
+            remaining = hunk_line_counts(line);
+            continue;
+        }
+        let counted = in_hunk && remaining.is_some_and(|(old, new)| old + new > 0);
+        if !counted
+            && line.starts_with("--- ")
+            && lines.peek().is_some_and(|next| next.starts_with("+++ "))
+        {
+            file_headers += 1;
+            if file_headers > 1 {
+                return Err(invalid(
+                    "Patch changes more than one file; apply it one file at a time",
+                ));
+            }
+            lines.next();
+            in_hunk = false;
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut().filter(|_| in_hunk) else {
+            continue;
+        };
+        let (old_used, new_used) = match line.chars().next() {
+            Some(' ') => {
+                hunk.lines.push(HunkLine::Context(line[1..].to_string()));
+                (1, 1)
+            }
+            // Editors and models often strip the space of empty context lines
+            None => {
+                hunk.lines.push(HunkLine::Context(String::new()));
+                (1, 1)
+            }
+            Some('-') => {
+                hunk.lines.push(HunkLine::Remove(line[1..].to_string()));
+                (1, 0)
+            }
+            Some('+') => {
+                hunk.lines.push(HunkLine::Add(line[1..].to_string()));
+                (0, 1)
+            }
+            // "\ No newline at end of file"
+            Some('\\') => (0, 0),
+            // Anything else (e.g. a `diff --git` line) ends the hunk
+            _ => {
+                in_hunk = false;
+                (0, 0)
+            }
+        };
+        if let Some((old, new)) = remaining.as_mut() {
+            *old = old.saturating_sub(old_used);
+            *new = new.saturating_sub(new_used);
+        }
+    }
+
+    if hunks.is_empty() {
+        return Err(invalid(
+            "Patch contains no hunks (expected '@@ -start,count +start,count @@' headers)",
+        ));
+    }
+    if hunks.iter().any(|hunk| hunk.lines.is_empty()) {
+        return Err(invalid("Patch contains an empty hunk"));
+    }
+    Ok(hunks)
+}
+
+/// Old start line of a `@@ -12,5 +12,6 @@` header
+fn parse_hunk_header(line: &str) -> Option<usize> {
+    let old = line.strip_prefix("@@")?.trim_start().strip_prefix('-')?;
+    let digits: String = old.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+/// Old and new line counts of a `@@ -12,5 +12,6 @@` header (a missing
+/// count is 1)
+fn hunk_line_counts(line: &str) -> Option<(usize, usize)> {
+    let count = |range: &str| match range.split_once(',') {
+        Some((_, count)) => count.parse().ok(),
+        None => Some(1),
+    };
+    let mut ranges = line.strip_prefix("@@")?.split_whitespace();
+    let old = ranges.next()?.strip_prefix('-')?;
+    let new = ranges.next()?.strip_prefix('+')?;
+    Some((count(old)?, count(new)?))
+}
+
+/// Whether a file line matches a hunk line at a matching `level`
+fn line_matches(file: &str, hunk: &str, level: usize) -> bool {
+    match level {
+        0 => file == hunk,
+        1 => file.trim_end() == hunk.trim_end(),
+        _ => file.split_whitespace().eq(hunk.split_whitespace()),
+    }
+}
+
+/// Position at or after `min_pos` nearest to `expected` where `old` matches
+fn find_nearest(
+    lines: &[String],
+    old: &[&str],
+    expected: usize,
+    min_pos: usize,
+    level: usize,
+) -> Option<usize> {
+    let last = lines.len().checked_sub(old.len())?;
+    if min_pos > last {
+        return None;
+    }
+    let expected = expected.clamp(min_pos, last);
+    let matches_at = |pos: usize| {
+        lines[pos..pos + old.len()]
+            .iter()
+            .zip(old)
+            .all(|(file, hunk)| line_matches(file, hunk, level))
+    };
+    (0..=last - min_pos).find_map(|distance| {
+        let after = expected + distance;
+        if after <= last && matches_at(after) {
+            return Some(after);
+        }
+        let before = expected.checked_sub(distance)?;
+        (before >= min_pos && matches_at(before)).then_some(before)
+    })
+}
+
+/// Apply `hunks` to the lines of a file, in order
+pub(super) fn apply_patch(lines: &mut Vec<String>, hunks: &[Hunk]) -> Result<()> {
+    // Hunks must not overlap, and earlier hunks shift the later ones
+    let mut min_pos = 0;
+    let mut offset: isize = 0;
+
+    for (number, hunk) in hunks.iter().enumerate().map(|(i, hunk)| (i + 1, hunk)) {
+        let expected = hunk
+            .old_start
+            .map(|start| (start.saturating_sub(1) as isize + offset).max(0) as usize)
+            .unwrap_or(min_pos)
+            .max(min_pos);
+        let old = hunk.old_lines();
+
+        let mut located = None;
+        let mut tried = None;
+        'fuzz: for fuzz in 0..=MAX_FUZZ {
+            let lead = fuzz.min(hunk.leading_context());
+            let trail = fuzz.min(hunk.trailing_context());
+            if tried == Some((lead, trail)) || lead + trail > old.len() {
+                continue;
+            }
+            tried = Some((lead, trail));
+            let window = &old[lead..old.len() - trail];
+            if window.is_empty() {
+                // Pure insertion (e.g. into an empty file)
+                if fuzz == 0 {
+                    located = Some((expected.min(lines.len()), lead, trail));
+                    break;
+                }
+                continue;
+            }
+            for level in 0..3 {
+                if let Some(pos) = find_nearest(lines, window, expected + lead, min_pos, level) {
+                    located = Some((pos, lead, trail));
+                    break 'fuzz;
+                }
+            }
+        }
+
+        let Some((pos, lead, trail)) = located else {
+            let first = old.first().copied().unwrap_or_default();
+            return Err(invalid(format!(
+                "Hunk {number} does not match the file (expected a line like {first:?}); \
+                 re-read the file and regenerate the patch"
+            )));
+        };
+
+        // Context lines keep the file's text; dropped context is left alone
+        let body = &hunk.lines[lead..hunk.lines.len() - trail];
+        let old_len = old.len() - lead - trail;
+        let mut file_line = pos;
+        let mut replacement = Vec::with_capacity(body.len());
+        for line in body {
+            match line {
+                HunkLine::Context(_) => {
+                    replacement.push(lines[file_line].clone());
+                    file_line += 1;
+                }
+                HunkLine::Remove(_) => file_line += 1,
+                HunkLine::Add(text) => replacement.push(text.clone()),
+            }
+        }
+
+        min_pos = pos + replacement.len();
+        offset += replacement.len() as isize - old_len as isize;
+        lines.splice(pos..pos + old_len, replacement);
+    }
+    Ok(())
+}
@@ -110,3 +110,265 @@ async fn test_file_write_blocks_secrets() {
         .await;
     assert!(result.is_err());
 }
+
+#[test]
+fn test_file_edit_definition() {
+    let tool = FileEditTool::new();
+    let def = tool.definition();
+
+    assert_eq!(def.name, "file_edit");
+    assert_eq!(def.risk_level, RiskLevel::Medium);
+    assert_eq!(def.category, ToolCategory::File);
+}
+
+#[test]
+fn test_edit_mode_selection() {
+    use super::edit::Edit;
+
+    let edit = Edit::from_input(&serde_json::json!({
+        "path": "a.txt", "old_string": "a", "new_string": "b"
+    }))
+    .unwrap();
+    assert!(matches!(edit, Edit::Replace { all: false, .. }));
+
+    let edit = Edit::from_input(&serde_json::json!({
+        "path": "a.txt", "start_line": 3, "new_string": "x"
+    }))
+    .unwrap();
+    assert!(matches!(
+        edit,
+        Edit::Lines {
+            start: 3,
+            end: 3,
+            ..
+        }
+    ));
+
+    // No mode, two modes, missing new_string, empty old_string
+    for input in [
+        serde_json::json!({"path": "a.txt"}),
+        serde_json::json!({"path": "a.txt", "old_string": "a", "new_string": "b", "patch": "@@"}),
+        serde_json::json!({"path": "a.txt", "old_string": "a"}),
+        serde_json::json!({"path": "a.txt", "old_string": "", "new_string": "b"}),
+    ] {
+        assert!(Edit::from_input(&input).is_err());
+    }
+}
+
+#[test]
+fn test_edit_replace() {
+    use super::edit::Edit;
+
+    let replace = |old: &str, new: &str, all: bool| Edit::Replace {
+        old: old.to_string(),
+        new: new.to_string(),
+        all,
+    };
+    let content = "let a = 1;\nlet b = 1;\n";
+
+    assert_eq!(
+        replace("let a = 1;", "let a = 2;", false)
+            .apply(content)
+            .unwrap(),
+        "let a = 2;\nlet b = 1;\n"
+    );
+    // Ambiguous unless replace_all
+    assert!(replace("= 1", "= 3", false).apply(content).is_err());
+    assert_eq!(
+        replace("= 1", "= 3", true).apply(content).unwrap(),
+        "let a = 3;\nlet b = 3;\n"
+    );
+    assert!(replace("let c", "let d", false).apply(content).is_err());
+
+    // `\n` in the edit matches CRLF files
+    assert_eq!(
+        replace("a\nb", "a\nx\nb", false)
+            .apply("a\r\nb\r\n")
+            .unwrap(),
+        "a\r\nx\r\nb\r\n"
+    );
+}
+
+#[test]
+fn test_edit_line_range() {
+    use super::edit::Edit;
+
+    let lines = |start: usize, end: usize, new: &str| Edit::Lines {
+        start,
+        end,
+        new: new.to_string(),
+    };
+    let content = "one\ntwo\nthree\n";
+
+    assert_eq!(
+        lines(2, 2, "TWO").apply(content).unwrap(),
+        "one\nTWO\nthree\n"
+    );
+    assert_eq!(lines(2, 3, "").apply(content).unwrap(), "one\n");
+    // end_line = start_line - 1 inserts
+    assert_eq!(
+        lines(1, 0, "zero\n").apply(content).unwrap(),
+        "zero\none\ntwo\nthree\n"
+    );
+    assert_eq!(
+        lines(4, 3, "four").apply(content).unwrap(),
+        "one\ntwo\nthree\nfour\n"
+    );
+    assert_eq!(lines(1, 1, "x").apply("no newline").unwrap(), "x");
+
+    assert!(lines(0, 1, "x").apply(content).is_err());
+    assert!(lines(2, 4, "x").apply(content).is_err());
+    assert!(lines(5, 4, "x").apply(content).is_err());
+}
+
+#[test]
+fn test_patch_apply() {
+    use super::edit::Edit;
+
+    let content = "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}\n\nfn other() {\n    todo!()\n}\n";
+    let patch = "\
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,4 +1,4 @@
+ fn main() {
+-    let x = 1;
++    let x = 2;
+     println!(\"{}\", x);
+ }
+@@ -6,3 +6,3 @@
+ fn other() {
+-    todo!()
++    unimplemented!()
+ }
+";
+    assert_eq!(
+        Edit::Patch(patch.to_string()).apply(content).unwrap(),
+        "fn main() {\n    let x = 2;\n    println!(\"{}\", x);\n}\n\nfn other() {\n    unimplemented!()\n}\n"
+    );
+}
+
+#[test]
+fn test_patch_fuzzy_context() {
+    use super::edit::Edit;
+
+    let content = "// header\n// more\nfn main() {\n    let x = 1;\n    run(x);\n}\n";
+
+    // Wrong line numbers and whitespace differences in the context
+    let patch = "@@ -10,3 +10,3 @@\n fn main()  {\n-    let x = 1;\n+    let x = 2;\n     run(x);";
+    assert_eq!(
+        Edit::Patch(patch.to_string()).apply(content).unwrap(),
+        "// header\n// more\nfn main() {\n    let x = 2;\n    run(x);\n}\n"
+    );
+
+    // A stale context line is dropped (fuzz)
+    let patch = "@@ -3,4 +3,4 @@\n fn main() {\n-    let x = 1;\n+    let x = 3;\n     run(x);\n-}\n+}\n // stale";
+    assert_eq!(
+        Edit::Patch(patch.to_string()).apply(content).unwrap(),
+        "// header\n// more\nfn main() {\n    let x = 3;\n    run(x);\n}\n"
+    );
+
+    // Removed lines must match
+    let patch = "@@ -3,3 +3,3 @@\n fn main() {\n-    let y = 1;\n+    let y = 2;\n";
+    assert!(Edit::Patch(patch.to_string()).apply(content).is_err());
+}
+
+#[test]
+fn test_patch_parse_errors() {
+    use super::patch::parse_patch;
+
+    assert!(parse_patch("just some text").is_err());
+    assert!(parse_patch("@@ -1 +1 @@\n").is_err());
+
+    let two_files =
+        "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n--- a/y\n+++ b/y\n@@ -1 +1 @@\n-c\n+d\n";
+    assert!(parse_patch(two_files).is_err());
+
+    // A removed line that starts with "--" is not a file header
+    let patch = "@@ -1,2 +1,1 @@\n--- comment\n keep\n";
+    assert_eq!(parse_patch(patch).unwrap().len(), 1);
+}
+
+#[test]
+fn test_patch_dash_lines_inside_hunk() {
+    use super::edit::Edit;
+
+    // Removing "-- old" right before adding "++ new" looks like a file header
+    let content = "select 1;\n-- old\nselect 2;\n";
+    let patch = "\
+--- a/query.sql
++++ b/query.sql
+@@ -1,3 +1,3 @@
+ select 1;
+--- old
++++ new
+ select 2;
+";
+    assert_eq!(
+        Edit::Patch(patch.to_string()).apply(content).unwrap(),
+        "select 1;\n++ new\nselect 2;\n"
+    );
+
+    // After the counted lines a second file header is still recognized
+    let two_files = format!("{patch}--- a/other.sql\n+++ b/other.sql\n@@ -1 +1 @@\n-a\n+b\n");
+    assert!(super::patch::parse_patch(&two_files).is_err());
+}
+
+#[tokio::test]
+async fn test_file_edit_returns_diff() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    std::fs::write(&path, "alpha\nbeta\ngamma\n").unwrap();
+    let path_str = path.to_string_lossy().to_string();
+
+    let tool = FileEditTool::new();
+    let result = tool
+        .execute(serde_json::json!({
+            "path": path_str,
+            "old_string": "beta",
+            "new_string": "BETA"
+        }))
+        .await
+        .unwrap();
+
+    assert!(result.success);
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "alpha\nBETA\ngamma\n"
+    );
+    assert_eq!(result.output["changed"], true);
+    assert_eq!(result.output["lines_added"], 1);
+    assert_eq!(result.output["lines_removed"], 1);
+    let diff = result.output["diff"].as_str().unwrap();
+    assert!(diff.contains("-beta\n+BETA\n"));
+    assert!(diff.contains("@@ -1,3 +1,3 @@"));
+
+    // Secrets are blocked like in file_write, and the file is left alone
+    let result = tool
+        .execute(serde_json::json!({
+            "path": path_str,
+            "old_string": "gamma",
+            "new_string": "password=hunter2"
+        }))
+        .await;
+    assert!(result.is_err());
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "alpha\nBETA\ngamma\n"
+    );
+}
+
+#[tokio::test]
+async fn test_file_edit_blocks_sensitive() {
+    let tool = FileEditTool::new();
+
+    for path in ["/home/user/.env", "/etc/hosts"] {
+        let result = tool
+            .execute(serde_json::json!({
+                "path": path,
+                "old_string": "a",
+                "new_string": "b"
+            }))
+            .await;
+        assert!(result.is_err());
+    }
+}
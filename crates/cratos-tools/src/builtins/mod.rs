@@ -1,7 +1,7 @@
 //! Builtins - Built-in tools for Cratos
 //!
 //! This module provides the core set of built-in tools:
-//! - File tools: file_read, file_write, file_list
+//! - File tools: file_read, file_write, file_edit, file_list
 //! - HTTP tools: http_get, http_post
 //! - Exec tool: exec (shell command execution)
 //! - Git tools: git_status, git_commit, git_branch, git_diff
@@ -32,7 +32,9 @@ pub use app_control::AppControlTool;
 pub use bash::{BashConfig, BashSecurityMode, BashTool};
 pub use config::{ConfigAction, ConfigInput, ConfigTarget, ConfigTool};
 pub use exec::{ExecConfig, ExecMode, ExecTool};
-pub use file::{is_sensitive_file, validate_path, FileListTool, FileReadTool, FileWriteTool};
+pub use file::{
+    is_sensitive_file, validate_path, FileEditTool, FileListTool, FileReadTool, FileWriteTool,
+};
 pub use git::{
     GitBranchTool, GitCloneTool, GitCommitTool, GitDiffTool, GitLogTool, GitPushTool, GitStatusTool,
 };
@@ -77,6 +79,7 @@ pub fn register_builtins_with_config(registry: &mut ToolRegistry, config: &Built
     // File tools
     registry.register(Arc::new(FileReadTool::new()));
     registry.register(Arc::new(FileWriteTool::new()));
+    registry.register(Arc::new(FileEditTool::new()));
     registry.register(Arc::new(FileListTool::new()));
 
     // HTTP tools
@@ -157,6 +160,7 @@ mod tests {
 
         assert!(registry.has("file_read"));
         assert!(registry.has("file_write"));
+        assert!(registry.has("file_edit"));
         assert!(registry.has("file_list"));
         assert!(registry.has("http_get"));
         assert!(registry.has("http_post"));
@@ -178,7 +182,7 @@ mod tests {
         assert!(registry.has("send_file"));
         assert!(registry.has("image_generate"));
         assert!(registry.has("app_control"));
-        // A2UI tools are NOT registered by default, so count is 23 (22 + app_control)
-        assert_eq!(registry.len(), 23);
+        // A2UI tools are NOT registered by default, so count is 24 (22 + app_control + file_edit)
+        assert_eq!(registry.len(), 24);
     }
 }
@@ -4,7 +4,7 @@
 //! - Registry: Tool registration and discovery
 //! - Runner: Tool execution engine with sandboxing
 //! - Builtins: Built-in tools (file, http, git, etc.)
-//! - Sandbox: Docker-based tool isolation
+//! - Sandbox: Tool isolation (Docker, Apple Container, native Linux)
 //! - MCP: Model Context Protocol client for external tools
 
 #![forbid(unsafe_code)]
@@ -25,16 +25,19 @@ pub use builtins::{
 };
 pub use doctor::{ChecklistItem, Diagnosis, FailureCategory, ProbableCause, ToolDoctor};
 pub use error::{Error, Result};
-pub use registry::{RiskLevel, Tool, ToolCategory, ToolDefinition, ToolRegistry, ToolResult};
+pub use registry::{
+    DynamicTools, RiskLevel, Tool, ToolCategory, ToolDefinition, ToolRegistry, ToolResult,
+};
 pub use runner::{ExecutionOptions, ExecutionResult, RunnerConfig, ToolRunner};
 pub use sandbox::{
-    DockerSandbox, Mount, NetworkMode, ResourceLimits, SandboxConfig, SandboxOutput, SandboxPolicy,
-    ToolSandbox,
+    ContainerRuntime, DockerSandbox, Mount, NetworkMode, ResourceLimits, SandboxConfig,
+    SandboxOutput, SandboxPolicy, ToolSandbox,
 };
 
 // Re-export MCP types
 pub use mcp::{
-    McpClient, McpClientConfig, McpError, McpServerConfig, McpTool, McpToolBridge, McpTransport,
+    McpClient, McpClientConfig, McpError, McpEvent, McpPrompt, McpResource, McpServerConfig,
+    McpTool, McpToolBridge, McpTransport,
 };
 
 // Re-export browser types
@@ -3,13 +3,23 @@
 //! High-level client for managing multiple MCP server connections.
 
 use super::protocol::{
-    McpContent, McpError, McpInitResult, McpRequest, McpResult, McpTool, McpToolResult,
+    McpCapabilities, McpContent, McpError, McpEvent, McpInitResult, McpNotification, McpPrompt,
+    McpPromptResult, McpRequest, McpResource, McpResourceContents, McpResult, McpTool,
+    McpToolResult,
 };
 use super::transport::{McpConnection, McpServerConfig};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use tokio::sync::broadcast;
 use tracing::{debug, info, warn};
 
+/// Protocol version offered during `initialize`
+const PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// Upper bound on pages fetched from a paginated list call
+const MAX_LIST_PAGES: usize = 50;
+
 /// MCP client configuration
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct McpClientConfig {
@@ -27,17 +37,29 @@ pub struct McpClient {
     connections: HashMap<String, McpConnection>,
     /// Cached tools by server name
     tool_cache: HashMap<String, Vec<McpTool>>,
+    /// Capabilities advertised by each server during `initialize`
+    capabilities: HashMap<String, McpCapabilities>,
+    /// Server notifications (list changes, resource updates)
+    events: broadcast::Sender<McpEvent>,
 }
 
 impl McpClient {
     /// Create a new MCP client
     pub fn new() -> Self {
+        let (events, _) = broadcast::channel(64);
         Self {
             connections: HashMap::new(),
             tool_cache: HashMap::new(),
+            capabilities: HashMap::new(),
+            events,
         }
     }
 
+    /// Subscribe to server notifications from all connections
+    pub fn subscribe(&self) -> broadcast::Receiver<McpEvent> {
+        self.events.subscribe()
+    }
+
     /// Create from configuration
     pub async fn from_config(config: &McpClientConfig) -> McpResult<Self> {
         let mut client = Self::new();
@@ -72,27 +94,29 @@ impl McpClient {
         }
 
         let mut connection = McpConnection::new(&config)?;
+        connection.set_event_sender(self.events.clone());
         connection.start().await?;
 
         // Initialize the server
-        self.initialize_server(&mut connection).await?;
+        let capabilities = Self::initialize_server(&connection).await?;
 
         // Cache tools
-        let tools = self.fetch_tools(&connection).await?;
+        let tools = Self::fetch_tools(&connection).await?;
         let tool_names: Vec<String> = tools.iter().map(|t| t.name.clone()).collect();
         info!(server = %name, tools = ?tool_names, "MCP server initialized");
 
         self.tool_cache.insert(name.clone(), tools);
+        self.capabilities.insert(name.clone(), capabilities);
         self.connections.insert(name, connection);
 
         Ok(())
     }
 
-    /// Initialize a server connection
-    async fn initialize_server(&self, connection: &mut McpConnection) -> McpResult<()> {
+    /// Initialize a server connection and return its capabilities
+    async fn initialize_server(connection: &McpConnection) -> McpResult<McpCapabilities> {
         let request =
             McpRequest::new("initialize", connection.next_id()).with_params(serde_json::json!({
-                "protocolVersion": "2024-11-05",
+                "protocolVersion": PROTOCOL_VERSION,
                 "capabilities": {
                     "tools": {}
                 },
@@ -114,46 +138,98 @@ impl McpClient {
                 "MCP server initialized"
             );
 
-            // Send initialized notification
-            let notification = McpRequest {
-                jsonrpc: "2.0".to_string(),
-                method: "notifications/initialized".to_string(),
-                id: connection.next_id(),
-                params: None,
-            };
-            // Don't wait for response to notification
-            let _ = connection.send(notification).await;
+            connection
+                .notify(McpNotification::new("notifications/initialized"))
+                .await?;
+            connection.on_initialized(&init_result.protocol_version);
+            return Ok(init_result.capabilities);
         }
 
-        Ok(())
+        Ok(McpCapabilities::default())
     }
 
     /// Fetch tools from a server
-    async fn fetch_tools(&self, connection: &McpConnection) -> McpResult<Vec<McpTool>> {
-        let request = McpRequest::new("tools/list", connection.next_id());
-        let response = connection.send(request).await?;
+    async fn fetch_tools(connection: &McpConnection) -> McpResult<Vec<McpTool>> {
+        Self::list_all(connection, "tools/list", "tools").await
+    }
 
-        if let Some(result) = response.result {
-            #[derive(Deserialize)]
-            struct ToolsResult {
-                tools: Vec<McpTool>,
+    /// Collect every page of a paginated list method
+    async fn list_all<T: DeserializeOwned>(
+        connection: &McpConnection,
+        method: &str,
+        key: &str,
+    ) -> McpResult<Vec<T>> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        for _ in 0..MAX_LIST_PAGES {
+            let mut request = McpRequest::new(method, connection.next_id());
+            if let Some(cursor) = &cursor {
+                request = request.with_params(serde_json::json!({ "cursor": cursor }));
             }
+            let Some(result) = connection.send(request).await?.result else {
+                break;
+            };
 
-            let tools_result: ToolsResult = serde_json::from_value(result)
-                .map_err(|e| McpError::Protocol(format!("Failed to parse tools: {}", e)))?;
+            if let Some(page) = result.get(key) {
+                let page: Vec<T> = serde_json::from_value(page.clone()).map_err(|e| {
+                    McpError::Protocol(format!("Failed to parse {}: {}", method, e))
+                })?;
+                items.extend(page);
+            }
 
-            Ok(tools_result.tools)
-        } else {
-            Ok(Vec::new())
+            cursor = result
+                .get("nextCursor")
+                .and_then(|c| c.as_str())
+                .map(String::from);
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Get a connection by server name
+    fn connection(&self, server_name: &str) -> McpResult<&McpConnection> {
+        self.connections
+            .get(server_name)
+            .ok_or_else(|| McpError::ServerNotFound(server_name.to_string()))
+    }
+
+    /// Send a request, re-initializing once if the HTTP session expired
+    async fn request(
+        &self,
+        server_name: &str,
+        method: &str,
+        params: serde_json::Value,
+    ) -> McpResult<Option<serde_json::Value>> {
+        let connection = self.connection(server_name)?;
+        let request = McpRequest::new(method, connection.next_id()).with_params(params.clone());
+
+        match connection.send(request).await {
+            Err(McpError::SessionExpired) => {
+                info!(server = %server_name, "MCP session expired, re-initializing");
+                Self::initialize_server(connection).await?;
+                let retry = McpRequest::new(method, connection.next_id()).with_params(params);
+                Ok(connection.send(retry).await?.result)
+            }
+            other => Ok(other?.result),
         }
     }
 
+    /// Capabilities a server advertised during `initialize`
+    pub fn capabilities(&self, server_name: &str) -> Option<&McpCapabilities> {
+        self.capabilities.get(server_name)
+    }
+
     /// Remove a server
     pub fn remove_server(&mut self, name: &str) -> McpResult<()> {
         if let Some(mut connection) = self.connections.remove(name) {
             connection.stop()?;
         }
         self.tool_cache.remove(name);
+        self.capabilities.remove(name);
         Ok(())
     }
 
@@ -198,11 +274,6 @@ impl McpClient {
         tool_name: &str,
         arguments: serde_json::Value,
     ) -> McpResult<McpToolResult> {
-        let connection = self
-            .connections
-            .get(server_name)
-            .ok_or_else(|| McpError::ServerNotFound(server_name.to_string()))?;
-
         // Verify tool exists
         if let Some(tools) = self.tool_cache.get(server_name) {
             if !tools.iter().any(|t| t.name == tool_name) {
@@ -213,15 +284,18 @@ impl McpClient {
             }
         }
 
-        let request =
-            McpRequest::new("tools/call", connection.next_id()).with_params(serde_json::json!({
-                "name": tool_name,
-                "arguments": arguments
-            }));
-
-        let response = connection.send(request).await?;
-
-        if let Some(result) = response.result {
+        let result = self
+            .request(
+                server_name,
+                "tools/call",
+                serde_json::json!({
+                    "name": tool_name,
+                    "arguments": arguments
+                }),
+            )
+            .await?;
+
+        if let Some(result) = result {
             let tool_result: McpToolResult = serde_json::from_value(result)
                 .map_err(|e| McpError::Protocol(format!("Failed to parse tool result: {}", e)))?;
             Ok(tool_result)
@@ -256,13 +330,150 @@ impl McpClient {
         self.call_tool(server_name, tool_name, arguments).await
     }
 
+    /// Fetch the current tool list from a server without touching the cache
+    ///
+    /// Lets callers holding a shared client fetch under a read lock and only
+    /// take the write lock for [`set_server_tools`](Self::set_server_tools).
+    pub async fn fetch_server_tools(&self, server_name: &str) -> McpResult<Vec<McpTool>> {
+        Self::fetch_tools(self.connection(server_name)?).await
+    }
+
+    /// Replace the cached tool list for a server
+    pub fn set_server_tools(&mut self, server_name: &str, tools: Vec<McpTool>) {
+        if self.connections.contains_key(server_name) {
+            self.tool_cache.insert(server_name.to_string(), tools);
+        }
+    }
+
+    /// List resources exposed by a server
+    pub async fn list_resources(&self, server_name: &str) -> McpResult<Vec<McpResource>> {
+        self.require(server_name, "resources", |c| c.resources.is_some())?;
+        Self::list_all(self.connection(server_name)?, "resources/list", "resources").await
+    }
+
+    /// Read a resource's contents
+    pub async fn read_resource(
+        &self,
+        server_name: &str,
+        uri: &str,
+    ) -> McpResult<Vec<McpResourceContents>> {
+        self.require(server_name, "resources", |c| c.resources.is_some())?;
+        let result = self
+            .request(
+                server_name,
+                "resources/read",
+                serde_json::json!({ "uri": uri }),
+            )
+            .await?;
+
+        #[derive(Deserialize)]
+        struct ReadResult {
+            #[serde(default)]
+            contents: Vec<McpResourceContents>,
+        }
+
+        match result {
+            Some(result) => serde_json::from_value::<ReadResult>(result)
+                .map(|r| r.contents)
+                .map_err(|e| McpError::Protocol(format!("Failed to parse resource: {}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Subscribe to `notifications/resources/updated` for a resource
+    pub async fn subscribe_resource(&self, server_name: &str, uri: &str) -> McpResult<()> {
+        self.require(server_name, "resource subscriptions", |c| {
+            c.resources.as_ref().is_some_and(|r| r.subscribe)
+        })?;
+        self.request(
+            server_name,
+            "resources/subscribe",
+            serde_json::json!({ "uri": uri }),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Cancel a resource subscription
+    pub async fn unsubscribe_resource(&self, server_name: &str, uri: &str) -> McpResult<()> {
+        self.require(server_name, "resource subscriptions", |c| {
+            c.resources.as_ref().is_some_and(|r| r.subscribe)
+        })?;
+        self.request(
+            server_name,
+            "resources/unsubscribe",
+            serde_json::json!({ "uri": uri }),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// List prompt templates exposed by a server
+    pub async fn list_prompts(&self, server_name: &str) -> McpResult<Vec<McpPrompt>> {
+        self.require(server_name, "prompts", |c| c.prompts.is_some())?;
+        Self::list_all(self.connection(server_name)?, "prompts/list", "prompts").await
+    }
+
+    /// List prompt templates from every server that supports them
+    pub async fn list_all_prompts(&self) -> Vec<(String, McpPrompt)> {
+        let mut all = Vec::new();
+        for (server_name, capabilities) in &self.capabilities {
+            if capabilities.prompts.is_none() {
+                continue;
+            }
+            match self.list_prompts(server_name).await {
+                Ok(prompts) => all.extend(prompts.into_iter().map(|p| (server_name.clone(), p))),
+                Err(e) => warn!(server = %server_name, error = %e, "Failed to list prompts"),
+            }
+        }
+        all
+    }
+
+    /// Render a prompt template with the given arguments
+    pub async fn get_prompt(
+        &self,
+        server_name: &str,
+        prompt_name: &str,
+        arguments: HashMap<String, String>,
+    ) -> McpResult<McpPromptResult> {
+        self.require(server_name, "prompts", |c| c.prompts.is_some())?;
+        let result = self
+            .request(
+                server_name,
+                "prompts/get",
+                serde_json::json!({ "name": prompt_name, "arguments": arguments }),
+            )
+            .await?
+            .ok_or_else(|| McpError::Protocol("Empty prompts/get result".to_string()))?;
+
+        serde_json::from_value(result)
+            .map_err(|e| McpError::Protocol(format!("Failed to parse prompt: {}", e)))
+    }
+
+    /// Fail unless the server advertised a capability
+    fn require(
+        &self,
+        server_name: &str,
+        what: &str,
+        check: impl Fn(&McpCapabilities) -> bool,
+    ) -> McpResult<()> {
+        match self.capabilities.get(server_name) {
+            Some(capabilities) if check(capabilities) => Ok(()),
+            Some(_) => Err(McpError::Unsupported(
+                server_name.to_string(),
+                what.to_string(),
+            )),
+            None => Err(McpError::ServerNotFound(server_name.to_string())),
+        }
+    }
+
     /// Refresh tool cache for all servers
     pub async fn refresh_tools(&mut self) -> McpResult<()> {
         let server_names: Vec<String> = self.connections.keys().cloned().collect();
 
         for name in server_names {
             if let Some(connection) = self.connections.get(&name) {
-                match self.fetch_tools(connection).await {
+                match Self::fetch_tools(connection).await {
                     Ok(tools) => {
                         self.tool_cache.insert(name.clone(), tools);
                     }
@@ -331,6 +542,19 @@ mod tests {
         assert_eq!(config.servers[0].name, "filesystem");
     }
 
+    #[tokio::test]
+    async fn test_unknown_server_errors() {
+        let client = McpClient::new();
+        assert!(matches!(
+            client.list_resources("missing").await,
+            Err(McpError::ServerNotFound(_))
+        ));
+        assert!(matches!(
+            client.get_prompt("missing", "review", HashMap::new()).await,
+            Err(McpError::ServerNotFound(_))
+        ));
+    }
+
     #[test]
     fn test_parse_full_tool_name() {
         // This test verifies the parsing logic
@@ -6,7 +6,12 @@
 //! ## Supported Transports
 //!
 //! - **stdio**: Spawns a child process and communicates via stdin/stdout (JSON-RPC)
-//! - **sse**: Server-Sent Events over HTTP (planned)
+//! - **http**: Streamable HTTP with session IDs and stream resumption
+//! - **sse**: Legacy HTTP+SSE transport
+//!
+//! Besides tools, the client exposes server resources (`resources/*`) and
+//! prompt templates (`prompts/*`). Tool list changes announced by a server
+//! are picked up live by [`spawn_tool_refresh`].
 //!
 //! ## Usage
 //!
@@ -35,30 +40,43 @@
 mod bridge;
 mod client;
 mod protocol;
+mod streamable;
 mod transport;
 
 pub use bridge::McpToolBridge;
 pub use client::{McpClient, McpClientConfig};
 pub use protocol::{
-    McpContent, McpError, McpRequest, McpResponse, McpResult, McpTool, McpToolCall,
+    McpCapabilities, McpContent, McpError, McpEvent, McpPrompt, McpPromptArgument,
+    McpPromptMessage, McpPromptResult, McpRequest, McpResource, McpResourceContents, McpResponse,
+    McpResult, McpTool, McpToolCall,
 };
 pub use transport::{McpServerConfig, McpTransport};
 
-use crate::registry::ToolRegistry;
+use crate::registry::{DynamicTools, Tool, ToolRegistry};
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{info, warn};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, info, warn};
 
 /// Entry in `.mcp.json` `mcpServers` map.
+///
+/// Stdio servers set `command`; remote servers set `url` with `type` of
+/// `"http"` (Streamable HTTP, the default for URLs) or `"sse"`.
 #[derive(Debug, serde::Deserialize)]
 struct McpJsonEntry {
-    command: String,
+    #[serde(default, rename = "type")]
+    transport_type: Option<String>,
+    #[serde(default)]
+    command: Option<String>,
     #[serde(default)]
     args: Vec<String>,
     #[serde(default)]
     env: HashMap<String, String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
     #[serde(default = "default_true")]
     enabled: bool,
     #[serde(default)]
@@ -66,6 +84,33 @@ struct McpJsonEntry {
     description: Option<String>,
 }
 
+impl McpJsonEntry {
+    /// Build the transport for this entry
+    fn transport(&self) -> crate::Result<McpTransport> {
+        match (&self.command, &self.url) {
+            (Some(command), _) => Ok(McpTransport::Stdio {
+                command: command.clone(),
+                args: self.args.clone(),
+                env: self.env.clone(),
+            }),
+            (None, Some(url)) if self.transport_type.as_deref() == Some("sse") => {
+                Ok(McpTransport::Sse {
+                    url: url.clone(),
+                    api_key: None,
+                })
+            }
+            (None, Some(url)) => Ok(McpTransport::StreamableHttp {
+                url: url.clone(),
+                api_key: None,
+                headers: self.headers.clone(),
+            }),
+            (None, None) => Err(crate::Error::Config(
+                "MCP server entry needs either 'command' or 'url'".to_string(),
+            )),
+        }
+    }
+}
+
 fn default_true() -> bool {
     true
 }
@@ -80,8 +125,11 @@ struct McpJsonFile {
 /// Load `.mcp.json`, start enabled servers, and register their tools
 /// into the given [`ToolRegistry`].
 ///
+/// Tools go into the registry's [`DynamicTools`] (one group per server) and
+/// are kept in sync with `notifications/tools/list_changed`.
+///
 /// Returns a shared `McpClient` for later use (e.g. the orchestrator
-/// can hold a reference for dynamic tool calls).
+/// reads resources and renders prompts through it).
 pub async fn register_mcp_tools(
     registry: &mut ToolRegistry,
     mcp_json_path: &Path,
@@ -101,13 +149,16 @@ pub async fn register_mcp_tools(
             continue;
         }
 
+        let transport = match entry.transport() {
+            Ok(transport) => transport,
+            Err(e) => {
+                warn!(server = %name, error = %e, "Invalid MCP server entry, skipping");
+                continue;
+            }
+        };
         let server_config = McpServerConfig {
             name: name.clone(),
-            transport: McpTransport::Stdio {
-                command: entry.command.clone(),
-                args: entry.args.clone(),
-                env: entry.env.clone(),
-            },
+            transport,
             auto_start: true,
         };
 
@@ -131,6 +182,7 @@ pub async fn register_mcp_tools(
     }
 
     let client = Arc::new(RwLock::new(client));
+    let dynamic = registry.dynamic_tools();
 
     // Collect tools from all connected servers and register them
     let tools = {
@@ -138,25 +190,80 @@ pub async fn register_mcp_tools(
         c.list_tools().await.unwrap_or_default()
     };
 
+    let mut by_server: HashMap<String, Vec<McpTool>> = HashMap::new();
     for (server_name, mcp_tool) in tools {
-        let definition = mcp_tool.to_tool_definition(&server_name);
-        let tool_name = definition.name.clone();
-
-        let bridge = McpToolBridge::new(
-            definition,
-            server_name,
-            mcp_tool.name.clone(),
-            client.clone(),
-        );
-
-        registry.register(Arc::new(bridge));
-        registered_tool_count += 1;
-        info!(tool = %tool_name, "Registered MCP tool");
+        by_server.entry(server_name).or_default().push(mcp_tool);
+    }
+    for (server_name, tools) in by_server {
+        registered_tool_count += tools.len();
+        register_server_tools(&dynamic, &client, &server_name, tools);
     }
 
+    spawn_tool_refresh(client.clone(), dynamic);
+
     info!(
         count = registered_tool_count,
         "MCP tool registration complete"
     );
     Ok(client)
 }
+
+/// Replace the registered bridges for one server
+fn register_server_tools(
+    dynamic: &DynamicTools,
+    client: &Arc<RwLock<McpClient>>,
+    server_name: &str,
+    tools: Vec<McpTool>,
+) {
+    let bridges: Vec<Arc<dyn Tool>> = tools
+        .into_iter()
+        .map(|mcp_tool| {
+            let definition = mcp_tool.to_tool_definition(server_name);
+            debug!(tool = %definition.name, "Registered MCP tool");
+            Arc::new(McpToolBridge::new(
+                definition,
+                server_name.to_string(),
+                mcp_tool.name,
+                client.clone(),
+            )) as Arc<dyn Tool>
+        })
+        .collect();
+    dynamic.replace_group(&format!("mcp_{}", server_name), bridges);
+}
+
+/// Keep MCP tool registrations in sync with `notifications/tools/list_changed`
+///
+/// Runs for the lifetime of the server; abort the returned handle to stop it.
+pub fn spawn_tool_refresh(
+    client: Arc<RwLock<McpClient>>,
+    dynamic: DynamicTools,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut events = client.read().await.subscribe();
+        loop {
+            let server_name = match events.recv().await {
+                Ok(McpEvent::ToolsChanged { server }) => server,
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "MCP event stream lagged");
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            // Fetch under the read lock so tool calls are not blocked meanwhile
+            let fetched = client.read().await.fetch_server_tools(&server_name).await;
+            match fetched {
+                Ok(tools) => {
+                    info!(server = %server_name, count = tools.len(), "MCP tool list changed, re-registering");
+                    client
+                        .write()
+                        .await
+                        .set_server_tools(&server_name, tools.clone());
+                    register_server_tools(&dynamic, &client, &server_name, tools);
+                }
+                Err(e) => warn!(server = %server_name, error = %e, "Failed to refresh MCP tools"),
+            }
+        }
+    })
+}
@@ -37,6 +37,14 @@ pub enum McpError {
     /// Tool not found
     #[error("Tool '{0}' not found on server '{1}'")]
     ToolNotFound(String, String),
+
+    /// Server did not advertise the capability needed for a request
+    #[error("Server '{0}' does not support {1}")]
+    Unsupported(String, String),
+
+    /// Streamable HTTP session was terminated by the server
+    #[error("MCP session expired")]
+    SessionExpired,
 }
 
 /// MCP Result type
@@ -89,6 +97,129 @@ pub struct McpResponse {
     pub error: Option<McpRpcError>,
 }
 
+impl McpResponse {
+    /// Empty success response (e.g. for a server `ping`)
+    pub fn empty(id: u64) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: Some(serde_json::json!({})),
+            error: None,
+        }
+    }
+}
+
+/// JSON-RPC notification (no ID, no response expected)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpNotification {
+    /// JSON-RPC version
+    pub jsonrpc: String,
+    /// Notification method
+    pub method: String,
+    /// Notification parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+}
+
+impl McpNotification {
+    /// Create a new notification
+    pub fn new(method: impl Into<String>) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.into(),
+            params: None,
+        }
+    }
+}
+
+/// Any JSON-RPC message a server can send
+#[derive(Debug, Clone)]
+pub enum McpMessage {
+    /// Response to one of our requests
+    Response(McpResponse),
+    /// Server notification
+    Notification(McpNotification),
+    /// Server-initiated request (e.g. `ping`)
+    Request {
+        /// Request ID
+        id: u64,
+        /// Request method
+        method: String,
+    },
+}
+
+impl McpMessage {
+    /// Classify a raw JSON-RPC message
+    pub fn parse(data: &str) -> McpResult<Self> {
+        let value: serde_json::Value = serde_json::from_str(data)
+            .map_err(|e| McpError::Protocol(format!("Invalid JSON-RPC message: {}", e)))?;
+
+        let method = value.get("method").and_then(|m| m.as_str());
+        let id = value.get("id").and_then(|i| i.as_u64());
+        match (method, id) {
+            (Some(method), Some(id)) => Ok(Self::Request {
+                id,
+                method: method.to_string(),
+            }),
+            (Some(_), None) => serde_json::from_value(value)
+                .map(Self::Notification)
+                .map_err(|e| McpError::Protocol(format!("Invalid notification: {}", e))),
+            (None, _) => serde_json::from_value(value)
+                .map(Self::Response)
+                .map_err(|e| McpError::Protocol(format!("Invalid response: {}", e))),
+        }
+    }
+}
+
+/// Server-side change reported through a notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum McpEvent {
+    /// `notifications/tools/list_changed`
+    ToolsChanged {
+        /// Server name
+        server: String,
+    },
+    /// `notifications/resources/list_changed`
+    ResourcesChanged {
+        /// Server name
+        server: String,
+    },
+    /// `notifications/resources/updated` for a subscribed resource
+    ResourceUpdated {
+        /// Server name
+        server: String,
+        /// Resource URI
+        uri: String,
+    },
+    /// `notifications/prompts/list_changed`
+    PromptsChanged {
+        /// Server name
+        server: String,
+    },
+}
+
+impl McpEvent {
+    /// Map a server notification to an event, if it is one we track
+    pub fn from_notification(server: &str, notification: &McpNotification) -> Option<Self> {
+        let server = server.to_string();
+        match notification.method.as_str() {
+            "notifications/tools/list_changed" => Some(Self::ToolsChanged { server }),
+            "notifications/resources/list_changed" => Some(Self::ResourcesChanged { server }),
+            "notifications/prompts/list_changed" => Some(Self::PromptsChanged { server }),
+            "notifications/resources/updated" => {
+                let uri = notification
+                    .params
+                    .as_ref()?
+                    .get("uri")?
+                    .as_str()?
+                    .to_string();
+                Some(Self::ResourceUpdated { server, uri })
+            }
+            _ => None,
+        }
+    }
+}
+
 /// JSON-RPC error object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpRpcError {
@@ -201,6 +332,94 @@ impl McpContent {
     }
 }
 
+/// Resource advertised by `resources/list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpResource {
+    /// Resource URI
+    pub uri: String,
+    /// Human-readable name
+    pub name: String,
+    /// Resource description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// MIME type
+    #[serde(default, rename = "mimeType", skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Contents returned by `resources/read`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpResourceContents {
+    /// Resource URI
+    pub uri: String,
+    /// MIME type
+    #[serde(default, rename = "mimeType", skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Text contents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Binary contents (base64)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+/// Prompt template advertised by `prompts/list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpPrompt {
+    /// Prompt name
+    pub name: String,
+    /// Prompt description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Template arguments
+    #[serde(default)]
+    pub arguments: Vec<McpPromptArgument>,
+}
+
+/// Argument accepted by a prompt template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpPromptArgument {
+    /// Argument name
+    pub name: String,
+    /// Argument description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the argument must be provided
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Rendered prompt returned by `prompts/get`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpPromptResult {
+    /// Prompt description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Prompt messages
+    #[serde(default)]
+    pub messages: Vec<McpPromptMessage>,
+}
+
+impl McpPromptResult {
+    /// Flatten the prompt messages into a single text block
+    pub fn to_text(&self) -> String {
+        self.messages
+            .iter()
+            .filter_map(|m| m.content.as_text())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Message in a rendered prompt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpPromptMessage {
+    /// Message role (`user` or `assistant`)
+    pub role: String,
+    /// Message content
+    pub content: McpContent,
+}
+
 /// MCP server capabilities
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct McpCapabilities {
@@ -297,6 +516,44 @@ mod tests {
         assert!(!tool.description.is_empty());
     }
 
+    #[test]
+    fn test_mcp_message_classification() {
+        let response = McpMessage::parse(r#"{"jsonrpc":"2.0","id":3,"result":{}}"#).unwrap();
+        assert!(matches!(response, McpMessage::Response(r) if r.id == 3));
+
+        let request = McpMessage::parse(r#"{"jsonrpc":"2.0","id":9,"method":"ping"}"#).unwrap();
+        assert!(matches!(request, McpMessage::Request { id: 9, ref method } if method == "ping"));
+
+        let notification = McpMessage::parse(
+            r#"{"jsonrpc":"2.0","method":"notifications/resources/updated","params":{"uri":"file:///a"}}"#,
+        )
+        .unwrap();
+        let McpMessage::Notification(notification) = notification else {
+            unreachable!("expected notification");
+        };
+        assert_eq!(
+            McpEvent::from_notification("fs", &notification),
+            Some(McpEvent::ResourceUpdated {
+                server: "fs".to_string(),
+                uri: "file:///a".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_prompt_result_to_text() {
+        let json = r#"{
+            "description": "Review code",
+            "messages": [
+                {"role": "user", "content": {"type": "text", "text": "Review this"}},
+                {"role": "user", "content": {"type": "resource", "uri": "file:///a.rs", "text": "fn main() {}"}}
+            ]
+        }"#;
+
+        let result: McpPromptResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.to_text(), "Review this\n\nfn main() {}");
+    }
+
     #[test]
     fn test_mcp_content_text() {
         let content = McpContent::Text {
@@ -0,0 +1,453 @@
+//! MCP Streamable HTTP transport
+//!
+//! Every JSON-RPC message is POSTed to a single endpoint. The server answers
+//! with either a JSON body or an SSE stream that carries the response (and
+//! any notifications sent while handling the request). A standing GET stream
+//! delivers server-initiated notifications.
+//!
+//! The session ID returned on `initialize` (`Mcp-Session-Id`) is echoed on
+//! every later request. SSE event IDs are tracked so that a dropped stream can
+//! be resumed with `Last-Event-ID` instead of losing the pending response.
+
+use super::protocol::{
+    McpError, McpEvent, McpMessage, McpNotification, McpRequest, McpResponse, McpResult,
+};
+use cratos_llm::SseParser;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
+use reqwest::{Client, Response, StatusCode};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+const SESSION_HEADER: &str = "mcp-session-id";
+const PROTOCOL_HEADER: &str = "mcp-protocol-version";
+const LAST_EVENT_HEADER: &str = "last-event-id";
+
+/// Reconnect attempts when a response stream drops mid-request
+const MAX_RESUME_ATTEMPTS: u32 = 3;
+
+/// Limit on a request, including reading its response stream
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Streamable HTTP connection state
+pub(super) struct StreamableHttp {
+    name: String,
+    url: String,
+    client: Client,
+    session_id: Arc<Mutex<Option<String>>>,
+    protocol_version: Arc<Mutex<Option<String>>>,
+    events: Option<broadcast::Sender<McpEvent>>,
+    cancel: CancellationToken,
+}
+
+impl StreamableHttp {
+    /// Create the transport (no network traffic until the first request)
+    pub(super) fn new(
+        name: &str,
+        url: &str,
+        api_key: Option<&str>,
+        headers: &HashMap<String, String>,
+        events: Option<broadcast::Sender<McpEvent>>,
+    ) -> McpResult<Self> {
+        let mut default_headers = HeaderMap::new();
+        if let Some(key) = api_key {
+            let value = HeaderValue::from_str(&format!("Bearer {}", key))
+                .map_err(|e| McpError::Transport(format!("Invalid API key: {}", e)))?;
+            default_headers.insert(reqwest::header::AUTHORIZATION, value);
+        }
+        for (key, value) in headers {
+            let name = HeaderName::from_bytes(key.as_bytes())
+                .map_err(|e| McpError::Transport(format!("Invalid header '{}': {}", key, e)))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| McpError::Transport(format!("Invalid header '{}': {}", key, e)))?;
+            default_headers.insert(name, value);
+        }
+
+        let client = Client::builder()
+            .default_headers(default_headers)
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| McpError::Transport(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            url: url.to_string(),
+            client,
+            session_id: Arc::new(Mutex::new(None)),
+            protocol_version: Arc::new(Mutex::new(None)),
+            events,
+            cancel: CancellationToken::new(),
+        })
+    }
+
+    /// Current session ID, if the server assigned one
+    pub(super) fn session_id(&self) -> Option<String> {
+        self.session_id
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Record the negotiated protocol version (sent on every later request)
+    pub(super) fn set_protocol_version(&self, version: &str) {
+        *self
+            .protocol_version
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(version.to_string());
+    }
+
+    /// Send a request and wait for its response
+    pub(super) async fn request(&self, request: &McpRequest) -> McpResult<McpResponse> {
+        let body = serde_json::to_value(request)
+            .map_err(|e| McpError::Protocol(format!("Failed to serialize request: {}", e)))?;
+        let response = self.post(&body).await?;
+
+        if is_event_stream(&response) {
+            return self.read_response_stream(response, request.id).await;
+        }
+
+        let text = response
+            .text()
+            .await
+            .map_err(|e| McpError::Transport(format!("Failed to read response: {}", e)))?;
+        match McpMessage::parse(&text)? {
+            McpMessage::Response(response) => Ok(response),
+            other => Err(McpError::Protocol(format!(
+                "Expected response to request {}, got {:?}",
+                request.id, other
+            ))),
+        }
+    }
+
+    /// Send a notification (the server replies 202 with no body)
+    pub(super) async fn notify(&self, notification: &McpNotification) -> McpResult<()> {
+        let body = serde_json::to_value(notification)
+            .map_err(|e| McpError::Protocol(format!("Failed to serialize notification: {}", e)))?;
+        self.post(&body).await.map(|_| ())
+    }
+
+    /// Open the standing GET stream for server-initiated messages
+    ///
+    /// Servers that do not offer one answer 405; the listener then exits.
+    pub(super) fn start_listener(&self) {
+        let listener = self.listener();
+        let cancel = self.cancel.clone();
+
+        tokio::spawn(async move {
+            let mut last_event_id: Option<String> = None;
+            let mut backoff_ms = 1000u64;
+            loop {
+                let request = listener.get(last_event_id.as_deref());
+                let response = tokio::select! {
+                    _ = cancel.cancelled() => return,
+                    r = request.send() => r,
+                };
+                match response {
+                    Ok(resp) if resp.status() == StatusCode::METHOD_NOT_ALLOWED => {
+                        debug!(server = %listener.name, "Server offers no notification stream");
+                        return;
+                    }
+                    Ok(resp) if resp.status() == StatusCode::NOT_FOUND => {
+                        warn!(server = %listener.name, "MCP session expired, notification stream closed");
+                        return;
+                    }
+                    Ok(resp) if resp.status().is_success() => {
+                        backoff_ms = 1000;
+                        let mut resp = resp;
+                        let mut parser = SseParser::default();
+                        loop {
+                            let chunk = tokio::select! {
+                                _ = cancel.cancelled() => return,
+                                c = resp.chunk() => c,
+                            };
+                            match chunk {
+                                Ok(Some(data)) => {
+                                    for event in parser.push(&data) {
+                                        listener.dispatch(&event.data);
+                                    }
+                                    if let Some(id) = parser.last_event_id() {
+                                        last_event_id = Some(id.to_string());
+                                    }
+                                }
+                                Ok(None) => break,
+                                Err(e) => {
+                                    warn!(server = %listener.name, error = %e, "Notification stream error");
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Ok(resp) => {
+                        warn!(server = %listener.name, status = %resp.status(), "Notification stream rejected");
+                    }
+                    Err(e) => {
+                        warn!(server = %listener.name, error = %e, backoff_ms, "Notification stream failed, retrying");
+                    }
+                }
+
+                tokio::select! {
+                    _ = cancel.cancelled() => return,
+                    _ = tokio::time::sleep(Duration::from_millis(backoff_ms)) => {}
+                }
+                backoff_ms = (backoff_ms * 2).min(30_000);
+            }
+        });
+    }
+
+    /// Stop the listener and terminate the session (best effort)
+    pub(super) fn close(&self) {
+        self.cancel.cancel();
+        let Some(session_id) = self
+            .session_id
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+        else {
+            return;
+        };
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let request = self
+                .client
+                .delete(&self.url)
+                .header(SESSION_HEADER, session_id);
+            handle.spawn(async move {
+                let _ = request.send().await;
+            });
+        }
+        info!(server = %self.name, "MCP HTTP session closed");
+    }
+
+    /// Detached view of the connection state for stream readers
+    fn listener(&self) -> Listener {
+        Listener {
+            name: self.name.clone(),
+            url: self.url.clone(),
+            client: self.client.clone(),
+            session_id: self.session_id.clone(),
+            protocol_version: self.protocol_version.clone(),
+            events: self.events.clone(),
+        }
+    }
+
+    /// POST a JSON-RPC message with session headers
+    async fn post(&self, body: &serde_json::Value) -> McpResult<Response> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(ACCEPT, "application/json, text/event-stream")
+            .header(CONTENT_TYPE, "application/json")
+            .timeout(REQUEST_TIMEOUT)
+            .json(body);
+        let session_id = self.session_id();
+        if let Some(id) = &session_id {
+            request = request.header(SESSION_HEADER, id);
+        }
+        if let Some(version) = self
+            .protocol_version
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_deref()
+        {
+            request = request.header(PROTOCOL_HEADER, version);
+        }
+
+        debug!(server = %self.name, url = %self.url, "Sending MCP HTTP message");
+        let response = request
+            .send()
+            .await
+            .map_err(|e| McpError::Transport(format!("HTTP POST failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND && session_id.is_some() {
+            *self.session_id.lock().unwrap_or_else(|e| e.into_inner()) = None;
+            return Err(McpError::SessionExpired);
+        }
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(McpError::Transport(format!(
+                "HTTP {}: {}",
+                status,
+                text.chars().take(200).collect::<String>()
+            )));
+        }
+
+        if let Some(id) = response
+            .headers()
+            .get(SESSION_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            *self.session_id.lock().unwrap_or_else(|e| e.into_inner()) = Some(id.to_string());
+        }
+        Ok(response)
+    }
+
+    /// Read an SSE response stream until the response for `id` arrives,
+    /// resuming with `Last-Event-ID` if the stream drops first
+    async fn read_response_stream(&self, response: Response, id: u64) -> McpResult<McpResponse> {
+        let listener = self.listener();
+
+        let mut response = response;
+        let mut last_event_id: Option<String> = None;
+        let mut attempts = 0;
+        loop {
+            let mut parser = SseParser::default();
+            loop {
+                match response.chunk().await {
+                    Ok(Some(data)) => {
+                        for event in parser.push(&data) {
+                            if let Some(found) = listener.dispatch(&event.data) {
+                                if found.id == id {
+                                    return Ok(found);
+                                }
+                            }
+                        }
+                        if let Some(event_id) = parser.last_event_id() {
+                            last_event_id = Some(event_id.to_string());
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        warn!(server = %self.name, error = %e, "Response stream error");
+                        break;
+                    }
+                }
+            }
+
+            let Some(event_id) = last_event_id.as_deref() else {
+                return Err(McpError::Transport(
+                    "Response stream closed before a response arrived".to_string(),
+                ));
+            };
+            attempts += 1;
+            if attempts > MAX_RESUME_ATTEMPTS {
+                return Err(McpError::Transport(format!(
+                    "Response stream lost after {} resume attempts",
+                    MAX_RESUME_ATTEMPTS
+                )));
+            }
+
+            debug!(server = %self.name, last_event_id = %event_id, "Resuming response stream");
+            tokio::time::sleep(Duration::from_millis(250 * u64::from(attempts))).await;
+            response = listener
+                .get(Some(event_id))
+                .timeout(REQUEST_TIMEOUT)
+                .send()
+                .await
+                .map_err(|e| McpError::Transport(format!("Stream resume failed: {}", e)))?;
+            if !response.status().is_success() {
+                return Err(McpError::Transport(format!(
+                    "Stream resume rejected: HTTP {}",
+                    response.status()
+                )));
+            }
+        }
+    }
+}
+
+impl Drop for StreamableHttp {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// Shared state for reading server streams outside of `&self`
+struct Listener {
+    name: String,
+    url: String,
+    client: Client,
+    session_id: Arc<Mutex<Option<String>>>,
+    protocol_version: Arc<Mutex<Option<String>>>,
+    events: Option<broadcast::Sender<McpEvent>>,
+}
+
+impl Listener {
+    /// Build a GET request for the server stream
+    fn get(&self, last_event_id: Option<&str>) -> reqwest::RequestBuilder {
+        let mut request = self
+            .client
+            .get(&self.url)
+            .header(ACCEPT, "text/event-stream");
+        if let Some(id) = self
+            .session_id
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_deref()
+        {
+            request = request.header(SESSION_HEADER, id);
+        }
+        if let Some(version) = self
+            .protocol_version
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_deref()
+        {
+            request = request.header(PROTOCOL_HEADER, version);
+        }
+        if let Some(id) = last_event_id {
+            request = request.header(LAST_EVENT_HEADER, id);
+        }
+        request
+    }
+
+    /// Route a message from a stream; responses are handed back to the caller
+    fn dispatch(&self, data: &str) -> Option<McpResponse> {
+        if data.trim().is_empty() {
+            return None;
+        }
+        match McpMessage::parse(data) {
+            Ok(McpMessage::Response(response)) => Some(response),
+            Ok(McpMessage::Notification(notification)) => {
+                if let (Some(tx), Some(event)) = (
+                    &self.events,
+                    McpEvent::from_notification(&self.name, &notification),
+                ) {
+                    let _ = tx.send(event);
+                }
+                None
+            }
+            Ok(McpMessage::Request { id, method }) => {
+                self.reply(id, &method);
+                None
+            }
+            Err(e) => {
+                debug!(server = %self.name, error = %e, "Ignoring malformed stream message");
+                None
+            }
+        }
+    }
+
+    /// Answer a server-initiated request (only `ping` is supported)
+    fn reply(&self, id: u64, method: &str) {
+        if method != "ping" {
+            debug!(server = %self.name, method = %method, "Ignoring server request");
+            return;
+        }
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&McpResponse::empty(id));
+        if let Some(session) = self
+            .session_id
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_deref()
+        {
+            request = request.header(SESSION_HEADER, session);
+        }
+        tokio::spawn(async move {
+            let _ = request.send().await;
+        });
+    }
+}
+
+fn is_event_stream(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/event-stream"))
+}
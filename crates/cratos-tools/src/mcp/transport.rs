@@ -2,7 +2,10 @@
 //!
 //! Handles communication with MCP servers over different transports.
 
-use super::protocol::{McpError, McpRequest, McpResponse, McpResult};
+use super::protocol::{
+    McpError, McpEvent, McpMessage, McpNotification, McpRequest, McpResponse, McpResult,
+};
+use super::streamable::StreamableHttp;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -10,7 +13,7 @@ use std::io::{BufRead, BufReader, Write};
 use std::process::{Child, Command, Stdio};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use tokio::sync::oneshot;
+use tokio::sync::{broadcast, oneshot};
 use tracing::{debug, error, info, warn};
 
 /// MCP server configuration
@@ -44,7 +47,7 @@ pub enum McpTransport {
         #[serde(default)]
         env: HashMap<String, String>,
     },
-    /// SSE transport (HTTP)
+    /// Legacy SSE transport (HTTP+SSE, protocol 2024-11-05)
     Sse {
         /// Server URL
         url: String,
@@ -52,6 +55,18 @@ pub enum McpTransport {
         #[serde(default)]
         api_key: Option<String>,
     },
+    /// Streamable HTTP transport (single endpoint, protocol 2025-03-26)
+    #[serde(rename = "http", alias = "streamable_http")]
+    StreamableHttp {
+        /// MCP endpoint URL
+        url: String,
+        /// Optional API key (sent as a bearer token)
+        #[serde(default)]
+        api_key: Option<String>,
+        /// Extra HTTP headers sent with every request
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
 }
 
 /// Active MCP server connection
@@ -74,6 +89,10 @@ pub struct McpConnection {
     sse_post_url: Option<String>,
     /// SSE cancel token
     sse_cancel: Option<tokio_util::sync::CancellationToken>,
+    /// Streamable HTTP transport state
+    streamable: Option<StreamableHttp>,
+    /// Sink for server notifications we track
+    events: Option<broadcast::Sender<McpEvent>>,
 }
 
 impl McpConnection {
@@ -89,9 +108,16 @@ impl McpConnection {
             http_client: None,
             sse_post_url: None,
             sse_cancel: None,
+            streamable: None,
+            events: None,
         })
     }
 
+    /// Forward tracked server notifications (list changes, resource updates)
+    pub fn set_event_sender(&mut self, events: broadcast::Sender<McpEvent>) {
+        self.events = Some(events);
+    }
+
     /// Start the connection
     pub async fn start(&mut self) -> McpResult<()> {
         // Clone transport data to avoid borrow issues
@@ -102,6 +128,31 @@ impl McpConnection {
                 self.start_stdio(&command, &args, &env).await
             }
             McpTransport::Sse { url, api_key } => self.start_sse(&url, api_key.as_deref()).await,
+            McpTransport::StreamableHttp {
+                url,
+                api_key,
+                headers,
+            } => {
+                info!(url = %url, "Starting MCP Streamable HTTP transport");
+                self.streamable = Some(StreamableHttp::new(
+                    &self.name,
+                    &url,
+                    api_key.as_deref(),
+                    &headers,
+                    self.events.clone(),
+                )?);
+                Ok(())
+            }
+        }
+    }
+
+    /// Called once `initialize` succeeded: record the negotiated protocol
+    /// version and open the server notification stream (Streamable HTTP)
+    pub fn on_initialized(&self, protocol_version: &str) {
+        if let Some(http) = &self.streamable {
+            http.set_protocol_version(protocol_version);
+            http.start_listener();
+            debug!(server = %self.name, session = ?http.session_id(), "MCP HTTP session established");
         }
     }
 
@@ -162,6 +213,8 @@ impl McpConnection {
         // Start reader thread
         let pending = self.pending.clone();
         let server_name = self.name.clone();
+        let events = self.events.clone();
+        let writer = self.stdin.clone();
 
         std::thread::spawn(move || {
             let reader = BufReader::new(stdout);
@@ -170,8 +223,8 @@ impl McpConnection {
                     Ok(line) if !line.is_empty() => {
                         debug!(server = %server_name, line = %line, "Received from MCP server");
 
-                        match serde_json::from_str::<McpResponse>(&line) {
-                            Ok(response) => {
+                        match McpMessage::parse(&line) {
+                            Ok(McpMessage::Response(response)) => {
                                 // Recover from poisoned mutex to ensure responses are delivered
                                 // even if another thread panicked while holding the lock
                                 let mut pending = pending.lock().unwrap_or_else(|e| e.into_inner());
@@ -179,6 +232,21 @@ impl McpConnection {
                                     let _ = sender.send(response);
                                 }
                             }
+                            Ok(McpMessage::Notification(notification)) => {
+                                forward_event(&events, &server_name, &notification);
+                            }
+                            Ok(McpMessage::Request { id, method }) if method == "ping" => {
+                                if let (Some(writer), Ok(json)) =
+                                    (&writer, serde_json::to_string(&McpResponse::empty(id)))
+                                {
+                                    let mut stdin =
+                                        writer.lock().unwrap_or_else(|e| e.into_inner());
+                                    let _ = writeln!(stdin, "{}", json).and_then(|_| stdin.flush());
+                                }
+                            }
+                            Ok(McpMessage::Request { method, .. }) => {
+                                debug!(server = %server_name, method = %method, "Ignoring server request");
+                            }
                             Err(e) => {
                                 warn!(server = %server_name, error = %e, "Failed to parse response");
                             }
@@ -220,6 +288,7 @@ impl McpConnection {
 
         let pending = self.pending.clone();
         let server_name = self.name.clone();
+        let events = self.events.clone();
         let auth_header = api_key.map(|k| k.to_string());
 
         // Spawn SSE reader task
@@ -256,8 +325,8 @@ impl McpConnection {
 
                                         for line in event.lines() {
                                             if let Some(data) = line.strip_prefix("data: ") {
-                                                match serde_json::from_str::<McpResponse>(data) {
-                                                    Ok(response) => {
+                                                match McpMessage::parse(data) {
+                                                    Ok(McpMessage::Response(response)) => {
                                                         let mut pend = pending
                                                             .lock()
                                                             .unwrap_or_else(|e| e.into_inner());
@@ -266,6 +335,10 @@ impl McpConnection {
                                                             let _ = tx.send(response);
                                                         }
                                                     }
+                                                    Ok(McpMessage::Notification(n)) => {
+                                                        forward_event(&events, &server_name, &n);
+                                                    }
+                                                    Ok(McpMessage::Request { .. }) => {}
                                                     Err(e) => {
                                                         debug!(
                                                             server = %server_name,
@@ -301,6 +374,18 @@ impl McpConnection {
 
     /// Send a request and wait for response
     pub async fn send(&self, request: McpRequest) -> McpResult<McpResponse> {
+        // Streamable HTTP: response comes back on the POST itself
+        if let Some(ref http) = self.streamable {
+            let response = http.request(&request).await?;
+            if let Some(error) = response.error {
+                return Err(McpError::Server {
+                    code: error.code,
+                    message: error.message,
+                });
+            }
+            return Ok(response);
+        }
+
         // SSE transport: send via HTTP POST
         if let Some(ref client) = self.http_client {
             return self.send_sse(client, request).await;
@@ -352,6 +437,38 @@ impl McpConnection {
         Ok(response)
     }
 
+    /// Send a notification (no response expected)
+    pub async fn notify(&self, notification: McpNotification) -> McpResult<()> {
+        if let Some(ref http) = self.streamable {
+            return http.notify(&notification).await;
+        }
+
+        if let Some(ref client) = self.http_client {
+            let post_url = self
+                .sse_post_url
+                .as_ref()
+                .ok_or_else(|| McpError::Transport("SSE post URL not configured".to_string()))?;
+            client
+                .post(post_url)
+                .json(&notification)
+                .send()
+                .await
+                .map_err(|e| McpError::Transport(format!("SSE POST failed: {}", e)))?;
+            return Ok(());
+        }
+
+        let stdin = self
+            .stdin
+            .as_ref()
+            .ok_or_else(|| McpError::Transport("Connection not started".to_string()))?;
+        let json = serde_json::to_string(&notification)
+            .map_err(|e| McpError::Protocol(format!("Failed to serialize notification: {}", e)))?;
+        let mut stdin = stdin.lock().unwrap_or_else(|e| e.into_inner());
+        writeln!(stdin, "{}", json)
+            .and_then(|_| stdin.flush())
+            .map_err(|e| McpError::Transport(format!("Failed to write to stdin: {}", e)))
+    }
+
     /// Send request via SSE HTTP POST
     async fn send_sse(&self, client: &Client, request: McpRequest) -> McpResult<McpResponse> {
         let post_url = self
@@ -409,6 +526,9 @@ impl McpConnection {
             cancel.cancel();
             info!(server = %self.name, "SSE connection stopped");
         }
+        if let Some(http) = self.streamable.take() {
+            http.close();
+        }
         self.stdin = None;
         self.http_client = None;
         self.sse_post_url = None;
@@ -417,7 +537,19 @@ impl McpConnection {
 
     /// Check if connection is active
     pub fn is_active(&self) -> bool {
-        self.stdin.is_some() || self.http_client.is_some()
+        self.stdin.is_some() || self.http_client.is_some() || self.streamable.is_some()
+    }
+}
+
+/// Publish a server notification if it maps to a tracked event
+fn forward_event(
+    events: &Option<broadcast::Sender<McpEvent>>,
+    server: &str,
+    notification: &McpNotification,
+) {
+    debug!(server = %server, method = %notification.method, "MCP notification");
+    if let (Some(tx), Some(event)) = (events, McpEvent::from_notification(server, notification)) {
+        let _ = tx.send(event);
     }
 }
 
@@ -476,4 +608,30 @@ mod tests {
             other => unreachable!("Expected SSE transport, got {:?}", other),
         }
     }
+
+    #[test]
+    fn test_streamable_http_config_deserialization() {
+        let json = r#"{
+            "name": "remote",
+            "transport": {
+                "type": "http",
+                "url": "https://example.com/mcp",
+                "headers": {"X-Team": "core"}
+            }
+        }"#;
+
+        let config: McpServerConfig = serde_json::from_str(json).unwrap();
+        match config.transport {
+            McpTransport::StreamableHttp {
+                url,
+                api_key,
+                headers,
+            } => {
+                assert_eq!(url, "https://example.com/mcp");
+                assert!(api_key.is_none());
+                assert_eq!(headers.get("X-Team").map(String::as_str), Some("core"));
+            }
+            other => unreachable!("Expected Streamable HTTP transport, got {:?}", other),
+        }
+    }
 }
@@ -223,10 +223,79 @@ pub enum ExecSecurityMode {
     AllowAll,
 }
 
+/// Named groups of dynamically registered tools
+type ToolGroups = HashMap<String, Vec<Arc<dyn Tool>>>;
+
+/// Tools that can be replaced after the registry is shared
+///
+/// The registry is frozen behind an `Arc` once the server starts. Sources
+/// whose tool list changes at runtime (e.g. MCP servers sending
+/// `notifications/tools/list_changed`) register groups of tools through this
+/// handle instead; each group is swapped atomically.
+#[derive(Clone, Default)]
+pub struct DynamicTools {
+    groups: Arc<std::sync::RwLock<ToolGroups>>,
+}
+
+impl DynamicTools {
+    /// Replace every tool in a group
+    pub fn replace_group(&self, group: &str, tools: Vec<Arc<dyn Tool>>) {
+        debug!(group = %group, count = tools.len(), "Replacing dynamic tool group");
+        let mut groups = self.groups.write().unwrap_or_else(|e| e.into_inner());
+        if tools.is_empty() {
+            groups.remove(group);
+        } else {
+            groups.insert(group.to_string(), tools);
+        }
+    }
+
+    /// Remove a group and its tools
+    pub fn remove_group(&self, group: &str) {
+        self.replace_group(group, Vec::new());
+    }
+
+    /// Get a tool by name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Tool>> {
+        let groups = self.groups.read().unwrap_or_else(|e| e.into_inner());
+        groups
+            .values()
+            .flatten()
+            .find(|t| t.definition().name == name)
+            .cloned()
+    }
+
+    /// Snapshot of all tool definitions
+    #[must_use]
+    pub fn definitions(&self) -> Vec<ToolDefinition> {
+        let groups = self.groups.read().unwrap_or_else(|e| e.into_inner());
+        groups
+            .values()
+            .flatten()
+            .map(|t| t.definition().clone())
+            .collect()
+    }
+
+    /// Number of tools across all groups
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let groups = self.groups.read().unwrap_or_else(|e| e.into_inner());
+        groups.values().map(Vec::len).sum()
+    }
+
+    /// Check if there are no dynamic tools
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 /// Registry for managing tools
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<dyn Tool>>,
     definitions: HashMap<String, ToolDefinition>,
+    /// Tools registered after startup (see [`DynamicTools`])
+    dynamic: DynamicTools,
     /// Allowlist for exec command
     exec_allowlist: Vec<String>,
     /// Security mode for exec command
@@ -246,6 +315,7 @@ impl ToolRegistry {
         Self {
             tools: HashMap::new(),
             definitions: HashMap::new(),
+            dynamic: DynamicTools::default(),
             exec_allowlist: Vec::new(),
             exec_security_mode: ExecSecurityMode::DenyByDefault,
         }
@@ -261,6 +331,7 @@ impl ToolRegistry {
         Self {
             tools: HashMap::new(),
             definitions: HashMap::new(),
+            dynamic: DynamicTools::default(),
             exec_allowlist: Vec::new(),
             exec_security_mode: ExecSecurityMode::AllowAll,
         }
@@ -280,10 +351,19 @@ impl ToolRegistry {
         self.tools.insert(name, tool);
     }
 
+    /// Handle for registering tools after the registry is shared
+    #[must_use]
+    pub fn dynamic_tools(&self) -> DynamicTools {
+        self.dynamic.clone()
+    }
+
     /// Get a tool by name
     #[must_use]
     pub fn get(&self, name: &str) -> Option<Arc<dyn Tool>> {
-        self.tools.get(name).cloned()
+        self.tools
+            .get(name)
+            .cloned()
+            .or_else(|| self.dynamic.get(name))
     }
 
     /// Get a tool definition by name
@@ -295,7 +375,7 @@ impl ToolRegistry {
     /// Check if a tool exists
     #[must_use]
     pub fn has(&self, name: &str) -> bool {
-        self.tools.contains_key(name)
+        self.tools.contains_key(name) || self.dynamic.get(name).is_some()
     }
 
     /// List all tool names
@@ -304,12 +384,31 @@ impl ToolRegistry {
         self.tools.keys().map(|s| s.as_str()).collect()
     }
 
+    /// List all tool names, including dynamic tools
+    #[must_use]
+    pub fn all_names(&self) -> Vec<String> {
+        self.all_definitions().into_iter().map(|d| d.name).collect()
+    }
+
     /// List all tool definitions
     #[must_use]
     pub fn list_definitions(&self) -> Vec<&ToolDefinition> {
         self.definitions.values().collect()
     }
 
+    /// Snapshot of all tool definitions, including dynamic tools
+    #[must_use]
+    pub fn all_definitions(&self) -> Vec<ToolDefinition> {
+        let mut definitions: Vec<ToolDefinition> = self.definitions.values().cloned().collect();
+        definitions.extend(
+            self.dynamic
+                .definitions()
+                .into_iter()
+                .filter(|d| !self.definitions.contains_key(&d.name)),
+        );
+        definitions
+    }
+
     /// List enabled tool definitions
     #[must_use]
     pub fn list_enabled(&self) -> Vec<&ToolDefinition> {
@@ -400,13 +499,13 @@ impl ToolRegistry {
     /// Get tool count
     #[must_use]
     pub fn len(&self) -> usize {
-        self.tools.len()
+        self.tools.len() + self.dynamic.len()
     }
 
     /// Check if registry is empty
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.tools.is_empty()
+        self.tools.is_empty() && self.dynamic.is_empty()
     }
 
     /// Convert definitions to LLM tool format
@@ -415,8 +514,9 @@ impl ToolRegistry {
     /// which tools are high-risk and may require user approval.
     #[must_use]
     pub fn to_llm_tools(&self) -> Vec<cratos_llm::ToolDefinition> {
-        self.list_enabled()
+        self.all_definitions()
             .into_iter()
+            .filter(|def| def.enabled)
             .map(|def| {
                 let desc = if def.risk_level == RiskLevel::High {
                     format!("[risk: high] {}", def.description)
@@ -472,6 +572,50 @@ mod tests {
         assert_eq!(registry.len(), 0);
     }
 
+    struct NoopTool(ToolDefinition);
+
+    #[async_trait::async_trait]
+    impl Tool for NoopTool {
+        fn definition(&self) -> &ToolDefinition {
+            &self.0
+        }
+
+        async fn execute(&self, _input: serde_json::Value) -> Result<ToolResult> {
+            Ok(ToolResult::success(serde_json::json!({}), 0))
+        }
+    }
+
+    #[test]
+    fn test_dynamic_tools_replace_group() {
+        let registry = ToolRegistry::new();
+        let dynamic = registry.dynamic_tools();
+
+        dynamic.replace_group(
+            "mcp_fs",
+            vec![Arc::new(NoopTool(ToolDefinition::new(
+                "mcp_fs_read",
+                "Read",
+            )))],
+        );
+        assert!(registry.has("mcp_fs_read"));
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.to_llm_tools().len(), 1);
+
+        dynamic.replace_group(
+            "mcp_fs",
+            vec![Arc::new(NoopTool(ToolDefinition::new(
+                "mcp_fs_write",
+                "Write",
+            )))],
+        );
+        assert!(!registry.has("mcp_fs_read"));
+        assert!(registry.get("mcp_fs_write").is_some());
+        assert_eq!(registry.all_names(), vec!["mcp_fs_write".to_string()]);
+
+        dynamic.remove_group("mcp_fs");
+        assert!(registry.is_empty());
+    }
+
     #[test]
     fn test_exec_allowlist_secure_default() {
         let registry = ToolRegistry::new();
@@ -154,6 +154,12 @@ impl ToolRunner {
         &self.registry
     }
 
+    /// Get a shared handle to the registry
+    #[must_use]
+    pub fn registry_arc(&self) -> Arc<ToolRegistry> {
+        Arc::clone(&self.registry)
+    }
+
     /// Get the configuration
     #[must_use]
     pub fn config(&self) -> &RunnerConfig {
@@ -1,6 +1,7 @@
 //! Sandbox configuration
 
 use super::limits::ResourceLimits;
+use super::mount::Mount;
 use super::policy::{NetworkMode, SandboxPolicy};
 use super::runtime::ContainerRuntime;
 use serde::{Deserialize, Serialize};
@@ -19,15 +20,26 @@ pub struct SandboxConfig {
     pub image: String,
     /// Additional security options (Docker only)
     pub security_opts: Vec<String>,
-    /// Path to seccomp profile JSON (Docker only)
+    /// Path to seccomp profile JSON (Docker and native Linux)
     #[serde(default)]
     pub seccomp_profile: Option<std::path::PathBuf>,
-    /// Preferred runtime: "auto", "docker", "apple_container", "none"
+    /// Preferred runtime: "auto", "docker", "apple_container", "linux_native", "none"
     #[serde(default = "default_runtime_preference")]
     pub runtime_preference: String,
     /// On macOS, prefer Apple Container over Docker when available
     #[serde(default = "default_true")]
     pub prefer_apple_container: bool,
+    /// Host paths always exposed to the native Linux sandbox
+    #[serde(default)]
+    pub mounts: Vec<Mount>,
+    /// Delegated cgroup v2 directory for native sandbox limits
+    /// (rlimits are used when unset or not writable)
+    #[serde(default)]
+    pub cgroup_parent: Option<std::path::PathBuf>,
+    /// Let the native Linux sandbox run commands without Landlock or a seccomp
+    /// profile (filesystem or syscalls unrestricted) instead of refusing
+    #[serde(default)]
+    pub allow_degraded: bool,
 }
 
 fn default_runtime_preference() -> String {
@@ -67,6 +79,9 @@ impl Default for SandboxConfig {
             seccomp_profile,
             runtime_preference: "auto".to_string(),
             prefer_apple_container: true,
+            mounts: Vec::new(),
+            cgroup_parent: None,
+            allow_degraded: false,
         }
     }
 }
@@ -91,6 +106,14 @@ impl SandboxConfig {
                     ContainerRuntime::None
                 }
             }
+            "linux_native" => {
+                if ContainerRuntime::check_linux_native().await {
+                    ContainerRuntime::LinuxNative
+                } else {
+                    warn!("Native Linux sandbox requested but not available");
+                    ContainerRuntime::None
+                }
+            }
             "none" => ContainerRuntime::None,
             _ => {
                 // Auto-detect
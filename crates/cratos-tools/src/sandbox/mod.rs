@@ -4,6 +4,7 @@
 //! Supports multiple runtimes:
 //! - Docker: Cross-platform, process-based isolation
 //! - Apple Container: macOS 26+ native, VM-based isolation (stronger security)
+//! - Linux Native: user namespaces, Landlock and seccomp, no container runtime
 //!
 //! Key features:
 //! - Network isolation (default: none)
@@ -18,9 +19,11 @@ mod config;
 mod docker;
 mod limits;
 mod mount;
+mod native;
 mod output;
 mod policy;
 mod runtime;
+mod seccomp;
 mod unified;
 
 #[cfg(test)]
@@ -31,7 +34,10 @@ pub use config::SandboxConfig;
 pub use docker::DockerSandbox;
 pub use limits::ResourceLimits;
 pub use mount::Mount;
+pub(crate) use native::NATIVE_DEFAULT_PATH;
+pub use native::{launcher_entry, NativeSpec, LAUNCHER_ARG};
 pub use output::SandboxOutput;
 pub use policy::{NetworkMode, SandboxPolicy};
 pub use runtime::ContainerRuntime;
+pub use seccomp::SeccompProfile;
 pub use unified::{ToolSandbox, UnifiedSandbox};
@@ -0,0 +1,627 @@
+//! Native Linux sandbox (no container runtime required)
+//!
+//! Isolation is built from kernel primitives instead of a container image:
+//! - User, PID, mount, IPC and UTS namespaces, plus a network namespace for
+//!   `NetworkMode::None`; the command runs as `nobody` and sees only its own
+//!   processes in a fresh `/proc`
+//! - Landlock filesystem rules: system directories read-only, mounts as configured
+//! - The Docker seccomp profile (`config/seccomp-default.json`)
+//! - cgroup v2 limits under a delegated parent, falling back to rlimits
+//!
+//! Without Landlock or a seccomp profile the launcher refuses to run the
+//! command, unless `allow_degraded` is set.
+//!
+//! `unshare(CLONE_NEWUSER)` only works in a single-threaded process, so the
+//! sandbox re-executes the current binary with [`LAUNCHER_ARG`]. Binaries that
+//! use this runtime must call [`launcher_entry`] before starting their async
+//! runtime. The launcher unshares the namespaces and starts itself again as
+//! PID 1 of the new PID namespace; that second stage mounts the private tree,
+//! drops to `nobody`, restricts itself and `exec`s the target command, so the
+//! command inherits every restriction.
+
+use super::config::SandboxConfig;
+use super::limits::ResourceLimits;
+use super::mount::Mount;
+use super::policy::{NetworkMode, SandboxPolicy};
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Hidden first argument that switches the binary into launcher mode
+pub const LAUNCHER_ARG: &str = "__cratos-sandbox-exec";
+
+/// Hidden first argument of the second launcher stage, inside the namespaces
+const LAUNCHER_INIT_ARG: &str = "__cratos-sandbox-init";
+
+/// Exit code when the launcher fails before the command starts
+const LAUNCH_FAILED: i32 = 126;
+
+/// `PATH` for sandboxed commands when the caller does not provide one
+pub(crate) const NATIVE_DEFAULT_PATH: &str = "/usr/local/bin:/usr/bin:/bin";
+
+/// Prefix of per-execution cgroups created under `cgroup_parent`
+const CGROUP_PREFIX: &str = "cratos-";
+
+/// uid and gid of the command inside the user namespace (`nobody`)
+const SANDBOX_ID: u32 = 65534;
+
+/// Host paths readable (and executable) inside the sandbox
+const SYSTEM_READ_PATHS: &[&str] = &[
+    "/usr",
+    "/bin",
+    "/sbin",
+    "/lib",
+    "/lib32",
+    "/lib64",
+    "/etc",
+    "/opt",
+    "/proc",
+    "/nix/store",
+];
+
+/// Device nodes writable inside the sandbox
+const DEVICE_PATHS: &[&str] = &[
+    "/dev/null",
+    "/dev/zero",
+    "/dev/full",
+    "/dev/random",
+    "/dev/urandom",
+    "/dev/tty",
+    "/dev/ptmx",
+    "/dev/pts",
+];
+
+/// Everything the launcher needs to sandbox one command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NativeSpec {
+    /// Command and arguments
+    pub command: Vec<String>,
+    /// Working directory (readable inside the sandbox)
+    pub cwd: Option<PathBuf>,
+    /// Host paths exposed to the command
+    pub mounts: Vec<Mount>,
+    /// Network mode
+    pub network: NetworkMode,
+    /// Resource limits
+    pub limits: ResourceLimits,
+    /// Sandbox policy (Strict refuses to run without Landlock and seccomp)
+    pub policy: SandboxPolicy,
+    /// Docker-format seccomp profile
+    pub seccomp_profile: Option<PathBuf>,
+    /// Delegated cgroup v2 directory for per-execution cgroups
+    pub cgroup_parent: Option<PathBuf>,
+    /// Run without Landlock or a seccomp profile instead of refusing
+    #[serde(default)]
+    pub allow_degraded: bool,
+}
+
+impl NativeSpec {
+    /// Create a spec for `command` from the sandbox configuration defaults
+    #[must_use]
+    pub fn new(config: &SandboxConfig, command: Vec<String>) -> Self {
+        Self {
+            command,
+            cwd: None,
+            mounts: config.mounts.clone(),
+            network: config.default_network,
+            limits: config.default_limits.clone(),
+            policy: config.policy,
+            // The launcher changes directory, so resolve relative paths now
+            seccomp_profile: config
+                .seccomp_profile
+                .as_ref()
+                .map(|p| std::fs::canonicalize(p).unwrap_or_else(|_| p.clone())),
+            cgroup_parent: config.cgroup_parent.clone(),
+            allow_degraded: config.allow_degraded,
+        }
+    }
+
+    /// Set the working directory
+    #[must_use]
+    pub fn with_cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Add mounts on top of the configured ones
+    #[must_use]
+    pub fn with_mounts(mut self, mounts: impl IntoIterator<Item = Mount>) -> Self {
+        self.mounts.extend(mounts);
+        self
+    }
+
+    /// Set the network mode
+    #[must_use]
+    pub fn with_network(mut self, network: NetworkMode) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Set the resource limits
+    #[must_use]
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Program and arguments that run this spec through the launcher
+    pub fn launcher_command(&self) -> Result<(PathBuf, Vec<String>)> {
+        if self.command.is_empty() {
+            return Err(Error::Execution("Empty command".to_string()));
+        }
+        if self.network == NetworkMode::Bridge {
+            warn!("Native sandbox has no bridge networking - sharing the host network");
+        }
+        if let Some(parent) = &self.cgroup_parent {
+            prune_stale_cgroups(parent);
+        }
+
+        let exe = std::env::current_exe()
+            .map_err(|e| Error::Execution(format!("Cannot locate sandbox launcher: {}", e)))?;
+        let spec = serde_json::to_string(self)
+            .map_err(|e| Error::Execution(format!("Invalid sandbox spec: {}", e)))?;
+
+        debug!(command = ?self.command, "Executing with native Linux sandbox");
+        Ok((exe, vec![LAUNCHER_ARG.to_string(), spec]))
+    }
+
+    /// Build a `std::process::Command` that runs this spec through the launcher
+    pub fn to_command(&self) -> Result<std::process::Command> {
+        let (exe, args) = self.launcher_command()?;
+        let mut cmd = std::process::Command::new(exe);
+        cmd.args(args);
+        Ok(cmd)
+    }
+}
+
+/// Run the sandbox launcher if the process was started in launcher mode
+///
+/// Returns `None` for normal invocations. In launcher mode this returns the
+/// exit code to use: the command's, or [`LAUNCH_FAILED`] if sandboxing or
+/// `exec` failed.
+#[must_use]
+pub fn launcher_entry() -> Option<i32> {
+    let mut args = std::env::args_os().skip(1);
+    let stage = args.next()?;
+    let init = if stage == LAUNCHER_ARG {
+        false
+    } else if stage == LAUNCHER_INIT_ARG {
+        true
+    } else {
+        return None;
+    };
+
+    let spec = args
+        .next()
+        .and_then(|arg| arg.into_string().ok())
+        .and_then(|json| serde_json::from_str::<NativeSpec>(&json).ok());
+    let result = match spec {
+        Some(spec) if init => launch_init(&spec).map(|never| match never {}),
+        Some(spec) => launch(&spec),
+        None => Err(Error::InvalidInput("invalid launcher spec".to_string())),
+    };
+    Some(result.unwrap_or_else(|err| {
+        eprintln!("cratos sandbox: {}", err);
+        LAUNCH_FAILED
+    }))
+}
+
+/// Check whether the kernel allows unprivileged user namespaces
+#[must_use]
+pub fn kernel_supported() -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+    let read = |path: &str| {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+    };
+    // Debian/Ubuntu knob; absent on upstream kernels
+    if read("/proc/sys/kernel/unprivileged_userns_clone") == Some(0) {
+        return false;
+    }
+    read("/proc/sys/user/max_user_namespaces").is_some_and(|n| n > 0)
+}
+
+/// Check that the launcher can actually sandbox a trivial command
+///
+/// Only the namespaces are probed: a host without Landlock or a seccomp
+/// profile still selects this runtime, and each command then fails closed
+/// rather than the tool falling back to running unsandboxed.
+pub async fn probe() -> bool {
+    if !kernel_supported() {
+        return false;
+    }
+    let mut spec = NativeSpec::new(&SandboxConfig::default(), vec!["true".to_string()])
+        .with_limits(ResourceLimits::default().with_timeout(Duration::from_secs(5)));
+    spec.allow_degraded = true;
+    let Ok(cmd) = spec.to_command() else {
+        return false;
+    };
+    let mut cmd = tokio::process::Command::from(cmd);
+    cmd.kill_on_drop(true);
+    matches!(
+        tokio::time::timeout(Duration::from_secs(5), cmd.output()).await,
+        Ok(Ok(output)) if output.status.success()
+    )
+}
+
+/// Remove empty per-execution cgroups left by finished launchers
+fn prune_stale_cgroups(parent: &Path) {
+    let Ok(entries) = std::fs::read_dir(parent) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(CGROUP_PREFIX)
+        {
+            // Fails with EBUSY while the cgroup still has processes
+            let _ = std::fs::remove_dir(entry.path());
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn launch(spec: &NativeSpec) -> Result<i32> {
+    linux::enter(spec)
+}
+
+#[cfg(target_os = "linux")]
+fn launch_init(spec: &NativeSpec) -> Result<std::convert::Infallible> {
+    linux::init(spec)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn launch(_spec: &NativeSpec) -> Result<i32> {
+    Err(Error::Config(
+        "the native sandbox is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn launch_init(_spec: &NativeSpec) -> Result<std::convert::Infallible> {
+    Err(Error::Config(
+        "the native sandbox is only supported on Linux".to_string(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::*;
+    use crate::sandbox::seccomp::SeccompProfile;
+    use landlock::{
+        path_beneath_rules, Access, AccessFs, Ruleset, RulesetAttr, RulesetCreatedAttr,
+        RulesetStatus, ABI,
+    };
+    use nix::mount::{mount, MsFlags};
+    use nix::sched::{unshare, CloneFlags};
+    use nix::sys::prctl::set_pdeathsig;
+    use nix::sys::resource::{setrlimit, Resource};
+    use nix::sys::signal::Signal;
+    use nix::sys::statvfs::{statvfs, FsFlags};
+    use nix::unistd::{getgid, getuid, sethostname};
+    use std::convert::Infallible;
+    use std::fs::File;
+    use std::os::fd::AsRawFd;
+    use std::os::unix::process::{CommandExt, ExitStatusExt};
+
+    /// First stage: limit and unshare the current (single-threaded) process,
+    /// then run the second stage in the new PID namespace and wait for it
+    pub(super) fn enter(spec: &NativeSpec) -> Result<i32> {
+        let cgroup_applied = match &spec.cgroup_parent {
+            Some(parent) => match apply_cgroup(parent, &spec.limits) {
+                Ok(()) => true,
+                Err(e) if spec.policy == SandboxPolicy::Strict => return Err(e),
+                Err(e) => {
+                    eprintln!("cratos sandbox: {} - falling back to rlimits", e);
+                    false
+                }
+            },
+            None => false,
+        };
+        apply_rlimits(&spec.limits, cgroup_applied)?;
+
+        enter_namespaces(spec.network)?;
+
+        // unshare(CLONE_NEWPID) only applies to children
+        let exe = std::env::current_exe()?;
+        let spec_json = serde_json::to_string(spec)
+            .map_err(|e| Error::Execution(format!("Invalid sandbox spec: {}", e)))?;
+        let status = std::process::Command::new(exe)
+            .arg(LAUNCHER_INIT_ARG)
+            .arg(spec_json)
+            .status()
+            .map_err(|e| Error::Execution(format!("failed to start sandbox init: {}", e)))?;
+        Ok(status
+            .code()
+            .or_else(|| status.signal().map(|signal| 128 + signal))
+            .unwrap_or(LAUNCH_FAILED))
+    }
+
+    /// Second stage, PID 1 of the new namespace: mount the private tree,
+    /// drop to `nobody`, restrict the process and exec the command
+    pub(super) fn init(spec: &NativeSpec) -> Result<Infallible> {
+        let (program, args) = spec
+            .command
+            .split_first()
+            .ok_or_else(|| Error::Execution("Empty command".to_string()))?;
+
+        // Compile seccomp before the private mounts and Landlock hide the profile
+        let seccomp = match &spec.seccomp_profile {
+            Some(path) => SeccompProfile::load(path)?.compile()?,
+            None if spec.policy == SandboxPolicy::Strict || !spec.allow_degraded => {
+                return Err(Error::PermissionDenied(
+                    "no seccomp profile configured (set allow_degraded to run without one)"
+                        .to_string(),
+                ))
+            }
+            None => {
+                eprintln!("cratos sandbox: no seccomp profile - syscalls are not filtered");
+                Vec::new()
+            }
+        };
+
+        // Hold the working directory and mount sources open: the private /tmp
+        // hides anything beneath the host's /tmp. Opened inside the namespaces
+        // so the fds belong to the new mount namespace (bind mounts require that).
+        let cwd = spec.cwd.as_ref().map(File::open).transpose()?;
+        let sources = spec
+            .mounts
+            .iter()
+            .map(|m| {
+                File::open(&m.source)
+                    .map_err(|e| Error::Execution(format!("mount source {}: {}", m.source, e)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        setup_mounts(&spec.mounts, &sources)?;
+
+        if let (Some(path), Some(dir)) = (&spec.cwd, &cwd) {
+            if path.is_dir() {
+                std::env::set_current_dir(path)?;
+            } else {
+                std::env::set_current_dir(format!("/proc/self/fd/{}", dir.as_raw_fd()))?;
+            }
+        }
+        drop(sources);
+        drop(cwd);
+
+        drop_privileges()?;
+        // The caller kills the first stage on timeout; take the command with it
+        set_pdeathsig(Signal::SIGKILL).map_err(|e| Error::Execution(format!("prctl: {}", e)))?;
+
+        restrict_filesystem(spec)?;
+        for program in &seccomp {
+            seccompiler::apply_filter(program)
+                .map_err(|e| Error::Execution(format!("seccomp: {}", e)))?;
+        }
+
+        let err = std::process::Command::new(program).args(args).exec();
+        Err(Error::Execution(format!(
+            "failed to exec {}: {}",
+            program, err
+        )))
+    }
+
+    /// Create a per-execution cgroup, set its limits and move into it
+    fn apply_cgroup(parent: &Path, limits: &ResourceLimits) -> Result<()> {
+        let dir = parent.join(format!("{}{}", CGROUP_PREFIX, std::process::id()));
+        let write = |file: &str, value: String| {
+            std::fs::write(dir.join(file), value)
+                .map_err(|e| Error::Config(format!("cgroup {}: {}", file, e)))
+        };
+
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| Error::Config(format!("cgroup {}: {}", dir.display(), e)))?;
+        write("memory.max", limits.memory_bytes.to_string())?;
+        if limits.no_swap {
+            // Absent when swap accounting is disabled
+            let _ = write("memory.swap.max", "0".to_string());
+        }
+        write(
+            "cpu.max",
+            format!("{} 100000", u64::from(limits.cpu_percent) * 1000),
+        )?;
+        write("pids.max", limits.max_pids.to_string())?;
+        write("cgroup.procs", std::process::id().to_string())
+    }
+
+    /// Apply rlimits; memory falls back to RLIMIT_AS when no cgroup is in use
+    fn apply_rlimits(limits: &ResourceLimits, cgroup_applied: bool) -> Result<()> {
+        let set = |resource: Resource, value: u64| {
+            setrlimit(resource, value, value)
+                .map_err(|e| Error::Execution(format!("setrlimit {:?}: {}", resource, e)))
+        };
+
+        set(Resource::RLIMIT_CORE, 0)?;
+        // Backstop for the wall-clock timeout enforced by the caller
+        set(Resource::RLIMIT_CPU, limits.timeout.as_secs().max(1) + 1)?;
+        if !cgroup_applied {
+            set(Resource::RLIMIT_AS, limits.memory_bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Unshare namespaces, mapping the caller's uid/gid to root inside so the
+    /// second stage keeps the capabilities it needs to mount
+    fn enter_namespaces(network: NetworkMode) -> Result<()> {
+        let uid = getuid();
+        let gid = getgid();
+
+        let mut flags = CloneFlags::CLONE_NEWUSER
+            | CloneFlags::CLONE_NEWPID
+            | CloneFlags::CLONE_NEWNS
+            | CloneFlags::CLONE_NEWIPC
+            | CloneFlags::CLONE_NEWUTS;
+        if network == NetworkMode::None {
+            flags |= CloneFlags::CLONE_NEWNET;
+        }
+        unshare(flags).map_err(|e| Error::Execution(format!("unshare: {}", e)))?;
+
+        write_id_maps(0, uid.as_raw(), gid.as_raw())?;
+
+        sethostname("cratos-sandbox")
+            .map_err(|e| Error::Execution(format!("sethostname: {}", e)))?;
+        Ok(())
+    }
+
+    /// Enter a nested user namespace as `nobody`
+    ///
+    /// Capabilities are dropped on exec and the command has none over the
+    /// mounts set up by the second stage, even when the server runs as root.
+    fn drop_privileges() -> Result<()> {
+        unshare(CloneFlags::CLONE_NEWUSER)
+            .map_err(|e| Error::Execution(format!("unshare: {}", e)))?;
+        write_id_maps(SANDBOX_ID, 0, 0)
+    }
+
+    /// Map a single uid/gid of the parent user namespace to `inside`
+    fn write_id_maps(inside: u32, uid: u32, gid: u32) -> Result<()> {
+        std::fs::write("/proc/self/setgroups", "deny")?;
+        std::fs::write("/proc/self/uid_map", format!("{} {} 1", inside, uid))?;
+        std::fs::write("/proc/self/gid_map", format!("{} {} 1", inside, gid))?;
+        Ok(())
+    }
+
+    /// Private mount tree: fresh /tmp and /proc plus bind mounts for configured paths
+    fn setup_mounts(mounts: &[Mount], sources: &[File]) -> Result<()> {
+        let mount_err = |what: &str, e: nix::Error| Error::Execution(format!("{}: {}", what, e));
+
+        mount(
+            None::<&str>,
+            "/",
+            None::<&str>,
+            MsFlags::MS_REC | MsFlags::MS_PRIVATE,
+            None::<&str>,
+        )
+        .map_err(|e| mount_err("make / private", e))?;
+
+        mount(
+            Some("tmpfs"),
+            "/tmp",
+            Some("tmpfs"),
+            MsFlags::MS_NOSUID | MsFlags::MS_NODEV,
+            Some("size=64m,mode=1777"),
+        )
+        .map_err(|e| mount_err("mount /tmp", e))?;
+
+        // Only the sandbox's own processes: the server's environment and
+        // memory are not reachable through /proc
+        mount(
+            Some("proc"),
+            "/proc",
+            Some("proc"),
+            MsFlags::MS_NOSUID | MsFlags::MS_NODEV | MsFlags::MS_NOEXEC,
+            None::<&str>,
+        )
+        .map_err(|e| mount_err("mount /proc", e))?;
+
+        for (m, source) in mounts.iter().zip(sources) {
+            let target = Path::new(&m.target);
+            if !target.exists() && target.starts_with("/tmp") {
+                if source.metadata()?.is_dir() {
+                    std::fs::create_dir_all(target)?;
+                } else {
+                    if let Some(parent) = target.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    File::create(target)?;
+                }
+            }
+
+            let source_path = format!("/proc/self/fd/{}", source.as_raw_fd());
+            mount(
+                Some(source_path.as_str()),
+                target,
+                None::<&str>,
+                MsFlags::MS_BIND | MsFlags::MS_REC,
+                None::<&str>,
+            )
+            .map_err(|e| mount_err(&format!("bind {}", m.target), e))?;
+
+            if m.read_only {
+                // Flags inherited from the host mount are locked in a user
+                // namespace and must be repeated on remount
+                let flags = MsFlags::MS_BIND
+                    | MsFlags::MS_REMOUNT
+                    | MsFlags::MS_RDONLY
+                    | locked_flags(target);
+                mount(None::<&str>, target, None::<&str>, flags, None::<&str>)
+                    .map_err(|e| mount_err(&format!("remount {} read-only", m.target), e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Mount flags that cannot be cleared from inside a user namespace
+    fn locked_flags(path: &Path) -> MsFlags {
+        let Ok(stat) = statvfs(path) else {
+            return MsFlags::empty();
+        };
+        let mut flags = MsFlags::empty();
+        for (fs_flag, ms_flag) in [
+            (FsFlags::ST_NOSUID, MsFlags::MS_NOSUID),
+            (FsFlags::ST_NODEV, MsFlags::MS_NODEV),
+            (FsFlags::ST_NOEXEC, MsFlags::MS_NOEXEC),
+            (FsFlags::ST_NOATIME, MsFlags::MS_NOATIME),
+            (FsFlags::ST_NODIRATIME, MsFlags::MS_NODIRATIME),
+            (FsFlags::ST_RELATIME, MsFlags::MS_RELATIME),
+        ] {
+            if stat.flags().contains(fs_flag) {
+                flags |= ms_flag;
+            }
+        }
+        flags
+    }
+
+    /// Landlock: system paths read-only, devices, /tmp and writable mounts read-write
+    fn restrict_filesystem(spec: &NativeSpec) -> Result<()> {
+        let landlock_err = |e: landlock::RulesetError| Error::Execution(format!("landlock: {}", e));
+        let abi = ABI::V3;
+
+        let mut read_only: Vec<PathBuf> = SYSTEM_READ_PATHS.iter().map(PathBuf::from).collect();
+        let mut read_write: Vec<PathBuf> = DEVICE_PATHS.iter().map(PathBuf::from).collect();
+        read_write.push(PathBuf::from("/tmp"));
+        if spec.cwd.is_some() {
+            read_only.push(PathBuf::from("."));
+        }
+        for m in &spec.mounts {
+            if m.read_only {
+                read_only.push(PathBuf::from(&m.target));
+            } else {
+                read_write.push(PathBuf::from(&m.target));
+            }
+        }
+
+        let status = Ruleset::default()
+            .handle_access(AccessFs::from_all(abi))
+            .map_err(landlock_err)?
+            .create()
+            .map_err(landlock_err)?
+            .add_rules(path_beneath_rules(&read_only, AccessFs::from_read(abi)))
+            .map_err(landlock_err)?
+            .add_rules(path_beneath_rules(&read_write, AccessFs::from_all(abi)))
+            .map_err(landlock_err)?
+            .restrict_self()
+            .map_err(landlock_err)?;
+
+        match status.ruleset {
+            RulesetStatus::NotEnforced
+                if spec.policy == SandboxPolicy::Strict || !spec.allow_degraded =>
+            {
+                Err(Error::PermissionDenied(
+                    "this kernel lacks Landlock (set allow_degraded to run without it)".to_string(),
+                ))
+            }
+            RulesetStatus::NotEnforced => {
+                eprintln!("cratos sandbox: Landlock unavailable - filesystem is not restricted");
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
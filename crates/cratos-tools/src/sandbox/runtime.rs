@@ -11,6 +11,8 @@ pub enum ContainerRuntime {
     Docker,
     /// Apple Container - macOS 26+ native, VM-based isolation (stronger)
     AppleContainer,
+    /// Linux namespaces + Landlock + seccomp, no container runtime needed
+    LinuxNative,
     /// No container runtime available
     None,
 }
@@ -19,7 +21,7 @@ impl ContainerRuntime {
     /// Detect the best available container runtime
     ///
     /// On macOS with Apple Silicon, prefers Apple Container if available.
-    /// Falls back to Docker, then None.
+    /// Falls back to Docker, then the native Linux sandbox, then None.
     pub async fn detect() -> Self {
         // On macOS with Apple Silicon, try Apple Container first
         #[cfg(target_os = "macos")]
@@ -36,6 +38,12 @@ impl ContainerRuntime {
             return Self::Docker;
         }
 
+        // Try the native Linux sandbox
+        if Self::check_linux_native().await {
+            info!("Using native Linux sandbox (namespaces + Landlock + seccomp)");
+            return Self::LinuxNative;
+        }
+
         warn!("No container runtime available - sandboxing disabled");
         Self::None
     }
@@ -64,6 +72,17 @@ impl ContainerRuntime {
         }
     }
 
+    /// Check if the native Linux sandbox works on this host
+    pub async fn check_linux_native() -> bool {
+        super::native::probe().await
+    }
+
+    /// Check if the kernel allows the native Linux sandbox (without probing)
+    #[must_use]
+    pub fn linux_native_supported() -> bool {
+        super::native::kernel_supported()
+    }
+
     /// Check if running on Apple Silicon
     #[cfg(target_os = "macos")]
     fn is_apple_silicon() -> bool {
@@ -76,6 +95,7 @@ impl ContainerRuntime {
         match self {
             Self::Docker => "Docker",
             Self::AppleContainer => "Apple Container",
+            Self::LinuxNative => "Linux Native (namespaces + Landlock)",
             Self::None => "None (no isolation)",
         }
     }
@@ -0,0 +1,274 @@
+//! Docker-format seccomp profiles for the native Linux runtime
+//!
+//! The native runtime reuses the profile Docker consumes
+//! (`config/seccomp-default.json`). Rules are grouped by action and each group
+//! becomes its own BPF filter; the kernel evaluates every installed filter and
+//! applies the most restrictive result, which matches Docker's semantics for
+//! allow-list profiles.
+
+use crate::error::{Error, Result};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::path::Path;
+
+/// Default errno returned for `SCMP_ACT_ERRNO` without `errnoRet` (EPERM)
+const DEFAULT_ERRNO: u32 = 1;
+
+/// Docker/OCI seccomp profile
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeccompProfile {
+    /// Action for syscalls not matched by any rule
+    pub default_action: String,
+    /// Errno returned when `default_action` is `SCMP_ACT_ERRNO`
+    #[serde(default)]
+    pub default_errno_ret: Option<u32>,
+    /// Syscall rules
+    #[serde(default)]
+    pub syscalls: Vec<SyscallRule>,
+}
+
+/// A single syscall rule from the profile
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyscallRule {
+    /// Syscall names covered by this rule
+    #[serde(default)]
+    pub names: Vec<String>,
+    /// Legacy single-name form
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Action when the rule matches
+    pub action: String,
+    /// Errno returned when `action` is `SCMP_ACT_ERRNO`
+    #[serde(default)]
+    pub errno_ret: Option<u32>,
+    /// Argument conditions (all must match)
+    #[serde(default)]
+    pub args: Vec<SyscallArg>,
+    /// Only apply on these architectures / capabilities
+    #[serde(default)]
+    pub includes: RuleFilter,
+    /// Never apply on these architectures
+    #[serde(default)]
+    pub excludes: RuleFilter,
+}
+
+/// Architecture/capability filter on a rule
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RuleFilter {
+    /// libseccomp architecture names (e.g. `SCMP_ARCH_X86_64`)
+    #[serde(default)]
+    pub arches: Vec<String>,
+    /// Capability names (e.g. `CAP_SYS_ADMIN`)
+    #[serde(default)]
+    pub caps: Vec<String>,
+}
+
+/// Argument comparison on a rule
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyscallArg {
+    /// Argument index (0-5)
+    pub index: u8,
+    /// Comparison value (the mask for `SCMP_CMP_MASKED_EQ`)
+    pub value: u64,
+    /// Masked comparison value for `SCMP_CMP_MASKED_EQ`
+    #[serde(default)]
+    pub value_two: u64,
+    /// libseccomp comparison operator (e.g. `SCMP_CMP_EQ`)
+    pub op: String,
+}
+
+impl SeccompProfile {
+    /// Load a profile from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| Error::Config(format!("seccomp profile {}: {}", path.display(), e)))?;
+        Self::from_json(&data)
+    }
+
+    /// Parse a profile from JSON
+    pub fn from_json(data: &str) -> Result<Self> {
+        serde_json::from_str(data)
+            .map_err(|e| Error::Config(format!("invalid seccomp profile: {}", e)))
+    }
+
+    /// Convert to seccompiler's JSON filter format for the given architecture
+    ///
+    /// `arch` is a Rust target arch (`x86_64`, `aarch64`). Syscall names for
+    /// which `is_known` returns false (e.g. `open` on aarch64) are skipped.
+    pub fn to_seccompiler_json(
+        &self,
+        arch: &str,
+        is_known: impl Fn(&str) -> bool,
+    ) -> Result<Value> {
+        let default_action = action_json(&self.default_action, self.default_errno_ret)?;
+        let scmp_arch = scmp_arch_name(arch);
+
+        // Group rules by action, preserving profile order
+        let mut groups: Vec<(Value, Vec<Value>)> = Vec::new();
+        for rule in &self.syscalls {
+            if !rule.applies_to(&scmp_arch) {
+                continue;
+            }
+            let action = action_json(&rule.action, rule.errno_ret)?;
+            let conditions = rule
+                .args
+                .iter()
+                .map(condition_json)
+                .collect::<Result<Vec<_>>>()?;
+
+            let names = rule.names.iter().chain(rule.name.iter());
+            let entries: Vec<Value> = names
+                .filter(|name| is_known(name))
+                .map(|name| {
+                    if conditions.is_empty() {
+                        json!({ "syscall": name })
+                    } else {
+                        json!({ "syscall": name, "args": conditions })
+                    }
+                })
+                .collect();
+
+            match groups.iter_mut().find(|(a, _)| *a == action) {
+                Some((_, existing)) => existing.extend(entries),
+                None => groups.push((action, entries)),
+            }
+        }
+
+        let allow = json!("allow");
+        let mut filters = Map::new();
+        for (index, (action, rules)) in groups.into_iter().enumerate() {
+            if rules.is_empty() {
+                continue;
+            }
+            let (mismatch, matched) = if action == allow {
+                if default_action == allow {
+                    // Allow rules under an allow default are no-ops
+                    continue;
+                }
+                (default_action.clone(), allow.clone())
+            } else {
+                (allow.clone(), action)
+            };
+            filters.insert(
+                format!("group_{:03}", index),
+                json!({
+                    "mismatch_action": mismatch,
+                    "match_action": matched,
+                    "filter": rules,
+                }),
+            );
+        }
+
+        if filters.is_empty() && default_action != allow {
+            // Profile denies everything by default and allows nothing
+            filters.insert(
+                "default".to_string(),
+                json!({
+                    "mismatch_action": default_action,
+                    "match_action": allow,
+                    "filter": [],
+                }),
+            );
+        }
+
+        Ok(Value::Object(filters))
+    }
+
+    /// Compile the profile into BPF programs for the host architecture
+    ///
+    /// Programs are returned in profile order and must be installed in that
+    /// order: among equal actions (e.g. two `SCMP_ACT_ERRNO` values) the most
+    /// recently installed filter wins, so later rules override earlier ones.
+    #[cfg(target_os = "linux")]
+    pub fn compile(&self) -> Result<Vec<seccompiler::BpfProgram>> {
+        use seccompiler::TargetArch;
+
+        let arch_name = std::env::consts::ARCH;
+        let arch = TargetArch::try_from(arch_name)
+            .map_err(|e| Error::Config(format!("seccomp: {}", e)))?;
+
+        let filters = self.to_seccompiler_json(arch_name, |name| is_known_syscall(name, arch))?;
+        let programs = seccompiler::compile_from_json(filters.to_string().as_bytes(), arch)
+            .map_err(|e| Error::Config(format!("seccomp: {}", e)))?;
+
+        let mut programs: Vec<_> = programs.into_iter().collect();
+        programs.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(programs.into_iter().map(|(_, program)| program).collect())
+    }
+}
+
+impl SyscallRule {
+    /// Whether this rule applies on the given architecture
+    ///
+    /// Rules gated on capabilities never apply: sandboxed processes hold none.
+    fn applies_to(&self, scmp_arch: &str) -> bool {
+        if !self.includes.caps.is_empty() {
+            return false;
+        }
+        if !self.includes.arches.is_empty() && !self.includes.arches.iter().any(|a| a == scmp_arch)
+        {
+            return false;
+        }
+        !self.excludes.arches.iter().any(|a| a == scmp_arch)
+    }
+}
+
+/// Check whether seccompiler knows a syscall name on the given architecture
+#[cfg(target_os = "linux")]
+fn is_known_syscall(name: &str, arch: seccompiler::TargetArch) -> bool {
+    let probe = json!({
+        "probe": {
+            "mismatch_action": "allow",
+            "match_action": "log",
+            "filter": [{ "syscall": name }],
+        }
+    });
+    seccompiler::compile_from_json(probe.to_string().as_bytes(), arch).is_ok()
+}
+
+/// Map a Rust target arch to the libseccomp architecture name
+fn scmp_arch_name(arch: &str) -> String {
+    format!("SCMP_ARCH_{}", arch.to_uppercase())
+}
+
+/// Translate a libseccomp action name to seccompiler JSON
+fn action_json(action: &str, errno: Option<u32>) -> Result<Value> {
+    Ok(match action {
+        "SCMP_ACT_ALLOW" => json!("allow"),
+        "SCMP_ACT_ERRNO" => json!({ "errno": errno.unwrap_or(DEFAULT_ERRNO) }),
+        "SCMP_ACT_KILL" | "SCMP_ACT_KILL_THREAD" => json!("kill_thread"),
+        "SCMP_ACT_KILL_PROCESS" => json!("kill_process"),
+        "SCMP_ACT_TRAP" => json!("trap"),
+        "SCMP_ACT_LOG" => json!("log"),
+        "SCMP_ACT_TRACE" => json!({ "trace": errno.unwrap_or(0) }),
+        other => {
+            return Err(Error::Config(format!(
+                "unsupported seccomp action: {}",
+                other
+            )))
+        }
+    })
+}
+
+/// Translate a libseccomp argument comparison to seccompiler JSON
+fn condition_json(arg: &SyscallArg) -> Result<Value> {
+    let (op, val) = match arg.op.as_str() {
+        "SCMP_CMP_EQ" => (json!("eq"), arg.value),
+        "SCMP_CMP_NE" => (json!("ne"), arg.value),
+        "SCMP_CMP_LT" => (json!("lt"), arg.value),
+        "SCMP_CMP_LE" => (json!("le"), arg.value),
+        "SCMP_CMP_GT" => (json!("gt"), arg.value),
+        "SCMP_CMP_GE" => (json!("ge"), arg.value),
+        "SCMP_CMP_MASKED_EQ" => (json!({ "masked_eq": arg.value }), arg.value_two),
+        other => {
+            return Err(Error::Config(format!(
+                "unsupported seccomp comparison: {}",
+                other
+            )))
+        }
+    };
+    Ok(json!({ "index": arg.index, "type": "qword", "op": op, "val": val }))
+}
@@ -91,6 +91,10 @@ fn test_container_runtime_display_name() {
         ContainerRuntime::AppleContainer.display_name(),
         "Apple Container"
     );
+    assert_eq!(
+        ContainerRuntime::LinuxNative.display_name(),
+        "Linux Native (namespaces + Landlock)"
+    );
     assert_eq!(ContainerRuntime::None.display_name(), "None (no isolation)");
 }
 
@@ -98,6 +102,7 @@ fn test_container_runtime_display_name() {
 fn test_container_runtime_vm_isolation() {
     assert!(!ContainerRuntime::Docker.is_vm_isolated());
     assert!(ContainerRuntime::AppleContainer.is_vm_isolated());
+    assert!(!ContainerRuntime::LinuxNative.is_vm_isolated());
     assert!(!ContainerRuntime::None.is_vm_isolated());
 }
 
@@ -160,3 +165,104 @@ fn test_unified_sandbox_is_valid_env_name() {
     assert!(!docker::DockerSandbox::is_valid_env_name("123VAR"));
     assert!(!docker::DockerSandbox::is_valid_env_name("MY-VAR"));
 }
+
+#[test]
+fn test_unified_sandbox_linux_native_runtime() {
+    let sandbox =
+        UnifiedSandbox::with_runtime(SandboxConfig::default(), ContainerRuntime::LinuxNative);
+    assert_eq!(sandbox.runtime(), ContainerRuntime::LinuxNative);
+    assert!(sandbox.is_available());
+}
+
+#[test]
+fn test_native_spec_launcher_command() {
+    let config = SandboxConfig {
+        mounts: vec![Mount::read_only("/srv/data", "/srv/data")],
+        ..SandboxConfig::default()
+    };
+    let spec = NativeSpec::new(&config, vec!["ls".to_string(), "-la".to_string()])
+        .with_cwd("/srv/data")
+        .with_mounts(vec![Mount::read_write("/srv/out", "/out")])
+        .with_limits(ResourceLimits::default().with_timeout(Duration::from_secs(5)));
+
+    let (_exe, args) = spec.launcher_command().unwrap();
+    assert_eq!(args[0], LAUNCHER_ARG);
+
+    let decoded: NativeSpec = serde_json::from_str(&args[1]).unwrap();
+    assert_eq!(decoded.command, vec!["ls", "-la"]);
+    assert_eq!(
+        decoded.cwd.as_deref(),
+        Some(std::path::Path::new("/srv/data"))
+    );
+    assert_eq!(decoded.mounts.len(), 2);
+    assert!(!decoded.mounts[1].read_only);
+    assert_eq!(decoded.limits.timeout, Duration::from_secs(5));
+    assert_eq!(decoded.policy, SandboxPolicy::Moderate);
+    assert!(!decoded.allow_degraded);
+}
+
+#[test]
+fn test_native_spec_rejects_empty_command() {
+    let spec = NativeSpec::new(&SandboxConfig::default(), Vec::new());
+    assert!(spec.launcher_command().is_err());
+}
+
+#[test]
+fn test_seccomp_profile_groups_by_action() {
+    let profile = SeccompProfile::from_json(
+        r#"{
+            "defaultAction": "SCMP_ACT_ERRNO",
+            "syscalls": [
+                { "names": ["read", "write", "open"], "action": "SCMP_ACT_ALLOW" },
+                { "names": ["clone3"], "action": "SCMP_ACT_ERRNO", "errnoRet": 38 },
+                {
+                    "names": ["arch_prctl"],
+                    "action": "SCMP_ACT_ALLOW",
+                    "args": [{ "index": 0, "value": 4098, "op": "SCMP_CMP_EQ" }]
+                },
+                { "names": ["mount"], "action": "SCMP_ACT_ALLOW", "includes": { "caps": ["CAP_SYS_ADMIN"] } },
+                { "names": ["personality"], "action": "SCMP_ACT_ALLOW", "excludes": { "arches": ["SCMP_ARCH_X86_64"] } }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    // Pretend `open` does not exist on this architecture
+    let filters = profile
+        .to_seccompiler_json("x86_64", |name| name != "open")
+        .unwrap();
+    let filters = filters.as_object().unwrap();
+    assert_eq!(filters.len(), 2);
+
+    let allow = &filters["group_000"];
+    assert_eq!(allow["match_action"], "allow");
+    assert_eq!(allow["mismatch_action"]["errno"], 1);
+    let names: Vec<&str> = allow["filter"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["syscall"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["read", "write", "arch_prctl"]);
+    assert_eq!(allow["filter"][2]["args"][0]["val"], 4098);
+
+    let enosys = &filters["group_001"];
+    assert_eq!(enosys["mismatch_action"], "allow");
+    assert_eq!(enosys["match_action"]["errno"], 38);
+}
+
+#[test]
+fn test_seccomp_profile_rejects_unknown_action() {
+    let profile = SeccompProfile::from_json(r#"{ "defaultAction": "SCMP_ACT_BOGUS" }"#).unwrap();
+    assert!(profile.to_seccompiler_json("x86_64", |_| true).is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_bundled_seccomp_profile_compiles() {
+    let path =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../config/seccomp-default.json");
+    let programs = SeccompProfile::load(&path).unwrap().compile().unwrap();
+    assert_eq!(programs.len(), 2);
+    assert!(programs.iter().all(|p| !p.is_empty()));
+}
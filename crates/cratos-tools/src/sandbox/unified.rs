@@ -1,9 +1,10 @@
-//! Unified sandbox supporting Docker and Apple Container
+//! Unified sandbox supporting Docker, Apple Container and native Linux
 
 use super::config::SandboxConfig;
 use super::docker::DockerSandbox;
 use super::limits::ResourceLimits;
 use super::mount::Mount;
+use super::native::{NativeSpec, NATIVE_DEFAULT_PATH};
 use super::output::SandboxOutput;
 use super::policy::{NetworkMode, SandboxPolicy};
 use super::runtime::ContainerRuntime;
@@ -50,7 +51,8 @@ impl ToolSandbox {
 /// Unified sandbox that automatically selects the best available runtime
 ///
 /// On macOS 26+ with Apple Silicon, prefers Apple Container for stronger
-/// VM-based isolation. Falls back to Docker on other platforms.
+/// VM-based isolation. Falls back to Docker on other platforms, and to the
+/// native Linux sandbox on Linux hosts without Docker.
 pub struct UnifiedSandbox {
     config: SandboxConfig,
     runtime: ContainerRuntime,
@@ -111,6 +113,10 @@ impl UnifiedSandbox {
                 self.execute_docker(command, env, mounts, network, limits)
                     .await
             }
+            ContainerRuntime::LinuxNative => {
+                self.execute_linux_native(command, env, mounts, network, limits)
+                    .await
+            }
             ContainerRuntime::None => {
                 // No sandboxing available - execute natively with caution
                 warn!("No sandbox available - executing without isolation");
@@ -274,6 +280,64 @@ impl UnifiedSandbox {
         })
     }
 
+    /// Execute using the native Linux sandbox
+    ///
+    /// Runs host binaries (the configured image is not used) with the
+    /// environment cleared down to `env` plus a default `PATH`.
+    async fn execute_linux_native(
+        &self,
+        command: &[String],
+        env: HashMap<String, String>,
+        mounts: Vec<Mount>,
+        network: Option<NetworkMode>,
+        limits: Option<ResourceLimits>,
+    ) -> Result<SandboxOutput> {
+        let network = network.unwrap_or(self.config.default_network);
+        let limits = limits.unwrap_or_else(|| self.config.default_limits.clone());
+        let timeout = limits.timeout;
+
+        let spec = NativeSpec::new(&self.config, command.to_vec())
+            .with_mounts(mounts)
+            .with_network(network)
+            .with_limits(limits);
+
+        let mut cmd = tokio::process::Command::from(spec.to_command()?);
+        cmd.env_clear().env("PATH", NATIVE_DEFAULT_PATH);
+        for (key, value) in &env {
+            if Self::is_valid_env_name(key) {
+                cmd.env(key, value);
+            } else {
+                warn!(key = %key, "Skipping invalid environment variable name");
+            }
+        }
+        // Dropping the child on timeout kills the sandboxed process
+        cmd.kill_on_drop(true);
+
+        let output = tokio::time::timeout(timeout, cmd.output())
+            .await
+            .map_err(|_| Error::Timeout(timeout.as_millis() as u64))?
+            .map_err(|e| Error::Execution(format!("Native sandbox execution failed: {}", e)))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let exit_code = output.status.code().unwrap_or(-1);
+
+        info!(
+            exit_code = exit_code,
+            stdout_len = stdout.len(),
+            stderr_len = stderr.len(),
+            runtime = "linux_native",
+            "Sandbox execution completed"
+        );
+
+        Ok(SandboxOutput {
+            stdout,
+            stderr,
+            exit_code,
+            success: output.status.success(),
+        })
+    }
+
     /// Execute without sandbox (fallback when no runtime is available)
     async fn execute_native(
         &self,
@@ -286,6 +286,7 @@ mod tests {
         let auth_store = Arc::new(AuthStore::new(true));
         let (key, _) = auth_store
             .generate_api_key("test", vec![Scope::SessionRead], "test")
+            .await
             .unwrap();
         let event_bus = Arc::new(EventBus::new(16));
         let node_registry = Arc::new(NodeRegistry::new(pool));
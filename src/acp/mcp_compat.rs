@@ -1,14 +1,18 @@
 //! MCP (Model Context Protocol) JSON-RPC 2.0 compatibility layer.
 //!
 //! Wraps Cratos tools as MCP-compatible methods so that IDEs (VS Code, Zed)
-//! can connect via `cratos acp --mcp` using the standard MCP protocol.
+//! can connect via `cratos acp --mcp` using the standard MCP protocol, and
+//! other agents can connect over HTTP via `cratos serve --mcp-http`.
 //!
 //! Supported methods:
 //! - `initialize` → server capabilities
 //! - `tools/list` → tool definitions
 //! - `tools/call` → execute a tool
-//! - `prompts/list` → empty (reserved)
-//! - `resources/list` → empty (reserved)
+//! - `resources/list`, `resources/templates/list`, `resources/read` → tools,
+//!   explicit memories, execution history and skills (see [`catalog`])
+//! - `prompts/list`, `prompts/get` → personas and active skills
+
+mod catalog;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -16,9 +20,20 @@ use std::sync::Arc;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tracing::{debug, error, info};
 
-use cratos_core::orchestrator::Orchestrator;
+use cratos_core::auth::{AuthContext, AuthMethod, Scope};
+use cratos_core::orchestrator::{Orchestrator, OrchestratorInput};
+use cratos_core::pantheon::PersonaLoader;
+use cratos_memory::GraphMemory;
+use cratos_replay::EventStore;
+use cratos_skills::SkillStore;
 use cratos_tools::ToolRegistry;
 
+/// Protocol version answered when the client does not request a supported one
+const DEFAULT_PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Protocol versions this server understands
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-03-26", "2024-11-05"];
+
 /// MCP JSON-RPC 2.0 request.
 #[derive(Debug, Deserialize)]
 pub struct JsonRpcRequest {
@@ -50,7 +65,7 @@ pub struct JsonRpcError {
 }
 
 impl JsonRpcResponse {
-    fn ok(id: Option<Value>, result: Value) -> Self {
+    pub(crate) fn ok(id: Option<Value>, result: Value) -> Self {
         Self {
             jsonrpc: "2.0",
             id,
@@ -59,7 +74,7 @@ impl JsonRpcResponse {
         }
     }
 
-    fn err(id: Option<Value>, code: i32, message: impl Into<String>) -> Self {
+    pub(crate) fn err(id: Option<Value>, code: i32, message: impl Into<String>) -> Self {
         Self {
             jsonrpc: "2.0",
             id,
@@ -76,6 +91,8 @@ impl JsonRpcResponse {
 #[derive(Debug, Serialize)]
 struct ServerCapabilities {
     tools: ToolCapability,
+    resources: ResourceCapability,
+    prompts: ToolCapability,
 }
 
 #[derive(Debug, Serialize)]
@@ -84,6 +101,13 @@ struct ToolCapability {
     list_changed: bool,
 }
 
+#[derive(Debug, Serialize)]
+struct ResourceCapability {
+    subscribe: bool,
+    #[serde(rename = "listChanged")]
+    list_changed: bool,
+}
+
 /// MCP tool definition (subset of JSON Schema).
 #[derive(Debug, Serialize)]
 struct McpToolDef {
@@ -94,10 +118,14 @@ struct McpToolDef {
     input_schema: Value,
 }
 
-/// MCP-compatible bridge over stdin/stdout.
+/// MCP-compatible bridge (transport-agnostic request handling).
 pub struct McpBridge {
     tool_registry: Arc<ToolRegistry>,
     orchestrator: Arc<Orchestrator>,
+    event_store: Option<Arc<EventStore>>,
+    skill_store: Option<Arc<SkillStore>>,
+    graph_memory: Option<Arc<GraphMemory>>,
+    persona_loader: PersonaLoader,
 }
 
 impl McpBridge {
@@ -105,9 +133,31 @@ impl McpBridge {
         Self {
             tool_registry,
             orchestrator,
+            event_store: None,
+            skill_store: None,
+            graph_memory: None,
+            persona_loader: PersonaLoader::new(),
         }
     }
 
+    /// Expose execution history as resources.
+    pub fn with_event_store(mut self, store: Arc<EventStore>) -> Self {
+        self.event_store = Some(store);
+        self
+    }
+
+    /// Expose skills as resources and prompts.
+    pub fn with_skill_store(mut self, store: Arc<SkillStore>) -> Self {
+        self.skill_store = Some(store);
+        self
+    }
+
+    /// Expose Graph RAG explicit memories as resources.
+    pub fn with_graph_memory(mut self, memory: Arc<GraphMemory>) -> Self {
+        self.graph_memory = Some(memory);
+        self
+    }
+
     /// Run the MCP JSON-RPC loop over stdin/stdout.
     pub async fn run(&self) -> anyhow::Result<()> {
         info!("MCP compatibility bridge started (JSON-RPC 2.0 over stdio)");
@@ -146,13 +196,42 @@ impl McpBridge {
         Ok(())
     }
 
+    /// Handle a request from the local (trusted) stdio client.
     async fn handle_request(&self, req: JsonRpcRequest) -> JsonRpcResponse {
+        let local = AuthContext {
+            user_id: "local".to_string(),
+            method: AuthMethod::BearerToken,
+            scopes: vec![Scope::Admin],
+            session_id: None,
+            device_id: None,
+        };
+        self.handle_request_as(req, &local).await
+    }
+
+    /// Handle a request on behalf of an authenticated caller.
+    ///
+    /// Each method requires a scope; resources are further filtered by kind.
+    pub async fn handle_request_as(
+        &self,
+        req: JsonRpcRequest,
+        auth: &AuthContext,
+    ) -> JsonRpcResponse {
+        if let Some(scope) = required_scope(&req.method) {
+            if let Err(e) = auth.require_scope(&scope) {
+                return JsonRpcResponse::err(req.id, -32001, e.to_string());
+            }
+        }
+
         match req.method.as_str() {
-            "initialize" => self.handle_initialize(req.id),
+            "initialize" => self.handle_initialize(req.id, &req.params),
+            "ping" => JsonRpcResponse::ok(req.id, serde_json::json!({})),
             "tools/list" => self.handle_tools_list(req.id),
-            "tools/call" => self.handle_tools_call(req.id, req.params).await,
-            "prompts/list" => JsonRpcResponse::ok(req.id, serde_json::json!({ "prompts": [] })),
-            "resources/list" => JsonRpcResponse::ok(req.id, serde_json::json!({ "resources": [] })),
+            "tools/call" => self.handle_tools_call(req.id, req.params, auth).await,
+            "resources/list" => self.handle_resources_list(req.id, auth).await,
+            "resources/templates/list" => self.handle_resource_templates_list(req.id, auth),
+            "resources/read" => self.handle_resources_read(req.id, &req.params, auth).await,
+            "prompts/list" => self.handle_prompts_list(req.id).await,
+            "prompts/get" => self.handle_prompts_get(req.id, &req.params).await,
             "notifications/initialized" => {
                 // Client acknowledges initialization — no response needed for notifications,
                 // but since we're line-based, return ok
@@ -162,13 +241,24 @@ impl McpBridge {
         }
     }
 
-    fn handle_initialize(&self, id: Option<Value>) -> JsonRpcResponse {
+    fn handle_initialize(&self, id: Option<Value>, params: &Value) -> JsonRpcResponse {
+        let protocol_version = params
+            .get("protocolVersion")
+            .and_then(|v| v.as_str())
+            .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+            .unwrap_or(DEFAULT_PROTOCOL_VERSION);
+
         JsonRpcResponse::ok(
             id,
             serde_json::json!({
-                "protocolVersion": "2024-11-05",
+                "protocolVersion": protocol_version,
                 "capabilities": ServerCapabilities {
                     tools: ToolCapability { list_changed: false },
+                    resources: ResourceCapability {
+                        subscribe: false,
+                        list_changed: false,
+                    },
+                    prompts: ToolCapability { list_changed: false },
                 },
                 "serverInfo": {
                     "name": "cratos",
@@ -181,19 +271,24 @@ impl McpBridge {
     fn handle_tools_list(&self, id: Option<Value>) -> JsonRpcResponse {
         let tools: Vec<McpToolDef> = self
             .tool_registry
-            .list_definitions()
-            .iter()
+            .all_definitions()
+            .into_iter()
             .map(|def| McpToolDef {
-                name: def.name.clone(),
-                description: Some(def.description.clone()),
-                input_schema: def.parameters.clone(),
+                name: def.name,
+                description: Some(def.description),
+                input_schema: def.parameters,
             })
             .collect();
 
         JsonRpcResponse::ok(id, serde_json::json!({ "tools": tools }))
     }
 
-    async fn handle_tools_call(&self, id: Option<Value>, params: Value) -> JsonRpcResponse {
+    async fn handle_tools_call(
+        &self,
+        id: Option<Value>,
+        params: Value,
+        auth: &AuthContext,
+    ) -> JsonRpcResponse {
         let name = match params.get("name").and_then(|v| v.as_str()) {
             Some(n) => n.to_string(),
             None => return JsonRpcResponse::err(id, -32602, "Missing 'name' parameter"),
@@ -204,7 +299,14 @@ impl McpBridge {
             .cloned()
             .unwrap_or(serde_json::json!({}));
 
-        match self.orchestrator.runner().execute(&name, arguments).await {
+        // Same security policy and approval rules as tool calls made by the
+        // model; memory tools act within the caller's memory
+        let input = OrchestratorInput::new("mcp", &auth.user_id, &auth.user_id, "");
+        let result = self
+            .orchestrator
+            .execute_tool_direct(&input, &name, arguments, &auth.memory_scope())
+            .await;
+        match result {
             Ok(exec_result) => {
                 let content = if exec_result.result.success {
                     serde_json::json!([{
@@ -243,6 +345,20 @@ impl McpBridge {
     }
 }
 
+/// Scope required to call an MCP method (`None` for handshake methods).
+fn required_scope(method: &str) -> Option<Scope> {
+    match method {
+        "tools/call" => Some(Scope::ExecutionWrite),
+        "tools/list"
+        | "resources/list"
+        | "resources/templates/list"
+        | "resources/read"
+        | "prompts/list"
+        | "prompts/get" => Some(Scope::ExecutionRead),
+        _ => None,
+    }
+}
+
 /// Run the MCP-compatible bridge as a standalone process.
 pub async fn run_mcp() -> anyhow::Result<()> {
     // Minimal setup for standalone MCP mode
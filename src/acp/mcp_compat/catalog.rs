@@ -0,0 +1,483 @@
+//! MCP resources and prompts backed by Cratos data.
+//!
+//! Resources use `cratos://<kind>/<id>` URIs:
+//! - `cratos://tools/{name}` → tool definition (JSON)
+//! - `cratos://memories/{name}` → Graph RAG explicit memory (Markdown)
+//! - `cratos://executions/{id}` → execution record (JSON)
+//! - `cratos://skills/{name}` → skill definition (JSON)
+//!
+//! Prompts are generated from personas (`persona_<name>`) and active skills
+//! (`skill_<name>`).
+
+use serde_json::{json, Value};
+use tracing::warn;
+use uuid::Uuid;
+
+use cratos_core::auth::{AuthContext, Scope};
+use cratos_core::pantheon::PersonaPreset;
+use cratos_skills::Skill;
+
+use super::{JsonRpcResponse, McpBridge};
+
+/// URI scheme prefix for Cratos resources
+const URI_SCHEME: &str = "cratos://";
+
+/// Maximum number of entries listed per resource kind
+const MAX_LISTED: u32 = 100;
+
+/// MCP error code for an unknown resource
+const RESOURCE_NOT_FOUND: i32 = -32002;
+
+/// Prompt name prefix for personas
+const PERSONA_PREFIX: &str = "persona_";
+
+/// Prompt name prefix for skills
+const SKILL_PREFIX: &str = "skill_";
+
+/// Kind of data exposed as an MCP resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResourceKind {
+    Tool,
+    Memory,
+    Execution,
+    Skill,
+}
+
+impl ResourceKind {
+    const ALL: [Self; 4] = [Self::Tool, Self::Memory, Self::Execution, Self::Skill];
+
+    /// URI path segment
+    fn segment(self) -> &'static str {
+        match self {
+            Self::Tool => "tools",
+            Self::Memory => "memories",
+            Self::Execution => "executions",
+            Self::Skill => "skills",
+        }
+    }
+
+    /// Scope required to list or read resources of this kind
+    fn scope(self) -> Scope {
+        match self {
+            Self::Memory => Scope::SessionRead,
+            Self::Tool | Self::Execution | Self::Skill => Scope::ExecutionRead,
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            Self::Memory => "text/markdown",
+            Self::Tool | Self::Execution | Self::Skill => "application/json",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Tool => "Cratos tool definition",
+            Self::Memory => "Explicit memory from the Graph RAG store",
+            Self::Execution => "Execution record (input, output, status)",
+            Self::Skill => "Cratos skill definition",
+        }
+    }
+
+    fn uri(self, id: &str) -> String {
+        format!("{}{}/{}", URI_SCHEME, self.segment(), id)
+    }
+}
+
+/// Split a `cratos://<kind>/<id>` URI
+fn parse_resource_uri(uri: &str) -> Option<(ResourceKind, &str)> {
+    let (segment, id) = uri.strip_prefix(URI_SCHEME)?.split_once('/')?;
+    let kind = ResourceKind::ALL
+        .into_iter()
+        .find(|k| k.segment() == segment)?;
+    (!id.is_empty()).then_some((kind, id))
+}
+
+/// Admins see every execution; other callers only their own
+fn can_read_execution(auth: &AuthContext, owner: &str) -> bool {
+    auth.scopes.contains(&Scope::Admin) || auth.user_id == owner
+}
+
+fn resource_entry(kind: ResourceKind, id: &str, name: &str, description: &str) -> Value {
+    json!({
+        "uri": kind.uri(id),
+        "name": name,
+        "description": description,
+        "mimeType": kind.mime_type(),
+    })
+}
+
+fn prompt_argument(name: &str, description: &str) -> Value {
+    json!({ "name": name, "description": description, "required": false })
+}
+
+/// String argument passed to `prompts/get`
+fn prompt_argument_value<'a>(params: &'a Value, key: &str) -> Option<&'a str> {
+    params
+        .get("arguments")
+        .and_then(|args| args.get(key))
+        .and_then(|v| v.as_str())
+}
+
+/// Render a skill as instructions for another agent
+fn skill_prompt_text(skill: &Skill, input: Option<&str>) -> String {
+    let mut text = format!(
+        "Run the \"{}\" skill: {}\n\nSteps:",
+        skill.name, skill.description
+    );
+    let mut steps: Vec<_> = skill.steps.iter().collect();
+    steps.sort_by_key(|s| s.order);
+    for step in steps {
+        text.push_str(&format!("\n{}. `{}`", step.order, step.tool_name));
+        if let Some(desc) = &step.description {
+            text.push_str(&format!(" — {}", desc));
+        }
+    }
+    if let Some(input) = input.filter(|i| !i.is_empty()) {
+        text.push_str(&format!("\n\nInput: {}", input));
+    }
+    text
+}
+
+/// Render a persona as a prompt, optionally followed by a task
+fn persona_prompt_text(preset: &PersonaPreset, user_name: &str, task: Option<&str>) -> String {
+    let mut text = preset.to_system_prompt(user_name);
+    if let Some(task) = task.filter(|t| !t.is_empty()) {
+        text.push_str(&format!("\n\n## Task\n{}", task));
+    }
+    text
+}
+
+impl McpBridge {
+    pub(super) async fn handle_resources_list(
+        &self,
+        id: Option<Value>,
+        auth: &AuthContext,
+    ) -> JsonRpcResponse {
+        let mut resources = Vec::new();
+
+        if auth.has_scope(&ResourceKind::Tool.scope()) {
+            let mut defs = self.tool_registry.all_definitions();
+            defs.sort_by(|a, b| a.name.cmp(&b.name));
+            resources.extend(defs.iter().map(|def| {
+                resource_entry(ResourceKind::Tool, &def.name, &def.name, &def.description)
+            }));
+        }
+
+        if let (Some(memory), true) = (
+            &self.graph_memory,
+            auth.has_scope(&ResourceKind::Memory.scope()),
+        ) {
+            match memory
+                .list_memories(&auth.memory_scope(), None, MAX_LISTED)
+                .await
+            {
+                Ok(memories) => {
+                    resources.extend(memories.iter().map(|m| {
+                        resource_entry(ResourceKind::Memory, &m.name, &m.name, &m.category)
+                    }))
+                }
+                Err(e) => warn!(error = %e, "MCP: failed to list explicit memories"),
+            }
+        }
+
+        if let (Some(store), true) = (
+            &self.event_store,
+            auth.has_scope(&ResourceKind::Execution.scope()),
+        ) {
+            let executions = if auth.scopes.contains(&Scope::Admin) {
+                store.list_recent_executions(MAX_LISTED.into()).await
+            } else {
+                store
+                    .list_executions_by_user(&auth.user_id, MAX_LISTED.into(), 0)
+                    .await
+            };
+            match executions {
+                Ok(executions) => resources.extend(executions.iter().map(|e| {
+                    let id = e.id.to_string();
+                    let summary: String = e.input_text.chars().take(80).collect();
+                    resource_entry(
+                        ResourceKind::Execution,
+                        &id,
+                        &summary,
+                        &e.status.to_string(),
+                    )
+                })),
+                Err(e) => warn!(error = %e, "MCP: failed to list executions"),
+            }
+        }
+
+        if let (Some(store), true) = (
+            &self.skill_store,
+            auth.has_scope(&ResourceKind::Skill.scope()),
+        ) {
+            match store.list_skills().await {
+                Ok(skills) => resources.extend(skills.iter().map(|s| {
+                    resource_entry(ResourceKind::Skill, &s.name, &s.name, &s.description)
+                })),
+                Err(e) => warn!(error = %e, "MCP: failed to list skills"),
+            }
+        }
+
+        JsonRpcResponse::ok(id, json!({ "resources": resources }))
+    }
+
+    pub(super) fn handle_resource_templates_list(
+        &self,
+        id: Option<Value>,
+        auth: &AuthContext,
+    ) -> JsonRpcResponse {
+        let templates: Vec<Value> = ResourceKind::ALL
+            .into_iter()
+            .filter(|kind| auth.has_scope(&kind.scope()))
+            .filter(|kind| match kind {
+                ResourceKind::Tool => true,
+                ResourceKind::Memory => self.graph_memory.is_some(),
+                ResourceKind::Execution => self.event_store.is_some(),
+                ResourceKind::Skill => self.skill_store.is_some(),
+            })
+            .map(|kind| {
+                let placeholder = match kind {
+                    ResourceKind::Execution => "{id}",
+                    _ => "{name}",
+                };
+                json!({
+                    "uriTemplate": kind.uri(placeholder),
+                    "name": kind.segment(),
+                    "description": kind.description(),
+                    "mimeType": kind.mime_type(),
+                })
+            })
+            .collect();
+
+        JsonRpcResponse::ok(id, json!({ "resourceTemplates": templates }))
+    }
+
+    pub(super) async fn handle_resources_read(
+        &self,
+        id: Option<Value>,
+        params: &Value,
+        auth: &AuthContext,
+    ) -> JsonRpcResponse {
+        let Some(uri) = params.get("uri").and_then(|v| v.as_str()) else {
+            return JsonRpcResponse::err(id, -32602, "Missing 'uri' parameter");
+        };
+        let Some((kind, key)) = parse_resource_uri(uri) else {
+            return JsonRpcResponse::err(
+                id,
+                RESOURCE_NOT_FOUND,
+                format!("Unknown resource: {}", uri),
+            );
+        };
+        if let Err(e) = auth.require_scope(&kind.scope()) {
+            return JsonRpcResponse::err(id, -32001, e.to_string());
+        }
+
+        match self.read_resource(kind, key, auth).await {
+            Ok(Some(text)) => JsonRpcResponse::ok(
+                id,
+                json!({
+                    "contents": [{
+                        "uri": uri,
+                        "mimeType": kind.mime_type(),
+                        "text": text,
+                    }]
+                }),
+            ),
+            Ok(None) => JsonRpcResponse::err(
+                id,
+                RESOURCE_NOT_FOUND,
+                format!("Resource not found: {}", uri),
+            ),
+            Err(e) => JsonRpcResponse::err(id, -32603, e),
+        }
+    }
+
+    /// Read a resource as text; `Ok(None)` when it does not exist or is not visible
+    async fn read_resource(
+        &self,
+        kind: ResourceKind,
+        key: &str,
+        auth: &AuthContext,
+    ) -> Result<Option<String>, String> {
+        match kind {
+            ResourceKind::Tool => Ok(self
+                .tool_registry
+                .all_definitions()
+                .into_iter()
+                .find(|def| def.name == key)
+                .map(|def| {
+                    json!({
+                        "name": def.name,
+                        "description": def.description,
+                        "inputSchema": def.parameters,
+                    })
+                    .to_string()
+                })),
+            ResourceKind::Memory => {
+                let Some(memory) = &self.graph_memory else {
+                    return Ok(None);
+                };
+                let mem = memory
+                    .get_memory(&auth.memory_scope(), key)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(mem.map(|m| m.content))
+            }
+            ResourceKind::Execution => {
+                let Some(store) = &self.event_store else {
+                    return Ok(None);
+                };
+                let Ok(execution_id) = Uuid::parse_str(key) else {
+                    return Ok(None);
+                };
+                // Missing executions surface as errors from the store
+                let Ok(execution) = store.get_execution(execution_id).await else {
+                    return Ok(None);
+                };
+                if !can_read_execution(auth, &execution.user_id) {
+                    return Ok(None);
+                }
+                serde_json::to_string(&execution)
+                    .map(Some)
+                    .map_err(|e| e.to_string())
+            }
+            ResourceKind::Skill => {
+                let Some(store) = &self.skill_store else {
+                    return Ok(None);
+                };
+                let skill = store
+                    .get_skill_by_name(key)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                skill
+                    .map(|s| serde_json::to_string(&s))
+                    .transpose()
+                    .map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    pub(super) async fn handle_prompts_list(&self, id: Option<Value>) -> JsonRpcResponse {
+        let mut prompts = Vec::new();
+
+        match self.persona_loader.load_all() {
+            Ok(presets) => prompts.extend(presets.iter().map(|preset| {
+                json!({
+                    "name": format!("{}{}", PERSONA_PREFIX, preset.persona.name.to_lowercase()),
+                    "description": format!("{} — {}", preset.persona.title, preset.persona.domain),
+                    "arguments": [
+                        prompt_argument("task", "Task to hand to the persona"),
+                        prompt_argument("user_name", "Name the persona addresses"),
+                    ],
+                })
+            })),
+            Err(e) => warn!(error = %e, "MCP: failed to load personas"),
+        }
+
+        if let Some(store) = &self.skill_store {
+            match store.list_active_skills().await {
+                Ok(skills) => prompts.extend(skills.iter().map(|skill| {
+                    json!({
+                        "name": format!("{}{}", SKILL_PREFIX, skill.name),
+                        "description": skill.description,
+                        "arguments": [prompt_argument("input", "Input for the skill")],
+                    })
+                })),
+                Err(e) => warn!(error = %e, "MCP: failed to list skills"),
+            }
+        }
+
+        JsonRpcResponse::ok(id, json!({ "prompts": prompts }))
+    }
+
+    pub(super) async fn handle_prompts_get(
+        &self,
+        id: Option<Value>,
+        params: &Value,
+    ) -> JsonRpcResponse {
+        let Some(name) = params.get("name").and_then(|v| v.as_str()) else {
+            return JsonRpcResponse::err(id, -32602, "Missing 'name' parameter");
+        };
+        let rendered = if let Some(persona) = name.strip_prefix(PERSONA_PREFIX) {
+            // Match against loaded personas rather than building a file path from input
+            let presets = self.persona_loader.load_all().unwrap_or_default();
+            presets
+                .into_iter()
+                .find(|p| p.persona.name.eq_ignore_ascii_case(persona))
+                .map(|preset| {
+                    let user_name = prompt_argument_value(params, "user_name").unwrap_or("User");
+                    let task = prompt_argument_value(params, "task");
+                    (
+                        preset.persona.title.clone(),
+                        persona_prompt_text(&preset, user_name, task),
+                    )
+                })
+        } else if let (Some(skill), Some(store)) =
+            (name.strip_prefix(SKILL_PREFIX), &self.skill_store)
+        {
+            match store.get_skill_by_name(skill).await {
+                Ok(skill) => skill.map(|s| {
+                    let text = skill_prompt_text(&s, prompt_argument_value(params, "input"));
+                    (s.description, text)
+                }),
+                Err(e) => return JsonRpcResponse::err(id, -32603, e.to_string()),
+            }
+        } else {
+            None
+        };
+
+        match rendered {
+            Some((description, text)) => JsonRpcResponse::ok(
+                id,
+                json!({
+                    "description": description,
+                    "messages": [{
+                        "role": "user",
+                        "content": { "type": "text", "text": text },
+                    }],
+                }),
+            ),
+            None => JsonRpcResponse::err(id, -32602, format!("Unknown prompt: {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cratos_skills::{SkillCategory, SkillStep};
+
+    #[test]
+    fn test_parse_resource_uri() {
+        assert_eq!(
+            parse_resource_uri("cratos://memories/deploy-notes"),
+            Some((ResourceKind::Memory, "deploy-notes"))
+        );
+        assert_eq!(
+            parse_resource_uri("cratos://tools/mcp_fs/read"),
+            Some((ResourceKind::Tool, "mcp_fs/read"))
+        );
+        assert_eq!(parse_resource_uri("cratos://skills/"), None);
+        assert_eq!(parse_resource_uri("cratos://unknown/x"), None);
+        assert_eq!(parse_resource_uri("file:///etc/passwd"), None);
+        assert_eq!(
+            ResourceKind::Execution.uri("abc"),
+            "cratos://executions/abc"
+        );
+    }
+
+    #[test]
+    fn test_skill_prompt_text_orders_steps() {
+        let mut skill = Skill::new("deploy", "Ship the app", SkillCategory::Workflow);
+        skill.steps = vec![
+            SkillStep::new(2, "exec", json!({})),
+            SkillStep::new(1, "git_status", json!({})),
+        ];
+        let text = skill_prompt_text(&skill, Some("staging"));
+        assert!(text.starts_with("Run the \"deploy\" skill: Ship the app"));
+        assert!(text.find("1. `git_status`").unwrap() < text.find("2. `exec`").unwrap());
+        assert!(text.ends_with("Input: staging"));
+    }
+}
@@ -0,0 +1,383 @@
+//! MCP Streamable HTTP transport for the MCP bridge.
+//!
+//! Mounted at `/mcp` by `cratos serve --mcp-http` so other agents can use
+//! Cratos as an MCP server over the network. Every request is authenticated
+//! against the `AuthStore` (Bearer token or `X-API-Key`) and each MCP method
+//! is checked against the caller's scopes.
+//!
+//! - `POST /mcp` → JSON-RPC request, notification or batch (JSON response)
+//! - `GET /mcp` → 405 (no server-initiated stream)
+//! - `DELETE /mcp` → terminate the session named by `Mcp-Session-Id`
+//!
+//! Sessions expire after [`SESSION_IDLE_TTL`] without requests; beyond
+//! [`MAX_SESSIONS`] the least recently used session is dropped.
+
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Extension, Json, Router,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+use super::mcp_compat::{JsonRpcRequest, JsonRpcResponse, McpBridge};
+use crate::middleware::auth::RequireAuthStrict;
+
+/// Session header defined by the Streamable HTTP transport
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Sessions without a request for this long are expired
+const SESSION_IDLE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Most sessions kept at once
+const MAX_SESSIONS: usize = 1024;
+
+/// An MCP HTTP session
+struct McpSession {
+    /// Owning user ID
+    user_id: String,
+    /// Time of the latest request
+    last_seen: Instant,
+}
+
+/// Shared state for the HTTP transport
+pub struct McpHttpState {
+    bridge: McpBridge,
+    /// Active sessions by ID
+    sessions: RwLock<HashMap<String, McpSession>>,
+}
+
+impl McpHttpState {
+    pub fn new(bridge: McpBridge) -> Self {
+        Self {
+            bridge,
+            sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Start a session for `user_id`, dropping expired and excess sessions
+    async fn create_session(&self, user_id: &str, now: Instant) -> String {
+        let mut sessions = self.sessions.write().await;
+        sessions.retain(|_, session| now.duration_since(session.last_seen) < SESSION_IDLE_TTL);
+        while sessions.len() >= MAX_SESSIONS {
+            let Some(oldest) = sessions
+                .iter()
+                .min_by_key(|(_, session)| session.last_seen)
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            sessions.remove(&oldest);
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        sessions.insert(
+            id.clone(),
+            McpSession {
+                user_id: user_id.to_string(),
+                last_seen: now,
+            },
+        );
+        id
+    }
+
+    /// Whether `id` is a live session of `user_id`, marking it as used
+    async fn touch_session(&self, id: &str, user_id: &str, now: Instant) -> bool {
+        let mut sessions = self.sessions.write().await;
+        match sessions.get_mut(id) {
+            Some(session) if now.duration_since(session.last_seen) >= SESSION_IDLE_TTL => {
+                sessions.remove(id);
+                false
+            }
+            Some(session) if session.user_id == user_id => {
+                session.last_seen = now;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Build the `/mcp` router
+pub fn mcp_http_router(bridge: McpBridge) -> Router {
+    let state = Arc::new(McpHttpState::new(bridge));
+    Router::new()
+        .route(
+            "/mcp",
+            post(handle_post).get(handle_get).delete(handle_delete),
+        )
+        .layer(Extension(state))
+}
+
+fn session_id(headers: &HeaderMap) -> Option<&str> {
+    headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok())
+}
+
+fn error_response(status: StatusCode, code: i32, message: &str) -> Response {
+    (status, Json(JsonRpcResponse::err(None, code, message))).into_response()
+}
+
+async fn handle_post(
+    RequireAuthStrict(auth): RequireAuthStrict,
+    Extension(state): Extension<Arc<McpHttpState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let body: Value = match serde_json::from_slice(&body) {
+        Ok(v) => v,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                -32700,
+                &format!("Parse error: {}", e),
+            )
+        }
+    };
+    let (messages, is_batch) = match body {
+        Value::Array(items) => (items, true),
+        single => (vec![single], false),
+    };
+
+    let is_initialize = messages
+        .iter()
+        .any(|m| m.get("method").and_then(|v| v.as_str()) == Some("initialize"));
+
+    // Sessions are created by `initialize` and required on every other request
+    let new_session = if is_initialize {
+        let id = state.create_session(&auth.user_id, Instant::now()).await;
+        info!(user = %auth.user_id, session = %id, "MCP HTTP session started");
+        Some(id)
+    } else {
+        match session_id(&headers) {
+            Some(id) => {
+                if !state.touch_session(id, &auth.user_id, Instant::now()).await {
+                    return error_response(StatusCode::NOT_FOUND, -32001, "Session not found");
+                }
+                None
+            }
+            None => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    -32600,
+                    "Missing Mcp-Session-Id header",
+                )
+            }
+        }
+    };
+
+    let mut responses = Vec::new();
+    for message in messages {
+        // Client responses and notifications carry no request to answer
+        let Some(method) = message.get("method").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        debug!(method = %method, user = %auth.user_id, "MCP HTTP request");
+        let response = match serde_json::from_value::<JsonRpcRequest>(message) {
+            Ok(req) if req.id.is_none() => continue,
+            Ok(req) => state.bridge.handle_request_as(req, &auth).await,
+            Err(e) => JsonRpcResponse::err(None, -32600, format!("Invalid request: {}", e)),
+        };
+        responses.push(response);
+    }
+
+    let mut response = if responses.is_empty() {
+        StatusCode::ACCEPTED.into_response()
+    } else if is_batch {
+        Json(responses).into_response()
+    } else {
+        Json(responses.remove(0)).into_response()
+    };
+
+    if let Some(id) = new_session {
+        if let Ok(value) = HeaderValue::from_str(&id) {
+            response.headers_mut().insert(SESSION_HEADER, value);
+        }
+    }
+    response
+}
+
+async fn handle_get(RequireAuthStrict(_auth): RequireAuthStrict) -> StatusCode {
+    StatusCode::METHOD_NOT_ALLOWED
+}
+
+async fn handle_delete(
+    RequireAuthStrict(auth): RequireAuthStrict,
+    Extension(state): Extension<Arc<McpHttpState>>,
+    headers: HeaderMap,
+) -> StatusCode {
+    let Some(id) = session_id(&headers) else {
+        return StatusCode::BAD_REQUEST;
+    };
+    let mut sessions = state.sessions.write().await;
+    if sessions.get(id).map(|session| session.user_id.as_str()) != Some(auth.user_id.as_str()) {
+        return StatusCode::NOT_FOUND;
+    }
+    sessions.remove(id);
+    info!(user = %auth.user_id, session = %id, "MCP HTTP session terminated");
+    StatusCode::OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cratos_core::auth::{AuthContext, AuthMethod, Scope};
+    use cratos_core::orchestrator::Orchestrator;
+    use cratos_tools::ToolRegistry;
+
+    fn test_state() -> Arc<McpHttpState> {
+        let provider: Arc<dyn cratos_llm::LlmProvider> = Arc::new(cratos_llm::MockProvider::new());
+        let registry = Arc::new(ToolRegistry::new());
+        let orchestrator = Arc::new(Orchestrator::new(
+            provider,
+            registry.clone(),
+            Default::default(),
+        ));
+        Arc::new(McpHttpState::new(McpBridge::new(registry, orchestrator)))
+    }
+
+    fn test_auth(user_id: &str, scopes: Vec<Scope>) -> RequireAuthStrict {
+        RequireAuthStrict(AuthContext {
+            user_id: user_id.to_string(),
+            method: AuthMethod::ApiKey,
+            scopes,
+            session_id: None,
+            device_id: None,
+        })
+    }
+
+    async fn post(
+        state: &Arc<McpHttpState>,
+        auth: RequireAuthStrict,
+        body: &str,
+        session: Option<&str>,
+    ) -> Response {
+        let mut headers = HeaderMap::new();
+        if let Some(id) = session {
+            headers.insert(SESSION_HEADER, HeaderValue::from_str(id).unwrap());
+        }
+        handle_post(
+            auth,
+            Extension(state.clone()),
+            headers,
+            Bytes::from(body.to_string()),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_initialize_creates_session() {
+        let state = test_state();
+        let admin = || test_auth("alice", vec![Scope::Admin]);
+
+        let resp = post(
+            &state,
+            admin(),
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26"}}"#,
+            None,
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let session = resp.headers()[SESSION_HEADER].to_str().unwrap().to_string();
+
+        let resp = post(
+            &state,
+            admin(),
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            Some(&session),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        let resp = post(
+            &state,
+            admin(),
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+            Some(&session),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Sessions belong to the user that created them
+        let resp = post(
+            &state,
+            test_auth("bob", vec![Scope::Admin]),
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/list"}"#,
+            Some(&session),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(SESSION_HEADER, HeaderValue::from_str(&session).unwrap());
+        let status = handle_delete(admin(), Extension(state.clone()), headers).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(state.sessions.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_requests_require_session() {
+        let state = test_state();
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#;
+
+        let resp = post(&state, test_auth("alice", vec![Scope::Admin]), body, None).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = post(
+            &state,
+            test_auth("alice", vec![Scope::Admin]),
+            body,
+            Some("unknown"),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_expire_and_are_capped() {
+        let state = test_state();
+        let start = Instant::now();
+        let first = state.create_session("alice", start).await;
+        let second = state
+            .create_session("alice", start + Duration::from_secs(1))
+            .await;
+        for _ in 2..MAX_SESSIONS {
+            state
+                .create_session("alice", start + Duration::from_secs(2))
+                .await;
+        }
+        let later = start + Duration::from_secs(3);
+        assert!(state.touch_session(&first, "alice", later).await);
+        assert!(!state.touch_session(&first, "bob", later).await);
+
+        // The least recently used session makes room for a new one
+        assert_eq!(state.sessions.read().await.len(), MAX_SESSIONS);
+        state.create_session("bob", later).await;
+        assert_eq!(state.sessions.read().await.len(), MAX_SESSIONS);
+        assert!(!state.touch_session(&second, "alice", later).await);
+        assert!(state.touch_session(&first, "alice", later).await);
+
+        // Idle sessions expire
+        let expired = later + SESSION_IDLE_TTL;
+        assert!(!state.touch_session(&first, "alice", expired).await);
+        assert!(!state.sessions.read().await.contains_key(&first));
+    }
+
+    #[tokio::test]
+    async fn test_scope_enforced_per_method() {
+        let state = test_state();
+        let auth = test_auth("reader", vec![Scope::ExecutionRead]);
+        let req: JsonRpcRequest = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"exec"}}"#,
+        )
+        .unwrap();
+
+        let resp = state.bridge.handle_request_as(req, &auth.0).await;
+        assert_eq!(resp.error.unwrap().code, -32001);
+    }
+}
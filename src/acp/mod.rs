@@ -4,7 +4,10 @@
 //! for IDE integration (VS Code, Zed, etc.).
 //!
 //! Usage: `cratos acp [--token <token>]`
+//!
+//! The MCP bridge is also served over HTTP by `cratos serve --mcp-http`.
 
 pub mod bridge;
 pub mod mcp_compat;
+pub mod mcp_http;
 pub mod protocol;
@@ -0,0 +1,246 @@
+//! API key management endpoints (Admin only)
+//!
+//! - `GET /api/v1/auth/keys` — List API keys
+//! - `POST /api/v1/auth/keys` — Create an API key
+//! - `DELETE /api/v1/auth/keys/:id` — Revoke an API key
+//! - `POST /api/v1/auth/keys/:id/rotate` — Rotate an API key
+//!
+//! `:id` is the key hash, or any unique prefix of it.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Path,
+    routing::{delete, get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use cratos_core::{default_user_scopes, ApiKeyInfo, AuthStore, Scope};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::config::ApiResponse;
+use crate::middleware::auth::{require_scope, AuthRejection, RequireAuth};
+
+/// Default time the old key keeps working after a rotation
+const DEFAULT_GRACE_HOURS: u32 = 24;
+
+/// API key view for API responses (never contains the key itself)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiKeyView {
+    /// Key hash, used to revoke or rotate the key
+    pub id: String,
+    pub user_id: String,
+    pub label: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked: bool,
+}
+
+impl From<ApiKeyInfo> for ApiKeyView {
+    fn from(info: ApiKeyInfo) -> Self {
+        Self {
+            id: info.key_hash,
+            user_id: info.user_id,
+            label: info.label,
+            scopes: info.scopes.iter().map(ToString::to_string).collect(),
+            created_at: info.created_at,
+            expires_at: info.expires_at,
+            last_used_at: info.last_used_at,
+            revoked: info.revoked,
+        }
+    }
+}
+
+/// Newly created (or rotated) API key
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedApiKey {
+    /// The raw key; it is not shown again
+    pub key: String,
+    pub info: ApiKeyView,
+}
+
+/// Body for creating an API key
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// Owner of the key
+    pub user_id: String,
+    /// Human-readable label
+    #[serde(default)]
+    pub label: String,
+    /// Granted scopes (default: every user scope except admin and node management)
+    #[serde(default)]
+    #[schema(value_type = Option<Vec<String>>)]
+    pub scopes: Option<Vec<Scope>>,
+    /// Days until the key expires (omit for a key that never expires)
+    #[serde(default)]
+    pub expires_in_days: Option<u32>,
+}
+
+/// Body for rotating an API key
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct RotateApiKeyRequest {
+    /// Hours the old key keeps working (default: 24)
+    #[serde(default)]
+    pub grace_hours: Option<u32>,
+}
+
+/// List API keys (requires Admin scope)
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/keys",
+    tag = "auth",
+    responses(
+        (status = 200, description = "API keys", body = Vec<ApiKeyView>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing Admin scope")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn list_keys(
+    RequireAuth(auth): RequireAuth,
+    Extension(store): Extension<Arc<AuthStore>>,
+) -> Result<Json<ApiResponse<Vec<ApiKeyView>>>, AuthRejection> {
+    require_scope(&auth, &Scope::Admin)?;
+    let keys = store.list_keys()?;
+    Ok(Json(ApiResponse::success(
+        keys.into_iter().map(ApiKeyView::from).collect(),
+    )))
+}
+
+/// Create an API key (requires Admin scope)
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/keys",
+    tag = "auth",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 200, description = "Created API key", body = CreatedApiKey),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing Admin scope")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn create_key(
+    RequireAuth(auth): RequireAuth,
+    Extension(store): Extension<Arc<AuthStore>>,
+    Json(body): Json<CreateApiKeyRequest>,
+) -> Result<Json<ApiResponse<CreatedApiKey>>, AuthRejection> {
+    require_scope(&auth, &Scope::Admin)?;
+    if body.user_id.trim().is_empty() {
+        return Ok(Json(ApiResponse::error("user_id is required")));
+    }
+
+    let scopes = body.scopes.unwrap_or_else(default_user_scopes);
+    let expires_at = body
+        .expires_in_days
+        .map(|days| Utc::now() + chrono::Duration::days(i64::from(days)));
+    let (key, info) = store
+        .create_api_key(&body.user_id, scopes, &body.label, expires_at)
+        .await?;
+    Ok(Json(ApiResponse::success(CreatedApiKey {
+        key: key.expose().to_string(),
+        info: info.into(),
+    })))
+}
+
+/// Revoke an API key (requires Admin scope)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/keys/{id}",
+    tag = "auth",
+    params(("id" = String, Path, description = "Key hash or a unique prefix of it")),
+    responses(
+        (status = 200, description = "Key revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing Admin scope")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn revoke_key(
+    RequireAuth(auth): RequireAuth,
+    Extension(store): Extension<Arc<AuthStore>>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<()>>, AuthRejection> {
+    require_scope(&auth, &Scope::Admin)?;
+    let Some(hash) = store.find_key(&id) else {
+        return Ok(Json(ApiResponse::error("API key not found")));
+    };
+    store.revoke_key(&hash).await?;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Rotate an API key (requires Admin scope)
+///
+/// Issues a new key with the same owner, scopes and label; the old key keeps
+/// working for the grace period.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/keys/{id}/rotate",
+    tag = "auth",
+    params(("id" = String, Path, description = "Key hash or a unique prefix of it")),
+    request_body = RotateApiKeyRequest,
+    responses(
+        (status = 200, description = "Replacement API key", body = CreatedApiKey),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing Admin scope")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn rotate_key(
+    RequireAuth(auth): RequireAuth,
+    Extension(store): Extension<Arc<AuthStore>>,
+    Path(id): Path<String>,
+    body: Option<Json<RotateApiKeyRequest>>,
+) -> Result<Json<ApiResponse<CreatedApiKey>>, AuthRejection> {
+    require_scope(&auth, &Scope::Admin)?;
+    let Some(hash) = store.find_key(&id) else {
+        return Ok(Json(ApiResponse::error("API key not found")));
+    };
+
+    let grace_hours = body
+        .and_then(|Json(b)| b.grace_hours)
+        .unwrap_or(DEFAULT_GRACE_HOURS);
+    let (key, info) = match store
+        .rotate_key(&hash, chrono::Duration::hours(i64::from(grace_hours)))
+        .await
+    {
+        Ok(rotated) => rotated,
+        Err(e) => return Ok(Json(ApiResponse::error(e.to_string()))),
+    };
+    Ok(Json(ApiResponse::success(CreatedApiKey {
+        key: key.expose().to_string(),
+        info: info.into(),
+    })))
+}
+
+/// Create API key management routes
+pub fn api_keys_routes() -> Router {
+    Router::new()
+        .route("/api/v1/auth/keys", get(list_keys).post(create_key))
+        .route("/api/v1/auth/keys/:id", delete(revoke_key))
+        .route("/api/v1/auth/keys/:id/rotate", post(rotate_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_parses_scopes() {
+        let body: CreateApiKeyRequest = serde_json::from_value(serde_json::json!({
+            "user_id": "ci",
+            "scopes": ["execution_read", "scheduler_write"],
+            "expires_in_days": 30
+        }))
+        .unwrap();
+        assert_eq!(
+            body.scopes,
+            Some(vec![Scope::ExecutionRead, Scope::SchedulerWrite])
+        );
+        assert_eq!(body.expires_in_days, Some(30));
+        assert!(body.label.is_empty());
+    }
+}
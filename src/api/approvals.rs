@@ -0,0 +1,278 @@
+//! Approval REST API endpoints
+//!
+//! Lets web and mobile clients answer approval requests without holding a
+//! WebSocket connection.
+//!
+//! - `GET /api/v1/approvals` — List pending approvals the caller may answer (all for Admin)
+//! - `GET /api/v1/approvals/:id` — Get a single approval request
+//! - `POST /api/v1/approvals/:id/approve` — Approve a request
+//! - `POST /api/v1/approvals/:id/reject` — Reject a request
+
+use axum::{
+    extract::Path,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use cratos_core::{ApprovalError, ApprovalRequest, ApprovalStatus, Scope, SharedApprovalManager};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::config::ApiResponse;
+use crate::middleware::auth::{require_scope, AuthRejection, RequireAuth};
+
+/// Approval request view for API responses
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApprovalView {
+    pub id: Uuid,
+    /// Must be echoed back when approving or rejecting
+    pub nonce: Uuid,
+    pub execution_id: Uuid,
+    pub channel_type: String,
+    pub user_id: String,
+    pub action: String,
+    pub tool_name: Option<String>,
+    pub tool_args: Option<serde_json::Value>,
+    pub risk_description: String,
+    pub status: String,
+    pub responder_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+impl From<&ApprovalRequest> for ApprovalView {
+    fn from(r: &ApprovalRequest) -> Self {
+        Self {
+            id: r.id,
+            nonce: r.nonce,
+            execution_id: r.execution_id,
+            channel_type: r.channel_type.clone(),
+            user_id: r.user_id.clone(),
+            action: r.action.clone(),
+            tool_name: r.tool_name.clone(),
+            tool_args: r.tool_args.clone(),
+            risk_description: r.risk_description.clone(),
+            status: r.status.as_str().to_string(),
+            responder_id: r.responder_id.clone(),
+            created_at: r.created_at,
+            expires_at: r.expires_at,
+            responded_at: r.responded_at,
+        }
+    }
+}
+
+/// Body for approve/reject
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ApprovalDecisionRequest {
+    /// Nonce from the approval request (replay defense)
+    pub nonce: Uuid,
+}
+
+/// List pending approval requests (requires ApprovalRespond scope)
+#[utoipa::path(
+    get,
+    path = "/api/v1/approvals",
+    tag = "approvals",
+    responses(
+        (status = 200, description = "Pending approval requests", body = Vec<ApprovalView>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing ApprovalRespond scope")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn list_approvals(
+    RequireAuth(auth): RequireAuth,
+    manager: Option<Extension<SharedApprovalManager>>,
+) -> Result<Json<ApiResponse<Vec<ApprovalView>>>, AuthRejection> {
+    require_scope(&auth, &Scope::ApprovalRespond)?;
+    let Some(Extension(manager)) = manager else {
+        return Ok(Json(ApiResponse::success(Vec::new())));
+    };
+
+    let pending = if auth.has_scope(&Scope::Admin) {
+        manager.pending_all().await
+    } else {
+        manager.pending_for_approver(&auth.user_id).await
+    };
+    Ok(Json(ApiResponse::success(
+        pending.iter().map(ApprovalView::from).collect(),
+    )))
+}
+
+/// Get an approval request (requires ApprovalRespond scope)
+#[utoipa::path(
+    get,
+    path = "/api/v1/approvals/{id}",
+    tag = "approvals",
+    params(("id" = Uuid, Path, description = "Approval request ID")),
+    responses(
+        (status = 200, description = "Approval request", body = ApprovalView),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing ApprovalRespond scope")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn get_approval(
+    RequireAuth(auth): RequireAuth,
+    manager: Option<Extension<SharedApprovalManager>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ApprovalView>>, AuthRejection> {
+    require_scope(&auth, &Scope::ApprovalRespond)?;
+    let Some(Extension(manager)) = manager else {
+        return Ok(Json(ApiResponse::error("Approval manager not configured")));
+    };
+
+    match manager.get(id).await {
+        Some(request)
+            if request.user_id == auth.user_id
+                || request.can_respond(&auth.user_id)
+                || auth.has_scope(&Scope::Admin) =>
+        {
+            Ok(Json(ApiResponse::success(ApprovalView::from(&request))))
+        }
+        _ => Ok(Json(ApiResponse::error(
+            ApprovalError::NotFound.to_string(),
+        ))),
+    }
+}
+
+/// Approve a pending request (requires ApprovalRespond scope)
+#[utoipa::path(
+    post,
+    path = "/api/v1/approvals/{id}/approve",
+    tag = "approvals",
+    params(("id" = Uuid, Path, description = "Approval request ID")),
+    request_body = ApprovalDecisionRequest,
+    responses(
+        (status = 200, description = "Resolved approval request", body = ApprovalView),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing ApprovalRespond scope")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn approve(
+    RequireAuth(auth): RequireAuth,
+    manager: Option<Extension<SharedApprovalManager>>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ApprovalDecisionRequest>,
+) -> Result<Json<ApiResponse<ApprovalView>>, AuthRejection> {
+    decide(auth, manager, id, body.nonce, ApprovalStatus::Approved).await
+}
+
+/// Reject a pending request (requires ApprovalRespond scope)
+#[utoipa::path(
+    post,
+    path = "/api/v1/approvals/{id}/reject",
+    tag = "approvals",
+    params(("id" = Uuid, Path, description = "Approval request ID")),
+    request_body = ApprovalDecisionRequest,
+    responses(
+        (status = 200, description = "Resolved approval request", body = ApprovalView),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing ApprovalRespond scope")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn reject(
+    RequireAuth(auth): RequireAuth,
+    manager: Option<Extension<SharedApprovalManager>>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ApprovalDecisionRequest>,
+) -> Result<Json<ApiResponse<ApprovalView>>, AuthRejection> {
+    decide(auth, manager, id, body.nonce, ApprovalStatus::Rejected).await
+}
+
+async fn decide(
+    auth: cratos_core::AuthContext,
+    manager: Option<Extension<SharedApprovalManager>>,
+    id: Uuid,
+    nonce: Uuid,
+    decision: ApprovalStatus,
+) -> Result<Json<ApiResponse<ApprovalView>>, AuthRejection> {
+    require_scope(&auth, &Scope::ApprovalRespond)?;
+    let Some(Extension(manager)) = manager else {
+        return Ok(Json(ApiResponse::error("Approval manager not configured")));
+    };
+
+    match manager.resolve(id, nonce, decision, &auth).await {
+        Ok(request) => Ok(Json(ApiResponse::success(ApprovalView::from(&request)))),
+        Err(e) => Ok(Json(ApiResponse::error(e.to_string()))),
+    }
+}
+
+/// Create approval routes
+pub fn approvals_routes() -> Router {
+    Router::new()
+        .route("/api/v1/approvals", get(list_approvals))
+        .route("/api/v1/approvals/:id", get(get_approval))
+        .route("/api/v1/approvals/:id/approve", post(approve))
+        .route("/api/v1/approvals/:id/reject", post(reject))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approval_view_exposes_nonce_and_status() {
+        let request =
+            ApprovalRequest::new(Uuid::new_v4(), "web", "c1", "u1", "Run tool", "risky", 60)
+                .with_tool("exec", serde_json::json!({"command": "ls"}));
+        let view = ApprovalView::from(&request);
+        assert_eq!(view.nonce, request.nonce);
+        assert_eq!(view.status, "pending");
+        assert_eq!(view.tool_name.as_deref(), Some("exec"));
+    }
+
+    #[tokio::test]
+    async fn test_designated_approver_can_list_get_and_approve() {
+        use cratos_core::tool_policy::{ApprovalPolicy, ApprovalRule, ApproverSpec};
+        use cratos_core::{ApprovalManager, AuthMethod};
+        use std::sync::Arc;
+
+        let manager: SharedApprovalManager = Arc::new(ApprovalManager::new().with_policy(
+            ApprovalPolicy::new().with_rule(ApprovalRule::new("exec").with_approvers(
+                ApproverSpec::Users {
+                    users: vec!["alice".to_string()],
+                },
+            )),
+        ));
+        let request = ApprovalRequest::new(Uuid::new_v4(), "web", "c1", "dev", "exec", "r", 60)
+            .with_tool("exec", serde_json::json!({"command": "ls"}));
+        let (request, _rx) = manager.submit(request, None).await;
+
+        let alice = || {
+            RequireAuth(cratos_core::AuthContext {
+                user_id: "alice".to_string(),
+                method: AuthMethod::ApiKey,
+                scopes: vec![Scope::ApprovalRespond],
+                session_id: None,
+                device_id: None,
+            })
+        };
+
+        let listed = list_approvals(alice(), Some(Extension(manager.clone())))
+            .await
+            .unwrap_or_else(|_| panic!("list rejected"));
+        let listed = listed.0.data.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, request.id);
+
+        let fetched = get_approval(alice(), Some(Extension(manager.clone())), Path(request.id))
+            .await
+            .unwrap_or_else(|_| panic!("get rejected"));
+        let nonce = fetched.0.data.unwrap().nonce;
+
+        let approved = approve(
+            alice(),
+            Some(Extension(manager.clone())),
+            Path(request.id),
+            Json(ApprovalDecisionRequest { nonce }),
+        )
+        .await
+        .unwrap_or_else(|_| panic!("approve rejected"));
+        assert_eq!(approved.0.data.unwrap().status, "approved");
+    }
+}
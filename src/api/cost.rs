@@ -0,0 +1,124 @@
+//! LLM cost API endpoint
+//!
+//! GET /api/v1/cost — summarizes the spending recorded in the cost ledger.
+//!
+//! Users without the Admin scope only see their own spending.
+
+use axum::{extract::Query, routing::get, Json, Router};
+use chrono::{DateTime, Utc};
+use cratos_core::Scope;
+use cratos_llm::{global_tracker, CostGroup, CostQuery, CostSummaryRow};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::config::ApiResponse;
+use crate::middleware::auth::{require_scope, AuthRejection, RequireAuth};
+
+/// Query parameters for the cost summary
+#[derive(Debug, Default, Deserialize, IntoParams, ToSchema)]
+pub struct CostSummaryQuery {
+    /// Only usage at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only usage before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Group by day, provider (default), model, user, persona or channel
+    pub group_by: Option<String>,
+    /// Only usage of this user (Admin only; others always see their own)
+    pub user_id: Option<String>,
+    /// Only usage of this provider
+    pub provider: Option<String>,
+}
+
+/// One group of the cost summary
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CostRow {
+    /// Group key (empty when the usage has no value for the dimension)
+    pub key: String,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl From<CostSummaryRow> for CostRow {
+    fn from(row: CostSummaryRow) -> Self {
+        Self {
+            key: row.key,
+            requests: row.requests,
+            input_tokens: row.input_tokens,
+            output_tokens: row.output_tokens,
+            cost_usd: row.cost_usd,
+        }
+    }
+}
+
+/// Response for GET /api/v1/cost
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CostSummary {
+    pub group_by: String,
+    pub rows: Vec<CostRow>,
+    pub total_cost_usd: f64,
+}
+
+/// Summarize LLM spending (requires ExecutionRead scope)
+#[utoipa::path(
+    get,
+    path = "/api/v1/cost",
+    tag = "cost",
+    params(CostSummaryQuery),
+    responses(
+        (status = 200, description = "Cost summary", body = CostSummary),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing ExecutionRead scope")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn get_cost(
+    RequireAuth(auth): RequireAuth,
+    Query(query): Query<CostSummaryQuery>,
+) -> Result<Json<ApiResponse<CostSummary>>, AuthRejection> {
+    require_scope(&auth, &Scope::ExecutionRead)?;
+
+    let group_by = match query.group_by.as_deref().map(str::parse::<CostGroup>) {
+        None => CostGroup::default(),
+        Some(Ok(group)) => group,
+        Some(Err(e)) => return Ok(Json(ApiResponse::error(e))),
+    };
+    let user_id = if auth.has_scope(&Scope::Admin) {
+        query.user_id
+    } else {
+        Some(auth.user_id.clone())
+    };
+
+    let Some(ledger) = global_tracker().ledger().await else {
+        return Ok(Json(ApiResponse::error("Cost ledger not initialized")));
+    };
+    let cost_query = CostQuery {
+        since: query.since,
+        until: query.until,
+        group_by,
+        user_id,
+        provider: query.provider,
+    };
+    let rows = match ledger.summarize(&cost_query).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            return Ok(Json(ApiResponse::error(format!(
+                "Failed to summarize costs: {}",
+                e
+            ))))
+        }
+    };
+
+    let total_cost_usd = rows.iter().map(|r| r.cost_usd).sum();
+    Ok(Json(ApiResponse::success(CostSummary {
+        group_by: group_by.to_string(),
+        rows: rows.into_iter().map(CostRow::from).collect(),
+        total_cost_usd,
+    })))
+}
+
+/// Create the cost routes.
+pub fn cost_routes() -> Router {
+    Router::new().route("/api/v1/cost", get(get_cost))
+}
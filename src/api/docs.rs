@@ -7,12 +7,24 @@ use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use super::{
+    api_keys::{ApiKeyView, CreateApiKeyRequest, CreatedApiKey, RotateApiKeyRequest},
+    approvals::{ApprovalDecisionRequest, ApprovalView},
     config::{ApiResponse, AppConfigView, ChannelsView, ConfigUpdateRequest},
-    executions::{EventSummary, ExecutionDetail, ExecutionSummary, ListExecutionsQuery},
+    cost::{CostRow, CostSummary, CostSummaryQuery},
+    executions::{
+        EventSummary, ExecutionDetail, ExecutionSummary, ForkExecutionRequest, ListExecutionsQuery,
+        ReplayRequest,
+    },
     graph::{GraphData, GraphEdge, GraphNode, GraphQuery, GraphStats},
+    openai::types::{
+        AssistantMessage, ChatCompletion, ChatCompletionChunk, ChatCompletionRequest, Choice,
+        ChunkChoice, Delta, ErrorDetail, ErrorResponse, ModelInfo, ModelList, StreamOptions, Usage,
+    },
     pantheon::PersonaSummary,
     quota::{ProviderQuota, QuotaNumbers, QuotaResponse, TodaySummary},
-    scheduler::{CreateTaskRequest, TaskView, UpdateTaskRequest},
+    scheduler::{
+        CreateTaskRequest, ExecutionView, RunNowResponse, RunPreview, TaskView, UpdateTaskRequest,
+    },
     skills::SkillInfo,
     tools::ToolInfo,
 };
@@ -31,10 +43,13 @@ Cratos provides a comprehensive API for:
 - **Tools**: List and manage available AI tools
 - **Executions**: View and replay execution history
 - **Scheduler**: Schedule automated tasks
+- **API keys**: Create, rotate and revoke API keys
 - **Quota**: Monitor API usage and rate limits
+- **Cost**: Summarize LLM spending and budgets
 - **Personas**: Manage AI personas (Olympus OS)
 - **Graph**: Access knowledge graph data
 - **Skills**: Manage auto-generated skills
+- **OpenAI compatibility**: Chat completions for OpenAI SDK clients
 
 ## Authentication
 Most endpoints require authentication via API key in the `Authorization` header:
@@ -64,6 +79,8 @@ Authorization: Bearer <api_key>
         crate::api::executions::handlers::list_executions,
         crate::api::executions::handlers::get_execution,
         crate::api::executions::handlers::get_replay_events,
+        crate::api::executions::handlers::replay_execution,
+        crate::api::executions::handlers::fork_execution,
         crate::api::executions::handlers::rerun_execution,
         // Scheduler
         crate::api::scheduler::handlers::list_tasks,
@@ -71,8 +88,23 @@ Authorization: Bearer <api_key>
         crate::api::scheduler::handlers::get_task,
         crate::api::scheduler::handlers::update_task,
         crate::api::scheduler::handlers::delete_task,
+        crate::api::scheduler::handlers::preview_task,
+        crate::api::scheduler::handlers::list_executions,
+        crate::api::scheduler::handlers::run_task,
+        // Approvals
+        crate::api::approvals::list_approvals,
+        crate::api::approvals::get_approval,
+        crate::api::approvals::approve,
+        crate::api::approvals::reject,
+        // API keys
+        crate::api::api_keys::list_keys,
+        crate::api::api_keys::create_key,
+        crate::api::api_keys::revoke_key,
+        crate::api::api_keys::rotate_key,
         // Quota
         crate::api::quota::get_quota,
+        // Cost
+        crate::api::cost::get_cost,
         // Pantheon
         crate::api::pantheon::list_personas,
         crate::api::pantheon::get_persona,
@@ -82,6 +114,9 @@ Authorization: Bearer <api_key>
         // Skills
         crate::api::skills::list_skills,
         crate::api::skills::get_skill,
+        // OpenAI compatibility
+        crate::api::openai::handlers::chat_completions,
+        crate::api::openai::handlers::list_models,
     ),
     components(
         schemas(
@@ -97,15 +132,32 @@ Authorization: Bearer <api_key>
             ExecutionSummary,
             ExecutionDetail,
             EventSummary,
+            ReplayRequest,
+            ForkExecutionRequest,
             // Scheduler
             TaskView,
             CreateTaskRequest,
             UpdateTaskRequest,
+            RunPreview,
+            ExecutionView,
+            RunNowResponse,
+            // Approvals
+            ApprovalView,
+            ApprovalDecisionRequest,
+            // API keys
+            ApiKeyView,
+            CreatedApiKey,
+            CreateApiKeyRequest,
+            RotateApiKeyRequest,
             // Quota
             QuotaResponse,
             ProviderQuota,
             QuotaNumbers,
             TodaySummary,
+            // Cost
+            CostSummaryQuery,
+            CostSummary,
+            CostRow,
             // Pantheon
             PersonaSummary,
             // Graph
@@ -116,6 +168,20 @@ Authorization: Bearer <api_key>
             GraphStats,
             // Skills
             SkillInfo,
+            // OpenAI compatibility
+            ChatCompletionRequest,
+            StreamOptions,
+            ChatCompletion,
+            Choice,
+            AssistantMessage,
+            ChatCompletionChunk,
+            ChunkChoice,
+            Delta,
+            Usage,
+            ModelList,
+            ModelInfo,
+            ErrorResponse,
+            ErrorDetail,
         )
     ),
     tags(
@@ -123,10 +189,14 @@ Authorization: Bearer <api_key>
         (name = "tools", description = "Tool registry operations"),
         (name = "executions", description = "Execution history and replay"),
         (name = "scheduler", description = "Task scheduling"),
+        (name = "approvals", description = "Pending approval requests"),
+        (name = "auth", description = "API key management"),
         (name = "quota", description = "API usage and rate limits"),
+        (name = "cost", description = "LLM spending from the cost ledger"),
         (name = "pantheon", description = "Persona management (Olympus OS)"),
         (name = "graph", description = "Knowledge graph data"),
         (name = "skills", description = "Auto-generated skill management"),
+        (name = "openai", description = "OpenAI-compatible chat completions"),
     )
 )]
 pub struct ApiDoc;
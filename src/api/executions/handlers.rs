@@ -8,13 +8,16 @@ use chrono::Utc;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use cratos_core::auth::Scope;
+use cratos_core::orchestrator::Orchestrator;
 use cratos_replay::{EventStore, ExecutionViewer, ReplayOptions};
 
 use super::super::config::ApiResponse;
 use super::types::{
-    EventSummary, ExecutionDetail, ExecutionStats, ExecutionSummary, ListExecutionsQuery,
+    EventSummary, ExecutionDetail, ExecutionStats, ExecutionSummary, ForkExecutionRequest,
+    ListExecutionsQuery, ReplayRequest, ReplayResponse,
 };
-use crate::middleware::auth::RequireAuth;
+use crate::middleware::auth::{require_scope, RequireAuth};
 
 /// List recent executions (requires authentication)
 #[utoipa::path(
@@ -149,6 +152,7 @@ pub async fn get_execution(
         })
         .collect();
 
+    let forked_from = execution.forked_from().map(|(parent, _)| parent);
     let detail = ExecutionDetail {
         id: execution.id,
         channel_type: execution.channel_type,
@@ -160,6 +164,7 @@ pub async fn get_execution(
         status: execution.status.to_string(),
         created_at: execution.created_at,
         completed_at: execution.completed_at,
+        forked_from,
         events: event_summaries,
     };
 
@@ -254,6 +259,154 @@ pub async fn rerun_execution(
     }
 }
 
+/// Replay an execution from its recorded events (requires ExecutionWrite scope)
+///
+/// The replay is stored as a new execution and compared with the original.
+#[utoipa::path(
+    post,
+    path = "/api/v1/executions/{id}/replay",
+    tag = "executions",
+    params(
+        ("id" = Uuid, Path, description = "Execution ID")
+    ),
+    request_body = ReplayRequest,
+    responses(
+        (status = 200, description = "Replay run and comparison with the original"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing ExecutionWrite scope"),
+        (status = 404, description = "Execution not found"),
+        (status = 422, description = "Execution was not recorded in enough detail to replay")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn replay_execution(
+    RequireAuth(auth): RequireAuth,
+    Extension(store): Extension<Arc<EventStore>>,
+    Extension(orchestrator): Extension<Arc<Orchestrator>>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ReplayRequest>,
+) -> impl IntoResponse {
+    if let Err(rejection) = require_scope(&auth, &Scope::ExecutionWrite) {
+        return rejection.into_response();
+    }
+
+    // Only admins may replay other users' executions
+    match store.get_execution(id).await {
+        Ok(execution) if execution.user_id == auth.user_id || auth.has_scope(&Scope::Admin) => {}
+        _ => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<()>::error(format!(
+                    "Execution not found: {}",
+                    id
+                ))),
+            )
+                .into_response();
+        }
+    }
+
+    let run = match orchestrator.replay_execution(id, request.mode).await {
+        Ok(run) => run,
+        Err(e) => {
+            let status = match e {
+                cratos_core::Error::Replay(cratos_replay::Error::NotReplayable(_)) => {
+                    StatusCode::UNPROCESSABLE_ENTITY
+                }
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            return (
+                status,
+                Json(ApiResponse::<()>::error(format!(
+                    "Failed to replay execution: {}",
+                    e
+                ))),
+            )
+                .into_response();
+        }
+    };
+
+    let viewer = ExecutionViewer::new((*store).clone());
+    match viewer.compare_executions(id, run.replay_execution_id).await {
+        Ok(comparison) => {
+            Json(ApiResponse::success(ReplayResponse { run, comparison })).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to compare replay: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Fork an execution at an event and continue it as a new execution (requires authentication)
+#[utoipa::path(
+    post,
+    path = "/api/v1/executions/{id}/fork",
+    tag = "executions",
+    params(
+        ("id" = Uuid, Path, description = "Execution ID")
+    ),
+    request_body = ForkExecutionRequest,
+    responses(
+        (status = 200, description = "Fork outcome"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Execution or event not found"),
+        (status = 422, description = "Edit does not apply to the event, or the execution was not recorded in enough detail")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn fork_execution(
+    RequireAuth(auth): RequireAuth,
+    Extension(store): Extension<Arc<EventStore>>,
+    Extension(orchestrator): Extension<Arc<Orchestrator>>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ForkExecutionRequest>,
+) -> impl IntoResponse {
+    if let Err(rejection) = require_scope(&auth, &Scope::ExecutionWrite) {
+        return rejection.into_response();
+    }
+
+    // Only admins may fork other users' executions
+    match store.get_execution(id).await {
+        Ok(execution) if execution.user_id == auth.user_id || auth.has_scope(&Scope::Admin) => {}
+        _ => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<()>::error(format!(
+                    "Execution not found: {}",
+                    id
+                ))),
+            )
+                .into_response();
+        }
+    }
+
+    match orchestrator.fork_execution(id, request.into()).await {
+        Ok(run) => Json(ApiResponse::success(run)).into_response(),
+        Err(e) => {
+            let status = match e {
+                cratos_core::Error::NotFound(_) => StatusCode::NOT_FOUND,
+                cratos_core::Error::InvalidState(_)
+                | cratos_core::Error::Replay(cratos_replay::Error::NotReplayable(_)) => {
+                    StatusCode::UNPROCESSABLE_ENTITY
+                }
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (
+                status,
+                Json(ApiResponse::<()>::error(format!(
+                    "Failed to fork execution: {}",
+                    e
+                ))),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// Get execution statistics for traffic analysis (requires authentication)
 #[utoipa::path(
     get,
@@ -2,6 +2,8 @@
 //!
 //! GET /api/v1/executions - List recent executions
 //! GET /api/v1/executions/:id - Get execution details
+//! POST /api/v1/executions/:id/replay - Replay from recorded LLM/tool responses
+//! POST /api/v1/executions/:id/fork - Fork at an event and continue as a new execution
 
 pub mod handlers;
 pub mod types;
@@ -10,9 +12,13 @@ pub mod types;
 mod tests;
 
 pub use handlers::{
-    get_execution, get_execution_stats, get_replay_events, list_executions, rerun_execution,
+    fork_execution, get_execution, get_execution_stats, get_replay_events, list_executions,
+    replay_execution, rerun_execution,
+};
+pub use types::{
+    EventSummary, ExecutionDetail, ExecutionSummary, ForkExecutionRequest, ListExecutionsQuery,
+    ReplayRequest,
 };
-pub use types::{EventSummary, ExecutionDetail, ExecutionSummary, ListExecutionsQuery};
 
 use axum::{
     routing::{get, post},
@@ -24,7 +30,11 @@ pub fn executions_routes() -> Router {
     Router::new()
         .route("/api/v1/executions", get(list_executions))
         .route("/api/v1/executions/:id", get(get_execution))
-        .route("/api/v1/executions/:id/replay", get(get_replay_events))
+        .route(
+            "/api/v1/executions/:id/replay",
+            get(get_replay_events).post(replay_execution),
+        )
+        .route("/api/v1/executions/:id/fork", post(fork_execution))
         .route("/api/v1/executions/:id/rerun", post(rerun_execution))
         .route("/api/v1/executions/stats", get(get_execution_stats))
 }
@@ -1,5 +1,6 @@
 use super::types::{
-    default_limit_inner, EventSummary, ExecutionDetail, ExecutionSummary, ListExecutionsQuery,
+    default_limit_inner, EventSummary, ExecutionDetail, ExecutionSummary, ForkExecutionRequest,
+    ListExecutionsQuery, ReplayRequest,
 };
 use chrono::Utc;
 use uuid::Uuid;
@@ -35,6 +36,7 @@ fn test_execution_detail_serialization() {
         status: "completed".to_string(),
         created_at: Utc::now(),
         completed_at: Some(Utc::now()),
+        forked_from: None,
         events: vec![EventSummary {
             id: Uuid::nil(),
             sequence_num: 1,
@@ -80,3 +82,26 @@ fn test_replay_result_serialization() {
     let json = serde_json::to_string(&result).unwrap();
     assert!(json.contains("\"dry_run\":true"));
 }
+
+#[test]
+fn test_replay_request_deserialization() {
+    let request: ReplayRequest = serde_json::from_str("{}").unwrap();
+    assert_eq!(request.mode, cratos_replay::ReplayMode::Cassette);
+
+    let request: ReplayRequest = serde_json::from_str(r#"{"mode": "live_llm"}"#).unwrap();
+    assert_eq!(request.mode, cratos_replay::ReplayMode::LiveLlm);
+}
+
+#[test]
+fn test_fork_request_deserialization() {
+    let request: ForkExecutionRequest =
+        serde_json::from_str(r#"{"sequence_num": 4, "tool_result": {"stdout": "ok"}}"#).unwrap();
+    assert_eq!(request.sequence_num, 4);
+    assert!(request.user_message.is_none());
+
+    let request: cratos_core::orchestrator::ForkRequest = request.into();
+    assert_eq!(
+        request.tool_result,
+        Some(serde_json::json!({"stdout": "ok"}))
+    );
+}
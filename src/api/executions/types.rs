@@ -1,4 +1,6 @@
 use chrono::{DateTime, Utc};
+use cratos_core::orchestrator::{ForkRequest, ReplayRun};
+use cratos_replay::{viewer::ExecutionComparison, ReplayMode};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
@@ -50,6 +52,8 @@ pub struct ExecutionDetail {
     pub status: String,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    /// Execution this one was forked from
+    pub forked_from: Option<Uuid>,
     pub events: Vec<EventSummary>,
 }
 
@@ -68,3 +72,43 @@ pub struct ExecutionStats {
     pub labels: Vec<String>,
     pub series: Vec<f64>,
 }
+
+/// Request body for replaying an execution
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct ReplayRequest {
+    /// `cassette` (default), `live_llm` or `live_tools`
+    #[serde(default)]
+    #[schema(value_type = String)]
+    pub mode: ReplayMode,
+}
+
+/// Replay outcome with its comparison against the original execution
+#[derive(Debug, Serialize)]
+pub struct ReplayResponse {
+    pub run: ReplayRun,
+    pub comparison: ExecutionComparison,
+}
+
+/// Request body for forking an execution
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ForkExecutionRequest {
+    /// Sequence number of the event to fork at
+    pub sequence_num: i32,
+    /// Replacement for the user message at the fork point, or the next user
+    /// message elsewhere
+    #[serde(default)]
+    pub user_message: Option<String>,
+    /// Replacement output for the tool result at the fork point
+    #[serde(default)]
+    pub tool_result: Option<serde_json::Value>,
+}
+
+impl From<ForkExecutionRequest> for ForkRequest {
+    fn from(request: ForkExecutionRequest) -> Self {
+        Self {
+            sequence_num: request.sequence_num,
+            user_message: request.user_message,
+            tool_result: request.tool_result,
+        }
+    }
+}
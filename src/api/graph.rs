@@ -11,6 +11,7 @@ use utoipa::{IntoParams, ToSchema};
 use cratos_memory::GraphMemory;
 
 use super::config::ApiResponse;
+use crate::middleware::auth::RequireAuth;
 
 /// Query parameters for graph data
 #[derive(Debug, Deserialize, IntoParams, ToSchema)]
@@ -42,6 +43,18 @@ pub enum NodeKind {
     Concept,
     /// Configuration key
     Config,
+    /// Person
+    Person,
+    /// Project or product
+    Project,
+    /// Company, team or other organization
+    Organization,
+    /// Place
+    Place,
+    /// Date or event
+    Event,
+    /// Web address
+    Url,
 }
 
 impl From<cratos_memory::types::EntityKind> for NodeKind {
@@ -54,13 +67,19 @@ impl From<cratos_memory::types::EntityKind> for NodeKind {
             cratos_memory::types::EntityKind::Error => Self::Error,
             cratos_memory::types::EntityKind::Concept => Self::Concept,
             cratos_memory::types::EntityKind::Config => Self::Config,
+            cratos_memory::types::EntityKind::Person => Self::Person,
+            cratos_memory::types::EntityKind::Project => Self::Project,
+            cratos_memory::types::EntityKind::Organization => Self::Organization,
+            cratos_memory::types::EntityKind::Place => Self::Place,
+            cratos_memory::types::EntityKind::Event => Self::Event,
+            cratos_memory::types::EntityKind::Url => Self::Url,
         }
     }
 }
 
 /// Edge kind for visualization
 #[derive(Debug, Serialize, ToSchema)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 pub enum EdgeKind {
     /// Entities appearing in the same turn
     Cooccurrence,
@@ -72,6 +91,14 @@ pub enum EdgeKind {
     Imports,
     /// General relationship
     Related,
+    /// Person works on a project
+    WorksOn,
+    /// Entity belongs to an organization
+    MemberOf,
+    /// Entity is located in a place
+    LocatedIn,
+    /// Person takes part in an event
+    Attends,
 }
 
 impl From<cratos_memory::types::RelationKind> for EdgeKind {
@@ -81,6 +108,10 @@ impl From<cratos_memory::types::RelationKind> for EdgeKind {
             cratos_memory::types::RelationKind::Calls => Self::Calls,
             cratos_memory::types::RelationKind::Imports => Self::Imports,
             cratos_memory::types::RelationKind::Related => Self::Related,
+            cratos_memory::types::RelationKind::WorksOn => Self::WorksOn,
+            cratos_memory::types::RelationKind::MemberOf => Self::MemberOf,
+            cratos_memory::types::RelationKind::LocatedIn => Self::LocatedIn,
+            cratos_memory::types::RelationKind::Attends => Self::Attends,
         }
     }
 }
@@ -121,6 +152,8 @@ pub struct GraphData {
 }
 
 /// Get graph data for visualization
+///
+/// Only entities mentioned in memory visible to the caller are returned.
 #[utoipa::path(
     get,
     path = "/api/v1/graph",
@@ -128,10 +161,12 @@ pub struct GraphData {
     params(GraphQuery),
     responses(
         (status = 200, description = "Graph data for visualization", body = GraphData),
+        (status = 401, description = "Unauthorized"),
         (status = 500, description = "Graph memory not initialized")
     )
 )]
 pub async fn get_graph(
+    RequireAuth(auth): RequireAuth,
     Extension(graph_memory): Extension<Option<Arc<GraphMemory>>>,
     Query(query): Query<GraphQuery>,
 ) -> Json<ApiResponse<GraphData>> {
@@ -142,8 +177,10 @@ pub async fn get_graph(
         }
     };
 
+    let scope = auth.memory_scope();
+
     // Fetch entities
-    let entities = match graph_memory.list_entities(query.limit).await {
+    let entities = match graph_memory.list_entities(&scope, query.limit).await {
         Ok(e) => e,
         Err(e) => {
             return Json(ApiResponse::error(format!(
@@ -166,7 +203,10 @@ pub async fn get_graph(
     let mut edges = Vec::new();
 
     // Fetch co-occurrences
-    let cooccurrences = match graph_memory.list_cooccurrences(query.limit * 2).await {
+    let cooccurrences = match graph_memory
+        .list_cooccurrences(&scope, query.limit * 2)
+        .await
+    {
         Ok(c) => c,
         Err(e) => {
             return Json(ApiResponse::error(format!(
@@ -186,7 +226,7 @@ pub async fn get_graph(
     }
 
     // Fetch explicit relations
-    let relations = match graph_memory.list_relations(query.limit * 2).await {
+    let relations = match graph_memory.list_relations(&scope, query.limit * 2).await {
         Ok(r) => r,
         Err(e) => {
             return Json(ApiResponse::error(format!(
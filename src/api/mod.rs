@@ -5,19 +5,26 @@
 //! - Tool listing and information
 //! - Execution history
 //! - Scheduler management
+//! - Approval requests
+//! - API key management
 //! - Webhooks for external services
+//! - OpenAI-compatible chat completions
 //! - API documentation (Swagger UI at /docs)
 
+pub mod api_keys;
+pub mod approvals;
 pub mod auth;
 pub mod browser;
 pub mod bundle;
 pub mod config;
+pub mod cost;
 pub mod dev_sessions;
 pub mod docs;
 pub mod executions;
 pub mod graph;
 pub mod health;
 pub mod nodes;
+pub mod openai;
 pub mod pairing;
 pub mod pantheon;
 pub mod quota;
@@ -29,16 +36,20 @@ pub mod webhooks;
 
 use axum::Router;
 
+pub use api_keys::api_keys_routes;
+pub use approvals::approvals_routes;
 pub use auth::auth_routes;
 pub use browser::browser_routes;
 pub use bundle::bundle_routes;
 pub use config::config_routes_with_state;
+pub use cost::cost_routes;
 pub use dev_sessions::dev_sessions_routes;
 pub use docs::docs_routes;
 pub use executions::executions_routes;
 pub use graph::graph_routes;
 pub use health::health_routes;
 pub use nodes::nodes_routes;
+pub use openai::openai_routes;
 pub use pairing::pairing_routes;
 pub use pantheon::pantheon_routes;
 pub use quota::quota_routes;
@@ -55,10 +66,13 @@ pub fn api_router_with_state(session_state: SessionState, config_state: ConfigSt
     Router::new()
         .merge(config_routes_with_state(config_state))
         .merge(auth_routes())
+        .merge(api_keys_routes())
         .merge(tools_routes())
         .merge(executions_routes())
         .merge(scheduler_routes())
+        .merge(approvals_routes())
         .merge(quota_routes())
+        .merge(cost_routes())
         .merge(sessions_routes_with_state(session_state))
         .merge(browser_routes())
         .merge(dev_sessions_routes())
@@ -69,4 +83,5 @@ pub fn api_router_with_state(session_state: SessionState, config_state: ConfigSt
         .merge(graph_routes())
         .merge(nodes_routes())
         .merge(bundle_routes())
+        .merge(openai_routes())
 }
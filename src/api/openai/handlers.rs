@@ -0,0 +1,377 @@
+use axum::{
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    Extension, Json,
+};
+use futures_util::stream;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use cratos_core::auth::Scope;
+use cratos_core::{EventBus, Orchestrator, OrchestratorEvent, OrchestratorInput};
+
+use super::types::{
+    parse_model, AssistantMessage, ChatCompletion, ChatCompletionChunk, ChatCompletionRequest,
+    Choice, ChunkChoice, Conversation, Delta, ErrorDetail, ErrorResponse, ModelInfo, ModelList,
+    Usage, BASE_MODEL,
+};
+use crate::middleware::auth::{require_scope, RequireAuth};
+
+/// Channel type recorded for executions started through this API
+const CHANNEL_TYPE: &str = "openai";
+
+/// OpenAI-style error response
+fn openai_error(
+    status: StatusCode,
+    message: impl Into<String>,
+    error_type: &'static str,
+    code: Option<&'static str>,
+) -> Response {
+    let body = ErrorResponse {
+        error: ErrorDetail {
+            message: message.into(),
+            error_type,
+            code,
+        },
+    };
+    (status, Json(body)).into_response()
+}
+
+/// List the models (base model plus one per persona)
+#[utoipa::path(
+    get,
+    path = "/v1/models",
+    tag = "openai",
+    responses(
+        (status = 200, description = "Available models", body = ModelList),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn list_models(
+    RequireAuth(_auth): RequireAuth,
+    Extension(orchestrator): Extension<Arc<Orchestrator>>,
+) -> Json<ModelList> {
+    let created = chrono::Utc::now().timestamp();
+    let mut personas: Vec<&str> = orchestrator
+        .persona_mapping()
+        .map(|m| m.persona_names())
+        .unwrap_or_default();
+    personas.sort_unstable();
+
+    let data = std::iter::once(BASE_MODEL.to_string())
+        .chain(personas.iter().map(|p| format!("{}/{}", BASE_MODEL, p)))
+        .map(|id| ModelInfo {
+            id,
+            object: "model",
+            created,
+            owned_by: "cratos",
+        })
+        .collect();
+
+    Json(ModelList {
+        object: "list",
+        data,
+    })
+}
+
+/// Run a conversation through the orchestrator
+///
+/// The `model` field selects the persona (`cratos/sindri`); Cratos runs its
+/// own tools, so client-supplied tools are ignored. With `stream: true` the
+/// response is sent as `chat.completion.chunk` server-sent events.
+#[utoipa::path(
+    post,
+    path = "/v1/chat/completions",
+    tag = "openai",
+    request_body = ChatCompletionRequest,
+    responses(
+        (status = 200, description = "Completion (or an SSE stream of chunks)", body = ChatCompletion),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Unknown model", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+pub async fn chat_completions(
+    RequireAuth(auth): RequireAuth,
+    Extension(orchestrator): Extension<Arc<Orchestrator>>,
+    Extension(event_bus): Extension<Arc<EventBus>>,
+    Json(request): Json<ChatCompletionRequest>,
+) -> Response {
+    if let Err(rejection) = require_scope(&auth, &Scope::ExecutionWrite) {
+        return rejection.into_response();
+    }
+
+    let persona = match parse_model(&request.model) {
+        Some(None) => None,
+        Some(Some(persona))
+            if orchestrator
+                .persona_mapping()
+                .is_some_and(|m| m.is_persona(persona)) =>
+        {
+            Some(persona.to_string())
+        }
+        _ => {
+            return openai_error(
+                StatusCode::NOT_FOUND,
+                format!("The model '{}' does not exist", request.model),
+                "invalid_request_error",
+                Some("model_not_found"),
+            )
+        }
+    };
+
+    let conversation = match Conversation::from_messages(&request.messages) {
+        Ok(c) => c,
+        Err(message) => {
+            return openai_error(
+                StatusCode::BAD_REQUEST,
+                message,
+                "invalid_request_error",
+                None,
+            )
+        }
+    };
+
+    // Each completion gets its own session; the client sends the history
+    let id = format!("chatcmpl-{}", Uuid::new_v4().simple());
+    let mut input = OrchestratorInput::new(CHANNEL_TYPE, &id, &auth.user_id, conversation.text)
+        .with_history(conversation.history)
+        .with_images(conversation.images);
+    if let Some(persona) = persona {
+        input = input.with_persona(persona);
+    }
+
+    let created = chrono::Utc::now().timestamp();
+    if request.stream {
+        let include_usage = request
+            .stream_options
+            .as_ref()
+            .is_some_and(|o| o.include_usage);
+        let chunks = ChunkBuilder {
+            id,
+            created,
+            model: request.model,
+        };
+        return stream_completion(orchestrator, &event_bus, input, chunks, include_usage)
+            .into_response();
+    }
+
+    match orchestrator.process(input).await {
+        Ok(result) => Json(ChatCompletion {
+            id,
+            object: "chat.completion",
+            created,
+            model: request.model,
+            choices: vec![Choice {
+                index: 0,
+                message: AssistantMessage {
+                    role: "assistant",
+                    content: result.response,
+                },
+                finish_reason: "stop",
+            }],
+            usage: Usage::from(&result.usage),
+        })
+        .into_response(),
+        Err(e) => {
+            error!(error = %e, "Chat completion failed");
+            openai_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Execution failed: {}", e),
+                "server_error",
+                None,
+            )
+        }
+    }
+}
+
+/// Builds the chunks of one streamed completion
+pub(crate) struct ChunkBuilder {
+    pub id: String,
+    pub created: i64,
+    pub model: String,
+}
+
+impl ChunkBuilder {
+    pub(crate) fn chunk(
+        &self,
+        delta: Delta,
+        finish_reason: Option<&'static str>,
+    ) -> ChatCompletionChunk {
+        ChatCompletionChunk {
+            id: self.id.clone(),
+            object: "chat.completion.chunk",
+            created: self.created,
+            model: self.model.clone(),
+            choices: vec![ChunkChoice {
+                index: 0,
+                delta,
+                finish_reason,
+            }],
+            usage: None,
+        }
+    }
+
+    pub(crate) fn usage(&self, usage: Usage) -> ChatCompletionChunk {
+        ChatCompletionChunk {
+            id: self.id.clone(),
+            object: "chat.completion.chunk",
+            created: self.created,
+            model: self.model.clone(),
+            choices: Vec::new(),
+            usage: Some(usage),
+        }
+    }
+}
+
+fn event(chunk: &ChatCompletionChunk) -> Event {
+    Event::default().data(serde_json::to_string(chunk).unwrap_or_default())
+}
+
+/// Stream a completion as server-sent events
+///
+/// Text deltas of the execution are forwarded as they arrive. When the
+/// provider does not stream, the whole response is sent as one chunk.
+/// Dropping the connection cancels the execution, and so does a provider
+/// failing mid-stream: text already sent can't be taken back, so the request
+/// fails instead of splicing the fallback's answer onto it.
+fn stream_completion(
+    orchestrator: Arc<Orchestrator>,
+    event_bus: &EventBus,
+    input: OrchestratorInput,
+    chunks: ChunkBuilder,
+    include_usage: bool,
+) -> Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>> {
+    // Subscribe before starting so no delta is missed
+    let mut events = event_bus.subscribe();
+    let (tx, rx) = mpsc::unbounded_channel::<Event>();
+    let session_key = input.session_key();
+
+    tokio::spawn(async move {
+        let _ = tx.send(event(&chunks.chunk(
+            Delta {
+                role: Some("assistant"),
+                content: None,
+            },
+            None,
+        )));
+
+        let process = orchestrator.process(input);
+        tokio::pin!(process);
+        let mut execution_id = None;
+        let mut streamed = false;
+        let mut interrupted = false;
+        let mut lagged = false;
+        let mut disconnected = false;
+
+        let result = loop {
+            tokio::select! {
+                result = &mut process => break result,
+                // The client went away: stop working on its behalf
+                _ = tx.closed(), if !disconnected => {
+                    disconnected = true;
+                    if let Some(id) = execution_id {
+                        orchestrator.cancel_execution(id);
+                    }
+                }
+                received = events.recv() => match received {
+                    Ok(OrchestratorEvent::ExecutionStarted { execution_id: id, session_key: key })
+                        if key == session_key =>
+                    {
+                        execution_id = Some(id);
+                        if disconnected {
+                            orchestrator.cancel_execution(id);
+                        }
+                    }
+                    Ok(OrchestratorEvent::ChatDelta { execution_id: id, delta, is_final: false })
+                        if Some(id) == execution_id && !lagged =>
+                    {
+                        streamed = true;
+                        let chunk = chunks.chunk(Delta { role: None, content: Some(delta) }, None);
+                        if tx.send(event(&chunk)).is_err() {
+                            if let Some(id) = execution_id {
+                                orchestrator.cancel_execution(id);
+                            }
+                        }
+                    }
+                    Ok(OrchestratorEvent::ChatReset { execution_id: id })
+                        if Some(id) == execution_id && streamed =>
+                    {
+                        interrupted = true;
+                        orchestrator.cancel_execution(id);
+                    }
+                    Ok(_) => {}
+                    // Deltas were dropped. Before any was streamed the whole
+                    // response is sent at the end instead; after that the
+                    // client's completion has a gap and cannot be repaired
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("Chat completion stream lagged by {} events", n);
+                        lagged = true;
+                        if streamed {
+                            if let Some(id) = execution_id {
+                                orchestrator.cancel_execution(id);
+                            }
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break (&mut process).await,
+                },
+            }
+        };
+
+        let failure = match result {
+            _ if interrupted => {
+                warn!("Provider failed mid-stream, ending chat completion");
+                Some("Execution failed: the provider failed mid-response".to_string())
+            }
+            _ if lagged && streamed => {
+                Some("Execution failed: streamed output was lost, retry the request".to_string())
+            }
+            Ok(result) => {
+                if !streamed && !result.response.is_empty() {
+                    let chunk = chunks.chunk(
+                        Delta {
+                            role: None,
+                            content: Some(result.response),
+                        },
+                        None,
+                    );
+                    let _ = tx.send(event(&chunk));
+                }
+                let _ = tx.send(event(&chunks.chunk(Delta::default(), Some("stop"))));
+                if include_usage {
+                    let _ = tx.send(event(&chunks.usage(Usage::from(&result.usage))));
+                }
+                None
+            }
+            Err(e) => {
+                error!(error = %e, "Streamed chat completion failed");
+                Some(format!("Execution failed: {}", e))
+            }
+        };
+        if let Some(message) = failure {
+            let body = ErrorResponse {
+                error: ErrorDetail {
+                    message,
+                    error_type: "server_error",
+                    code: None,
+                },
+            };
+            let _ =
+                tx.send(Event::default().data(serde_json::to_string(&body).unwrap_or_default()));
+        }
+        let _ = tx.send(Event::default().data("[DONE]"));
+    });
+
+    let stream = stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|event| (Ok(event), rx))
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
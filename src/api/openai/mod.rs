@@ -0,0 +1,28 @@
+//! OpenAI-compatible API endpoints
+//!
+//! Lets tools built on the OpenAI SDKs talk to Cratos directly:
+//!
+//! POST /v1/chat/completions - Run a conversation through the orchestrator
+//! GET  /v1/models           - List models (`cratos`, `cratos/<persona>`)
+//!
+//! Requests authenticate with a regular Cratos API key as the bearer token.
+
+pub mod handlers;
+pub mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use handlers::{chat_completions, list_models};
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+/// Create OpenAI-compatible routes
+pub fn openai_routes() -> Router {
+    Router::new()
+        .route("/v1/chat/completions", post(chat_completions))
+        .route("/v1/models", get(list_models))
+}
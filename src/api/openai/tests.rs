@@ -0,0 +1,117 @@
+use super::handlers::ChunkBuilder;
+use super::types::{parse_model, ChatCompletionRequest, Conversation, Delta, Usage};
+use cratos_llm::MessageRole;
+use serde_json::json;
+
+fn request(value: serde_json::Value) -> ChatCompletionRequest {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_parse_model() {
+    assert_eq!(parse_model("cratos"), Some(None));
+    assert_eq!(parse_model("cratos/sindri"), Some(Some("sindri")));
+    assert_eq!(parse_model("cratos/"), None);
+    assert_eq!(parse_model("cratosx"), None);
+    assert_eq!(parse_model("gpt-4o"), None);
+}
+
+#[test]
+fn test_request_deserialize_ignores_unknown_fields() {
+    let req = request(json!({
+        "model": "cratos",
+        "messages": [{ "role": "user", "content": "hi" }],
+        "temperature": 0.2,
+        "tools": [],
+        "stream": true,
+        "stream_options": { "include_usage": true }
+    }));
+    assert!(req.stream);
+    assert!(req.stream_options.unwrap().include_usage);
+}
+
+#[test]
+fn test_conversation_from_messages() {
+    let req = request(json!({
+        "model": "cratos",
+        "messages": [
+            { "role": "system", "content": "Be brief." },
+            { "role": "user", "content": "What is 2+2?" },
+            { "role": "assistant", "content": null, "tool_calls": [] },
+            { "role": "tool", "tool_call_id": "c1", "content": "4" },
+            { "role": "assistant", "content": "4" },
+            { "role": "user", "content": [
+                { "type": "text", "text": "And this?" },
+                { "type": "image_url", "image_url": { "url": "data:image/png;base64,aGk=" } },
+                { "type": "image_url", "image_url": { "url": "https://example.com/a.png" } },
+                { "type": "input_audio", "input_audio": {} }
+            ]}
+        ]
+    }));
+
+    let conversation = Conversation::from_messages(&req.messages).unwrap();
+    let roles: Vec<MessageRole> = conversation.history.iter().map(|m| m.role).collect();
+    assert_eq!(
+        roles,
+        vec![
+            MessageRole::System,
+            MessageRole::User,
+            MessageRole::Assistant
+        ]
+    );
+    assert_eq!(conversation.history[2].content, "4");
+    assert_eq!(conversation.text, "And this?");
+    assert_eq!(conversation.images.len(), 1);
+    assert_eq!(conversation.images[0].mime_type, "image/png");
+    assert_eq!(conversation.images[0].data, b"hi");
+}
+
+#[test]
+fn test_conversation_requires_trailing_user_message() {
+    assert!(Conversation::from_messages(&[]).is_err());
+
+    let req = request(json!({
+        "model": "cratos",
+        "messages": [
+            { "role": "user", "content": "hi" },
+            { "role": "assistant", "content": "hello" }
+        ]
+    }));
+    assert!(Conversation::from_messages(&req.messages).is_err());
+}
+
+#[test]
+fn test_chunk_serialization() {
+    let chunks = ChunkBuilder {
+        id: "chatcmpl-1".to_string(),
+        created: 1,
+        model: "cratos/sindri".to_string(),
+    };
+
+    let first = serde_json::to_value(chunks.chunk(
+        Delta {
+            role: Some("assistant"),
+            content: None,
+        },
+        None,
+    ))
+    .unwrap();
+    assert_eq!(first["object"], "chat.completion.chunk");
+    assert_eq!(first["model"], "cratos/sindri");
+    assert_eq!(first["choices"][0]["delta"], json!({ "role": "assistant" }));
+    assert!(first["choices"][0]["finish_reason"].is_null());
+    assert!(first.get("usage").is_none());
+
+    let last = serde_json::to_value(chunks.chunk(Delta::default(), Some("stop"))).unwrap();
+    assert_eq!(last["choices"][0]["delta"], json!({}));
+    assert_eq!(last["choices"][0]["finish_reason"], "stop");
+
+    let usage = serde_json::to_value(chunks.usage(Usage {
+        prompt_tokens: 3,
+        completion_tokens: 2,
+        total_tokens: 5,
+    }))
+    .unwrap();
+    assert_eq!(usage["choices"], json!([]));
+    assert_eq!(usage["usage"]["total_tokens"], 5);
+}
@@ -0,0 +1,329 @@
+//! OpenAI chat completions wire types
+//!
+//! Only the fields Cratos acts on are modelled; unknown request fields
+//! (temperature, tools, ...) are accepted and ignored.
+
+use base64::Engine;
+use cratos_llm::{ImageContent, Message};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Model ID that runs the orchestrator without a fixed persona
+pub const BASE_MODEL: &str = "cratos";
+
+/// `POST /v1/chat/completions` request
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ChatCompletionRequest {
+    /// `cratos` or `cratos/<persona>` (e.g. `cratos/sindri`)
+    pub model: String,
+    /// Conversation; the last message must come from the user
+    #[schema(value_type = Vec<Object>)]
+    pub messages: Vec<ChatMessage>,
+    /// Stream the response as server-sent events
+    #[serde(default)]
+    pub stream: bool,
+    /// Streaming options
+    #[serde(default)]
+    pub stream_options: Option<StreamOptions>,
+}
+
+/// Streaming options
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct StreamOptions {
+    /// Send a final chunk carrying token usage
+    #[serde(default)]
+    pub include_usage: bool,
+}
+
+/// A message of the conversation
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatMessage {
+    /// `system`, `developer`, `user`, `assistant` or `tool`
+    pub role: String,
+    /// Text, or an array of content parts
+    #[serde(default)]
+    pub content: Option<MessageContent>,
+}
+
+/// Message content
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum MessageContent {
+    /// Plain text
+    Text(String),
+    /// Text and image parts
+    Parts(Vec<ContentPart>),
+}
+
+/// A part of multi-part message content
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    /// Text part
+    Text {
+        /// Text
+        text: String,
+    },
+    /// Image part (only `data:` URLs are supported)
+    ImageUrl {
+        /// Image reference
+        image_url: ImageUrl,
+    },
+    /// Any other part type (ignored)
+    #[serde(other)]
+    Unsupported,
+}
+
+/// Image reference of an image part
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageUrl {
+    /// `data:<mime>;base64,<data>` URL
+    pub url: String,
+}
+
+impl ChatMessage {
+    /// Text of the message (text parts joined by newlines)
+    pub fn text(&self) -> String {
+        match &self.content {
+            None => String::new(),
+            Some(MessageContent::Text(text)) => text.clone(),
+            Some(MessageContent::Parts(parts)) => parts
+                .iter()
+                .filter_map(|p| match p {
+                    ContentPart::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    /// Inline images of the message (remote URLs are skipped)
+    pub fn images(&self) -> Vec<ImageContent> {
+        let Some(MessageContent::Parts(parts)) = &self.content else {
+            return Vec::new();
+        };
+        parts
+            .iter()
+            .filter_map(|p| match p {
+                ContentPart::ImageUrl { image_url } => decode_data_url(&image_url.url),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Decode a base64 `data:` URL into an image
+fn decode_data_url(url: &str) -> Option<ImageContent> {
+    let (mime_type, data) = url.strip_prefix("data:")?.split_once(";base64,")?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .ok()?;
+    Some(ImageContent::new(mime_type, bytes))
+}
+
+/// Conversation converted for the orchestrator
+#[derive(Debug)]
+pub struct Conversation {
+    /// Messages before the last user message
+    pub history: Vec<Message>,
+    /// Text of the last user message
+    pub text: String,
+    /// Images of the last user message
+    pub images: Vec<ImageContent>,
+}
+
+impl Conversation {
+    /// Split request messages into history and the new user turn
+    ///
+    /// Tool messages are dropped (Cratos runs its own tools), as are
+    /// assistant turns without text.
+    pub fn from_messages(messages: &[ChatMessage]) -> Result<Self, String> {
+        let Some((last, earlier)) = messages.split_last() else {
+            return Err("messages must not be empty".to_string());
+        };
+        if last.role != "user" {
+            return Err("the last message must have role 'user'".to_string());
+        }
+
+        let history = earlier
+            .iter()
+            .filter_map(|m| {
+                let text = m.text();
+                match m.role.as_str() {
+                    "system" | "developer" => Some(Message::system(text)),
+                    "user" => {
+                        let images = m.images();
+                        if images.is_empty() {
+                            Some(Message::user(text))
+                        } else {
+                            Some(Message::user_with_images(text, images))
+                        }
+                    }
+                    "assistant" if !text.is_empty() => Some(Message::assistant(text)),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            history,
+            text: last.text(),
+            images: last.images(),
+        })
+    }
+}
+
+/// Persona selected by a model ID
+///
+/// Returns `Some(None)` for the base model, `Some(Some(persona))` for
+/// `cratos/<persona>`, and `None` for anything else.
+pub fn parse_model(model: &str) -> Option<Option<&str>> {
+    if model == BASE_MODEL {
+        return Some(None);
+    }
+    model
+        .strip_prefix(BASE_MODEL)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .filter(|persona| !persona.is_empty())
+        .map(Some)
+}
+
+/// Token usage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct Usage {
+    /// Prompt tokens
+    pub prompt_tokens: u32,
+    /// Completion tokens
+    pub completion_tokens: u32,
+    /// Total tokens
+    pub total_tokens: u32,
+}
+
+impl From<&cratos_llm::TokenUsage> for Usage {
+    fn from(usage: &cratos_llm::TokenUsage) -> Self {
+        Self {
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            total_tokens: usage.total_tokens,
+        }
+    }
+}
+
+/// `POST /v1/chat/completions` response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChatCompletion {
+    /// Completion ID (`chatcmpl-...`)
+    pub id: String,
+    /// Always `chat.completion`
+    pub object: &'static str,
+    /// Unix timestamp (seconds)
+    pub created: i64,
+    /// Requested model
+    pub model: String,
+    /// Always exactly one choice
+    pub choices: Vec<Choice>,
+    /// Tokens consumed across all LLM calls of the execution
+    pub usage: Usage,
+}
+
+/// A completion choice
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Choice {
+    /// Choice index
+    pub index: u32,
+    /// Assistant reply
+    pub message: AssistantMessage,
+    /// Why generation stopped
+    pub finish_reason: &'static str,
+}
+
+/// Assistant reply
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AssistantMessage {
+    /// Always `assistant`
+    pub role: &'static str,
+    /// Reply text
+    pub content: String,
+}
+
+/// A server-sent event of a streamed completion
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChatCompletionChunk {
+    /// Completion ID (same for every chunk)
+    pub id: String,
+    /// Always `chat.completion.chunk`
+    pub object: &'static str,
+    /// Unix timestamp (seconds)
+    pub created: i64,
+    /// Requested model
+    pub model: String,
+    /// One choice, or none in the usage chunk
+    pub choices: Vec<ChunkChoice>,
+    /// Token usage (only in the final chunk when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+}
+
+/// A streamed choice
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChunkChoice {
+    /// Choice index
+    pub index: u32,
+    /// Incremental content
+    pub delta: Delta,
+    /// Set on the last content chunk
+    pub finish_reason: Option<&'static str>,
+}
+
+/// Incremental content of a streamed choice
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct Delta {
+    /// `assistant` on the first chunk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<&'static str>,
+    /// Text delta
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// `GET /v1/models` response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ModelList {
+    /// Always `list`
+    pub object: &'static str,
+    /// Available models
+    pub data: Vec<ModelInfo>,
+}
+
+/// An available model
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ModelInfo {
+    /// Model ID to pass as `model`
+    pub id: String,
+    /// Always `model`
+    pub object: &'static str,
+    /// Unix timestamp (seconds)
+    pub created: i64,
+    /// Always `cratos`
+    pub owned_by: &'static str,
+}
+
+/// OpenAI-style error body
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Error details
+    pub error: ErrorDetail,
+}
+
+/// OpenAI-style error details
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ErrorDetail {
+    /// Human-readable message
+    pub message: String,
+    /// Error type (e.g. `invalid_request_error`)
+    #[serde(rename = "type")]
+    pub error_type: &'static str,
+    /// Machine-readable code
+    pub code: Option<&'static str>,
+}
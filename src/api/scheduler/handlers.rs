@@ -1,4 +1,7 @@
-use axum::{extract::Path, Extension, Json};
+use axum::{
+    extract::{Path, Query},
+    Extension, Json,
+};
 use std::sync::Arc;
 use tracing::{info, warn};
 use uuid::Uuid;
@@ -7,7 +10,8 @@ use cratos_core::scheduler::{ScheduledTask, SchedulerEngine};
 
 use super::super::config::ApiResponse;
 use super::types::{
-    parse_action, parse_trigger, task_to_view, CreateTaskRequest, TaskView, UpdateTaskRequest,
+    apply_policies, parse_action, parse_trigger, task_to_view, CreateTaskRequest, ExecutionView,
+    ExecutionsQuery, PreviewQuery, RunNowResponse, RunPreview, TaskView, UpdateTaskRequest,
 };
 use crate::middleware::auth::{require_scope, RequireAuth};
 
@@ -83,10 +87,14 @@ pub async fn create_task(
     // Build task
     let mut task =
         ScheduledTask::new(&request.name, trigger, action).with_priority(request.priority);
-    if let Some(desc) = request.description {
+    if let Some(desc) = &request.description {
         task = task.with_description(desc);
     }
     task.enabled = request.enabled;
+    let task = match apply_policies(task, &request) {
+        Ok(t) => t,
+        Err(e) => return Ok(Json(ApiResponse::error(e))),
+    };
 
     let view = task_to_view(&task);
 
@@ -133,6 +141,123 @@ pub async fn get_task(
     }
 }
 
+/// Maximum number of run times returned by the preview
+const MAX_PREVIEW_RUNS: usize = 100;
+
+/// Preview the next run times of a task (requires authentication + scheduler_read scope)
+#[utoipa::path(
+    get,
+    path = "/api/v1/scheduler/tasks/{id}/preview",
+    tag = "scheduler",
+    params(
+        ("id" = Uuid, Path, description = "Task ID"),
+        PreviewQuery
+    ),
+    responses(
+        (status = 200, description = "Upcoming run times", body = RunPreview),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn preview_task(
+    RequireAuth(auth): RequireAuth,
+    engine: Option<Extension<Arc<SchedulerEngine>>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PreviewQuery>,
+) -> Result<Json<ApiResponse<RunPreview>>, crate::middleware::auth::AuthRejection> {
+    require_scope(&auth, &cratos_core::Scope::SchedulerRead)?;
+    let Some(Extension(engine)) = engine else {
+        return Ok(Json(ApiResponse::error("Scheduler not enabled")));
+    };
+
+    let count = query.count.min(MAX_PREVIEW_RUNS);
+    match engine.preview_runs(id, count).await {
+        Ok(runs) => Ok(Json(ApiResponse::success(RunPreview { task_id: id, runs }))),
+        Err(e) => Ok(Json(ApiResponse::error(format!("Task not found: {}", e)))),
+    }
+}
+
+/// Maximum number of executions returned by the history
+const MAX_EXECUTIONS: i64 = 200;
+
+/// List recent executions of a task (requires authentication + scheduler_read scope)
+#[utoipa::path(
+    get,
+    path = "/api/v1/scheduler/tasks/{id}/executions",
+    tag = "scheduler",
+    params(
+        ("id" = Uuid, Path, description = "Task ID"),
+        ExecutionsQuery
+    ),
+    responses(
+        (status = 200, description = "Executions, newest first", body = Vec<ExecutionView>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn list_executions(
+    RequireAuth(auth): RequireAuth,
+    engine: Option<Extension<Arc<SchedulerEngine>>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ExecutionsQuery>,
+) -> Result<Json<ApiResponse<Vec<ExecutionView>>>, crate::middleware::auth::AuthRejection> {
+    require_scope(&auth, &cratos_core::Scope::SchedulerRead)?;
+    let Some(Extension(engine)) = engine else {
+        return Ok(Json(ApiResponse::error("Scheduler not enabled")));
+    };
+
+    let limit = query.limit.clamp(1, MAX_EXECUTIONS);
+    match engine.task_executions(id, limit).await {
+        Ok(executions) => Ok(Json(ApiResponse::success(
+            executions.into_iter().map(ExecutionView::from).collect(),
+        ))),
+        Err(e) => Ok(Json(ApiResponse::error(format!(
+            "Failed to list executions: {}",
+            e
+        )))),
+    }
+}
+
+/// Run a task now, outside its schedule (requires authentication + scheduler_write scope)
+#[utoipa::path(
+    post,
+    path = "/api/v1/scheduler/tasks/{id}/run",
+    tag = "scheduler",
+    params(
+        ("id" = Uuid, Path, description = "Task ID")
+    ),
+    responses(
+        (status = 200, description = "Run started, queued or skipped per the overlap policy", body = RunNowResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - missing SchedulerWrite scope"),
+        (status = 404, description = "Task not found")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn run_task(
+    RequireAuth(auth): RequireAuth,
+    engine: Option<Extension<Arc<SchedulerEngine>>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<RunNowResponse>>, crate::middleware::auth::AuthRejection> {
+    require_scope(&auth, &cratos_core::Scope::SchedulerWrite)?;
+    let Some(Extension(engine)) = engine else {
+        return Ok(Json(ApiResponse::error("Scheduler not enabled")));
+    };
+
+    match engine.run_now(id).await {
+        Ok(outcome) => {
+            info!("Manual run of task {}: {:?}", id, outcome);
+            Ok(Json(ApiResponse::success(RunNowResponse {
+                task_id: id,
+                outcome,
+            })))
+        }
+        Err(e) => Ok(Json(ApiResponse::error(format!("Task not found: {}", e)))),
+    }
+}
+
 /// Update a task (requires authentication + scheduler_write scope)
 #[utoipa::path(
     put,
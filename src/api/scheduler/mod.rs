@@ -5,6 +5,9 @@
 //! GET    /api/v1/scheduler/tasks/:id - Get task details
 //! PUT    /api/v1/scheduler/tasks/:id - Update a task
 //! DELETE /api/v1/scheduler/tasks/:id - Delete a task
+//! GET    /api/v1/scheduler/tasks/:id/preview - Preview upcoming run times
+//! GET    /api/v1/scheduler/tasks/:id/executions - Run history
+//! POST   /api/v1/scheduler/tasks/:id/run - Run a task now
 
 pub mod handlers;
 pub mod types;
@@ -12,10 +15,18 @@ pub mod types;
 #[cfg(test)]
 mod tests;
 
-pub use handlers::{create_task, delete_task, get_task, list_tasks, update_task};
-pub use types::{CreateTaskRequest, TaskView, UpdateTaskRequest};
+pub use handlers::{
+    create_task, delete_task, get_task, list_executions, list_tasks, preview_task, run_task,
+    update_task,
+};
+pub use types::{
+    CreateTaskRequest, ExecutionView, RunNowResponse, RunPreview, TaskView, UpdateTaskRequest,
+};
 
-use axum::{routing::get, Router};
+use axum::{
+    routing::{get, post},
+    Router,
+};
 
 /// Create scheduler routes
 pub fn scheduler_routes() -> Router {
@@ -25,4 +36,10 @@ pub fn scheduler_routes() -> Router {
             "/api/v1/scheduler/tasks/:id",
             get(get_task).put(update_task).delete(delete_task),
         )
+        .route("/api/v1/scheduler/tasks/:id/preview", get(preview_task))
+        .route(
+            "/api/v1/scheduler/tasks/:id/executions",
+            get(list_executions),
+        )
+        .route("/api/v1/scheduler/tasks/:id/run", post(run_task))
 }
@@ -1,5 +1,9 @@
-use super::types::{parse_action, parse_trigger, task_to_view};
-use cratos_core::scheduler::{ScheduledTask, TaskAction, TriggerType};
+use super::types::{
+    apply_policies, parse_action, parse_trigger, task_to_view, CreateTaskRequest, ExecutionView,
+};
+use cratos_core::scheduler::{
+    MisfirePolicy, OverlapPolicy, ScheduledTask, TaskAction, TaskExecution, TriggerType,
+};
 
 fn is_valid_trigger_type(trigger_type: &str) -> bool {
     matches!(
@@ -11,7 +15,7 @@ fn is_valid_trigger_type(trigger_type: &str) -> bool {
 fn is_valid_action_type(action_type: &str) -> bool {
     matches!(
         action_type,
-        "natural_language" | "tool_call" | "notification" | "shell" | "webhook"
+        "natural_language" | "tool_call" | "notification" | "shell" | "webhook" | "chain"
     )
 }
 
@@ -38,6 +42,43 @@ fn test_parse_trigger_cron() {
     assert!(matches!(trigger, TriggerType::Cron(_)));
 }
 
+#[test]
+fn test_parse_trigger_cron_timezone() {
+    let config = serde_json::json!({"expression": "0 9 * * MON-FRI", "timezone": "Asia/Seoul"});
+    let trigger = parse_trigger("cron", &config).unwrap();
+    match trigger {
+        TriggerType::Cron(cron) => assert_eq!(cron.timezone.as_deref(), Some("Asia/Seoul")),
+        other => panic!("Expected cron trigger, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_trigger_cron_invalid() {
+    let config = serde_json::json!({"expression": "0 9 * *"});
+    assert!(parse_trigger("cron", &config).is_err());
+
+    let config = serde_json::json!({"expression": "0 9 * * *", "timezone": "Mars/Base"});
+    assert!(parse_trigger("cron", &config).is_err());
+}
+
+#[test]
+fn test_parse_trigger_file_and_system() {
+    let config = serde_json::json!({"path": "/tmp/inbox/*.csv", "events": ["created"]});
+    let trigger = parse_trigger("file", &config).unwrap();
+    assert!(matches!(trigger, TriggerType::File(f) if f.debounce_ms == 500));
+
+    let config = serde_json::json!({
+        "metric": "disk_usage",
+        "threshold": 90.0,
+        "comparison": "greater_than"
+    });
+    let trigger = parse_trigger("system", &config).unwrap();
+    assert!(matches!(trigger, TriggerType::System(s) if s.cooldown_secs == 300));
+
+    let config = serde_json::json!({"metric": "disk_usage"});
+    assert!(parse_trigger("system", &config).is_err());
+}
+
 #[test]
 fn test_parse_trigger_interval() {
     let config = serde_json::json!({"seconds": 3600});
@@ -66,6 +107,29 @@ fn test_parse_action_tool_call() {
     assert!(matches!(action, TaskAction::ToolCall { .. }));
 }
 
+#[test]
+fn test_parse_action_chain() {
+    let config = serde_json::json!({"steps": [
+        {"action": {"type": "tool_call", "tool": "http_get", "args": {"url": "https://example.com"}}},
+        {"action": {"type": "notification", "channel": "telegram", "channel_id": "42", "message": "Down: {{output}}"},
+         "when": {"type": "regex", "pattern": "(?i)outage"}}
+    ]});
+    let action = parse_action("chain", &config).unwrap();
+    assert!(matches!(&action, TaskAction::Chain { steps } if steps.len() == 2));
+
+    let task = ScheduledTask::new("status", TriggerType::interval(600), action);
+    let view = task_to_view(&task);
+    assert_eq!(view.action_type, "chain");
+    assert_eq!(view.action_config, config);
+
+    assert!(parse_action("chain", &serde_json::json!({})).is_err());
+    assert!(parse_action("chain", &serde_json::json!({"steps": []})).is_err());
+    let bad_regex = serde_json::json!({"steps": [
+        {"action": {"type": "natural_language", "prompt": "hi"}, "when": {"type": "regex", "pattern": "("}}
+    ]});
+    assert!(parse_action("chain", &bad_regex).is_err());
+}
+
 #[test]
 fn test_parse_action_invalid() {
     let config = serde_json::json!({});
@@ -89,3 +153,76 @@ fn test_task_to_view() {
     assert_eq!(view.action_type, "natural_language");
     assert!(view.enabled);
 }
+
+fn create_request(extra: serde_json::Value) -> CreateTaskRequest {
+    let mut request = serde_json::json!({
+        "name": "report",
+        "trigger_type": "cron",
+        "trigger_config": {"expression": "0 9 * * *"},
+        "action_type": "natural_language",
+        "action_config": {"prompt": "Report"}
+    });
+    request
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    serde_json::from_value(request).unwrap()
+}
+
+fn report_task() -> ScheduledTask {
+    ScheduledTask::new(
+        "report",
+        TriggerType::cron("0 9 * * *"),
+        TaskAction::natural_language("Report"),
+    )
+}
+
+#[test]
+fn test_apply_policies() {
+    let request = create_request(serde_json::json!({
+        "misfire_policy": {"type": "run_all", "max_runs": 3},
+        "overlap_policy": "queue",
+        "jitter_secs": 60
+    }));
+    let task = apply_policies(report_task(), &request).unwrap();
+    assert_eq!(task.misfire_policy, MisfirePolicy::RunAll { max_runs: 3 });
+    assert_eq!(task.overlap_policy, OverlapPolicy::Queue);
+    assert_eq!(task.jitter_secs, 60);
+
+    let view = task_to_view(&task);
+    assert_eq!(view.misfire_policy["type"], "run_all");
+    assert_eq!(view.overlap_policy, "queue");
+
+    // Defaults
+    let task = apply_policies(report_task(), &create_request(serde_json::json!({}))).unwrap();
+    assert_eq!(task.misfire_policy, MisfirePolicy::RunOnce);
+    assert_eq!(task.overlap_policy, OverlapPolicy::Skip);
+}
+
+#[test]
+fn test_apply_policies_invalid() {
+    for extra in [
+        serde_json::json!({"misfire_policy": {"type": "run_some"}}),
+        serde_json::json!({"overlap_policy": "parallel"}),
+        serde_json::json!({"jitter_secs": 1_000_000}),
+    ] {
+        assert!(apply_policies(report_task(), &create_request(extra)).is_err());
+    }
+}
+
+#[test]
+fn test_execution_view_duration() {
+    let started_at = chrono::Utc::now();
+    let execution = TaskExecution {
+        id: uuid::Uuid::new_v4(),
+        task_id: uuid::Uuid::new_v4(),
+        started_at,
+        finished_at: Some(started_at + chrono::Duration::milliseconds(1500)),
+        status: "success".to_string(),
+        result: Some("done".to_string()),
+        attempt: 1,
+    };
+    let view = ExecutionView::from(execution);
+    assert_eq!(view.duration_ms, Some(1500));
+    assert_eq!(view.status, "success");
+}
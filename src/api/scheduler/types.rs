@@ -1,9 +1,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use cratos_core::scheduler::{ScheduledTask, TaskAction, TriggerType};
+use cratos_core::scheduler::{
+    ChainStep, FileTrigger, MisfirePolicy, OverlapPolicy, RunDispatch, ScheduledTask,
+    SystemTrigger, TaskAction, TaskExecution, TriggerType, MAX_JITTER_SECS,
+};
 
 /// Task view for API responses
 #[derive(Debug, Clone, Serialize, ToSchema)]
@@ -22,6 +25,9 @@ pub struct TaskView {
     pub next_run_at: Option<DateTime<Utc>>,
     pub run_count: i64,
     pub failure_count: i64,
+    pub misfire_policy: serde_json::Value,
+    pub overlap_policy: String,
+    pub jitter_secs: u64,
 }
 
 /// Request to create a new task
@@ -37,6 +43,15 @@ pub struct CreateTaskRequest {
     pub enabled: bool,
     #[serde(default)]
     pub priority: i32,
+    /// Runs to catch up after downtime: `{"type": "skip" | "run_once"}` or
+    /// `{"type": "run_all", "max_runs": N}` (default `run_once`)
+    pub misfire_policy: Option<serde_json::Value>,
+    /// Behavior when the task fires while still running: "skip" (default),
+    /// "queue" or "cancel_previous"
+    pub overlap_policy: Option<String>,
+    /// Random delay of up to this many seconds added to each scheduled time
+    #[serde(default)]
+    pub jitter_secs: u64,
 }
 
 pub(crate) fn default_true() -> bool {
@@ -55,6 +70,77 @@ pub struct UpdateTaskRequest {
     pub priority: Option<i32>,
 }
 
+/// Query parameters for the run preview
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PreviewQuery {
+    /// Number of upcoming run times (default 5, max 100)
+    #[serde(default = "default_preview_count")]
+    pub count: usize,
+}
+
+fn default_preview_count() -> usize {
+    5
+}
+
+/// Upcoming run times of a task
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RunPreview {
+    pub task_id: Uuid,
+    pub runs: Vec<DateTime<Utc>>,
+}
+
+/// Query parameters for the execution history
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExecutionsQuery {
+    /// Number of executions, newest first (default 20, max 200)
+    #[serde(default = "default_executions_limit")]
+    pub limit: i64,
+}
+
+fn default_executions_limit() -> i64 {
+    20
+}
+
+/// One run of a task
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExecutionView {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// running, success, failed, skipped or cancelled
+    pub status: String,
+    pub result: Option<String>,
+    pub attempt: i32,
+    pub duration_ms: Option<i64>,
+}
+
+impl From<TaskExecution> for ExecutionView {
+    fn from(execution: TaskExecution) -> Self {
+        Self {
+            duration_ms: execution
+                .finished_at
+                .map(|finished| (finished - execution.started_at).num_milliseconds()),
+            id: execution.id,
+            task_id: execution.task_id,
+            started_at: execution.started_at,
+            finished_at: execution.finished_at,
+            status: execution.status,
+            result: execution.result,
+            attempt: execution.attempt,
+        }
+    }
+}
+
+/// Result of a manual run
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RunNowResponse {
+    pub task_id: Uuid,
+    /// started, queued, skipped or replaced (per the task's overlap policy)
+    #[schema(value_type = String)]
+    pub outcome: RunDispatch,
+}
+
 /// Convert a ScheduledTask to a TaskView for API response
 pub fn task_to_view(task: &ScheduledTask) -> TaskView {
     let (trigger_type, trigger_config) = match &task.trigger {
@@ -94,9 +180,9 @@ pub fn task_to_view(task: &ScheduledTask) -> TaskView {
             "notification".to_string(),
             serde_json::json!({ "channel": channel, "channel_id": channel_id, "message": message }),
         ),
-        TaskAction::Shell { command, cwd } => (
+        TaskAction::Shell { command, args, cwd } => (
             "shell".to_string(),
-            serde_json::json!({ "command": command, "cwd": cwd }),
+            serde_json::json!({ "command": command, "args": args, "cwd": cwd }),
         ),
         TaskAction::Webhook { .. } => (
             "webhook".to_string(),
@@ -110,6 +196,7 @@ pub fn task_to_view(task: &ScheduledTask) -> TaskView {
             "prune_stale_skills".to_string(),
             serde_json::json!({ "days": days }),
         ),
+        TaskAction::Chain { steps } => ("chain".to_string(), serde_json::json!({ "steps": steps })),
     };
 
     TaskView {
@@ -127,6 +214,9 @@ pub fn task_to_view(task: &ScheduledTask) -> TaskView {
         next_run_at: task.next_run_at,
         run_count: task.run_count,
         failure_count: task.failure_count,
+        misfire_policy: serde_json::to_value(task.misfire_policy).unwrap_or_default(),
+        overlap_policy: task.overlap_policy.as_str().to_string(),
+        jitter_secs: task.jitter_secs,
     }
 }
 
@@ -140,7 +230,12 @@ pub fn parse_trigger(
             let expr = config["expression"]
                 .as_str()
                 .ok_or("Missing cron expression")?;
-            Ok(TriggerType::cron(expr))
+            let trigger = match config["timezone"].as_str() {
+                Some(tz) => TriggerType::cron_in(expr, tz),
+                None => TriggerType::cron(expr),
+            };
+            trigger.validate().map_err(|e| e.to_string())?;
+            Ok(trigger)
         }
         "interval" => {
             let seconds = config["seconds"]
@@ -155,10 +250,43 @@ pub fn parse_trigger(
                 .with_timezone(&Utc);
             Ok(TriggerType::one_time(at))
         }
+        "file" => {
+            let file: FileTrigger = serde_json::from_value(config.clone())
+                .map_err(|e| format!("Invalid file trigger: {}", e))?;
+            let trigger = TriggerType::File(file);
+            trigger.validate().map_err(|e| e.to_string())?;
+            Ok(trigger)
+        }
+        "system" => {
+            let system: SystemTrigger = serde_json::from_value(config.clone())
+                .map_err(|e| format!("Invalid system trigger: {}", e))?;
+            Ok(TriggerType::System(system))
+        }
         other => Err(format!("Invalid trigger type: {}", other)),
     }
 }
 
+/// Apply the misfire/overlap policies and jitter of a create request
+pub fn apply_policies(
+    task: ScheduledTask,
+    request: &CreateTaskRequest,
+) -> Result<ScheduledTask, String> {
+    let mut task = task;
+    if let Some(misfire) = &request.misfire_policy {
+        task.misfire_policy = serde_json::from_value::<MisfirePolicy>(misfire.clone())
+            .map_err(|e| format!("Invalid misfire policy: {}", e))?;
+    }
+    if let Some(overlap) = &request.overlap_policy {
+        task.overlap_policy =
+            serde_json::from_value::<OverlapPolicy>(serde_json::Value::String(overlap.clone()))
+                .map_err(|_| format!("Invalid overlap policy: {}", overlap))?;
+    }
+    if request.jitter_secs > MAX_JITTER_SECS {
+        return Err(format!("jitter_secs must be at most {}", MAX_JITTER_SECS));
+    }
+    Ok(task.with_jitter(request.jitter_secs))
+}
+
 /// Parse action from API request
 pub fn parse_action(action_type: &str, config: &serde_json::Value) -> Result<TaskAction, String> {
     match action_type {
@@ -202,8 +330,16 @@ pub fn parse_action(action_type: &str, config: &serde_json::Value) -> Result<Tas
                 .as_str()
                 .ok_or("Missing command")?
                 .to_string();
+            let args = config["args"]
+                .as_array()
+                .map(|args| {
+                    args.iter()
+                        .filter_map(|arg| arg.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
             let cwd = config["cwd"].as_str().map(String::from);
-            Ok(TaskAction::Shell { command, cwd })
+            Ok(TaskAction::Shell { command, args, cwd })
         }
         "webhook" => serde_json::from_value(config.clone()).map_err(|e| e.to_string()),
         "run_skill_analysis" => {
@@ -214,6 +350,14 @@ pub fn parse_action(action_type: &str, config: &serde_json::Value) -> Result<Tas
             let days = config["days"].as_u64().unwrap_or(90) as u32;
             Ok(TaskAction::PruneStaleSkills { days })
         }
+        "chain" => {
+            let steps: Vec<ChainStep> =
+                serde_json::from_value(config.get("steps").cloned().ok_or("Missing steps")?)
+                    .map_err(|e| format!("Invalid steps: {}", e))?;
+            let action = TaskAction::Chain { steps };
+            action.validate().map_err(|e| e.to_string())?;
+            Ok(action)
+        }
         other => Err(format!("Invalid action type: {}", other)),
     }
 }
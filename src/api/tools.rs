@@ -43,14 +43,14 @@ pub async fn list_tools(
 ) -> Json<ApiResponse<Vec<ToolInfo>>> {
     let tools: Vec<ToolInfo> = match registry {
         Some(Extension(reg)) => reg
-            .list_definitions()
+            .all_definitions()
             .into_iter()
             .map(|def| ToolInfo {
-                name: def.name.clone(),
-                description: def.description.clone(),
                 category: format!("{:?}", def.category),
                 requires_approval: def.risk_level != cratos_tools::RiskLevel::Low,
-                parameters: def.parameters.clone(),
+                name: def.name,
+                description: def.description,
+                parameters: def.parameters,
             })
             .collect(),
         None => Vec::new(),
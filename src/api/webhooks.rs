@@ -4,11 +4,12 @@ use axum::{
     extract::{Extension, Query},
     http::StatusCode,
     response::IntoResponse,
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
 use cratos_channels::{
-    whatsapp::business::WhatsAppBusinessHandler, WhatsAppBusinessAdapter, WhatsAppBusinessWebhook,
+    whatsapp::business::WhatsAppBusinessHandler, MattermostActionRequest, MattermostActionResponse,
+    MattermostAdapter, WhatsAppBusinessAdapter, WhatsAppBusinessWebhook,
 };
 use cratos_core::Orchestrator;
 use serde::Deserialize;
@@ -71,12 +72,29 @@ async fn whatsapp_business_webhook(
     StatusCode::OK
 }
 
+/// Handle a Mattermost interactive button click (POST)
+///
+/// Mattermost posts here when a user clicks a button created by the adapter.
+/// The response updates the post or shows an ephemeral message.
+async fn mattermost_action(
+    Extension(orchestrator): Extension<Arc<Orchestrator>>,
+    Extension(adapter): Extension<Arc<MattermostAdapter>>,
+    Json(request): Json<MattermostActionRequest>,
+) -> Json<MattermostActionResponse> {
+    Json(adapter.handle_action(&orchestrator, request).await)
+}
+
 /// Create webhook routes
 pub fn webhooks_routes() -> Router {
-    Router::new().route(
-        "/api/v1/webhooks/whatsapp-business",
-        get(whatsapp_business_verify).post(whatsapp_business_webhook),
-    )
+    Router::new()
+        .route(
+            "/api/v1/webhooks/whatsapp-business",
+            get(whatsapp_business_verify).post(whatsapp_business_webhook),
+        )
+        .route(
+            "/api/v1/webhooks/mattermost/actions",
+            post(mattermost_action),
+        )
 }
 
 #[cfg(test)]
@@ -0,0 +1,211 @@
+//! API key management CLI commands
+//!
+//! `cratos auth key create`  — Create an API key
+//! `cratos auth key list`    — List API keys
+//! `cratos auth key revoke`  — Revoke an API key
+//! `cratos auth key rotate`  — Replace an API key, keeping the old one for a grace period
+//!
+//! Keys are stored in `cratos.db`; a running server picks up changes within
+//! its sync interval.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use clap::Subcommand;
+use cratos_core::{default_user_scopes, ApiKeyInfo, AuthStore, Scope};
+use cratos_replay::EventStore;
+
+/// Auth subcommands
+#[derive(Subcommand, Debug)]
+pub enum AuthCommands {
+    /// Manage API keys
+    #[command(subcommand)]
+    Key(KeyCommands),
+}
+
+/// API key subcommands
+#[derive(Subcommand, Debug)]
+pub enum KeyCommands {
+    /// Create an API key (printed once)
+    Create {
+        /// Owner of the key
+        #[arg(short, long)]
+        user: String,
+        /// Human-readable label
+        #[arg(short, long, default_value = "")]
+        label: String,
+        /// Comma-separated scopes (default: all user scopes)
+        #[arg(short, long, value_delimiter = ',')]
+        scopes: Vec<Scope>,
+        /// Days until the key expires (omit for no expiry)
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+    },
+    /// List API keys
+    List,
+    /// Revoke an API key
+    Revoke {
+        /// Key ID (hash or unique prefix, see `cratos auth key list`)
+        id: String,
+    },
+    /// Rotate an API key
+    Rotate {
+        /// Key ID (hash or unique prefix, see `cratos auth key list`)
+        id: String,
+        /// Hours the old key keeps working
+        #[arg(long, default_value = "24")]
+        grace_hours: u32,
+    },
+}
+
+/// Run an auth subcommand.
+pub async fn run(cmd: AuthCommands) -> Result<()> {
+    let AuthCommands::Key(cmd) = cmd;
+    let store = open_store().await?;
+
+    match cmd {
+        KeyCommands::Create {
+            user,
+            label,
+            scopes,
+            expires_in_days,
+        } => {
+            let scopes = if scopes.is_empty() {
+                default_user_scopes()
+            } else {
+                scopes
+            };
+            let expires_at =
+                expires_in_days.map(|days| Utc::now() + chrono::Duration::days(i64::from(days)));
+            let (key, info) = store
+                .create_api_key(&user, scopes, &label, expires_at)
+                .await?;
+            print_new_key(key.expose(), &info);
+        }
+        KeyCommands::List => list(&store)?,
+        KeyCommands::Revoke { id } => {
+            let hash = resolve(&store, &id)?;
+            store.revoke_key(&hash).await?;
+            println!("Revoked {}", short_id(&hash));
+        }
+        KeyCommands::Rotate { id, grace_hours } => {
+            let hash = resolve(&store, &id)?;
+            let (key, info) = store
+                .rotate_key(&hash, chrono::Duration::hours(i64::from(grace_hours)))
+                .await?;
+            print_new_key(key.expose(), &info);
+            println!(
+                "  The old key {} keeps working for {} hour(s).",
+                short_id(&hash),
+                grace_hours
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Open the persistent key store in the configured data directory.
+async fn open_store() -> Result<AuthStore> {
+    let config = crate::server::load_config().context("Failed to load configuration")?;
+    let data_dir = config
+        .data_dir
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(cratos_replay::default_data_dir);
+
+    let event_store = EventStore::from_path(&data_dir.join("cratos.db"))
+        .await
+        .context("Failed to open cratos.db")?;
+    AuthStore::new_with_db(true, event_store.pool().clone())
+        .await
+        .context("Failed to open API key store")
+}
+
+fn resolve(store: &AuthStore, id: &str) -> Result<String> {
+    store
+        .find_key(id)
+        .with_context(|| format!("No unique API key matches '{}'", id))
+}
+
+fn short_id(hash: &str) -> &str {
+    hash.get(..12).unwrap_or(hash)
+}
+
+fn print_new_key(key: &str, info: &ApiKeyInfo) {
+    println!();
+    println!("  API key for {}", info.user_id);
+    println!("  {}", "-".repeat(40));
+    println!("  Key:      {}", key);
+    println!("  ID:       {}", short_id(&info.key_hash));
+    if let Some(expires_at) = info.expires_at {
+        println!("  Expires:  {}", expires_at.format("%Y-%m-%d %H:%M UTC"));
+    }
+    println!();
+    println!("  Store this key now; it is not shown again.");
+    println!();
+}
+
+fn list(store: &AuthStore) -> Result<()> {
+    let keys = store.list_keys()?;
+    if keys.is_empty() {
+        println!("\nNo API keys.");
+        println!("Run `cratos auth key create --user <id>` to create one.\n");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    println!("\nAPI Keys ({})\n{}", keys.len(), "-".repeat(60));
+    for key in &keys {
+        let status = if key.revoked {
+            "revoked".to_string()
+        } else {
+            match key.expires_at {
+                Some(t) if t <= now => "expired".to_string(),
+                Some(t) => format!("expires {}", t.format("%Y-%m-%d %H:%M")),
+                None => "active".to_string(),
+            }
+        };
+        let last_used = key
+            .last_used_at
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "never".to_string());
+        let scopes: Vec<String> = key.scopes.iter().map(ToString::to_string).collect();
+        println!(
+            "  {}  {} [{}] — {} (last used: {})",
+            short_id(&key.key_hash),
+            key.user_id,
+            key.label,
+            status,
+            last_used,
+        );
+        println!("      scopes: {}", scopes.join(", "));
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_parse_create_scopes() {
+        let cli = Cli::parse_from([
+            "cratos",
+            "auth",
+            "key",
+            "create",
+            "--user",
+            "ci",
+            "--scopes",
+            "execution_read,scheduler_write",
+        ]);
+        let Some(Commands::Auth(AuthCommands::Key(KeyCommands::Create { scopes, .. }))) =
+            cli.command
+        else {
+            panic!("expected auth key create");
+        };
+        assert_eq!(scopes, vec![Scope::ExecutionRead, Scope::SchedulerWrite]);
+    }
+}
@@ -0,0 +1,257 @@
+//! CLI command: `cratos cost`
+//!
+//! Summarizes the LLM spending recorded in the cost ledger (`cratos.db`),
+//! grouped by day, provider, model, user, persona or channel, and shows the
+//! status of the configured budgets.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use cratos_llm::cost::BudgetPeriod;
+use cratos_llm::{BudgetConfig, BudgetLimit, CostGroup, CostLedger, CostQuery, CostSummaryRow};
+use cratos_replay::EventStore;
+
+/// Reporting period
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum CostPeriod {
+    /// Since midnight (UTC)
+    Today,
+    /// Since the first of the month (UTC)
+    #[default]
+    Month,
+    /// Everything in the ledger
+    All,
+}
+
+impl CostPeriod {
+    fn since(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Today => Some(BudgetPeriod::Daily.start(now)),
+            Self::Month => Some(BudgetPeriod::Monthly.start(now)),
+            Self::All => None,
+        }
+    }
+}
+
+/// Options of `cratos cost`
+#[derive(Debug, Clone)]
+pub struct CostOptions {
+    pub period: CostPeriod,
+    pub group_by: CostGroup,
+    pub user: Option<String>,
+    pub provider: Option<String>,
+    pub json: bool,
+}
+
+/// Run the cost subcommand.
+pub async fn run(options: CostOptions) -> Result<()> {
+    let config = crate::server::load_config().context("Failed to load configuration")?;
+    let data_dir = config
+        .data_dir
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(cratos_replay::default_data_dir);
+
+    let event_store = EventStore::from_path(&data_dir.join("cratos.db"))
+        .await
+        .context("Failed to open cratos.db")?;
+    let ledger = CostLedger::new(event_store.pool().clone())
+        .await
+        .context("Failed to open the cost ledger")?;
+
+    let now = Utc::now();
+    let query = CostQuery {
+        since: options.period.since(now),
+        until: None,
+        group_by: options.group_by,
+        user_id: options.user.clone(),
+        provider: options.provider.clone(),
+    };
+    let rows = ledger.summarize(&query).await?;
+    let budgets = budget_status(&ledger, config.llm.budget.as_ref(), now).await?;
+
+    if options.json {
+        let output = serde_json::json!({
+            "since": query.since,
+            "group_by": query.group_by,
+            "rows": rows,
+            "total_usd": total_cost(&rows),
+            "budgets": budgets,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print_table(&options, &rows, &budgets);
+    }
+    Ok(())
+}
+
+/// Spending of one configured budget
+#[derive(Debug, serde::Serialize)]
+struct BudgetStatus {
+    scope: String,
+    name: String,
+    period: String,
+    spent_usd: f64,
+    limit_usd: f64,
+}
+
+async fn budget_status(
+    ledger: &CostLedger,
+    config: Option<&BudgetConfig>,
+    now: DateTime<Utc>,
+) -> Result<Vec<BudgetStatus>> {
+    let Some(config) = config else {
+        return Ok(Vec::new());
+    };
+
+    let mut entries: Vec<(&str, &String, &BudgetLimit)> = config
+        .users
+        .iter()
+        .map(|(name, limit)| ("user", name, limit))
+        .chain(
+            config
+                .providers
+                .iter()
+                .map(|(name, limit)| ("provider", name, limit)),
+        )
+        .collect();
+    entries.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+
+    let mut status = Vec::new();
+    for (scope, name, limit) in entries {
+        for (period, limit_usd) in limit.periods() {
+            let (user, provider) = if scope == "user" {
+                (Some(name.as_str()), None)
+            } else {
+                (None, Some(name.as_str()))
+            };
+            let spent_usd = ledger.total_cost(period.start(now), user, provider).await?;
+            status.push(BudgetStatus {
+                scope: scope.to_string(),
+                name: name.clone(),
+                period: period.to_string(),
+                spent_usd,
+                limit_usd,
+            });
+        }
+    }
+    Ok(status)
+}
+
+fn total_cost(rows: &[CostSummaryRow]) -> f64 {
+    rows.iter().map(|r| r.cost_usd).sum()
+}
+
+fn print_table(options: &CostOptions, rows: &[CostSummaryRow], budgets: &[BudgetStatus]) {
+    let period = match options.period {
+        CostPeriod::Today => "today",
+        CostPeriod::Month => "this month",
+        CostPeriod::All => "all time",
+    };
+
+    println!();
+    println!("  LLM Cost ({}, by {})", period, options.group_by);
+    println!("  {}", "-".repeat(72));
+    println!(
+        "  {:<28} {:>9} {:>11} {:>11} {:>10}",
+        capitalize(&options.group_by.to_string()),
+        "Requests",
+        "Input",
+        "Output",
+        "Cost"
+    );
+    println!("  {}", "-".repeat(72));
+
+    if rows.is_empty() {
+        println!("  (no usage recorded)");
+    } else {
+        for row in rows {
+            let key = if row.key.is_empty() { "-" } else { &row.key };
+            println!(
+                "  {:<28} {:>9} {:>11} {:>11} {:>10}",
+                key,
+                row.requests,
+                cratos_llm::format_compact_number(row.input_tokens),
+                cratos_llm::format_compact_number(row.output_tokens),
+                format!("${:.4}", row.cost_usd)
+            );
+        }
+    }
+    println!("  {}", "-".repeat(72));
+    println!(
+        "  {:<28} {:>43}",
+        "Total",
+        format!("${:.4}", total_cost(rows))
+    );
+
+    if !budgets.is_empty() {
+        println!();
+        println!("  Budgets");
+        println!("  {}", "-".repeat(72));
+        for b in budgets {
+            let pct = if b.limit_usd > 0.0 {
+                b.spent_usd / b.limit_usd * 100.0
+            } else {
+                100.0
+            };
+            let warn = if b.spent_usd >= b.limit_usd {
+                " !!"
+            } else {
+                ""
+            };
+            println!(
+                "  {:<28} {:<8} ${:>9.2} / ${:<9.2} {:>5.0}%{}",
+                format!("{} {}", b.scope, b.name),
+                b.period,
+                b.spent_usd,
+                b.limit_usd,
+                pct,
+                warn
+            );
+        }
+    }
+    println!();
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_parse_cost_args() {
+        let cli = Cli::parse_from([
+            "cratos", "cost", "--period", "today", "--by", "model", "--user", "alice",
+        ]);
+        let Some(Commands::Cost {
+            period, by, user, ..
+        }) = cli.command
+        else {
+            panic!("expected cost");
+        };
+        assert_eq!(period, CostPeriod::Today);
+        assert_eq!(by, CostGroup::Model);
+        assert_eq!(user.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn test_period_since() {
+        let now = DateTime::parse_from_rfc3339("2026-03-15T10:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            CostPeriod::Month.since(now).unwrap().to_rfc3339(),
+            "2026-03-01T00:00:00+00:00"
+        );
+        assert!(CostPeriod::All.since(now).is_none());
+    }
+}
@@ -257,6 +257,11 @@ async fn check_container_runtime() -> bool {
                 true
             }
         }
+        Err(_) if cratos_tools::ContainerRuntime::linux_native_supported() => {
+            println!("✅ Native Linux sandbox available (namespaces + Landlock)");
+            println!("  Enable with [security.sandbox] runtime = \"linux_native\"");
+            true
+        }
         Err(_) => {
             println!("ℹ️  No container runtime (sandboxing disabled)");
             println!("  Install Docker for enhanced security isolation");
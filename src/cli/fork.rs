@@ -0,0 +1,81 @@
+//! CLI handler for the `fork` command.
+//!
+//! Forks a recorded execution at an event, optionally editing the next user
+//! message or a tool result, and continues it as a new execution.
+
+use anyhow::{Context, Result};
+use cratos_core::orchestrator::ForkRequest;
+use uuid::Uuid;
+
+/// Build the fork request from the command line options.
+fn build_request(at: i32, message: Option<String>, tool_result: Option<&str>) -> ForkRequest {
+    // Plain text is accepted as a JSON string
+    let tool_result = tool_result.map(|raw| {
+        serde_json::from_str(raw).unwrap_or_else(|_| serde_json::Value::String(raw.into()))
+    });
+
+    ForkRequest {
+        sequence_num: at,
+        user_message: message,
+        tool_result,
+    }
+}
+
+/// Run the fork command.
+pub async fn run(
+    execution_id: &str,
+    at: i32,
+    message: Option<String>,
+    tool_result: Option<&str>,
+) -> Result<()> {
+    let execution_id: Uuid = execution_id
+        .parse()
+        .with_context(|| format!("Invalid execution ID: {}", execution_id))?;
+    let request = build_request(at, message, tool_result);
+
+    let config = crate::server::load_config().context("Failed to load configuration")?;
+    let orchestrator = crate::server::build_orchestrator_for_cli(&config)
+        .await
+        .context("Failed to build orchestrator")?;
+
+    println!("Forking {} at event #{}...", execution_id, at);
+    println!("---");
+
+    let run = orchestrator
+        .fork_execution(execution_id, request)
+        .await
+        .map_err(|e| anyhow::anyhow!("Fork failed: {}", e))?;
+
+    println!("{}", run.response);
+    println!(
+        "\nFork {} ({:?}) of {}",
+        run.execution_id, run.status, run.parent_execution_id
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_request_parses_tool_result() {
+        let request = build_request(4, None, Some(r#"{"stdout": "ok"}"#));
+        assert_eq!(request.sequence_num, 4);
+        assert_eq!(
+            request.tool_result,
+            Some(serde_json::json!({"stdout": "ok"}))
+        );
+
+        let request = build_request(4, None, Some("plain text"));
+        assert_eq!(request.tool_result, Some(serde_json::json!("plain text")));
+    }
+
+    #[test]
+    fn test_build_request_with_message() {
+        let request = build_request(1, Some("try again".to_string()), None);
+        assert_eq!(request.user_message.as_deref(), Some("try again"));
+        assert!(request.tool_result.is_none());
+    }
+}
@@ -12,16 +12,20 @@ use clap::{Parser, Subcommand, ValueEnum};
 /// Path to the environment configuration file.
 pub const ENV_FILE_PATH: &str = ".env";
 
+pub mod auth;
 pub mod browser_ext;
 pub mod chronicle;
 pub mod config;
+pub mod cost;
 pub mod data;
 pub mod decrees;
 pub mod develop;
 pub mod doctor;
+pub mod fork;
 pub mod pair;
 pub mod pantheon;
 pub mod quota;
+pub mod scheduler;
 pub mod security;
 pub mod setup;
 pub mod skill;
@@ -66,11 +70,33 @@ pub enum Commands {
         #[arg(long)]
         watch: bool,
     },
+    /// Show LLM spending from the cost ledger
+    Cost {
+        /// Reporting period
+        #[arg(long, value_enum, default_value = "month")]
+        period: cost::CostPeriod,
+        /// Group by day, provider, model, user, persona or channel
+        #[arg(long, default_value = "provider")]
+        by: cratos_llm::CostGroup,
+        /// Only usage of this user
+        #[arg(long)]
+        user: Option<String>,
+        /// Only usage of this provider
+        #[arg(long)]
+        provider: Option<String>,
+        /// Output as JSON (for scripting)
+        #[arg(long)]
+        json: bool,
+    },
     /// View and modify configuration
     #[command(subcommand)]
     Config(config::ConfigCommands),
     /// Start the server (default)
-    Serve,
+    Serve {
+        /// Also expose Cratos as an MCP server over HTTP at `/mcp`
+        #[arg(long)]
+        mcp_http: bool,
+    },
     /// Launch interactive TUI chat
     Tui {
         /// Persona to start with (e.g., sindri, athena)
@@ -92,6 +118,12 @@ pub enum Commands {
         #[arg(long)]
         mcp: bool,
     },
+    /// Manage API keys (create, list, revoke, rotate)
+    #[command(subcommand)]
+    Auth(auth::AuthCommands),
+    /// Scheduled tasks: list, run history, run now
+    #[command(subcommand)]
+    Scheduler(scheduler::SchedulerCommands),
     /// Security audit and diagnostics
     #[command(subcommand)]
     Security(SecurityCommands),
@@ -118,6 +150,20 @@ pub enum Commands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Fork a recorded execution at an event and continue it
+    Fork {
+        /// Execution ID to fork
+        execution_id: String,
+        /// Sequence number of the event to fork at
+        #[arg(long)]
+        at: i32,
+        /// Replacement (at a user input) or next user message
+        #[arg(short, long)]
+        message: Option<String>,
+        /// Replacement output for the tool result at the fork point (JSON or text)
+        #[arg(long)]
+        tool_result: Option<String>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -375,14 +421,30 @@ pub async fn run(cli: Cli) -> anyhow::Result<()> {
         Some(Commands::Decrees(cmd)) => decrees::run(cmd).await,
         Some(Commands::Chronicle(cmd)) => chronicle::run(cmd).await,
         Some(Commands::Quota { json, watch }) => quota::run(json, watch).await,
+        Some(Commands::Cost {
+            period,
+            by,
+            user,
+            provider,
+            json,
+        }) => {
+            cost::run(cost::CostOptions {
+                period,
+                group_by: by,
+                user,
+                provider,
+                json,
+            })
+            .await
+        }
         Some(Commands::Config(cmd)) => config::run(cmd),
-        Some(Commands::Serve) => {
+        Some(Commands::Serve { mcp_http }) => {
             if !std::path::Path::new(ENV_FILE_PATH).exists() {
                 setup::run(None).await?;
                 // Reload .env after setup creates it
                 let _ = dotenvy::dotenv();
             }
-            crate::server::run().await
+            crate::server::run(crate::server::ServeOptions { mcp_http }).await
         }
         Some(Commands::Skill(cmd)) => skill::run(cmd).await,
         Some(Commands::Data(cmd)) => data::run(cmd).await,
@@ -394,6 +456,8 @@ pub async fn run(cli: Cli) -> anyhow::Result<()> {
                 crate::acp::bridge::run_acp(token).await
             }
         }
+        Some(Commands::Auth(cmd)) => auth::run(cmd).await,
+        Some(Commands::Scheduler(cmd)) => scheduler::run(cmd).await,
         Some(Commands::Security(cmd)) => match cmd {
             SecurityCommands::Audit { json } => security::run_audit_cli(json).await,
         },
@@ -404,6 +468,12 @@ pub async fn run(cli: Cli) -> anyhow::Result<()> {
             repo,
             dry_run,
         }) => develop::run(&issue, repo.as_deref(), dry_run).await,
+        Some(Commands::Fork {
+            execution_id,
+            at,
+            message,
+            tool_result,
+        }) => fork::run(&execution_id, at, message, tool_result.as_deref()).await,
         Some(Commands::Browser(cmd)) => match cmd {
             BrowserCommands::Extension(ext) => match ext {
                 BrowserExtCommands::Install => browser_ext::install().await,
@@ -0,0 +1,231 @@
+//! Scheduler CLI commands
+//!
+//! `cratos scheduler list`     — List scheduled tasks
+//! `cratos scheduler history`  — Show the run history of a task
+//! `cratos scheduler run`      — Run a task now (through the running server)
+//!
+//! `list` and `history` read `scheduler.db` directly. `run` asks the running
+//! server (`CRATOS_SERVER_URL`, default `http://127.0.0.1:19527`) so the run
+//! goes through its scheduler and overlap policy; set `CRATOS_API_KEY` when
+//! authentication is enabled.
+
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use cratos_core::scheduler::{ScheduledTask, SchedulerStore};
+
+/// Scheduler subcommands
+#[derive(Subcommand, Debug)]
+pub enum SchedulerCommands {
+    /// List scheduled tasks
+    List {
+        /// Output as JSON (for scripting)
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show recent runs of a task
+    History {
+        /// Task name, ID or unique ID prefix
+        task: String,
+        /// Number of runs to show
+        #[arg(short, long, default_value = "20")]
+        limit: i64,
+        /// Output as JSON (for scripting)
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run a task now, outside its schedule
+    Run {
+        /// Task name, ID or unique ID prefix
+        task: String,
+    },
+}
+
+/// Run a scheduler subcommand.
+pub async fn run(cmd: SchedulerCommands) -> Result<()> {
+    let store = open_store().await?;
+    match cmd {
+        SchedulerCommands::List { json } => list(&store, json).await,
+        SchedulerCommands::History { task, limit, json } => {
+            history(&store, &task, limit, json).await
+        }
+        SchedulerCommands::Run { task } => run_now(&store, &task).await,
+    }
+}
+
+async fn open_store() -> Result<SchedulerStore> {
+    let config = crate::server::load_config().context("Failed to load configuration")?;
+    let data_dir = config
+        .data_dir
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(cratos_replay::default_data_dir);
+    SchedulerStore::from_path(&data_dir.join("scheduler.db"))
+        .await
+        .context("Failed to open scheduler.db")
+}
+
+/// Find a task by exact name, full ID or unique ID prefix
+fn resolve_task<'a>(tasks: &'a [ScheduledTask], query: &str) -> Result<&'a ScheduledTask> {
+    if let Some(task) = tasks.iter().find(|t| t.name == query) {
+        return Ok(task);
+    }
+    let matches: Vec<&ScheduledTask> = tasks
+        .iter()
+        .filter(|t| t.id.to_string().starts_with(query))
+        .collect();
+    match matches.as_slice() {
+        [task] => Ok(task),
+        [] => bail!("No task named or with ID '{}'", query),
+        _ => bail!(
+            "'{}' matches {} tasks, use a longer ID",
+            query,
+            matches.len()
+        ),
+    }
+}
+
+async fn list(store: &SchedulerStore, json: bool) -> Result<()> {
+    let tasks = store.list_all_tasks().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&tasks)?);
+        return Ok(());
+    }
+    if tasks.is_empty() {
+        println!("No scheduled tasks.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<10} {:<28} {:<8} {:<20} {:>6} {:>6}",
+        "ID", "NAME", "ENABLED", "NEXT RUN (UTC)", "RUNS", "FAILS"
+    );
+    for task in &tasks {
+        let next_run = task
+            .next_run_at
+            .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<10} {:<28} {:<8} {:<20} {:>6} {:>6}",
+            &task.id.to_string()[..8],
+            task.name,
+            if task.enabled { "yes" } else { "no" },
+            next_run,
+            task.run_count,
+            task.failure_count
+        );
+    }
+    Ok(())
+}
+
+async fn history(store: &SchedulerStore, query: &str, limit: i64, json: bool) -> Result<()> {
+    let tasks = store.list_all_tasks().await?;
+    let task = resolve_task(&tasks, query)?;
+    let executions = store.get_task_executions(task.id, limit.max(1)).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&executions)?);
+        return Ok(());
+    }
+    println!("Runs of {} ({}):", task.name, task.id);
+    if executions.is_empty() {
+        println!("  (none)");
+        return Ok(());
+    }
+    for execution in &executions {
+        let duration = execution
+            .finished_at
+            .map(|finished| {
+                format!(
+                    "{:.1}s",
+                    (finished - execution.started_at).num_milliseconds() as f64 / 1000.0
+                )
+            })
+            .unwrap_or_else(|| "-".to_string());
+        let result = execution
+            .result
+            .as_deref()
+            .map(|r| {
+                r.lines()
+                    .next()
+                    .unwrap_or("")
+                    .chars()
+                    .take(60)
+                    .collect::<String>()
+            })
+            .unwrap_or_default();
+        println!(
+            "  {}  {:<10} {:>8}  {}",
+            execution.started_at.format("%Y-%m-%d %H:%M:%S"),
+            execution.status,
+            duration,
+            result
+        );
+    }
+    Ok(())
+}
+
+async fn run_now(store: &SchedulerStore, query: &str) -> Result<()> {
+    let tasks = store.list_all_tasks().await?;
+    let task = resolve_task(&tasks, query)?;
+
+    let url = server_url(&format!("/api/v1/scheduler/tasks/{}/run", task.id));
+    let mut request = reqwest::Client::new().post(&url);
+    if let Ok(key) = std::env::var("CRATOS_API_KEY") {
+        request = request.bearer_auth(key);
+    }
+    let resp = request
+        .send()
+        .await
+        .context("Failed to connect to server (is `cratos serve` running?)")?;
+    let status = resp.status();
+    let body: serde_json::Value = resp.json().await.unwrap_or_default();
+
+    if !status.is_success() || body["success"] != true {
+        let error = body["error"]
+            .as_str()
+            .map(String::from)
+            .unwrap_or_else(|| status.to_string());
+        bail!("Failed to run {}: {}", task.name, error);
+    }
+    let outcome = body["data"]["outcome"].as_str().unwrap_or("started");
+    println!("{}: {}", task.name, outcome);
+    println!(
+        "See `cratos scheduler history {}` for the result.",
+        task.name
+    );
+    Ok(())
+}
+
+/// Build server URL from env or default.
+fn server_url(path: &str) -> String {
+    let base =
+        std::env::var("CRATOS_SERVER_URL").unwrap_or_else(|_| "http://127.0.0.1:19527".to_string());
+    format!("{}{}", base.trim_end_matches('/'), path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cratos_core::scheduler::{TaskAction, TriggerType};
+
+    fn task(name: &str) -> ScheduledTask {
+        ScheduledTask::new(
+            name,
+            TriggerType::interval(3600),
+            TaskAction::natural_language("Hello"),
+        )
+    }
+
+    #[test]
+    fn test_resolve_task() {
+        let tasks = vec![task("backup"), task("report")];
+
+        assert_eq!(resolve_task(&tasks, "report").unwrap().id, tasks[1].id);
+        let id = tasks[0].id.to_string();
+        assert_eq!(resolve_task(&tasks, &id).unwrap().name, "backup");
+        assert_eq!(resolve_task(&tasks, &id[..8]).unwrap().name, "backup");
+        assert!(resolve_task(&tasks, "missing").is_err());
+        // The empty prefix matches every task
+        assert!(resolve_task(&tasks, "").is_err());
+    }
+}
@@ -98,6 +98,10 @@ impl Default for TuiState {
 #[derive(Debug)]
 pub enum AppEvent {
     Chat(ChatMessage),
+    /// Response text streamed by the current execution
+    ChatDelta(String),
+    /// Discard the streamed text (a fallback provider took over)
+    ChatReset,
     ExecutionStarted(uuid::Uuid),
     ExecutionEnded(uuid::Uuid),
 }
@@ -121,6 +125,8 @@ pub struct App {
     commands: Arc<CommandRegistry>,
 
     current_execution_id: Option<uuid::Uuid>,
+    /// Index in `messages` of the assistant message being streamed
+    streaming_message: Option<usize>,
 
     response_tx: mpsc::UnboundedSender<AppEvent>,
     pub response_rx: mpsc::UnboundedReceiver<AppEvent>,
@@ -153,6 +159,7 @@ impl App {
             session_id,
             commands: Arc::new(CommandRegistry::new()),
             current_execution_id: None,
+            streaming_message: None,
             response_tx: tx,
             response_rx: rx,
             settings_state: None,
@@ -348,20 +355,37 @@ impl App {
 
         let event_tx = tx.clone();
         let event_bus = orchestrator.event_bus().cloned();
+        let session_key = cratos_core::SessionContext::make_key("tui", &session_id, "tui-user");
 
         tokio::spawn(async move {
             let event_handle = if let Some(bus) = event_bus {
                 let mut rx = bus.subscribe();
                 let etx = event_tx.clone();
                 Some(tokio::spawn(async move {
+                    let mut own_execution = None;
                     while let Ok(event) = rx.recv().await {
                         match event {
                             cratos_core::event_bus::OrchestratorEvent::ExecutionStarted {
                                 execution_id,
-                                ..
+                                session_key: key,
                             } => {
+                                if key == session_key {
+                                    own_execution = Some(execution_id);
+                                }
                                 let _ = etx.send(AppEvent::ExecutionStarted(execution_id));
                             }
+                            cratos_core::event_bus::OrchestratorEvent::ChatDelta {
+                                execution_id,
+                                delta,
+                                is_final: false,
+                            } if own_execution == Some(execution_id) => {
+                                let _ = etx.send(AppEvent::ChatDelta(delta));
+                            }
+                            cratos_core::event_bus::OrchestratorEvent::ChatReset {
+                                execution_id,
+                            } if own_execution == Some(execution_id) => {
+                                let _ = etx.send(AppEvent::ChatReset);
+                            }
                             cratos_core::event_bus::OrchestratorEvent::ToolStarted {
                                 tool_name,
                                 ..
@@ -445,9 +469,41 @@ impl App {
             match event {
                 AppEvent::Chat(msg) => {
                     self.ui_state.is_loading = false;
-                    self.messages.push(msg);
+                    // The final response replaces the text streamed for it
+                    match self.streaming_message.take() {
+                        Some(index)
+                            if msg.role == Role::Assistant && index < self.messages.len() =>
+                        {
+                            self.messages[index] = msg
+                        }
+                        _ => self.messages.push(msg),
+                    }
                     self.scroll_to_bottom();
                 }
+                // The index is checked as /clear may have emptied the messages
+                AppEvent::ChatDelta(delta) => match self
+                    .streaming_message
+                    .and_then(|index| self.messages.get_mut(index))
+                {
+                    Some(message) => message.content.push_str(&delta),
+                    None => {
+                        self.messages.push(ChatMessage {
+                            role: Role::Assistant,
+                            sender: self.persona.clone(),
+                            content: delta,
+                            timestamp: Local::now(),
+                        });
+                        self.streaming_message = Some(self.messages.len() - 1);
+                        self.scroll_to_bottom();
+                    }
+                },
+                AppEvent::ChatReset => {
+                    if let Some(index) = self.streaming_message.take() {
+                        if index < self.messages.len() {
+                            self.messages.remove(index);
+                        }
+                    }
+                }
                 AppEvent::ExecutionStarted(id) => {
                     self.current_execution_id = Some(id);
                     self.ui_state.is_loading = true; // Ensure loading state
@@ -462,6 +518,31 @@ impl App {
         }
     }
 
+    /// Summarize older turns of this session through the LLM.
+    pub fn compact_session(&mut self) {
+        let orchestrator = self.orchestrator.clone();
+        let session_key = cratos_core::SessionContext::make_key("tui", &self.session_id, "tui-user");
+        let tx = self.response_tx.clone();
+
+        self.push_system("Compacting conversation...".into());
+        tokio::spawn(async move {
+            let content = match orchestrator.compact_session(&session_key).await {
+                Ok(Some(report)) => format!(
+                    "Compacted {} message(s): {} -> {} tokens.",
+                    report.messages_compacted, report.tokens_before, report.tokens_after
+                ),
+                Ok(None) => "Nothing to compact yet.".to_string(),
+                Err(e) => format!("Compaction failed: {e}"),
+            };
+            let _ = tx.send(AppEvent::Chat(ChatMessage {
+                role: Role::System,
+                sender: "system".into(),
+                content,
+                timestamp: Local::now(),
+            }));
+        });
+    }
+
     pub fn has_active_execution(&self) -> bool {
         self.current_execution_id.is_some()
     }
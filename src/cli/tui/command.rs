@@ -29,6 +29,7 @@ impl CommandRegistry {
                 let mut help_text = String::from("Available commands:\n");
                 help_text.push_str("  /persona <name>  Switch persona\n");
                 help_text.push_str("  /clear           Clear chat\n");
+                help_text.push_str("  /compact         Summarize older conversation turns\n");
                 help_text.push_str("  /help            Show this help\n");
                 help_text.push_str("  /quit            Exit TUI\n");
 
@@ -47,6 +48,14 @@ impl CommandRegistry {
             },
         });
 
+        self.commands.push(Command {
+            name: "compact",
+            handler: |app, _| {
+                app.compact_session();
+                Ok(())
+            },
+        });
+
         self.commands.push(Command {
             name: "quit",
             handler: |app, _| {
@@ -17,8 +17,16 @@ mod server;
 mod tools;
 mod websocket;
 
+fn main() -> Result<()> {
+    // Native sandbox launcher: must run before the async runtime starts threads
+    if let Some(code) = cratos_tools::sandbox::launcher_entry() {
+        std::process::exit(code);
+    }
+    run()
+}
+
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn run() -> Result<()> {
     let _ = dotenvy::dotenv();
 
     let is_tui = std::env::args().any(|a| a == "tui");
@@ -81,7 +89,7 @@ async fn main() -> Result<()> {
     let skip_startup_log = matches!(
         &cli.command,
         Some(cli::Commands::Init { .. })
-            | Some(cli::Commands::Serve)
+            | Some(cli::Commands::Serve { .. })
             | Some(cli::Commands::Tui { .. })
             | Some(cli::Commands::Acp { .. })
     );
@@ -1,7 +1,7 @@
 //! Authentication middleware for Axum
 //!
 //! Extracts Bearer tokens or API keys from requests and validates them
-//! against the AuthStore. Provides `RequireAuth` extractor for handlers.
+//! against the AuthStore (JWTs go to the configured OIDC provider). Provides `RequireAuth` extractor for handlers.
 
 use axum::{
     extract::FromRequestParts,
@@ -61,6 +61,10 @@ impl From<AuthError> for AuthRejection {
                 status: StatusCode::UNAUTHORIZED,
                 body: AuthErrorResponse::new("Token has been revoked", "TOKEN_REVOKED"),
             },
+            AuthError::TokenExpired => AuthRejection {
+                status: StatusCode::UNAUTHORIZED,
+                body: AuthErrorResponse::new("Token has expired", "TOKEN_EXPIRED"),
+            },
             AuthError::InsufficientScope { required } => AuthRejection {
                 status: StatusCode::FORBIDDEN,
                 body: AuthErrorResponse::new(
@@ -118,7 +122,7 @@ where
 
         // Try extracting token from various sources
         let token = extract_token(parts)?;
-        let ctx = auth_store.validate_token(&token)?;
+        let ctx = auth_store.authenticate(&token).await?;
 
         Ok(RequireAuth(ctx))
     }
@@ -182,7 +186,7 @@ where
 
         // NEVER bypass — always require a valid token regardless of is_enabled()
         let token = extract_token(parts)?;
-        let ctx = auth_store.validate_token(&token)?;
+        let ctx = auth_store.authenticate(&token).await?;
         Ok(RequireAuthStrict(ctx))
     }
 }
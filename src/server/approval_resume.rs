@@ -0,0 +1,78 @@
+//! Approval Resume Loop
+//!
+//! Continues executions that were parked on an approval when the server
+//! restarted. Once such a request is approved, the execution picks up from
+//! the tool round it was waiting in (the approval manager holds a one-shot
+//! grant for the approved call) and its response is sent back through the
+//! channel the request came from.
+
+use super::channel_senders::ChannelSenders;
+use cratos_channels::OutgoingMessage;
+use cratos_core::{ApprovalManager, Orchestrator, OrchestratorInput};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, warn};
+
+/// Start the approval resume loop
+pub fn start_approval_resume_loop(
+    orchestrator: Arc<Orchestrator>,
+    approval_manager: Arc<ApprovalManager>,
+    senders: ChannelSenders,
+) {
+    let mut resumed = approval_manager.subscribe_resumed();
+    tokio::spawn(async move {
+        debug!("Approval resume loop started");
+        loop {
+            let request = match resumed.recv().await {
+                Ok(request) => request,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "Approval resume loop lagged");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            let Some(parked) = request.parked else {
+                continue;
+            };
+
+            info!(
+                request_id = %request.id,
+                previous_execution_id = %request.execution_id,
+                "Resuming execution parked on approval"
+            );
+            let orchestrator = orchestrator.clone();
+            let senders = senders.clone();
+            tokio::spawn(async move {
+                let channel_type = parked.channel_type.clone();
+                let channel_id = parked.channel_id.clone();
+                let thread_id = parked.thread_id.clone();
+
+                let input = OrchestratorInput::from(parked).with_resumed_approval(request.id);
+                let result = match orchestrator.process(input).await {
+                    Ok(result) => result,
+                    Err(e) => {
+                        warn!(request_id = %request.id, error = %e, "Resumed execution failed");
+                        return;
+                    }
+                };
+                if result.response.is_empty() {
+                    return;
+                }
+
+                let mut message = OutgoingMessage::text(result.response);
+                if let Some(thread_id) = thread_id {
+                    message = message.in_thread(thread_id);
+                }
+                if let Err(e) = senders.send(&channel_type, &channel_id, message).await {
+                    warn!(
+                        request_id = %request.id,
+                        channel = %channel_type,
+                        error = %e,
+                        "Failed to deliver resumed execution response"
+                    );
+                }
+            });
+        }
+        debug!("Approval resume loop stopped");
+    });
+}
@@ -2,10 +2,13 @@
 //!
 //! Contains functions to start scheduler, skill generation, and cleanup tasks.
 
+use super::channel_senders::ChannelSenders;
 use super::config::AppConfig;
 use cratos_core::{
-    EventBus, Orchestrator, SchedulerConfig, SchedulerEngine, SchedulerStore, ShutdownController,
+    ApprovalManager, EventBus, Orchestrator, SchedulerConfig, SchedulerEngine, SchedulerStore,
+    ShutdownController,
 };
+use cratos_llm::LlmProvider;
 use cratos_replay::EventStore;
 use cratos_skills::{SkillRegistry, SkillStore};
 use std::path::Path;
@@ -13,12 +16,15 @@ use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
 /// Start the proactive scheduler
+#[allow(clippy::too_many_arguments)]
 pub async fn start_scheduler(
     config: &AppConfig,
     data_dir: &Path,
     orchestrator: &Arc<Orchestrator>,
+    llm_provider: &Arc<dyn LlmProvider>,
     event_bus: &Arc<EventBus>,
     skill_store: &Arc<SkillStore>,
+    channel_senders: &ChannelSenders,
     shutdown_controller: &ShutdownController,
 ) -> Option<Arc<SchedulerEngine>> {
     if !config.scheduler.enabled {
@@ -32,28 +38,37 @@ pub async fn start_scheduler(
             let scheduler_config = SchedulerConfig::default()
                 .with_check_interval(config.scheduler.check_interval_secs)
                 .with_retry_delay(config.scheduler.retry_delay_secs)
-                .with_max_concurrent(config.scheduler.max_concurrent);
+                .with_max_concurrent(config.scheduler.max_concurrent)
+                .with_sample_interval(config.scheduler.sample_interval_secs)
+                .with_misfire_grace(config.scheduler.misfire_grace_secs)
+                .with_max_queued_runs(config.scheduler.max_queued_runs);
 
             // Build real executor using orchestrator and event bus
             let sched_orch = orchestrator.clone();
             let sched_event_bus = event_bus.clone();
             let sched_skill_store = skill_store.clone();
             let security_config = config.security.clone();
+            let senders = channel_senders.clone();
             let task_executor: cratos_core::scheduler::TaskExecutor =
                 Arc::new(move |action: cratos_core::scheduler::TaskAction| {
                     let orch = sched_orch.clone();
                     let eb = sched_event_bus.clone();
                     let ss = sched_skill_store.clone();
                     let sec = security_config.clone();
+                    let senders = senders.clone();
                     Box::pin(async move {
-                        crate::server::task_handler::execute_task(action, orch, eb, ss, sec).await
+                        crate::server::task_handler::execute_task(
+                            action, orch, eb, ss, sec, senders,
+                        )
+                        .await
                     })
                 });
 
             let scheduler_store_arc = Arc::new(scheduler_store);
             let scheduler_engine = Arc::new(
                 SchedulerEngine::new(scheduler_store_arc.clone(), scheduler_config)
-                    .with_executor(task_executor),
+                    .with_executor(task_executor)
+                    .with_classifier(llm_provider.clone()),
             );
 
             // Register default system tasks
@@ -164,10 +179,12 @@ pub fn start_skill_generation_task(
 /// Start the cleanup background task
 pub fn start_cleanup_task(
     event_store: &Arc<EventStore>,
+    approval_manager: &Arc<ApprovalManager>,
     retention_days: u32,
     shutdown_controller: &ShutdownController,
 ) {
     let cleanup_event_store = event_store.clone();
+    let cleanup_approvals = approval_manager.clone();
     let cleanup_shutdown = shutdown_controller.token();
     tokio::spawn(async move {
         let cleanup_interval = tokio::time::Duration::from_secs(3600);
@@ -185,6 +202,11 @@ pub fn start_cleanup_task(
                             warn!("Cleanup failed: {}", e);
                         }
                     }
+                    cleanup_approvals.cleanup_expired().await;
+                    let pruned = cleanup_approvals.prune_history(retention_days as i64).await;
+                    if pruned > 0 {
+                        info!("Cleanup: deleted {} resolved approval requests", pruned);
+                    }
                 }
                 _ = cleanup_shutdown.cancelled() => {
                     info!("Cleanup task shutting down");
@@ -0,0 +1,51 @@
+//! Outbound channel registry
+//!
+//! Channel adapters register here when they start, so work that finishes
+//! outside a conversation (resumed approvals, scheduled notifications) can
+//! be delivered through the channel it belongs to.
+
+use anyhow::{anyhow, Result};
+use cratos_channels::{ChannelAdapter, OutgoingMessage};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Running channel adapters, keyed by channel type ("telegram", "slack", ...)
+#[derive(Clone, Default)]
+pub struct ChannelSenders {
+    adapters: Arc<RwLock<HashMap<&'static str, Arc<dyn ChannelAdapter>>>>,
+}
+
+impl ChannelSenders {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a started adapter
+    pub fn register(&self, adapter: Arc<dyn ChannelAdapter>) {
+        let channel_type = adapter.channel_type().as_str();
+        self.adapters
+            .write()
+            .expect("failed to lock channel senders")
+            .insert(channel_type, adapter);
+    }
+
+    /// Send a message to `channel_id` through the adapter of `channel_type`
+    ///
+    /// Returns the ID of the sent message.
+    pub async fn send(
+        &self,
+        channel_type: &str,
+        channel_id: &str,
+        message: OutgoingMessage,
+    ) -> Result<String> {
+        let adapter = self
+            .adapters
+            .read()
+            .expect("failed to lock channel senders")
+            .get(channel_type)
+            .cloned()
+            .ok_or_else(|| anyhow!("No running adapter for channel '{}'", channel_type))?;
+        Ok(adapter.send_message(channel_id, message).await?)
+    }
+}
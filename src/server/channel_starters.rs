@@ -3,22 +3,27 @@
 //! Contains functions to start various channel adapters (Telegram, Slack, Discord, etc.)
 
 use cratos_channels::{
-    DiscordAdapter, DiscordConfig, MatrixAdapter, MatrixConfig, TelegramAdapter, TelegramConfig,
-    WhatsAppAdapter, WhatsAppConfig,
+    DiscordAdapter, DiscordConfig, EmailAdapter, EmailConfig, MatrixAdapter, MatrixConfig,
+    MattermostAdapter, MattermostConfig, TelegramAdapter, TelegramConfig, WhatsAppAdapter,
+    WhatsAppConfig,
 };
 use cratos_core::{DevSessionMonitor, Orchestrator, ShutdownController};
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
+use super::channel_senders::ChannelSenders;
+
 /// Start the Telegram adapter
 pub fn start_telegram_adapter(
     orchestrator: &Arc<Orchestrator>,
     dev_monitor: &Arc<DevSessionMonitor>,
     shutdown_controller: &ShutdownController,
+    senders: &ChannelSenders,
 ) -> Option<tokio::task::JoinHandle<()>> {
     match TelegramConfig::from_env() {
         Ok(telegram_config) => {
             let telegram_adapter = Arc::new(TelegramAdapter::new(telegram_config));
+            senders.register(telegram_adapter.clone());
             let telegram_orchestrator = orchestrator.clone();
             let telegram_dev_monitor = Some(dev_monitor.clone());
             let telegram_shutdown = shutdown_controller.token();
@@ -50,10 +55,12 @@ pub fn start_telegram_adapter(
 pub fn start_slack_adapter(
     orchestrator: &Arc<Orchestrator>,
     shutdown_controller: &ShutdownController,
+    senders: &ChannelSenders,
 ) -> Option<tokio::task::JoinHandle<()>> {
     match cratos_channels::SlackConfig::from_env() {
         Ok(slack_config) => {
             let slack_adapter = Arc::new(cratos_channels::SlackAdapter::new(slack_config));
+            senders.register(slack_adapter.clone());
             let slack_orch = orchestrator.clone();
             let slack_shutdown = shutdown_controller.token();
             let handle = tokio::task::spawn_blocking(move || {
@@ -90,6 +97,7 @@ pub fn start_slack_adapter(
 pub fn start_matrix_adapter(
     orchestrator: &Arc<Orchestrator>,
     shutdown_controller: &ShutdownController,
+    senders: &ChannelSenders,
 ) -> Option<tokio::task::JoinHandle<()>> {
     let config = match MatrixConfig::from_env() {
         Ok(c) => c,
@@ -106,6 +114,7 @@ pub fn start_matrix_adapter(
             return None;
         }
     };
+    senders.register(adapter.clone());
 
     let orch = orchestrator.clone();
     let shutdown = shutdown_controller.token();
@@ -120,6 +129,79 @@ pub fn start_matrix_adapter(
     Some(handle)
 }
 
+/// Start the Mattermost adapter
+///
+/// Listens on the Mattermost WebSocket and replies over the REST API.
+/// Returns the adapter alongside the handle so the button callback route can
+/// reach it. Returns None if configuration is missing.
+pub fn start_mattermost_adapter(
+    orchestrator: &Arc<Orchestrator>,
+    shutdown_controller: &ShutdownController,
+    senders: &ChannelSenders,
+) -> Option<(tokio::task::JoinHandle<()>, Arc<MattermostAdapter>)> {
+    let config = match MattermostConfig::from_env() {
+        Ok(c) => c,
+        Err(e) => {
+            warn!(error = %e, "Mattermost adapter not started: missing configuration");
+            return None;
+        }
+    };
+
+    let adapter = Arc::new(MattermostAdapter::new(config));
+    senders.register(adapter.clone());
+    let runner = adapter.clone();
+    let orch = orchestrator.clone();
+    let shutdown = shutdown_controller.token();
+
+    let handle = tokio::spawn(async move {
+        if let Err(e) = runner.run(orch, shutdown).await {
+            error!(error = %e, "Mattermost adapter error");
+        }
+    });
+
+    info!("Mattermost adapter started");
+    Some((handle, adapter))
+}
+
+/// Start the email adapter
+///
+/// Watches an IMAP mailbox and replies over SMTP.
+/// Returns None if configuration is missing or adapter creation fails.
+pub fn start_email_adapter(
+    orchestrator: &Arc<Orchestrator>,
+    shutdown_controller: &ShutdownController,
+    senders: &ChannelSenders,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let config = match EmailConfig::from_env() {
+        Ok(c) => c,
+        Err(e) => {
+            warn!(error = %e, "Email adapter not started: missing configuration");
+            return None;
+        }
+    };
+
+    let adapter = match EmailAdapter::new(config) {
+        Ok(a) => Arc::new(a),
+        Err(e) => {
+            warn!(error = %e, "Failed to create email adapter");
+            return None;
+        }
+    };
+    senders.register(adapter.clone());
+
+    let orch = orchestrator.clone();
+    let shutdown = shutdown_controller.token();
+
+    let handle = tokio::spawn(async move {
+        if let Err(e) = adapter.run(orch, shutdown).await {
+            error!(error = %e, "Email adapter error");
+        }
+    });
+
+    info!("Email adapter started");
+    Some(handle)
+}
+
 /// Start the Discord adapter
 ///
 /// Creates a Discord bot using serenity and starts the event loop.
@@ -127,6 +209,7 @@ pub fn start_matrix_adapter(
 pub fn start_discord_adapter(
     orchestrator: &Arc<Orchestrator>,
     shutdown_controller: &ShutdownController,
+    senders: &ChannelSenders,
 ) -> Option<tokio::task::JoinHandle<()>> {
     let config = match DiscordConfig::from_env() {
         Ok(c) => c,
@@ -137,6 +220,7 @@ pub fn start_discord_adapter(
     };
 
     let adapter = Arc::new(DiscordAdapter::new(config));
+    senders.register(adapter.clone());
     let orch = orchestrator.clone();
     let shutdown = shutdown_controller.token();
 
@@ -163,6 +247,7 @@ pub fn start_discord_adapter(
 /// Returns None if configuration is missing or adapter creation fails.
 pub fn start_whatsapp_adapter(
     shutdown_controller: &ShutdownController,
+    senders: &ChannelSenders,
 ) -> Option<tokio::task::JoinHandle<()>> {
     let config = match WhatsAppConfig::from_env() {
         Ok(c) => c,
@@ -179,6 +264,7 @@ pub fn start_whatsapp_adapter(
             return None;
         }
     };
+    senders.register(adapter.clone());
 
     let shutdown = shutdown_controller.token();
 
@@ -9,7 +9,8 @@ use cratos_core::{Orchestrator, OrchestratorConfig, PlannerConfig};
 use cratos_llm::LlmProvider;
 use cratos_replay::EventStore;
 use cratos_tools::{
-    register_builtins_with_config, BuiltinsConfig, ExecConfig, ExecMode, RunnerConfig, ToolRegistry,
+    register_builtins_with_config, BashConfig, BuiltinsConfig, ExecConfig, ExecMode, RunnerConfig,
+    ToolRegistry,
 };
 use std::sync::Arc;
 
@@ -35,6 +36,8 @@ pub async fn build_orchestrator_for_cli(config: &AppConfig) -> Result<Arc<Orches
     let llm_provider: Arc<dyn LlmProvider> = llm_router.clone();
 
     let mut tool_registry = ToolRegistry::new();
+    let sandbox_config = config.security.sandbox_config();
+    let sandbox_runtime = config.security.sandbox_runtime(&sandbox_config).await;
     let exec_config = {
         let sec = &config.security.exec;
         let mode = match sec.mode.as_str() {
@@ -48,11 +51,20 @@ pub async fn build_orchestrator_for_cli(config: &AppConfig) -> Result<Arc<Orches
             allowed_commands: sec.allowed_commands.clone(),
             blocked_paths: sec.blocked_paths.clone(),
             allow_network_commands: false,
+            sandbox_runtime,
+            sandbox: Some(sandbox_config.clone()),
             ..ExecConfig::default()
         }
     };
+    let bash_config = BashConfig {
+        sandbox: config
+            .security
+            .bash_sandbox(sandbox_runtime, &sandbox_config),
+        ..BashConfig::default()
+    };
     let builtins_config = BuiltinsConfig {
         exec: exec_config,
+        bash: bash_config,
         ..BuiltinsConfig::default()
     };
     register_builtins_with_config(&mut tool_registry, &builtins_config);
@@ -92,8 +104,13 @@ pub async fn build_orchestrator_for_cli(config: &AppConfig) -> Result<Arc<Orches
         .with_event_store(event_store)
         .with_persona_mapping(cratos_core::PersonaMapping::default_mapping());
 
-    // Auto-detect fallback provider
-    {
+    // Failover chains from [llm.failover], otherwise auto-detect a fallback provider
+    let failover = config.llm.failover.as_ref().and_then(|failover| {
+        cratos_core::FailoverChains::from_config(failover, |name| llm_router.get(name))
+    });
+    if let Some(chains) = failover {
+        orchestrator = orchestrator.with_failover(chains);
+    } else {
         let primary = config.llm.default_provider.clone();
         let fallback_candidates = [
             "groq",
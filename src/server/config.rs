@@ -5,6 +5,7 @@
 use crate::middleware::rate_limit::RateLimitSettings;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
@@ -94,6 +95,21 @@ pub struct OrchestratorAppConfig {
     /// Enable automatic skill pattern detection
     #[serde(default = "default_true")]
     pub auto_skill_detection: bool,
+    /// Summarize old conversation turns instead of dropping them
+    #[serde(default)]
+    pub compaction_enabled: bool,
+    /// Session token count that triggers automatic compaction
+    #[serde(default = "default_compaction_threshold_tokens")]
+    pub compaction_threshold_tokens: usize,
+    /// Number of most recent messages kept verbatim when compacting
+    #[serde(default = "default_compaction_keep_recent")]
+    pub compaction_keep_recent: usize,
+    /// Maximum low-risk tool calls of one turn run concurrently (1 = sequential)
+    #[serde(default = "default_max_parallel_tools")]
+    pub max_parallel_tools: usize,
+    /// Extract Graph RAG entities with a cheap LLM tier (rule-based otherwise)
+    #[serde(default)]
+    pub graph_rag_llm_extraction: bool,
 }
 
 impl Default for OrchestratorAppConfig {
@@ -104,6 +120,11 @@ impl Default for OrchestratorAppConfig {
             max_consecutive_failures: default_max_consecutive_failures(),
             max_total_failures: default_max_total_failures(),
             auto_skill_detection: true,
+            compaction_enabled: false,
+            compaction_threshold_tokens: default_compaction_threshold_tokens(),
+            compaction_keep_recent: default_compaction_keep_recent(),
+            max_parallel_tools: default_max_parallel_tools(),
+            graph_rag_llm_extraction: false,
         }
     }
 }
@@ -120,6 +141,15 @@ fn default_max_consecutive_failures() -> usize {
 fn default_max_total_failures() -> usize {
     6
 }
+fn default_max_parallel_tools() -> usize {
+    4
+}
+fn default_compaction_threshold_tokens() -> usize {
+    60_000
+}
+fn default_compaction_keep_recent() -> usize {
+    10
+}
 
 /// Scheduler configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -135,12 +165,33 @@ pub struct SchedulerAppConfig {
     pub max_concurrent: usize,
     #[serde(default = "default_true")]
     pub logging_enabled: bool,
+    /// How often system triggers sample CPU/memory/disk/network (seconds)
+    #[serde(default = "default_sample_interval")]
+    pub sample_interval_secs: u64,
+    /// How late a scheduled run may start before it counts as missed (seconds)
+    #[serde(default = "default_misfire_grace")]
+    pub misfire_grace_secs: u64,
+    /// Most runs that may wait behind a running task (overlap policy `queue`)
+    #[serde(default = "default_max_queued_runs")]
+    pub max_queued_runs: usize,
 }
 
 fn default_check_interval() -> u64 {
     60
 }
 
+fn default_sample_interval() -> u64 {
+    10
+}
+
+fn default_misfire_grace() -> u64 {
+    120
+}
+
+fn default_max_queued_runs() -> usize {
+    10
+}
+
 fn default_retry_delay() -> u64 {
     30
 }
@@ -173,6 +224,9 @@ pub struct AuthConfig {
     /// Key storage backend: keychain | encrypted_file | memory
     #[serde(default = "default_key_storage")]
     pub key_storage: String,
+    /// Accept bearer JWTs from an OIDC provider (`[server.auth.oidc]`)
+    #[serde(default)]
+    pub oidc: Option<cratos_core::OidcConfig>,
 }
 
 impl Default for AuthConfig {
@@ -181,6 +235,7 @@ impl Default for AuthConfig {
             enabled: true,
             auto_generate_key: true,
             key_storage: "keychain".to_string(),
+            oidc: None,
         }
     }
 }
@@ -207,6 +262,16 @@ pub struct LlmConfig {
     /// Model routing configuration for cost-optimized tiered routing
     #[serde(default)]
     pub model_routing: Option<ModelRoutingConfig>,
+    /// Spending budgets loaded from [llm.budget] in TOML
+    #[serde(default)]
+    pub budget: Option<cratos_llm::BudgetConfig>,
+    /// Directory of local HuggingFace tokenizers (`<dir>/<model>/tokenizer.json`),
+    /// defaults to `<data_dir>/tokenizers`
+    #[serde(default)]
+    pub tokenizer_dir: Option<String>,
+    /// Provider failover chains loaded from [llm.failover] in TOML
+    #[serde(default)]
+    pub failover: Option<cratos_core::FailoverConfig>,
 }
 
 /// Model routing configuration loaded from [llm.model_routing] in TOML
@@ -286,6 +351,9 @@ impl Default for LlmConfig {
             gemini: None,
             routing: None,
             model_routing: None,
+            budget: None,
+            tokenizer_dir: None,
+            failover: None,
         }
     }
 }
@@ -294,6 +362,10 @@ impl Default for ApprovalConfig {
     fn default() -> Self {
         Self {
             default_mode: "never".to_string(),
+            groups: HashMap::new(),
+            rules: Vec::new(),
+            host_owner: None,
+            timeout_secs: default_approval_timeout_secs(),
         }
     }
 }
@@ -314,6 +386,8 @@ impl Default for ChannelsConfig {
             slack: SlackChannelConfig { enabled: false },
             discord: DiscordChannelConfig::default(),
             matrix: MatrixChannelConfig::default(),
+            email: EmailChannelConfig::default(),
+            mattermost: MattermostChannelConfig::default(),
             whatsapp: WhatsAppChannelConfig::default(),
             whatsapp_business: WhatsAppBusinessChannelConfig::default(),
         }
@@ -325,6 +399,22 @@ impl Default for ChannelsConfig {
 
 pub struct ApprovalConfig {
     pub default_mode: String,
+    /// Named approver groups (`[approval.groups]`)
+    #[serde(default)]
+    pub groups: HashMap<String, Vec<String>>,
+    /// Multi-approver / auto-approve rules (`[[approval.rules]]`)
+    #[serde(default)]
+    pub rules: Vec<cratos_core::ApprovalRule>,
+    /// Owner of this machine, who answers `node_owner` rules
+    #[serde(default)]
+    pub host_owner: Option<String>,
+    /// Seconds an approval request waits for an answer
+    #[serde(default = "default_approval_timeout_secs")]
+    pub timeout_secs: i64,
+}
+
+fn default_approval_timeout_secs() -> i64 {
+    300
 }
 
 /// Security configuration
@@ -334,6 +424,8 @@ pub struct SecurityConfig {
     #[serde(default)]
     pub exec: ExecSecurityConfig,
     #[serde(default)]
+    pub sandbox: SandboxSecurityConfig,
+    #[serde(default)]
     pub sandbox_policy: Option<String>,
     #[serde(default)]
     pub credential_backend: Option<String>,
@@ -341,6 +433,132 @@ pub struct SecurityConfig {
     pub enable_injection_protection: Option<bool>,
 }
 
+impl SecurityConfig {
+    /// Sandbox settings for the exec (`host: "sandbox"`) and bash tools
+    pub fn sandbox_config(&self) -> cratos_tools::SandboxConfig {
+        let policy = match self.sandbox_policy.as_deref() {
+            Some("strict") => cratos_tools::SandboxPolicy::Strict,
+            Some("disabled") => cratos_tools::SandboxPolicy::Disabled,
+            _ => cratos_tools::SandboxPolicy::Moderate,
+        };
+        let sandbox = &self.sandbox;
+        cratos_tools::SandboxConfig {
+            policy,
+            default_network: sandbox.default_network,
+            default_limits: cratos_tools::ResourceLimits {
+                memory_bytes: sandbox.max_memory_mb * 1024 * 1024,
+                cpu_percent: sandbox.max_cpu_percent.min(100),
+                timeout: std::time::Duration::from_secs(sandbox.timeout_seconds),
+                max_pids: sandbox.max_pids,
+                ..cratos_tools::ResourceLimits::default()
+            },
+            runtime_preference: sandbox.runtime.clone(),
+            mounts: sandbox.mounts.clone(),
+            cgroup_parent: sandbox.cgroup_parent.clone(),
+            allow_degraded: sandbox.allow_degraded,
+            ..cratos_tools::SandboxConfig::default()
+        }
+    }
+
+    /// Resolve the runtime for `host: "sandbox"` ("docker" skips detection)
+    pub async fn sandbox_runtime(
+        &self,
+        sandbox: &cratos_tools::SandboxConfig,
+    ) -> cratos_tools::ContainerRuntime {
+        match self.sandbox.runtime.as_str() {
+            "docker" => cratos_tools::ContainerRuntime::Docker,
+            _ => sandbox.select_runtime().await,
+        }
+    }
+
+    /// Sandbox for bash tool commands, if enabled and the native runtime is in use
+    pub fn bash_sandbox(
+        &self,
+        runtime: cratos_tools::ContainerRuntime,
+        sandbox: &cratos_tools::SandboxConfig,
+    ) -> Option<cratos_tools::SandboxConfig> {
+        if !self.sandbox.bash || sandbox.policy == cratos_tools::SandboxPolicy::Disabled {
+            return None;
+        }
+        if runtime != cratos_tools::ContainerRuntime::LinuxNative {
+            tracing::warn!(
+                "[security.sandbox] bash = true requires the linux_native runtime; bash runs unsandboxed"
+            );
+            return None;
+        }
+        Some(sandbox.clone())
+    }
+}
+
+/// Sandbox configuration (from [security.sandbox] in TOML)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub struct SandboxSecurityConfig {
+    /// Runtime for sandboxed execution: "docker" (default), "linux_native" or "auto"
+    #[serde(default = "default_sandbox_runtime")]
+    pub runtime: String,
+    /// Network mode: "none" (default), "bridge" or "host"
+    #[serde(default)]
+    pub default_network: cratos_tools::NetworkMode,
+    #[serde(default = "default_sandbox_memory_mb")]
+    pub max_memory_mb: u64,
+    #[serde(default = "default_sandbox_cpu_percent")]
+    pub max_cpu_percent: u32,
+    #[serde(default = "default_sandbox_pids")]
+    pub max_pids: u32,
+    #[serde(default = "default_sandbox_timeout")]
+    pub timeout_seconds: u64,
+    /// Host paths exposed to the native Linux sandbox
+    #[serde(default)]
+    pub mounts: Vec<cratos_tools::Mount>,
+    /// Delegated cgroup v2 directory for native sandbox limits
+    #[serde(default)]
+    pub cgroup_parent: Option<std::path::PathBuf>,
+    /// Let the native Linux sandbox run without Landlock or a seccomp profile
+    #[serde(default)]
+    pub allow_degraded: bool,
+    /// Run bash tool commands in the native Linux sandbox
+    #[serde(default)]
+    pub bash: bool,
+}
+
+impl Default for SandboxSecurityConfig {
+    fn default() -> Self {
+        Self {
+            runtime: default_sandbox_runtime(),
+            default_network: cratos_tools::NetworkMode::None,
+            max_memory_mb: default_sandbox_memory_mb(),
+            max_cpu_percent: default_sandbox_cpu_percent(),
+            max_pids: default_sandbox_pids(),
+            timeout_seconds: default_sandbox_timeout(),
+            mounts: Vec::new(),
+            cgroup_parent: None,
+            allow_degraded: false,
+            bash: false,
+        }
+    }
+}
+
+fn default_sandbox_runtime() -> String {
+    "docker".to_string()
+}
+
+fn default_sandbox_memory_mb() -> u64 {
+    512
+}
+
+fn default_sandbox_cpu_percent() -> u32 {
+    50
+}
+
+fn default_sandbox_pids() -> u32 {
+    100
+}
+
+fn default_sandbox_timeout() -> u64 {
+    60
+}
+
 /// Exec security configuration (from [security.exec] in TOML)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 
@@ -417,6 +635,10 @@ pub struct ChannelsConfig {
     #[serde(default)]
     pub matrix: MatrixChannelConfig,
     #[serde(default)]
+    pub email: EmailChannelConfig,
+    #[serde(default)]
+    pub mattermost: MattermostChannelConfig,
+    #[serde(default)]
     pub whatsapp: WhatsAppChannelConfig,
     #[serde(default)]
     pub whatsapp_business: WhatsAppBusinessChannelConfig,
@@ -450,6 +672,22 @@ pub struct MatrixChannelConfig {
     pub enabled: bool,
 }
 
+/// Email channel config
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+
+pub struct EmailChannelConfig {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Mattermost channel config
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+
+pub struct MattermostChannelConfig {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
 /// WhatsApp (Baileys) channel config
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 
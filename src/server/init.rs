@@ -4,13 +4,16 @@
 
 use super::a2ui_steering::start_a2ui_steering_loop;
 use super::adapters::SkillRouterAdapter;
+use super::approval_resume::start_approval_resume_loop;
 use super::background_tasks::{start_cleanup_task, start_scheduler, start_skill_generation_task};
+use super::channel_senders::ChannelSenders;
 use super::channel_starters::{
-    start_discord_adapter, start_matrix_adapter, start_slack_adapter, start_telegram_adapter,
+    start_discord_adapter, start_email_adapter, start_matrix_adapter, start_mattermost_adapter, start_slack_adapter, start_telegram_adapter,
     start_whatsapp_adapter,
 };
 use super::init_helpers::{
-    init_auth, init_embedding_provider, init_graph_memory, init_vector_search,
+    init_auth, init_cost_tracking, init_embedding_provider, init_entity_extractor, init_graph_memory,
+    init_vector_search,
 };
 use super::init_stores::init_stores;
 use super::loader::load_config;
@@ -21,12 +24,14 @@ use crate::middleware::rate_limit::RateLimitLayer;
 use anyhow::{Context, Result};
 use axum::{routing::get, Extension, Router};
 use cratos_core::{
-    shutdown_signal_with_controller, ApprovalManager, EventBus, OlympusConfig, OlympusHooks,
-    Orchestrator, OrchestratorConfig, PlannerConfig, RedisStore, SessionStore, ShutdownController,
+    shutdown_signal_with_controller, ApprovalManager, ApprovalPolicy, CompactionConfig, EventBus,
+    OlympusConfig, OlympusHooks, Orchestrator, OrchestratorConfig, PlannerConfig, RedisStore,
+    SessionStore, ShutdownController,
 };
 use cratos_llm::LlmProvider;
 use cratos_tools::{
-    register_builtins_with_config, BuiltinsConfig, ExecConfig, ExecMode, RunnerConfig, ToolRegistry,
+    register_builtins_with_config, BashConfig, BuiltinsConfig, ExecConfig, ExecMode, RunnerConfig,
+    ToolRegistry,
 };
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -34,8 +39,15 @@ use tokio::sync::mpsc;
 use tower_http::{cors::CorsLayer, services::ServeDir};
 use tracing::{debug, info, warn};
 
+/// Options for `cratos serve`
+#[derive(Debug, Clone, Default)]
+pub struct ServeOptions {
+    /// Expose the MCP bridge over HTTP at `/mcp`
+    pub mcp_http: bool,
+}
+
 /// Run the server
-pub async fn run() -> Result<()> {
+pub async fn run(options: ServeOptions) -> Result<()> {
     info!(
         "Starting Cratos AI Assistant v{}",
         env!("CARGO_PKG_VERSION")
@@ -76,6 +88,14 @@ pub async fn run() -> Result<()> {
     )
     .await?;
 
+    let tokenizer_dir = config
+        .llm
+        .tokenizer_dir
+        .clone()
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| data_dir.join("tokenizers"));
+    cratos_llm::global_tokenizers().set_local_dir(Some(tokenizer_dir));
+
     let llm_router = resolve_llm_provider(&config.llm)?;
     let llm_provider: Arc<dyn LlmProvider> = llm_router.clone();
     info!("LLM provider initialized: {}", llm_provider.name());
@@ -105,6 +125,8 @@ pub async fn run() -> Result<()> {
     let mut tool_registry = ToolRegistry::new();
     // Convert security config to ExecConfig
     let exec_timeout_secs = config.security.exec.max_timeout_secs;
+    let sandbox_config = config.security.sandbox_config();
+    let sandbox_runtime = config.security.sandbox_runtime(&sandbox_config).await;
     let exec_config = {
         let sec = &config.security.exec;
         let mode = match sec.mode.as_str() {
@@ -118,9 +140,17 @@ pub async fn run() -> Result<()> {
             allowed_commands: sec.allowed_commands.clone(),
             blocked_paths: sec.blocked_paths.clone(),
             allow_network_commands: false,
+            sandbox_runtime,
+            sandbox: Some(sandbox_config.clone()),
             ..ExecConfig::default()
         }
     };
+    let bash_config = BashConfig {
+        sandbox: config
+            .security
+            .bash_sandbox(sandbox_runtime, &sandbox_config),
+        ..BashConfig::default()
+    };
 
     // Initialize A2UI Session Manager if Canvas is enabled
     let a2ui_manager = canvas_state
@@ -134,6 +164,7 @@ pub async fn run() -> Result<()> {
 
     let builtins_config = BuiltinsConfig {
         exec: exec_config,
+        bash: bash_config,
         a2ui_manager,
         session_sender: Some(a2a_router.clone()), // Injected A2A router
         ..BuiltinsConfig::default()
@@ -146,10 +177,12 @@ pub async fn run() -> Result<()> {
 
     // MCP tool auto-registration from .mcp.json
     let mcp_json_path = std::path::Path::new(".mcp.json");
+    let mut mcp_client = None;
     if mcp_json_path.exists() {
         match cratos_tools::mcp::register_mcp_tools(&mut tool_registry, mcp_json_path).await {
-            Ok(_mcp_client) => {
+            Ok(client) => {
                 info!("MCP tools registered from .mcp.json");
+                mcp_client = Some(client);
             }
             Err(e) => warn!("Failed to register MCP tools: {}", e),
         }
@@ -160,6 +193,7 @@ pub async fn run() -> Result<()> {
         &data_dir,
         &vectors_dir,
         &embedding_provider,
+        init_entity_extractor(&config, &llm_router),
         &mut tool_registry,
     )
     .await;
@@ -188,11 +222,18 @@ pub async fn run() -> Result<()> {
     let mut orchestrator_config = OrchestratorConfig::new()
         .with_max_iterations(config.orchestrator.max_iterations)
         .with_auto_skill_detection(config.orchestrator.auto_skill_detection)
+        .with_max_parallel_tools(config.orchestrator.max_parallel_tools)
         .with_logging(true);
     orchestrator_config.max_execution_secs = config.orchestrator.max_execution_secs;
     orchestrator_config.max_consecutive_failures = config.orchestrator.max_consecutive_failures;
     orchestrator_config.max_total_failures = config.orchestrator.max_total_failures;
     let orchestrator_config = orchestrator_config
+        .with_compaction(
+            CompactionConfig::new()
+                .with_enabled(config.orchestrator.compaction_enabled)
+                .with_threshold(config.orchestrator.compaction_threshold_tokens)
+                .with_keep_recent(config.orchestrator.compaction_keep_recent),
+        )
         .with_planner_config({
             // Resolve actual provider name (not "router")
             let (prov_name, model_name) = if llm_provider.name() == "router" {
@@ -218,10 +259,29 @@ pub async fn run() -> Result<()> {
         })
         .with_runner_config(runner_config);
 
-    let approval_manager = Arc::new(ApprovalManager::new());
+    // ApprovalManager — try SQLite so pending approvals survive restarts, fall back to in-memory
+    let approval_policy = ApprovalPolicy {
+        groups: config.approval.groups.clone(),
+        rules: config.approval.rules.clone(),
+        host_owner: config.approval.host_owner.clone(),
+    };
+    let approval_timeout = config.approval.timeout_secs;
+    let approval_manager =
+        match ApprovalManager::new_with_db(event_store.pool().clone(), approval_timeout).await {
+            Ok(mgr) => mgr,
+            Err(e) => {
+                warn!(
+                    "ApprovalManager SQLite init failed ({}), using in-memory",
+                    e
+                );
+                ApprovalManager::with_timeout_secs(approval_timeout)
+            }
+        };
+    let approval_manager = Arc::new(approval_manager.with_policy(approval_policy));
     info!(
-        "Approval manager initialized (mode: {})",
-        config.approval.default_mode
+        "Approval manager initialized (mode: {}, rules: {})",
+        config.approval.default_mode,
+        config.approval.rules.len()
     );
 
     let olympus_hooks = OlympusHooks::new(OlympusConfig::default());
@@ -230,6 +290,8 @@ pub async fn run() -> Result<()> {
     let event_bus = Arc::new(EventBus::new(256));
     info!("EventBus initialized (capacity: 256)");
 
+    init_cost_tracking(&config, event_store.pool().clone(), event_bus.clone()).await;
+
     let mut orchestrator = Orchestrator::new(
         llm_provider.clone(),
         tool_registry.clone(),
@@ -238,7 +300,7 @@ pub async fn run() -> Result<()> {
     .with_event_store(event_store.clone())
     .with_event_bus(event_bus.clone())
     .with_memory(session_store)
-    .with_approval_manager(approval_manager)
+    .with_approval_manager(approval_manager.clone())
     .with_olympus_hooks(olympus_hooks)
     .with_persona_mapping(cratos_core::PersonaMapping::default_mapping());
 
@@ -247,10 +309,23 @@ pub async fn run() -> Result<()> {
     if let Some(gm) = graph_memory {
         orchestrator = orchestrator.with_graph_memory(gm);
     }
+    if let Some(client) = mcp_client {
+        orchestrator = orchestrator.with_mcp_client(client);
+    }
 
-    // Phase 4: Configure fallback provider
-    // Priority: explicit model_routing.fallback config > auto-detect from candidates
-    {
+    // Phase 4: Configure failover chains, or a single fallback provider
+    // Priority: [llm.failover] chains > explicit model_routing.fallback config
+    // > auto-detect from candidates
+    let failover = config.llm.failover.as_ref().and_then(|failover| {
+        cratos_core::FailoverChains::from_config(failover, |name| llm_router.get(name))
+    });
+    if let Some(chains) = failover {
+        info!(
+            chain = ?chains.for_task(cratos_llm::TaskType::Planning).provider_names(),
+            "LLM failover chains configured"
+        );
+        orchestrator = orchestrator.with_failover(chains);
+    } else {
         let primary = config.llm.default_provider.clone();
 
         // 1) Check explicit fallback from [llm.model_routing] in TOML/env
@@ -313,6 +388,17 @@ pub async fn run() -> Result<()> {
     // Start A2UI Steering Loop
     start_a2ui_steering_loop(orchestrator.clone(), a2ui_rx);
 
+    // Channel adapters register here as they start, for replies sent
+    // outside a conversation
+    let channel_senders = ChannelSenders::new();
+
+    // Resume executions that were parked on an approval before a restart
+    start_approval_resume_loop(
+        orchestrator.clone(),
+        approval_manager.clone(),
+        channel_senders.clone(),
+    );
+
     // Dev Session Monitor (AI session detection) - created early for channel use
     let dev_monitor = Arc::new(cratos_core::DevSessionMonitor::new(
         std::time::Duration::from_secs(30),
@@ -324,38 +410,67 @@ pub async fn run() -> Result<()> {
 
     // Start Telegram adapter
     if config.channels.telegram.enabled {
-        if let Some(handle) =
-            start_telegram_adapter(&orchestrator, &dev_monitor, &shutdown_controller)
-        {
+        if let Some(handle) = start_telegram_adapter(
+            &orchestrator,
+            &dev_monitor,
+            &shutdown_controller,
+            &channel_senders,
+        ) {
             channel_handles.push(handle);
         }
     }
 
     // Start Slack adapter
     if config.channels.slack.enabled {
-        if let Some(handle) = start_slack_adapter(&orchestrator, &shutdown_controller) {
+        if let Some(handle) =
+            start_slack_adapter(&orchestrator, &shutdown_controller, &channel_senders)
+        {
             channel_handles.push(handle);
         }
     }
 
     // Start WhatsApp adapter (Baileys bridge)
     if config.channels.whatsapp.enabled {
-        if let Some(handle) = start_whatsapp_adapter(&shutdown_controller) {
+        if let Some(handle) = start_whatsapp_adapter(&shutdown_controller, &channel_senders) {
             channel_handles.push(handle);
         }
     }
 
     // Start Discord adapter
     if config.channels.discord.enabled {
-        if let Some(handle) = start_discord_adapter(&orchestrator, &shutdown_controller) {
+        if let Some(handle) =
+            start_discord_adapter(&orchestrator, &shutdown_controller, &channel_senders)
+        {
             channel_handles.push(handle);
         }
     }
 
     // Start Matrix adapter
     if config.channels.matrix.enabled {
-        if let Some(handle) = start_matrix_adapter(&orchestrator, &shutdown_controller) {
+        if let Some(handle) =
+            start_matrix_adapter(&orchestrator, &shutdown_controller, &channel_senders)
+        {
+            channel_handles.push(handle);
+        }
+    }
+
+    // Start email adapter
+    if config.channels.email.enabled {
+        if let Some(handle) =
+            start_email_adapter(&orchestrator, &shutdown_controller, &channel_senders)
+        {
+            channel_handles.push(handle);
+        }
+    }
+
+    // Start Mattermost adapter (kept for the button callback route)
+    let mut mattermost_adapter = None;
+    if config.channels.mattermost.enabled {
+        if let Some((handle, adapter)) =
+            start_mattermost_adapter(&orchestrator, &shutdown_controller, &channel_senders)
+        {
             channel_handles.push(handle);
+            mattermost_adapter = Some(adapter);
         }
     }
 
@@ -364,8 +479,10 @@ pub async fn run() -> Result<()> {
         &config,
         &data_dir,
         &orchestrator,
+        &llm_provider,
         &event_bus,
         &skill_store,
+        &channel_senders,
         &shutdown_controller,
     )
     .await;
@@ -381,6 +498,7 @@ pub async fn run() -> Result<()> {
     // Cleanup task
     start_cleanup_task(
         &event_store,
+        &approval_manager,
         config.replay.retention_days,
         &shutdown_controller,
     );
@@ -390,7 +508,7 @@ pub async fn run() -> Result<()> {
     // ================================================================
     // Authentication
     // ================================================================
-    let auth_store = init_auth(&config);
+    let auth_store = init_auth(&config, event_store.pool().clone()).await;
 
     // ================================================================
     // Rate Limiting
@@ -477,6 +595,23 @@ pub async fn run() -> Result<()> {
         info!("Web UI enabled: serving from {}", web_ui_dir.display());
     }
 
+    // MCP server over HTTP (tools, memories, executions, skills, personas)
+    let mcp_http_routes = if options.mcp_http {
+        let mut bridge = crate::acp::mcp_compat::McpBridge::new(
+            tool_registry.clone(),
+            orchestrator.clone(),
+        )
+        .with_event_store(event_store.clone())
+        .with_skill_store(skill_store.clone());
+        if let Some(gm) = graph_memory_ext.clone() {
+            bridge = bridge.with_graph_memory(gm);
+        }
+        info!("MCP HTTP server enabled at /mcp");
+        crate::acp::mcp_http::mcp_http_router(bridge)
+    } else {
+        Router::new()
+    };
+
     // Build the main router with all endpoints
     let config_state = crate::api::config::ConfigState::with_config(&config);
     let app = Router::new()
@@ -491,6 +626,8 @@ pub async fn run() -> Result<()> {
         ))
         // WebSocket routes
         .merge(crate::websocket::websocket_router())
+        // MCP server routes (empty unless --mcp-http)
+        .merge(mcp_http_routes)
         // Layers (applied to all routes)
         .layer(Extension(redis_url_for_health))
         .layer(Extension(auth_store))
@@ -507,6 +644,7 @@ pub async fn run() -> Result<()> {
         .layer(Extension(graph_memory_ext))
         .layer(Extension(e2e_ciphers))
         .layer(Extension(pairing_manager))
+        .layer(Extension(approval_manager))
         .layer(Extension(challenge_store))
         .layer(rate_limit_layer)
         .layer(CorsLayer::permissive());
@@ -532,6 +670,13 @@ pub async fn run() -> Result<()> {
         app
     };
 
+    // Conditionally add Mattermost adapter Extension
+    let app = if let Some(mm_adapter) = mattermost_adapter {
+        app.layer(Extension(mm_adapter))
+    } else {
+        app
+    };
+
     // Add Web UI static file serving (SPA fallback) or simple text response
     let app = if serve_web_ui {
         // Serve static files, fallback to index.html for SPA routing
@@ -5,9 +5,14 @@
 use super::adapters::{EmbeddingAdapter, SkillEmbeddingAdapter};
 use super::config::AppConfig;
 use anyhow::{Context, Result};
-use cratos_core::{admin_scopes, AuthStore};
-use cratos_llm::{EmbeddingProvider, SharedEmbeddingProvider, TractEmbeddingProvider};
-use cratos_memory::{GraphMemory, VectorBridge};
+use cratos_core::{
+    admin_scopes, AuthStore, EventBus, ExternalAuthRegistry, OidcVerifier, OrchestratorEvent,
+};
+use cratos_llm::{
+    CostLedger, EmbeddingProvider, LlmRouter, ModelTier, SharedEmbeddingProvider,
+    TractEmbeddingProvider,
+};
+use cratos_memory::{EntityExtractor, GraphMemory, LlmExtractor, VectorBridge};
 use cratos_replay::EventStore;
 use cratos_search::{IndexConfig, VectorIndex};
 use cratos_skills::{SemanticSkillRouter, SkillRegistry};
@@ -90,16 +95,47 @@ pub async fn init_vector_search(
     }
 }
 
+/// Build the LLM entity extractor for Graph RAG indexing, if enabled
+///
+/// Uses the cheapest tier model of the default provider.
+pub fn init_entity_extractor(
+    config: &AppConfig,
+    llm_router: &LlmRouter,
+) -> Option<Arc<dyn EntityExtractor>> {
+    if !config.orchestrator.graph_rag_llm_extraction {
+        return None;
+    }
+    let Some(provider) = llm_router.default_provider() else {
+        warn!("LLM entity extraction enabled, but no default provider is available");
+        return None;
+    };
+    let model = ModelTier::UltraBudget
+        .model_for(provider.name())
+        .map(str::to_string)
+        .unwrap_or_else(|| provider.default_model().to_string());
+    info!(
+        provider = provider.name(),
+        model = %model,
+        "Graph RAG LLM entity extraction enabled"
+    );
+    Some(Arc::new(LlmExtractor::new(provider, model)))
+}
+
 /// Initialize Graph RAG memory
 pub async fn init_graph_memory(
     data_dir: &std::path::Path,
     vectors_dir: &std::path::Path,
     embedding_provider: &Option<SharedEmbeddingProvider>,
+    entity_extractor: Option<Arc<dyn EntityExtractor>>,
     tool_registry: &mut ToolRegistry,
 ) -> Option<Arc<GraphMemory>> {
     let memory_db_path = data_dir.join("memory.db");
     match GraphMemory::from_path(&memory_db_path).await {
         Ok(gm) => {
+            let gm = match entity_extractor {
+                Some(extractor) => gm.with_extractor(extractor),
+                None => gm,
+            };
             let gm = if let Some(ref embedder) = embedding_provider {
                 let dimensions = embedder.dimensions();
                 // Turn embedding index
@@ -150,14 +186,91 @@ pub async fn init_graph_memory(
     }
 }
 
+/// Initialize LLM cost tracking
+///
+/// Usage records are persisted in the given database, so spending survives
+/// restarts. Budget alerts are forwarded to the event bus.
+pub async fn init_cost_tracking(
+    config: &AppConfig,
+    db: sqlx::SqlitePool,
+    event_bus: Arc<EventBus>,
+) {
+    let tracker = cratos_llm::global_tracker();
+    match CostLedger::new(db).await {
+        Ok(ledger) => tracker.set_ledger(ledger).await,
+        Err(e) => {
+            warn!("Failed to open the LLM cost ledger, spending will not survive a restart: {e}")
+        }
+    }
+
+    if let Some(budget) = &config.llm.budget {
+        info!(
+            users = budget.users.len(),
+            providers = budget.providers.len(),
+            action = ?budget.action,
+            "LLM budgets configured"
+        );
+    }
+    tracker.set_budgets(config.llm.budget.clone()).await;
+
+    let mut alerts = tracker.subscribe_alerts();
+    tokio::spawn(async move {
+        loop {
+            match alerts.recv().await {
+                Ok(alert) => {
+                    event_bus.publish(OrchestratorEvent::BudgetWarning {
+                        scope: alert.scope.to_string(),
+                        name: alert.name,
+                        period: alert.period.to_string(),
+                        spent_usd: alert.spent_usd,
+                        limit_usd: alert.limit_usd,
+                        exceeded: alert.exceeded,
+                    });
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                    warn!("Budget alert forwarding lagged by {} alerts", n);
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+/// How often the server picks up API keys changed through the CLI
+///
+/// A key revoked through the CLI stays valid in a running server for up to
+/// this long; revoke through the API for immediate effect.
+const AUTH_SYNC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// Initialize authentication store
-pub fn init_auth(config: &AppConfig) -> Arc<AuthStore> {
+///
+/// API keys are persisted (hashed) in the given database, so they survive
+/// restarts. With `[server.auth.oidc]` configured, bearer JWTs from that
+/// provider are accepted as well.
+pub async fn init_auth(config: &AppConfig, db: sqlx::SqlitePool) -> Arc<AuthStore> {
     let auth_enabled = config.server.auth.enabled;
-    let auth_store = Arc::new(AuthStore::new(auth_enabled));
+    let mut auth_store = match AuthStore::new_with_db(auth_enabled, db).await {
+        Ok(store) => store,
+        Err(e) => {
+            warn!("Failed to load persisted API keys, keys will not survive a restart: {e}");
+            AuthStore::new(auth_enabled)
+        }
+    };
+    if let Some(oidc) = &config.server.auth.oidc {
+        info!(issuer = %oidc.issuer, "OIDC bearer token authentication enabled");
+        let mut registry = ExternalAuthRegistry::new();
+        registry.register(Box::new(OidcVerifier::new(oidc.clone())));
+        auth_store = auth_store.with_external_auth(Arc::new(registry));
+    }
+    let auth_store = Arc::new(auth_store);
+    auth_store.spawn_sync(AUTH_SYNC_INTERVAL);
 
     if auth_enabled && config.server.auth.auto_generate_key && auth_store.active_key_count() == 0 {
         // Auto-generate admin API key on first run
-        match auth_store.generate_api_key("admin", admin_scopes(), "auto-generated admin key") {
+        match auth_store
+            .generate_api_key("admin", admin_scopes(), "auto-generated admin key")
+            .await
+        {
             Ok((key, _hash)) => {
                 info!("==========================================================");
                 info!("  AUTO-GENERATED ADMIN API KEY (save this, shown once!):");
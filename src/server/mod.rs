@@ -14,7 +14,9 @@
 
 mod a2ui_steering;
 pub mod adapters;
+mod approval_resume;
 mod background_tasks;
+mod channel_senders;
 mod channel_starters;
 mod cli;
 pub mod config;
@@ -29,6 +31,6 @@ mod validation;
 
 // Re-export public API
 pub use cli::build_orchestrator_for_cli;
-pub use init::run;
+pub use init::{run, ServeOptions};
 pub use loader::{load_config, DEFAULT_CONFIG};
 pub use providers::resolve_llm_provider;
@@ -5,10 +5,10 @@
 use super::config::LlmConfig;
 use anyhow::Result;
 use cratos_llm::{
-    AnthropicConfig, AnthropicProvider, DeepSeekConfig, DeepSeekProvider, GeminiConfig,
-    GeminiProvider, GlmConfig, GlmProvider, GroqConfig, GroqProvider, LlmRouter, MoonshotConfig,
-    MoonshotProvider, NovitaConfig, NovitaProvider, OllamaConfig, OllamaProvider, OpenAiConfig,
-    OpenAiProvider, OpenRouterConfig, OpenRouterProvider, QwenConfig, QwenProvider,
+    global_tracker, AnthropicConfig, AnthropicProvider, DeepSeekConfig, DeepSeekProvider,
+    GeminiConfig, GeminiProvider, GlmConfig, GlmProvider, GroqConfig, GroqProvider, LlmRouter,
+    MoonshotConfig, MoonshotProvider, NovitaConfig, NovitaProvider, OllamaConfig, OllamaProvider,
+    OpenAiConfig, OpenAiProvider, OpenRouterConfig, OpenRouterProvider, QwenConfig, QwenProvider,
 };
 use std::sync::Arc;
 use tracing::{debug, info, warn};
@@ -214,5 +214,8 @@ pub fn resolve_llm_provider(llm_config: &LlmConfig) -> Result<Arc<LlmRouter>> {
         router.list_providers()
     );
 
+    // Record usage and apply budgets for every provider call
+    router.set_cost_tracker(global_tracker());
+
     Ok(Arc::new(router))
 }
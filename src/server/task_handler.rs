@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use cratos_channels::OutgoingMessage;
 use cratos_core::orchestrator::{Orchestrator, OrchestratorInput};
 use cratos_core::scheduler::{SchedulerError, TaskAction};
 use cratos_core::EventBus;
@@ -8,6 +9,7 @@ use cratos_tools::builtins::{ExecConfig, ExecMode, ExecTool};
 use cratos_tools::registry::Tool;
 use tracing::{info, warn};
 
+use crate::server::channel_senders::ChannelSenders;
 use crate::server::config::SecurityConfig;
 
 /// Execute a scheduled task action
@@ -17,8 +19,12 @@ pub async fn execute_task(
     _event_bus: Arc<EventBus>,
     skill_store: Arc<SkillStore>,
     security_config: SecurityConfig,
+    senders: ChannelSenders,
 ) -> Result<String, SchedulerError> {
     match action {
+        TaskAction::Chain { .. } => Err(SchedulerError::Execution(
+            "Task chains are run step by step by the scheduler engine".to_string(),
+        )),
         TaskAction::PruneStaleSkills { days } => {
             info!("Pruning skills older than {} days...", days);
             match skill_store.prune_stale_skills(days).await {
@@ -73,21 +79,22 @@ pub async fn execute_task(
             channel_id,
             message,
         } => {
-            // Send notification via configured channels
-            // Currently supporting a simple log or potential integration
-            info!(
-                channel = %channel,
-                channel_id = %channel_id,
-                "Scheduled Notification: {}",
-                message
-            );
-
-            // Here we could integration with NotificationService if available
-            // For MVP, logging and return success is sufficient unless an adapter is active
-
-            Ok(format!("Notification sent to {}: {}", channel, message))
+            info!(channel = %channel, channel_id = %channel_id, "Sending scheduled notification");
+            match senders
+                .send(&channel, &channel_id, OutgoingMessage::text(message))
+                .await
+            {
+                Ok(message_id) => Ok(format!(
+                    "Notification sent to {}:{} (message {})",
+                    channel, channel_id, message_id
+                )),
+                Err(e) => {
+                    warn!(channel = %channel, error = %e, "Failed to send scheduled notification");
+                    Err(SchedulerError::Execution(e.to_string()))
+                }
+            }
         }
-        TaskAction::Shell { command, cwd } => {
+        TaskAction::Shell { command, args, cwd } => {
             info!("Executing scheduled shell command (secure): {}", command);
 
             // Convert server SecurityConfig to tools ExecConfig
@@ -108,7 +115,8 @@ pub async fn execute_task(
             let tool = ExecTool::with_config(exec_config);
 
             let mut input = serde_json::json!({
-                "command": command
+                "command": command,
+                "args": args
             });
 
             if let Some(dir) = cwd {
@@ -2,8 +2,12 @@
 //!
 //! Registered as a built-in tool so users can say "기억해줘" or "그때 그거 뭐였지?"
 //! and have knowledge persisted across sessions.
+//!
+//! Memories belong to the caller: the orchestrator and the MCP bridge run the
+//! tool within the requesting user's [`MemoryScope`]. Calls made without one
+//! are refused rather than run with the unrestricted scope.
 
-use cratos_memory::GraphMemory;
+use cratos_memory::{GraphMemory, MemoryScope, MemoryVisibility};
 use cratos_tools::registry::{RiskLevel, Tool, ToolCategory, ToolDefinition, ToolResult};
 use serde_json::json;
 use std::sync::Arc;
@@ -52,6 +56,10 @@ impl MemoryTool {
                 "query": {
                     "type": "string",
                     "description": "Search query (required for recall)"
+                },
+                "shared": {
+                    "type": "boolean",
+                    "description": "Share the saved memory with the whole team (default: private)"
                 }
             },
             "required": ["action"]
@@ -74,6 +82,11 @@ impl Tool for MemoryTool {
 
     async fn execute(&self, input: serde_json::Value) -> cratos_tools::Result<ToolResult> {
         let start = Instant::now();
+        let scope = MemoryScope::current().ok_or_else(|| {
+            cratos_tools::Error::PermissionDenied(
+                "The memory tool can only be used on behalf of a caller".to_string(),
+            )
+        })?;
 
         let action = input
             .get("action")
@@ -113,10 +126,15 @@ impl Tool for MemoryTool {
                             .collect()
                     })
                     .unwrap_or_default();
+                let visibility = if input.get("shared").and_then(|v| v.as_bool()) == Some(true) {
+                    MemoryVisibility::Team
+                } else {
+                    MemoryVisibility::Private
+                };
 
                 match self
                     .graph_memory
-                    .save_memory(name, content, category, &tags)
+                    .save_memory(&scope, name, content, category, &tags, visibility)
                     .await
                 {
                     Ok(id) => json!({
@@ -125,6 +143,7 @@ impl Tool for MemoryTool {
                         "name": name,
                         "category": category,
                         "tags": tags,
+                        "visibility": visibility.to_string(),
                     }),
                     Err(e) => json!({"error": format!("save failed: {e}")}),
                 }
@@ -141,7 +160,7 @@ impl Tool for MemoryTool {
                     }
                 };
 
-                match self.graph_memory.recall_memories(query, 5).await {
+                match self.graph_memory.recall_memories(&scope, query, 5).await {
                     Ok(memories) if memories.is_empty() => {
                         json!({"status": "no_results", "query": query})
                     }
@@ -166,7 +185,7 @@ impl Tool for MemoryTool {
 
             "list" => {
                 let category = input.get("category").and_then(|v| v.as_str());
-                match self.graph_memory.list_memories(category, 20).await {
+                match self.graph_memory.list_memories(&scope, category, 20).await {
                     Ok(memories) => {
                         let items: Vec<serde_json::Value> = memories
                             .iter()
@@ -206,7 +225,7 @@ impl Tool for MemoryTool {
 
                 match self
                     .graph_memory
-                    .update_memory(name, content, category, tags.as_deref())
+                    .update_memory(&scope, name, content, category, tags.as_deref())
                     .await
                 {
                     Ok(()) => json!({"status": "updated", "name": name}),
@@ -225,7 +244,7 @@ impl Tool for MemoryTool {
                     }
                 };
 
-                match self.graph_memory.delete_memory(name).await {
+                match self.graph_memory.delete_memory(&scope, name).await {
                     Ok(true) => json!({"status": "deleted", "name": name}),
                     Ok(false) => json!({"status": "not_found", "name": name}),
                     Err(e) => json!({"error": format!("delete failed: {e}")}),
@@ -6,6 +6,11 @@ async fn make_tool() -> Result<MemoryTool, Box<dyn std::error::Error>> {
     Ok(MemoryTool::new(Arc::new(gm)))
 }
 
+/// Run the tool as a single user
+async fn run(tool: &MemoryTool, input: serde_json::Value) -> cratos_tools::Result<ToolResult> {
+    MemoryScope::user("tester").scope(tool.execute(input)).await
+}
+
 #[tokio::test]
 async fn test_memory_tool_definition() -> Result<(), Box<dyn std::error::Error>> {
     let tool = make_tool().await?;
@@ -20,25 +25,29 @@ async fn test_save_and_recall() -> Result<(), Box<dyn std::error::Error>> {
     let tool = make_tool().await?;
 
     // Save
-    let result = tool
-        .execute(json!({
+    let result = run(
+        &tool,
+        json!({
             "action": "save",
             "name": "api-secret",
             "content": "The API key for service X is abc123",
             "category": "knowledge",
             "tags": ["api", "secret"]
-        }))
-        .await?;
+        }),
+    )
+    .await?;
     assert!(result.success);
     assert_eq!(result.output["status"], "saved");
 
     // Recall
-    let result = tool
-        .execute(json!({
+    let result = run(
+        &tool,
+        json!({
             "action": "recall",
             "query": "api-secret"
-        }))
-        .await?;
+        }),
+    )
+    .await?;
     assert!(result.success);
     assert_eq!(result.output["status"], "found");
     assert_eq!(result.output["count"], 1);
@@ -49,14 +58,17 @@ async fn test_save_and_recall() -> Result<(), Box<dyn std::error::Error>> {
 async fn test_list_memories() -> Result<(), Box<dyn std::error::Error>> {
     let tool = make_tool().await?;
 
-    tool.execute(json!({
-        "action": "save",
-        "name": "note-1",
-        "content": "First note"
-    }))
+    run(
+        &tool,
+        json!({
+            "action": "save",
+            "name": "note-1",
+            "content": "First note"
+        }),
+    )
     .await?;
 
-    let result = tool.execute(json!({"action": "list"})).await?;
+    let result = run(&tool, json!({"action": "list"})).await?;
     assert!(result.success);
     assert_eq!(result.output["count"], 1);
     Ok(())
@@ -66,22 +78,21 @@ async fn test_list_memories() -> Result<(), Box<dyn std::error::Error>> {
 async fn test_delete_memory() -> Result<(), Box<dyn std::error::Error>> {
     let tool = make_tool().await?;
 
-    tool.execute(json!({
-        "action": "save",
-        "name": "temp",
-        "content": "temporary"
-    }))
+    run(
+        &tool,
+        json!({
+            "action": "save",
+            "name": "temp",
+            "content": "temporary"
+        }),
+    )
     .await?;
 
-    let result = tool
-        .execute(json!({"action": "delete", "name": "temp"}))
-        .await?;
+    let result = run(&tool, json!({"action": "delete", "name": "temp"})).await?;
     assert_eq!(result.output["status"], "deleted");
 
     // Delete again → not_found
-    let result = tool
-        .execute(json!({"action": "delete", "name": "temp"}))
-        .await?;
+    let result = run(&tool, json!({"action": "delete", "name": "temp"})).await?;
     assert_eq!(result.output["status"], "not_found");
     Ok(())
 }
@@ -90,26 +101,29 @@ async fn test_delete_memory() -> Result<(), Box<dyn std::error::Error>> {
 async fn test_update_memory() -> Result<(), Box<dyn std::error::Error>> {
     let tool = make_tool().await?;
 
-    tool.execute(json!({
-        "action": "save",
-        "name": "evolving",
-        "content": "version 1"
-    }))
+    run(
+        &tool,
+        json!({
+            "action": "save",
+            "name": "evolving",
+            "content": "version 1"
+        }),
+    )
     .await?;
 
-    let result = tool
-        .execute(json!({
+    let result = run(
+        &tool,
+        json!({
             "action": "update",
             "name": "evolving",
             "content": "version 2"
-        }))
-        .await?;
+        }),
+    )
+    .await?;
     assert_eq!(result.output["status"], "updated");
 
     // Recall and verify content
-    let result = tool
-        .execute(json!({"action": "recall", "query": "evolving"}))
-        .await?;
+    let result = run(&tool, json!({"action": "recall", "query": "evolving"})).await?;
     let mems = result.output["memories"]
         .as_array()
         .ok_or("Expected memories array")?;
@@ -120,9 +134,7 @@ async fn test_update_memory() -> Result<(), Box<dyn std::error::Error>> {
 #[tokio::test]
 async fn test_recall_empty() -> Result<(), Box<dyn std::error::Error>> {
     let tool = make_tool().await?;
-    let result = tool
-        .execute(json!({"action": "recall", "query": "nonexistent"}))
-        .await?;
+    let result = run(&tool, json!({"action": "recall", "query": "nonexistent"})).await?;
     assert_eq!(result.output["status"], "no_results");
     Ok(())
 }
@@ -132,17 +144,63 @@ async fn test_missing_required_fields() -> Result<(), Box<dyn std::error::Error>
     let tool = make_tool().await?;
 
     // Save without name
-    let r = tool
-        .execute(json!({"action": "save", "content": "x"}))
-        .await?;
+    let r = run(&tool, json!({"action": "save", "content": "x"})).await?;
     assert!(r.output.get("error").is_some());
 
     // Save without content
-    let r = tool.execute(json!({"action": "save", "name": "x"})).await?;
+    let r = run(&tool, json!({"action": "save", "name": "x"})).await?;
     assert!(r.output.get("error").is_some());
 
     // Recall without query
-    let r = tool.execute(json!({"action": "recall"})).await?;
+    let r = run(&tool, json!({"action": "recall"})).await?;
     assert!(r.output.get("error").is_some());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_memories_scoped_to_caller() -> Result<(), Box<dyn std::error::Error>> {
+    let tool = make_tool().await?;
+
+    MemoryScope::user("alice")
+        .scope(tool.execute(json!({
+            "action": "save",
+            "name": "alice-note",
+            "content": "Alice's deploy checklist"
+        })))
+        .await?;
+    MemoryScope::user("alice")
+        .scope(tool.execute(json!({
+            "action": "save",
+            "name": "team-note",
+            "content": "Shared deploy checklist",
+            "shared": true
+        })))
+        .await?;
+
+    let result = MemoryScope::user("bob")
+        .scope(tool.execute(json!({"action": "recall", "query": "deploy"})))
+        .await?;
+    let mems = result.output["memories"]
+        .as_array()
+        .ok_or("Expected memories array")?;
+    assert_eq!(mems.len(), 1);
+    assert_eq!(mems[0]["name"], "team-note");
+
+    // Only the owner may delete a shared memory
+    let result = MemoryScope::user("bob")
+        .scope(tool.execute(json!({"action": "delete", "name": "team-note"})))
+        .await?;
+    assert_eq!(result.output["status"], "not_found");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unscoped_call_is_refused() -> Result<(), Box<dyn std::error::Error>> {
+    let tool = make_tool().await?;
+    let result = tool.execute(json!({"action": "list"})).await;
+    assert!(matches!(
+        result,
+        Err(cratos_tools::Error::PermissionDenied(_))
+    ));
+    Ok(())
+}
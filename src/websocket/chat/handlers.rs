@@ -354,6 +354,11 @@ async fn stream_events(
                         is_final: *is_final,
                         persona: persona.to_string(),
                     })),
+                    OrchestratorEvent::ChatReset { execution_id } => {
+                        Some(Arc::new(ServerMessage::ChatReset {
+                            execution_id: *execution_id,
+                        }))
+                    }
                     OrchestratorEvent::ToolStarted {
                         execution_id,
                         tool_name,
@@ -45,6 +45,8 @@ pub enum ServerMessage {
         is_final: bool,
         persona: String,
     },
+    /// Discard the streamed response text so far (a fallback provider took over)
+    ChatReset { execution_id: Uuid },
     /// Status update
     Status {
         connected: bool,
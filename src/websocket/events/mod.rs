@@ -267,6 +267,15 @@ fn convert_event(
                 timestamp: now,
             })
         }
+        OrchestratorEvent::ChatReset { execution_id } => {
+            if !state.matches("chat_delta", *execution_id) {
+                return None;
+            }
+            Some(EventNotification::ChatReset {
+                execution_id: *execution_id,
+                timestamp: now,
+            })
+        }
         OrchestratorEvent::ApprovalRequired {
             execution_id,
             request_id,
@@ -282,5 +291,6 @@ fn convert_event(
         }
         OrchestratorEvent::A2aMessageSent { .. } => None,
         OrchestratorEvent::QuotaWarning { .. } => None,
+        OrchestratorEvent::BudgetWarning { .. } => None,
     }
 }
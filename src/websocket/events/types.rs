@@ -77,6 +77,11 @@ pub enum EventNotification {
         is_final: bool,
         timestamp: DateTime<Utc>,
     },
+    /// Streamed text so far is void (a fallback provider took over)
+    ChatReset {
+        execution_id: Uuid,
+        timestamp: DateTime<Utc>,
+    },
     /// Approval required
     ApprovalRequired {
         execution_id: Uuid,
@@ -272,7 +272,7 @@ async fn wait_for_connect(
         }
     };
 
-    let auth = match auth_store.validate_token(&connect_params.token) {
+    let auth = match auth_store.authenticate(&connect_params.token).await {
         Ok(auth) => auth,
         Err(_) => {
             let frame = GatewayFrame::err(
@@ -36,6 +36,7 @@ pub(crate) async fn dispatch_method(
         m if m.starts_with("approval.") => handlers::approval::handle(id, m, params, ctx).await,
         m if m.starts_with("node.") => handlers::node::handle(id, m, params, ctx).await,
         m if m.starts_with("a2a.") => handlers::a2a::handle(id, m, params, ctx).await,
+        m if m.starts_with("mcp.") => handlers::mcp::handle(id, m, params, ctx).await,
         m if m.starts_with("browser.") => {
             handlers::browser::handle(id, m, params, ctx.browser_relay).await
         }
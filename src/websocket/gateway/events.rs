@@ -41,6 +41,12 @@ pub fn convert_event(event: &OrchestratorEvent) -> Option<GatewayFrame> {
                 "is_final": is_final,
             }),
         ),
+        OrchestratorEvent::ChatReset { execution_id } => (
+            "chat.reset",
+            serde_json::json!({
+                "execution_id": execution_id,
+            }),
+        ),
         OrchestratorEvent::ToolStarted {
             execution_id,
             tool_name,
@@ -123,6 +129,24 @@ pub fn convert_event(event: &OrchestratorEvent) -> Option<GatewayFrame> {
                 "reset_in_secs": reset_in_secs,
             }),
         ),
+        OrchestratorEvent::BudgetWarning {
+            scope,
+            name,
+            period,
+            spent_usd,
+            limit_usd,
+            exceeded,
+        } => (
+            "budget.warning",
+            serde_json::json!({
+                "scope": scope,
+                "name": name,
+                "period": period,
+                "spent_usd": spent_usd,
+                "limit_usd": limit_usd,
+                "exceeded": exceeded,
+            }),
+        ),
     };
 
     Some(GatewayFrame::event(name, data))
@@ -150,6 +174,7 @@ mod tests {
                 delta: "hi".to_string(),
                 is_final: false,
             },
+            OrchestratorEvent::ChatReset { execution_id: id },
             OrchestratorEvent::ToolStarted {
                 execution_id: id,
                 tool_name: "exec".to_string(),
@@ -178,6 +203,14 @@ mod tests {
                 to_agent: "frontend".to_string(),
                 message_id: Uuid::new_v4(),
             },
+            OrchestratorEvent::BudgetWarning {
+                scope: "user".to_string(),
+                name: "alice".to_string(),
+                period: "daily".to_string(),
+                spent_usd: 4.2,
+                limit_usd: 5.0,
+                exceeded: false,
+            },
         ];
 
         for event in events {
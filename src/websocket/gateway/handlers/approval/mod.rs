@@ -117,7 +117,7 @@ async fn list_pending(id: &str, ctx: &DispatchContext<'_>) -> GatewayFrame {
         }
     };
 
-    let pending = manager.pending_for_user(&ctx.auth.user_id).await;
+    let pending = manager.pending_for_approver(&ctx.auth.user_id).await;
     let summaries: Vec<serde_json::Value> = pending
         .iter()
         .map(|r| {
@@ -1,5 +1,5 @@
 use cratos_core::auth::Scope;
-use cratos_core::OrchestratorInput;
+use cratos_core::{McpResourceRef, OrchestratorInput};
 use uuid::Uuid;
 
 use super::super::dispatch::DispatchContext;
@@ -45,7 +45,21 @@ async fn send(id: &str, params: serde_json::Value, ctx: &DispatchContext<'_>) ->
         .unwrap_or("default");
 
     // Build orchestrator input
-    let input = OrchestratorInput::new("websocket", session_id, &ctx.auth.user_id, text);
+    let mut input = OrchestratorInput::new("websocket", session_id, &ctx.auth.user_id, text);
+    if let Some(resources) = params.get("resources") {
+        match serde_json::from_value::<Vec<McpResourceRef>>(resources.clone()) {
+            Ok(resources) => input.mcp_resources = resources,
+            Err(e) => {
+                return GatewayFrame::err(
+                    id,
+                    GatewayError::new(
+                        GatewayErrorCode::InvalidParams,
+                        format!("Invalid 'resources' parameter: {}", e),
+                    ),
+                )
+            }
+        }
+    }
 
     let execution_id = Uuid::new_v4();
 
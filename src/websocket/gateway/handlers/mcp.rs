@@ -0,0 +1,61 @@
+use cratos_core::auth::Scope;
+
+use super::super::dispatch::DispatchContext;
+use crate::websocket::protocol::{GatewayError, GatewayErrorCode, GatewayFrame};
+
+pub(crate) async fn handle(
+    id: &str,
+    method: &str,
+    _params: serde_json::Value,
+    ctx: &DispatchContext<'_>,
+) -> GatewayFrame {
+    if !ctx.auth.has_scope(&Scope::ExecutionWrite) {
+        return GatewayFrame::err(
+            id,
+            GatewayError::new(GatewayErrorCode::Forbidden, "Requires ExecutionWrite scope"),
+        );
+    }
+    match method {
+        "mcp.prompts" => prompts(id, ctx).await,
+        "mcp.resources" => resources(id, ctx).await,
+        _ => GatewayFrame::err(
+            id,
+            GatewayError::new(
+                GatewayErrorCode::UnknownMethod,
+                format!("Unknown method: {}", method),
+            ),
+        ),
+    }
+}
+
+/// MCP prompt templates, usable as `/<command> args` in `chat.send`
+async fn prompts(id: &str, ctx: &DispatchContext<'_>) -> GatewayFrame {
+    let prompts: Vec<serde_json::Value> = ctx
+        .orchestrator
+        .mcp_prompt_commands()
+        .await
+        .into_iter()
+        .map(|p| {
+            serde_json::json!({
+                "command": format!("/{}", p.command),
+                "description": p.description,
+                "arguments": p.arguments,
+            })
+        })
+        .collect();
+    GatewayFrame::ok(id, serde_json::json!({ "prompts": prompts }))
+}
+
+/// MCP resources, attachable via `chat.send` `resources`
+async fn resources(id: &str, ctx: &DispatchContext<'_>) -> GatewayFrame {
+    let resources: Vec<serde_json::Value> = ctx
+        .orchestrator
+        .mcp_resources()
+        .await
+        .into_iter()
+        .map(
+            |(server, uri, name)| serde_json::json!({ "server": server, "uri": uri, "name": name }),
+        )
+        .collect();
+    GatewayFrame::ok(id, serde_json::json!({ "resources": resources }))
+}
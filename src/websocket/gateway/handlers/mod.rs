@@ -2,5 +2,6 @@ pub(crate) mod a2a;
 pub(crate) mod approval;
 pub(crate) mod browser;
 pub(crate) mod chat;
+pub(crate) mod mcp;
 pub(crate) mod node;
 pub(crate) mod session;
@@ -109,6 +109,7 @@ fn test_tool_registry_with_builtins() {
     let expected_tools = [
         "file_read",
         "file_write",
+        "file_edit",
         "file_list",
         "http_get",
         "http_post",